@@ -0,0 +1,119 @@
+//! Benchmarks for generator hot paths
+//!
+//! Run with `cargo bench`, or a single group with e.g.
+//! `cargo bench --bench generator -- escape_xml`. Compare a change against a
+//! baseline with `--save-baseline main` / `--baseline main`.
+
+use std::hint::black_box;
+use std::io::Cursor;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use ppt_rs::cli::parse_markdown;
+use ppt_rs::generator::slide_xml::create_slide_xml_with_content;
+use ppt_rs::generator::tables_xml::generate_table_xml;
+use ppt_rs::generator::{Shape, ShapeFill, ShapeType, SlideContent, TableBuilder, TableCell, TableRow};
+use ppt_rs::{create_pptx_with_content, escape_xml};
+
+/// A bullet-heavy slide with a few shapes and speaker notes
+fn sample_slide(i: usize) -> SlideContent {
+    let mut slide = SlideContent::new(&format!("Quarterly review <part {i}> & outlook"))
+        .notes("Mention the \"Q3\" numbers & thank the team");
+    for b in 0..8 {
+        slide = slide.add_bullet(&format!("Point {b}: revenue grew 12% & churn < 3%"));
+    }
+    for s in 0..4 {
+        slide = slide.add_shape(
+            Shape::new(ShapeType::Rectangle, 500_000 + s * 1_500_000, 4_500_000, 1_200_000, 800_000)
+                .with_fill(ShapeFill::new("4472C4"))
+                .with_text(&format!("Box {s}")),
+        );
+    }
+    slide
+}
+
+fn sample_table(rows: usize, cols: usize) -> ppt_rs::generator::Table {
+    let mut builder = TableBuilder::new(vec![1_500_000; cols]);
+    for r in 0..rows {
+        let cells = (0..cols).map(|c| TableCell::new(&format!("R{r}C{c} <&>"))).collect();
+        builder = builder.add_row(TableRow::new(cells));
+    }
+    builder.build()
+}
+
+fn large_markdown(slides: usize) -> String {
+    let mut md = String::new();
+    for i in 0..slides {
+        md.push_str(&format!("# Slide {i}\n\n"));
+        for b in 0..6 {
+            md.push_str(&format!("- Bullet {b} with **bold** and `code`\n"));
+        }
+        md.push_str("\n| Name | Value |\n|------|-------|\n| a | 1 |\n| b | 2 |\n\n");
+        md.push_str("```rust\nfn main() {\n    println!(\"hello\");\n}\n```\n\n");
+    }
+    md
+}
+
+fn bench_escape_xml(c: &mut Criterion) {
+    let plain = "The quick brown fox jumps over the lazy dog. ".repeat(100);
+    let markup = "<a href=\"x\">Tom & Jerry's</a> ".repeat(100);
+
+    let mut group = c.benchmark_group("escape_xml");
+    group.throughput(Throughput::Bytes(plain.len() as u64));
+    group.bench_function("plain", |b| b.iter(|| escape_xml(black_box(&plain))));
+    group.throughput(Throughput::Bytes(markup.len() as u64));
+    group.bench_function("markup", |b| b.iter(|| escape_xml(black_box(&markup))));
+    group.finish();
+}
+
+fn bench_slide_xml(c: &mut Criterion) {
+    let slide = sample_slide(1);
+    c.bench_function("slide_xml", |b| {
+        b.iter(|| create_slide_xml_with_content(1, black_box(&slide), &[]))
+    });
+}
+
+fn bench_table_xml(c: &mut Criterion) {
+    let mut group = c.benchmark_group("table_xml");
+    for (rows, cols) in [(5, 4), (50, 8)] {
+        let table = sample_table(rows, cols);
+        group.bench_function(format!("{rows}x{cols}"), |b| {
+            b.iter(|| generate_table_xml(black_box(&table), 10))
+        });
+    }
+    group.finish();
+}
+
+fn bench_packaging(c: &mut Criterion) {
+    let mut group = c.benchmark_group("packaging");
+    group.sample_size(20);
+    for count in [10, 100] {
+        let slides: Vec<_> = (0..count).map(sample_slide).collect();
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_function(format!("{count}_slides"), |b| {
+            b.iter_batched(
+                || slides.clone(),
+                |slides| create_pptx_with_content("Bench", slides).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_parsing(c: &mut Criterion) {
+    let markdown = large_markdown(200);
+    let deck = create_pptx_with_content("Bench", (0..200).map(sample_slide).collect()).unwrap();
+
+    let mut group = c.benchmark_group("parsing");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(markdown.len() as u64));
+    group.bench_function("markdown_200_slides", |b| b.iter(|| parse_markdown(black_box(&markdown)).unwrap()));
+    group.throughput(Throughput::Bytes(deck.len() as u64));
+    group.bench_function("open_pptx_200_slides", |b| {
+        b.iter(|| ppt_rs::api::open_reader(Cursor::new(black_box(&deck))).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_escape_xml, bench_slide_xml, bench_table_xml, bench_packaging, bench_parsing);
+criterion_main!(benches);
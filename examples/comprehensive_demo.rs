@@ -18,7 +18,7 @@
 //! 15. NEW v0.2.1: Material & Carbon Design colors
 
 use ppt_rs::generator::{
-    create_pptx_with_content, SlideContent, SlideLayout,
+    create_pptx_with_content, SlideContent, SlideLayout, Region,
     TableRow, TableCell, TableBuilder,
     ChartType, ChartSeries, ChartBuilder,
     Shape, ShapeType, ShapeFill, ShapeLine,
@@ -28,7 +28,7 @@ use ppt_rs::generator::{
 };
 use ppt_rs::generator::shapes::{GradientFill, GradientDirection};
 use ppt_rs::prelude::{colors, themes, font_sizes};
-use ppt_rs::opc::Package;
+use ppt_rs::Package;
 use ppt_rs::parts::{
     SlideLayoutPart, LayoutType,
     SlideMasterPart,
@@ -195,10 +195,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("📈 Slide 8: Chart Types");
     
     // Create chart data structures (for demonstration)
-    let _bar_chart = ChartBuilder::new("Sales by Region", ChartType::Bar)
+    let bar_chart = ChartBuilder::new("Sales by Region", ChartType::Bar)
         .categories(vec!["North", "South", "East", "West"])
         .add_series(ChartSeries::new("2023", vec![100.0, 80.0, 120.0, 90.0]))
         .add_series(ChartSeries::new("2024", vec![120.0, 95.0, 140.0, 110.0]))
+        .region(Region::LeftHalf)
         .build();
     
     let _line_chart = ChartBuilder::new("Monthly Trend", ChartType::Line)
@@ -206,19 +207,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .add_series(ChartSeries::new("Revenue", vec![10.0, 12.0, 15.0, 14.0, 18.0, 22.0]))
         .build();
     
-    let _pie_chart = ChartBuilder::new("Market Share", ChartType::Pie)
+    let pie_chart = ChartBuilder::new("Market Share", ChartType::Pie)
         .categories(vec!["Product A", "Product B", "Product C", "Others"])
         .add_series(ChartSeries::new("Share", vec![40.0, 30.0, 20.0, 10.0]))
+        .region(Region::RightHalf)
         .build();
     
     slides.push(
-        SlideContent::new("Chart Types: Bar, Line, Pie")
-            .with_chart()
+        SlideContent::new("Chart Types: Bar and Pie")
+            .layout(SlideLayout::TitleOnly)
             .title_color("1F497D")
-            .add_bullet("Bar Chart: Compare categories")
-            .add_bullet("Line Chart: Show trends over time")
-            .add_bullet("Pie Chart: Show proportions")
-            .content_size(24)
+            .add_chart(bar_chart)
+            .add_chart(pie_chart)
     );
 
     // =========================================================================
@@ -435,16 +435,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Build advanced table using generator's TableBuilder with alignment
     let advanced_table = TableBuilder::new(vec![2000000, 2000000, 2000000, 2000000])
         .add_row(TableRow::new(vec![
-            TableCell::new("Q1 2024 Financial Report").bold().background_color("1F4E79").text_color("FFFFFF").align_center().font_size(14),
+            TableCell::new("Q1 2024 Financial Report").bold().background_color("1F4E79").align_center().font_size(14),
             TableCell::new("").background_color("1F4E79"),
             TableCell::new("").background_color("1F4E79"),
             TableCell::new("").background_color("1F4E79"),
         ]))
         .add_row(TableRow::new(vec![
-            TableCell::new("Category").bold().background_color("2E75B6").text_color("FFFFFF").align_center(),
-            TableCell::new("Revenue").bold().background_color("2E75B6").text_color("FFFFFF").align_center(),
-            TableCell::new("Expenses").bold().background_color("2E75B6").text_color("FFFFFF").align_center(),
-            TableCell::new("Profit").bold().background_color("2E75B6").text_color("FFFFFF").align_center(),
+            TableCell::new("Category").bold().background_color("2E75B6").align_center(),
+            TableCell::new("Revenue").bold().background_color("2E75B6").align_center(),
+            TableCell::new("Expenses").bold().background_color("2E75B6").align_center(),
+            TableCell::new("Profit").bold().background_color("2E75B6").align_center(),
         ]))
         .add_row(TableRow::new(vec![
             TableCell::new("Product Sales").text_color("000000").align_left(),
@@ -459,9 +459,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             TableCell::new("$570,000").bold().text_color("2E7D32").align_right(),
         ]))
         .add_row(TableRow::new(vec![
-            TableCell::new("Total").bold().background_color("E7E6E6").text_color("000000").align_left(),
-            TableCell::new("$2,140,000").bold().background_color("E7E6E6").text_color("000000").align_right(),
-            TableCell::new("$770,000").bold().background_color("E7E6E6").text_color("000000").align_right(),
+            TableCell::new("Total").bold().background_color("E7E6E6").align_left(),
+            TableCell::new("$2,140,000").bold().background_color("E7E6E6").align_right(),
+            TableCell::new("$770,000").bold().background_color("E7E6E6").align_right(),
             TableCell::new("$1,370,000").bold().background_color("C6EFCE").text_color("006100").align_right(),
         ]))
         .position(300000, 1600000)
@@ -481,10 +481,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let comparison_table = TableBuilder::new(vec![2000000, 1500000, 1500000, 1500000])
         .add_row(TableRow::new(vec![
-            TableCell::new("Feature").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Basic").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Pro").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Enterprise").bold().background_color("4472C4").text_color("FFFFFF"),
+            TableCell::new("Feature").bold().background_color("4472C4"),
+            TableCell::new("Basic").bold().background_color("4472C4"),
+            TableCell::new("Pro").bold().background_color("4472C4"),
+            TableCell::new("Enterprise").bold().background_color("4472C4"),
         ]))
         .add_row(TableRow::new(vec![
             TableCell::new("Storage").text_color("000000"),
@@ -511,10 +511,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             TableCell::new("Yes + Priority").bold().text_color("2E7D32"),
         ]))
         .add_row(TableRow::new(vec![
-            TableCell::new("Price/month").bold().background_color("F2F2F2").text_color("000000"),
-            TableCell::new("$9").bold().background_color("F2F2F2").text_color("000000"),
-            TableCell::new("$29").bold().background_color("F2F2F2").text_color("000000"),
-            TableCell::new("$99").bold().background_color("F2F2F2").text_color("000000"),
+            TableCell::new("Price/month").bold().background_color("F2F2F2"),
+            TableCell::new("$9").bold().background_color("F2F2F2"),
+            TableCell::new("$29").bold().background_color("F2F2F2"),
+            TableCell::new("$99").bold().background_color("F2F2F2"),
         ]))
         .position(500000, 1600000)
         .build();
@@ -740,11 +740,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     let timeline_table = TableBuilder::new(vec![1500000, 1500000, 1500000, 1500000, 1500000])
         .add_row(TableRow::new(vec![
-            TableCell::new("Q1 2024").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Q2 2024").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Q3 2024").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Q4 2024").bold().background_color("4472C4").text_color("FFFFFF"),
-            TableCell::new("Q1 2025").bold().background_color("4472C4").text_color("FFFFFF"),
+            TableCell::new("Q1 2024").bold().background_color("4472C4"),
+            TableCell::new("Q2 2024").bold().background_color("4472C4"),
+            TableCell::new("Q3 2024").bold().background_color("4472C4"),
+            TableCell::new("Q4 2024").bold().background_color("4472C4"),
+            TableCell::new("Q1 2025").bold().background_color("4472C4"),
         ]))
         .add_row(TableRow::new(vec![
             TableCell::new("Research\n& Planning").background_color("BDD7EE").text_color("1F497D"),
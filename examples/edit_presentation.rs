@@ -8,7 +8,7 @@
 //! - Save modified presentations
 
 use ppt_rs::generator::{create_pptx_with_content, SlideContent, SlideLayout};
-use ppt_rs::oxml::{PresentationEditor, PresentationReader};
+use ppt_rs::{PresentationEditor, PresentationReader};
 use std::fs;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -1,6 +1,6 @@
 //! Example demonstrating reading and inspecting PPTX files
 
-use ppt_rs::opc::package::Package;
+use ppt_rs::Package;
 use std::fs;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -7,7 +7,7 @@
 //! - Extract all text from a presentation
 
 use ppt_rs::generator::{create_pptx_with_content, SlideContent, SlideLayout};
-use ppt_rs::oxml::{PresentationReader, SlideParser};
+use ppt_rs::{PresentationReader, SlideParser};
 use std::fs;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -6,7 +6,7 @@
 //! 3. Repair the issues
 //! 4. Save the repaired file
 
-use ppt_rs::oxml::repair::{PptxRepair, RepairIssue};
+use ppt_rs::{PptxRepair, RepairIssue};
 use std::fs;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
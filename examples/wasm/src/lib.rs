@@ -0,0 +1,35 @@
+//! Browser-side deck generation
+//!
+//! Builds a presentation entirely in WASM and hands it to JavaScript as a
+//! `Blob`, ready for a download link or an upload. See `index.html`.
+
+use ppt_rs::generator::{SlideContent, SlideLayout};
+use ppt_rs::Presentation;
+use wasm_bindgen::prelude::*;
+
+const PPTX_MIME: &str = "application/vnd.openxmlformats-officedocument.presentationml.presentation";
+
+/// Generate a deck with a title slide plus one bullet slide per outline entry
+///
+/// `outline` holds one slide per line, written as `Title: bullet; bullet`.
+#[wasm_bindgen]
+pub fn generate_deck(title: &str, outline: &str) -> Result<web_sys::Blob, JsValue> {
+    let mut pres = Presentation::with_title(title)
+        .add_slide(SlideContent::new(title).layout(SlideLayout::CenteredTitle));
+
+    for line in outline.lines().filter(|l| !l.trim().is_empty()) {
+        let (heading, bullets) = line.split_once(':').unwrap_or((line, ""));
+        let mut slide = SlideContent::new(heading.trim());
+        for bullet in bullets.split(';').map(str::trim).filter(|b| !b.is_empty()) {
+            slide = slide.add_bullet(bullet);
+        }
+        pres = pres.add_slide(slide);
+    }
+
+    let bytes = pres.build().map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(bytes.as_slice()));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(PPTX_MIME);
+    web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)
+}
@@ -0,0 +1,48 @@
+/* Build a two-slide deck through the C API.
+ *
+ *   cargo build --release
+ *   cc examples/hello.c -Iinclude -Ltarget/release -lppt_rs_ffi -o hello
+ *   LD_LIBRARY_PATH=target/release ./hello hello.pptx
+ */
+#include <stdio.h>
+
+#include "ppt_rs.h"
+
+static const char *SLIDES[] = {
+    "{\"title\": \"Overview\", \"bullets\": [\"Revenue up 25%\", {\"text\": \"EMEA leads\", \"level\": 1}]}",
+    "{\"title\": \"Sales\", \"layout\": \"titleOnly\", \"visualizations\": [{\"type\": \"bar\","
+    " \"data\": [{\"q\": \"Q1\", \"v\": 10}, {\"q\": \"Q2\", \"v\": 14}], \"encoding\": {\"x\": \"q\", \"y\": \"v\"}}]}",
+};
+
+int main(int argc, char **argv) {
+    const char *path = argc > 1 ? argv[1] : "hello.pptx";
+    PptBuilder *deck = ppt_builder_new("Quarterly review");
+    for (size_t i = 0; i < sizeof SLIDES / sizeof *SLIDES; i++) {
+        if (ppt_builder_add_slide_json(deck, SLIDES[i]) != PPT_STATUS_OK) {
+            fprintf(stderr, "%s\n", ppt_last_error());
+            ppt_builder_free(deck);
+            return 1;
+        }
+    }
+
+    uint8_t *data;
+    size_t len;
+    int status = ppt_builder_finish(deck, &data, &len);
+    ppt_builder_free(deck);
+    if (status != PPT_STATUS_OK) {
+        fprintf(stderr, "%s\n", ppt_last_error());
+        return 1;
+    }
+
+    FILE *file = fopen(path, "wb");
+    if (!file) {
+        perror(path);
+        ppt_buffer_free(data, len);
+        return 1;
+    }
+    fwrite(data, 1, len, file);
+    fclose(file);
+    ppt_buffer_free(data, len);
+    printf("wrote %zu bytes to %s\n", len, path);
+    return 0;
+}
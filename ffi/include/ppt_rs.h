@@ -0,0 +1,103 @@
+/* Generated by cbindgen from src/lib.rs; do not edit. */
+
+#ifndef PPT_RS_H
+#define PPT_RS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of this C API; bumped on any incompatible change
+#define PPT_ABI_VERSION 1
+
+// Result of a call
+typedef enum PptStatus {
+  PPT_STATUS_OK = 0,
+  // A required pointer argument was null
+  PPT_STATUS_NULL_ARGUMENT = 1,
+  // A string argument was not valid UTF-8
+  PPT_STATUS_INVALID_UTF8 = 2,
+  // Slide JSON could not be parsed or described an invalid slide
+  PPT_STATUS_INVALID_SLIDE = 3,
+  // Generating the package failed
+  PPT_STATUS_BUILD_FAILED = 4,
+  // The library panicked; the builder should be freed
+  PPT_STATUS_PANIC = 5,
+} PptStatus;
+
+// Deck being assembled; create with [`ppt_builder_new`]
+typedef struct PptBuilder PptBuilder;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Version of the C API this library implements ([`PPT_ABI_VERSION`])
+uint32_t ppt_abi_version(void);
+
+// New empty deck with `title` as its document title
+//
+// Returns null if `title` is null or not UTF-8. Free with [`ppt_builder_free`].
+//
+// # Safety
+//
+// `title` must be null or point to a NUL-terminated string.
+struct PptBuilder *ppt_builder_new(const char *title);
+
+// Append a slide described by JSON (see `SlideSpec` in the Rust docs)
+//
+// # Safety
+//
+// `builder` must come from [`ppt_builder_new`] and not be freed; `json`
+// must be null or point to a NUL-terminated string.
+enum PptStatus ppt_builder_add_slide_json(struct PptBuilder *builder, const char *json);
+
+// Number of slides added so far (0 for a null builder)
+//
+// # Safety
+//
+// `builder` must be null or come from [`ppt_builder_new`] and not be freed.
+size_t ppt_builder_slide_count(const struct PptBuilder *builder);
+
+// Generate the `.pptx` into a new buffer
+//
+// On success `*out_data` and `*out_len` hold the package; release it with
+// [`ppt_buffer_free`]. The builder is left unchanged and must still be
+// freed with [`ppt_builder_free`].
+//
+// # Safety
+//
+// `builder` must come from [`ppt_builder_new`] and not be freed;
+// `out_data` and `out_len` must be valid for writes.
+enum PptStatus ppt_builder_finish(const struct PptBuilder *builder,
+                                  uint8_t **out_data,
+                                  size_t *out_len);
+
+// Free a buffer returned by [`ppt_builder_finish`]; null is ignored
+//
+// # Safety
+//
+// `data` and `len` must be exactly as returned by [`ppt_builder_finish`],
+// and the buffer must not be used afterwards.
+void ppt_buffer_free(uint8_t *data, size_t len);
+
+// Free a builder; null is ignored
+//
+// # Safety
+//
+// `builder` must be null or come from [`ppt_builder_new`], and must not be
+// used afterwards.
+void ppt_builder_free(struct PptBuilder *builder);
+
+// Message for the last failed call on this thread, or null
+//
+// The string stays valid until the next failing call on the same thread.
+const char *ppt_last_error(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PPT_RS_H */
@@ -0,0 +1,255 @@
+//! C API for the generator
+//!
+//! A small `extern "C"` surface so Go, Java, .NET and other services can
+//! generate decks through FFI. Slides are passed as JSON
+//! ([`SlideSpec`](ppt_rs::generator::SlideSpec)), so the ABI stays a handful
+//! of functions however the slide format grows:
+//!
+//! ```c
+//! PptBuilder *deck = ppt_builder_new("Quarterly review");
+//! ppt_builder_add_slide_json(deck, "{\"title\": \"Overview\", \"bullets\": [\"Revenue up\"]}");
+//! uint8_t *data; size_t len;
+//! if (ppt_builder_finish(deck, &data, &len) == PPT_STATUS_OK) {
+//!     fwrite(data, 1, len, file);
+//!     ppt_buffer_free(data, len);
+//! } else {
+//!     fprintf(stderr, "%s\n", ppt_last_error());
+//! }
+//! ppt_builder_free(deck);
+//! ```
+//!
+//! Every fallible call returns a [`PptStatus`]; the message for the last
+//! failure on the calling thread is available from [`ppt_last_error`].
+//! Panics are caught and reported as [`PptStatus::Panic`] instead of
+//! unwinding into the caller. The header `include/ppt_rs.h` is generated by
+//! the build script.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use ppt_rs::generator::{SlideContent, SlideSpec, create_pptx_with_content};
+
+/// Version of this C API; bumped on any incompatible change
+pub const PPT_ABI_VERSION: u32 = 1;
+
+/// Result of a call
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PptStatus {
+    Ok = 0,
+    /// A required pointer argument was null
+    NullArgument = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// Slide JSON could not be parsed or described an invalid slide
+    InvalidSlide = 3,
+    /// Generating the package failed
+    BuildFailed = 4,
+    /// The library panicked; the builder should be freed
+    Panic = 5,
+}
+
+/// Deck being assembled; create with [`ppt_builder_new`]
+pub struct PptBuilder {
+    title: String,
+    slides: Vec<SlideContent>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run `f`, turning its error or panic into a status and the last error message
+fn guard(f: impl FnOnce() -> Result<(), (PptStatus, String)>) -> PptStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => PptStatus::Ok,
+        Ok(Err((status, message))) => {
+            set_last_error(message);
+            status
+        }
+        Err(_) => {
+            set_last_error("internal error (panic)".to_string());
+            PptStatus::Panic
+        }
+    }
+}
+
+/// Borrow a C string as UTF-8
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<&'a str, (PptStatus, String)> {
+    if s.is_null() {
+        return Err((PptStatus::NullArgument, format!("{name} is null")));
+    }
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map_err(|e| (PptStatus::InvalidUtf8, format!("{name} is not UTF-8: {e}")))
+}
+
+/// Version of the C API this library implements ([`PPT_ABI_VERSION`])
+#[unsafe(no_mangle)]
+pub extern "C" fn ppt_abi_version() -> u32 {
+    PPT_ABI_VERSION
+}
+
+/// New empty deck with `title` as its document title
+///
+/// Returns null if `title` is null or not UTF-8. Free with [`ppt_builder_free`].
+///
+/// # Safety
+///
+/// `title` must be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_builder_new(title: *const c_char) -> *mut PptBuilder {
+    let mut builder = None;
+    guard(|| {
+        let title = unsafe { str_arg(title, "title") }?;
+        builder = Some(Box::new(PptBuilder { title: title.to_string(), slides: Vec::new() }));
+        Ok(())
+    });
+    builder.map_or(std::ptr::null_mut(), Box::into_raw)
+}
+
+/// Append a slide described by JSON (see `SlideSpec` in the Rust docs)
+///
+/// # Safety
+///
+/// `builder` must come from [`ppt_builder_new`] and not be freed; `json`
+/// must be null or point to a NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_builder_add_slide_json(builder: *mut PptBuilder, json: *const c_char) -> PptStatus {
+    guard(|| {
+        let builder = unsafe { builder.as_mut() }.ok_or((PptStatus::NullArgument, "builder is null".to_string()))?;
+        let json = unsafe { str_arg(json, "json") }?;
+        let slide = SlideSpec::from_json(json)
+            .and_then(|spec| spec.build())
+            .map_err(|e| (PptStatus::InvalidSlide, format!("slide {}: {e}", builder.slides.len() + 1)))?;
+        builder.slides.push(slide);
+        Ok(())
+    })
+}
+
+/// Number of slides added so far (0 for a null builder)
+///
+/// # Safety
+///
+/// `builder` must be null or come from [`ppt_builder_new`] and not be freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_builder_slide_count(builder: *const PptBuilder) -> usize {
+    unsafe { builder.as_ref() }.map_or(0, |builder| builder.slides.len())
+}
+
+/// Generate the `.pptx` into a new buffer
+///
+/// On success `*out_data` and `*out_len` hold the package; release it with
+/// [`ppt_buffer_free`]. The builder is left unchanged and must still be
+/// freed with [`ppt_builder_free`].
+///
+/// # Safety
+///
+/// `builder` must come from [`ppt_builder_new`] and not be freed;
+/// `out_data` and `out_len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_builder_finish(
+    builder: *const PptBuilder,
+    out_data: *mut *mut u8,
+    out_len: *mut usize,
+) -> PptStatus {
+    guard(|| {
+        let builder = unsafe { builder.as_ref() }.ok_or((PptStatus::NullArgument, "builder is null".to_string()))?;
+        if out_data.is_null() || out_len.is_null() {
+            return Err((PptStatus::NullArgument, "output pointer is null".to_string()));
+        }
+        let data = create_pptx_with_content(&builder.title, builder.slides.clone())
+            .map_err(|e| (PptStatus::BuildFailed, e.to_string()))?;
+        let data = Box::into_raw(data.into_boxed_slice());
+        unsafe {
+            *out_len = data.len();
+            *out_data = data.cast();
+        }
+        Ok(())
+    })
+}
+
+/// Free a buffer returned by [`ppt_builder_finish`]; null is ignored
+///
+/// # Safety
+///
+/// `data` and `len` must be exactly as returned by [`ppt_builder_finish`],
+/// and the buffer must not be used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_buffer_free(data: *mut u8, len: usize) {
+    if !data.is_null() {
+        drop(unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(data, len)) });
+    }
+}
+
+/// Free a builder; null is ignored
+///
+/// # Safety
+///
+/// `builder` must be null or come from [`ppt_builder_new`], and must not be
+/// used afterwards.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ppt_builder_free(builder: *mut PptBuilder) {
+    if !builder.is_null() {
+        drop(unsafe { Box::from_raw(builder) });
+    }
+}
+
+/// Message for the last failed call on this thread, or null
+///
+/// The string stays valid until the next failing call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn ppt_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(std::ptr::null(), |message| message.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_deck() {
+        unsafe {
+            let deck = ppt_builder_new(c"Review".as_ptr());
+            let slide = cr#"{"title": "Overview", "bullets": ["Revenue up", {"text": "EMEA", "level": 1}]}"#;
+            assert_eq!(ppt_builder_add_slide_json(deck, slide.as_ptr()), PptStatus::Ok);
+            assert_eq!(ppt_builder_slide_count(deck), 1);
+
+            let (mut data, mut len) = (std::ptr::null_mut(), 0);
+            assert_eq!(ppt_builder_finish(deck, &mut data, &mut len), PptStatus::Ok);
+            assert_eq!(&std::slice::from_raw_parts(data, len)[..2], b"PK");
+            ppt_buffer_free(data, len);
+            ppt_builder_free(deck);
+        }
+    }
+
+    #[test]
+    fn test_errors_set_status_and_message() {
+        unsafe {
+            assert!(ppt_builder_new(std::ptr::null()).is_null());
+            assert_eq!(CStr::from_ptr(ppt_last_error()).to_str().unwrap(), "title is null");
+
+            let deck = ppt_builder_new(c"Deck".as_ptr());
+            let status = ppt_builder_add_slide_json(deck, cr#"{"layout": "sideways"}"#.as_ptr());
+            assert_eq!(status, PptStatus::InvalidSlide);
+            let message = CStr::from_ptr(ppt_last_error()).to_str().unwrap();
+            assert!(message.starts_with("slide 1: ") && message.contains("sideways"), "{message}");
+            assert_eq!(ppt_builder_slide_count(deck), 0);
+
+            let invalid = [0xFFu8, 0];
+            assert_eq!(ppt_builder_add_slide_json(deck, invalid.as_ptr().cast()), PptStatus::InvalidUtf8);
+            assert_eq!(ppt_builder_add_slide_json(std::ptr::null_mut(), c"{}".as_ptr()), PptStatus::NullArgument);
+            ppt_builder_free(deck);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+import assert from 'node:assert/strict'
+import { createRequire } from 'node:module'
+import test from 'node:test'
+
+const { fromMarkdown, fromSlides } = createRequire(import.meta.url)('../index.js')
+
+const isZip = (buffer) => Buffer.isBuffer(buffer) && buffer.subarray(0, 2).toString() === 'PK'
+
+test('fromMarkdown returns a pptx buffer', () => {
+  assert.ok(isZip(fromMarkdown('# Hello\n- from Node\n\n# Bye', { title: 'Notes' })))
+  assert.ok(isZip(fromMarkdown('# Untitled')))
+})
+
+test('fromSlides takes JSON slide specs', () => {
+  const chart = { type: 'bar', data: [{ q: 'Q1', v: 10 }], encoding: { x: 'q', y: 'v' } }
+  const deck = fromSlides([
+    { title: 'Overview', bullets: ['Revenue up', { text: 'EMEA', level: 1 }], notes: 'Headline first' },
+    { title: 'Sales', layout: 'titleOnly', visualizations: [chart] },
+  ])
+  assert.ok(deck.includes('ppt/charts/chart1.xml'))
+})
+
+test('invalid slides throw with the slide number', () => {
+  assert.throws(() => fromSlides([{ title: 'ok' }, { layout: 'sideways' }]), /slide 2: .*sideways/)
+  assert.throws(() => fromSlides([{ bulets: [] }]), /slide 1: .*bulets/)
+})
@@ -0,0 +1,27 @@
+/* Typings for the functions exported by src/lib.rs */
+
+/** Options shared by every generator */
+export interface DeckOptions {
+  /** Document title; defaults to "Presentation from Markdown" or "Presentation" */
+  title?: string
+}
+
+/** Bullet text, or text with an indent level (0 or 1) */
+export type BulletSpec = string | { text: string; level?: number }
+
+/** JSON slide spec, as taken by the C API */
+export interface SlideSpec {
+  title?: string
+  /** Layout name: "titleAndContent", "twoColumn", "blank", ... */
+  layout?: string
+  bullets?: BulletSpec[]
+  notes?: string
+  /** Chart or table specs: { type, title, data, encoding, colors } */
+  visualizations?: object[]
+}
+
+/** `.pptx` for a Markdown document (`#` headings start slides; Mermaid diagrams supported) */
+export function fromMarkdown(markdown: string, options?: DeckOptions): Buffer
+
+/** `.pptx` for a list of JSON slide specs */
+export function fromSlides(slides: SlideSpec[], options?: DeckOptions): Buffer
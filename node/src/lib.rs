@@ -0,0 +1,59 @@
+//! Node.js bindings
+//!
+//! The `@pptx-rs/node` addon lets TypeScript backends generate decks
+//! server-side, from Markdown or from the same JSON slide specs the C API
+//! takes ([`SlideSpec`](ppt_rs::generator::SlideSpec)):
+//!
+//! ```js
+//! const { fromMarkdown, fromSlides } = require('@pptx-rs/node')
+//!
+//! fs.writeFileSync('notes.pptx', fromMarkdown('# Hello\n- from Node', { title: 'Notes' }))
+//! fs.writeFileSync('review.pptx', fromSlides([{ title: 'Overview', bullets: ['Revenue up'] }]))
+//! ```
+//!
+//! Both return a `Buffer` holding the `.pptx` and throw on invalid input.
+
+use napi::bindgen_prelude::Buffer;
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+use ppt_rs::cli::parse_markdown;
+use ppt_rs::generator::{SlideContent, SlideSpec, create_pptx_with_content};
+
+/// Options shared by every generator
+#[napi(object)]
+#[derive(Default)]
+pub struct DeckOptions {
+    /// Document title; defaults to "Presentation from Markdown" or "Presentation"
+    pub title: Option<String>,
+}
+
+fn build(title: &str, slides: Vec<SlideContent>) -> Result<Buffer> {
+    create_pptx_with_content(title, slides)
+        .map(Buffer::from)
+        .map_err(|e| Error::new(Status::GenericFailure, e.to_string()))
+}
+
+/// `.pptx` for a Markdown document (`#` headings start slides; Mermaid diagrams supported)
+#[napi]
+pub fn from_markdown(markdown: String, options: Option<DeckOptions>) -> Result<Buffer> {
+    let slides = parse_markdown(&markdown).map_err(|e| Error::new(Status::InvalidArg, e))?;
+    let title = options.unwrap_or_default().title;
+    build(title.as_deref().unwrap_or("Presentation from Markdown"), slides)
+}
+
+/// `.pptx` for a list of JSON slide specs
+#[napi]
+pub fn from_slides(slides: Vec<serde_json::Value>, options: Option<DeckOptions>) -> Result<Buffer> {
+    let slides = slides
+        .into_iter()
+        .enumerate()
+        .map(|(i, value)| {
+            serde_json::from_value::<SlideSpec>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|spec| spec.build().map_err(|e| e.to_string()))
+                .map_err(|e| Error::new(Status::InvalidArg, format!("slide {}: {e}", i + 1)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let title = options.unwrap_or_default().title;
+    build(title.as_deref().unwrap_or("Presentation"), slides)
+}
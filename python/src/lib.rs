@@ -0,0 +1,248 @@
+//! Python bindings for the generator
+//!
+//! Exposes slides, tables, charts, images and the Markdown converter as the
+//! `ppt_rs` Python module, so pipelines built on python-pptx can hand deck
+//! generation to this engine without being rewritten in Rust:
+//!
+//! ```python
+//! import ppt_rs
+//!
+//! sales = ppt_rs.Chart("Sales", "bar", ["Q1", "Q2"], {"2024": [10.0, 12.5]})
+//! slides = [
+//!     ppt_rs.SlideContent("Overview").add_bullet("Revenue up 25%"),
+//!     ppt_rs.SlideContent("Sales").add_chart(sales),
+//! ]
+//! data = ppt_rs.create_pptx_with_content("Quarterly review", slides)
+//! open("review.pptx", "wb").write(data)
+//! ```
+//!
+//! Objects are immutable: every builder method returns a new object, so
+//! calls chain like the Rust API.
+
+use ppt_rs::cli::parse_markdown;
+use ppt_rs::generator::{
+    Chart as RsChart, ChartBuilder, ChartSeries, ChartType, Fit, Image, ImageSource, Region, SlideContent as RsSlide,
+    SlideLayout, Table as RsTable, TableCell, TableRow,
+};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+
+/// Region from a name such as `"left_half"` or an `(x, y, width, height)` tuple in EMU
+fn region(value: &Bound<'_, PyAny>) -> PyResult<Region> {
+    if let Ok((x, y, width, height)) = value.extract::<(u32, u32, u32, u32)>() {
+        return Ok(Region::Custom { x, y, width, height });
+    }
+    let name: String = value.extract()?;
+    match name.as_str() {
+        "content" => Ok(Region::Content),
+        "left_half" => Ok(Region::LeftHalf),
+        "right_half" => Ok(Region::RightHalf),
+        "top_half" => Ok(Region::TopHalf),
+        "bottom_half" => Ok(Region::BottomHalf),
+        "full_slide" => Ok(Region::FullSlide),
+        _ => Err(PyValueError::new_err(format!("unknown region \"{name}\""))),
+    }
+}
+
+/// Table of text cells, the first row optionally styled as a header
+#[pyclass(name = "Table", module = "ppt_rs", frozen)]
+#[derive(Clone)]
+struct Table(RsTable);
+
+#[pymethods]
+impl Table {
+    /// `rows` is a list of rows of cell text; `region` defaults to the content area
+    #[new]
+    #[pyo3(signature = (rows, header = true, region = None))]
+    fn new(rows: Vec<Vec<String>>, header: bool, region: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if columns == 0 {
+            return Err(PyValueError::new_err("table has no columns"));
+        }
+        let (x, y, width, _) = region.map(self::region).transpose()?.unwrap_or_default().bounds();
+        let rows = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut row)| {
+                row.resize(columns, String::new());
+                TableRow::new(
+                    row.iter()
+                        .map(|text| if header && i == 0 { TableCell::new(text).bold() } else { TableCell::new(text) })
+                        .collect(),
+                )
+            })
+            .collect();
+        Ok(Table(RsTable::new(rows, vec![width / columns as u32; columns], x, y)))
+    }
+
+    #[getter]
+    fn rows(&self) -> usize {
+        self.0.row_count()
+    }
+
+    #[getter]
+    fn columns(&self) -> usize {
+        self.0.column_count()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Table({} x {})", self.0.row_count(), self.0.column_count())
+    }
+}
+
+/// Chart of any type, e.g. `"bar"`, `"line"`, `"pie"` or `"barStacked"`
+#[pyclass(name = "Chart", module = "ppt_rs", frozen)]
+#[derive(Clone)]
+struct Chart(RsChart);
+
+#[pymethods]
+impl Chart {
+    /// `series` is a dict (or list of pairs) of series name to values, one per category
+    #[new]
+    #[pyo3(signature = (title, chart_type, categories, series, region = None))]
+    fn new(
+        title: &str,
+        chart_type: &str,
+        categories: Vec<String>,
+        series: &Bound<'_, PyAny>,
+        region: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Self> {
+        let chart_type = ChartType::from_name(chart_type)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown chart type \"{chart_type}\"")))?;
+        let series: Vec<(String, Vec<f64>)> = match series.downcast::<PyDict>() {
+            Ok(dict) => dict.items().extract()?,
+            Err(_) => series.extract()?,
+        };
+        let mut builder = ChartBuilder::new(title, chart_type)
+            .categories(categories.iter().map(String::as_str).collect());
+        if let Some(region) = region {
+            builder = builder.region(self::region(region)?);
+        }
+        for (name, values) in series {
+            builder = builder.add_series(ChartSeries::new(&name, values));
+        }
+        Ok(Chart(builder.build()))
+    }
+
+    #[getter]
+    fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Chart({:?}, {:?})", self.0.title, self.0.chart_type.as_str())
+    }
+}
+
+/// One slide: title, bullets, notes, tables, charts and images
+#[pyclass(name = "SlideContent", module = "ppt_rs", frozen)]
+#[derive(Clone)]
+struct SlideContent(RsSlide);
+
+#[pymethods]
+impl SlideContent {
+    #[new]
+    fn new(title: &str) -> Self {
+        SlideContent(RsSlide::new(title))
+    }
+
+    fn add_bullet(&self, text: &str) -> Self {
+        SlideContent(self.0.clone().add_bullet(text))
+    }
+
+    fn add_sub_bullet(&self, text: &str) -> Self {
+        SlideContent(self.0.clone().add_sub_bullet(text))
+    }
+
+    /// Layout by name: `"titleAndContent"`, `"twoColumn"`, `"blank"`, ...
+    fn layout(&self, name: &str) -> PyResult<Self> {
+        let layout = SlideLayout::from_name(name)
+            .ok_or_else(|| PyValueError::new_err(format!("unknown layout \"{name}\"")))?;
+        Ok(SlideContent(self.0.clone().layout(layout)))
+    }
+
+    fn notes(&self, text: &str) -> Self {
+        SlideContent(self.0.clone().notes(text))
+    }
+
+    fn table(&self, table: &Table) -> Self {
+        SlideContent(self.0.clone().table(table.0.clone()))
+    }
+
+    fn add_chart(&self, chart: &Chart) -> Self {
+        SlideContent(self.0.clone().add_chart(chart.0.clone()))
+    }
+
+    /// Add an image from its bytes, fitted inside `region` (the content area by default)
+    #[pyo3(signature = (data, filename = "image.png", region = None))]
+    fn add_image(&self, data: Vec<u8>, filename: &str, region: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let (x, y, width, height) = region.map(self::region).transpose()?.unwrap_or_default().bounds();
+        let image = Image::from_source(filename, ImageSource::Bytes(data))
+            .position(x, y)
+            .fit(Fit::Contain, width, height);
+        Ok(SlideContent(self.0.clone().add_image(image)))
+    }
+
+    #[getter]
+    fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    /// Bullet texts, sub-bullets included
+    #[getter]
+    fn bullets(&self) -> Vec<String> {
+        self.0.bullets.iter().map(|bullet| bullet.text.clone()).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SlideContent({:?})", self.0.title)
+    }
+}
+
+/// `.pptx` bytes for a deck of `slides`
+#[pyfunction]
+fn create_pptx_with_content<'py>(
+    py: Python<'py>,
+    title: &str,
+    slides: Vec<SlideContent>,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let slides = slides.into_iter().map(|slide| slide.0).collect();
+    let data = ppt_rs::generator::create_pptx_with_content(title, slides)
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &data))
+}
+
+/// `.pptx` bytes for a deck of `slides` empty slides
+#[pyfunction]
+fn create_pptx<'py>(py: Python<'py>, title: &str, slides: usize) -> PyResult<Bound<'py, PyBytes>> {
+    let data = ppt_rs::generator::create_pptx(title, slides).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    Ok(PyBytes::new(py, &data))
+}
+
+/// Slides for a Markdown document (`#` headings start slides; Mermaid diagrams supported)
+#[pyfunction(name = "parse_markdown")]
+fn py_parse_markdown(markdown: &str) -> PyResult<Vec<SlideContent>> {
+    let slides = parse_markdown(markdown).map_err(PyValueError::new_err)?;
+    Ok(slides.into_iter().map(SlideContent).collect())
+}
+
+/// `.pptx` bytes for a Markdown document
+#[pyfunction]
+fn markdown_to_pptx<'py>(py: Python<'py>, markdown: &str, title: &str) -> PyResult<Bound<'py, PyBytes>> {
+    create_pptx_with_content(py, title, py_parse_markdown(markdown)?)
+}
+
+#[pymodule]
+#[pyo3(name = "ppt_rs")]
+fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("__version__", ppt_rs::VERSION)?;
+    module.add_class::<SlideContent>()?;
+    module.add_class::<Table>()?;
+    module.add_class::<Chart>()?;
+    module.add_function(wrap_pyfunction!(create_pptx_with_content, module)?)?;
+    module.add_function(wrap_pyfunction!(create_pptx, module)?)?;
+    module.add_function(wrap_pyfunction!(py_parse_markdown, module)?)?;
+    module.add_function(wrap_pyfunction!(markdown_to_pptx, module)?)?;
+    Ok(())
+}
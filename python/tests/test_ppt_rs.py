@@ -0,0 +1,42 @@
+import io
+import zipfile
+
+import pytest
+
+import ppt_rs
+
+
+def parts(data):
+    return zipfile.ZipFile(io.BytesIO(data)).namelist()
+
+
+def test_slides_tables_and_charts():
+    table = ppt_rs.Table([["Region", "Sales"], ["EMEA", "12"], ["APAC"]])
+    chart = ppt_rs.Chart("Sales", "bar", ["Q1", "Q2"], {"2024": [10.0, 12.5], "2025": [11.0, 14.0]})
+    slides = [
+        ppt_rs.SlideContent("Overview").add_bullet("Revenue up").add_sub_bullet("EMEA leads").notes("Say hi"),
+        ppt_rs.SlideContent("Table").layout("titleOnly").table(table),
+        ppt_rs.SlideContent("Chart").add_chart(chart),
+    ]
+    assert (table.rows, table.columns) == (3, 2)
+    assert slides[0].bullets == ["Revenue up", "EMEA leads"]
+
+    names = parts(ppt_rs.create_pptx_with_content("Review", slides))
+    assert "ppt/slides/slide3.xml" in names
+    assert any(name.startswith("ppt/charts/") for name in names)
+    assert any(name.startswith("ppt/notesSlides/") for name in names)
+
+
+def test_markdown_to_pptx():
+    slides = ppt_rs.parse_markdown("# One\n- a\n- b\n\n# Two\nText")
+    assert [slide.title for slide in slides] == ["One", "Two"]
+    assert "ppt/slides/slide1.xml" in parts(ppt_rs.markdown_to_pptx("# One\n- a", "Deck"))
+
+
+def test_invalid_names_raise_value_error():
+    with pytest.raises(ValueError, match="unknown layout"):
+        ppt_rs.SlideContent("x").layout("nope")
+    with pytest.raises(ValueError, match="unknown chart type"):
+        ppt_rs.Chart("x", "nope", ["a"], {"s": [1.0]})
+    with pytest.raises(ValueError, match="unknown region"):
+        ppt_rs.Table([["a"]], region="middle")
@@ -0,0 +1,264 @@
+//! Presentation analysis
+//!
+//! Estimates how long a deck takes to present from speaker-note word counts
+//! and bullet counts, and summarizes pacing per slide and per section.
+
+use std::fmt;
+
+use crate::api::Presentation;
+use crate::generator::SlideContent;
+
+/// Settings for duration estimation
+#[derive(Clone, Debug, PartialEq)]
+pub struct PacingConfig {
+    /// Speaking rate used for speaker notes
+    pub words_per_minute: u32,
+    /// Time spent on each bullet point, in seconds
+    pub seconds_per_bullet: u32,
+    /// Minimum time any slide stays on screen, in seconds
+    pub min_seconds_per_slide: u32,
+}
+
+impl Default for PacingConfig {
+    fn default() -> Self {
+        PacingConfig {
+            words_per_minute: 130,
+            seconds_per_bullet: 10,
+            min_seconds_per_slide: 15,
+        }
+    }
+}
+
+impl PacingConfig {
+    /// Create a pacing config with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set speaking rate in words per minute
+    pub fn words_per_minute(mut self, wpm: u32) -> Self {
+        self.words_per_minute = wpm.max(1);
+        self
+    }
+
+    /// Set time per bullet point in seconds
+    pub fn seconds_per_bullet(mut self, seconds: u32) -> Self {
+        self.seconds_per_bullet = seconds;
+        self
+    }
+
+    /// Set minimum time per slide in seconds
+    pub fn min_seconds_per_slide(mut self, seconds: u32) -> Self {
+        self.min_seconds_per_slide = seconds;
+        self
+    }
+}
+
+/// Estimated pacing for a single slide
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlidePacing {
+    /// 1-based slide number
+    pub number: usize,
+    /// Slide title
+    pub title: String,
+    /// Words in the speaker notes
+    pub notes_words: usize,
+    /// Number of bullet points
+    pub bullet_count: usize,
+    /// Estimated time on the slide, in seconds
+    pub seconds: u32,
+}
+
+/// Estimated pacing for a section (a divider slide and the slides after it)
+#[derive(Clone, Debug, PartialEq)]
+pub struct SectionPacing {
+    /// Section title (the divider slide's title)
+    pub title: String,
+    /// 1-based number of the first slide in the section
+    pub first_slide: usize,
+    /// Number of slides in the section
+    pub slide_count: usize,
+    /// Estimated time for the whole section, in seconds
+    pub seconds: u32,
+}
+
+/// Duration estimate and pacing breakdown for a presentation
+#[derive(Clone, Debug, PartialEq)]
+pub struct PacingReport {
+    pub slides: Vec<SlidePacing>,
+    pub sections: Vec<SectionPacing>,
+    /// Estimated total duration, in seconds
+    pub total_seconds: u32,
+}
+
+impl PacingReport {
+    /// Estimated total duration in whole minutes (rounded up)
+    pub fn total_minutes(&self) -> u32 {
+        self.total_seconds.div_ceil(60)
+    }
+}
+
+/// Estimate duration and pacing for a presentation
+///
+/// Sections start at centered-title or section-header slides, and at
+/// title-only slides with no other content (how dividers look once a
+/// deck has been re-imported). Slides before the first divider form an
+/// untitled opening section.
+pub fn analyze(presentation: &Presentation, config: &PacingConfig) -> PacingReport {
+    let mut slides = Vec::new();
+    let mut sections: Vec<SectionPacing> = Vec::new();
+
+    for (i, slide) in presentation.slides().iter().enumerate() {
+        let pacing = slide_pacing(i + 1, slide, config);
+
+        if is_section_divider(slide) || sections.is_empty() {
+            let title = if is_section_divider(slide) { slide.title.clone() } else { String::new() };
+            sections.push(SectionPacing {
+                title,
+                first_slide: pacing.number,
+                slide_count: 0,
+                seconds: 0,
+            });
+        }
+        if let Some(section) = sections.last_mut() {
+            section.slide_count += 1;
+            section.seconds += pacing.seconds;
+        }
+
+        slides.push(pacing);
+    }
+
+    let total_seconds = slides.iter().map(|s| s.seconds).sum();
+    PacingReport { slides, sections, total_seconds }
+}
+
+fn slide_pacing(number: usize, slide: &SlideContent, config: &PacingConfig) -> SlidePacing {
+    let notes_words = slide.notes.as_deref().map_or(0, |n| n.split_whitespace().count());
+    let bullet_count = slide.bullets.len().max(slide.content.len());
+
+    let speaking = (notes_words as u32 * 60).div_ceil(config.words_per_minute.max(1));
+    let seconds = (speaking + bullet_count as u32 * config.seconds_per_bullet)
+        .max(config.min_seconds_per_slide);
+
+    SlidePacing {
+        number,
+        title: slide.title.clone(),
+        notes_words,
+        bullet_count,
+        seconds,
+    }
+}
+
+fn is_section_divider(slide: &SlideContent) -> bool {
+    if slide.layout.is_section_layout() {
+        return true;
+    }
+    !slide.title.trim().is_empty()
+        && slide.bullets.is_empty()
+        && slide.content.is_empty()
+        && slide.table.is_none()
+        && slide.charts.is_empty()
+        && slide.images.is_empty()
+        && slide.shapes.is_empty()
+        && slide.code_blocks.is_empty()
+}
+
+fn format_duration(seconds: u32) -> String {
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+impl fmt::Display for PacingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Pacing Report")?;
+        writeln!(f, "=============")?;
+        writeln!(
+            f,
+            "Estimated duration: {} ({} slides)",
+            format_duration(self.total_seconds),
+            self.slides.len()
+        )?;
+
+        for section in &self.sections {
+            let title = if section.title.is_empty() { "(opening)" } else { section.title.as_str() };
+            writeln!(f)?;
+            writeln!(
+                f,
+                "{}  [{} slides, {}]",
+                title,
+                section.slide_count,
+                format_duration(section.seconds)
+            )?;
+            let end = section.first_slide + section.slide_count;
+            for slide in self.slides.iter().filter(|s| s.number >= section.first_slide && s.number < end) {
+                writeln!(
+                    f,
+                    "  {:>3}. {:<40} {:>5}  ({} words, {} bullets)",
+                    slide.number,
+                    slide.title,
+                    format_duration(slide.seconds),
+                    slide.notes_words,
+                    slide.bullet_count
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::SlideLayout;
+
+    #[test]
+    fn test_slide_estimate_uses_notes_and_bullets() {
+        let notes = vec!["word"; 130].join(" ");
+        let pres = Presentation::new().add_slide(
+            SlideContent::new("Intro").add_bullet("A").add_bullet("B").notes(&notes),
+        );
+
+        let report = analyze(&pres, &PacingConfig::default());
+        assert_eq!(report.slides[0].notes_words, 130);
+        assert_eq!(report.slides[0].bullet_count, 2);
+        assert_eq!(report.slides[0].seconds, 60 + 20);
+        assert_eq!(report.total_seconds, 80);
+        assert_eq!(report.total_minutes(), 2);
+    }
+
+    #[test]
+    fn test_words_per_minute_and_minimum() {
+        let notes = vec!["word"; 100].join(" ");
+        let pres = Presentation::new()
+            .add_slide(SlideContent::new("Talk").notes(&notes))
+            .add_slide(SlideContent::new("Quick").add_bullet("One"));
+
+        let config = PacingConfig::new().words_per_minute(200).min_seconds_per_slide(20);
+        let report = analyze(&pres, &config);
+        assert_eq!(report.slides[0].seconds, 30);
+        assert_eq!(report.slides[1].seconds, 20);
+    }
+
+    #[test]
+    fn test_sections_split_on_dividers() {
+        let pres = Presentation::new()
+            .add_slide(SlideContent::new("Agenda").add_bullet("Topics"))
+            .add_slide(SlideContent::new("Part 1").layout(SlideLayout::SectionHeader))
+            .add_slide(SlideContent::new("Details").add_bullet("A"))
+            .add_slide(SlideContent::new("Part 2"))
+            .add_slide(SlideContent::new("More").add_bullet("B"));
+
+        let report = analyze(&pres, &PacingConfig::default());
+        let titles: Vec<&str> = report.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["", "Part 1", "Part 2"]);
+        assert_eq!(report.sections[1].first_slide, 2);
+        assert_eq!(report.sections[1].slide_count, 2);
+        assert_eq!(
+            report.sections.iter().map(|s| s.seconds).sum::<u32>(),
+            report.total_seconds
+        );
+
+        let text = report.to_string();
+        assert!(text.contains("Part 1  [2 slides"));
+        assert!(text.contains("(opening)"));
+    }
+}
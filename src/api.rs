@@ -3,12 +3,26 @@
 //! High-level API for working with PowerPoint presentations.
 
 use crate::exc::{Result, PptxError};
+use crate::instrument::event;
 use crate::opc::Package;
-use crate::generator::{SlideContent, create_pptx_with_content, Image};
+use crate::generator::builder::{PackageOptions, write_pptx_with_options};
+use crate::generator::{SlideContent, Provenance, ThemeFonts, GradientFill, PrintSettings, SlideSize, SlideNumbering, ImagePlaceholder, Stepper, SlideLayout, Table, BuildProgress, BuildCancelled, BudgetExceeded, ValidationError, GenerationOutput, CompatProfile, Warnings, assign_stable_ids};
+use crate::generator::warnings::{check_bounds, check_slides};
+use crate::prelude::themes::Theme;
+use crate::style_guide::{Enforcement, StyleProfile};
+use crate::stylesheet::StyleSheet;
+use crate::citations::Citations;
+use crate::integration::PresentationMetadata;
+use std::io::{Cursor, Read, Seek, Write};
+#[cfg(all(feature = "media", not(target_arch = "wasm32")))]
+use crate::generator::Image;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::import::import_pptx;
+#[cfg(not(target_arch = "wasm32"))]
 use crate::export::html::export_to_html;
-use std::io::{Read, Seek};
+#[cfg(not(target_arch = "wasm32"))]
 use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
 use std::process::Command;
 
 /// Represents a PowerPoint presentation
@@ -16,6 +30,24 @@ use std::process::Command;
 pub struct Presentation {
     title: String,
     slides: Vec<SlideContent>,
+    section_band: Option<GradientFill>,
+    section_stepper: Option<Stepper>,
+    theme: Option<Theme>,
+    print_settings: Option<PrintSettings>,
+    slide_size: SlideSize,
+    style_profile: Option<(StyleProfile, Enforcement)>,
+    slide_numbering: Option<SlideNumbering>,
+    provenance: Option<Provenance>,
+    fonts: Option<ThemeFonts>,
+    image_placeholder: Option<ImagePlaceholder>,
+    compat: CompatProfile,
+    styles: StyleSheet,
+    citations: Citations,
+    metadata: Option<PresentationMetadata>,
+    auto_title_slide: bool,
+    closing_slide: Option<(String, String)>,
+    auto_text_color: bool,
+    stable_ids: bool,
 }
 
 impl Presentation {
@@ -24,6 +56,24 @@ impl Presentation {
         Presentation {
             title: String::new(),
             slides: Vec::new(),
+            section_band: None,
+            section_stepper: None,
+            theme: None,
+            print_settings: None,
+            slide_size: SlideSize::STANDARD,
+            style_profile: None,
+            slide_numbering: None,
+            provenance: None,
+            fonts: None,
+            image_placeholder: None,
+            compat: CompatProfile::PowerPoint,
+            styles: StyleSheet::new(),
+            citations: Citations::new(),
+            metadata: None,
+            auto_title_slide: false,
+            closing_slide: None,
+            auto_text_color: true,
+            stable_ids: false,
         }
     }
 
@@ -32,6 +82,24 @@ impl Presentation {
         Presentation {
             title: title.to_string(),
             slides: Vec::new(),
+            section_band: None,
+            section_stepper: None,
+            theme: None,
+            print_settings: None,
+            slide_size: SlideSize::STANDARD,
+            style_profile: None,
+            slide_numbering: None,
+            provenance: None,
+            fonts: None,
+            image_placeholder: None,
+            compat: CompatProfile::PowerPoint,
+            styles: StyleSheet::new(),
+            citations: Citations::new(),
+            metadata: None,
+            auto_title_slide: false,
+            closing_slide: None,
+            auto_text_color: true,
+            stable_ids: false,
         }
     }
 
@@ -47,6 +115,193 @@ impl Presentation {
         self
     }
 
+    /// Give every section divider a gradient band derived from `theme`
+    ///
+    /// Applies `theme.section_gradient()` (primary → dark) to all
+    /// `CenteredTitle` and `SectionHeader` slides that don't set their own band.
+    pub fn section_bands(mut self, theme: &Theme) -> Self {
+        self.section_band = Some(theme.section_gradient());
+        self
+    }
+
+    /// Draw `stepper` atop every slide that follows a section header
+    ///
+    /// Each `SectionHeader` slide starts the next step, which becomes the
+    /// current step on the slides after it. A stepper without labels is
+    /// labelled with the section headers' titles.
+    pub fn section_stepper(mut self, stepper: Stepper) -> Self {
+        self.section_stepper = Some(stepper);
+        self
+    }
+
+    /// Use `theme` for every color the slides leave unset
+    ///
+    /// Titles, unfilled shapes, table header rows and chart series pick up
+    /// the theme's palette at build time (see [`Theme::apply_defaults`]), so
+    /// slide code doesn't repeat hex constants.
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.theme = Some(theme.clone());
+        self
+    }
+
+    /// Set the default print settings (e.g. 6-up grayscale handouts)
+    ///
+    /// Written to `ppt/presProps.xml`, so printing or exporting to PDF from
+    /// PowerPoint uses the intended handout format.
+    pub fn print_settings(mut self, settings: PrintSettings) -> Self {
+        self.print_settings = Some(settings);
+        self
+    }
+
+    /// Set the slide size (4:3 by default)
+    ///
+    /// Shapes placed with `Shape::at` are resolved against this size when
+    /// the presentation is built. Layout placeholders keep their 4:3 geometry.
+    pub fn slide_size(mut self, size: SlideSize) -> Self {
+        self.slide_size = size;
+        self
+    }
+
+    /// Check every slide against an org-wide style profile at build time
+    ///
+    /// With [`Enforcement::Error`] the build fails with
+    /// `PptxError::Validation` at the first violation; with
+    /// [`Enforcement::AutoCorrect`] violations are fixed (see
+    /// [`StyleProfile::correct`]). Theme defaults are applied first.
+    pub fn style_profile(mut self, profile: StyleProfile, enforcement: Enforcement) -> Self {
+        self.style_profile = Some((profile, enforcement));
+        self
+    }
+
+    /// Number the slides with PowerPoint slide number fields
+    ///
+    /// Numbers are written as `slidenum` fields in a `sldNum` placeholder, so
+    /// they stay correct when slides are reordered in PowerPoint (see
+    /// [`crate::generator::slide_number`] for the format caveats).
+    pub fn slide_numbers(mut self, numbering: SlideNumbering) -> Self {
+        self.slide_numbering = Some(numbering);
+        self
+    }
+
+    /// Record how the deck was generated in a custom XML part
+    ///
+    /// Stores the generator, its version, the source hash and the build
+    /// time; read it back with `PresentationReader::provenance()`.
+    pub fn provenance(mut self, provenance: Provenance) -> Self {
+        self.provenance = Some(provenance);
+        self
+    }
+
+    /// Set the named styles bullets, shapes and table cells refer to with `.style(name)`
+    ///
+    /// Styles are resolved at build time, before theme defaults, with scheme
+    /// colors taken from the theme (see [`crate::stylesheet`]).
+    pub fn styles(mut self, styles: StyleSheet) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Register the sources bullets cite with `.cite(key)`
+    ///
+    /// Markers are numbered and the sources listed (in footnotes or on a
+    /// final slide) at build time; see [`crate::citations`].
+    pub fn citations(mut self, citations: Citations) -> Self {
+        self.citations = citations;
+        self
+    }
+
+    /// Set the theme's heading and body fonts
+    ///
+    /// Titles and text without an explicit font follow these, and
+    /// PowerPoint's font menus list them as the theme fonts.
+    pub fn fonts(mut self, fonts: ThemeFonts) -> Self {
+        self.fonts = Some(fonts);
+        self
+    }
+
+    /// Set the box drawn for images whose data cannot be read
+    ///
+    /// Applies to images without a placeholder of their own; such images
+    /// are also reported by [`Self::build_with_warnings`].
+    pub fn image_placeholder(mut self, placeholder: ImagePlaceholder) -> Self {
+        self.image_placeholder = Some(placeholder);
+        self
+    }
+
+    /// Avoid constructs that `profile`'s app imports poorly
+    ///
+    /// Transitions, shapes and charts it lacks are replaced with the nearest
+    /// equivalent (see [`crate::generator::compat`]); each replacement is
+    /// reported by [`Self::build_with_warnings`].
+    pub fn compat(mut self, profile: CompatProfile) -> Self {
+        self.compat = profile;
+        self
+    }
+
+    /// Set the subtitle, author, date and logo of the generated cover
+    pub fn metadata(mut self, metadata: PresentationMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Open with a cover generated from the metadata and theme
+    ///
+    /// The cover shows the metadata's title (the presentation's without
+    /// [`Self::metadata`]), subtitle, author, date and logo; see
+    /// [`PresentationMetadata::title_slide`].
+    ///
+    /// ```
+    /// use ppt_rs::{Presentation, PresentationMetadata, SlideContent};
+    /// use ppt_rs::prelude::themes::CORPORATE;
+    ///
+    /// let pres = Presentation::with_title("Q3 Review")
+    ///     .theme(&CORPORATE)
+    ///     .metadata(PresentationMetadata::new("Q3 Review", 1).subtitle("Results and outlook").author("Finance"))
+    ///     .auto_title_slide(true)
+    ///     .auto_closing_slide("Thank You", "finance@example.com")
+    ///     .add_slide(SlideContent::new("Revenue").add_bullet("Up 12%"));
+    /// assert!(pres.build().is_ok());
+    /// ```
+    pub fn auto_title_slide(mut self, enabled: bool) -> Self {
+        self.auto_title_slide = enabled;
+        self
+    }
+
+    /// End with a themed slide showing `heading` and `contact_info`
+    pub fn auto_closing_slide(mut self, heading: &str, contact_info: &str) -> Self {
+        self.closing_slide = Some((heading.to_string(), contact_info.to_string()));
+        self
+    }
+
+    /// Pick white or black text for table cells that have a background
+    /// color but no text color, by the background's luminance (on by default)
+    ///
+    /// ```
+    /// use ppt_rs::{Presentation, SlideContent};
+    /// use ppt_rs::generator::{TableBuilder, TableCell, TableRow};
+    ///
+    /// // Header text turns white on the dark fill; opt out to keep theme text colors
+    /// let table = TableBuilder::new(vec![2_000_000])
+    ///     .add_row(TableRow::new(vec![TableCell::new("Region").bold().background_color("1F497D")]))
+    ///     .build();
+    /// let pres = Presentation::new()
+    ///     .auto_text_color(false)
+    ///     .add_slide(SlideContent::new("Sales").table(table));
+    /// assert!(pres.build().is_ok());
+    /// ```
+    pub fn auto_text_color(mut self, enabled: bool) -> Self {
+        self.auto_text_color = enabled;
+        self
+    }
+
+    /// Derive slide and shape IDs from their content instead of their
+    /// position, so they stay the same when the deck is regenerated after
+    /// slides or shapes are added elsewhere (see [`assign_stable_ids`])
+    pub fn stable_ids(mut self, enabled: bool) -> Self {
+        self.stable_ids = enabled;
+        self
+    }
+
     /// Append slides from another presentation
     pub fn add_presentation(mut self, other: Presentation) -> Self {
         self.slides.extend(other.slides);
@@ -68,15 +323,228 @@ impl Presentation {
         &self.title
     }
 
+    /// Get the slide size
+    pub fn get_slide_size(&self) -> SlideSize {
+        self.slide_size
+    }
+
     /// Build the presentation as PPTX bytes
     pub fn build(&self) -> Result<Vec<u8>> {
+        Ok(self.write_to(Cursor::new(Vec::new()))?.into_inner())
+    }
+
+    /// Build the presentation as a password-protected file
+    ///
+    /// The package is encrypted with AES-256 (see [`crate::opc::crypto`]);
+    /// PowerPoint asks for `password` when opening it.
+    #[cfg(feature = "encryption")]
+    pub fn build_encrypted(&self, password: &str) -> Result<Vec<u8>> {
+        crate::opc::crypto::encrypt(&self.build()?, password)
+    }
+
+    /// Hash of the built deck's content that ignores build timestamps,
+    /// element IDs and generated UUIDs (see [`Package::fingerprint`])
+    ///
+    /// Building the same content twice gives the same fingerprint, so a
+    /// cache can tell whether a regenerated deck actually changed:
+    ///
+    /// ```
+    /// use ppt_rs::{Presentation, SlideContent};
+    ///
+    /// let deck = |bullet: &str| Presentation::new().add_slide(SlideContent::new("Q3").add_bullet(bullet));
+    /// assert_eq!(deck("Revenue up").fingerprint()?, deck("Revenue up").fingerprint()?);
+    /// assert_ne!(deck("Revenue up").fingerprint()?, deck("Revenue down").fingerprint()?);
+    /// # Ok::<(), ppt_rs::PptxError>(())
+    /// ```
+    pub fn fingerprint(&self) -> Result<String> {
+        Ok(Package::open_reader(Cursor::new(self.build()?))?.fingerprint())
+    }
+
+    /// Write the presentation as a PPTX package to `writer`
+    ///
+    /// Available on every target, including `wasm32-unknown-unknown`.
+    pub fn write_to<W: Write + Seek>(&self, writer: W) -> Result<W> {
+        self.write_with_progress(writer, BuildProgress::new())
+    }
+
+    /// Build the presentation, reporting progress after each slide
+    ///
+    /// Returns `PptxError::Cancelled` or `PptxError::DeadlineExceeded` if the
+    /// progress' cancel token fires, and `PptxError::BudgetExceeded` if its
+    /// memory budget is exhausted.
+    /// `Presentation` is `Send + Sync + 'static`, so a clone can be moved into
+    /// `tokio::task::spawn_blocking` (see [`crate::generator::progress`]).
+    pub fn build_with_progress(&self, progress: BuildProgress) -> Result<Vec<u8>> {
+        Ok(self.write_with_progress(Cursor::new(Vec::new()), progress)?.into_inner())
+    }
+
+    /// Build the presentation and collect non-fatal warnings
+    ///
+    /// Warnings (text likely to overflow, images that could not be read,
+    /// elements reaching off the slide) are numbered by the slides as
+    /// written, after any style-profile splits.
+    ///
+    /// ```
+    /// use ppt_rs::{BuildProgress, Presentation, SlideContent};
+    ///
+    /// let output = Presentation::with_title("Demo")
+    ///     .add_slide(SlideContent::new("Intro").add_bullet("Hello"))
+    ///     .build_with_warnings(BuildProgress::new())
+    ///     .unwrap();
+    /// assert!(output.warnings.is_empty());
+    /// assert!(!output.data.is_empty());
+    /// ```
+    pub fn build_with_warnings(&self, progress: BuildProgress) -> Result<GenerationOutput> {
+        let (slides, notes) = self.final_slides()?;
+        let mut warnings: Vec<_> = check_slides(&slides).into_iter()
+            .chain(check_bounds(&slides, self.slide_size))
+            .chain(notes)
+            .collect();
+        warnings.sort_by_key(|w| w.slide);
+        let warnings = warnings.into_iter().collect();
+        let data = self.write_slides(Cursor::new(Vec::new()), slides, progress)?.into_inner();
+        Ok(GenerationOutput { data, warnings })
+    }
+
+    /// Build the presentation and strictly check the resulting package
+    ///
+    /// Runs the slide checks of [`Self::build`], then
+    /// [`validate_package`](crate::generator::validate::validate_package) on
+    /// the bytes: broken relationships, missing content types, malformed
+    /// XML or duplicate shape IDs fail with [`PptxError::Validation`].
+    pub fn validate_strict(&self) -> Result<()> {
+        let data = self.build()?;
+        crate::generator::validate::validate_package(&data)?;
+        Ok(())
+    }
+
+    fn write_with_progress<W: Write + Seek>(&self, writer: W, progress: BuildProgress) -> Result<W> {
+        let (slides, _) = self.final_slides()?;
+        self.write_slides(writer, slides, progress)
+    }
+
+    /// Slides as they will be written: prepared, style-enforced, numbered and
+    /// adapted to the compatibility profile, with unknown style references
+    /// and the profile's substitutions
+    fn final_slides(&self) -> Result<(Vec<SlideContent>, Warnings)> {
         if self.slides.is_empty() {
             return Err(PptxError::InvalidState("Presentation has no slides".into()));
         }
-        create_pptx_with_content(&self.title, self.slides.clone())
-            .map_err(|e| PptxError::Generic(e.to_string()))
+        let mut slides = self.prepared_slides();
+        let mut warnings = self.styles.check(&slides);
+        let first_slide = 1 + usize::from(self.auto_title_slide);
+        warnings.extend(self.citations.check(&self.slides, first_slide));
+        if let Some((profile, enforcement)) = &self.style_profile {
+            match enforcement {
+                Enforcement::Error => {
+                    if let Some(violation) = profile.check(&slides).into_iter().next() {
+                        return Err(violation.into());
+                    }
+                }
+                Enforcement::AutoCorrect => slides = profile.correct(slides),
+            }
+        }
+        event!(DEBUG, slides = slides.len(), "slides prepared");
+        // After auto-correction, which can split slides
+        if let Some(numbering) = &self.slide_numbering {
+            numbering.apply(&mut slides);
+        }
+        warnings.extend(self.compat.apply(&mut slides));
+        if self.stable_ids {
+            assign_stable_ids(&mut slides);
+        }
+        Ok((slides, warnings))
+    }
+
+    fn write_slides<W: Write + Seek>(&self, writer: W, slides: Vec<SlideContent>, progress: BuildProgress) -> Result<W> {
+        let package = PackageOptions {
+            print: self.print_settings.as_ref(),
+            slide_size: self.slide_size,
+            provenance: self.provenance.as_ref(),
+            fonts: self.fonts.as_ref(),
+        };
+        write_pptx_with_options(writer, &self.title, slides, &package, progress)
+            .map_err(|e| {
+                if let Some(&reason) = e.downcast_ref::<BuildCancelled>() {
+                    reason.into()
+                } else if let Some(&exceeded) = e.downcast_ref::<BudgetExceeded>() {
+                    exceeded.into()
+                } else if let Some(invalid) = e.downcast_ref::<ValidationError>() {
+                    invalid.clone().into()
+                } else {
+                    PptxError::Generic(e.to_string())
+                }
+            })
+    }
+
+    /// Slides with citations numbered, the generated cover, sources and
+    /// closing slides added, the section band, styles and theme defaults
+    /// applied, and image data read
+    fn prepared_slides(&self) -> Vec<SlideContent> {
+        let mut slides = self.slides.clone();
+        self.citations.apply(&mut slides);
+        if self.auto_title_slide || self.closing_slide.is_some() {
+            let metadata = self.metadata.clone().unwrap_or_else(|| PresentationMetadata::new(&self.title, slides.len()));
+            let theme = self.theme.as_ref();
+            if self.auto_title_slide {
+                slides.insert(0, metadata.title_slide(theme));
+            }
+            if let Some((heading, contact_info)) = &self.closing_slide {
+                slides.push(metadata.closing_slide(heading, contact_info, theme));
+            }
+        }
+        if let Some(stepper) = &self.section_stepper {
+            apply_section_stepper(&mut slides, stepper);
+        }
+        for slide in slides.iter_mut() {
+            if let Some(band) = &self.section_band
+                && slide.layout.is_section_layout()
+                && slide.section_band.is_none()
+            {
+                slide.section_band = Some(band.clone());
+            }
+            self.styles.apply(slide, self.theme.as_ref());
+            if let Some(theme) = &self.theme {
+                theme.apply_defaults(slide);
+            }
+            if self.auto_text_color {
+                slide.table = slide.table.take().map(Table::contrast_text);
+            }
+            for image in &mut slide.images {
+                if !image.load() && image.placeholder.is_none() {
+                    image.placeholder = self.image_placeholder.clone();
+                }
+            }
+        }
+        slides
+    }
+}
+
+/// Add `stepper` to the slides after each section header, with that
+/// section as the current step
+fn apply_section_stepper(slides: &mut [SlideContent], stepper: &Stepper) {
+    let stepper = if stepper.labels().is_empty() {
+        let titles = slides.iter()
+            .filter(|s| s.layout == SlideLayout::SectionHeader)
+            .map(|s| s.title.clone())
+            .collect();
+        stepper.clone().with_labels(titles)
+    } else {
+        stepper.clone()
+    };
+    let mut section = None;
+    for slide in slides.iter_mut() {
+        if slide.layout == SlideLayout::SectionHeader {
+            section = Some(section.map_or(0, |s| s + 1));
+        } else if let Some(section) = section {
+            slide.shapes.extend(stepper.clone().current(section).to_shapes());
+        }
     }
+}
 
+/// File-system and external-tool I/O (not available on `wasm32`)
+#[cfg(not(target_arch = "wasm32"))]
+impl Presentation {
     /// Save the presentation to a file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let data = self.build()?;
@@ -84,12 +552,36 @@ impl Presentation {
         Ok(())
     }
 
+    /// Save the presentation so it opens only with `password`
+    #[cfg(feature = "encryption")]
+    pub fn save_encrypted<P: AsRef<Path>>(&self, path: P, password: &str) -> Result<()> {
+        std::fs::write(path, self.build_encrypted(password)?)?;
+        Ok(())
+    }
+
     /// Create a presentation from a PPTX file
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path_str = path.as_ref().to_string_lossy();
         import_pptx(&path_str)
     }
 
+    /// Create a presentation from a password-protected PPTX file
+    ///
+    /// The package is decrypted in memory and read like any other deck.
+    /// Returns `PptxError::WrongPassword` if `password` doesn't open it.
+    ///
+    /// ```no_run
+    /// use ppt_rs::Presentation;
+    ///
+    /// let template = Presentation::open_with_password("board-template.pptx", "s3cret")?;
+    /// template.save_encrypted("board-2026-q4.pptx", "s3cret")?;
+    /// # Ok::<(), ppt_rs::PptxError>(())
+    /// ```
+    #[cfg(feature = "encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        crate::import::import_pptx_with_password(&path.as_ref().to_string_lossy(), password)
+    }
+
     /// Export the presentation to HTML
     pub fn save_as_html<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         let html = export_to_html(self)?;
@@ -204,7 +696,8 @@ impl Presentation {
 
     /// Create a presentation from a PDF file (each page becomes a slide)
     /// 
-    /// Requires `pdftoppm` (from poppler) to be installed.
+    /// Requires `pdftoppm` (from poppler) to be installed and the `media` feature.
+    #[cfg(feature = "media")]
     pub fn from_pdf<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         if !path.exists() {
@@ -293,6 +786,7 @@ impl Presentation {
 }
 
 /// Open a presentation from a file path
+#[cfg(not(target_arch = "wasm32"))]
 pub fn open<P: AsRef<Path>>(path: P) -> Result<Package> {
     Package::open(path)
 }
@@ -323,4 +817,293 @@ mod tests {
         let result = pres.build();
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_build_with_progress_reports_each_slide() {
+        let pres = Presentation::with_title("Test")
+            .add_slide(SlideContent::new("Slide 1"))
+            .add_slide(SlideContent::new("Slide 2"));
+
+        let mut reports = Vec::new();
+        pres.build_with_progress(BuildProgress::new().on_progress(|done, total| reports.push((done, total))))
+            .unwrap();
+        assert_eq!(reports, vec![(1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_build_with_progress_cancels() {
+        use crate::generator::CancelToken;
+
+        fn assert_send<T: Send + 'static>() {}
+        fn assert_sync<T: Sync>() {}
+        assert_send::<Presentation>();
+        assert_sync::<Presentation>();
+        assert_send::<BuildProgress<'static>>();
+
+        let pres = Presentation::with_title("Test")
+            .add_slide(SlideContent::new("Slide 1"))
+            .add_slide(SlideContent::new("Slide 2"));
+        let token = CancelToken::new();
+        let cancel = token.clone();
+        let mut done_slides = 0;
+        let result = pres.build_with_progress(BuildProgress::new()
+            .cancel_token(token)
+            .on_progress(|done, _| {
+                done_slides = done;
+                cancel.cancel();
+            }));
+
+        assert!(matches!(result, Err(PptxError::Cancelled)));
+        assert_eq!(done_slides, 1);
+    }
+
+    #[test]
+    fn test_build_with_memory_budget() {
+        use crate::generator::{AudioFormat, SlideContent};
+
+        let pres = Presentation::with_title("Test")
+            .add_slide(SlideContent::new("Slide 1"))
+            .add_slide(SlideContent::new("Slide 2").narration(vec![0; 64 * 1024], AudioFormat::Mp3));
+
+        assert!(pres.build_with_progress(BuildProgress::new().memory_budget(1024 * 1024)).is_ok());
+        let result = pres.build_with_progress(BuildProgress::new().memory_budget(32 * 1024));
+        assert!(matches!(result, Err(PptxError::BudgetExceeded { limit, .. }) if limit == 32 * 1024));
+    }
+
+    #[test]
+    fn test_build_reports_validation_errors() {
+        use crate::generator::{Shape, ShapeFill, ShapeType};
+
+        let shape = Shape::new(ShapeType::Rectangle, 0, 0, 1000, 1000).with_fill(ShapeFill::new("FF0000").with_transparency(150));
+        let pres = Presentation::with_title("Test").add_slide(SlideContent::new("Slide 1").add_shape(shape));
+
+        let result = pres.build();
+        assert!(matches!(result, Err(PptxError::Validation { ref location, .. }) if location == "slide 1, shape 1"));
+        assert!(pres.build_with_progress(BuildProgress::new().skip_validation()).is_ok());
+    }
+
+    #[test]
+    fn test_write_to_reopens_as_package() {
+        let pres = Presentation::with_title("Test")
+            .add_slide(SlideContent::new("Slide 1"));
+
+        let cursor = pres.write_to(Cursor::new(Vec::new())).unwrap();
+        let package = open_reader(Cursor::new(cursor.into_inner())).unwrap();
+        assert!(package.get_part("ppt/slides/slide1.xml").is_some());
+    }
+
+    #[test]
+    fn test_auto_title_and_closing_slides() {
+        use crate::prelude::themes::CORPORATE;
+
+        let metadata = PresentationMetadata::new("Q3 Review", 1).subtitle("Outlook").author("Finance").date("2024-10-01");
+        let pres = Presentation::with_title("Test")
+            .theme(&CORPORATE)
+            .metadata(metadata)
+            .auto_title_slide(true)
+            .auto_closing_slide("Thank You", "finance@example.com\n+1 555 0100")
+            .add_slide(SlideContent::new("Revenue"));
+
+        let slides = pres.prepared_slides();
+        assert_eq!(slides.len(), 3);
+        assert_eq!(slides[0].title, "Q3 Review");
+        assert_eq!(slides[0].title_color.as_deref(), Some(CORPORATE.background));
+        let lines: Vec<_> = slides[0].shapes.iter().map(|s| s.rich_text[0].text.as_str()).collect();
+        assert_eq!(lines, ["Outlook", "Finance · 2024-10-01"]);
+        // Theme defaults leave the text lines transparent
+        assert!(slides[0].shapes.iter().all(|s| s.fill.as_ref().unwrap().transparency == Some(0)));
+        assert_eq!(slides[2].title, "Thank You");
+        assert_eq!(slides[2].shapes[0].rich_text[0].text, "finance@example.com · +1 555 0100");
+        assert_eq!(pres.slide_count(), 1);
+        assert!(pres.build().is_ok());
+    }
+
+    #[test]
+    fn test_citations_before_closing_slide_and_warnings_skip_cover() {
+        use crate::citations::Citations;
+        use crate::generator::BulletPoint;
+
+        let pres = Presentation::with_title("Test")
+            .citations(Citations::new().source("idc", "IDC 2024"))
+            .auto_title_slide(true)
+            .auto_closing_slide("Thank You", "")
+            .add_slide(SlideContent::new("Market").add_bullet_point(BulletPoint::new("Growth").cite("idc").cite("gartner")));
+
+        let titles: Vec<_> = pres.prepared_slides().iter().map(|s| s.title.clone()).collect();
+        assert_eq!(titles, ["Test", "Market", "Sources", "Thank You"]);
+        let output = pres.build_with_warnings(BuildProgress::new()).unwrap();
+        let messages: Vec<String> = output.warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(messages, ["slide 2: no source registered for citation 'gartner'"]);
+    }
+
+    #[test]
+    fn test_section_bands_apply_to_dividers_only() {
+        use crate::generator::SlideLayout;
+        use crate::prelude::themes::CORPORATE;
+
+        let pres = Presentation::with_title("Test")
+            .section_bands(&CORPORATE)
+            .add_slide(SlideContent::new("Part 1").layout(SlideLayout::SectionHeader))
+            .add_slide(SlideContent::new("Intro").layout(SlideLayout::CenteredTitle))
+            .add_slide(SlideContent::new("Details").add_bullet("Point"));
+
+        let slides = pres.prepared_slides();
+        assert!(slides[0].section_band.is_some());
+        assert!(slides[1].section_band.is_some());
+        assert!(slides[2].section_band.is_none());
+        assert!(pres.slides()[0].section_band.is_none());
+        assert!(pres.build().is_ok());
+    }
+
+    #[test]
+    fn test_section_stepper_follows_sections() {
+        let pres = Presentation::with_title("Test")
+            .section_stepper(Stepper::new(Vec::<String>::new()))
+            .add_slide(SlideContent::new("Agenda"))
+            .add_slide(SlideContent::new("Plan").layout(SlideLayout::SectionHeader))
+            .add_slide(SlideContent::new("Goals"))
+            .add_slide(SlideContent::new("Ship").layout(SlideLayout::SectionHeader))
+            .add_slide(SlideContent::new("Dates"));
+
+        let slides = pres.prepared_slides();
+        let current = |slide: &SlideContent| slide.shapes.iter()
+            .find(|s| s.name.as_deref() == Some("Step Label") && s.rich_text[0].format.bold)
+            .map(|s| s.rich_text[0].text.clone());
+        assert!(slides[0].shapes.is_empty());
+        assert!(slides[1].shapes.is_empty());
+        assert_eq!(current(&slides[2]).as_deref(), Some("Plan"));
+        assert_eq!(current(&slides[4]).as_deref(), Some("Ship"));
+        assert!(pres.build().is_ok());
+    }
+
+    #[test]
+    fn test_auto_text_color() {
+        use crate::generator::{TableCell, TableRow};
+
+        let table = Table::new(vec![TableRow::new(vec![TableCell::new("Region").background_color("1F497D")])], vec![1000], 0, 0);
+        let pres = Presentation::new().add_slide(SlideContent::new("Sales").table(table));
+        let text_color = |pres: &Presentation| pres.prepared_slides()[0].table.as_ref().unwrap().rows[0].cells[0].text_color.clone();
+        assert_eq!(text_color(&pres).as_deref(), Some("FFFFFF"));
+        assert_eq!(text_color(&pres.auto_text_color(false)), None);
+    }
+
+    #[test]
+    fn test_stable_ids_survive_inserted_slides() {
+        use crate::generator::{Shape, ShapeType};
+
+        let roadmap = || SlideContent::new("Roadmap").add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_text("Q1"));
+        let ids = |pres: Presentation| {
+            let (slides, _) = pres.stable_ids(true).final_slides().unwrap();
+            let slide = slides.into_iter().last().unwrap();
+            (slide.slide_id, slide.shapes[0].id)
+        };
+        let before = ids(Presentation::new().add_slide(roadmap()));
+        let after = ids(Presentation::new().add_slide(SlideContent::new("Agenda")).add_slide(roadmap()));
+        assert!(before.0.is_some() && before.1.is_some());
+        assert_eq!(before, after);
+        assert_eq!(Presentation::new().add_slide(roadmap()).final_slides().unwrap().0[0].slide_id, None);
+    }
+
+    #[test]
+    #[cfg(feature = "encryption")]
+    fn test_open_with_password() {
+        let pptx = Presentation::with_title("Board")
+            .add_slide(SlideContent::new("Results").add_bullet("Revenue up"))
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("ppt_rs_locked_{}.pptx", uuid::Uuid::new_v4()));
+        std::fs::write(&path, crate::opc::crypto::encrypt_with_spin_count(&pptx, "s3cret", 10).unwrap()).unwrap();
+
+        let opened = Presentation::open_with_password(&path, "s3cret").unwrap();
+        assert_eq!(opened.get_title(), "Board");
+        assert_eq!(opened.slides[0].bullets[0].text, "Revenue up");
+        assert!(matches!(Presentation::open_with_password(&path, "guess"), Err(PptxError::WrongPassword)));
+        assert!(matches!(Presentation::from_path(&path), Err(PptxError::Encrypted)));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_tags_survive_round_trip() {
+        use crate::generator::{Shape, ShapeType};
+
+        let pptx = Presentation::new()
+            .add_slide(SlideContent::new("Budget")
+                .tag("owner", "finance")
+                .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 914400).with_name("Total").tag("region", "EMEA")))
+            .build()
+            .unwrap();
+        let path = std::env::temp_dir().join(format!("ppt_rs_tags_{}.pptx", uuid::Uuid::new_v4()));
+        std::fs::write(&path, pptx).unwrap();
+
+        let opened = Presentation::from_path(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let rebuilt = opened.build().unwrap();
+        let reader = crate::oxml::PresentationReader::from_package(crate::opc::Package::open_reader(std::io::Cursor::new(rebuilt)).unwrap()).unwrap();
+        assert_eq!(reader.slide_tags(0).unwrap().get("owner"), Some("finance"));
+        assert_eq!(reader.shape_tags(0).unwrap()[0].1.get("region"), Some("EMEA"));
+    }
+
+    #[test]
+    fn test_theme_fills_unset_colors() {
+        use crate::generator::{ChartBuilder, ChartSeries, ChartType, Shape, ShapeFill, ShapeType, TableBuilder};
+        use crate::prelude::themes::CARBON;
+
+        let chart = ChartBuilder::new("Sales", ChartType::Bar)
+            .add_series(ChartSeries::new("A", vec![1.0]))
+            .add_series(ChartSeries::new("B", vec![2.0]).with_color("FF0000"))
+            .add_series(ChartSeries::new("C", vec![3.0]))
+            .build();
+        let slide = SlideContent::new("Themed")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_fill(ShapeFill::new("00FF00")))
+            .table(TableBuilder::new(vec![1000]).add_simple_row(vec!["Header"]).add_simple_row(vec!["Body"]).build())
+            .add_chart(chart);
+        let pres = Presentation::with_title("Test").theme(&CARBON).add_slide(slide);
+
+        let slide = &pres.prepared_slides()[0];
+        assert_eq!(slide.title_color.as_deref(), Some(CARBON.primary));
+        assert_eq!(slide.shapes[0].fill.as_ref().unwrap().color, CARBON.primary);
+        assert_eq!(slide.shapes[1].fill.as_ref().unwrap().color, "00FF00");
+        let table = slide.table.as_ref().unwrap();
+        assert_eq!(table.rows[0].cells[0].background_color.as_deref(), Some(CARBON.primary));
+        assert_eq!(table.rows[1].cells[0].background_color, None);
+        let colors: Vec<_> = slide.charts[0].series.iter().map(|s| s.color.as_deref().unwrap()).collect();
+        assert_eq!(colors, [CARBON.primary, "FF0000", CARBON.secondary]);
+        assert!(pres.build().is_ok());
+    }
+
+    #[test]
+    fn test_slide_numbers_written_as_fields() {
+        use crate::generator::{SlideNumberFormat, SlideNumbering};
+
+        let pres = Presentation::with_title("Test")
+            .slide_numbers(SlideNumbering::new(SlideNumberFormat::OfTotal).skip_title(true).start_at(0))
+            .add_slide(SlideContent::new("Title"))
+            .add_slide(SlideContent::new("Agenda"));
+
+        let package = open_reader(Cursor::new(pres.build().unwrap())).unwrap();
+        let part = |name: &str| String::from_utf8(package.get_part(name).unwrap().to_vec()).unwrap();
+        assert!(part("ppt/presentation.xml").contains(r#"firstSlideNum="0""#));
+        assert!(!part("ppt/slides/slide1.xml").contains("slidenum"));
+        let slide2 = part("ppt/slides/slide2.xml");
+        assert!(slide2.contains(r#"<p:ph type="sldNum""#));
+        assert!(slide2.contains("<a:t>1</a:t></a:fld>"));
+        assert!(slide2.contains("<a:t> / 1</a:t>"));
+    }
+
+    #[test]
+    fn test_missing_image_uses_presentation_placeholder_and_warns() {
+        use crate::generator::{Image, ImagePlaceholder, WarningKind};
+
+        let pres = Presentation::with_title("Test")
+            .image_placeholder(ImagePlaceholder::new().icon("").show_file_name(true))
+            .add_slide(SlideContent::new("Chart").add_image(Image::new("no/such/chart.png", 914400, 914400, "png")));
+
+        let output = pres.build_with_warnings(BuildProgress::new()).unwrap();
+        let warnings: Vec<_> = output.warnings.iter().map(|w| &w.kind).collect();
+        assert_eq!(warnings, [&WarningKind::MissingImage { image: "no/such/chart.png".into() }]);
+        let package = open_reader(Cursor::new(output.data)).unwrap();
+        let slide = String::from_utf8(package.get_part("ppt/slides/slide1.xml").unwrap().to_vec()).unwrap();
+        assert!(slide.contains("<a:t>no/such/chart.png</a:t>"));
+    }
 }
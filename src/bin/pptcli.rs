@@ -1,19 +1,50 @@
 //! PPTX CLI - Command-line tool for creating PowerPoint presentations
 
 use clap::Parser;
-use ppt_rs::cli::{Cli, Commands, CreateCommand, FromMarkdownCommand, InfoCommand, ValidateCommand, ExportFormat};
+use ppt_rs::cli::{Cli, CliConfig, ProgressBar, help_json, write_completions, Commands, CreateCommand, FromMarkdownCommand, InfoCommand, ValidateCommand, L10nCommand, GraphCommand, CommentsCommand, L10nAction, ExportFormat};
 use ppt_rs::api::Presentation;
+use ppt_rs::generator::BuildProgress;
+use ppt_rs::export::{notes_script, VisualDiff};
 
 fn main() {
+    // Handled before parsing so that required arguments need not be given
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--help-json") {
+        let path: Vec<&str> = args.iter().take_while(|a| !a.starts_with('-')).map(String::as_str).collect();
+        match help_json(&path) {
+            Some(json) => println!("{json:#}"),
+            None => {
+                eprintln!("✗ Error: unknown command '{}'", path.join(" "));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     let cli = Cli::parse();
+    init_tracing(cli.verbose, cli.quiet);
+    // Bars would interleave with -v log lines
+    let show_progress = !cli.quiet && cli.verbose == 0;
+    let show_warnings = !cli.quiet;
+    let mut config = match CliConfig::resolve(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("✗ Error: {e}");
+            std::process::exit(1);
+        }
+    };
 
     match cli.command {
-        Commands::Create { output, title, slides, template } => {
-            match CreateCommand::execute(
+        Commands::Create { output, title, slides, template, slide_size } => {
+            if slide_size.is_some() {
+                config.slide_size = slide_size;
+            }
+            match CreateCommand::execute_configured(
                 &output,
                 title.as_deref(),
                 slides,
                 template.as_deref(),
+                &config,
             ) {
                 Ok(_) => {
                     println!("✓ Created presentation: {output}");
@@ -27,13 +58,60 @@ fn main() {
                 }
             }
         }
-        Commands::Md2Ppt { input, output, title } => {
+        Commands::Md2Ppt { input, output, more_inputs, title, out_dir, jobs, theme, slide_size, density, defines } => {
+            if theme.is_some() {
+                config.theme = theme;
+            }
+            if slide_size.is_some() {
+                config.slide_size = slide_size;
+            }
+            if density.is_some() {
+                config.density = density;
+            }
+            // pptx.toml's out_dir only turns on batch mode for several inputs
+            let batch_dir = out_dir.or_else(|| config.out_dir.clone().filter(|_| !more_inputs.is_empty()));
+            if let Some(out_dir) = batch_dir {
+                let inputs: Vec<String> = std::iter::once(input).chain(output).chain(more_inputs).collect();
+                let bar = ProgressBar::new("Files", show_progress);
+                match FromMarkdownCommand::execute_batch(&inputs, &out_dir, jobs, &config, &defines, &bar) {
+                    Ok(report) => {
+                        for (input, output) in &report.converted {
+                            println!("✓ {input} → {output}");
+                        }
+                        for (input, error) in &report.failed {
+                            eprintln!("✗ {input}: {error}");
+                        }
+                        if show_warnings {
+                            for (input, warning) in &report.warnings {
+                                eprintln!("⚠ {input}: {warning}");
+                            }
+                        }
+                        let converted = report.converted.len();
+                        let failed = report.failed.len();
+                        println!("\nConverted {converted} file(s), {failed} failed");
+                        if failed > 0 {
+                            std::process::exit(1);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("✗ Error: {e}");
+                        std::process::exit(1);
+                    }
+                }
+                return;
+            }
+            if !more_inputs.is_empty() {
+                eprintln!("✗ Error: converting several files needs --out-dir");
+                std::process::exit(1);
+            }
             // Auto-generate output if not provided
             let output_path = output.unwrap_or_else(|| {
                 use std::path::Path;
                 let input_path = Path::new(&input);
                 if let Some(stem) = input_path.file_stem() {
-                    if let Some(parent) = input_path.parent() {
+                    if let Some(dir) = &config.out_dir {
+                        format!("{}/{}.pptx", dir.trim_end_matches('/'), stem.to_string_lossy())
+                    } else if let Some(parent) = input_path.parent() {
                         if parent.as_os_str().is_empty() {
                             format!("{}.pptx", stem.to_string_lossy())
                         } else {
@@ -47,16 +125,27 @@ fn main() {
                 }
             });
             
-            match FromMarkdownCommand::execute(
+            let bar = ProgressBar::new("Slides", show_progress);
+            let result = FromMarkdownCommand::execute_with_progress(
                 &input,
                 &output_path,
                 title.as_deref(),
-            ) {
-                Ok(_) => {
+                &defines,
+                &config,
+                BuildProgress::new().on_progress(|done, total| bar.set(done, total)),
+            );
+            bar.finish();
+            match result {
+                Ok(warnings) => {
                     println!("✓ Created presentation: {output_path}");
                     println!("  Input: {input}");
                     let title = title.as_deref().unwrap_or("Presentation from Markdown");
                     println!("  Title: {title}");
+                    if show_warnings {
+                        for warning in &warnings {
+                            eprintln!("⚠ {warning}");
+                        }
+                    }
                 }
                 Err(e) => {
                     eprintln!("✗ Error: {e}");
@@ -64,17 +153,28 @@ fn main() {
                 }
             }
         }
-        Commands::Info { file } => {
-            match InfoCommand::execute(&file) {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("✗ Error: {e}");
-                    std::process::exit(1);
+        Commands::Completions { shell } => {
+            write_completions(shell, &mut std::io::stdout());
+        }
+        Commands::Info { file, pacing, wpm } => {
+            let result = InfoCommand::execute(&file).and_then(|_| {
+                if pacing {
+                    InfoCommand::execute_pacing(&file, wpm)
+                } else {
+                    Ok(())
                 }
+            });
+            if let Err(e) = result {
+                eprintln!("✗ Error: {e}");
+                std::process::exit(1);
             }
         }
-        Commands::Validate { file } => {
-            match ValidateCommand::execute(&file) {
+        Commands::Validate { file, profile } => {
+            let result = ValidateCommand::execute(&file).and_then(|_| match &profile {
+                Some(profile) => ValidateCommand::execute_profile(&file, profile),
+                None => Ok(()),
+            });
+            match result {
                 Ok(_) => {
                     println!("\n✓ Validation completed successfully");
                 }
@@ -151,6 +251,93 @@ fn main() {
                 }
             }
         }
+        Commands::Notes { input, output } => {
+            let pres = match Presentation::from_path(&input) {
+                Ok(p) => p,
+                Err(e) => {
+                    eprintln!("✗ Error loading presentation: {}", e);
+                    std::process::exit(1);
+                }
+            };
+
+            let script = notes_script(&pres);
+            match output {
+                Some(output) => match std::fs::write(&output, script) {
+                    Ok(_) => println!("✓ Notes script written: {}", output),
+                    Err(e) => {
+                        eprintln!("✗ Error writing notes script: {}", e);
+                        std::process::exit(1);
+                    }
+                },
+                None => print!("{}", script),
+            }
+        }
+        Commands::Graph { file, dot } => {
+            match GraphCommand::execute(&file, dot) {
+                Ok(graph) => print!("{}", graph),
+                Err(e) => {
+                    eprintln!("✗ Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Comments { file, json } => {
+            match CommentsCommand::execute(&file, json) {
+                Ok(comments) => print!("{}", comments),
+                Err(e) => {
+                    eprintln!("✗ Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::L10n { action: L10nAction::Extract { input, output, source_lang } } => {
+            match L10nCommand::extract(&input, &output, &source_lang) {
+                Ok(count) => println!("✓ Extracted {} strings to {}", count, output),
+                Err(e) => {
+                    eprintln!("✗ Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::L10n { action: L10nAction::Apply { input, translations, output } } => {
+            let output = output.unwrap_or_else(|| {
+                std::path::Path::new(&input).with_extension("localized.pptx").to_string_lossy().to_string()
+            });
+            match L10nCommand::apply(&input, &translations, &output) {
+                Ok(report) => {
+                    println!("✓ Localized copy written: {} ({} strings translated)", output, report.translated);
+                    if !report.missing.is_empty() {
+                        println!("  ⚠ {} strings have no translation", report.missing.len());
+                    }
+                    for id in &report.unknown {
+                        println!("  ⚠ Unknown string ID: {}", id);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("✗ Error: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::VisualDiff { old, new, out, threshold, fail_on_change } => {
+            let load = |path: &str| {
+                Presentation::from_path(path).unwrap_or_else(|e| {
+                    eprintln!("✗ Error loading {}: {}", path, e);
+                    std::process::exit(1);
+                })
+            };
+            let diff = VisualDiff::compare(&load(&old), &load(&new), threshold / 100.0);
+            print!("{}", diff);
+            if let Err(e) = std::fs::write(&out, diff.to_html()) {
+                eprintln!("✗ Error writing report: {}", e);
+                std::process::exit(1);
+            }
+            let flagged = diff.flagged().count();
+            println!("✓ Report written: {} ({} slide(s) flagged)", out, flagged);
+            if fail_on_change && flagged > 0 {
+                std::process::exit(1);
+            }
+        }
         Commands::Pdf2Ppt { input, output } => {
             let output = output.unwrap_or_else(|| {
                 let path = std::path::Path::new(&input);
@@ -174,12 +361,35 @@ fn main() {
                 }
             }
         }
-        Commands::Web2Ppt { url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout, verbose } => {
-            execute_web2ppt(url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout, verbose);
+        #[cfg(feature = "web2ppt")]
+        Commands::Web2Ppt { url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout } => {
+            execute_web2ppt(url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout, cli.verbose > 0);
         }
     }
 }
 
+/// Log to stderr at the level chosen by `-v`/`-q` (warnings by default)
+fn init_tracing(verbose: u8, quiet: bool) {
+    use tracing_subscriber::filter::LevelFilter;
+    use tracing_subscriber::fmt::format::FmtSpan;
+
+    let level = match (quiet, verbose) {
+        (true, _) => LevelFilter::ERROR,
+        (false, 0) => LevelFilter::WARN,
+        (false, 1) => LevelFilter::INFO,
+        (false, 2) => LevelFilter::DEBUG,
+        (false, _) => LevelFilter::TRACE,
+    };
+    // From -vv on, also log how long each span (parse, slide, zip write) took
+    let span_events = if verbose >= 2 { FmtSpan::CLOSE } else { FmtSpan::NONE };
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_span_events(span_events)
+        .with_target(false)
+        .with_writer(std::io::stderr)
+        .init();
+}
+
 #[cfg(feature = "web2ppt")]
 fn execute_web2ppt(
     url: String,
@@ -303,22 +513,3 @@ fn execute_web2ppt(
         }
     }
 }
-
-#[cfg(not(feature = "web2ppt"))]
-fn execute_web2ppt(
-    _url: String,
-    _output: String,
-    _title: Option<String>,
-    _max_slides: usize,
-    _max_bullets: usize,
-    _no_images: bool,
-    _no_tables: bool,
-    _no_code: bool,
-    _no_source_url: bool,
-    _timeout: u64,
-    _verbose: bool,
-) {
-    eprintln!("✗ web2ppt feature is not enabled.");
-    eprintln!("  Rebuild with: cargo build --features web2ppt");
-    std::process::exit(1);
-}
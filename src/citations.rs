@@ -0,0 +1,197 @@
+//! Footnote-style citations
+//!
+//! Bullets cite sources with `.cite(key)`; the presentation registers the
+//! sources in [`Citations`] and numbers them in registration order. Cited
+//! bullets get superscript markers, and the sources are listed either in a
+//! footnote on each slide or on a sources slide at the end of the deck:
+//!
+//! ```
+//! use ppt_rs::{BulletPoint, Presentation, SlideContent};
+//! use ppt_rs::citations::Citations;
+//!
+//! let sources = Citations::new()
+//!     .source("idc", "IDC Worldwide Cloud Forecast, 2024")
+//!     .source("survey", "Internal customer survey, n = 412");
+//!
+//! let slide = SlideContent::new("Market")
+//!     .add_bullet_point(BulletPoint::new("Cloud spend grows 19% a year").cite("idc"))
+//!     .add_bullet_point(BulletPoint::new("Most customers want SSO").cite("survey"));
+//!
+//! let pres = Presentation::with_title("Strategy").citations(sources).add_slide(slide);
+//! assert!(pres.build().is_ok()); // two slides: "Market" and "Sources"
+//! ```
+//!
+//! Citations of keys no source is registered under keep the key as their
+//! marker and are reported by
+//! [`Presentation::build_with_warnings`](crate::api::Presentation::build_with_warnings).
+
+use crate::generator::constants::{SLIDE_HEIGHT, SLIDE_WIDTH};
+use crate::generator::{BulletPoint, FormattedText, Shape, ShapeFill, ShapeType, SlideContent, Warning, WarningKind, Warnings};
+
+/// Left/right margin and bottom offset of the footnote line
+const FOOTNOTE_MARGIN: u32 = 457200;
+const FOOTNOTE_HEIGHT: u32 = 365760;
+/// Footnote and sources-slide font sizes in points
+const FOOTNOTE_SIZE: u32 = 10;
+const SOURCES_SIZE: u32 = 16;
+
+/// Where the cited sources are listed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SourcesPlacement {
+    /// A footnote on each slide, listing the sources it cites
+    Footnotes,
+    /// One slide with this title after the last slide, listing every source
+    EndSlide(String),
+}
+
+/// Sources that bullets cite, in marker order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Citations {
+    sources: Vec<(String, String)>,
+    placement: SourcesPlacement,
+}
+
+impl Default for Citations {
+    fn default() -> Self {
+        Citations { sources: Vec::new(), placement: SourcesPlacement::EndSlide("Sources".to_string()) }
+    }
+}
+
+impl Citations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `text` under `key`; it is numbered after the sources registered before it
+    pub fn source(mut self, key: &str, text: &str) -> Self {
+        match self.sources.iter_mut().find(|(k, _)| k == key) {
+            Some(source) => source.1 = text.to_string(),
+            None => self.sources.push((key.to_string(), text.to_string())),
+        }
+        self
+    }
+
+    /// List the sources a slide cites in a footnote on that slide
+    pub fn footnotes(mut self) -> Self {
+        self.placement = SourcesPlacement::Footnotes;
+        self
+    }
+
+    /// List all sources on a final slide titled `title` (the default, as "Sources")
+    pub fn sources_slide(mut self, title: &str) -> Self {
+        self.placement = SourcesPlacement::EndSlide(title.to_string());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// 1-based number of the source registered under `key`
+    pub fn number(&self, key: &str) -> Option<usize> {
+        self.sources.iter().position(|(k, _)| k == key).map(|i| i + 1)
+    }
+
+    /// Number the cited bullets' markers and add the footnotes or sources slide
+    pub fn apply(&self, slides: &mut Vec<SlideContent>) {
+        if self.is_empty() {
+            return;
+        }
+        for slide in slides.iter_mut() {
+            let mut cited = Vec::new();
+            for key in slide.bullets.iter_mut().flat_map(|b| &mut b.citations) {
+                if let Some(number) = self.number(key) {
+                    *key = number.to_string();
+                    if !cited.contains(&number) {
+                        cited.push(number);
+                    }
+                }
+            }
+            if self.placement == SourcesPlacement::Footnotes && !cited.is_empty() {
+                cited.sort_unstable();
+                slide.shapes.push(self.footnote(&cited));
+            }
+        }
+        if let SourcesPlacement::EndSlide(title) = &self.placement {
+            let mut slide = SlideContent::new(title).content_size(SOURCES_SIZE);
+            for (i, (_, text)) in self.sources.iter().enumerate() {
+                slide = slide.add_bullet_point(BulletPoint::new(&format!("[{}] {text}", i + 1)));
+            }
+            slides.push(slide);
+        }
+    }
+
+    /// Footnote line listing the sources numbered `cited`
+    fn footnote(&self, cited: &[usize]) -> Shape {
+        let text = cited.iter()
+            .map(|&n| format!("{n}. {}", self.sources[n - 1].1))
+            .collect::<Vec<_>>()
+            .join("   ");
+        let width = SLIDE_WIDTH - 2 * FOOTNOTE_MARGIN;
+        Shape::new(ShapeType::Rectangle, FOOTNOTE_MARGIN, SLIDE_HEIGHT - FOOTNOTE_MARGIN - FOOTNOTE_HEIGHT, width, FOOTNOTE_HEIGHT)
+            .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+            .with_rich_text(vec![FormattedText::new(&text).font_size(FOOTNOTE_SIZE).color("595959")])
+            .with_name("Footnotes")
+    }
+
+    /// Warnings for citation keys no source is registered under
+    ///
+    /// Slides are numbered from `first_slide`.
+    pub fn check(&self, slides: &[SlideContent], first_slide: usize) -> Warnings {
+        let mut warnings = Warnings::new();
+        for (i, slide) in slides.iter().enumerate() {
+            let mut reported: Vec<&str> = Vec::new();
+            for key in slide.bullets.iter().flat_map(|b| &b.citations) {
+                if self.number(key).is_none() && !reported.contains(&key.as_str()) {
+                    reported.push(key);
+                    warnings.push(Warning::new(Some(first_slide + i), WarningKind::UnknownCitation { key: key.clone() }));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deck() -> Vec<SlideContent> {
+        vec![
+            SlideContent::new("A")
+                .add_bullet_point(BulletPoint::new("x").cite("b").cite("a"))
+                .add_bullet_point(BulletPoint::new("y").cite("b")),
+            SlideContent::new("B").add_bullet("no sources"),
+        ]
+    }
+
+    fn sources() -> Citations {
+        Citations::new().source("a", "Alpha report").source("b", "Beta study")
+    }
+
+    #[test]
+    fn test_markers_are_numbered_by_registration() {
+        let mut slides = deck();
+        sources().apply(&mut slides);
+        assert_eq!(slides[0].bullets[0].citations, ["2", "1"]);
+        assert_eq!(slides.len(), 3);
+        let listed: Vec<_> = slides[2].bullets.iter().map(|b| b.text.as_str()).collect();
+        assert_eq!(listed, ["[1] Alpha report", "[2] Beta study"]);
+    }
+
+    #[test]
+    fn test_footnotes_list_only_cited_sources() {
+        let mut slides = deck();
+        sources().footnotes().apply(&mut slides);
+        assert_eq!(slides.len(), 2);
+        assert_eq!(slides[0].shapes[0].rich_text[0].text, "1. Alpha report   2. Beta study");
+        assert!(slides[1].shapes.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_reported() {
+        let slides = vec![SlideContent::new("A").add_bullet_point(BulletPoint::new("x").cite("c").cite("c"))];
+        let messages: Vec<String> = sources().check(&slides, 2).iter().map(ToString::to_string).collect();
+        assert_eq!(messages, ["slide 2: no source registered for citation 'c'"]);
+    }
+}
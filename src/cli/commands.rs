@@ -1,20 +1,52 @@
 //! CLI commands implementation
 
 use std::fs;
-use std::path::PathBuf;
-use crate::generator;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::prelude::themes::{self, Theme};
+use super::config::CliConfig;
+use super::progress_bar::ProgressBar;
+use crate::generator::{BuildProgress, SlideContent, SlideLayout, Warning, Warnings};
+use crate::instrument::event;
 
 pub struct CreateCommand;
 pub struct FromMarkdownCommand;
 pub struct InfoCommand;
 pub struct ValidateCommand;
+pub struct L10nCommand;
+pub struct GraphCommand;
+pub struct CommentsCommand;
+
+/// Outcome of a batch markdown conversion
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// (input, output) pairs that were converted
+    pub converted: Vec<(String, String)>,
+    /// (input, error) pairs that failed
+    pub failed: Vec<(String, String)>,
+    /// (input, warning) pairs raised by converted files
+    pub warnings: Vec<(String, Warning)>,
+}
 
 impl CreateCommand {
     pub fn execute(
+        output: &str,
+        title: Option<&str>,
+        slides: usize,
+        template: Option<&str>,
+    ) -> Result<(), String> {
+        Self::execute_configured(output, title, slides, template, &CliConfig::default())
+    }
+
+    /// Create blank slides with the theme, slide size, fonts, footer and IDs of `config`
+    ///
+    /// The first slide carries `title`; the others are titled "Slide N".
+    pub fn execute_configured(
         output: &str,
         title: Option<&str>,
         slides: usize,
         _template: Option<&str>,
+        config: &CliConfig,
     ) -> Result<(), String> {
         // Create output directory if needed
         if let Some(parent) = PathBuf::from(output).parent() {
@@ -27,7 +59,13 @@ impl CreateCommand {
         let title = title.unwrap_or("Presentation");
 
         // Generate proper PPTX file
-        let pptx_data = generator::create_pptx(title, slides)
+        let pptx_data = (1..=slides)
+            .map(|n| {
+                let slide_title = if n == 1 { title.to_string() } else { format!("Slide {n}") };
+                SlideContent::new(&slide_title).layout(SlideLayout::TitleOnly)
+            })
+            .fold(config.presentation(title)?, |pres, slide| pres.add_slide(slide))
+            .build()
             .map_err(|e| format!("Failed to generate PPTX: {e}"))?;
 
         // Write to file
@@ -44,12 +82,47 @@ impl FromMarkdownCommand {
         output: &str,
         title: Option<&str>,
     ) -> Result<(), String> {
+        Self::execute_with_defines(input, output, title, &[])
+    }
+
+    /// Convert with `--define` variables for `{{var}}` and `<!-- if -->` blocks
+    pub fn execute_with_defines(
+        input: &str,
+        output: &str,
+        title: Option<&str>,
+        defines: &[super::markdown::Define],
+    ) -> Result<(), String> {
+        Self::execute_configured(input, output, title, defines, &CliConfig::default()).map(|_| ())
+    }
+
+    /// Convert with the theme, slide size, fonts, footer and Mermaid colors of `config`
+    ///
+    /// Returns the non-fatal warnings raised while parsing and generating.
+    pub fn execute_configured(
+        input: &str,
+        output: &str,
+        title: Option<&str>,
+        defines: &[super::markdown::Define],
+        config: &CliConfig,
+    ) -> Result<Warnings, String> {
+        Self::execute_with_progress(input, output, title, defines, config, BuildProgress::new())
+    }
+
+    /// [`Self::execute_configured`], reporting each written slide to `progress`
+    pub fn execute_with_progress(
+        input: &str,
+        output: &str,
+        title: Option<&str>,
+        defines: &[super::markdown::Define],
+        config: &CliConfig,
+        progress: BuildProgress,
+    ) -> Result<Warnings, String> {
         // Read markdown file
         let md_content = fs::read_to_string(input)
             .map_err(|e| format!("Failed to read markdown file: {e}"))?;
 
         // Parse markdown into slides using enhanced parser
-        let slides = super::markdown::parse_markdown(&md_content)?;
+        let (slides, mut warnings) = super::markdown::parse_markdown_with_warnings(&md_content, defines, &config.mermaid)?;
 
         if slides.is_empty() {
             return Err("No slides found in markdown file".to_string());
@@ -64,17 +137,129 @@ impl FromMarkdownCommand {
         }
 
         let title = title.unwrap_or("Presentation from Markdown");
+        let density = config.density()?;
 
         // Generate PPTX with content
-        let pptx_data = generator::create_pptx_with_content(title, slides)
+        let generated = slides.into_iter()
+            .map(|slide| match density {
+                Some(density) => slide.density(density),
+                None => slide,
+            })
+            .fold(config.presentation(title)?, |pres, slide| pres.add_slide(slide))
+            .build_with_warnings(progress)
             .map_err(|e| format!("Failed to generate PPTX: {e}"))?;
+        warnings.extend(generated.warnings);
 
         // Write to file
-        fs::write(output, pptx_data)
+        fs::write(output, generated.data)
             .map_err(|e| format!("Failed to write file: {e}"))?;
 
-        Ok(())
+        Ok(warnings)
+    }
+
+    /// Convert many markdown files into `out_dir`, `jobs` at a time
+    ///
+    /// Directories in `inputs` contribute their `*.md` files. Each deck is
+    /// written as `out_dir/<stem>.pptx` and titled after its file stem;
+    /// a failing file is recorded in the report and the rest carry on.
+    /// `progress` advances as files finish.
+    pub fn execute_batch(
+        inputs: &[String],
+        out_dir: &str,
+        jobs: usize,
+        config: &CliConfig,
+        defines: &[super::markdown::Define],
+        progress: &ProgressBar,
+    ) -> Result<BatchReport, String> {
+        // Fail on a bad theme, size or density once, not for every file
+        config.theme()?;
+        config.slide_size()?;
+        config.density()?;
+        let files = expand_markdown_inputs(inputs)?;
+        if files.is_empty() {
+            return Err("No markdown files found".to_string());
+        }
+        fs::create_dir_all(out_dir).map_err(|e| format!("Failed to create directory: {e}"))?;
+
+        let mut report = BatchReport::default();
+        let mut planned: Vec<(String, String)> = Vec::new();
+        for input in files {
+            let stem = Path::new(&input).file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+            let output = Path::new(out_dir).join(format!("{stem}.pptx")).to_string_lossy().into_owned();
+            if let Some((other, _)) = planned.iter().find(|(_, o)| *o == output) {
+                report.failed.push((input, format!("Output {output} is already written for {other}")));
+            } else {
+                planned.push((input, output));
+            }
+        }
+
+        let total = planned.len();
+        progress.set(0, total);
+        let queue = Mutex::new(planned.into_iter());
+        let results = Mutex::new(Vec::new());
+        std::thread::scope(|scope| {
+            for _ in 0..jobs.max(1) {
+                scope.spawn(|| loop {
+                    let Some((input, output)) = queue.lock().unwrap().next() else { break };
+                    let stem = Path::new(&input).file_stem().map(|s| s.to_string_lossy().into_owned());
+                    let result = Self::execute_configured(&input, &output, stem.as_deref(), defines, config);
+                    match &result {
+                        Ok(_) => event!(INFO, input = %input, output = %output, "converted"),
+                        Err(e) => event!(WARN, input = %input, error = %e, "conversion failed"),
+                    }
+                    let mut results = results.lock().unwrap();
+                    results.push((input, output, result));
+                    progress.set(results.len(), total);
+                });
+            }
+        });
+
+        progress.finish();
+        let mut results = results.into_inner().unwrap();
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+        for (input, output, result) in results {
+            match result {
+                Ok(warnings) => {
+                    report.warnings.extend(warnings.into_iter().map(|w| (input.clone(), w)));
+                    report.converted.push((input, output));
+                }
+                Err(e) => report.failed.push((input, e)),
+            }
+        }
+        report.failed.sort();
+        Ok(report)
+    }
+}
+
+/// Built-in theme by name (case-insensitive)
+pub fn theme_by_name(name: &str) -> Result<Theme, String> {
+    themes::all().into_iter()
+        .find(|theme| theme.name.eq_ignore_ascii_case(name))
+        .ok_or_else(|| {
+            let names: Vec<_> = themes::all().iter().map(|t| t.name.to_lowercase()).collect();
+            format!("Unknown theme '{name}' (available: {})", names.join(", "))
+        })
+}
+
+/// Input files, with directories replaced by the `*.md` files they contain
+fn expand_markdown_inputs(inputs: &[String]) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut found: Vec<String> = fs::read_dir(path)
+                .map_err(|e| format!("Failed to read directory {input}: {e}"))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("md")))
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(input.clone());
+        }
     }
+    Ok(files)
 }
 
 
@@ -122,9 +307,39 @@ impl InfoCommand {
 
         Ok(())
     }
+
+    /// Print the estimated duration and per-section pacing of a PPTX file
+    pub fn execute_pacing(file: &str, words_per_minute: u32) -> Result<(), String> {
+        let pres = crate::api::Presentation::from_path(file)
+            .map_err(|e| format!("Failed to read presentation: {e}"))?;
+        let config = crate::analysis::PacingConfig::new().words_per_minute(words_per_minute);
+        let report = crate::analysis::analyze(&pres, &config);
+
+        println!();
+        print!("{report}");
+        Ok(())
+    }
 }
 
 impl ValidateCommand {
+    /// Lint a PPTX file against a JSON style profile
+    pub fn execute_profile(file: &str, profile: &str) -> Result<(), String> {
+        let profile = crate::style_guide::StyleProfile::load(profile)
+            .map_err(|e| format!("Failed to load style profile: {e}"))?;
+        let violations = profile.lint_file(file)
+            .map_err(|e| format!("Failed to read presentation: {e}"))?;
+
+        println!("\nChecking style profile...");
+        if violations.is_empty() {
+            println!("  ✓ No style violations");
+            return Ok(());
+        }
+        for violation in &violations {
+            println!("  ✗ {violation}");
+        }
+        Err(format!("Style check failed with {} violation(s)", violations.len()))
+    }
+
     /// Validate a PPTX file for ECMA-376 compliance
     pub fn execute(file: &str) -> Result<(), String> {
         use std::io::Read;
@@ -242,18 +457,10 @@ impl ValidateCommand {
     }
 }
 
-#[allow(dead_code)]
-fn escape_xml(s: &str) -> String {
-    s.replace("&", "&amp;")
-        .replace("<", "&lt;")
-        .replace(">", "&gt;")
-        .replace("\"", "&quot;")
-        .replace("'", "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::escape_xml;
     use std::fs;
     use std::path::Path;
 
@@ -268,6 +475,55 @@ mod tests {
         let _ = fs::remove_file(output);
     }
 
+    #[test]
+    fn test_create_command_applies_config() {
+        let output = std::env::temp_dir().join("pptx_create_configured.pptx");
+        let output = output.to_string_lossy();
+        let config = CliConfig {
+            theme: Some("corporate".into()),
+            footer: Some("ACME Confidential".into()),
+            stable_ids: true,
+            ..CliConfig::default()
+        };
+        CreateCommand::execute_configured(&output, Some("Kickoff"), 2, None, &config).unwrap();
+
+        let package = crate::opc::Package::open(&*output).unwrap();
+        let slide = package.get_part_string("ppt/slides/slide1.xml").unwrap();
+        assert!(slide.contains("Kickoff"));
+        assert!(slide.contains("ACME Confidential"));
+        assert!(slide.contains(themes::CORPORATE.primary));
+        assert!(package.get_part_string("ppt/slides/slide2.xml").unwrap().contains("Slide 2"));
+        let _ = fs::remove_file(&*output);
+    }
+
+    #[test]
+    fn test_batch_continues_on_error() {
+        let dir = std::env::temp_dir().join("pptx_batch_test");
+        let out_dir = dir.join("decks");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(dir.join(format!("{name}.md")), format!("# Deck {name}\n- point")).unwrap();
+        }
+        fs::write(dir.join("c.md"), "# Deck c\n![Logo](logo.png)").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        let missing = dir.join("missing.md").to_string_lossy().into_owned();
+
+        let inputs = [dir.to_string_lossy().into_owned(), missing.clone()];
+        let config = CliConfig { theme: Some("corporate".into()), ..CliConfig::default() };
+        let report = FromMarkdownCommand::execute_batch(&inputs, &out_dir.to_string_lossy(), 2, &config, &[], &ProgressBar::hidden()).unwrap();
+
+        assert_eq!(report.converted.len(), 3);
+        assert!(out_dir.join("b.pptx").exists());
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, missing);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].0.ends_with("c.md"));
+        assert_eq!(report.warnings[0].1.slide, Some(1));
+        assert!(theme_by_name("sepia").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
     #[test]
     fn test_escape_xml() {
         assert_eq!(escape_xml("a & b"), "a &amp; b");
@@ -275,3 +531,85 @@ mod tests {
         assert_eq!(escape_xml("\"quoted\""), "&quot;quoted&quot;");
     }
 }
+
+impl GraphCommand {
+    /// The deck's part relationships as an indented listing or DOT source
+    pub fn execute(file: &str, dot: bool) -> Result<String, String> {
+        let package = crate::opc::Package::open(file)
+            .map_err(|e| format!("Failed to open presentation: {e}"))?;
+        let graph = package.relationship_graph();
+        Ok(if dot { graph.to_dot() } else { graph.to_string() })
+    }
+}
+
+impl CommentsCommand {
+    /// The deck's comment threads, one per line with indented replies, or
+    /// as a JSON array
+    pub fn execute(file: &str, json: bool) -> Result<String, String> {
+        let reader = crate::oxml::PresentationReader::open(file)
+            .map_err(|e| format!("Failed to open presentation: {e}"))?;
+        let comments = reader.comments();
+        if json {
+            return serde_json::to_string_pretty(&comments)
+                .map(|json| json + "\n")
+                .map_err(|e| format!("Failed to write JSON: {e}"));
+        }
+        let mut out = String::new();
+        for comment in &comments {
+            let shape = comment.shape.as_ref().map(|s| format!(" on \"{s}\"")).unwrap_or_default();
+            let status = match comment.status {
+                crate::oxml::CommentStatus::Active => String::new(),
+                status => format!(" [{status}]"),
+            };
+            out.push_str(&format!(
+                "Slide {}{shape}{status}: {} ({}): {}\n",
+                comment.slide,
+                comment.author,
+                comment.created.as_deref().unwrap_or("no date"),
+                comment.text.replace('\n', " / ")
+            ));
+            for reply in &comment.replies {
+                out.push_str(&format!("  ↳ {}: {}\n", reply.author, reply.text.replace('\n', " / ")));
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl L10nCommand {
+    /// Write the deck's translatable strings to XLIFF or JSON (by extension)
+    pub fn extract(input: &str, output: &str, source_lang: &str) -> Result<usize, String> {
+        let package = crate::opc::Package::open(input)
+            .map_err(|e| format!("Failed to open presentation: {e}"))?;
+        let units = crate::l10n::extract(&package);
+        let content = if output.ends_with(".json") {
+            crate::l10n::to_json(&units)
+        } else {
+            let original = PathBuf::from(input).file_name().map_or_else(|| input.to_string(), |n| n.to_string_lossy().to_string());
+            crate::l10n::to_xliff(&units, &original, source_lang)
+        };
+        fs::write(output, content)
+            .map_err(|e| format!("Failed to write strings: {e}"))?;
+        Ok(units.len())
+    }
+
+    /// Write a copy of the deck with translated run text
+    pub fn apply(input: &str, translations: &str, output: &str) -> Result<crate::l10n::ApplyReport, String> {
+        let text = fs::read_to_string(translations)
+            .map_err(|e| format!("Failed to read translations: {e}"))?;
+        let units = if translations.ends_with(".json") {
+            crate::l10n::parse_json(&text)
+        } else {
+            crate::l10n::parse_xliff(&text)
+        }
+        .map_err(|e| format!("Failed to parse translations: {e}"))?;
+
+        let mut package = crate::opc::Package::open(input)
+            .map_err(|e| format!("Failed to open presentation: {e}"))?;
+        let report = crate::l10n::apply(&mut package, &units)
+            .map_err(|e| format!("Failed to apply translations: {e}"))?;
+        package.save(output)
+            .map_err(|e| format!("Failed to write presentation: {e}"))?;
+        Ok(report)
+    }
+}
@@ -0,0 +1,206 @@
+//! Project configuration (`pptx.toml`)
+//!
+//! A `pptx.toml` in the working directory (or any parent) holds the defaults
+//! a project would otherwise repeat on every command line:
+//!
+//! ```toml
+//! theme = "corporate"
+//! slide_size = "16:9"
+//! density = "compact"
+//! footer = "ACME Corp — Confidential"
+//! out_dir = "decks"
+//! stable_ids = true
+//!
+//! [fonts]
+//! heading = "Georgia"
+//! body = "Arial"
+//!
+//! [mermaid]
+//! fill = "E3F2FD"
+//! line_color = "1565C0"
+//! ```
+//!
+//! Every key is optional, and command-line flags override the file.
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::api::Presentation;
+use crate::generator::{Density, SlideSize, ThemeFonts};
+use crate::prelude::themes::Theme;
+use crate::style_guide::{Enforcement, StyleProfile};
+use super::markdown::MermaidStyle;
+
+/// File name looked up by [`CliConfig::discover`]
+pub const CONFIG_FILE: &str = "pptx.toml";
+
+/// CLI defaults read from `pptx.toml`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    /// Built-in theme name
+    pub theme: Option<String>,
+    /// `4:3`, `16:9` or `<width>x<height>` in inches
+    pub slide_size: Option<String>,
+    /// `compact`, `cozy` or `comfortable` text on every slide
+    pub density: Option<String>,
+    /// Text added to the bottom of every slide
+    pub footer: Option<String>,
+    /// Output directory for converted decks
+    pub out_dir: Option<String>,
+    /// Derive slide and shape IDs from content so they survive regeneration
+    pub stable_ids: bool,
+    /// Theme heading and body fonts
+    pub fonts: FontsConfig,
+    /// Mermaid diagram colors
+    pub mermaid: MermaidStyle,
+}
+
+/// `[fonts]` table of `pptx.toml`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FontsConfig {
+    pub heading: Option<String>,
+    pub body: Option<String>,
+}
+
+impl CliConfig {
+    /// Parse a configuration from TOML
+    pub fn from_toml(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|e| format!("Invalid configuration: {e}"))
+    }
+
+    /// Read a configuration file
+    ///
+    /// A relative `out_dir` is taken relative to the file, so the same
+    /// decks directory is used from anywhere in the project.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let mut config = Self::from_toml(&content).map_err(|e| format!("{}: {e}", path.display()))?;
+        if let (Some(dir), Some(base)) = (&config.out_dir, path.parent())
+            && Path::new(dir).is_relative()
+            && !base.as_os_str().is_empty()
+        {
+            config.out_dir = Some(base.join(dir).to_string_lossy().into_owned());
+        }
+        Ok(config)
+    }
+
+    /// Find `pptx.toml` in the current directory or its parents
+    pub fn discover() -> Option<PathBuf> {
+        let cwd = std::env::current_dir().ok()?;
+        cwd.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Configuration from `explicit`, else the discovered `pptx.toml`, else defaults
+    pub fn resolve(explicit: Option<&str>) -> Result<Self, String> {
+        match explicit.map(PathBuf::from).or_else(Self::discover) {
+            Some(path) => Self::load(path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// The configured theme, if any
+    pub fn theme(&self) -> Result<Option<Theme>, String> {
+        self.theme.as_deref().map(super::commands::theme_by_name).transpose()
+    }
+
+    /// The configured slide size, if any
+    pub fn slide_size(&self) -> Result<Option<SlideSize>, String> {
+        self.slide_size.as_deref().map(parse_slide_size).transpose()
+    }
+
+    /// The configured text density, if any
+    pub fn density(&self) -> Result<Option<Density>, String> {
+        self.density.as_deref().map(str::parse).transpose()
+    }
+
+    /// Theme fonts, when either font is configured (the other stays Calibri)
+    pub fn theme_fonts(&self) -> Option<ThemeFonts> {
+        if self.fonts.heading.is_none() && self.fonts.body.is_none() {
+            return None;
+        }
+        let default = ThemeFonts::default();
+        Some(ThemeFonts {
+            heading: self.fonts.heading.clone().unwrap_or(default.heading),
+            body: self.fonts.body.clone().unwrap_or(default.body),
+        })
+    }
+
+    /// An empty presentation with the theme, slide size, fonts, footer and
+    /// ID scheme applied
+    pub fn presentation(&self, title: &str) -> Result<Presentation, String> {
+        let mut pres = Presentation::with_title(title).stable_ids(self.stable_ids);
+        if let Some(theme) = self.theme()? {
+            pres = pres.theme(&theme);
+        }
+        if let Some(size) = self.slide_size()? {
+            pres = pres.slide_size(size);
+        }
+        if let Some(fonts) = self.theme_fonts() {
+            pres = pres.fonts(fonts);
+        }
+        if let Some(footer) = &self.footer {
+            pres = pres.style_profile(StyleProfile::new().footer(footer), Enforcement::AutoCorrect);
+        }
+        Ok(pres)
+    }
+}
+
+/// Parse `4:3`, `16:9` or `<width>x<height>` (inches, e.g. `11x8.5`)
+pub fn parse_slide_size(s: &str) -> Result<SlideSize, String> {
+    match s.trim().to_lowercase().as_str() {
+        "4:3" | "standard" => Ok(SlideSize::STANDARD),
+        "16:9" | "widescreen" => Ok(SlideSize::WIDESCREEN),
+        other => {
+            let inches = other.strip_suffix("in").unwrap_or(other);
+            let (w, h) = inches.split_once('x')
+                .and_then(|(w, h)| Some((w.trim().parse::<f64>().ok()?, h.trim().parse::<f64>().ok()?)))
+                .filter(|(w, h)| *w > 0.0 && *h > 0.0)
+                .ok_or_else(|| format!("Invalid slide size '{s}' (use 4:3, 16:9 or WIDTHxHEIGHT in inches)"))?;
+            Ok(SlideSize::new(crate::generator::inches_to_emu(w), crate::generator::inches_to_emu(h)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = CliConfig::from_toml(r#"
+theme = "dark"
+slide_size = "16:9"
+density = "compact"
+footer = "Confidential"
+stable_ids = true
+
+[fonts]
+body = "Arial"
+
+[mermaid]
+line_color = "333333"
+"#).unwrap();
+
+        assert_eq!(config.theme().unwrap().unwrap().name, "Dark");
+        assert_eq!(config.slide_size().unwrap(), Some(SlideSize::WIDESCREEN));
+        assert_eq!(config.density().unwrap(), Some(Density::Compact));
+        assert!(config.stable_ids);
+        assert_eq!(config.theme_fonts(), Some(ThemeFonts::new("Calibri", "Arial")));
+        assert_eq!(config.mermaid.line_color.as_deref(), Some("333333"));
+        assert!(CliConfig::from_toml("colour = \"red\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_slide_size() {
+        assert_eq!(parse_slide_size("4:3").unwrap(), SlideSize::STANDARD);
+        assert_eq!(parse_slide_size("10x7.5in").unwrap(), SlideSize::STANDARD);
+        assert!(parse_slide_size("wide").is_err());
+    }
+}
@@ -0,0 +1,101 @@
+//! Machine-readable description of the command-line interface
+//!
+//! Both outputs are generated from the clap definitions in
+//! [`super::parser`], so they never drift from what `pptcli` accepts:
+//! [`help_json`] describes commands and flags for wrapper tools and GUIs,
+//! and [`write_completions`] prints a shell completion script.
+
+use std::io::Write;
+
+use clap::{Arg, ArgAction, CommandFactory};
+use clap_complete::Shell;
+use serde_json::{json, Value};
+
+use super::parser::Cli;
+
+/// Name of the binary used in completion scripts
+const BIN_NAME: &str = "pptcli";
+
+/// JSON description of `pptcli`, or of the subcommand at `path`
+///
+/// `path` names nested subcommands (aliases work too), e.g. `["l10n", "extract"]`;
+/// `None` if there is no such subcommand.
+///
+/// ```
+/// let json = ppt_rs::cli::help_json(&["md2ppt"]).unwrap();
+/// assert_eq!(json["name"], "md2ppt");
+/// assert!(json["args"].as_array().unwrap().iter().any(|a| a["long"] == "out-dir"));
+/// ```
+pub fn help_json(path: &[&str]) -> Option<Value> {
+    let mut cmd = Cli::command();
+    for name in path {
+        cmd = cmd.find_subcommand(name)?.clone();
+    }
+    Some(command_json(&cmd))
+}
+
+/// Write the completion script for `shell` to `out`
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, out);
+}
+
+fn command_json(cmd: &clap::Command) -> Value {
+    json!({
+        "name": cmd.get_name(),
+        "version": cmd.get_version(),
+        "about": cmd.get_about().map(|s| s.to_string()),
+        "aliases": cmd.get_all_aliases().collect::<Vec<_>>(),
+        "args": cmd.get_arguments().filter(|a| !a.is_hide_set()).map(arg_json).collect::<Vec<_>>(),
+        "subcommands": cmd.get_subcommands().map(command_json).collect::<Vec<_>>(),
+    })
+}
+
+fn arg_json(arg: &Arg) -> Value {
+    let takes_value = matches!(arg.get_action(), ArgAction::Set | ArgAction::Append);
+    json!({
+        "id": arg.get_id().as_str(),
+        "long": arg.get_long(),
+        "short": arg.get_short().map(String::from),
+        "positional": arg.is_positional(),
+        "required": arg.is_required_set(),
+        "global": arg.is_global_set(),
+        "takes_value": takes_value,
+        "multiple": matches!(arg.get_action(), ArgAction::Append | ArgAction::Count)
+            || arg.get_num_args().is_some_and(|n| n.max_values() > 1),
+        "value_names": arg.get_value_names().map(|names| names.iter().map(|n| n.as_str()).collect::<Vec<_>>()),
+        "default": arg.get_default_values().iter().map(|v| v.to_string_lossy()).collect::<Vec<_>>(),
+        "possible_values": arg.get_possible_values().iter().map(|v| v.get_name()).collect::<Vec<_>>(),
+        "help": arg.get_help().map(|s| s.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_help_json_describes_commands() {
+        let json = help_json(&[]).unwrap();
+        assert_eq!(json["name"], "pptcli");
+        let commands: Vec<_> = json["subcommands"].as_array().unwrap().iter().map(|c| c["name"].clone()).collect();
+        assert!(commands.contains(&json!("md2ppt")));
+        assert!(json["args"].as_array().unwrap().iter().any(|a| a["long"] == "config" && a["global"] == true));
+
+        let export = help_json(&["export"]).unwrap();
+        let format = export["args"].as_array().unwrap().iter().find(|a| a["id"] == "format").unwrap();
+        assert_eq!(format["possible_values"], json!(["pdf", "html", "png"]));
+
+        assert_eq!(help_json(&["from-md"]).unwrap()["name"], "md2ppt");
+        assert_eq!(help_json(&["l10n", "extract"]).unwrap()["name"], "extract");
+        assert!(help_json(&["nope"]).is_none());
+    }
+
+    #[test]
+    fn test_completions() {
+        let mut script = Vec::new();
+        write_completions(Shell::Bash, &mut script);
+        let script = String::from_utf8(script).unwrap();
+        assert!(script.contains("pptcli"));
+        assert!(script.contains("md2ppt"));
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 use crate::generator::{Shape, ShapeType, ShapeFill, ShapeLine};
+use crate::generator::routing::route_connectors;
 use crate::generator::connectors::{Connector, ConnectorType, ConnectorLine, ArrowType, LineDash, ConnectionSite};
 use super::types::{*, DiagramBounds};
 
@@ -374,6 +375,10 @@ pub fn generate_elements(flowchart: &Flowchart) -> DiagramElements {
         }
     }
     
+    // Keep edges from running through the nodes between their ends
+    route_connectors(&shapes, &mut connectors);
+    element_bounds.extend(connectors.iter().flat_map(|c| c.waypoints.iter().map(|&(x, y)| (x.to_u32_saturating(), y.to_u32_saturating(), 0, 0))));
+
     // Calculate bounding box for the entire diagram
     let bounds = DiagramBounds::from_elements(&element_bounds);
     
@@ -416,6 +421,7 @@ fn create_node_shape(node: &FlowNode, x: u32, y: u32, width: u32, height: u32, s
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::Emu;
 
     #[test]
     fn test_parse_flowchart_nodes() {
@@ -449,4 +455,15 @@ mod tests {
         let elements = generate_elements(&flowchart);
         assert!(!elements.shapes.is_empty());
     }
+
+    #[test]
+    fn test_skipping_edge_routes_around_nodes() {
+        let flowchart = parse("flowchart TD\n    A --> B\n    B --> C\n    A --> C");
+        let elements = generate_elements(&flowchart);
+        // The three nodes are stacked, so only the edge between the outer two bends
+        let routed: Vec<&Connector> = elements.connectors.iter().filter(|c| !c.waypoints.is_empty()).collect();
+        assert_eq!(routed.len(), 1);
+        let bounds = elements.bounds.unwrap();
+        assert!(routed[0].waypoints.iter().all(|&(x, _)| x >= Emu::from(bounds.x) && x <= Emu::from(bounds.x + bounds.width)));
+    }
 }
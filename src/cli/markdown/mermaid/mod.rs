@@ -32,7 +32,7 @@ mod gitgraph;
 
 pub use types::*;
 
-use crate::generator::{Shape, ShapeType, ShapeFill, ShapeLine};
+use crate::generator::{Shape, ShapeType, ShapeFill, ShapeLine, CancelToken, BuildCancelled};
 
 /// Detect the type of Mermaid diagram from code
 pub fn detect_type(code: &str) -> MermaidType {
@@ -123,6 +123,60 @@ pub fn create_diagram_elements(code: &str) -> DiagramElements {
     }
 }
 
+/// Create diagram elements unless `cancel` has fired
+///
+/// Flowcharts, the diagrams that grow large, are checked again between
+/// parsing and layout.
+pub fn create_diagram_elements_checked(code: &str, cancel: &CancelToken) -> Result<DiagramElements, BuildCancelled> {
+    cancel.check()?;
+    if detect_type(code) == MermaidType::Flowchart {
+        let fc = flowchart::parse(code);
+        cancel.check()?;
+        return Ok(flowchart::generate_elements(&fc));
+    }
+    Ok(create_diagram_elements(code))
+}
+
+/// Colors laid over the built-in per-diagram palette
+///
+/// `fill` recolors the nodes of flowcharts, class, state and ER diagrams
+/// (charts such as pies keep their series colors); `line_color` recolors
+/// every outline and connector.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MermaidStyle {
+    /// Node fill (hex RGB)
+    pub fill: Option<String>,
+    /// Outline and connector color (hex RGB)
+    pub line_color: Option<String>,
+}
+
+impl MermaidStyle {
+    /// Recolor the elements of a `diagram_type` diagram
+    pub fn apply(&self, diagram_type: MermaidType, elements: &mut DiagramElements) {
+        let node_diagram = matches!(
+            diagram_type,
+            MermaidType::Flowchart | MermaidType::ClassDiagram | MermaidType::StateDiagram | MermaidType::ErDiagram
+        );
+        for shape in &mut elements.shapes {
+            if let (Some(fill), Some(color)) = (&mut shape.fill, &self.fill)
+                && node_diagram
+                && shape.gradient.is_none()
+            {
+                fill.color = color.clone();
+            }
+            if let (Some(line), Some(color)) = (&mut shape.line, &self.line_color) {
+                line.color = color.clone();
+            }
+        }
+        if let Some(color) = &self.line_color {
+            for connector in &mut elements.connectors {
+                connector.line.color = color.clone();
+            }
+        }
+    }
+}
+
 /// Get diagram style info (for backward compatibility)
 pub fn get_diagram_style(diagram_type: MermaidType) -> (&'static str, &'static str, &'static str, &'static str) {
     match diagram_type {
@@ -146,6 +200,22 @@ pub fn get_diagram_style(diagram_type: MermaidType) -> (&'static str, &'static s
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_style_recolors_nodes_and_lines() {
+        let code = "flowchart LR\n    A --> B";
+        let mut elements = create_diagram_elements(code);
+        let style = MermaidStyle { fill: Some("FFEEDD".into()), line_color: Some("112233".into()) };
+        style.apply(detect_type(code), &mut elements);
+
+        assert!(elements.shapes.iter().filter_map(|s| s.fill.as_ref()).all(|f| f.color == "FFEEDD"));
+        assert!(elements.connectors.iter().all(|c| c.line.color == "112233"));
+
+        let pie = "pie\n    \"A\" : 1\n    \"B\" : 2";
+        let mut elements = create_diagram_elements(pie);
+        style.apply(detect_type(pie), &mut elements);
+        assert!(elements.shapes.iter().filter_map(|s| s.fill.as_ref()).any(|f| f.color != "FFEEDD"));
+    }
+
     #[test]
     fn test_detect_flowchart() {
         assert_eq!(detect_type("flowchart LR"), MermaidType::Flowchart);
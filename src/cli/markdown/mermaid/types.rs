@@ -137,7 +137,7 @@ impl DiagramElements {
     pub fn from_shapes(shapes: Vec<Shape>) -> Self {
         let element_bounds: Vec<(u32, u32, u32, u32)> = shapes
             .iter()
-            .map(|s| (s.x, s.y, s.width, s.height))
+            .map(|s| (s.x.to_u32_saturating(), s.y.to_u32_saturating(), s.width.to_u32_saturating(), s.height.to_u32_saturating()))
             .collect();
         let bounds = DiagramBounds::from_elements(&element_bounds);
         
@@ -153,7 +153,7 @@ impl DiagramElements {
     pub fn from_shapes_and_connectors(shapes: Vec<Shape>, connectors: Vec<Connector>) -> Self {
         let element_bounds: Vec<(u32, u32, u32, u32)> = shapes
             .iter()
-            .map(|s| (s.x, s.y, s.width, s.height))
+            .map(|s| (s.x.to_u32_saturating(), s.y.to_u32_saturating(), s.width.to_u32_saturating(), s.height.to_u32_saturating()))
             .collect();
         let bounds = DiagramBounds::from_elements(&element_bounds);
         
@@ -15,14 +15,52 @@
 //! - **Images**: Placeholder shapes for images
 //! - **Horizontal rules**: Create slide breaks
 //! - **Speaker notes**: Blockquotes become speaker notes
+//! - **Variables and conditionals**: `{{var}}` and `<!-- if audience == "exec" -->`
+//!   blocks, resolved by [`parse_markdown_with_defines`]
 
 mod mermaid;
 mod parser;
+mod preprocess;
 
-pub use mermaid::MermaidType;
+pub use mermaid::{MermaidType, MermaidStyle};
 pub use parser::parse;
+pub use preprocess::{preprocess, parse_define, Define};
 
 /// Parse markdown content into slides (convenience re-export)
 pub fn parse_markdown(content: &str) -> Result<Vec<crate::generator::SlideContent>, String> {
     parser::parse(content)
 }
+
+/// Parse markdown content into slides after expanding `{{var}}` placeholders
+/// and `<!-- if -->` blocks with `defines`
+pub fn parse_markdown_with_defines(content: &str, defines: &[Define]) -> Result<Vec<crate::generator::SlideContent>, String> {
+    parser::parse(&preprocess(content, defines)?)
+}
+
+/// [`parse_markdown_with_defines`], recoloring Mermaid diagrams with `style`
+pub fn parse_markdown_styled(
+    content: &str,
+    defines: &[Define],
+    style: &MermaidStyle,
+) -> Result<Vec<crate::generator::SlideContent>, String> {
+    parser::parse_styled(&preprocess(content, defines)?, style)
+}
+
+/// [`parse_markdown_styled`], also returning warnings for images drawn as
+/// placeholders and markdown that was skipped or simplified
+pub fn parse_markdown_with_warnings(
+    content: &str,
+    defines: &[Define],
+    style: &MermaidStyle,
+) -> Result<(Vec<crate::generator::SlideContent>, crate::generator::Warnings), String> {
+    parser::parse_with_warnings(&preprocess(content, defines)?, style)
+}
+
+/// Parse markdown content into slides, aborting once `cancel` is cancelled
+/// or its deadline passes (`PptxError::Cancelled` / `PptxError::DeadlineExceeded`)
+pub fn parse_markdown_with_cancel(
+    content: &str,
+    cancel: &crate::generator::CancelToken,
+) -> crate::exc::Result<Vec<crate::generator::SlideContent>> {
+    parser::parse_with_cancel(content, cancel)
+}
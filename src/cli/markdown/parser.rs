@@ -3,15 +3,48 @@
 //! Handles parsing of markdown content into slide structures.
 
 use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag, TagEnd};
-use crate::generator::{SlideContent, TableBuilder, TableRow, TableCell, Shape, ShapeType, ShapeFill, CodeBlock};
+use crate::generator::{SlideContent, TableBuilder, TableRow, TableCell, Shape, ShapeType, ShapeFill, CodeBlock, CancelToken, BuildCancelled, Warning, WarningKind, Warnings};
+use crate::core::Emu;
+use crate::exc::PptxError;
+use crate::instrument::event;
 use super::mermaid;
 
+const NO_SLIDES: &str = "No slides found in markdown file";
+
 /// Parse markdown content into slides
 pub fn parse(content: &str) -> Result<Vec<SlideContent>, String> {
-    let mut parser = MarkdownParser::new();
+    parse_with_cancel(content, &CancelToken::new()).map_err(|e| match e {
+        PptxError::InvalidValue(msg) => msg,
+        e => e.to_string(),
+    })
+}
+
+/// Parse markdown content into slides, stopping once `cancel` fires
+///
+/// The token is checked before every markdown event and around each Mermaid
+/// diagram; a stop surfaces as `PptxError::Cancelled` or
+/// `PptxError::DeadlineExceeded`.
+pub fn parse_with_cancel(content: &str, cancel: &CancelToken) -> Result<Vec<SlideContent>, PptxError> {
+    let mut parser = MarkdownParser::new(cancel.clone());
     parser.parse(content)
 }
 
+/// Parse markdown content into slides, recoloring Mermaid diagrams with `style`
+pub fn parse_styled(content: &str, style: &mermaid::MermaidStyle) -> Result<Vec<SlideContent>, String> {
+    parse_with_warnings(content, style).map(|(slides, _)| slides)
+}
+
+/// [`parse_styled`], also returning what was skipped or simplified
+pub fn parse_with_warnings(content: &str, style: &mermaid::MermaidStyle) -> Result<(Vec<SlideContent>, Warnings), String> {
+    let mut parser = MarkdownParser::new(CancelToken::new());
+    parser.mermaid_style = style.clone();
+    let slides = parser.parse(content).map_err(|e| match e {
+        PptxError::InvalidValue(msg) => msg,
+        e => e.to_string(),
+    })?;
+    Ok((slides, parser.warnings))
+}
+
 /// State machine for markdown parsing
 struct MarkdownParser {
     slides: Vec<SlideContent>,
@@ -38,10 +71,13 @@ struct MarkdownParser {
     blockquote_text: String,
     // Image state
     pending_image: Option<(String, String)>,
+    mermaid_style: mermaid::MermaidStyle,
+    warnings: Warnings,
+    cancel: CancelToken,
 }
 
 impl MarkdownParser {
-    fn new() -> Self {
+    fn new(cancel: CancelToken) -> Self {
         Self {
             slides: Vec::new(),
             current_slide: None,
@@ -61,10 +97,14 @@ impl MarkdownParser {
             in_blockquote: false,
             blockquote_text: String::new(),
             pending_image: None,
+            mermaid_style: mermaid::MermaidStyle::default(),
+            warnings: Warnings::new(),
+            cancel,
         }
     }
 
-    fn parse(&mut self, content: &str) -> Result<Vec<SlideContent>, String> {
+    #[cfg_attr(feature = "tracing", tracing::instrument(name = "parse_markdown", level = "debug", skip_all, fields(bytes = content.len())))]
+    fn parse(&mut self, content: &str) -> Result<Vec<SlideContent>, PptxError> {
         let options = Options::ENABLE_TABLES 
             | Options::ENABLE_STRIKETHROUGH
             | Options::ENABLE_TASKLISTS;
@@ -72,19 +112,21 @@ impl MarkdownParser {
         let parser = Parser::new_ext(content, options);
         
         for event in parser {
-            self.handle_event(event);
+            self.cancel.check()?;
+            self.handle_event(event)?;
         }
         
         self.finalize_current_slide();
         
         if self.slides.is_empty() {
-            return Err("No slides found in markdown file".to_string());
+            return Err(PptxError::InvalidValue(NO_SLIDES.to_string()));
         }
+        event!(DEBUG, slides = self.slides.len(), "markdown parsed");
         
         Ok(std::mem::take(&mut self.slides))
     }
 
-    fn handle_event(&mut self, event: Event) {
+    fn handle_event(&mut self, event: Event) -> Result<(), BuildCancelled> {
         match event {
             // Headings create new slides
             Event::Start(Tag::Heading { level, .. }) => {
@@ -171,7 +213,7 @@ impl MarkdownParser {
             }
             Event::End(TagEnd::CodeBlock) => {
                 self.in_code_block = false;
-                self.flush_code_block();
+                self.flush_code_block()?;
             }
             
             // Blockquotes (speaker notes)
@@ -201,8 +243,17 @@ impl MarkdownParser {
             Event::End(TagEnd::Image) => {
                 if let Some((url, alt)) = self.pending_image.take() {
                     self.add_image_placeholder(&url, &alt);
+                    self.warn(WarningKind::ImagePlaceholder { image: url });
                 }
             }
+
+            // Kept as plain text, or dropped
+            Event::Start(Tag::Link { .. }) => self.warn_unsupported("link target (text kept)"),
+            Event::Start(Tag::Strikethrough) => self.warn_unsupported("strikethrough"),
+            Event::TaskListMarker(_) => self.warn_unsupported("task list checkbox"),
+            Event::Html(html) | Event::InlineHtml(html) if !html.trim_start().starts_with("<!--") => {
+                self.warn_unsupported("raw HTML");
+            }
             
             // Horizontal rule = slide break
             Event::Rule => {
@@ -238,6 +289,7 @@ impl MarkdownParser {
             
             _ => {}
         }
+        Ok(())
     }
 
     fn push_text(&mut self, text: &str) {
@@ -334,9 +386,9 @@ impl MarkdownParser {
         }
     }
 
-    fn flush_code_block(&mut self) {
+    fn flush_code_block(&mut self) -> Result<(), BuildCancelled> {
         if self.code_content.is_empty() {
-            return;
+            return Ok(());
         }
         
         let code = std::mem::take(&mut self.code_content);
@@ -344,8 +396,7 @@ impl MarkdownParser {
         let lang_str = lang.as_deref().unwrap_or("text");
         
         if lang_str == "mermaid" {
-            self.add_mermaid_diagram(&code);
-            return;
+            return self.add_mermaid_diagram(&code);
         }
         
         let code_block = CodeBlock::new(code.trim(), lang_str);
@@ -357,11 +408,20 @@ impl MarkdownParser {
             slide.code_blocks.push(code_block);
             self.current_slide = Some(slide);
         }
+        Ok(())
     }
 
-    fn add_mermaid_diagram(&mut self, code: &str) {
-        let elements = mermaid::create_diagram_elements(code);
+    fn add_mermaid_diagram(&mut self, code: &str) -> Result<(), BuildCancelled> {
+        let mut elements = mermaid::create_diagram_elements_checked(code, &self.cancel)?;
         let diagram_type = mermaid::detect_type(code);
+        self.mermaid_style.apply(diagram_type, &mut elements);
+        event!(
+            DEBUG,
+            diagram = ?diagram_type,
+            shapes = elements.shapes.len(),
+            connectors = elements.connectors.len(),
+            "mermaid diagram laid out"
+        );
         let (_, _, title, _) = mermaid::get_diagram_style(diagram_type);
         
         // Center diagram on slide if bounds are available
@@ -384,17 +444,21 @@ impl MarkdownParser {
         
         // Apply offset to shapes
         let shapes: Vec<_> = elements.shapes.into_iter().map(|mut shape| {
-            shape.x = (shape.x as i32 + offset_x).max(0) as u32;
-            shape.y = (shape.y as i32 + offset_y).max(0) as u32;
+            shape.x = (shape.x + Emu::from(offset_x)).max(Emu::ZERO);
+            shape.y = (shape.y + Emu::from(offset_y)).max(Emu::ZERO);
             shape
         }).collect();
         
         // Apply offset to connectors
         let connectors: Vec<_> = elements.connectors.into_iter().map(|mut conn| {
-            conn.start_x = (conn.start_x as i32 + offset_x).max(0) as u32;
-            conn.start_y = (conn.start_y as i32 + offset_y).max(0) as u32;
-            conn.end_x = (conn.end_x as i32 + offset_x).max(0) as u32;
-            conn.end_y = (conn.end_y as i32 + offset_y).max(0) as u32;
+            conn.start_x = (conn.start_x + Emu::from(offset_x)).max(Emu::ZERO);
+            conn.start_y = (conn.start_y + Emu::from(offset_y)).max(Emu::ZERO);
+            conn.end_x = (conn.end_x + Emu::from(offset_x)).max(Emu::ZERO);
+            conn.end_y = (conn.end_y + Emu::from(offset_y)).max(Emu::ZERO);
+            for point in &mut conn.waypoints {
+                point.0 = (point.0 + Emu::from(offset_x)).max(Emu::ZERO);
+                point.1 = (point.1 + Emu::from(offset_y)).max(Emu::ZERO);
+            }
             conn
         }).collect();
         
@@ -415,6 +479,7 @@ impl MarkdownParser {
             }
             self.current_slide = Some(slide);
         }
+        Ok(())
     }
 
     fn flush_blockquote(&mut self) {
@@ -429,6 +494,15 @@ impl MarkdownParser {
         }
     }
 
+    /// Record a warning against the slide being built
+    fn warn(&mut self, kind: WarningKind) {
+        self.warnings.push(Warning::new(Some(self.slides.len() + 1), kind));
+    }
+
+    fn warn_unsupported(&mut self, feature: &str) {
+        self.warn(WarningKind::UnsupportedMarkdown { feature: feature.to_string() });
+    }
+
     fn add_image_placeholder(&mut self, url: &str, alt: &str) {
         let label = if alt.is_empty() { url } else { alt };
         
@@ -449,6 +523,7 @@ impl MarkdownParser {
         self.flush_list_items();
         
         if let Some(slide) = self.current_slide.take() {
+            event!(TRACE, slide = self.slides.len() + 1, title = %slide.title, "slide parsed");
             self.slides.push(slide);
         }
     }
@@ -516,4 +591,35 @@ mod tests {
         let slides = parse(md).unwrap();
         assert!(!slides[0].shapes.is_empty());
     }
+    #[test]
+    fn test_parse_with_cancel_stops_with_typed_error() {
+        let md = "# Process\n\n```mermaid\nflowchart LR\n    A --> B\n```";
+        assert!(parse_with_cancel(md, &CancelToken::new()).is_ok());
+
+        let cancelled = CancelToken::new();
+        cancelled.cancel();
+        assert!(matches!(parse_with_cancel(md, &cancelled), Err(PptxError::Cancelled)));
+
+        let expired = CancelToken::with_deadline(std::time::Instant::now());
+        assert!(matches!(parse_with_cancel(md, &expired), Err(PptxError::DeadlineExceeded)));
+    }
+
+    #[test]
+    fn test_parse_keeps_no_slides_message() {
+        assert_eq!(parse("").unwrap_err(), "No slides found in markdown file");
+    }
+
+    #[test]
+    fn test_parse_with_warnings() {
+        let md = "# Intro\nSee [docs](https://example.com) and ~~old~~ plans.\n\n# Pictures\n![Logo](logo.png)\n\n<div>raw</div>\n<!-- note -->";
+        let (slides, warnings) = parse_with_warnings(md, &mermaid::MermaidStyle::default()).unwrap();
+        assert_eq!(slides.len(), 2);
+        let messages: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(messages, [
+            "slide 1: unsupported markdown skipped: link target (text kept)",
+            "slide 1: unsupported markdown skipped: strikethrough",
+            "slide 2: image 'logo.png' is shown as a placeholder",
+            "slide 2: unsupported markdown skipped: raw HTML",
+        ]);
+    }
 }
@@ -0,0 +1,200 @@
+//! Build-time variables and conditional blocks
+//!
+//! Runs over the markdown source before it is parsed, so one file can
+//! produce several audience-specific decks:
+//!
+//! ```markdown
+//! # Roadmap for {{team}}
+//! <!-- if audience == "exec" -->
+//! - Budget summary
+//! <!-- else -->
+//! - Sprint details
+//! <!-- endif -->
+//! ```
+//!
+//! Directives must sit on their own line. Conditions are `name == "value"`,
+//! `name != "value"`, `name` (defined and non-empty) or `!name`; undefined
+//! variables compare as empty. `{{name}}` placeholders for undefined
+//! variables are left as written.
+
+/// Variable defined on the command line with `--define key=value`
+pub type Define = (String, String);
+
+/// Expand `{{var}}` placeholders and resolve `<!-- if -->` blocks
+pub fn preprocess(content: &str, defines: &[Define]) -> Result<String, String> {
+    // One entry per open `if`: (taking this branch, an earlier branch was taken, line)
+    let mut stack: Vec<(bool, bool, usize)> = Vec::new();
+    let mut output = String::with_capacity(content.len());
+
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let active = stack.iter().all(|(taking, _, _)| *taking);
+
+        match directive(line) {
+            Some(Directive::If(condition)) => {
+                let taking = active && evaluate(condition, defines).map_err(|e| format!("line {line_no}: {e}"))?;
+                stack.push((taking, taking, line_no));
+            }
+            Some(Directive::Elif(condition)) => {
+                let Some((_, taken, _)) = stack.pop() else {
+                    return Err(format!("line {line_no}: `elif` without `if`"));
+                };
+                let parent_active = stack.iter().all(|(t, _, _)| *t);
+                let matched = !taken && parent_active
+                    && evaluate(condition, defines).map_err(|e| format!("line {line_no}: {e}"))?;
+                stack.push((matched, taken || matched, line_no));
+            }
+            Some(Directive::Else) => {
+                let Some((_, taken, _)) = stack.pop() else {
+                    return Err(format!("line {line_no}: `else` without `if`"));
+                };
+                let parent_active = stack.iter().all(|(t, _, _)| *t);
+                stack.push((parent_active && !taken, true, line_no));
+            }
+            Some(Directive::EndIf) => {
+                stack.pop().ok_or_else(|| format!("line {line_no}: `endif` without `if`"))?;
+            }
+            None if active => {
+                output.push_str(&substitute(line, defines));
+                output.push('\n');
+            }
+            None => {}
+        }
+    }
+
+    match stack.last() {
+        Some((_, _, line_no)) => Err(format!("line {line_no}: `if` block is not closed with `<!-- endif -->`")),
+        None => Ok(output),
+    }
+}
+
+/// Parse a `key=value` definition as given to `--define`
+pub fn parse_define(s: &str) -> Result<Define, String> {
+    match s.split_once('=') {
+        Some((key, value)) if is_name(key.trim()) => Ok((key.trim().to_string(), value.to_string())),
+        _ => Err(format!("expected KEY=VALUE, got `{s}`")),
+    }
+}
+
+enum Directive<'a> {
+    If(&'a str),
+    Elif(&'a str),
+    Else,
+    EndIf,
+}
+
+fn directive(line: &str) -> Option<Directive<'_>> {
+    let inner = line.trim().strip_prefix("<!--")?.strip_suffix("-->")?.trim();
+    if let Some(condition) = inner.strip_prefix("if ") {
+        Some(Directive::If(condition.trim()))
+    } else if let Some(condition) = inner.strip_prefix("elif ") {
+        Some(Directive::Elif(condition.trim()))
+    } else {
+        match inner {
+            "else" => Some(Directive::Else),
+            "endif" => Some(Directive::EndIf),
+            _ => None,
+        }
+    }
+}
+
+fn lookup<'a>(defines: &'a [Define], name: &str) -> Option<&'a str> {
+    defines.iter().rev().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+}
+
+fn is_name(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+fn evaluate(condition: &str, defines: &[Define]) -> Result<bool, String> {
+    for (op, equal) in [("==", true), ("!=", false)] {
+        if let Some((name, value)) = condition.split_once(op) {
+            let name = name.trim();
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+            if !is_name(name) {
+                return Err(format!("invalid condition `{condition}`"));
+            }
+            return Ok((lookup(defines, name).unwrap_or("") == value) == equal);
+        }
+    }
+    let (name, negate) = match condition.strip_prefix('!') {
+        Some(name) => (name.trim(), true),
+        None => (condition, false),
+    };
+    if !is_name(name) {
+        return Err(format!("invalid condition `{condition}`"));
+    }
+    Ok(lookup(defines, name).is_some_and(|v| !v.is_empty()) != negate)
+}
+
+fn substitute(line: &str, defines: &[Define]) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else { break };
+        out.push_str(&rest[..start]);
+        match lookup(defines, after[..end].trim()) {
+            Some(value) => out.push_str(value),
+            None => out.push_str(&rest[start..start + 2 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defines(pairs: &[(&str, &str)]) -> Vec<Define> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_substitute_variables() {
+        let vars = defines(&[("team", "Platform"), ("year", "2026")]);
+        let out = preprocess("# {{team}} in {{ year }}\n- {{unknown}}", &vars).unwrap();
+        assert_eq!(out, "# Platform in 2026\n- {{unknown}}\n");
+    }
+
+    #[test]
+    fn test_conditional_blocks() {
+        let source = "# Plan\n<!-- if audience == \"exec\" -->\n- Budget\n<!-- elif audience == \"eng\" -->\n- Sprints\n<!-- else -->\n- Overview\n<!-- endif -->\n- Q&A";
+        let exec = preprocess(source, &defines(&[("audience", "exec")])).unwrap();
+        assert_eq!(exec, "# Plan\n- Budget\n- Q&A\n");
+        let eng = preprocess(source, &defines(&[("audience", "eng")])).unwrap();
+        assert_eq!(eng, "# Plan\n- Sprints\n- Q&A\n");
+        let other = preprocess(source, &[]).unwrap();
+        assert_eq!(other, "# Plan\n- Overview\n- Q&A\n");
+    }
+
+    #[test]
+    fn test_nested_and_flag_conditions() {
+        let source = "<!-- if draft -->\nA\n<!-- if !internal -->\nB\n<!-- endif -->\n<!-- endif -->\nC";
+        assert_eq!(preprocess(source, &defines(&[("draft", "1")])).unwrap(), "A\nB\nC\n");
+        assert_eq!(preprocess(source, &defines(&[("draft", "1"), ("internal", "yes")])).unwrap(), "A\nC\n");
+        assert_eq!(preprocess(source, &[]).unwrap(), "C\n");
+    }
+
+    #[test]
+    fn test_unbalanced_blocks_are_errors() {
+        assert!(preprocess("<!-- if a -->\nx", &[]).unwrap_err().contains("line 1"));
+        assert!(preprocess("x\n<!-- endif -->", &[]).unwrap_err().contains("line 2"));
+        assert!(preprocess("<!-- if a == -->\n<!-- endif -->", &[]).is_ok());
+        assert!(preprocess("<!-- if a b -->\n<!-- endif -->", &[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_define() {
+        assert_eq!(parse_define("audience=exec").unwrap(), ("audience".to_string(), "exec".to_string()));
+        assert_eq!(parse_define("title=A=B").unwrap().1, "A=B");
+        assert!(parse_define("novalue").is_err());
+        assert!(parse_define("=x").is_err());
+    }
+}
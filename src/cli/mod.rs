@@ -1,15 +1,37 @@
 //! CLI module for PPTX tool
+//!
+//! Markdown parsing needs the `markdown` feature and the command-line parser
+//! and commands need the `cli` feature. Syntax highlighting is also used by
+//! the generator, so it is always built (colored only with `markdown`).
 
+#[cfg(feature = "cli")]
 pub mod commands;
+#[cfg(feature = "cli")]
+pub mod config;
+#[cfg(feature = "cli")]
+pub mod introspect;
+#[cfg(feature = "cli")]
+pub mod progress_bar;
+#[cfg(feature = "cli")]
 pub mod parser;
+#[cfg(feature = "markdown")]
 pub mod markdown;
 pub mod syntax;
 
-pub use commands::{CreateCommand, FromMarkdownCommand, InfoCommand, ValidateCommand};
+#[cfg(feature = "cli")]
+pub use commands::{CreateCommand, FromMarkdownCommand, InfoCommand, ValidateCommand, L10nCommand, GraphCommand, CommentsCommand, BatchReport, theme_by_name};
+#[cfg(feature = "cli")]
+pub use config::{CliConfig, FontsConfig, CONFIG_FILE, parse_slide_size};
+#[cfg(feature = "cli")]
+pub use introspect::{help_json, write_completions};
+#[cfg(feature = "cli")]
+pub use progress_bar::ProgressBar;
+#[cfg(feature = "cli")]
 pub use parser::{
     Cli, Commands, Parser, Command, 
-    CreateArgs, FromMarkdownArgs, InfoArgs, ValidateArgs, Web2PptArgs,
-    ExportFormat,
+    CreateArgs, FromMarkdownArgs, FromMarkdownBatchArgs, InfoArgs, ValidateArgs, GraphArgs, CommentsArgs, Web2PptArgs,
+    ExportFormat, L10nAction,
 };
-pub use markdown::parse_markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::{parse_markdown, parse_markdown_with_cancel, parse_markdown_with_defines, parse_markdown_styled, parse_markdown_with_warnings, MermaidStyle};
 pub use syntax::{highlight_code, generate_highlighted_code_xml};
@@ -25,10 +25,33 @@ Examples:
   pptcli validate presentation.pptx
 
   # Show presentation information
-  pptcli info presentation.pptx"
+  pptcli info presentation.pptx
+
+Defaults for theme, slide size, footer, fonts, output directory and
+Mermaid colors are read from pptx.toml in the current directory or a
+parent (or the file given with --config); command-line flags win.
+
+Wrapper tools can get shell completions with `pptcli completions <SHELL>`
+and a JSON description of every command and flag with --help-json."
 )]
 #[command(version)]
 pub struct Cli {
+    /// Configuration file
+    #[arg(long, global = true, value_name = "FILE", help = "Read defaults from FILE instead of pptx.toml")]
+    pub config: Option<String>,
+
+    /// Print the command-line interface as JSON
+    #[arg(long, global = true, help = "Describe the commands and flags (of the given subcommand) as JSON and exit")]
+    pub help_json: bool,
+
+    /// Log what is being done (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, help = "Log progress to stderr (-v info, -vv debug, -vvv trace)")]
+    pub verbose: u8,
+
+    /// Only report errors
+    #[arg(short, long, global = true, conflicts_with = "verbose", help = "Hide progress bars and all log output except errors")]
+    pub quiet: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -59,6 +82,10 @@ Examples:
         /// Template file to use
         #[arg(long, help = "Template PPTX file to use as base (not yet implemented)")]
         template: Option<String>,
+
+        /// Slide size
+        #[arg(long, value_name = "SIZE", help = "4:3, 16:9 or WIDTHxHEIGHT in inches")]
+        slide_size: Option<String>,
     },
     
     /// Generate PPTX from Markdown file
@@ -81,6 +108,9 @@ Supported Markdown Features:
   ```code```     → Code blocks (as shapes)
   ```mermaid     → Mermaid diagrams (12 types)
   ---            → Slide break (continuation)
+  {{name}}       → Value from --define name=...
+  <!-- if audience == \"exec\" --> ... <!-- else --> ... <!-- endif -->
+                 → Conditional blocks (also elif, name, !name, !=)
 
 Example Markdown:
   # Introduction
@@ -94,10 +124,18 @@ Example Markdown:
 
   > Speaker notes go here
 
+Batch Mode:
+  With --out-dir, every positional argument is an input (directories
+  contribute their *.md files). Files are converted --jobs at a time,
+  each to OUT_DIR/<stem>.pptx titled after its file name; failures are
+  reported in a summary and the remaining files are still converted.
+
 Examples:
   pptcli md2ppt slides.md presentation.pptx
   pptcli md2ppt slides.md --title \"My Presentation\"
-  pptcli md2ppt slides.md  # Auto-generates slides.pptx"
+  pptcli md2ppt slides.md  # Auto-generates slides.pptx
+  pptcli md2ppt talk.md exec.pptx -D audience=exec -D team=Platform
+  pptcli from-markdown docs/*.md --out-dir decks/ --jobs 4 --theme corporate"
     )]
     Md2Ppt {
         /// Input markdown file
@@ -105,12 +143,46 @@ Examples:
         input: String,
         
         /// Output PPTX file (optional: auto-generated from input if not provided)
-        #[arg(value_name = "OUTPUT", help = "Path to the output PPTX file (default: INPUT.pptx)")]
+        #[arg(value_name = "OUTPUT", help = "Path to the output PPTX file (default: INPUT.pptx); another input with --out-dir")]
         output: Option<String>,
+
+        /// More inputs (batch mode)
+        #[arg(value_name = "MORE", help = "Further Markdown files or directories (requires --out-dir)")]
+        more_inputs: Vec<String>,
         
         /// Presentation title
         #[arg(long, help = "Title of the presentation (overrides Markdown content)")]
         title: Option<String>,
+
+        /// Batch output directory
+        #[arg(long, value_name = "DIR", help = "Convert every input into DIR/<stem>.pptx")]
+        out_dir: Option<String>,
+
+        /// Parallel conversions in batch mode
+        #[arg(long, short = 'j', default_value_t = 1, help = "Number of files converted in parallel (with --out-dir)")]
+        jobs: usize,
+
+        /// Shared theme
+        #[arg(long, value_name = "NAME", help = "Built-in theme for unset colors (corporate, modern, vibrant, dark, nature, tech, carbon)")]
+        theme: Option<String>,
+
+        /// Slide size
+        #[arg(long, value_name = "SIZE", help = "4:3, 16:9 or WIDTHxHEIGHT in inches")]
+        slide_size: Option<String>,
+
+        /// Text density
+        #[arg(long, value_name = "DENSITY", help = "Font sizes and spacing for every slide (compact, cozy, comfortable)")]
+        density: Option<String>,
+
+        /// Build-time variables
+        #[arg(
+            long = "define",
+            short = 'D',
+            value_name = "KEY=VALUE",
+            value_parser = super::markdown::parse_define,
+            help = "Set a variable for {{KEY}} placeholders and <!-- if --> blocks (repeatable)"
+        )]
+        defines: Vec<super::markdown::Define>,
     },
     
     /// Show presentation information
@@ -118,14 +190,25 @@ Examples:
         long_about = "Display information about a PPTX file.
 
 Shows file size, modification date, and basic metadata.
+With --pacing, also estimates the talk duration from speaker notes
+and bullet counts, broken down per section.
 
-Example:
-  pptcli info presentation.pptx"
+Examples:
+  pptcli info presentation.pptx
+  pptcli info presentation.pptx --pacing --wpm 150"
     )]
     Info {
         /// PPTX file to inspect
         #[arg(value_name = "FILE", help = "Path to the PPTX file to inspect")]
         file: String,
+
+        /// Show estimated duration and pacing report
+        #[arg(long)]
+        pacing: bool,
+
+        /// Speaking rate for the pacing estimate
+        #[arg(long, default_value_t = 130, help = "Words per minute used by --pacing")]
+        wpm: u32,
     },
     
     /// Validate a PPTX file
@@ -136,12 +219,19 @@ Checks for:
 - Valid ZIP structure
 - Required parts (presentation.xml, slide masters, etc.)
 - Content types
-- Relationships"
+- Relationships
+
+With --profile, also lints fonts, font sizes, colors, footer text and
+bullet counts against a JSON style profile."
     )]
     Validate {
         /// PPTX file to validate
         #[arg(value_name = "FILE")]
         file: String,
+
+        /// Style profile to lint against
+        #[arg(long, value_name = "PROFILE", help = "JSON style profile (fonts, sizes, palette, footer, bullets)")]
+        profile: Option<String>,
     },
     
     /// Export presentation to other formats
@@ -183,6 +273,123 @@ Slides from all input files will be appended in order."
         inputs: Vec<String>,
     },
     
+    /// Export speaker notes as a narration script
+    #[command(
+        long_about = "Export slide numbers, titles and speaker notes as a Markdown script.
+
+Prints to stdout unless an output file is given.
+
+Examples:
+  pptcli notes deck.pptx -o script.md
+  pptcli notes deck.pptx"
+    )]
+    Notes {
+        /// Input PPTX file
+        #[arg(value_name = "INPUT")]
+        input: String,
+
+        /// Output script file (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Show the parts of a deck and their relationships
+    #[command(
+        long_about = "List every part of a PPTX file with the relationships it holds, resolved
+to part names. Relationships to missing parts and parts nothing refers to
+are flagged, which helps when PowerPoint refuses to open a deck.
+
+With --dot, prints Graphviz DOT source instead.
+
+Examples:
+  pptcli graph deck.pptx
+  pptcli graph deck.pptx --dot | dot -Tsvg -o deck.svg"
+    )]
+    Graph {
+        /// PPTX file to inspect
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// Print Graphviz DOT source
+        #[arg(long)]
+        dot: bool,
+    },
+
+    /// List the review comments of a deck
+    #[command(
+        long_about = "List every comment thread of a PPTX file with its slide, author,
+timestamp, anchored shape, status and replies. Reads both legacy comments
+and modern threaded comments.
+
+With --json, prints an array of threads for review dashboards.
+
+Examples:
+  pptcli comments deck.pptx
+  pptcli comments deck.pptx --json > comments.json"
+    )]
+    Comments {
+        /// PPTX file to read
+        #[arg(value_name = "FILE")]
+        file: String,
+
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Extract or apply translations
+    #[command(
+        long_about = "Localization workflow: extract the text of every run on slides and
+speaker notes with stable IDs, then rebuild a translated copy of the deck
+with all formatting preserved.
+
+The translations format follows the file extension: .json for a flat
+{\"id\": \"text\"} object, XLIFF 1.2 otherwise.
+
+Examples:
+  pptcli l10n extract deck.pptx -o strings.xliff
+  pptcli l10n apply deck.pptx strings.fr.xliff -o deck.fr.pptx"
+    )]
+    L10n {
+        #[command(subcommand)]
+        action: L10nAction,
+    },
+
+    /// Compare the rendered slides of two deck versions
+    #[command(
+        name = "visual-diff",
+        long_about = "Render both decks slide by slide and flag slides whose rendering changed
+by more than a threshold, plus added and removed slides.
+
+Writes a self-contained HTML report with old and new renderings side by
+side, suitable as a CI artifact.
+
+Examples:
+  pptcli visual-diff old.pptx new.pptx --out report.html
+  pptcli visual-diff old.pptx new.pptx --threshold 2 --fail-on-change"
+    )]
+    VisualDiff {
+        /// Previous version of the deck
+        #[arg(value_name = "OLD")]
+        old: String,
+
+        /// New version of the deck
+        #[arg(value_name = "NEW")]
+        new: String,
+
+        /// HTML report file
+        #[arg(short, long, default_value = "visual-diff.html")]
+        out: String,
+
+        /// Percentage of the slide that must change for it to be flagged
+        #[arg(long, default_value_t = 0.1)]
+        threshold: f64,
+
+        /// Exit with status 1 when any slide is flagged
+        #[arg(long)]
+        fail_on_change: bool,
+    },
+
     /// Convert PDF to PowerPoint
     #[command(
         name = "pdf2ppt",
@@ -254,10 +461,54 @@ Extracts:
         /// Request timeout in seconds
         #[arg(long, default_value_t = 30)]
         timeout: u64,
-        
-        /// Enable verbose logging
+    },
+
+    /// Generate shell completions
+    #[command(
+        long_about = "Print a completion script for SHELL to stdout.
+
+Examples:
+  pptcli completions bash > /etc/bash_completion.d/pptcli
+  pptcli completions zsh > \"${fpath[1]}/_pptcli\"
+  pptcli completions fish > ~/.config/fish/completions/pptcli.fish
+  pptcli completions powershell >> $PROFILE"
+    )]
+    Completions {
+        /// Target shell
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+#[derive(Subcommand, Clone, Debug)]
+pub enum L10nAction {
+    /// Write translatable strings to XLIFF or JSON
+    Extract {
+        /// Input PPTX file
+        #[arg(value_name = "INPUT")]
+        input: String,
+
+        /// Strings file (.xliff or .json)
+        #[arg(short, long)]
+        output: String,
+
+        /// Source language code written to XLIFF
+        #[arg(long, default_value = "en")]
+        source_lang: String,
+    },
+    /// Build a localized copy of a deck from translated strings
+    Apply {
+        /// Input PPTX file
+        #[arg(value_name = "INPUT")]
+        input: String,
+
+        /// Translated strings file (.xliff or .json)
+        #[arg(value_name = "TRANSLATIONS")]
+        translations: String,
+
+        /// Output PPTX file (default: <input>.localized.pptx)
         #[arg(short, long)]
-        verbose: bool,
+        output: Option<String>,
     },
 }
 
@@ -275,6 +526,7 @@ pub struct CreateArgs {
     pub title: Option<String>,
     pub slides: usize,
     pub template: Option<String>,
+    pub slide_size: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -282,6 +534,21 @@ pub struct FromMarkdownArgs {
     pub input: String,
     pub output: String,
     pub title: Option<String>,
+    pub defines: Vec<super::markdown::Define>,
+    pub theme: Option<String>,
+    pub slide_size: Option<String>,
+    pub density: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FromMarkdownBatchArgs {
+    pub inputs: Vec<String>,
+    pub out_dir: String,
+    pub jobs: usize,
+    pub theme: Option<String>,
+    pub slide_size: Option<String>,
+    pub density: Option<String>,
+    pub defines: Vec<super::markdown::Define>,
 }
 
 #[derive(Debug, Clone)]
@@ -289,16 +556,20 @@ pub struct Md2PptArgs {
     pub input: String,
     pub output: Option<String>,
     pub title: Option<String>,
+    pub defines: Vec<super::markdown::Define>,
 }
 
 #[derive(Debug, Clone)]
 pub struct InfoArgs {
     pub file: String,
+    pub pacing: bool,
+    pub wpm: u32,
 }
 
 #[derive(Debug, Clone)]
 pub struct ValidateArgs {
     pub file: String,
+    pub profile: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -329,6 +600,33 @@ pub struct MergeArgs {
     pub inputs: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct NotesArgs {
+    pub input: String,
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GraphArgs {
+    pub file: String,
+    pub dot: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct CommentsArgs {
+    pub file: String,
+    pub json: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct VisualDiffArgs {
+    pub old: String,
+    pub new: String,
+    pub out: String,
+    pub threshold: f64,
+    pub fail_on_change: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct Pdf2PptArgs {
     pub input: String,
@@ -339,27 +637,39 @@ pub struct Pdf2PptArgs {
 pub enum Command {
     Create(CreateArgs),
     FromMarkdown(FromMarkdownArgs),
+    FromMarkdownBatch(FromMarkdownBatchArgs),
     Md2Ppt(Md2PptArgs),
     Info(InfoArgs),
     Validate(ValidateArgs),
     Web2Ppt(Web2PptArgs),
     Export(ExportArgs),
     Merge(MergeArgs),
+    Notes(NotesArgs),
+    Graph(GraphArgs),
+    Comments(CommentsArgs),
+    L10n(L10nAction),
+    VisualDiff(VisualDiffArgs),
+    Completions(clap_complete::Shell),
     Pdf2Ppt(Pdf2PptArgs),
 }
 
 impl From<Commands> for Command {
     fn from(cmd: Commands) -> Self {
         match cmd {
-            Commands::Create { output, title, slides, template } => {
+            Commands::Create { output, title, slides, template, slide_size } => {
                 Command::Create(CreateArgs {
                     output,
                     title,
                     slides,
                     template,
+                    slide_size,
                 })
             }
-            Commands::Md2Ppt { input, output, title } => {
+            Commands::Md2Ppt { input, output, more_inputs, out_dir: Some(out_dir), jobs, theme, slide_size, density, defines, .. } => {
+                let inputs = std::iter::once(input).chain(output).chain(more_inputs).collect();
+                Command::FromMarkdownBatch(FromMarkdownBatchArgs { inputs, out_dir, jobs, theme, slide_size, density, defines })
+            }
+            Commands::Md2Ppt { input, output, title, defines, theme, slide_size, density, .. } => {
                 // If output is not provided, auto-generate it
                 let output = output.unwrap_or_else(|| {
                     use std::path::Path;
@@ -383,15 +693,20 @@ impl From<Commands> for Command {
                     input,
                     output,
                     title,
+                    defines,
+                    theme,
+                    slide_size,
+                    density,
                 })
             }
-            Commands::Info { file } => {
-                Command::Info(InfoArgs { file })
+            Commands::Info { file, pacing, wpm } => {
+                Command::Info(InfoArgs { file, pacing, wpm })
             }
-            Commands::Validate { file } => {
-                Command::Validate(ValidateArgs { file })
+            Commands::Validate { file, profile } => {
+                Command::Validate(ValidateArgs { file, profile })
             }
-            Commands::Web2Ppt { url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout, verbose } => {
+            #[cfg(feature = "web2ppt")]
+            Commands::Web2Ppt { url, output, title, max_slides, max_bullets, no_images, no_tables, no_code, no_source_url, timeout } => {
                 Command::Web2Ppt(Web2PptArgs {
                     url,
                     output,
@@ -403,7 +718,8 @@ impl From<Commands> for Command {
                     no_code,
                     no_source_url,
                     timeout,
-                    verbose,
+                    // Global flag, set from `Cli::verbose`
+                    verbose: false,
                 })
             }
             Commands::Export { input, output, format } => {
@@ -419,12 +735,31 @@ impl From<Commands> for Command {
                     inputs,
                 })
             }
+            Commands::Notes { input, output } => {
+                Command::Notes(NotesArgs {
+                    input,
+                    output,
+                })
+            }
+            Commands::Graph { file, dot } => Command::Graph(GraphArgs { file, dot }),
+            Commands::Comments { file, json } => Command::Comments(CommentsArgs { file, json }),
+            Commands::L10n { action } => Command::L10n(action),
+            Commands::VisualDiff { old, new, out, threshold, fail_on_change } => {
+                Command::VisualDiff(VisualDiffArgs {
+                    old,
+                    new,
+                    out,
+                    threshold,
+                    fail_on_change,
+                })
+            }
             Commands::Pdf2Ppt { input, output } => {
                 Command::Pdf2Ppt(Pdf2PptArgs {
                     input,
                     output,
                 })
             }
+            Commands::Completions { shell } => Command::Completions(shell),
         }
     }
 }
@@ -435,7 +770,11 @@ pub struct LegacyParser;
 impl LegacyParser {
     pub fn parse(args: &[String]) -> Result<Command, String> {
         let cli = Cli::parse_from(std::iter::once(&"pptcli".to_string()).chain(args.iter()));
-        Ok(cli.command.into())
+        let mut command = Command::from(cli.command);
+        if let Command::Web2Ppt(args) = &mut command {
+            args.verbose = cli.verbose > 0;
+        }
+        Ok(command)
     }
 }
 
@@ -477,7 +816,7 @@ mod tests {
         ];
         let cli = Cli::parse_from(args.iter());
         match cli.command {
-            Commands::Md2Ppt { input, output, title } => {
+            Commands::Md2Ppt { input, output, title, .. } => {
                 assert_eq!(input, "input.md");
                 assert_eq!(output, Some("output.pptx".to_string()));
                 assert_eq!(title, Some("From Markdown".to_string()));
@@ -497,7 +836,7 @@ mod tests {
         ];
         let cli = Cli::parse_from(args.iter());
         match cli.command {
-            Commands::Md2Ppt { input, output, title } => {
+            Commands::Md2Ppt { input, output, title, .. } => {
                 assert_eq!(input, "input.md");
                 assert_eq!(output, None);
                 assert_eq!(title, Some("From Markdown".to_string()));
@@ -506,6 +845,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_md2ppt_defines() {
+        let args = ["pptcli", "md2ppt", "talk.md", "-D", "audience=exec", "--define", "team=Platform"];
+        let cli = Cli::parse_from(args.iter());
+        match cli.command {
+            Commands::Md2Ppt { defines, .. } => {
+                assert_eq!(defines, vec![
+                    ("audience".to_string(), "exec".to_string()),
+                    ("team".to_string(), "Platform".to_string()),
+                ]);
+            }
+            _ => panic!("Expected Md2Ppt command"),
+        }
+        assert!(Cli::try_parse_from(["pptcli", "md2ppt", "talk.md", "-D", "audience"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_from_markdown_batch() {
+        let cli = Cli::parse_from(["pptcli", "from-markdown", "a.md", "b.md", "docs", "--out-dir", "decks", "--jobs", "4", "--theme", "tech"]);
+        match Command::from(cli.command) {
+            Command::FromMarkdownBatch(args) => {
+                assert_eq!(args.inputs, ["a.md", "b.md", "docs"]);
+                assert_eq!(args.out_dir, "decks");
+                assert_eq!(args.jobs, 4);
+                assert_eq!(args.theme.as_deref(), Some("tech"));
+            }
+            other => panic!("Expected batch conversion, got {other:?}"),
+        }
+
+        let cli = Cli::parse_from(["pptcli", "md2ppt", "a.md", "a.pptx"]);
+        assert!(matches!(Command::from(cli.command), Command::FromMarkdown(args) if args.output == "a.pptx" && args.theme.is_none()));
+    }
+
+    #[test]
+    fn test_parse_config_flag() {
+        let cli = Cli::parse_from(["pptcli", "create", "out.pptx", "--config", "ci/pptx.toml", "--slide-size", "16:9"]);
+        assert_eq!(cli.config.as_deref(), Some("ci/pptx.toml"));
+        assert!(matches!(Command::from(cli.command), Command::Create(args) if args.slide_size.as_deref() == Some("16:9")));
+    }
+
     #[test]
     fn test_parse_from_md_alias() {
         let args = vec![
@@ -533,10 +912,120 @@ mod tests {
         ];
         let cli = Cli::parse_from(args.iter());
         match cli.command {
-            Commands::Info { file } => {
+            Commands::Info { file, pacing, wpm } => {
                 assert_eq!(file, "test.pptx");
+                assert!(!pacing);
+                assert_eq!(wpm, 130);
             }
             _ => panic!("Expected Info command"),
         }
     }
+
+    #[test]
+    fn test_parse_info_pacing() {
+        let args = [
+            "pptcli".to_string(),
+            "info".to_string(),
+            "deck.pptx".to_string(),
+            "--pacing".to_string(),
+            "--wpm".to_string(),
+            "150".to_string(),
+        ];
+        let cli = Cli::parse_from(args.iter());
+        match cli.command {
+            Commands::Info { pacing, wpm, .. } => {
+                assert!(pacing);
+                assert_eq!(wpm, 150);
+            }
+            _ => panic!("Expected Info command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_validate_profile() {
+        let cli = Cli::parse_from(["pptcli", "validate", "deck.pptx", "--profile", "brand.json"]);
+        match cli.command {
+            Commands::Validate { file, profile } => {
+                assert_eq!(file, "deck.pptx");
+                assert_eq!(profile.as_deref(), Some("brand.json"));
+            }
+            _ => panic!("Expected Validate command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_l10n() {
+        let cli = Cli::parse_from(["pptcli", "l10n", "extract", "deck.pptx", "-o", "strings.xliff"]);
+        match cli.command {
+            Commands::L10n { action: L10nAction::Extract { input, output, source_lang } } => {
+                assert_eq!((input.as_str(), output.as_str(), source_lang.as_str()), ("deck.pptx", "strings.xliff", "en"));
+            }
+            _ => panic!("Expected l10n extract"),
+        }
+
+        let cli = Cli::parse_from(["pptcli", "l10n", "apply", "deck.pptx", "fr.json"]);
+        match cli.command {
+            Commands::L10n { action: L10nAction::Apply { translations, output, .. } } => {
+                assert_eq!(translations, "fr.json");
+                assert!(output.is_none());
+            }
+            _ => panic!("Expected l10n apply"),
+        }
+    }
+
+    #[test]
+    fn test_parse_visual_diff() {
+        let cli = Cli::parse_from(["pptcli", "visual-diff", "old.pptx", "new.pptx", "--out", "report.html", "--fail-on-change"]);
+        match cli.command {
+            Commands::VisualDiff { old, new, out, threshold, fail_on_change } => {
+                assert_eq!((old.as_str(), new.as_str(), out.as_str()), ("old.pptx", "new.pptx", "report.html"));
+                assert_eq!(threshold, 0.1);
+                assert!(fail_on_change);
+            }
+            _ => panic!("Expected VisualDiff command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_notes() {
+        let args = [
+            "pptcli".to_string(),
+            "notes".to_string(),
+            "deck.pptx".to_string(),
+            "-o".to_string(),
+            "script.md".to_string(),
+        ];
+        let cli = Cli::parse_from(args.iter());
+        match cli.command {
+            Commands::Notes { input, output } => {
+                assert_eq!(input, "deck.pptx");
+                assert_eq!(output, Some("script.md".to_string()));
+            }
+            _ => panic!("Expected Notes command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_graph() {
+        let cli = Cli::parse_from(["pptcli", "graph", "deck.pptx", "--dot"]);
+        match cli.command {
+            Commands::Graph { file, dot } => {
+                assert_eq!(file, "deck.pptx");
+                assert!(dot);
+            }
+            _ => panic!("Expected Graph command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        let cli = Cli::parse_from(["pptcli", "comments", "deck.pptx", "--json"]);
+        match cli.command {
+            Commands::Comments { file, json } => {
+                assert_eq!(file, "deck.pptx");
+                assert!(json);
+            }
+            _ => panic!("Expected Comments command"),
+        }
+    }
 }
@@ -0,0 +1,94 @@
+//! Terminal progress bar for long CLI operations
+//!
+//! Drawn on stderr, and only when stderr is a terminal, so piped output and
+//! CI logs stay clean. Safe to update from several worker threads.
+
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+/// Width of the bar in characters
+const BAR_WIDTH: usize = 30;
+
+/// Single-line `label [#####.....] done/total` progress bar
+#[derive(Debug)]
+pub struct ProgressBar {
+    label: String,
+    visible: bool,
+    /// Last drawn (filled cells, done), to skip identical redraws
+    drawn: Mutex<Option<(usize, usize)>>,
+}
+
+impl ProgressBar {
+    /// Bar shown when `visible` and stderr is a terminal
+    pub fn new(label: &str, visible: bool) -> Self {
+        ProgressBar {
+            label: label.to_string(),
+            visible: visible && std::io::stderr().is_terminal(),
+            drawn: Mutex::new(None),
+        }
+    }
+
+    /// Bar that never draws
+    pub fn hidden() -> Self {
+        Self::new("", false)
+    }
+
+    /// Whether the bar is drawn
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show `done` of `total` steps
+    pub fn set(&self, done: usize, total: usize) {
+        if !self.visible {
+            return;
+        }
+        let filled = filled_cells(done, total);
+        let mut drawn = self.drawn.lock().unwrap_or_else(|e| e.into_inner());
+        if *drawn == Some((filled, done)) {
+            return;
+        }
+        *drawn = Some((filled, done));
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r{}", render(&self.label, done, total));
+        let _ = stderr.flush();
+    }
+
+    /// Clear the bar's line
+    pub fn finish(&self) {
+        let mut drawn = self.drawn.lock().unwrap_or_else(|e| e.into_inner());
+        if self.visible && drawn.take().is_some() {
+            let _ = write!(std::io::stderr(), "\r\x1b[2K");
+        }
+    }
+}
+
+fn filled_cells(done: usize, total: usize) -> usize {
+    (done.min(total) * BAR_WIDTH).checked_div(total).unwrap_or(BAR_WIDTH)
+}
+
+/// Text of the bar, e.g. `Slides [###...] 12/300`
+fn render(label: &str, done: usize, total: usize) -> String {
+    let filled = filled_cells(done, total);
+    format!("{label} [{}{}] {done}/{total}", "#".repeat(filled), ".".repeat(BAR_WIDTH - filled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        assert_eq!(render("Slides", 0, 3), format!("Slides [{}] 0/3", ".".repeat(30)));
+        assert_eq!(render("Slides", 1, 3), format!("Slides [{}{}] 1/3", "#".repeat(10), ".".repeat(20)));
+        assert_eq!(render("Files", 5, 5), format!("Files [{}] 5/5", "#".repeat(30)));
+    }
+
+    #[test]
+    fn test_hidden_bar_is_silent() {
+        let bar = ProgressBar::hidden();
+        bar.set(1, 2);
+        bar.finish();
+        assert!(!bar.is_visible());
+    }
+}
@@ -1,10 +1,15 @@
 //! Syntax highlighting for code blocks
 //!
 //! Uses syntect to provide syntax highlighting for code blocks in presentations.
+//! Without the `markdown` feature code is shown in a single color.
 
+#[cfg(feature = "markdown")]
 use syntect::highlighting::{ThemeSet, Style};
+#[cfg(feature = "markdown")]
 use syntect::parsing::SyntaxSet;
+#[cfg(feature = "markdown")]
 use syntect::easy::HighlightLines;
+use crate::core::escape_xml;
 
 /// A highlighted text segment with color
 #[derive(Debug, Clone)]
@@ -16,6 +21,7 @@ pub struct HighlightedSegment {
 }
 
 /// Highlight code with syntax coloring
+#[cfg(feature = "markdown")]
 pub fn highlight_code(code: &str, language: &str) -> Vec<Vec<HighlightedSegment>> {
     let ps = SyntaxSet::load_defaults_newlines();
     let ts = ThemeSet::load_defaults();
@@ -75,7 +81,26 @@ pub fn highlight_code(code: &str, language: &str) -> Vec<Vec<HighlightedSegment>
     lines
 }
 
+/// Split code into lines in the Solarized base0 text color
+#[cfg(not(feature = "markdown"))]
+pub fn highlight_code(code: &str, _language: &str) -> Vec<Vec<HighlightedSegment>> {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                return Vec::new();
+            }
+            vec![HighlightedSegment {
+                text: line.to_string(),
+                color: "839496".to_string(),
+                bold: false,
+                italic: false,
+            }]
+        })
+        .collect()
+}
+
 /// Convert syntect Style to hex color
+#[cfg(feature = "markdown")]
 fn style_to_hex(style: &Style) -> String {
     format!("{:02X}{:02X}{:02X}", style.foreground.r, style.foreground.g, style.foreground.b)
 }
@@ -111,15 +136,6 @@ pub fn generate_highlighted_code_xml(code: &str, language: &str) -> String {
     xml
 }
 
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
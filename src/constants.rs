@@ -1,10 +1,16 @@
 //! Global constants - no hardcoding
 
 /// Version information
+///
+/// Never tracked the crate version; kept so old code still compiles.
 pub mod version {
+    #[deprecated(since = "0.2.4", note = "use `ppt_rs::VERSION`")]
     pub const MAJOR: u32 = 1;
+    #[deprecated(since = "0.2.4", note = "use `ppt_rs::VERSION`")]
     pub const MINOR: u32 = 0;
+    #[deprecated(since = "0.2.4", note = "use `ppt_rs::VERSION`")]
     pub const PATCH: u32 = 2;
+    #[deprecated(since = "0.2.4", note = "use `ppt_rs::VERSION`")]
     pub const VERSION_STRING: &str = "1.0.2";
 }
 
@@ -95,6 +101,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(deprecated)]
     fn test_version() {
         assert_eq!(version::VERSION_STRING, "1.0.2");
     }
@@ -0,0 +1,140 @@
+//! Layout helpers over any [`Positioned`] element
+//!
+//! Shapes, images, tables, charts and connectors all implement
+//! [`Positioned`], so a mixed selection can be moved, resized or aligned
+//! together:
+//!
+//! ```rust,ignore
+//! let mut items: [&mut dyn Positioned; 2] = [&mut shape, &mut chart];
+//! arrange::align(&mut items, Align::Top);
+//! arrange::translate(&mut items, Emu::from(Inches(1.0)), Emu::ZERO);
+//! ```
+
+use super::traits::Positioned;
+use super::units::Emu;
+
+/// Edge or center line that [`align`] lines elements up on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Bounding box `(x, y, width, height)` of `items`, or `None` if empty
+pub fn bounds(items: &[&mut dyn Positioned]) -> Option<(Emu, Emu, Emu, Emu)> {
+    let mut iter = items.iter();
+    let first = iter.next()?;
+    let (mut left, mut top) = (first.x(), first.y());
+    let (mut right, mut bottom) = (left + first.width(), top + first.height());
+    for item in iter {
+        left = left.min(item.x());
+        top = top.min(item.y());
+        right = right.max(item.x() + item.width());
+        bottom = bottom.max(item.y() + item.height());
+    }
+    Some((left, top, right - left, bottom - top))
+}
+
+/// Move every element by `dx`, `dy`
+pub fn translate(items: &mut [&mut dyn Positioned], dx: Emu, dy: Emu) {
+    for item in items.iter_mut() {
+        let (x, y) = (item.x(), item.y());
+        item.set_position(x + dx, y + dy);
+    }
+}
+
+/// Resize the selection by `factor`, keeping its top-left corner fixed
+///
+/// Sizes and the distances from the selection's corner are scaled alike, so
+/// the elements keep their relative arrangement.
+pub fn scale(items: &mut [&mut dyn Positioned], factor: f64) {
+    let Some((left, top, _, _)) = bounds(items) else { return };
+    for item in items.iter_mut() {
+        let (x, y) = (item.x(), item.y());
+        let (width, height) = (item.width(), item.height());
+        item.set_position(left + (x - left).scale(factor), top + (y - top).scale(factor));
+        item.set_size(width.scale(factor), height.scale(factor));
+    }
+}
+
+/// Line elements up on an edge or center line of the selection's bounding box
+pub fn align(items: &mut [&mut dyn Positioned], alignment: Align) {
+    let Some((left, top, width, height)) = bounds(items) else { return };
+    for item in items.iter_mut() {
+        let (x, y) = (item.x(), item.y());
+        let (x, y) = match alignment {
+            Align::Left => (left, y),
+            Align::Center => (left + (width - item.width()) / 2, y),
+            Align::Right => (left + width - item.width(), y),
+            Align::Top => (x, top),
+            Align::Middle => (x, top + (height - item.height()) / 2),
+            Align::Bottom => (x, top + height - item.height()),
+        };
+        item.set_position(x, y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ElementSized;
+
+    struct Box2 {
+        x: Emu,
+        y: Emu,
+        w: Emu,
+        h: Emu,
+    }
+
+    impl Positioned for Box2 {
+        fn x(&self) -> Emu { self.x }
+        fn y(&self) -> Emu { self.y }
+        fn set_position(&mut self, x: Emu, y: Emu) { (self.x, self.y) = (x, y); }
+    }
+
+    impl ElementSized for Box2 {
+        fn width(&self) -> Emu { self.w }
+        fn height(&self) -> Emu { self.h }
+        fn set_size(&mut self, width: Emu, height: Emu) { (self.w, self.h) = (width, height); }
+    }
+
+    fn boxes() -> (Box2, Box2) {
+        (
+            Box2 { x: Emu(100), y: Emu(100), w: Emu(200), h: Emu(100) },
+            Box2 { x: Emu(400), y: Emu(300), w: Emu(100), h: Emu(50) },
+        )
+    }
+
+    #[test]
+    fn test_bounds_and_translate() {
+        let (mut a, mut b) = boxes();
+        let mut items: [&mut dyn Positioned; 2] = [&mut a, &mut b];
+        assert_eq!(bounds(&items), Some((Emu(100), Emu(100), Emu(400), Emu(250))));
+
+        translate(&mut items, Emu(-150), Emu(10));
+        assert_eq!((a.x, a.y, b.x, b.y), (Emu(-50), Emu(110), Emu(250), Emu(310)));
+        assert_eq!(bounds(&[]), None);
+    }
+
+    #[test]
+    fn test_scale_keeps_corner() {
+        let (mut a, mut b) = boxes();
+        scale(&mut [&mut a, &mut b], 0.5);
+        assert_eq!((a.x, a.y, a.w, a.h), (Emu(100), Emu(100), Emu(100), Emu(50)));
+        assert_eq!((b.x, b.y, b.w, b.h), (Emu(250), Emu(200), Emu(50), Emu(25)));
+    }
+
+    #[test]
+    fn test_align() {
+        let (mut a, mut b) = boxes();
+        align(&mut [&mut a, &mut b], Align::Right);
+        assert_eq!((a.x, b.x), (Emu(300), Emu(400)));
+
+        align(&mut [&mut a, &mut b], Align::Middle);
+        assert_eq!((a.y, b.y), (Emu(175), Emu(200)));
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! This module provides the foundational traits that enable trait-based
 //! XML generation and consistent behavior across all PPTX elements.
+//!
+//! Everything here is pure string and EMU math on `core` + `alloc`, so it
+//! is also available when the crate is built without the `std` feature.
 
 mod traits;
 mod xml_utils;
+pub mod units;
+pub mod arrange;
 
 pub use traits::{ToXml, XmlElement, Positioned, Sized as ElementSized, Styled};
-pub use xml_utils::{escape_xml, XmlWriter};
+pub use xml_utils::{escape_xml, escape_xml_attr, Escaped, EscapedAttr, XmlWriter};
+pub use units::{Emu, Pt, Inches, Cm, emu_to_inches, inches_to_emu, cm_to_emu, mm_to_emu, pt_to_emu};
@@ -3,6 +3,11 @@
 //! These traits provide a consistent interface for XML generation
 //! and element manipulation across the library.
 
+use alloc::format;
+use alloc::string::{String, ToString};
+
+use super::units::Emu;
+
 /// Trait for types that can be converted to XML
 pub trait ToXml {
     /// Generate XML representation of this element
@@ -36,27 +41,30 @@ pub trait XmlElement: ToXml {
 }
 
 /// Trait for positioned elements (x, y coordinates)
-pub trait Positioned {
+///
+/// Every positioned element also has a size, so layout helpers in
+/// [`arrange`](super::arrange) can work on `&mut dyn Positioned` alone.
+pub trait Positioned: Sized {
     /// Get X position in EMU
-    fn x(&self) -> u32;
-    
+    fn x(&self) -> Emu;
+
     /// Get Y position in EMU
-    fn y(&self) -> u32;
-    
+    fn y(&self) -> Emu;
+
     /// Set position
-    fn set_position(&mut self, x: u32, y: u32);
+    fn set_position(&mut self, x: Emu, y: Emu);
 }
 
 /// Trait for sized elements (width, height)
 pub trait Sized {
     /// Get width in EMU
-    fn width(&self) -> u32;
-    
+    fn width(&self) -> Emu;
+
     /// Get height in EMU
-    fn height(&self) -> u32;
-    
+    fn height(&self) -> Emu;
+
     /// Set size
-    fn set_size(&mut self, width: u32, height: u32);
+    fn set_size(&mut self, width: Emu, height: Emu);
 }
 
 /// Trait for styled elements (color, formatting)
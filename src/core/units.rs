@@ -0,0 +1,301 @@
+//! EMU (English Metric Unit) conversions
+//!
+//! DrawingML measures positions and sizes in EMU.
+//! 1 inch = 914400 EMU, 1 cm = 360000 EMU, 1 pt = 12700 EMU
+//!
+//! [`Emu`] is a signed 64-bit length, so offsets left of or above the slide
+//! and positions on very large slides are represented exactly. [`Pt`],
+//! [`Inches`] and [`Cm`] convert into it with rounding:
+//!
+//! ```
+//! use ppt_rs::core::units::{Emu, Inches, Pt};
+//!
+//! assert_eq!(Emu::from(Inches(1.0)), Emu(914_400));
+//! assert_eq!(Emu::from(Pt(-1.0)), Emu(-12_700));
+//! assert_eq!(Emu(914_400).checked_add(Emu(i64::MAX)), None);
+//! assert_eq!(Emu(914_400).checked_scale(0.5), Some(Emu(457_200)));
+//! assert_eq!(Emu(i64::MAX).checked_scale(2.0), None);
+//! ```
+//!
+//! Shapes, images, charts, tables, connectors, slide zooms, video and audio
+//! store their position and size as [`Emu`], as does the lowered
+//! [`Frame`](crate::generator::ir::Frame); the `oxml` readers keep the
+//! signed `i64` offsets of the file. Lengths that cannot be negative stay
+//! unsigned: table column widths and row heights, [`Region`] bounds, the
+//! slide size of a presentation part, and the positions of the calendar,
+//! heatmap, stepper and funnel builders, which lay out inside the slide.
+//! Builders that take a size as [`Emu`] but store it unsigned (QR codes,
+//! icons, swimlanes, quadrant charts) treat a negative size as 0.
+//!
+//! [`Region`]: crate::generator::Region
+
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// EMU conversion constants
+pub const EMU_PER_INCH: i64 = 914400;
+pub const EMU_PER_CM: i64 = 360000;
+pub const EMU_PER_MM: i64 = 36000;
+pub const EMU_PER_PT: i64 = 12700;
+
+/// A length in EMU
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Emu(pub i64);
+
+/// A length in points (1/72 inch)
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Pt(pub f64);
+
+/// A length in inches
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Inches(pub f64);
+
+/// A length in centimeters
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cm(pub f64);
+
+/// Round `value * per_unit` to the nearest EMU, saturating at the `i64` range
+fn round_emu(value: f64, per_unit: i64) -> Emu {
+    let emu = value * per_unit as f64;
+    Emu(if emu < 0.0 { emu - 0.5 } else { emu + 0.5 } as i64)
+}
+
+impl Emu {
+    pub const ZERO: Emu = Emu(0);
+
+    /// The length in EMU
+    pub const fn get(self) -> i64 {
+        self.0
+    }
+
+    /// The length in inches
+    pub fn to_inches(self) -> f64 {
+        self.0 as f64 / EMU_PER_INCH as f64
+    }
+
+    /// The length in centimeters
+    pub fn to_cm(self) -> f64 {
+        self.0 as f64 / EMU_PER_CM as f64
+    }
+
+    /// The length in points
+    pub fn to_pt(self) -> f64 {
+        self.0 as f64 / EMU_PER_PT as f64
+    }
+
+    /// The length multiplied by `factor`, rounded to the nearest EMU
+    pub fn scale(self, factor: f64) -> Emu {
+        round_emu(self.0 as f64 * factor, 1)
+    }
+
+    /// The length multiplied by `factor`, or `None` if the result is not a
+    /// finite length in the `i64` range
+    pub fn checked_scale(self, factor: f64) -> Option<Emu> {
+        let emu = self.0 as f64 * factor;
+        (emu.is_finite() && emu >= i64::MIN as f64 && emu < i64::MAX as f64).then(|| round_emu(emu, 1))
+    }
+
+    /// The length as `u32`, or `None` if it is negative or too large
+    pub fn to_u32(self) -> Option<u32> {
+        u32::try_from(self.0).ok()
+    }
+
+    /// The length as `u32`, clamping negative values to 0
+    ///
+    /// For element types that still store unsigned EMU.
+    pub fn to_u32_saturating(self) -> u32 {
+        self.0.clamp(0, u32::MAX as i64) as u32
+    }
+
+    /// `self + rhs`, or `None` on overflow
+    pub fn checked_add(self, rhs: Emu) -> Option<Emu> {
+        self.0.checked_add(rhs.0).map(Emu)
+    }
+
+    /// `self - rhs`, or `None` on overflow
+    pub fn checked_sub(self, rhs: Emu) -> Option<Emu> {
+        self.0.checked_sub(rhs.0).map(Emu)
+    }
+
+    /// `self * rhs`, or `None` on overflow
+    pub fn checked_mul(self, rhs: i64) -> Option<Emu> {
+        self.0.checked_mul(rhs).map(Emu)
+    }
+
+    /// `self + rhs`, clamped to the `i64` range
+    pub fn saturating_add(self, rhs: Emu) -> Emu {
+        Emu(self.0.saturating_add(rhs.0))
+    }
+
+    /// `self - rhs`, clamped to the `i64` range
+    pub fn saturating_sub(self, rhs: Emu) -> Emu {
+        Emu(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl fmt::Display for Emu {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<i64> for Emu {
+    fn from(emu: i64) -> Self {
+        Emu(emu)
+    }
+}
+
+impl From<i32> for Emu {
+    fn from(emu: i32) -> Self {
+        Emu(emu as i64)
+    }
+}
+
+impl From<u32> for Emu {
+    fn from(emu: u32) -> Self {
+        Emu(emu as i64)
+    }
+}
+
+impl From<Emu> for i64 {
+    fn from(emu: Emu) -> Self {
+        emu.0
+    }
+}
+
+impl From<Pt> for Emu {
+    fn from(pt: Pt) -> Self {
+        round_emu(pt.0, EMU_PER_PT)
+    }
+}
+
+impl From<Inches> for Emu {
+    fn from(inches: Inches) -> Self {
+        round_emu(inches.0, EMU_PER_INCH)
+    }
+}
+
+impl From<Cm> for Emu {
+    fn from(cm: Cm) -> Self {
+        round_emu(cm.0, EMU_PER_CM)
+    }
+}
+
+impl From<Emu> for Pt {
+    fn from(emu: Emu) -> Self {
+        Pt(emu.to_pt())
+    }
+}
+
+impl From<Emu> for Inches {
+    fn from(emu: Emu) -> Self {
+        Inches(emu.to_inches())
+    }
+}
+
+impl From<Emu> for Cm {
+    fn from(emu: Emu) -> Self {
+        Cm(emu.to_cm())
+    }
+}
+
+impl Add for Emu {
+    type Output = Emu;
+    fn add(self, rhs: Emu) -> Emu {
+        Emu(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Emu {
+    type Output = Emu;
+    fn sub(self, rhs: Emu) -> Emu {
+        Emu(self.0 - rhs.0)
+    }
+}
+
+impl Mul<i64> for Emu {
+    type Output = Emu;
+    fn mul(self, rhs: i64) -> Emu {
+        Emu(self.0 * rhs)
+    }
+}
+
+impl Div<i64> for Emu {
+    type Output = Emu;
+    fn div(self, rhs: i64) -> Emu {
+        Emu(self.0 / rhs)
+    }
+}
+
+impl Neg for Emu {
+    type Output = Emu;
+    fn neg(self) -> Emu {
+        Emu(-self.0)
+    }
+}
+
+impl AddAssign for Emu {
+    fn add_assign(&mut self, rhs: Emu) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for Emu {
+    fn sub_assign(&mut self, rhs: Emu) {
+        self.0 -= rhs.0;
+    }
+}
+
+/// Convert EMU (English Metric Units) to inches
+pub fn emu_to_inches(emu: u32) -> f64 {
+    emu as f64 / EMU_PER_INCH as f64
+}
+
+/// Convert inches to EMU, rounded and clamped to the `u32` range
+pub fn inches_to_emu(inches: f64) -> u32 {
+    round_emu(inches, EMU_PER_INCH).to_u32_saturating()
+}
+
+/// Convert centimeters to EMU
+pub fn cm_to_emu(cm: f64) -> u32 {
+    round_emu(cm, EMU_PER_CM).to_u32_saturating()
+}
+
+/// Convert millimeters to EMU
+pub fn mm_to_emu(mm: f64) -> u32 {
+    round_emu(mm, EMU_PER_MM).to_u32_saturating()
+}
+
+/// Convert points to EMU
+pub fn pt_to_emu(pt: f64) -> u32 {
+    round_emu(pt, EMU_PER_PT).to_u32_saturating()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emu_conversions() {
+        assert_eq!(inches_to_emu(1.0), 914400);
+        assert_eq!(cm_to_emu(2.54), 914400);
+        assert_eq!(mm_to_emu(10.0), 360000);
+        assert_eq!(pt_to_emu(72.0), 914400);
+        assert_eq!(emu_to_inches(457200), 0.5);
+    }
+
+    #[test]
+    fn test_emu_newtype() {
+        assert_eq!(Emu::from(Cm(2.54)), Emu(914_400));
+        assert_eq!(Emu::from(Inches(-0.5)), Emu(-457_200));
+        assert_eq!(Emu::from(Pt(0.5)).get(), 6350);
+        assert_eq!(Pt::from(Emu(12_700)), Pt(1.0));
+        assert_eq!(Emu(-1).to_u32(), None);
+        assert_eq!(Emu(5_000_000_000).to_u32(), None);
+        assert_eq!(Emu(-1).to_u32_saturating(), 0);
+        assert_eq!(Emu(i64::MAX).checked_add(Emu(1)), None);
+        assert_eq!(Emu(i64::MIN).saturating_sub(Emu(1)), Emu(i64::MIN));
+        assert_eq!(Emu(10) - Emu(25), Emu(-15));
+        assert_eq!(alloc::format!("{}", Emu(-42)), "-42");
+    }
+}
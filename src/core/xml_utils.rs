@@ -2,13 +2,80 @@
 //!
 //! Centralized XML utilities to avoid duplication across modules.
 
-/// Escape special XML characters
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// Escape special XML characters for element text
 pub fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
+    let mut out = String::with_capacity(s.len());
+    let _ = escape_into(&mut out, s, false);
+    out
+}
+
+/// Escape special XML characters for an attribute value
+///
+/// Like [`escape_xml`], but also writes tabs and line breaks as character
+/// references: a parser replaces literal ones in attributes with spaces, so
+/// a multi-line shape name or tooltip would otherwise lose its line breaks.
+pub fn escape_xml_attr(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let _ = escape_into(&mut out, s, true);
+    out
+}
+
+/// Write `s` to `out` with special XML characters escaped, in one pass
+fn escape_into(out: &mut impl Write, s: &str, attr: bool) -> fmt::Result {
+    const TEXT: &[char] = &['&', '<', '>', '"', '\''];
+    const ATTR: &[char] = &['&', '<', '>', '"', '\'', '\t', '\n', '\r'];
+    let special = if attr { ATTR } else { TEXT };
+    let mut rest = s;
+    while let Some(i) = rest.find(special) {
+        out.write_str(&rest[..i])?;
+        out.write_str(match rest.as_bytes()[i] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            b'\'' => "&apos;",
+            b'\t' => "&#9;",
+            b'\n' => "&#10;",
+            _ => "&#13;",
+        })?;
+        rest = &rest[i + 1..];
+    }
+    out.write_str(rest)
+}
+
+/// Displays a string escaped for element text, without allocating
+///
+/// For escaped values inside `write!` templates.
+pub struct Escaped<'a>(pub &'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape_into(f, self.0, false)
+    }
+}
+
+/// Displays a string escaped for an attribute value, without allocating
+pub struct EscapedAttr<'a>(pub &'a str);
+
+impl fmt::Display for EscapedAttr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        escape_into(f, self.0, true)
+    }
+}
+
+/// `fmt::Write` adapter that escapes everything written through it
+struct Escaping<'a> {
+    out: &'a mut String,
+    attr: bool,
+}
+
+impl Write for Escaping<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        escape_into(self.out, s, self.attr)
+    }
 }
 
 /// Normalize color string (remove # prefix, uppercase)
@@ -19,6 +86,11 @@ pub fn normalize_color(color: &str) -> String {
 }
 
 /// XML writer helper for building XML strings efficiently
+///
+/// Everything is appended to one buffer: attribute values and text are
+/// escaped in place, and `write!(writer, ...)` formats fixed markup
+/// without an intermediate `String`. Emitters take `&mut XmlWriter` so a
+/// whole part can be built in a single buffer.
 #[allow(dead_code)]
 pub struct XmlWriter {
     buffer: String,
@@ -36,8 +108,13 @@ impl XmlWriter {
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
+        Self::from_string(String::with_capacity(capacity))
+    }
+
+    /// Append to an existing buffer
+    pub fn from_string(buffer: String) -> Self {
         Self {
-            buffer: String::with_capacity(capacity),
+            buffer,
             indent_level: 0,
             indent_str: "  ",
         }
@@ -55,11 +132,7 @@ impl XmlWriter {
         self.buffer.push('<');
         self.buffer.push_str(name);
         for (key, value) in attrs {
-            self.buffer.push(' ');
-            self.buffer.push_str(key);
-            self.buffer.push_str("=\"");
-            self.buffer.push_str(&escape_xml(value));
-            self.buffer.push('"');
+            self.attr(key, value);
         }
         self.buffer.push('>');
         self.indent_level += 1;
@@ -80,22 +153,61 @@ impl XmlWriter {
         self.buffer.push('<');
         self.buffer.push_str(name);
         for (key, value) in attrs {
-            self.buffer.push(' ');
-            self.buffer.push_str(key);
-            self.buffer.push_str("=\"");
-            self.buffer.push_str(&escape_xml(value));
-            self.buffer.push('"');
+            self.attr(key, value);
         }
         self.buffer.push_str("/>");
         self
     }
 
+    /// Open a start tag (`<name`); follow with [`attr`](Self::attr) calls and
+    /// [`close`](Self::close) or [`close_empty`](Self::close_empty)
+    pub fn open(&mut self, name: &str) -> &mut Self {
+        self.buffer.push('<');
+        self.buffer.push_str(name);
+        self
+    }
+
+    /// Write an attribute of the open tag, escaping its value
+    pub fn attr(&mut self, key: &str, value: impl fmt::Display) -> &mut Self {
+        self.buffer.push(' ');
+        self.buffer.push_str(key);
+        self.buffer.push_str("=\"");
+        let _ = write!(Escaping { out: &mut self.buffer, attr: true }, "{value}");
+        self.buffer.push('"');
+        self
+    }
+
+    /// Finish the open tag as a start tag (`>`)
+    pub fn close(&mut self) -> &mut Self {
+        self.buffer.push('>');
+        self.indent_level += 1;
+        self
+    }
+
+    /// Finish the open tag as a self-closing element (`/>`)
+    pub fn close_empty(&mut self) -> &mut Self {
+        self.buffer.push_str("/>");
+        self
+    }
+
+    /// Write `<name>text</name>`, escaping the text
+    pub fn leaf(&mut self, name: &str, text: impl fmt::Display) -> &mut Self {
+        self.open(name).close();
+        let _ = write!(Escaping { out: &mut self.buffer, attr: false }, "{text}");
+        self.end_element(name)
+    }
+
     /// Write text content
     pub fn text(&mut self, content: &str) -> &mut Self {
-        self.buffer.push_str(&escape_xml(content));
+        let _ = escape_into(&mut self.buffer, content, false);
         self
     }
 
+    /// Write formatted raw XML (no escaping); this is what `write!` calls
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) {
+        let _ = self.buffer.write_fmt(args);
+    }
+
     /// Write raw XML (no escaping)
     pub fn raw(&mut self, xml: &str) -> &mut Self {
         self.buffer.push_str(xml);
@@ -147,6 +259,34 @@ mod tests {
         assert_eq!(writer.finish(), r#"<root attr="value">content</root>"#);
     }
 
+    #[test]
+    fn test_xml_writer_attrs_and_leaf() {
+        let mut writer = XmlWriter::from_string(String::from("<p>"));
+        writer.open("a:rPr").attr("sz", 1800).attr("typeface", "A&B").close_empty();
+        writer.leaf("a:t", "x < y");
+        write!(writer, "<n v=\"{}\" t=\"{}\"/>", 2, Escaped("'"));
+        assert_eq!(
+            writer.finish(),
+            r#"<p><a:rPr sz="1800" typeface="A&amp;B"/><a:t>x &lt; y</a:t><n v="2" t="&apos;"/>"#
+        );
+    }
+
+    #[test]
+    fn test_escape_xml_all_characters() {
+        assert_eq!(escape_xml("plain"), "plain");
+        assert_eq!(escape_xml("<a b='c' d=\"e\">&</a>"), "&lt;a b=&apos;c&apos; d=&quot;e&quot;&gt;&amp;&lt;/a&gt;");
+    }
+
+    #[test]
+    fn test_escape_xml_attr_line_breaks() {
+        assert_eq!(escape_xml("a\nb"), "a\nb");
+        assert_eq!(escape_xml_attr("\"a\"\r\n\tb"), "&quot;a&quot;&#13;&#10;&#9;b");
+
+        let mut writer = XmlWriter::new();
+        writer.open("p:cNvPr").attr("name", "Line 1\nLine 2").close_empty();
+        assert_eq!(writer.finish(), r#"<p:cNvPr name="Line 1&#10;Line 2"/>"#);
+    }
+
     #[test]
     fn test_xml_writer_empty_element() {
         let mut writer = XmlWriter::new();
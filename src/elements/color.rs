@@ -2,6 +2,9 @@
 //!
 //! Provides unified color handling for all PPTX elements.
 
+use std::fmt;
+use std::str::FromStr;
+
 use crate::core::ToXml;
 
 /// RGB color (6-digit hex)
@@ -30,6 +33,11 @@ impl RgbColor {
         Some(Self { r, g, b })
     }
 
+    /// Create from a `0xRRGGBB` value
+    pub const fn from_u32(rgb: u32) -> Self {
+        Self { r: (rgb >> 16) as u8, g: (rgb >> 8) as u8, b: rgb as u8 }
+    }
+
     /// Convert to hex string (uppercase, no #)
     pub fn to_hex(&self) -> String {
         format!("{:02X}{:02X}{:02X}", self.r, self.g, self.b)
@@ -99,6 +107,27 @@ impl SchemeColor {
     }
 }
 
+impl SchemeColor {
+    /// RGB value of this slot in the generator's built-in theme
+    pub fn default_rgb(&self) -> RgbColor {
+        let rgb = match self {
+            SchemeColor::Dark1 | SchemeColor::Text1 => 0x000000,
+            SchemeColor::Light1 | SchemeColor::Background1 => 0xFFFFFF,
+            SchemeColor::Dark2 | SchemeColor::Text2 => 0x1F497D,
+            SchemeColor::Light2 | SchemeColor::Background2 => 0xEEECE1,
+            SchemeColor::Accent1 => 0x4F81BD,
+            SchemeColor::Accent2 => 0xC0504D,
+            SchemeColor::Accent3 => 0x9BBB59,
+            SchemeColor::Accent4 => 0x8064A2,
+            SchemeColor::Accent5 => 0x4BACC6,
+            SchemeColor::Accent6 => 0xF79646,
+            SchemeColor::Hyperlink => 0x0000FF,
+            SchemeColor::FollowedHyperlink => 0x800080,
+        };
+        RgbColor::from_u32(rgb)
+    }
+}
+
 impl ToXml for SchemeColor {
     fn to_xml(&self) -> String {
         format!(r#"<a:schemeClr val="{}"/>"#, self.as_str())
@@ -107,8 +136,11 @@ impl ToXml for SchemeColor {
 
 /// Unified color type
 #[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Color {
     Rgb(RgbColor),
+    /// RGB with alpha (0 = transparent, 255 = opaque), from `#RRGGBBAA`
+    Rgba(RgbColor, u8),
     Scheme(SchemeColor),
 }
 
@@ -120,24 +152,221 @@ impl Color {
 
     /// Create from hex string
     pub fn from_hex(hex: &str) -> Option<Self> {
-        RgbColor::from_hex(hex).map(Color::Rgb)
+        Self::parse_hex(hex).ok()
     }
 
     /// Create from scheme color
     pub fn scheme(color: SchemeColor) -> Self {
         Color::Scheme(color)
     }
+
+    /// Parse `RGB`, `RRGGBB` or `RRGGBBAA` hex, with or without a leading `#`
+    pub fn parse_hex(hex: &str) -> Result<Self, ColorParseError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        let error = || ColorParseError { input: hex.to_string() };
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(error());
+        }
+        let value = u32::from_str_radix(digits, 16).map_err(|_| error())?;
+        match digits.len() {
+            3 => {
+                let expand = |nibble: u32| (nibble & 0xF) * 0x11;
+                Ok(Color::Rgb(RgbColor::from_u32(
+                    expand(value >> 8) << 16 | expand(value >> 4) << 8 | expand(value),
+                )))
+            }
+            6 => Ok(Color::Rgb(RgbColor::from_u32(value))),
+            8 => Ok(Color::Rgba(RgbColor::from_u32(value >> 8), value as u8)),
+            _ => Err(error()),
+        }
+    }
+
+    /// Parse hex (see [`Color::parse_hex`]) or a CSS color name such as `rebeccapurple`
+    pub fn parse(text: &str) -> Result<Self, ColorParseError> {
+        let name = text.trim().to_ascii_lowercase();
+        match CSS_COLORS.binary_search_by(|(css, _)| css.cmp(&name.as_str())) {
+            Ok(i) => Ok(Color::Rgb(RgbColor::from_u32(CSS_COLORS[i].1))),
+            Err(_) => Self::parse_hex(text.trim()),
+        }
+    }
+
+    /// Uppercase `RRGGBB` hex (scheme colors use the built-in theme)
+    pub fn to_hex(&self) -> String {
+        match self {
+            Color::Rgb(rgb) | Color::Rgba(rgb, _) => rgb.to_hex(),
+            Color::Scheme(scheme) => scheme.default_rgb().to_hex(),
+        }
+    }
+
+    /// Alpha channel, if the color carries one
+    pub fn alpha(&self) -> Option<u8> {
+        match self {
+            Color::Rgba(_, alpha) => Some(*alpha),
+            _ => None,
+        }
+    }
 }
 
 impl ToXml for Color {
     fn to_xml(&self) -> String {
         match self {
             Color::Rgb(rgb) => rgb.to_xml(),
+            Color::Rgba(rgb, alpha) => format!(
+                r#"<a:srgbClr val="{}"><a:alpha val="{}"/></a:srgbClr>"#,
+                rgb.to_hex(),
+                *alpha as u32 * 100000 / 255
+            ),
             Color::Scheme(scheme) => scheme.to_xml(),
         }
     }
 }
 
+impl FromStr for Color {
+    type Err = ColorParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<RgbColor> for Color {
+    fn from(rgb: RgbColor) -> Self {
+        Color::Rgb(rgb)
+    }
+}
+
+impl From<SchemeColor> for Color {
+    fn from(scheme: SchemeColor) -> Self {
+        Color::Scheme(scheme)
+    }
+}
+
+/// Color argument of the color setters
+///
+/// A [`Color`], or text that [`Color::parse`] reads. Text that doesn't parse
+/// is kept as written instead of failing the setter, and build validation
+/// reports it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorInput(Result<Color, String>);
+
+impl ColorInput {
+    /// The parsed color, or the text that didn't parse
+    pub fn color(&self) -> Result<&Color, &str> {
+        self.0.as_ref().map_err(String::as_str)
+    }
+
+    /// Uppercase `RRGGBB` hex, or the rejected text without a leading `#`
+    pub fn to_hex(&self) -> String {
+        match &self.0 {
+            Ok(color) => color.to_hex(),
+            Err(text) => text.trim_start_matches('#').to_string(),
+        }
+    }
+
+    /// Alpha channel, if the color carries one
+    pub fn alpha(&self) -> Option<u8> {
+        self.0.as_ref().ok().and_then(Color::alpha)
+    }
+}
+
+impl From<&str> for ColorInput {
+    fn from(text: &str) -> Self {
+        ColorInput(Color::parse(text).map_err(|_| text.to_string()))
+    }
+}
+
+impl From<&String> for ColorInput {
+    fn from(text: &String) -> Self {
+        ColorInput::from(text.as_str())
+    }
+}
+
+impl From<String> for ColorInput {
+    fn from(text: String) -> Self {
+        ColorInput(Color::parse(&text).map_err(|_| text))
+    }
+}
+
+impl From<Color> for ColorInput {
+    fn from(color: Color) -> Self {
+        ColorInput(Ok(color))
+    }
+}
+
+impl From<RgbColor> for ColorInput {
+    fn from(rgb: RgbColor) -> Self {
+        ColorInput(Ok(Color::Rgb(rgb)))
+    }
+}
+
+impl From<SchemeColor> for ColorInput {
+    fn from(scheme: SchemeColor) -> Self {
+        ColorInput(Ok(Color::Scheme(scheme)))
+    }
+}
+
+/// Error returned by [`Color::parse`] and [`Color::parse_hex`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorParseError {
+    /// The rejected text
+    pub input: String,
+}
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid color \"{}\": expected #RGB, #RRGGBB, #RRGGBBAA or a CSS color name", self.input)
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+/// CSS named colors, sorted by name for binary search
+const CSS_COLORS: [(&str, u32); 148] = [
+    ("aliceblue", 0xF0F8FF), ("antiquewhite", 0xFAEBD7), ("aqua", 0x00FFFF),
+    ("aquamarine", 0x7FFFD4), ("azure", 0xF0FFFF), ("beige", 0xF5F5DC), ("bisque", 0xFFE4C4),
+    ("black", 0x000000), ("blanchedalmond", 0xFFEBCD), ("blue", 0x0000FF), ("blueviolet", 0x8A2BE2),
+    ("brown", 0xA52A2A), ("burlywood", 0xDEB887), ("cadetblue", 0x5F9EA0), ("chartreuse", 0x7FFF00),
+    ("chocolate", 0xD2691E), ("coral", 0xFF7F50), ("cornflowerblue", 0x6495ED),
+    ("cornsilk", 0xFFF8DC), ("crimson", 0xDC143C), ("cyan", 0x00FFFF), ("darkblue", 0x00008B),
+    ("darkcyan", 0x008B8B), ("darkgoldenrod", 0xB8860B), ("darkgray", 0xA9A9A9),
+    ("darkgreen", 0x006400), ("darkgrey", 0xA9A9A9), ("darkkhaki", 0xBDB76B),
+    ("darkmagenta", 0x8B008B), ("darkolivegreen", 0x556B2F), ("darkorange", 0xFF8C00),
+    ("darkorchid", 0x9932CC), ("darkred", 0x8B0000), ("darksalmon", 0xE9967A),
+    ("darkseagreen", 0x8FBC8F), ("darkslateblue", 0x483D8B), ("darkslategray", 0x2F4F4F),
+    ("darkslategrey", 0x2F4F4F), ("darkturquoise", 0x00CED1), ("darkviolet", 0x9400D3),
+    ("deeppink", 0xFF1493), ("deepskyblue", 0x00BFFF), ("dimgray", 0x696969), ("dimgrey", 0x696969),
+    ("dodgerblue", 0x1E90FF), ("firebrick", 0xB22222), ("floralwhite", 0xFFFAF0),
+    ("forestgreen", 0x228B22), ("fuchsia", 0xFF00FF), ("gainsboro", 0xDCDCDC),
+    ("ghostwhite", 0xF8F8FF), ("gold", 0xFFD700), ("goldenrod", 0xDAA520), ("gray", 0x808080),
+    ("green", 0x008000), ("greenyellow", 0xADFF2F), ("grey", 0x808080), ("honeydew", 0xF0FFF0),
+    ("hotpink", 0xFF69B4), ("indianred", 0xCD5C5C), ("indigo", 0x4B0082), ("ivory", 0xFFFFF0),
+    ("khaki", 0xF0E68C), ("lavender", 0xE6E6FA), ("lavenderblush", 0xFFF0F5),
+    ("lawngreen", 0x7CFC00), ("lemonchiffon", 0xFFFACD), ("lightblue", 0xADD8E6),
+    ("lightcoral", 0xF08080), ("lightcyan", 0xE0FFFF), ("lightgoldenrodyellow", 0xFAFAD2),
+    ("lightgray", 0xD3D3D3), ("lightgreen", 0x90EE90), ("lightgrey", 0xD3D3D3),
+    ("lightpink", 0xFFB6C1), ("lightsalmon", 0xFFA07A), ("lightseagreen", 0x20B2AA),
+    ("lightskyblue", 0x87CEFA), ("lightslategray", 0x778899), ("lightslategrey", 0x778899),
+    ("lightsteelblue", 0xB0C4DE), ("lightyellow", 0xFFFFE0), ("lime", 0x00FF00),
+    ("limegreen", 0x32CD32), ("linen", 0xFAF0E6), ("magenta", 0xFF00FF), ("maroon", 0x800000),
+    ("mediumaquamarine", 0x66CDAA), ("mediumblue", 0x0000CD), ("mediumorchid", 0xBA55D3),
+    ("mediumpurple", 0x9370DB), ("mediumseagreen", 0x3CB371), ("mediumslateblue", 0x7B68EE),
+    ("mediumspringgreen", 0x00FA9A), ("mediumturquoise", 0x48D1CC), ("mediumvioletred", 0xC71585),
+    ("midnightblue", 0x191970), ("mintcream", 0xF5FFFA), ("mistyrose", 0xFFE4E1),
+    ("moccasin", 0xFFE4B5), ("navajowhite", 0xFFDEAD), ("navy", 0x000080), ("oldlace", 0xFDF5E6),
+    ("olive", 0x808000), ("olivedrab", 0x6B8E23), ("orange", 0xFFA500), ("orangered", 0xFF4500),
+    ("orchid", 0xDA70D6), ("palegoldenrod", 0xEEE8AA), ("palegreen", 0x98FB98),
+    ("paleturquoise", 0xAFEEEE), ("palevioletred", 0xDB7093), ("papayawhip", 0xFFEFD5),
+    ("peachpuff", 0xFFDAB9), ("peru", 0xCD853F), ("pink", 0xFFC0CB), ("plum", 0xDDA0DD),
+    ("powderblue", 0xB0E0E6), ("purple", 0x800080), ("rebeccapurple", 0x663399), ("red", 0xFF0000),
+    ("rosybrown", 0xBC8F8F), ("royalblue", 0x4169E1), ("saddlebrown", 0x8B4513),
+    ("salmon", 0xFA8072), ("sandybrown", 0xF4A460), ("seagreen", 0x2E8B57), ("seashell", 0xFFF5EE),
+    ("sienna", 0xA0522D), ("silver", 0xC0C0C0), ("skyblue", 0x87CEEB), ("slateblue", 0x6A5ACD),
+    ("slategray", 0x708090), ("slategrey", 0x708090), ("snow", 0xFFFAFA), ("springgreen", 0x00FF7F),
+    ("steelblue", 0x4682B4), ("tan", 0xD2B48C), ("teal", 0x008080), ("thistle", 0xD8BFD8),
+    ("tomato", 0xFF6347), ("turquoise", 0x40E0D0), ("violet", 0xEE82EE), ("wheat", 0xF5DEB3),
+    ("white", 0xFFFFFF), ("whitesmoke", 0xF5F5F5), ("yellow", 0xFFFF00), ("yellowgreen", 0x9ACD32),
+];
+
 impl Default for Color {
     fn default() -> Self {
         Color::Rgb(RgbColor::black())
@@ -180,6 +409,32 @@ mod tests {
         assert_eq!(scheme.to_xml(), r#"<a:schemeClr val="dk1"/>"#);
     }
 
+    #[test]
+    fn test_parse_color_forms() {
+        assert_eq!(Color::parse_hex("#f80").unwrap().to_hex(), "FF8800");
+        assert_eq!(Color::parse_hex("1f4e79").unwrap(), Color::rgb(0x1F, 0x4E, 0x79));
+        let rgba = Color::parse_hex("#FF000080").unwrap();
+        assert_eq!((rgba.to_hex(), rgba.alpha()), ("FF0000".to_string(), Some(0x80)));
+        assert_eq!(rgba.to_xml(), r#"<a:srgbClr val="FF0000"><a:alpha val="50196"/></a:srgbClr>"#);
+
+        assert_eq!(Color::parse("RebeccaPurple").unwrap().to_hex(), "663399");
+        assert_eq!("navy".parse::<Color>().unwrap().to_hex(), "000080");
+        assert_eq!(Color::from(SchemeColor::Accent1).to_hex(), "4F81BD");
+    }
+
+    #[test]
+    fn test_parse_color_errors() {
+        let err = Color::parse("redd").unwrap_err();
+        assert_eq!(err.input, "redd");
+        assert!(err.to_string().contains("#RRGGBB"));
+        assert!(Color::parse_hex("#12345").is_err());
+        assert!(Color::parse_hex("+12345").is_err());
+        assert_eq!(ColorInput::from("redd").color(), Err("redd"));
+        assert_eq!(ColorInput::from("#redd").to_hex(), "redd");
+        assert_eq!(ColorInput::from("teal").color(), Ok(&Color::rgb(0, 0x80, 0x80)));
+        assert!(CSS_COLORS.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
     #[test]
     fn test_common_colors() {
         assert_eq!(RgbColor::black().to_hex(), "000000");
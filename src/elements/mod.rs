@@ -26,7 +26,7 @@
 mod color;
 mod position;
 
-pub use color::{Color, RgbColor, SchemeColor};
+pub use color::{Color, ColorInput, RgbColor, SchemeColor, ColorParseError};
 pub use position::{Position, Size, Transform, EMU_PER_INCH, EMU_PER_CM, EMU_PER_MM, EMU_PER_PT};
 
 // Re-export core traits
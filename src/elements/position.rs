@@ -7,11 +7,7 @@
 
 use crate::core::ToXml;
 
-/// EMU conversion constants
-pub const EMU_PER_INCH: i64 = 914400;
-pub const EMU_PER_CM: i64 = 360000;
-pub const EMU_PER_MM: i64 = 36000;
-pub const EMU_PER_PT: i64 = 12700;
+pub use crate::core::units::{EMU_PER_INCH, EMU_PER_CM, EMU_PER_MM, EMU_PER_PT};
 
 /// Position in EMU
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
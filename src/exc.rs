@@ -34,6 +34,24 @@ pub enum PptxError {
 
     #[error("Invalid operation: {0}")]
     InvalidOperation(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Deadline exceeded")]
+    DeadlineExceeded,
+
+    #[error("Memory budget exceeded: {used} of {limit} bytes")]
+    BudgetExceeded { used: usize, limit: usize },
+
+    #[error("Validation failed at {location}: {message}")]
+    Validation { location: String, message: String },
+
+    #[error("File is password-protected (encrypted); open it with a password")]
+    Encrypted,
+
+    #[error("Wrong password")]
+    WrongPassword,
 }
 
 pub type Result<T> = std::result::Result<T, PptxError>;
@@ -1,5 +1,6 @@
 use crate::api::Presentation;
-use crate::generator::{SlideContent, Image};
+use crate::generator::ir::{Element, Slide, TextRole};
+use crate::generator::{SlideContent, SlideSize, Image};
 use crate::exc::Result;
 
 /// Export a presentation to a single HTML file
@@ -20,13 +21,14 @@ pub fn export_to_html(presentation: &Presentation) -> Result<String> {
     html.push_str("</head>\n<body>\n");
     
     // Title Slide (Presentation Title)
-    html.push_str("<div class=\"slide title-slide\">\n");
+    let size = presentation.get_slide_size();
+    html.push_str(&format!("<div class=\"slide title-slide\" style=\"{}\">\n", aspect_ratio(size)));
     html.push_str(&format!("<h1>{}</h1>\n", presentation.get_title()));
     html.push_str("</div>\n");
     
     // Slides
     for (i, slide) in presentation.slides().iter().enumerate() {
-        html.push_str(&render_slide(slide, i + 1));
+        html.push_str(&render_slide(slide, size, i + 1));
     }
     
     html.push_str("</body>\n</html>");
@@ -34,43 +36,58 @@ pub fn export_to_html(presentation: &Presentation) -> Result<String> {
     Ok(html)
 }
 
-fn render_slide(slide: &SlideContent, index: usize) -> String {
+/// Inline style giving a slide div the shape of `size`
+fn aspect_ratio(size: SlideSize) -> String {
+    format!("aspect-ratio: {} / {}", size.width, size.height)
+}
+
+fn render_slide(slide: &SlideContent, size: SlideSize, index: usize) -> String {
+    let slide = Slide::from_content(slide, size);
     let mut html = String::new();
     
-    html.push_str(&format!("<div class=\"slide\" id=\"slide-{}\">\n", index));
+    html.push_str(&format!("<div class=\"slide\" id=\"slide-{}\" style=\"{}\">\n", index, aspect_ratio(size)));
     
     // Slide Number
     html.push_str(&format!("<div class=\"slide-number\">{}</div>\n", index));
     
     // Title
-    html.push_str(&format!("<h2>{}</h2>\n", slide.title));
+    if let Some(title) = slide.title() {
+        html.push_str(&format!("<h2>{}</h2>\n", title.text()));
+    }
     
     // Content Container
     html.push_str("<div class=\"content\">\n");
     
-    // Bullets / Content
-    if !slide.content.is_empty() {
+    // Bullets / Content, across columns
+    let bullets: Vec<_> = slide
+        .text_boxes()
+        .filter(|t| t.role != TextRole::Title)
+        .flat_map(|t| &t.paragraphs)
+        .collect();
+    if !bullets.is_empty() {
         html.push_str("<ul>\n");
-        for item in &slide.content {
-            html.push_str(&format!("<li>{}</li>\n", item));
+        for item in bullets {
+            html.push_str(&format!("<li>{}</li>\n", item.text));
         }
         html.push_str("</ul>\n");
     }
     
-    // Images
-    for image in &slide.images {
-        if let Some(img_html) = render_image(image) {
-            html.push_str(&img_html);
+    for element in &slide.elements {
+        match element {
+            Element::Picture(image) => {
+                if let Some(img_html) = render_image(image) {
+                    html.push_str(&img_html);
+                }
+            }
+            Element::Code(code) => {
+                html.push_str("<pre><code>");
+                html.push_str(&code.code);
+                html.push_str("</code></pre>\n");
+            }
+            _ => {}
         }
     }
     
-    // Code Blocks
-    for code in &slide.code_blocks {
-        html.push_str("<pre><code>");
-        html.push_str(&code.code);
-        html.push_str("</code></pre>\n");
-    }
-    
     html.push_str("</div>\n"); // content
     html.push_str("</div>\n"); // slide
     
@@ -101,7 +118,7 @@ fn render_image(image: &Image) -> Option<String> {
 }
 
 // Simple base64 encoder
-fn base64_encode(data: &[u8]) -> String {
+pub(crate) fn base64_encode(data: &[u8]) -> String {
     const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     let mut output = String::with_capacity(data.len() * 4 / 3 + 4);
     
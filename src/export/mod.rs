@@ -3,3 +3,10 @@
 //! Exports presentations to various formats.
 
 pub mod html;
+pub mod notes;
+pub mod svg;
+pub mod visual_diff;
+
+pub use notes::notes_script;
+pub use svg::slide_to_svg;
+pub use visual_diff::{SlideChange, SlideDiff, VisualDiff};
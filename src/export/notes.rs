@@ -0,0 +1,68 @@
+//! Speaker notes script export
+//!
+//! Collects slide numbers, titles and speaker notes into a Markdown
+//! narration script that presenters can read or rehearse from.
+
+use crate::api::Presentation;
+
+/// Placeholder written for slides without speaker notes
+const NO_NOTES: &str = "_No speaker notes._";
+
+/// Build a Markdown narration script from the presentation's speaker notes
+///
+/// Each slide becomes a `## Slide N: Title` section followed by its notes.
+/// The output reads fine as plain text as well.
+pub fn notes_script(presentation: &Presentation) -> String {
+    let mut script = String::new();
+
+    let title = presentation.get_title();
+    if !title.is_empty() {
+        script.push_str(&format!("# {}\n\n", title));
+    }
+
+    for (i, slide) in presentation.slides().iter().enumerate() {
+        let slide_title = slide.title.trim();
+        if slide_title.is_empty() {
+            script.push_str(&format!("## Slide {}\n\n", i + 1));
+        } else {
+            script.push_str(&format!("## Slide {}: {}\n\n", i + 1, slide_title));
+        }
+
+        match slide.notes.as_deref().map(str::trim) {
+            Some(notes) if !notes.is_empty() => script.push_str(notes),
+            _ => script.push_str(NO_NOTES),
+        }
+        script.push_str("\n\n");
+    }
+
+    let trimmed_len = script.trim_end().len();
+    script.truncate(trimmed_len);
+    script.push('\n');
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::SlideContent;
+
+    #[test]
+    fn test_notes_script_sections() {
+        let pres = Presentation::with_title("Quarterly Review")
+            .add_slide(SlideContent::new("Welcome").notes("Thank everyone for coming."))
+            .add_slide(SlideContent::new("Numbers"));
+
+        let script = notes_script(&pres);
+        assert!(script.starts_with("# Quarterly Review\n\n"));
+        assert!(script.contains("## Slide 1: Welcome\n\nThank everyone for coming."));
+        assert!(script.contains("## Slide 2: Numbers\n\n_No speaker notes._"));
+        assert!(script.ends_with("_No speaker notes._\n"));
+    }
+
+    #[test]
+    fn test_notes_script_untitled_slide() {
+        let pres = Presentation::new().add_slide(SlideContent::new("").notes("Intro"));
+        let script = notes_script(&pres);
+        assert_eq!(script, "## Slide 1\n\nIntro\n");
+    }
+}
@@ -0,0 +1,333 @@
+//! SVG rendering of slides
+//!
+//! An approximate, dependency-free rendering of the slide's
+//! [intermediate model](crate::generator::ir): titles and bullets in their
+//! layout's text boxes, shapes with their fill and outline, tables as cell
+//! grids, charts as bar sketches of their data, and images and code blocks as
+//! boxes. It shows layout, text and color, which is what
+//! [`visual_diff`](super::visual_diff) compares; it is not a substitute for
+//! PowerPoint's renderer.
+
+use crate::core::{escape_xml, Emu};
+use crate::generator::ir::{Element, Slide, TextBox, TextRole};
+use crate::generator::{ShapeType, SlideContent, SlideSize};
+
+/// EMU per SVG pixel (96 dpi)
+const EMU_PER_PX: f64 = 9525.0;
+
+/// Default row height of tables without an explicit one (0.4 inch)
+const TABLE_ROW_HEIGHT: u32 = 365760;
+
+/// Office's default series colors
+const SERIES_COLORS: [&str; 6] = ["4472C4", "ED7D31", "A5A5A5", "FFC000", "5B9BD5", "70AD47"];
+
+/// Drawing primitive in pixel coordinates
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Primitive {
+    Rect { x: f64, y: f64, w: f64, h: f64, fill: Option<String>, stroke: Option<String> },
+    Ellipse { x: f64, y: f64, w: f64, h: f64, fill: Option<String>, stroke: Option<String> },
+    Line { x1: f64, y1: f64, x2: f64, y2: f64, color: String },
+    /// Single line of text; `y` is the baseline
+    Text { x: f64, y: f64, size: f64, color: String, bold: bool, text: String },
+}
+
+fn px(emu: impl Into<Emu>) -> f64 {
+    emu.into().get() as f64 / EMU_PER_PX
+}
+
+/// Points to pixels
+fn pt(points: u32) -> f64 {
+    points as f64 * 96.0 / 72.0
+}
+
+/// Rendered `(width, height)` of a slide of `size` in pixels
+pub(crate) fn canvas(size: SlideSize) -> (f64, f64) {
+    (px(size.width), px(size.height))
+}
+
+/// Render `slide` on a slide of `size` as a standalone SVG document
+pub fn slide_to_svg(slide: &SlideContent, size: SlideSize) -> String {
+    primitives_to_svg(&scene(slide, size), size)
+}
+
+/// Drawing primitives for `slide` laid out on `size`, back to front
+pub(crate) fn scene(slide: &SlideContent, size: SlideSize) -> Vec<Primitive> {
+    let (w, h) = canvas(size);
+    let mut out = vec![Primitive::Rect { x: 0.0, y: 0.0, w, h, fill: Some("FFFFFF".to_string()), stroke: None }];
+    for element in &Slide::from_content(slide, size).elements {
+        element_primitives(element, &mut out);
+    }
+    out
+}
+
+fn element_primitives(element: &Element, out: &mut Vec<Primitive>) {
+    match element {
+        Element::Band { frame, fill } => {
+            if let Some(stop) = fill.stops.first() {
+                out.push(Primitive::Rect {
+                    x: px(frame.x),
+                    y: px(frame.y),
+                    w: px(frame.width),
+                    h: px(frame.height),
+                    fill: Some(stop.color.clone()),
+                    stroke: None,
+                });
+            }
+        }
+        Element::Text(text) if text.role == TextRole::Title => {
+            if let Some(title) = text.paragraphs.first().filter(|p| !p.text.is_empty()) {
+                let size = pt(text.style.size);
+                out.push(Primitive::Text {
+                    x: px(text.frame.x),
+                    y: px(text.frame.y) + (px(text.frame.height) + size) / 2.0,
+                    size,
+                    color: text.style.color.clone().unwrap_or_else(|| "000000".to_string()),
+                    bold: text.style.bold,
+                    text: title.text.clone(),
+                });
+            }
+        }
+        Element::Text(text) => bullets(text, out),
+        Element::Table(table) => {
+            let mut y = px(table.y);
+            for row in &table.rows {
+                let h = px(row.height.unwrap_or(TABLE_ROW_HEIGHT));
+                let mut x = px(table.x);
+                for (cell, width) in row.cells.iter().zip(&table.column_widths) {
+                    let w = px(*width);
+                    out.push(Primitive::Rect { x, y, w, h, fill: cell.background_color.clone(), stroke: Some("808080".to_string()) });
+                    let size = pt(cell.font_size.unwrap_or(18));
+                    out.push(Primitive::Text {
+                        x: x + 4.0,
+                        y: y + (h + size) / 2.0,
+                        size,
+                        color: cell.text_color.clone().unwrap_or_else(|| "000000".to_string()),
+                        bold: cell.bold,
+                        text: cell.text.clone(),
+                    });
+                    x += w;
+                }
+                y += h;
+            }
+        }
+        Element::Shape(shape) => {
+            let (x, y, w, h) = (px(shape.x), px(shape.y), px(shape.width), px(shape.height));
+            let fill = match (&shape.fill, &shape.gradient) {
+                (Some(fill), _) => Some(fill.color.clone()),
+                (None, Some(gradient)) => gradient.stops.first().map(|s| s.color.clone()),
+                (None, None) => None,
+            };
+            let stroke = shape.line.as_ref().map(|l| l.color.clone());
+            out.push(match shape.shape_type {
+                ShapeType::Ellipse | ShapeType::Circle => Primitive::Ellipse { x, y, w, h, fill, stroke },
+                _ => Primitive::Rect { x, y, w, h, fill, stroke },
+            });
+            if let Some(text) = shape.text.as_deref().filter(|t| !t.is_empty()) {
+                let size = pt(18);
+                out.push(Primitive::Text {
+                    x: x + 8.0,
+                    y: y + (h + size) / 2.0,
+                    size,
+                    color: "000000".to_string(),
+                    bold: false,
+                    text: text.to_string(),
+                });
+            }
+        }
+        Element::Connector(connector) => {
+            out.push(Primitive::Line {
+                x1: px(connector.start_x),
+                y1: px(connector.start_y),
+                x2: px(connector.end_x),
+                y2: px(connector.end_y),
+                color: connector.line.color.clone(),
+            });
+        }
+        Element::Picture(image) => {
+            out.push(Primitive::Rect {
+                x: px(image.x),
+                y: px(image.y),
+                w: px(image.width),
+                h: px(image.height),
+                fill: Some("D9D9D9".to_string()),
+                stroke: Some("808080".to_string()),
+            });
+        }
+        Element::Chart(chart) => {
+            let (x, y, w, h) = (px(chart.x), px(chart.y), px(chart.width), px(chart.height));
+            out.push(Primitive::Rect { x, y, w, h, fill: None, stroke: Some("BFBFBF".to_string()) });
+            out.push(Primitive::Text {
+                x: x + 8.0,
+                y: y + 24.0,
+                size: pt(14),
+                color: "000000".to_string(),
+                bold: true,
+                text: chart.title.clone(),
+            });
+            let max = chart.series.iter().flat_map(|s| &s.values).fold(0.0_f64, |m, v| m.max(v.abs()));
+            let bars = chart.series.len() * chart.category_count().max(1);
+            if max > 0.0 && bars > 0 {
+                let (plot_y, plot_h) = (y + 36.0, h - 44.0);
+                let bar_w = (w - 16.0) / bars as f64;
+                for (s, series) in chart.series.iter().enumerate() {
+                    let color = series.color.clone().unwrap_or_else(|| SERIES_COLORS[s % SERIES_COLORS.len()].to_string());
+                    for (c, value) in series.values.iter().enumerate() {
+                        let bar_h = plot_h * value.abs() / max;
+                        out.push(Primitive::Rect {
+                            x: x + 8.0 + (c * chart.series.len() + s) as f64 * bar_w,
+                            y: plot_y + plot_h - bar_h,
+                            w: bar_w * 0.8,
+                            h: bar_h,
+                            fill: Some(color.clone()),
+                            stroke: None,
+                        });
+                    }
+                }
+            }
+        }
+        Element::Code(block) => {
+            let (x, y) = (px(block.x), px(block.y));
+            out.push(Primitive::Rect {
+                x,
+                y,
+                w: px(block.width),
+                h: px(block.height),
+                fill: Some("1E1E1E".to_string()),
+                stroke: None,
+            });
+            for (i, line) in block.code.lines().enumerate() {
+                out.push(Primitive::Text {
+                    x: x + 8.0,
+                    y: y + 20.0 + i as f64 * 18.0,
+                    size: pt(12),
+                    color: "FFFFFF".to_string(),
+                    bold: false,
+                    text: line.to_string(),
+                });
+            }
+        }
+    }
+}
+
+/// Bullet lines of a body text box, cut off at its bottom
+fn bullets(text: &TextBox, out: &mut Vec<Primitive>) {
+    let base_color = text.style.color.clone().unwrap_or_else(|| "000000".to_string());
+    let bottom = px(text.frame.y + text.frame.height);
+    let mut y = px(text.frame.y);
+    for paragraph in &text.paragraphs {
+        let format = paragraph.format.as_ref();
+        let size = pt(format.and_then(|f| f.font_size).unwrap_or(text.style.size));
+        y += size * 1.5;
+        if y > bottom {
+            break;
+        }
+        let x = px(text.frame.x) + 24.0 + paragraph.level as f64 * 36.0;
+        let color = format.and_then(|f| f.color.clone()).unwrap_or_else(|| base_color.clone());
+        out.push(Primitive::Ellipse {
+            x: x - 18.0,
+            y: y - size * 0.45,
+            w: size * 0.25,
+            h: size * 0.25,
+            fill: Some(color.clone()),
+            stroke: None,
+        });
+        let max_chars = (px(text.frame.width) / (size * 0.5)) as usize;
+        let line = if paragraph.text.chars().count() > max_chars {
+            paragraph.text.chars().take(max_chars).collect()
+        } else {
+            paragraph.text.clone()
+        };
+        out.push(Primitive::Text { x, y, size, color, bold: text.style.bold, text: line });
+    }
+}
+
+/// Serialize primitives as an SVG document the size of a `size` slide
+pub(crate) fn primitives_to_svg(primitives: &[Primitive], size: SlideSize) -> String {
+    let paint = |color: &Option<String>| color.as_ref().map_or("none".to_string(), |c| format!("#{c}"));
+    let (width, height) = canvas(size);
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {width} {height}\" width=\"{width}\" height=\"{height}\">\n"
+    );
+    for primitive in primitives {
+        match primitive {
+            Primitive::Rect { x, y, w, h, fill, stroke } => svg.push_str(&format!(
+                "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{w:.1}\" height=\"{h:.1}\" fill=\"{}\" stroke=\"{}\"/>\n",
+                paint(fill),
+                paint(stroke)
+            )),
+            Primitive::Ellipse { x, y, w, h, fill, stroke } => svg.push_str(&format!(
+                "<ellipse cx=\"{:.1}\" cy=\"{:.1}\" rx=\"{:.1}\" ry=\"{:.1}\" fill=\"{}\" stroke=\"{}\"/>\n",
+                x + w / 2.0,
+                y + h / 2.0,
+                w / 2.0,
+                h / 2.0,
+                paint(fill),
+                paint(stroke)
+            )),
+            Primitive::Line { x1, y1, x2, y2, color } => svg.push_str(&format!(
+                "<line x1=\"{x1:.1}\" y1=\"{y1:.1}\" x2=\"{x2:.1}\" y2=\"{y2:.1}\" stroke=\"#{color}\" stroke-width=\"2\"/>\n"
+            )),
+            Primitive::Text { x, y, size, color, bold, text } => svg.push_str(&format!(
+                "<text x=\"{x:.1}\" y=\"{y:.1}\" font-family=\"Calibri, Arial, sans-serif\" font-size=\"{size:.1}\" fill=\"#{color}\"{}>{}</text>\n",
+                if *bold { " font-weight=\"bold\"" } else { "" },
+                escape_xml(text)
+            )),
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{ChartBuilder, ChartSeries, ChartType, Shape, ShapeFill};
+
+    #[test]
+    fn test_slide_to_svg() {
+        let mut slide = SlideContent::new("Q3 <Results>").add_bullet("Revenue up").title_color("1565C0");
+        slide.shapes.push(Shape::new(ShapeType::Ellipse, 914400, 914400, 914400, 914400).with_fill(ShapeFill::new("FF0000")));
+        let svg = slide_to_svg(&slide, SlideSize::STANDARD);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("Q3 &lt;Results&gt;"));
+        assert!(svg.contains("fill=\"#1565C0\""));
+        assert!(svg.contains("Revenue up"));
+        assert!(svg.contains("<ellipse cx=\"144.0\" cy=\"144.0\" rx=\"48.0\" ry=\"48.0\" fill=\"#FF0000\""));
+    }
+
+    #[test]
+    fn test_svg_follows_slide_size() {
+        let slide = SlideContent::new("Roadmap").add_bullet(&"Milestone ".repeat(30));
+        let svg = slide_to_svg(&slide, SlideSize::WIDESCREEN);
+        assert!(svg.contains("viewBox=\"0 0 1280 720\""));
+        assert!(slide_to_svg(&slide, SlideSize::STANDARD).contains("viewBox=\"0 0 960 720\""));
+        // The body spans the wider slide, so more of the bullet fits
+        let bullet_len = |size| {
+            scene(&slide, size)
+                .iter()
+                .filter_map(|p| match p {
+                    Primitive::Text { text, .. } if text.starts_with("Milestone") => Some(text.len()),
+                    _ => None,
+                })
+                .max()
+                .unwrap()
+        };
+        assert!(bullet_len(SlideSize::WIDESCREEN) > bullet_len(SlideSize::STANDARD));
+    }
+
+    #[test]
+    fn test_chart_bars_follow_data() {
+        let chart = |values: Vec<f64>| {
+            let mut slide = SlideContent::new("");
+            slide.charts.push(
+                ChartBuilder::new("Sales", ChartType::Bar)
+                    .categories(vec!["A", "B"])
+                    .add_series(ChartSeries::new("2025", values))
+                    .build(),
+            );
+            scene(&slide, SlideSize::STANDARD)
+        };
+        assert_ne!(chart(vec![1.0, 2.0]), chart(vec![2.0, 1.0]));
+        assert_eq!(chart(vec![1.0, 2.0]), chart(vec![1.0, 2.0]));
+    }
+}
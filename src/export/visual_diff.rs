@@ -0,0 +1,348 @@
+//! Visual regression between two versions of a deck
+//!
+//! Both decks are rendered slide by slide with [`svg`](super::svg), the
+//! renderings are rasterized onto a coarse grid, and each slide is scored by
+//! the share of grid cells whose color changed. Slides scoring above the
+//! threshold, and slides that were added or removed, are flagged;
+//! [`VisualDiff::to_html`] writes a side-by-side report for CI artifacts:
+//!
+//! ```rust,ignore
+//! let diff = VisualDiff::compare(&Presentation::from_path("old.pptx")?, &Presentation::from_path("new.pptx")?, 0.001);
+//! std::fs::write("report.html", diff.to_html())?;
+//! ```
+//!
+//! Text is rasterized as a per-character pattern rather than real glyphs,
+//! so edits that keep the text length still register.
+
+use std::fmt;
+
+use super::svg::{Primitive, canvas, primitives_to_svg, scene};
+use crate::api::Presentation;
+use crate::generator::SlideSize;
+
+/// Raster grid columns
+const GRID_COLUMNS: usize = 320;
+
+/// Raster grid rows
+const GRID_ROWS: usize = 240;
+
+/// Default share of changed grid cells above which a slide counts as changed
+pub const DEFAULT_THRESHOLD: f64 = 0.001;
+
+/// How a slide differs between the two decks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SlideChange {
+    /// Rendered output differs by at most the threshold
+    Unchanged,
+    /// Rendered output differs by more than the threshold
+    Changed,
+    /// Slide only exists in the new deck
+    Added,
+    /// Slide only exists in the old deck
+    Removed,
+}
+
+/// Comparison of one slide position
+#[derive(Clone, Debug)]
+pub struct SlideDiff {
+    /// 1-based slide number
+    pub index: usize,
+    /// Verdict against the threshold
+    pub change: SlideChange,
+    /// Share of grid cells whose color changed (0.0 to 1.0)
+    pub score: f64,
+    /// Bounding box `(x, y, width, height)` of the changed area in SVG pixels
+    pub region: Option<(f64, f64, f64, f64)>,
+    /// Rendering of the old slide
+    pub old_svg: Option<String>,
+    /// Rendering of the new slide
+    pub new_svg: Option<String>,
+}
+
+/// Slide-by-slide visual comparison of two decks
+#[derive(Clone, Debug)]
+pub struct VisualDiff {
+    /// Score above which a slide counts as changed
+    pub threshold: f64,
+    /// One entry per slide position in either deck
+    pub slides: Vec<SlideDiff>,
+}
+
+impl VisualDiff {
+    /// Render and compare every slide of `old` and `new`
+    pub fn compare(old: &Presentation, new: &Presentation, threshold: f64) -> Self {
+        let count = old.slide_count().max(new.slide_count());
+        let (old_size, new_size) = (old.get_slide_size(), new.get_slide_size());
+        let slides = (0..count)
+            .map(|i| {
+                let old_scene = old.slides().get(i).map(|s| scene(s, old_size));
+                let new_scene = new.slides().get(i).map(|s| scene(s, new_size));
+                let (change, score, region) = match (&old_scene, &new_scene) {
+                    (Some(a), Some(b)) => {
+                        let (score, region) = compare_rasters(&rasterize(a, old_size), &rasterize(b, new_size), new_size);
+                        let change = if score > threshold { SlideChange::Changed } else { SlideChange::Unchanged };
+                        (change, score, region)
+                    }
+                    (None, _) => (SlideChange::Added, 1.0, None),
+                    (_, None) => (SlideChange::Removed, 1.0, None),
+                };
+                SlideDiff {
+                    index: i + 1,
+                    change,
+                    score,
+                    region,
+                    old_svg: old_scene.as_deref().map(|p| primitives_to_svg(p, old_size)),
+                    new_svg: new_scene.as_deref().map(|p| primitives_to_svg(p, new_size)),
+                }
+            })
+            .collect();
+        VisualDiff { threshold, slides }
+    }
+
+    /// Slides that changed, were added or were removed
+    pub fn flagged(&self) -> impl Iterator<Item = &SlideDiff> {
+        self.slides.iter().filter(|s| s.change != SlideChange::Unchanged)
+    }
+
+    /// Whether any slide is flagged
+    pub fn has_changes(&self) -> bool {
+        self.flagged().next().is_some()
+    }
+
+    /// Self-contained HTML report with old and new renderings side by side
+    ///
+    /// Flagged slides come first; the changed area is outlined in red on the
+    /// new rendering.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"UTF-8\">\n<title>Visual diff</title>\n<style>\n");
+        html.push_str(
+            "body{font-family:sans-serif;margin:24px}\n\
+             .slide{margin:16px 0;padding:12px;border:1px solid #ccc}\n\
+             .flagged{border:2px solid #d33}\n\
+             .pair{display:flex;gap:16px}\n\
+             .pair div{flex:1}\n\
+             svg{width:100%;height:auto;border:1px solid #999}\n",
+        );
+        html.push_str("</style>\n</head>\n<body>\n");
+        html.push_str(&format!(
+            "<h1>Visual diff</h1>\n<p>{} of {} slides flagged (threshold {:.2}% of the slide)</p>\n",
+            self.flagged().count(),
+            self.slides.len(),
+            self.threshold * 100.0
+        ));
+
+        let mut ordered: Vec<&SlideDiff> = self.flagged().collect();
+        ordered.extend(self.slides.iter().filter(|s| s.change == SlideChange::Unchanged));
+        for slide in ordered {
+            let flagged = slide.change != SlideChange::Unchanged;
+            html.push_str(&format!(
+                "<div class=\"slide{}\" id=\"slide-{}\">\n<h2>Slide {}: {} ({:.2}% changed)</h2>\n<div class=\"pair\">\n",
+                if flagged { " flagged" } else { "" },
+                slide.index,
+                slide.index,
+                slide.change,
+                slide.score * 100.0
+            ));
+            let column = |label: &str, svg: Option<String>| {
+                format!("<div><h3>{label}</h3>\n{}</div>\n", svg.unwrap_or_else(|| "<p>(no slide)</p>\n".to_string()))
+            };
+            html.push_str(&column("Old", slide.old_svg.clone()));
+            let new_svg = slide.new_svg.clone().map(|svg| match slide.region.filter(|_| flagged) {
+                Some((x, y, w, h)) => svg.replace(
+                    "</svg>",
+                    &format!(
+                        "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{w:.1}\" height=\"{h:.1}\" fill=\"#FF0000\" fill-opacity=\"0.15\" stroke=\"#FF0000\" stroke-width=\"3\"/>\n</svg>"
+                    ),
+                ),
+                None => svg,
+            });
+            html.push_str(&column("New", new_svg));
+            html.push_str("</div>\n</div>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+impl fmt::Display for SlideChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SlideChange::Unchanged => "unchanged",
+            SlideChange::Changed => "changed",
+            SlideChange::Added => "added",
+            SlideChange::Removed => "removed",
+        })
+    }
+}
+
+impl fmt::Display for VisualDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for slide in &self.slides {
+            writeln!(f, "Slide {}: {} ({:.2}%)", slide.index, slide.change, slide.score * 100.0)?;
+        }
+        Ok(())
+    }
+}
+
+type Raster = Vec<u32>;
+
+fn rgb(hex: &str) -> u32 {
+    u32::from_str_radix(hex, 16).unwrap_or(0)
+}
+
+/// Pixel `(width, height)` of one grid cell on a slide of `size`
+fn cell_size(size: SlideSize) -> (f64, f64) {
+    let (width, height) = canvas(size);
+    (width / GRID_COLUMNS as f64, height / GRID_ROWS as f64)
+}
+
+/// Paint grid cells whose centers satisfy `inside`
+fn paint(grid: &mut Raster, cell: (f64, f64), bounds: (f64, f64, f64, f64), color: u32, inside: impl Fn(f64, f64) -> bool) {
+    let (cell_w, cell_h) = cell;
+    let (x, y, w, h) = bounds;
+    let col = |v: f64| ((v / cell_w).floor().max(0.0) as usize).min(GRID_COLUMNS);
+    let row = |v: f64| ((v / cell_h).floor().max(0.0) as usize).min(GRID_ROWS);
+    for r in row(y)..row(y + h + cell_h).min(GRID_ROWS) {
+        for c in col(x)..col(x + w + cell_w).min(GRID_COLUMNS) {
+            let (cx, cy) = ((c as f64 + 0.5) * cell_w, (r as f64 + 0.5) * cell_h);
+            if inside(cx, cy) {
+                grid[r * GRID_COLUMNS + c] = color;
+            }
+        }
+    }
+}
+
+fn rasterize(primitives: &[Primitive], size: SlideSize) -> Raster {
+    let cell = cell_size(size);
+    let mut grid = vec![0xFFFFFF; GRID_COLUMNS * GRID_ROWS];
+    for primitive in primitives {
+        match primitive {
+            Primitive::Rect { x, y, w, h, fill, stroke } => {
+                let within = |px: f64, py: f64| px >= *x && px < x + w && py >= *y && py < y + h;
+                if let Some(fill) = fill {
+                    paint(&mut grid, cell, (*x, *y, *w, *h), rgb(fill), within);
+                }
+                if let Some(stroke) = stroke {
+                    let edge = 1.5;
+                    paint(&mut grid, cell, (*x, *y, *w, *h), rgb(stroke), |px, py| {
+                        within(px, py) && (px - x < edge || x + w - px < edge || py - y < edge || y + h - py < edge)
+                    });
+                }
+            }
+            Primitive::Ellipse { x, y, w, h, fill, stroke } => {
+                let (rx, ry) = (w / 2.0, h / 2.0);
+                let distance = |px: f64, py: f64| ((px - x - rx) / rx).powi(2) + ((py - y - ry) / ry).powi(2);
+                if let Some(color) = fill.as_ref().or(stroke.as_ref()) {
+                    paint(&mut grid, cell, (*x, *y, *w, *h), rgb(color), |px, py| distance(px, py) <= 1.0);
+                }
+            }
+            Primitive::Line { x1, y1, x2, y2, color } => {
+                let steps = (x2 - x1).abs().max((y2 - y1).abs()).ceil().max(1.0) as usize;
+                for i in 0..=steps {
+                    let t = i as f64 / steps as f64;
+                    let (px, py) = (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t);
+                    paint(&mut grid, cell, (px, py, 0.0, 0.0), rgb(color), |_, _| true);
+                }
+            }
+            Primitive::Text { x, y, size, color, text, .. } => {
+                // Each character is a 2x3 block pattern taken from its code point
+                let advance = size * 0.5;
+                let top = y - size * 0.8;
+                for (i, ch) in text.chars().enumerate().filter(|(_, c)| !c.is_whitespace()) {
+                    let left = x + i as f64 * advance;
+                    let bits = ((ch as u32).wrapping_mul(2_654_435_761) >> 26) | 1;
+                    for bit in 0..6 {
+                        if bits & (1 << bit) != 0 {
+                            let (bx, by) = (left + (bit % 2) as f64 * advance / 2.0, top + (bit / 2) as f64 * size * 0.3);
+                            paint(&mut grid, cell, (bx, by, advance / 2.0, size * 0.3), rgb(color), |px, py| {
+                                px >= bx && px < bx + advance / 2.0 && py >= by && py < by + size * 0.3
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    grid
+}
+
+/// Share of differing cells and the bounding box of the difference on a `size` slide
+fn compare_rasters(old: &Raster, new: &Raster, size: SlideSize) -> (f64, Option<(f64, f64, f64, f64)>) {
+    let mut changed = 0;
+    let (mut min_c, mut min_r, mut max_c, mut max_r) = (usize::MAX, usize::MAX, 0, 0);
+    for (i, (a, b)) in old.iter().zip(new).enumerate() {
+        if a != b {
+            changed += 1;
+            let (r, c) = (i / GRID_COLUMNS, i % GRID_COLUMNS);
+            (min_c, min_r, max_c, max_r) = (min_c.min(c), min_r.min(r), max_c.max(c), max_r.max(r));
+        }
+    }
+    if changed == 0 {
+        return (0.0, None);
+    }
+    let (cell_w, cell_h) = cell_size(size);
+    let region = (
+        min_c as f64 * cell_w,
+        min_r as f64 * cell_h,
+        (max_c - min_c + 1) as f64 * cell_w,
+        (max_r - min_r + 1) as f64 * cell_h,
+    );
+    (changed as f64 / old.len() as f64, Some(region))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{Shape, ShapeFill, ShapeType, SlideContent};
+
+    fn deck(title: &str, fill: &str) -> Presentation {
+        let mut slide = SlideContent::new(title).add_bullet("Revenue up 12%");
+        slide.shapes.push(Shape::new(ShapeType::Rectangle, 5486400, 3657600, 1828800, 914400).with_fill(ShapeFill::new(fill)));
+        Presentation::new().add_slide(slide).add_slide(SlideContent::new("Thanks"))
+    }
+
+    #[test]
+    fn test_identical_decks_are_unchanged() {
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &deck("Q3", "1565C0"), 0.0);
+        assert!(!diff.has_changes());
+        assert!(diff.slides.iter().all(|s| s.score == 0.0 && s.region.is_none()));
+    }
+
+    #[test]
+    fn test_changes_above_threshold_are_flagged() {
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &deck("Q3", "FF6F00"), DEFAULT_THRESHOLD);
+        assert_eq!(diff.slides[0].change, SlideChange::Changed);
+        assert_eq!(diff.slides[1].change, SlideChange::Unchanged);
+        // The changed area is the recolored shape
+        let (x, y, _, _) = diff.slides[0].region.unwrap();
+        assert!((x - 576.0).abs() < 4.0 && (y - 384.0).abs() < 4.0, "{:?}", diff.slides[0].region);
+
+        // Same-length title edit still registers
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &deck("Q4", "1565C0"), 0.0);
+        assert_eq!(diff.slides[0].change, SlideChange::Changed);
+
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &deck("Q3", "FF6F00"), 0.5);
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_added_and_removed_slides() {
+        let longer = deck("Q3", "1565C0").add_slide(SlideContent::new("Appendix"));
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &longer, DEFAULT_THRESHOLD);
+        assert_eq!(diff.slides[2].change, SlideChange::Added);
+        assert!(diff.slides[2].old_svg.is_none());
+        let diff = VisualDiff::compare(&longer, &deck("Q3", "1565C0"), DEFAULT_THRESHOLD);
+        assert_eq!(diff.slides[2].change, SlideChange::Removed);
+    }
+
+    #[test]
+    fn test_html_report() {
+        let diff = VisualDiff::compare(&deck("Q3", "1565C0"), &deck("Q3", "FF6F00"), DEFAULT_THRESHOLD);
+        let html = diff.to_html();
+        assert!(html.contains("1 of 2 slides flagged"));
+        assert!(html.contains("Slide 1: changed"));
+        assert!(html.contains("stroke=\"#FF0000\""));
+        assert!(html.find("slide-1").unwrap() < html.find("slide-2").unwrap());
+        assert_eq!(diff.to_string(), format!("Slide 1: changed ({:.2}%)\nSlide 2: unchanged (0.00%)\n", diff.slides[0].score * 100.0));
+    }
+}
@@ -0,0 +1,237 @@
+//! Screenshot annotations
+//!
+//! [`Annotations`] overlays numbered markers, highlight boxes and arrows on
+//! an image, for product walkthroughs. Positions are fractions of the image
+//! (`0.0`–`1.0` from its top-left corner), so they survive resizing. With
+//! [`Annotations::dim`], semi-transparent rectangles darken everything
+//! outside the highlight boxes. Add them to a slide with
+//! [`SlideContent::annotate`](super::SlideContent::annotate); they are drawn
+//! above the slide's images.
+
+use crate::core::Emu;
+use crate::elements::ColorInput;
+use super::connectors::{ArrowType, Connector};
+use super::images::Image;
+use super::shapes::{Shape, ShapeFill, ShapeLine, ShapeType};
+use super::text::FormattedText;
+
+/// Diameter of the numbered markers (0.4 inch)
+const MARKER_SIZE: u32 = 365760;
+/// Marker number font size in points
+const MARKER_FONT_SIZE: u32 = 14;
+/// Outline width of highlight boxes and arrows (2.25 pt)
+const LINE_WIDTH: u32 = 28575;
+
+/// Markers, highlights and arrows over one image
+///
+/// ```
+/// use ppt_rs::generator::{Annotations, Image, SlideContent};
+///
+/// let screenshot = Image::new("settings.png", 6_400_000, 4_000_000, "png").position(1_371_600, 1_600_200);
+/// let notes = Annotations::over(&screenshot)
+///     .highlight(0.05, 0.10, 0.30, 0.15)
+///     .marker(0.38, 0.12)
+///     .arrow((0.6, 0.5), (0.36, 0.2))
+///     .dim(40);
+///
+/// let slide = SlideContent::new("Open Settings").add_image(screenshot).annotate(notes);
+/// assert_eq!(slide.connectors.len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Annotations {
+    x: Emu,
+    y: Emu,
+    width: Emu,
+    height: Emu,
+    color: String,
+    dim: Option<u32>,
+    markers: Vec<(f64, f64)>,
+    highlights: Vec<(f64, f64, f64, f64)>,
+    arrows: Vec<((f64, f64), (f64, f64))>,
+}
+
+impl Annotations {
+    /// Annotate the area `image` covers
+    pub fn over(image: &Image) -> Self {
+        Annotations {
+            x: image.x,
+            y: image.y,
+            width: image.width,
+            height: image.height,
+            color: "E53935".to_string(),
+            dim: None,
+            markers: Vec::new(),
+            highlights: Vec::new(),
+            arrows: Vec::new(),
+        }
+    }
+
+    /// Set the color of markers, highlight outlines and arrows (red by default)
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = color.into().to_hex();
+        self
+    }
+
+    /// Numbered circle centered on (`x`, `y`); markers are numbered from 1 in the order added
+    pub fn marker(mut self, x: f64, y: f64) -> Self {
+        self.markers.push((x, y));
+        self
+    }
+
+    /// Outlined box with its top-left corner at (`x`, `y`)
+    pub fn highlight(mut self, x: f64, y: f64, width: f64, height: f64) -> Self {
+        self.highlights.push((x, y, width, height));
+        self
+    }
+
+    /// Arrow pointing from `from` to `to`
+    pub fn arrow(mut self, from: (f64, f64), to: (f64, f64)) -> Self {
+        self.arrows.push((from, to));
+        self
+    }
+
+    /// Darken the image outside the highlight boxes by `percent` (0-100)
+    pub fn dim(mut self, percent: u32) -> Self {
+        self.dim = Some(percent.min(100));
+        self
+    }
+
+    /// Image fraction to slide EMU, clamped to the image
+    fn point(&self, (fx, fy): (f64, f64)) -> (Emu, Emu) {
+        let x = self.x + self.width.scale(fx.clamp(0.0, 1.0));
+        let y = self.y + self.height.scale(fy.clamp(0.0, 1.0));
+        (x, y)
+    }
+
+    /// Highlight boxes in slide EMU as (left, top, right, bottom)
+    fn highlight_bounds(&self) -> Vec<(Emu, Emu, Emu, Emu)> {
+        self.highlights.iter().map(|&(x, y, w, h)| {
+            let (left, top) = self.point((x, y));
+            let (right, bottom) = self.point((x + w, y + h));
+            (left, top, right, bottom)
+        }).collect()
+    }
+
+    /// Dimming rectangles, highlight boxes and markers (back to front), and the arrows
+    pub fn layout(&self) -> (Vec<Shape>, Vec<Connector>) {
+        let mut shapes = Vec::new();
+        let highlights = self.highlight_bounds();
+
+        if let Some(percent) = self.dim {
+            let fill = ShapeFill::new("000000").with_transparency(100 - percent);
+            for (left, top, right, bottom) in self.dim_cells(&highlights) {
+                shapes.push(Shape::new(ShapeType::Rectangle, left, top, right - left, bottom - top)
+                    .with_fill(fill.clone())
+                    .with_name("Dim"));
+            }
+        }
+        for &(left, top, right, bottom) in &highlights {
+            shapes.push(Shape::new(ShapeType::Rectangle, left, top, right - left, bottom - top)
+                .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+                .with_line(ShapeLine::new(self.color.as_str(), LINE_WIDTH))
+                .with_name("Highlight"));
+        }
+        for (i, &marker) in self.markers.iter().enumerate() {
+            let (x, y) = self.point(marker);
+            let number = FormattedText::new(&(i + 1).to_string()).bold().color("FFFFFF").font_size(MARKER_FONT_SIZE);
+            shapes.push(Shape::new(ShapeType::Ellipse, x - Emu::from(MARKER_SIZE / 2), y - Emu::from(MARKER_SIZE / 2), MARKER_SIZE, MARKER_SIZE)
+                .with_fill(ShapeFill::new(self.color.as_str()))
+                .with_rich_text(vec![number])
+                .with_name(&format!("Marker {}", i + 1)));
+        }
+
+        let arrows = self.arrows.iter().map(|&(from, to)| {
+            let ((x1, y1), (x2, y2)) = (self.point(from), self.point(to));
+            Connector::straight(x1, y1, x2, y2)
+                .with_color(self.color.as_str())
+                .with_width(LINE_WIDTH)
+                .with_end_arrow(ArrowType::Triangle)
+        }).collect();
+        (shapes, arrows)
+    }
+
+    /// Parts of the image outside every highlight, as row-wise merged cells
+    ///
+    /// The image is cut along every highlight edge; cells no highlight
+    /// covers are dimmed, and neighbours in a row are joined.
+    fn dim_cells(&self, highlights: &[(Emu, Emu, Emu, Emu)]) -> Vec<(Emu, Emu, Emu, Emu)> {
+        let edges = |bounds: (Emu, Emu), cuts: &mut dyn Iterator<Item = Emu>| {
+            let mut edges: Vec<Emu> = cuts.filter(|&c| c > bounds.0 && c < bounds.1).collect();
+            edges.extend([bounds.0, bounds.1]);
+            edges.sort_unstable();
+            edges.dedup();
+            edges
+        };
+        let xs = edges((self.x, self.x + self.width), &mut highlights.iter().flat_map(|h| [h.0, h.2]));
+        let ys = edges((self.y, self.y + self.height), &mut highlights.iter().flat_map(|h| [h.1, h.3]));
+
+        let mut cells = Vec::new();
+        for row in ys.windows(2) {
+            let (top, bottom) = (row[0], row[1]);
+            let mut run: Option<(Emu, Emu)> = None;
+            for column in xs.windows(2) {
+                let (left, right) = (column[0], column[1]);
+                let covered = highlights.iter().any(|h| h.0 <= left && right <= h.2 && h.1 <= top && bottom <= h.3);
+                run = match (covered, run) {
+                    (false, Some((start, _))) => Some((start, right)),
+                    (false, None) => Some((left, right)),
+                    (true, Some((start, end))) => {
+                        cells.push((start, top, end, bottom));
+                        None
+                    }
+                    (true, None) => None,
+                };
+            }
+            if let Some((start, end)) = run {
+                cells.push((start, top, end, bottom));
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screenshot() -> Image {
+        Image::new("app.png", 1000, 500, "png").position(100, 100)
+    }
+
+    #[test]
+    fn test_dim_surrounds_single_highlight() {
+        let (shapes, _) = Annotations::over(&screenshot()).highlight(0.2, 0.2, 0.5, 0.4).dim(50).layout();
+        let dim: Vec<_> = shapes.iter().filter(|s| s.name.as_deref() == Some("Dim"))
+            .map(|s| (s.x.0, s.y.0, s.width.0, s.height.0)).collect();
+        // Strip above, left and right of the box, strip below
+        assert_eq!(dim, [(100, 100, 1000, 100), (100, 200, 200, 200), (800, 200, 300, 200), (100, 400, 1000, 200)]);
+        let dimmed: i64 = dim.iter().map(|d| d.2 * d.3).sum();
+        assert_eq!(dimmed, 1000 * 500 - 500 * 200);
+        assert_eq!(shapes.last().unwrap().name.as_deref(), Some("Highlight"));
+    }
+
+    #[test]
+    fn test_markers_are_numbered_and_centered() {
+        let image = Image::new("app.png", 4_000_000, 2_000_000, "png").position(1_000_000, 1_000_000);
+        let (shapes, arrows) = Annotations::over(&image).marker(0.5, 0.5).marker(0.0, 0.0).arrow((1.0, 1.0), (0.5, 0.5)).layout();
+        assert_eq!(shapes[0].rich_text[0].text, "1");
+        assert_eq!((shapes[0].x.0, shapes[0].y.0), (3_000_000 - 182_880, 2_000_000 - 182_880));
+        assert_eq!(shapes[1].name.as_deref(), Some("Marker 2"));
+        assert_eq!((arrows[0].start_x.0, arrows[0].end_y.0), (5_000_000, 2_000_000));
+        assert_eq!(arrows[0].end_arrow, ArrowType::Triangle);
+    }
+
+    #[test]
+    fn test_overlays_draw_above_images() {
+        use crate::generator::SlideContent;
+        use crate::generator::ir::{Element, Slide};
+        use crate::generator::SlideSize;
+
+        let slide = SlideContent::new("Walkthrough").add_image(screenshot())
+            .annotate(Annotations::over(&screenshot()).marker(0.5, 0.5));
+        let elements = Slide::from_content(&slide, SlideSize::STANDARD).elements;
+        let picture = elements.iter().position(|e| matches!(e, Element::Picture(_))).unwrap();
+        let marker = elements.iter().position(|e| matches!(e, Element::Shape(_))).unwrap();
+        assert!(picture < marker);
+    }
+}
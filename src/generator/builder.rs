@@ -1,6 +1,6 @@
 //! PPTX builder - orchestrates ZIP creation and file writing
 
-use std::io::{Write, Cursor};
+use std::io::{Write, Seek, Cursor};
 use zip::ZipWriter;
 use zip::write::FileOptions;
 use super::xml::*;
@@ -8,18 +8,53 @@ use super::notes_xml::*;
 use super::package_xml::{
     create_content_types_xml_with_notes_and_charts,
     create_presentation_rels_xml_with_notes,
-    create_slide_rels_xml_extended
+    create_slide_rels_xml_with_media,
 };
+use super::media::{MediaRelIds, NARRATION_ICON_PNG};
+use super::zoom::{ZoomRelIds, generate_zoom_thumbnail_with_size};
+use super::hyperlinks::{ActionRelIds, Hyperlink, HyperlinkAction};
+use super::print::{PrintSettings, create_pres_props_xml};
+use crate::instrument::{event, span};
+use super::progress::BuildProgress;
+use super::placement::SlideSize;
+use super::package_xml::{create_presentation_xml_with_ids, slide_id};
+use super::provenance::{Provenance, PROVENANCE_PART, create_provenance_item_props_xml, create_provenance_rels_xml};
 use crate::generator::charts::generate_chart_part_xml;
+use super::tags::{Tags, tags_content_type_xml, tags_relationship_xml};
+
+/// File name (under `ppt/media/`) of the shared, hidden narration icon
+const NARRATION_ICON_FILE: &str = "narrationIcon.png";
+
+/// Content type override for `ppt/presProps.xml`
+const PRES_PROPS_CONTENT_TYPE: &str = "<Override PartName=\"/ppt/presProps.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.presentationml.presProps+xml\"/>";
+const XLSX_CONTENT_TYPE: &str = "<Default Extension=\"xlsx\" ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet\"/>";
+const CUSTOM_XML_PROPS_CONTENT_TYPE: &str = "<Override PartName=\"/customXml/itemProps1.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.customXmlProperties+xml\"/>";
+
+/// Package-wide settings and optional parts
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct PackageOptions<'a> {
+    pub print: Option<&'a PrintSettings>,
+    pub slide_size: SlideSize,
+    pub provenance: Option<&'a Provenance>,
+    pub fonts: Option<&'a ThemeFonts>,
+}
 
 /// Create a minimal but valid PPTX file
 pub fn create_pptx(title: &str, slides: usize) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    create_pptx_with_options(title, slides, &PackageOptions::default())
+}
+
+/// [`create_pptx`] with package options (slide size, theme fonts)
+pub(crate) fn create_pptx_with_options(
+    title: &str,
+    slides: usize,
+    package: &PackageOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     let buffer = Vec::new();
     let cursor = Cursor::new(buffer);
     let mut zip = ZipWriter::new(cursor);
-    let options = FileOptions::default();
 
-    write_package_files(&mut zip, &options, title, slides, None)?;
+    write_package_files(&mut zip, title, slides, None, package, &mut BuildProgress::new())?;
 
     let cursor = zip.finish()?;
     Ok(cursor.into_inner())
@@ -30,25 +65,115 @@ pub fn create_pptx_with_content(
     title: &str,
     slides: Vec<super::xml::SlideContent>,
 ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
-    let buffer = Vec::new();
-    let cursor = Cursor::new(buffer);
-    let mut zip = ZipWriter::new(cursor);
-    let options = FileOptions::default();
+    let cursor = write_pptx_with_content(Cursor::new(Vec::new()), title, slides, None)?;
+    Ok(cursor.into_inner())
+}
 
-    write_package_files(&mut zip, &options, title, slides.len(), Some(&slides))?;
+/// Create a PPTX file with custom slide content and default print settings
+///
+/// The settings are written to `ppt/presProps.xml`, where PowerPoint picks
+/// them up for printing and PDF export.
+pub fn create_pptx_with_print_settings(
+    title: &str,
+    slides: Vec<super::xml::SlideContent>,
+    print: &PrintSettings,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let cursor = write_pptx_with_content(Cursor::new(Vec::new()), title, slides, Some(print))?;
+    Ok(cursor.into_inner())
+}
 
-    let cursor = zip.finish()?;
+/// Write a PPTX package with custom slide content to any seekable writer
+///
+/// The generator itself never touches the file system, so this works the
+/// same for a `File`, an in-memory `Cursor` or a WASM buffer. Returns the
+/// writer once the archive is finished.
+pub fn write_pptx_with_content<W: Write + Seek>(
+    writer: W,
+    title: &str,
+    slides: Vec<super::xml::SlideContent>,
+    print: Option<&PrintSettings>,
+) -> Result<W, Box<dyn std::error::Error>> {
+    write_pptx_with_progress(writer, title, slides, print, SlideSize::STANDARD, BuildProgress::new())
+}
+
+/// Create a PPTX file, reporting progress and honoring cancellation
+///
+/// `progress` is called after each slide; if its cancel token fires, the
+/// build stops before the next slide and fails with
+/// [`BuildCancelled`](super::progress::BuildCancelled). If its memory budget
+/// is exhausted, it fails with [`BudgetExceeded`](super::progress::BudgetExceeded).
+pub fn create_pptx_with_progress(
+    title: &str,
+    slides: Vec<super::xml::SlideContent>,
+    progress: BuildProgress,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let cursor = write_pptx_with_progress(Cursor::new(Vec::new()), title, slides, None, SlideSize::STANDARD, progress)?;
     Ok(cursor.into_inner())
 }
 
+/// [`write_pptx_with_content`] with a slide size, progress reporting and cancellation
+///
+/// Shapes positioned with [`Shape::at`](super::Shape::at), charts and
+/// picture placeholders placed into a [`Region`](super::Region) and the
+/// layouts' title and body are resolved against `slide_size`, and every slide is validated (unless `progress`
+/// skips validation), before any slide is written.
+pub fn write_pptx_with_progress<W: Write + Seek>(
+    writer: W,
+    title: &str,
+    slides: Vec<super::xml::SlideContent>,
+    print: Option<&PrintSettings>,
+    slide_size: SlideSize,
+    progress: BuildProgress,
+) -> Result<W, Box<dyn std::error::Error>> {
+    let options = PackageOptions { print, slide_size, ..PackageOptions::default() };
+    write_pptx_with_options(writer, title, slides, &options, progress)
+}
+
+/// [`write_pptx_with_progress`] with all package options
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "write_pptx", level = "info", skip_all, fields(title = %title, slides = slides.len())))]
+pub(crate) fn write_pptx_with_options<W: Write + Seek>(
+    writer: W,
+    title: &str,
+    mut slides: Vec<super::xml::SlideContent>,
+    package: &PackageOptions,
+    mut progress: BuildProgress,
+) -> Result<W, Box<dyn std::error::Error>> {
+    let mut zip = ZipWriter::new(writer);
+
+    for slide in &mut slides {
+        slide.resolve_for_size(package.slide_size);
+    }
+    load_images(&mut slides);
+    if progress.validates() {
+        for (i, slide) in slides.iter().enumerate() {
+            slide.validate().map_err(|e| e.within(format!("slide {}", i + 1)))?;
+        }
+    }
+    write_package_files(&mut zip, title, slides.len(), Some(&slides), package, &mut progress)?;
+
+    let writer = {
+        let _span = span!(DEBUG, "zip_write");
+        zip.finish()?
+    };
+    event!(INFO, "package written");
+    Ok(writer)
+}
+
 /// Write all package files to the ZIP archive
 fn write_package_files(
-    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
-    options: &FileOptions,
+    zip: &mut ZipWriter<impl Write + Seek>,
     title: &str,
     slide_count: usize,
     custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    package: &PackageOptions,
+    progress: &mut BuildProgress,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let PackageOptions { print, slide_size, provenance, fonts } = *package;
+    let options = &FileOptions::default();
+    if let Some(slides) = custom_slides {
+        validate_slide_targets(slides)?;
+    }
+
     // Check if any slides have notes and calculate chart info
     let has_notes = custom_slides
         .map(|slides| slides.iter().any(|s| s.notes.is_some()))
@@ -56,15 +181,33 @@ fn write_package_files(
     
     let mut total_charts = 0;
     let mut slide_chart_start_indices = Vec::new();
+    let mut total_tags = 0;
+    let mut slide_tag_start_indices = Vec::new();
     if let Some(slides) = custom_slides {
         for slide in slides {
             slide_chart_start_indices.push(total_charts + 1);
             total_charts += slide.charts.len();
+            slide_tag_start_indices.push(total_tags + 1);
+            total_tags += slide_tag_lists(slide).count();
         }
     }
 
     // 1. Content types (with notes and charts)
-    let content_types = create_content_types_xml_with_notes_and_charts(slide_count, custom_slides, total_charts);
+    let mut content_types = create_content_types_xml_with_notes_and_charts(slide_count, custom_slides, total_charts);
+    if print.is_some() {
+        insert_before_closing_tag(&mut content_types, "</Types>", PRES_PROPS_CONTENT_TYPE);
+    }
+    let embeds_workbooks = custom_slides
+        .is_some_and(|slides| slides.iter().flat_map(|s| &s.charts).any(|c| c.data_source.is_some()));
+    if embeds_workbooks {
+        insert_before_closing_tag(&mut content_types, "</Types>", XLSX_CONTENT_TYPE);
+    }
+    if provenance.is_some() {
+        insert_before_closing_tag(&mut content_types, "</Types>", CUSTOM_XML_PROPS_CONTENT_TYPE);
+    }
+    for number in 1..=total_tags {
+        insert_before_closing_tag(&mut content_types, "</Types>", &tags_content_type_xml(number));
+    }
     zip.start_file("[Content_Types].xml", *options)?;
     zip.write_all(content_types.as_bytes())?;
 
@@ -74,24 +217,44 @@ fn write_package_files(
     zip.write_all(rels.as_bytes())?;
 
     // 3. Presentation relationships (with notes master if notes present)
-    let pres_rels = if has_notes {
+    let mut pres_rels = if has_notes {
         create_presentation_rels_xml_with_notes(slide_count)
     } else {
         create_presentation_rels_xml(slide_count)
     };
+    if print.is_some() {
+        // After the slides and the (optional) notes master
+        let rid = slide_count + 4;
+        insert_before_closing_tag(&mut pres_rels, "</Relationships>", &format!(
+            "    <Relationship Id=\"rId{rid}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/presProps\" Target=\"presProps.xml\"/>"
+        ));
+    }
+    if provenance.is_some() {
+        // After the presProps slot
+        let rid = slide_count + 5;
+        insert_before_closing_tag(&mut pres_rels, "</Relationships>", &format!(
+            "    <Relationship Id=\"rId{rid}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml\" Target=\"../{PROVENANCE_PART}\"/>"
+        ));
+    }
     zip.start_file("ppt/_rels/presentation.xml.rels", *options)?;
     zip.write_all(pres_rels.as_bytes())?;
 
     // 4. Presentation document
-    let presentation = create_presentation_xml(title, slide_count);
+    let first_slide_num = custom_slides
+        .and_then(|slides| slides.iter().find_map(|s| s.slide_number))
+        .map_or(1, |number| number.first);
+    let slide_ids: Vec<usize> = (1..=slide_count)
+        .map(|i| custom_slides.and_then(|slides| slides.get(i - 1)?.slide_id).map_or(slide_id(i), |id| id as usize))
+        .collect();
+    let presentation = create_presentation_xml_with_ids(&slide_ids, slide_size, first_slide_num);
     zip.start_file("ppt/presentation.xml", *options)?;
     zip.write_all(presentation.as_bytes())?;
 
     // 5. Slides (and notes if present)
-    write_slides(zip, options, slide_count, custom_slides)?;
+    write_slides(zip, options, slide_count, custom_slides, slide_size, progress)?;
 
     // 6. Slide relationships (with notes references if present)
-    write_slide_relationships_extended(zip, options, custom_slides, &slide_chart_start_indices, slide_count)?;
+    write_slide_relationships_extended(zip, options, custom_slides, &slide_chart_start_indices, &slide_tag_start_indices, slide_count)?;
 
     // 7. Notes relationships (if notes present)
     if has_notes {
@@ -129,7 +292,10 @@ fn write_package_files(
     zip.write_all(master_rels.as_bytes())?;
 
     // 12. Theme
-    let theme = create_theme_xml();
+    let theme = match fonts {
+        Some(fonts) => create_theme_xml_with_fonts(fonts),
+        None => create_theme_xml(),
+    };
     zip.start_file("ppt/theme/theme1.xml", *options)?;
     zip.write_all(theme.as_bytes())?;
 
@@ -145,60 +311,216 @@ fn write_package_files(
 
     // 15. Charts
     if total_charts > 0 {
-        write_charts(zip, options, custom_slides, &slide_chart_start_indices)?;
+        write_charts(zip, options, custom_slides, &slide_chart_start_indices, progress)?;
+    }
+
+    // 16. Narration audio
+    write_narration_media(zip, options, custom_slides, progress)?;
+
+    // 17. Slide zoom thumbnails
+    write_zoom_thumbnails(zip, options, custom_slides, slide_size, progress)?;
+
+    // 18. Shape action sounds
+    write_action_sounds(zip, options, custom_slides, progress)?;
+
+    // 19. Pictures
+    write_images(zip, options, custom_slides, progress)?;
+
+    // 20. Tag lists of slides and shapes
+    if let Some(slides) = custom_slides {
+        for (number, tags) in (1..).zip(slides.iter().flat_map(slide_tag_lists)) {
+            zip.start_file(format!("ppt/tags/tag{number}.xml"), *options)?;
+            zip.write_all(tags.to_xml().as_bytes())?;
+        }
+    }
+
+    // 21. Presentation properties (print settings)
+    if let Some(print) = print {
+        zip.start_file("ppt/presProps.xml", *options)?;
+        zip.write_all(create_pres_props_xml(print).as_bytes())?;
+    }
+
+    // 22. Provenance (custom XML part)
+    if let Some(provenance) = provenance {
+        zip.start_file(PROVENANCE_PART, *options)?;
+        zip.write_all(provenance.to_xml().as_bytes())?;
+        zip.start_file("customXml/itemProps1.xml", *options)?;
+        zip.write_all(create_provenance_item_props_xml().as_bytes())?;
+        zip.start_file("customXml/_rels/item1.xml.rels", *options)?;
+        zip.write_all(create_provenance_rels_xml().as_bytes())?;
     }
 
     Ok(())
 }
 
+/// Insert `line` on its own line before the closing tag of a part
+fn insert_before_closing_tag(xml: &mut String, closing_tag: &str, line: &str) {
+    if let Some(pos) = xml.rfind(closing_tag) {
+        xml.insert_str(pos, &format!("{line}\n"));
+    }
+}
+
 /// Write slide XML files
 fn write_slides(
-    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    zip: &mut ZipWriter<impl Write + Seek>,
     options: &FileOptions,
     slide_count: usize,
     custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    slide_size: SlideSize,
+    progress: &mut BuildProgress,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match custom_slides {
         Some(slides) => {
             for (i, slide) in slides.iter().enumerate() {
                 let slide_num = i + 1;
+                progress.check_cancelled()?;
+                let _span = span!(DEBUG, "emit_slide", slide = slide_num);
                 
-                // Calculate chart rIds
-                let mut chart_rids = Vec::new();
-                let start_rid = if slide.notes.is_some() { 3 } else { 2 };
-                for j in 0..slide.charts.len() {
-                    chart_rids.push(format!("rId{}", start_rid + j));
-                }
-
-                let slide_xml = create_slide_xml_with_content(slide_num, slide, &chart_rids);
+                let rel_ids = slide_relationship_ids(slide);
+                let slide_xml = create_slide_xml_with_size(slide_num, slide, &rel_ids, slide_size);
+                progress.charge(slide_xml.len())?;
                 zip.start_file(format!("ppt/slides/slide{slide_num}.xml"), *options)?;
                 zip.write_all(slide_xml.as_bytes())?;
+                event!(TRACE, bytes = slide_xml.len(), "slide written");
                 
                 // Write notes if present
                 if let Some(notes) = &slide.notes {
                     let notes_xml = create_notes_xml(slide_num, notes);
+                    progress.charge(notes_xml.len())?;
                     zip.start_file(format!("ppt/notesSlides/notesSlide{slide_num}.xml"), *options)?;
                     zip.write_all(notes_xml.as_bytes())?;
                 }
+                progress.report(slide_num, slide_count);
             }
         }
         None => {
             for i in 1..=slide_count {
+                progress.check_cancelled()?;
                 let slide_xml = create_slide_xml(i, "Presentation");
+                progress.charge(slide_xml.len())?;
                 zip.start_file(format!("ppt/slides/slide{i}.xml"), *options)?;
                 zip.write_all(slide_xml.as_bytes())?;
+                progress.report(i, slide_count);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Relationship IDs for everything a slide references besides its layout
+///
+/// rId1 is the slide layout and rId2 the notes slide (when present); charts,
+/// narration, slide zooms, shape actions, embedded pictures and tag lists are
+/// numbered after those, in that order. Used for both the slide XML and its relationships part so the two
+/// always agree.
+fn slide_relationship_ids(slide: &super::xml::SlideContent) -> SlideRelIds {
+    let mut next = if slide.notes.is_some() { 3 } else { 2 };
+    let mut rid = || {
+        let id = format!("rId{}", next);
+        next += 1;
+        id
+    };
+
+    let charts = slide.charts.iter().map(|_| rid()).collect();
+    let narration = slide.narration.as_ref().map(|_| MediaRelIds {
+        audio: rid(),
+        media: rid(),
+        image: rid(),
+    });
+    let zooms = slide.slide_zooms.iter()
+        .map(|_| ZoomRelIds { slide: rid(), image: rid() })
+        .collect();
+
+    let has_actions = slide.shapes.iter().any(|s| s.hyperlink.is_some() || s.hover.is_some());
+    let mut action_rel_ids = |action: Option<&Hyperlink>| ActionRelIds {
+        link: action.filter(|h| h.action.needs_relationship()).map(|_| rid()),
+        sound: action.filter(|h| h.sound.is_some()).map(|_| rid()),
+    };
+    let shape_actions = if has_actions {
+        slide.shapes.iter()
+            .map(|shape| ShapeActionRelIds {
+                click: action_rel_ids(shape.hyperlink.as_ref()),
+                hover: action_rel_ids(shape.hover.as_ref()),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let images = slide.images.iter().map(|image| image.is_loaded().then(&mut rid)).collect();
+
+    let tags = (!slide.tags.is_empty()).then(&mut rid);
+    let shape_tags = if slide.shapes.iter().any(|s| !s.tags.is_empty()) {
+        slide.shapes.iter().map(|shape| (!shape.tags.is_empty()).then(&mut rid)).collect()
+    } else {
+        Vec::new()
+    };
+
+    SlideRelIds { charts, narration, zooms, shape_actions, images, tags, shape_tags }
+}
+
+/// Tag lists of a slide that get their own part: the slide's, then its shapes'
+fn slide_tag_lists(slide: &super::xml::SlideContent) -> impl Iterator<Item = &Tags> {
+    std::iter::once(&slide.tags)
+        .chain(slide.shapes.iter().map(|shape| &shape.tags))
+        .filter(|tags| !tags.is_empty())
+}
+
+/// Click and hover actions of a slide's shapes, in relationship order
+fn shape_actions(slide: &super::xml::SlideContent) -> impl Iterator<Item = &Hyperlink> {
+    slide.shapes.iter().flat_map(|shape| shape.hyperlink.iter().chain(&shape.hover))
+}
+
+/// Reject slide zooms and slide-jump actions that point outside the presentation
+fn validate_slide_targets(slides: &[super::xml::SlideContent]) -> Result<(), String> {
+    for (i, slide) in slides.iter().enumerate() {
+        let zoom_targets = slide.slide_zooms.iter().map(|z| ("a zoom", z.target_slide));
+        let jump_targets = shape_actions(slide).filter_map(|h| match h.action {
+            HyperlinkAction::Slide(num) => Some(("an action", num as usize)),
+            _ => None,
+        });
+        for (kind, target) in zoom_targets.chain(jump_targets) {
+            if target == 0 || target > slides.len() {
+                return Err(format!(
+                    "Slide {} has {} to slide {}, but the presentation has {} slides",
+                    i + 1,
+                    kind,
+                    target,
+                    slides.len()
+                ));
             }
         }
     }
     Ok(())
 }
 
+/// File name (under `ppt/media/`) of a shape action's sound
+fn action_sound_file_name(slide_num: usize, sound_index: usize) -> String {
+    format!("sound{}_{}.wav", slide_num, sound_index + 1)
+}
+
+/// File name (under `ppt/media/`) of a slide zoom's thumbnail
+fn zoom_thumbnail_file_name(slide_num: usize, zoom_index: usize) -> String {
+    format!("zoom{}_{}.png", slide_num, zoom_index + 1)
+}
+
+/// File name (under `ppt/media/`) of an embedded picture
+fn image_file_name(slide_num: usize, image_index: usize, image: &super::images::Image) -> String {
+    format!("image{}_{}.{}", slide_num, image_index + 1, image.extension())
+}
+
+/// File name (under `ppt/media/`) of a slide's narration clip
+fn narration_file_name(slide_num: usize, narration: &super::media::Narration) -> String {
+    format!("narration{}.{}", slide_num, narration.format.extension())
+}
+
 /// Write slide relationship files with notes and charts
 fn write_slide_relationships_extended(
-    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    zip: &mut ZipWriter<impl Write + Seek>,
     options: &FileOptions,
     custom_slides: Option<&Vec<super::xml::SlideContent>>,
     slide_chart_start_indices: &[usize],
+    slide_tag_start_indices: &[usize],
     slide_count: usize,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match custom_slides {
@@ -206,17 +528,69 @@ fn write_slide_relationships_extended(
             for (i, slide) in slides.iter().enumerate() {
                 let slide_num = i + 1;
                 
-                let mut chart_rels = Vec::new();
                 let start_chart_idx = slide_chart_start_indices[i];
-                let start_rid = if slide.notes.is_some() { 3 } else { 2 };
-                
-                for j in 0..slide.charts.len() {
-                    let rid = format!("rId{}", start_rid + j);
-                    let target = format!("../charts/chart{}.xml", start_chart_idx + j);
-                    chart_rels.push((rid, target));
-                }
+                let rel_ids = slide_relationship_ids(slide);
+                let chart_rels: Vec<(String, String)> = rel_ids.charts
+                    .iter()
+                    .enumerate()
+                    .map(|(j, rid)| (rid.clone(), format!("../charts/chart{}.xml", start_chart_idx + j)))
+                    .collect();
+
+                let media_target = slide.narration.as_ref()
+                    .map(|n| format!("../media/{}", narration_file_name(slide_num, n)));
+                let icon_target = format!("../media/{NARRATION_ICON_FILE}");
+                let audio = rel_ids.narration.as_ref()
+                    .zip(media_target.as_deref())
+                    .map(|(rids, target)| (rids, target, icon_target.as_str()));
+                let zooms: Vec<(&ZoomRelIds, usize, String)> = rel_ids.zooms
+                    .iter()
+                    .zip(&slide.slide_zooms)
+                    .enumerate()
+                    .map(|(j, (rids, zoom))| {
+                        (rids, zoom.target_slide, format!("../media/{}", zoom_thumbnail_file_name(slide_num, j)))
+                    })
+                    .collect();
+                let mut sound_index = 0;
+                let actions: Vec<(&Hyperlink, &ActionRelIds, String)> = slide.shapes.iter()
+                    .zip(&rel_ids.shape_actions)
+                    .flat_map(|(shape, ids)| {
+                        shape.hyperlink.as_ref().map(|h| (h, &ids.click))
+                            .into_iter()
+                            .chain(shape.hover.as_ref().map(|h| (h, &ids.hover)))
+                    })
+                    .map(|(h, ids)| {
+                        let sound_target = if ids.sound.is_some() {
+                            sound_index += 1;
+                            format!("../media/{}", action_sound_file_name(slide_num, sound_index - 1))
+                        } else {
+                            String::new()
+                        };
+                        (h, ids, sound_target)
+                    })
+                    .collect();
 
-                let slide_rels = create_slide_rels_xml_extended(slide_num, slide.notes.is_some(), &chart_rels);
+                let images: Vec<(String, String)> = rel_ids.images
+                    .iter()
+                    .zip(&slide.images)
+                    .enumerate()
+                    .filter_map(|(j, (rid, image))| {
+                        rid.clone().map(|rid| (rid, format!("../media/{}", image_file_name(slide_num, j, image))))
+                    })
+                    .collect();
+
+                let mut slide_rels = create_slide_rels_xml_with_media(
+                    slide_num,
+                    slide.notes.is_some(),
+                    &chart_rels,
+                    audio,
+                    &zooms,
+                    &actions,
+                    &images,
+                );
+                let tag_rel_ids = rel_ids.tags.iter().chain(rel_ids.shape_tags.iter().flatten());
+                for (number, rid) in (slide_tag_start_indices[i]..).zip(tag_rel_ids) {
+                    insert_before_closing_tag(&mut slide_rels, "</Relationships>", &tags_relationship_xml(rid, number));
+                }
                 zip.start_file(format!("ppt/slides/_rels/slide{slide_num}.xml.rels"), *options)?;
                 zip.write_all(slide_rels.as_bytes())?;
             }
@@ -235,10 +609,11 @@ fn write_slide_relationships_extended(
 
 /// Write chart files
 fn write_charts(
-    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    zip: &mut ZipWriter<impl Write + Seek>,
     options: &FileOptions,
     custom_slides: Option<&Vec<super::xml::SlideContent>>,
     slide_chart_start_indices: &[usize],
+    progress: &mut BuildProgress,
 ) -> Result<(), Box<dyn std::error::Error>> {
     if let Some(slides) = custom_slides {
         for (i, slide) in slides.iter().enumerate() {
@@ -246,8 +621,133 @@ fn write_charts(
             for (j, chart) in slide.charts.iter().enumerate() {
                 let chart_idx = start_chart_idx + j;
                 let chart_xml = generate_chart_part_xml(chart);
+                progress.charge(chart_xml.len())?;
                 zip.start_file(format!("ppt/charts/chart{}.xml", chart_idx), *options)?;
                 zip.write_all(chart_xml.as_bytes())?;
+
+                // Source workbook as the chart's editable data (`c:externalData` rId1)
+                if let Some(source) = &chart.data_source {
+                    progress.charge(source.workbook.len())?;
+                    let workbook = format!("Microsoft_Excel_Worksheet{chart_idx}.xlsx");
+                    zip.start_file(format!("ppt/embeddings/{workbook}"), *options)?;
+                    zip.write_all(&source.workbook)?;
+                    zip.start_file(format!("ppt/charts/_rels/chart{chart_idx}.xml.rels"), *options)?;
+                    zip.write_all(format!(
+                        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/package" Target="../embeddings/{workbook}"/>
+</Relationships>"#
+                    ).as_bytes())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write narration audio clips and the shared icon image
+fn write_narration_media(
+    zip: &mut ZipWriter<impl Write + Seek>,
+    options: &FileOptions,
+    custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    progress: &mut BuildProgress,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(slides) = custom_slides else {
+        return Ok(());
+    };
+
+    let mut has_narration = false;
+    for (i, slide) in slides.iter().enumerate() {
+        if let Some(narration) = &slide.narration {
+            progress.charge(narration.data.len())?;
+            zip.start_file(format!("ppt/media/{}", narration_file_name(i + 1, narration)), *options)?;
+            zip.write_all(&narration.data)?;
+            has_narration = true;
+        }
+    }
+
+    if has_narration {
+        progress.charge(NARRATION_ICON_PNG.len())?;
+        zip.start_file(format!("ppt/media/{NARRATION_ICON_FILE}"), *options)?;
+        zip.write_all(NARRATION_ICON_PNG)?;
+    }
+    Ok(())
+}
+
+/// Write a generated thumbnail for every slide zoom
+fn write_zoom_thumbnails(
+    zip: &mut ZipWriter<impl Write + Seek>,
+    options: &FileOptions,
+    custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    slide_size: SlideSize,
+    progress: &mut BuildProgress,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(slides) = custom_slides else {
+        return Ok(());
+    };
+
+    for (i, slide) in slides.iter().enumerate() {
+        for (j, zoom) in slide.slide_zooms.iter().enumerate() {
+            let thumbnail = generate_zoom_thumbnail_with_size(&slides[zoom.target_slide - 1], slide_size);
+            progress.charge(thumbnail.len())?;
+            zip.start_file(format!("ppt/media/{}", zoom_thumbnail_file_name(i + 1, j)), *options)?;
+            zip.write_all(&thumbnail)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the sounds played by shape actions
+fn write_action_sounds(
+    zip: &mut ZipWriter<impl Write + Seek>,
+    options: &FileOptions,
+    custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    progress: &mut BuildProgress,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(slides) = custom_slides else {
+        return Ok(());
+    };
+
+    for (i, slide) in slides.iter().enumerate() {
+        let sounds = shape_actions(slide).filter_map(|h| h.sound.as_ref());
+        for (j, sound) in sounds.enumerate() {
+            progress.charge(sound.data.len())?;
+            zip.start_file(format!("ppt/media/{}", action_sound_file_name(i + 1, j)), *options)?;
+            zip.write_all(&sound.data)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read every image's data, warning about those drawn as placeholders
+fn load_images(slides: &mut [super::xml::SlideContent]) {
+    // The index is only read by `event!`, which expands to nothing without `tracing`
+    for (_i, slide) in slides.iter_mut().enumerate() {
+        for image in &mut slide.images {
+            if !image.load() {
+                event!(WARN, slide = _i + 1, image = %image.filename, "image unreadable, drawing a placeholder");
+            }
+        }
+    }
+}
+
+/// Write the picture of every image whose data was read
+fn write_images(
+    zip: &mut ZipWriter<impl Write + Seek>,
+    options: &FileOptions,
+    custom_slides: Option<&Vec<super::xml::SlideContent>>,
+    progress: &mut BuildProgress,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(slides) = custom_slides else {
+        return Ok(());
+    };
+
+    for (i, slide) in slides.iter().enumerate() {
+        for (j, image) in slide.images.iter().enumerate() {
+            if let Some(super::images::ImageSource::Bytes(data)) = image.source.as_ref().filter(|_| image.is_loaded()) {
+                progress.charge(data.len())?;
+                zip.start_file(format!("ppt/media/{}", image_file_name(i + 1, j, image)), *options)?;
+                zip.write_all(data)?;
             }
         }
     }
@@ -256,7 +756,7 @@ fn write_charts(
 
 /// Write notes relationship files
 fn write_notes_relationships(
-    zip: &mut ZipWriter<Cursor<Vec<u8>>>,
+    zip: &mut ZipWriter<impl Write + Seek>,
     options: &FileOptions,
     custom_slides: Option<&Vec<super::xml::SlideContent>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
@@ -0,0 +1,211 @@
+//! Month-view calendars
+//!
+//! [`Calendar`] lays a month out as a table: a header row of weekday names,
+//! then one row per week with the day number and that day's events in each
+//! cell. Days can be highlighted with a background color, e.g. a release
+//! window or holidays. Add it to a slide with
+//! [`SlideContent::calendar`](super::SlideContent::calendar).
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::elements::ColorInput;
+use super::slide_content::Region;
+use super::tables::{Table, TableCell, TableRow};
+
+/// Header row height (0.35 inch)
+const HEADER_HEIGHT: u32 = 320040;
+/// Day cell font size in points
+const DAY_FONT_SIZE: u32 = 11;
+/// Background of the cells before the 1st and after the last day
+const OUTSIDE_COLOR: &str = "F2F2F2";
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June",
+    "July", "August", "September", "October", "November", "December",
+];
+
+/// One month as a table of weeks
+///
+/// ```
+/// use ppt_rs::generator::{Calendar, SlideContent};
+///
+/// let june = Calendar::month(2025, 6)
+///     .highlight(16..=20, "FFE082")
+///     .event(18, "Launch");
+/// assert_eq!(june.title(), "June 2025");
+///
+/// let table = june.to_table();
+/// assert_eq!(table.row_count(), 7); // header and six weeks
+/// assert_eq!(table.rows[4].cells[2].text, "18\nLaunch");
+///
+/// let slide = SlideContent::new(&june.title()).calendar(&june);
+/// assert!(slide.table.is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Calendar {
+    year: i32,
+    month: u32,
+    sunday_first: bool,
+    /// Day of the month and color; later entries win
+    highlights: Vec<(u32, String)>,
+    events: Vec<(u32, String)>,
+    header_color: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Calendar {
+    /// Calendar of `month` (1-12, clamped) in `year`, filling the content area
+    pub fn month(year: i32, month: u32) -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
+        Calendar {
+            year,
+            month: month.clamp(1, 12),
+            sunday_first: false,
+            highlights: Vec::new(),
+            events: Vec::new(),
+            header_color: "455A64".to_string(),
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Shade `days` of the month; days the month doesn't have are ignored
+    pub fn highlight(mut self, days: impl IntoIterator<Item = u32>, color: impl Into<ColorInput>) -> Self {
+        let color = color.into().to_hex();
+        self.highlights.extend(days.into_iter().map(|day| (day, color.clone())));
+        self
+    }
+
+    /// Add an event line under the day number
+    pub fn event(mut self, day: u32, text: &str) -> Self {
+        self.events.push((day, text.to_string()));
+        self
+    }
+
+    /// Start weeks on Sunday instead of Monday
+    pub fn sunday_first(mut self, sunday_first: bool) -> Self {
+        self.sunday_first = sunday_first;
+        self
+    }
+
+    /// Set the fill of the weekday header row
+    pub fn header_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.header_color = color.into().to_hex();
+        self
+    }
+
+    pub fn position(mut self, x: u32, y: u32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Month and year, e.g. "June 2025"
+    pub fn title(&self) -> String {
+        format!("{} {}", MONTHS[self.month as usize - 1], self.year)
+    }
+
+    /// Number of days in the month
+    pub fn days(&self) -> u32 {
+        let (year, month) = if self.month == 12 { (self.year + 1, 1) } else { (self.year, self.month + 1) };
+        NaiveDate::from_ymd_opt(year, month, 1)
+            .and_then(|next| next.pred_opt())
+            .map_or(31, |last| last.day())
+    }
+
+    /// Day numbers by week, `None` for the days of neighbouring months
+    pub fn weeks(&self) -> Vec<[Option<u32>; 7]> {
+        let first = NaiveDate::from_ymd_opt(self.year, self.month, 1).map_or(0, |d| {
+            if self.sunday_first { d.weekday().num_days_from_sunday() } else { d.weekday().num_days_from_monday() }
+        });
+        let cells = (first + self.days()).div_ceil(7) * 7;
+        let days: Vec<Option<u32>> = (0..cells)
+            .map(|i| i.checked_sub(first).map(|d| d + 1).filter(|d| *d <= self.days()))
+            .collect();
+        days.chunks(7).map(|week| week.try_into().unwrap()).collect()
+    }
+
+    /// The month as a table with a weekday header row
+    pub fn to_table(&self) -> Table {
+        let mut weekdays = WEEKDAYS.to_vec();
+        if self.sunday_first {
+            weekdays.rotate_right(1);
+        }
+        let header = weekdays.iter()
+            .map(|name| TableCell::new(name).bold().text_color("FFFFFF").background_color(self.header_color.as_str()))
+            .collect();
+
+        let weeks = self.weeks();
+        let week_height = self.height.saturating_sub(HEADER_HEIGHT) / weeks.len().max(1) as u32;
+        let mut rows = vec![TableRow::new(header).with_height(HEADER_HEIGHT)];
+        for week in weeks {
+            let cells = week.iter().map(|day| match *day {
+                Some(day) => {
+                    let mut lines = vec![day.to_string()];
+                    lines.extend(self.events.iter().filter(|(d, _)| *d == day).map(|(_, text)| text.clone()));
+                    let cell = TableCell::new(&lines.join("\n")).font_size(DAY_FONT_SIZE).align_left().valign_top();
+                    match self.highlights.iter().rev().find(|(d, _)| *d == day) {
+                        Some((_, color)) => cell.background_color(color.as_str()),
+                        None => cell,
+                    }
+                }
+                None => TableCell::new("").background_color(OUTSIDE_COLOR),
+            }).collect();
+            rows.push(TableRow::new(cells).with_height(week_height));
+        }
+        Table::new(rows, vec![self.width / 7; 7], self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weeks_start_on_the_right_weekday() {
+        // 1 June 2025 is a Sunday
+        let june = Calendar::month(2025, 6);
+        assert_eq!(june.days(), 30);
+        let weeks = june.weeks();
+        assert_eq!(weeks[0], [None, None, None, None, None, None, Some(1)]);
+        assert_eq!(weeks.len(), 6);
+        assert_eq!(weeks[5][0], Some(30));
+
+        let weeks = june.sunday_first(true).weeks();
+        assert_eq!(weeks[0][0], Some(1));
+        assert_eq!(weeks.len(), 5);
+    }
+
+    #[test]
+    fn test_leap_february() {
+        assert_eq!(Calendar::month(2024, 2).days(), 29);
+        assert_eq!(Calendar::month(2025, 2).days(), 28);
+        assert_eq!(Calendar::month(2025, 12).days(), 31);
+        assert_eq!(Calendar::month(2025, 13).title(), "December 2025");
+    }
+
+    #[test]
+    fn test_highlights_and_outside_days() {
+        let table = Calendar::month(2025, 6)
+            .highlight([2, 3], "FFE082")
+            .highlight([3, 40], "EF9A9A")
+            .to_table();
+        let week = &table.rows[2].cells;
+        assert_eq!(week[0].background_color.as_deref(), Some("FFE082"));
+        assert_eq!(week[1].background_color.as_deref(), Some("EF9A9A"));
+        assert_eq!(table.rows[1].cells[0].background_color.as_deref(), Some(OUTSIDE_COLOR));
+        assert_eq!(table.rows[0].cells[6].text, "Sun");
+    }
+}
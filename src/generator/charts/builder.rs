@@ -1,7 +1,15 @@
 //! Chart builder for fluent API
 
 use super::types::ChartType;
-use super::data::{Chart, ChartSeries};
+use super::data::{Chart, ChartDataSource, ChartSeries};
+use super::style::ChartStyle;
+use super::template::ChartTemplate;
+use super::reference::{ReferenceLine, TargetBand};
+use super::legend::{LegendOverflow, LegendPosition};
+use super::data_table::DataTable;
+use super::units::DisplayUnit;
+use crate::core::Emu;
+use crate::generator::slide_content::Region;
 
 /// Chart builder for fluent API
 pub struct ChartBuilder {
@@ -9,38 +17,81 @@ pub struct ChartBuilder {
     chart_type: ChartType,
     categories: Vec<String>,
     series: Vec<ChartSeries>,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+    x: Emu,
+    y: Emu,
+    width: Emu,
+    height: Emu,
+    style: Option<ChartStyle>,
+    category_label_rotation: Option<i32>,
+    category_label_wrap: Option<usize>,
+    category_label_max_len: Option<usize>,
+    data_source: Option<ChartDataSource>,
+    reference_lines: Vec<ReferenceLine>,
+    target_bands: Vec<TargetBand>,
+    legend_position: Option<LegendPosition>,
+    legend_overflow: Option<LegendOverflow>,
+    data_table: Option<DataTable>,
+    display_units: Option<DisplayUnit>,
+    unit_label: Option<String>,
+    region: Option<Region>,
+    template: Option<ChartTemplate>,
 }
 
 impl ChartBuilder {
     /// Create a new chart builder
+    ///
+    /// The chart fills the content area under the slide title unless
+    /// `position()`/`size()` or `region()` are used.
     pub fn new(title: &str, chart_type: ChartType) -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
         ChartBuilder {
             title: title.to_string(),
             chart_type,
             categories: Vec::new(),
             series: Vec::new(),
-            x: 0,
-            y: 0,
-            width: 5000000,  // Default width (5 inches in EMU)
-            height: 3750000, // Default height (3.75 inches in EMU)
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            style: None,
+            category_label_rotation: None,
+            category_label_wrap: None,
+            category_label_max_len: None,
+            data_source: None,
+            reference_lines: Vec::new(),
+            target_bands: Vec::new(),
+            legend_position: None,
+            legend_overflow: None,
+            data_table: None,
+            display_units: None,
+            unit_label: None,
+            region: Some(Region::Content),
+            template: None,
         }
     }
 
     /// Set chart position
-    pub fn position(mut self, x: u32, y: u32) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
         self
     }
 
     /// Set chart size
-    pub fn size(mut self, width: u32, height: u32) -> Self {
-        self.width = width;
-        self.height = height;
+    pub fn size(mut self, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
+        self.width = width.into();
+        self.height = height.into();
+        self
+    }
+
+    /// Place the chart into a named slide region
+    pub fn region(mut self, region: Region) -> Self {
+        let (x, y, width, height) = region.bounds();
+        self.x = x.into();
+        self.y = y.into();
+        self.width = width.into();
+        self.height = height.into();
+        self.region = Some(region);
         self
     }
 
@@ -56,9 +107,102 @@ impl ChartBuilder {
         self
     }
 
+    /// Apply a shared chart style (fonts, gridlines, plot area)
+    pub fn style(mut self, style: ChartStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Use a named chart template for everything the builder doesn't set
+    ///
+    /// Settings made on the builder win over the template's, whichever is
+    /// called first (see [`ChartTemplate`]).
+    pub fn template(mut self, template: &ChartTemplate) -> Self {
+        self.template = Some(template.clone());
+        self
+    }
+
+    /// Rotate category axis labels by the given angle in degrees (e.g., -45)
+    ///
+    /// Angles outside -90..=90 fail [`Chart::validate`].
+    pub fn category_label_rotation(mut self, degrees: i32) -> Self {
+        self.category_label_rotation = Some(degrees);
+        self
+    }
+
+    /// Wrap category labels onto new lines after `width` characters
+    ///
+    /// Shortens the category text itself; see [`Chart::display_categories`].
+    pub fn wrap_category_labels(mut self, width: usize) -> Self {
+        self.category_label_wrap = Some(width);
+        self
+    }
+
+    /// Truncate category labels longer than `max_len` characters with an ellipsis
+    ///
+    /// Shortens the category text itself; see [`Chart::display_categories`].
+    pub fn truncate_category_labels(mut self, max_len: usize) -> Self {
+        self.category_label_max_len = Some(max_len);
+        self
+    }
+
+    /// Embed the workbook the data came from and reference its range
+    pub fn data_source(mut self, source: ChartDataSource) -> Self {
+        self.data_source = Some(source);
+        self
+    }
+
+    /// Add a reference line, e.g. a target value
+    pub fn reference_line(mut self, line: ReferenceLine) -> Self {
+        self.reference_lines.push(line);
+        self
+    }
+
+    /// Add a shaded target band
+    pub fn target_band(mut self, band: TargetBand) -> Self {
+        self.target_bands.push(band);
+        self
+    }
+
+    /// Draw the legend on the given side of the chart
+    pub fn legend_position(mut self, position: LegendPosition) -> Self {
+        self.legend_position = Some(position);
+        self
+    }
+
+    /// Choose how a legend with more entries than fit is laid out
+    pub fn legend_overflow(mut self, overflow: LegendOverflow) -> Self {
+        self.legend_overflow = Some(overflow);
+        self
+    }
+
+    /// Show the values in a table under the plot area, with legend keys
+    pub fn show_data_table(mut self, show: bool) -> Self {
+        self.data_table = show.then(DataTable::new);
+        self
+    }
+
+    /// Show the values in a data table with the given borders, keys and text size
+    pub fn data_table(mut self, table: DataTable) -> Self {
+        self.data_table = Some(table);
+        self
+    }
+
+    /// Label the value axis in thousands, millions, ... instead of raw values
+    pub fn display_units(mut self, unit: DisplayUnit) -> Self {
+        self.display_units = Some(unit);
+        self
+    }
+
+    /// Replace the display unit's name on the value axis, e.g. "($M)"
+    pub fn unit_label(mut self, label: &str) -> Self {
+        self.unit_label = Some(label.to_string());
+        self
+    }
+
     /// Build the chart
     pub fn build(self) -> Chart {
-        Chart {
+        let mut chart = Chart {
             title: self.title,
             chart_type: self.chart_type,
             categories: self.categories,
@@ -67,13 +211,31 @@ impl ChartBuilder {
             y: self.y,
             width: self.width,
             height: self.height,
+            style: self.style,
+            category_label_rotation: self.category_label_rotation,
+            category_label_wrap: self.category_label_wrap,
+            category_label_max_len: self.category_label_max_len,
+            data_source: self.data_source,
+            reference_lines: self.reference_lines,
+            target_bands: self.target_bands,
+            legend_position: self.legend_position,
+            legend_overflow: self.legend_overflow,
+            data_table: self.data_table,
+            display_units: self.display_units,
+            unit_label: self.unit_label,
+            region: self.region,
+        };
+        if let Some(template) = &self.template {
+            template.apply_to(&mut chart);
         }
+        chart
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator::SlideSize;
 
     #[test]
     fn test_chart_builder() {
@@ -89,7 +251,38 @@ mod tests {
         assert_eq!(chart.chart_type, ChartType::Bar);
         assert_eq!(chart.category_count(), 3);
         assert_eq!(chart.series_count(), 2);
-        assert_eq!(chart.x, 100000);
-        assert_eq!(chart.y, 200000);
+        assert_eq!(chart.x, Emu(100000));
+        assert_eq!(chart.y, Emu(200000));
+        assert!(chart.style.is_none());
+    }
+
+    #[test]
+    fn test_chart_builder_default_fills_content_area() {
+        let chart = ChartBuilder::new("Default", ChartType::Bar).build();
+        assert_eq!(
+            (chart.x, chart.y, chart.width, chart.height),
+            Region::Content.emu_bounds_on(SlideSize::STANDARD)
+        );
+    }
+
+    #[test]
+    fn test_chart_builder_region() {
+        let chart = ChartBuilder::new("Right", ChartType::Pie)
+            .region(Region::RightHalf)
+            .build();
+        assert_eq!(
+            (chart.x, chart.y, chart.width, chart.height),
+            Region::RightHalf.emu_bounds_on(SlideSize::STANDARD)
+        );
+    }
+
+    #[test]
+    fn test_chart_builder_style() {
+        let style = ChartStyle::new().font("Arial").title_size(24);
+        let chart = ChartBuilder::new("Styled", ChartType::Line)
+            .style(style.clone())
+            .build();
+
+        assert_eq!(chart.style, Some(style));
     }
 }
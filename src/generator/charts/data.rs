@@ -1,12 +1,26 @@
 //! Chart data structures
 
 use super::types::ChartType;
+use super::style::ChartStyle;
+use super::reference::{ReferenceLine, TargetBand};
+use super::legend::{LegendOverflow, LegendPosition};
+use super::data_table::DataTable;
+use super::units::DisplayUnit;
+use crate::core::{Emu, Positioned, ElementSized};
+use crate::generator::validate::ValidationError;
+use crate::elements::ColorInput;
+use crate::generator::xlsx::{column_name, quote_sheet};
+use crate::generator::placement::SlideSize;
+use crate::generator::slide_content::Region;
+use std::sync::Arc;
 
 /// Chart data series
 #[derive(Clone, Debug)]
 pub struct ChartSeries {
     pub name: String,
     pub values: Vec<f64>,
+    /// Fill/line color (RGB hex); PowerPoint's automatic color when unset
+    pub color: Option<String>,
 }
 
 impl ChartSeries {
@@ -15,9 +29,18 @@ impl ChartSeries {
         ChartSeries {
             name: name.to_string(),
             values,
+            color: None,
         }
     }
 
+    /// Set the series color
+    ///
+    /// Ignored by pie and doughnut charts, which color each point.
+    pub fn with_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = Some(color.into().to_hex());
+        self
+    }
+
     /// Get the number of data points
     pub fn len(&self) -> usize {
         self.values.len()
@@ -29,6 +52,48 @@ impl ChartSeries {
     }
 }
 
+/// Worksheet range a chart's data came from
+///
+/// The workbook is embedded as the chart's data and the chart's formulas
+/// point into the range: categories in its first column, series names in
+/// its first row, series values below them.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChartDataSource {
+    /// Workbook file embedded in the presentation
+    pub workbook: Arc<[u8]>,
+    pub sheet: String,
+    /// 0-based row of the range's top-left (header) cell
+    pub first_row: u32,
+    /// 0-based column of the range's top-left (category) cell
+    pub first_col: u32,
+}
+
+impl ChartDataSource {
+    /// Reference to the `rows` cells below the header in column `col` of the
+    /// range (0 = categories, 1.. = series)
+    pub fn column_ref(&self, col: usize, rows: usize) -> String {
+        let column = column_name(self.first_col + col as u32);
+        let first = self.first_row + 2;
+        format!("{}!${column}${first}:${column}${}", quote_sheet(&self.sheet), first as usize + rows.max(1) - 1)
+    }
+
+    /// Reference to the header cell of column `col` of the range
+    pub fn header_ref(&self, col: usize) -> String {
+        format!("{}!${}${}", quote_sheet(&self.sheet), column_name(self.first_col + col as u32), self.first_row + 1)
+    }
+}
+
+impl std::fmt::Debug for ChartDataSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChartDataSource")
+            .field("workbook", &self.workbook.len())
+            .field("sheet", &self.sheet)
+            .field("first_row", &self.first_row)
+            .field("first_col", &self.first_col)
+            .finish()
+    }
+}
+
 /// Chart definition
 #[derive(Clone, Debug)]
 pub struct Chart {
@@ -36,10 +101,40 @@ pub struct Chart {
     pub chart_type: ChartType,
     pub categories: Vec<String>,
     pub series: Vec<ChartSeries>,
-    pub x: u32,      // Position X in EMU
-    pub y: u32,      // Position Y in EMU
-    pub width: u32,  // Width in EMU
-    pub height: u32, // Height in EMU
+    /// Position X; negative values place the chart partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the chart partly above the slide
+    pub y: Emu,
+    pub width: Emu,
+    pub height: Emu,
+    /// Shared typography/gridline/plot area styling
+    pub style: Option<ChartStyle>,
+    /// Category axis label rotation in degrees (e.g., -45), within -90..=90
+    pub category_label_rotation: Option<i32>,
+    /// Wrap category labels onto new lines after this many characters
+    /// (rewrites the cached category text, see [`Self::display_categories`])
+    pub category_label_wrap: Option<usize>,
+    /// Truncate category labels longer than this many characters
+    /// (rewrites the cached category text, see [`Self::display_categories`])
+    pub category_label_max_len: Option<usize>,
+    /// Embedded workbook and range the data was read from
+    pub data_source: Option<ChartDataSource>,
+    /// Lines across the plot area at fixed values
+    pub reference_lines: Vec<ReferenceLine>,
+    /// Shaded value ranges across the plot area
+    pub target_bands: Vec<TargetBand>,
+    /// Side the legend is drawn on (right when unset)
+    pub legend_position: Option<LegendPosition>,
+    /// Handling of legends too long for their position (wrap when unset)
+    pub legend_overflow: Option<LegendOverflow>,
+    /// Table of the values under the plot area
+    pub data_table: Option<DataTable>,
+    /// Power of ten the value axis labels are shown in
+    pub display_units: Option<DisplayUnit>,
+    /// Value axis unit label replacing the unit's name, e.g. "($M)"
+    pub unit_label: Option<String>,
+    /// Slide region the chart fills, re-resolved for the deck's slide size
+    pub region: Option<Region>,
 }
 
 impl Chart {
@@ -48,20 +143,33 @@ impl Chart {
         title: &str,
         chart_type: ChartType,
         categories: Vec<String>,
-        x: u32,
-        y: u32,
-        width: u32,
-        height: u32,
+        x: impl Into<Emu>,
+        y: impl Into<Emu>,
+        width: impl Into<Emu>,
+        height: impl Into<Emu>,
     ) -> Self {
         Chart {
             title: title.to_string(),
             chart_type,
             categories,
             series: Vec::new(),
-            x,
-            y,
-            width,
-            height,
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            style: None,
+            category_label_rotation: None,
+            category_label_wrap: None,
+            category_label_max_len: None,
+            data_source: None,
+            reference_lines: Vec::new(),
+            target_bands: Vec::new(),
+            legend_position: None,
+            legend_overflow: None,
+            data_table: None,
+            display_units: None,
+            unit_label: None,
+            region: None,
         }
     }
 
@@ -71,6 +179,53 @@ impl Chart {
         self
     }
 
+    /// Apply a shared chart style
+    pub fn with_style(mut self, style: ChartStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Add a reference line, e.g. a target value
+    pub fn with_reference_line(mut self, line: ReferenceLine) -> Self {
+        self.reference_lines.push(line);
+        self
+    }
+
+    /// Add a shaded target band
+    pub fn with_target_band(mut self, band: TargetBand) -> Self {
+        self.target_bands.push(band);
+        self
+    }
+
+    /// Fit the chart to its region on a slide of `size`
+    ///
+    /// Charts moved or resized after being placed keep their bounds.
+    pub fn resolve_region(&mut self, size: SlideSize) {
+        if let Some(region) = self.region.take()
+            && (self.x, self.y, self.width, self.height) == region.emu_bounds_on(SlideSize::STANDARD)
+        {
+            (self.x, self.y, self.width, self.height) = region.emu_bounds_on(size);
+        }
+    }
+
+    /// Get the effective style (explicit or default)
+    pub fn effective_style(&self) -> ChartStyle {
+        self.style.clone().unwrap_or_default()
+    }
+
+    /// Get category labels as displayed (truncated and wrapped)
+    ///
+    /// These are written as the chart's cached category values, so with
+    /// wrapping or truncation they differ from [`Self::categories`] and from
+    /// an embedded workbook's cells, which keep the full text. PowerPoint
+    /// shows the workbook's text again once the chart data is edited.
+    pub fn display_categories(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .map(|c| format_category_label(c, self.category_label_max_len, self.category_label_wrap))
+            .collect()
+    }
+
     /// Get number of categories
     pub fn category_count(&self) -> usize {
         self.categories.len()
@@ -80,6 +235,127 @@ impl Chart {
     pub fn series_count(&self) -> usize {
         self.series.len()
     }
+
+    /// Check that the series fit the categories and chart type
+    ///
+    /// If a category chart has categories, every series needs one value per
+    /// category; all values must be finite. Stock charts need 3 (HLC) or 4 (OHLC) series.
+    /// Category labels can be rotated by at most 90° either way.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.series.is_empty() {
+            return Err(ValidationError::new(format!("chart \"{}\" has no series", self.title)));
+        }
+        if let Some(degrees) = self.category_label_rotation.filter(|d| !(-90..=90).contains(d)) {
+            return Err(ValidationError::new(format!(
+                "chart \"{}\" rotates category labels by {degrees}°; use -90 to 90",
+                self.title
+            )));
+        }
+        let required_series = match self.chart_type {
+            ChartType::StockHLC => Some(3),
+            ChartType::StockOHLC => Some(4),
+            _ => None,
+        };
+        if let Some(required) = required_series.filter(|&n| n != self.series.len()) {
+            return Err(ValidationError::new(format!(
+                "{:?} chart needs {} series but has {}",
+                self.chart_type,
+                required,
+                self.series.len()
+            )));
+        }
+        if self.has_reference_marks() && !self.supports_reference_marks() {
+            return Err(ValidationError::new(format!(
+                "{:?} chart \"{}\" has no value axis for reference lines or bands",
+                self.chart_type,
+                self.title
+            )));
+        }
+        let uses_categories = !self.categories.is_empty() && !matches!(
+            self.chart_type,
+            ChartType::Scatter | ChartType::ScatterLines | ChartType::ScatterSmooth | ChartType::Bubble
+        );
+        for series in &self.series {
+            if uses_categories && series.len() != self.categories.len() {
+                return Err(ValidationError::new(format!(
+                    "series \"{}\" has {} values but there are {} categories",
+                    series.name,
+                    series.len(),
+                    self.categories.len()
+                )));
+            }
+            if let Some(value) = series.values.iter().find(|v| !v.is_finite()) {
+                return Err(ValidationError::new(format!("series \"{}\" contains {value}", series.name)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Truncate a label to `max_len` characters (with an ellipsis) and
+/// greedily wrap it on word boundaries every `wrap` characters
+fn format_category_label(label: &str, max_len: Option<usize>, wrap: Option<usize>) -> String {
+    let mut text = label.to_string();
+
+    if let Some(max_len) = max_len
+        && max_len > 0
+        && text.chars().count() > max_len
+    {
+        text = text.chars().take(max_len - 1).collect::<String>().trim_end().to_string();
+        text.push('…');
+    }
+
+    if let Some(width) = wrap
+        && width > 0
+    {
+        let mut lines: Vec<String> = Vec::new();
+        let mut line = String::new();
+        for word in text.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        if !line.is_empty() {
+            lines.push(line);
+        }
+        text = lines.join("\n");
+    }
+
+    text
+}
+
+impl Positioned for Chart {
+    fn x(&self) -> Emu {
+        self.x
+    }
+
+    fn y(&self) -> Emu {
+        self.y
+    }
+
+    fn set_position(&mut self, x: Emu, y: Emu) {
+        self.x = x;
+        self.y = y;
+    }
+}
+
+impl ElementSized for Chart {
+    fn width(&self) -> Emu {
+        self.width
+    }
+
+    fn height(&self) -> Emu {
+        self.height
+    }
+
+    fn set_size(&mut self, width: Emu, height: Emu) {
+        self.width = width;
+        self.height = height;
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +377,38 @@ mod tests {
 
         assert_eq!(chart.series_count(), 1);
     }
+
+    #[test]
+    fn test_chart_validate() {
+        let categories = vec!["Q1".to_string(), "Q2".to_string(), "Q3".to_string()];
+        let chart = Chart::new("Sales", ChartType::Bar, categories, 0, 0, 1000000, 1000000)
+            .add_series(ChartSeries::new("2024", vec![1.0, 2.0, 3.0]));
+        assert!(chart.validate().is_ok());
+
+        let err = chart.clone().add_series(ChartSeries::new("2025", vec![1.0, 2.0, 3.0, 4.0])).validate().unwrap_err();
+        assert_eq!(err.to_string(), "series \"2025\" has 4 values but there are 3 categories");
+
+        let nan = chart.clone().add_series(ChartSeries::new("2025", vec![1.0, f64::NAN, 3.0]));
+        assert!(nan.validate().is_err());
+        let stock = Chart::new("Stock", ChartType::StockHLC, vec![], 0, 0, 1, 1).add_series(ChartSeries::new("High", vec![1.0]));
+        assert!(stock.validate().unwrap_err().message.contains("needs 3 series"));
+    }
+
+    #[test]
+    fn test_display_categories_wrap_and_truncate() {
+        let mut chart = Chart::new(
+            "Test",
+            ChartType::Bar,
+            vec!["North America Region".to_string(), "EU".to_string()],
+            0, 0, 1000000, 1000000,
+        );
+        assert_eq!(chart.display_categories()[0], "North America Region");
+
+        chart.category_label_wrap = Some(10);
+        assert_eq!(chart.display_categories()[0], "North\nAmerica\nRegion");
+
+        chart.category_label_wrap = None;
+        chart.category_label_max_len = Some(10);
+        assert_eq!(chart.display_categories(), vec!["North Ame…", "EU"]);
+    }
 }
@@ -0,0 +1,170 @@
+//! Data table under the plot area
+//!
+//! A data table (`c:dTable`) repeats the chart's values in a grid under the
+//! category axis, one row per series, with the series' legend key in front
+//! of each row. Since the keys already identify the series, a chart showing
+//! them drops its legend, as PowerPoint's "data table with legend keys"
+//! layout does.
+
+use serde::{Deserialize, Serialize};
+
+use super::data::Chart;
+use super::types::ChartType;
+
+/// Borders, legend keys and text size of a chart's data table
+///
+/// ```
+/// use ppt_rs::generator::{ChartBuilder, ChartSeries, ChartType, DataTable};
+///
+/// let chart = ChartBuilder::new("Revenue", ChartType::Line)
+///     .categories(vec!["Q1", "Q2"])
+///     .add_series(ChartSeries::new("2025", vec![1.0, 2.0]))
+///     .data_table(DataTable::new().vertical_borders(false).font_size(9))
+///     .build();
+/// assert!(chart.shows_data_table());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DataTable {
+    /// Lines between the rows
+    pub horizontal_borders: bool,
+    /// Lines between the columns
+    pub vertical_borders: bool,
+    /// Line around the table
+    pub outline: bool,
+    /// Series legend key in front of each row
+    pub legend_keys: bool,
+    /// Text size in points; the chart's label size when unset
+    pub font_size: Option<u32>,
+}
+
+impl Default for DataTable {
+    fn default() -> Self {
+        DataTable {
+            horizontal_borders: true,
+            vertical_borders: true,
+            outline: true,
+            legend_keys: true,
+            font_size: None,
+        }
+    }
+}
+
+impl DataTable {
+    /// Data table with all borders and legend keys
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Show or hide the lines between rows
+    pub fn horizontal_borders(mut self, show: bool) -> Self {
+        self.horizontal_borders = show;
+        self
+    }
+
+    /// Show or hide the lines between columns
+    pub fn vertical_borders(mut self, show: bool) -> Self {
+        self.vertical_borders = show;
+        self
+    }
+
+    /// Show or hide the line around the table
+    pub fn outline(mut self, show: bool) -> Self {
+        self.outline = show;
+        self
+    }
+
+    /// Show or hide the series legend keys
+    pub fn legend_keys(mut self, show: bool) -> Self {
+        self.legend_keys = show;
+        self
+    }
+
+    /// Set the text size in points
+    pub fn font_size(mut self, size: u32) -> Self {
+        self.font_size = Some(size);
+        self
+    }
+}
+
+impl Chart {
+    /// Whether the chart draws a data table
+    ///
+    /// Only charts with a horizontal category axis (line, area, stock and
+    /// combo charts) can; PowerPoint doesn't draw one for horizontal bars.
+    pub fn shows_data_table(&self) -> bool {
+        self.data_table.is_some()
+            && matches!(
+                self.chart_type,
+                ChartType::Line | ChartType::LineMarkers | ChartType::LineStacked
+                    | ChartType::Area | ChartType::AreaStacked | ChartType::AreaStacked100
+                    | ChartType::StockHLC | ChartType::StockOHLC | ChartType::Combo
+            )
+    }
+
+    /// Whether the chart has a legend, rather than legend keys in its data table
+    pub fn shows_legend(&self) -> bool {
+        !(self.shows_data_table() && self.data_table.is_some_and(|t| t.legend_keys))
+    }
+
+    /// Rows of the data table including the category header, none when it isn't shown
+    pub(crate) fn data_table_rows(&self) -> usize {
+        if self.shows_data_table() { self.series.len() + 1 } else { 0 }
+    }
+
+    /// `c:dTable`, written after the plot area's charts and axes
+    pub(crate) fn data_table_xml(&self) -> String {
+        let Some(table) = self.data_table.filter(|_| self.shows_data_table()) else {
+            return String::new();
+        };
+        let flag = |on: bool| if on { 1 } else { 0 };
+        let text = match table.font_size {
+            Some(size) => self.effective_style().text_properties_xml(size),
+            None => self.style.as_ref().map(|s| s.label_text_xml()).unwrap_or_default(),
+        };
+        format!(
+            "\n<c:dTable><c:showHorzBorder val=\"{}\"/><c:showVertBorder val=\"{}\"/><c:showOutline val=\"{}\"/><c:showKeys val=\"{}\"/>{text}</c:dTable>",
+            flag(table.horizontal_borders),
+            flag(table.vertical_borders),
+            flag(table.outline),
+            flag(table.legend_keys),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::charts::{generate_chart_part_xml, ChartBuilder, ChartSeries};
+
+    fn chart(chart_type: ChartType, table: DataTable) -> Chart {
+        ChartBuilder::new("Revenue", chart_type)
+            .categories(vec!["Q1", "Q2"])
+            .add_series(ChartSeries::new("2024", vec![1.0, 2.0]))
+            .add_series(ChartSeries::new("2025", vec![2.0, 3.0]))
+            .data_table(table)
+            .build()
+    }
+
+    #[test]
+    fn test_data_table_replaces_legend() {
+        let xml = generate_chart_part_xml(&chart(ChartType::Line, DataTable::new().font_size(9)));
+        assert!(xml.contains(
+            "</c:valAx>\n<c:dTable><c:showHorzBorder val=\"1\"/><c:showVertBorder val=\"1\"/><c:showOutline val=\"1\"/><c:showKeys val=\"1\"/>\n<c:txPr>"
+        ));
+        assert!(xml.contains(r#"<a:defRPr sz="900">"#));
+        assert!(!xml.contains("<c:legend>"));
+
+        let xml = generate_chart_part_xml(&chart(ChartType::Line, DataTable::new().legend_keys(false).outline(false)));
+        assert!(xml.contains("<c:showOutline val=\"0\"/><c:showKeys val=\"0\"/></c:dTable>"));
+        assert!(xml.contains("<c:legend>"));
+    }
+
+    #[test]
+    fn test_no_data_table_on_horizontal_bars() {
+        let bar = chart(ChartType::Bar, DataTable::new());
+        assert!(!bar.shows_data_table() && bar.shows_legend());
+        assert!(!generate_chart_part_xml(&bar).contains("c:dTable"));
+        assert!(chart(ChartType::Combo, DataTable::new()).shows_data_table());
+    }
+}
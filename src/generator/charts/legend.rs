@@ -0,0 +1,286 @@
+//! Legend placement and overflow
+//!
+//! PowerPoint lays legends out itself, but doesn't make room for long ones:
+//! with many series a legend on the right runs past the chart frame or over
+//! the plot area. The chart estimates how many entries fit at the legend's
+//! position from the label size and the frame, and when they don't it
+//! either moves the legend to the bottom, wrapped into rows, and shrinks
+//! the plot area to make room ([`LegendOverflow::Wrap`]), or shows the
+//! entries that fit followed by a "+N more" note ([`LegendOverflow::Truncate`]).
+//! The note is drawn over the chart frame, like reference marks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::{Emu, XmlWriter};
+use crate::generator::shapes::{Shape, ShapeFill, ShapeType};
+use crate::generator::text::FormattedText;
+use super::data::Chart;
+use super::types::ChartType;
+
+/// Plot area edges (left, top, right, bottom) as fractions of the chart
+/// frame, leaving room for the title and axis labels
+const PLOT_EDGES: (f64, f64, f64, f64) = (0.12, 0.16, 0.96, 0.84);
+/// Width of a legend beside the plot area
+const SIDE_WIDTH: f64 = 0.2;
+/// Legend row height, in multiples of the label size
+const LINE_HEIGHT: f64 = 1.5;
+/// Average character width, in multiples of the label size
+const CHAR_WIDTH: f64 = 0.55;
+/// Legend key and the gap after each entry, in multiples of the label size
+const KEY_WIDTH: f64 = 2.5;
+/// Padding around the legend, as a fraction of the chart frame
+const PADDING: f64 = 0.02;
+/// Largest share of the chart frame's height a wrapped legend takes
+const MAX_WRAPPED_HEIGHT: f64 = 0.4;
+/// Smallest share of the chart frame's height left to the plot area
+const MIN_PLOT_HEIGHT: f64 = 0.2;
+const EMU_PER_POINT: f64 = 12700.0;
+
+/// Side of the chart the legend is drawn on
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LegendPosition {
+    #[default]
+    Right,
+    Bottom,
+    Top,
+    Left,
+}
+
+impl LegendPosition {
+    /// `c:legendPos` value
+    fn as_str(self) -> &'static str {
+        match self {
+            LegendPosition::Right => "r",
+            LegendPosition::Bottom => "b",
+            LegendPosition::Top => "t",
+            LegendPosition::Left => "l",
+        }
+    }
+
+    fn is_side(self) -> bool {
+        matches!(self, LegendPosition::Right | LegendPosition::Left)
+    }
+}
+
+/// What to do when the legend's entries don't fit at its position
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LegendOverflow {
+    /// Move the legend to the bottom (if it is on a side), wrap it into as
+    /// many rows as needed and shrink the plot area to make room
+    #[default]
+    Wrap,
+    /// Keep the legend where it is, showing the entries that fit and a
+    /// "+N more" note for the rest
+    Truncate,
+}
+
+/// Where the legend ends up and how much of it is shown
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LegendLayout {
+    pub position: LegendPosition,
+    /// Legend box (x, y, width, height) as fractions of the chart frame,
+    /// when it is placed manually
+    pub frame: Option<(f64, f64, f64, f64)>,
+    /// Height (top/bottom) or width (sides) the legend takes from the plot area
+    pub extent: f64,
+    /// Entries shown, in order; the rest are hidden
+    pub shown: usize,
+    pub total: usize,
+}
+
+impl Chart {
+    /// Legend entry texts: categories for pie and doughnut charts, series names otherwise
+    pub fn legend_entries(&self) -> Vec<&str> {
+        match self.chart_type {
+            ChartType::Pie | ChartType::Doughnut => self.categories.iter().map(String::as_str).collect(),
+            _ => self.series.iter().map(|s| s.name.as_str()).collect(),
+        }
+    }
+
+    /// Height of one legend row and width of one entry, as fractions of the chart frame
+    fn legend_metrics(&self) -> (f64, f64) {
+        let size = self.effective_style().label_size as f64;
+        let (width, height) = ((self.width.get() as f64 / EMU_PER_POINT).max(1.0), (self.height.get() as f64 / EMU_PER_POINT).max(1.0));
+        let longest = self.legend_entries().iter().map(|e| e.chars().count()).max().unwrap_or(0);
+        (LINE_HEIGHT * size / height, (KEY_WIDTH + CHAR_WIDTH * longest as f64) * size / width)
+    }
+
+    pub(crate) fn legend_layout(&self) -> LegendLayout {
+        let position = self.legend_position.unwrap_or_default();
+        let overflow = self.legend_overflow.unwrap_or_default();
+        let total = self.legend_entries().len();
+        let (line, entry) = self.legend_metrics();
+        let (_, top, _, bottom) = PLOT_EDGES;
+        let fit = LegendLayout { position, frame: None, extent: 0.0, shown: total, total };
+        if !self.shows_legend() {
+            return fit;
+        }
+
+        if position.is_side() {
+            let capacity = (((bottom - top) / line).floor() as usize).max(1);
+            if total <= capacity {
+                return LegendLayout { extent: SIDE_WIDTH, ..fit };
+            }
+            match overflow {
+                LegendOverflow::Wrap => self.row_layout(LegendPosition::Bottom, total, line, entry),
+                LegendOverflow::Truncate => {
+                    let shown = capacity.saturating_sub(1).max(1);
+                    let h = (shown + 1) as f64 * line + PADDING;
+                    let x = if position == LegendPosition::Right { 1.0 - SIDE_WIDTH - PADDING } else { PADDING };
+                    LegendLayout { frame: Some((x, (1.0 - h) / 2.0, SIDE_WIDTH, h)), extent: SIDE_WIDTH, shown, ..fit }
+                }
+            }
+        } else {
+            let per_row = (((1.0 - 2.0 * PADDING) / entry).floor() as usize).max(1);
+            if total <= per_row {
+                return LegendLayout { extent: line + PADDING, ..fit };
+            }
+            match overflow {
+                LegendOverflow::Wrap => self.row_layout(position, total, line, entry),
+                LegendOverflow::Truncate => {
+                    // One centered row of entries, with the note after the last
+                    let shown = per_row.saturating_sub(1).max(1);
+                    let width = shown as f64 * entry;
+                    let x = (1.0 - width - entry) / 2.0;
+                    let h = line + PADDING;
+                    let y = if position == LegendPosition::Bottom { 1.0 - h - PADDING } else { top - h };
+                    LegendLayout { frame: Some((x, y, width, h)), extent: h, shown, ..fit }
+                }
+            }
+        }
+    }
+
+    /// Legend across the top or bottom, wrapped into rows
+    fn row_layout(&self, position: LegendPosition, total: usize, line: f64, entry: f64) -> LegendLayout {
+        let per_row = (((1.0 - 2.0 * PADDING) / entry).floor() as usize).max(1);
+        let rows = total.div_ceil(per_row);
+        let h = (rows as f64 * line + PADDING).min(MAX_WRAPPED_HEIGHT);
+        let y = if position == LegendPosition::Bottom { 1.0 - h - PADDING } else { PLOT_EDGES.1 - h };
+        let frame = (rows > 1).then_some((PADDING, y, 1.0 - 2.0 * PADDING, h));
+        LegendLayout { position, frame, extent: h, shown: total, total }
+    }
+
+    /// Plot area (x, y, width, height) as fractions of the chart frame, when
+    /// it has to be fixed: for reference marks, or to make room for a legend
+    /// placed manually. A data table under the plot area takes room from it too.
+    pub(crate) fn plot_area(&self) -> Option<(f64, f64, f64, f64)> {
+        let legend = self.legend_layout();
+        if self.value_axis_bounds().is_none() && legend.frame.is_none() {
+            return None;
+        }
+        let (line, _) = self.legend_metrics();
+        let (mut left, mut top, mut right, mut bottom) = PLOT_EDGES;
+        match legend.position {
+            LegendPosition::Right => right -= legend.extent,
+            LegendPosition::Left => left += legend.extent,
+            LegendPosition::Bottom => bottom -= legend.extent,
+            LegendPosition::Top => top += legend.extent,
+        }
+        bottom = (bottom - self.data_table_rows() as f64 * line).max(top + MIN_PLOT_HEIGHT);
+        let round = |v: f64| (v * 10_000.0).round() / 10_000.0;
+        Some((round(left), round(top), round(right - left), round(bottom - top)))
+    }
+
+    /// "+N more" note after a truncated legend
+    pub fn legend_note(&self) -> Option<Shape> {
+        let legend = self.legend_layout();
+        let hidden = legend.total - legend.shown;
+        let (x, y, w, h) = legend.frame.filter(|_| hidden > 0)?;
+        let (line, entry) = self.legend_metrics();
+        let (width, height) = (self.width.get() as f64, self.height.get() as f64);
+        let (nx, ny, nw) = if legend.position.is_side() {
+            (x, y + h - line - PADDING / 2.0, w)
+        } else {
+            (x + w, y, entry)
+        };
+        let emu = |v: f64| Emu(v.round().max(0.0) as i64);
+        Some(Shape::new(
+            ShapeType::Rectangle,
+            self.x + emu(nx * width),
+            self.y + emu(ny * height),
+            emu(nw * width),
+            emu(line * height),
+        )
+            .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+            .with_rich_text(vec![FormattedText::new(&format!("+{hidden} more")).color("595959").font_size(self.effective_style().label_size)])
+            .with_name("Legend Overflow"))
+    }
+
+    /// `c:legendPos`, hidden entries and the layout of `c:legend`
+    pub(crate) fn write_legend_layout(&self, xml: &mut XmlWriter) {
+        let legend = self.legend_layout();
+        write!(xml, "\n<c:legendPos val=\"{}\"/>", legend.position.as_str());
+        for idx in legend.shown..legend.total {
+            write!(xml, "\n<c:legendEntry><c:idx val=\"{idx}\"/><c:delete val=\"1\"/></c:legendEntry>");
+        }
+        if let Some((x, y, w, h)) = legend.frame {
+            write!(
+                xml,
+                r#"
+<c:layout><c:manualLayout><c:xMode val="edge"/><c:yMode val="edge"/><c:x val="{x:.4}"/><c:y val="{y:.4}"/><c:w val="{w:.4}"/><c:h val="{h:.4}"/></c:manualLayout></c:layout>"#
+            );
+        } else {
+            xml.raw("\n<c:layout/>");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::charts::{ChartBuilder, ChartSeries};
+
+    fn chart(series: usize) -> ChartBuilder {
+        (0..series).fold(
+            ChartBuilder::new("Spend", ChartType::Line).categories(vec!["Q1"]).size(6_000_000, 3_000_000),
+            |chart, i| chart.add_series(ChartSeries::new(&format!("Department {i}"), vec![i as f64])),
+        )
+    }
+
+    #[test]
+    fn test_small_legend_keeps_automatic_layout() {
+        let chart = chart(3).build();
+        let layout = chart.legend_layout();
+        assert_eq!((layout.position, layout.frame, layout.shown), (LegendPosition::Right, None, 3));
+        assert_eq!(chart.plot_area(), None);
+        assert!(chart.legend_note().is_none());
+    }
+
+    #[test]
+    fn test_long_legend_wraps_at_bottom_and_shrinks_plot_area() {
+        let chart = chart(20).build();
+        let layout = chart.legend_layout();
+        assert_eq!((layout.position, layout.shown), (LegendPosition::Bottom, 20));
+        let (_, y, w, h) = layout.frame.unwrap();
+        assert!((y + h - (1.0 - PADDING)).abs() < 1e-9 && w > 0.9);
+        let (_, top, width, height) = chart.plot_area().unwrap();
+        assert_eq!(width, PLOT_EDGES.2 - PLOT_EDGES.0);
+        assert!(top + height <= y);
+    }
+
+    #[test]
+    fn test_truncated_legend_notes_hidden_entries() {
+        let chart = chart(20).legend_overflow(LegendOverflow::Truncate).build();
+        let layout = chart.legend_layout();
+        assert_eq!(layout.position, LegendPosition::Right);
+        assert!(layout.shown < 20);
+        let note = chart.legend_note().unwrap();
+        assert_eq!(note.rich_text[0].text, format!("+{} more", 20 - layout.shown));
+
+        let mut xml = XmlWriter::with_capacity(256);
+        chart.write_legend_layout(&mut xml);
+        let xml = xml.finish();
+        assert_eq!(xml.matches("<c:delete val=\"1\"/>").count(), 20 - layout.shown);
+        assert!(xml.contains(&format!("<c:idx val=\"{}\"/>", layout.shown)));
+    }
+
+    #[test]
+    fn test_bottom_legend_fitting_one_row() {
+        let chart = chart(2).legend_position(LegendPosition::Bottom).build();
+        let layout = chart.legend_layout();
+        assert_eq!((layout.frame, layout.shown), (None, 2));
+        assert_eq!(chart.plot_area(), None);
+    }
+}
@@ -4,23 +4,32 @@
 //! - `types` - Chart type definitions
 //! - `data` - Chart data structures (Series, Chart)
 //! - `builder` - Fluent chart builder
+//! - `style` - Shared chart styling (fonts, gridlines, plot area)
+//! - `template` - Named, serializable chart templates (style plus builder settings)
+//! - `reference` - Reference lines and target bands drawn over the plot area
+//! - `legend` - Legend placement and handling of legends too long to fit
+//! - `data_table` - Table of the chart's values under the plot area
+//! - `units` - Value axis display units (thousands, millions, ...)
 //! - `xml` - XML generation for charts
 
 mod types;
 mod data;
 mod builder;
+mod style;
+mod template;
+mod reference;
+mod legend;
+mod data_table;
+mod units;
 pub mod xml;
 
 pub use types::ChartType;
-pub use data::{Chart, ChartSeries};
+pub use data::{Chart, ChartDataSource, ChartSeries};
 pub use builder::ChartBuilder;
+pub use style::ChartStyle;
+pub use template::ChartTemplate;
+pub use reference::{ReferenceLine, TargetBand};
+pub use legend::{LegendOverflow, LegendPosition};
+pub use data_table::DataTable;
+pub use units::DisplayUnit;
 pub use xml::{generate_chart_part_xml, generate_chart_ref_xml};
-
-/// Escape XML special characters
-pub(crate) fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
@@ -0,0 +1,288 @@
+//! Reference lines and target bands
+//!
+//! A [`ReferenceLine`] marks one value on a chart's value axis (a target of
+//! 100, say) and a [`TargetBand`] shades a range of it. DrawingML charts have
+//! no element for either, so they are drawn as shapes over the chart frame.
+//! To make that position exact, a chart with marks gets a fixed plot area
+//! and explicit value axis bounds instead of PowerPoint's automatic ones.
+//!
+//! Marks run across the plot area, perpendicular to the value axis:
+//! horizontal on line, area, combo and stock charts, vertical on bar charts
+//! (which are drawn with horizontal bars). Pie, doughnut, radar, scatter
+//! and bubble charts don't support them.
+
+use crate::core::Emu;
+use crate::elements::ColorInput;
+use crate::generator::connectors::{Connector, ConnectorLine, LineDash};
+use crate::generator::shapes::{Shape, ShapeFill, ShapeType};
+use crate::generator::text::FormattedText;
+use super::data::Chart;
+use super::types::ChartType;
+
+/// Reference line width (1.5 pt)
+const LINE_WIDTH: u32 = 19050;
+/// Label font size in points
+const LABEL_FONT_SIZE: u32 = 10;
+/// Label box height (0.25 inch)
+const LABEL_HEIGHT: u32 = 228600;
+/// Label box width (1.5 inch)
+const LABEL_WIDTH: u32 = 1371600;
+
+/// Line across the plot area at one value
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReferenceLine {
+    pub value: f64,
+    pub label: Option<String>,
+    /// Line color (RGB hex)
+    pub color: String,
+    pub dash: LineDash,
+}
+
+impl ReferenceLine {
+    /// Dashed dark red line at `value`
+    pub fn new(value: f64) -> Self {
+        ReferenceLine { value, label: None, color: "C62828".to_string(), dash: LineDash::Dash }
+    }
+
+    /// Text drawn next to the line, e.g. "Target"
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = color.into().to_hex();
+        self
+    }
+
+    pub fn dash(mut self, dash: LineDash) -> Self {
+        self.dash = dash;
+        self
+    }
+}
+
+/// Shaded range of values across the plot area
+#[derive(Clone, Debug, PartialEq)]
+pub struct TargetBand {
+    pub from: f64,
+    pub to: f64,
+    pub label: Option<String>,
+    /// Fill color (RGB hex)
+    pub color: String,
+    /// Fill transparency in percent, so the data stays visible under the band
+    pub transparency: u32,
+}
+
+impl TargetBand {
+    /// Translucent green band between `from` and `to` (in either order)
+    pub fn new(from: f64, to: f64) -> Self {
+        TargetBand {
+            from: from.min(to),
+            to: from.max(to),
+            label: None,
+            color: "43A047".to_string(),
+            transparency: 75,
+        }
+    }
+
+    /// Text drawn inside the band, at its start
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = color.into().to_hex();
+        self
+    }
+
+    /// Set the fill transparency (0-100)
+    pub fn transparency(mut self, percent: u32) -> Self {
+        self.transparency = percent.min(100);
+        self
+    }
+}
+
+impl Chart {
+    /// Whether the chart has reference lines or target bands
+    pub fn has_reference_marks(&self) -> bool {
+        !self.reference_lines.is_empty() || !self.target_bands.is_empty()
+    }
+
+    /// Whether the chart type has a value axis marks can be drawn against
+    pub(crate) fn supports_reference_marks(&self) -> bool {
+        !matches!(
+            self.chart_type,
+            ChartType::Pie | ChartType::Doughnut | ChartType::Radar | ChartType::RadarFilled
+                | ChartType::Scatter | ChartType::ScatterLines | ChartType::ScatterSmooth | ChartType::Bubble
+        )
+    }
+
+    /// Whether the value axis runs horizontally (bar charts)
+    fn value_axis_horizontal(&self) -> bool {
+        matches!(
+            self.chart_type,
+            ChartType::Bar | ChartType::BarHorizontal | ChartType::BarStacked | ChartType::BarStacked100
+        )
+    }
+
+    /// Value axis bounds written to the chart when it has marks
+    ///
+    /// Covers the data, zero and every mark, rounded outwards to a step of
+    /// 1, 2 or 5 times a power of ten. Only area charts are drawn stacked,
+    /// so only their series are summed per category.
+    pub fn value_axis_bounds(&self) -> Option<(f64, f64)> {
+        if !self.has_reference_marks() || !self.supports_reference_marks() {
+            return None;
+        }
+        let mut values: Vec<f64> = match self.chart_type {
+            ChartType::AreaStacked100 => return Some((0.0, 1.0)),
+            ChartType::AreaStacked => {
+                let count = self.series.iter().map(|s| s.values.len()).max().unwrap_or(0);
+                (0..count).flat_map(|i| {
+                    let at = |positive: bool| self.series.iter()
+                        .filter_map(|s| s.values.get(i))
+                        .filter(|v| (**v >= 0.0) == positive)
+                        .sum::<f64>();
+                    [at(true), at(false)]
+                }).collect()
+            }
+            _ => self.series.iter().flat_map(|s| s.values.iter().copied()).collect(),
+        };
+        values.extend(self.reference_lines.iter().map(|l| l.value));
+        values.extend(self.target_bands.iter().flat_map(|b| [b.from, b.to]));
+
+        let finite = values.into_iter().filter(|v| v.is_finite());
+        let (low, high) = finite.fold((0.0f64, 0.0f64), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        if high == low {
+            return Some((low, low + 1.0));
+        }
+        let raw_step = (high - low) / 10.0;
+        let magnitude = 10f64.powf(raw_step.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0].iter().map(|m| m * magnitude).find(|s| *s >= raw_step).unwrap_or(raw_step);
+        Some(((low / step).floor() * step, (high / step).ceil() * step))
+    }
+
+    /// Plot area in slide EMU as (x, y, width, height)
+    fn plot_frame(&self) -> (f64, f64, f64, f64) {
+        let (left, top, width, height) = self.plot_area().unwrap_or_default();
+        let (w, h) = (self.width.get() as f64, self.height.get() as f64);
+        (self.x.get() as f64 + left * w, self.y.get() as f64 + top * h, width * w, height * h)
+    }
+
+    /// Band rectangles and labels (as shapes) and reference lines (as connectors)
+    ///
+    /// Empty unless the chart supports marks; drawn above the chart.
+    pub fn reference_marks(&self) -> (Vec<Shape>, Vec<Connector>) {
+        let Some((min, max)) = self.value_axis_bounds() else {
+            return (Vec::new(), Vec::new());
+        };
+        let (px, py, pw, ph) = self.plot_frame();
+        let horizontal_axis = self.value_axis_horizontal();
+        // Offset of `value` along the value axis, in EMU from the plot area's start
+        let along = |value: f64| {
+            let fraction = ((value - min) / (max - min)).clamp(0.0, 1.0);
+            if horizontal_axis { fraction * pw } else { (1.0 - fraction) * ph }
+        };
+        let emu = |v: f64| Emu(v.round() as i64);
+        let label = |text: &str, color: &str, x: f64, y: f64| {
+            Shape::new(ShapeType::Rectangle, emu(x), emu(y), LABEL_WIDTH, LABEL_HEIGHT)
+                .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+                .with_rich_text(vec![FormattedText::new(text).bold().color(color).font_size(LABEL_FONT_SIZE)])
+                .with_name(&format!("Reference Label: {text}"))
+        };
+
+        let mut shapes = Vec::new();
+        for band in &self.target_bands {
+            let (start, end) = (along(band.from), along(band.to));
+            let (x, y, w, h) = if horizontal_axis {
+                (px + start, py, end - start, ph)
+            } else {
+                (px, py + end, pw, start - end)
+            };
+            shapes.push(Shape::new(ShapeType::Rectangle, emu(x), emu(y), emu(w), emu(h))
+                .with_fill(ShapeFill::new(band.color.as_str()).with_transparency(band.transparency))
+                .with_name("Target Band"));
+            if let Some(text) = &band.label {
+                // Bottom-left corner inside the band, or its top on bar charts
+                let (lx, ly) = if horizontal_axis { (x, y) } else { (x, y + h - LABEL_HEIGHT as f64) };
+                shapes.push(label(text, &band.color, lx, ly));
+            }
+        }
+
+        let mut lines = Vec::new();
+        for line in &self.reference_lines {
+            let offset = along(line.value);
+            let ((x1, y1), (x2, y2)) = if horizontal_axis {
+                ((px + offset, py), (px + offset, py + ph))
+            } else {
+                ((px, py + offset), (px + pw, py + offset))
+            };
+            lines.push(Connector::straight(emu(x1), emu(y1), emu(x2), emu(y2))
+                .with_line(ConnectorLine::new(line.color.as_str(), LINE_WIDTH).with_dash(line.dash)));
+            if let Some(text) = &line.label {
+                // Above the right end of the line, or beside its top on bar charts
+                let (lx, ly) = if horizontal_axis {
+                    (x1 + LINE_WIDTH as f64, py - LABEL_HEIGHT as f64)
+                } else {
+                    (x2 - LABEL_WIDTH as f64, y1 - LABEL_HEIGHT as f64)
+                };
+                shapes.push(label(text, &line.color, lx, ly));
+            }
+        }
+        (shapes, lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::charts::ChartSeries;
+
+    fn line_chart() -> Chart {
+        Chart::new("Sales", ChartType::Line, vec!["Q1".into(), "Q2".into()], 1_000_000, 1_000_000, 5_000_000, 4_000_000)
+            .add_series(ChartSeries::new("2024", vec![40.0, 100.0]))
+    }
+
+    #[test]
+    fn test_bounds_cover_data_and_marks() {
+        assert_eq!(line_chart().value_axis_bounds(), None);
+        let chart = line_chart().with_reference_line(ReferenceLine::new(120.0));
+        assert_eq!(chart.value_axis_bounds(), Some((0.0, 120.0)));
+        let chart = line_chart().with_target_band(TargetBand::new(90.0, -15.0));
+        assert_eq!(chart.value_axis_bounds(), Some((-20.0, 100.0)));
+    }
+
+    #[test]
+    fn test_horizontal_line_on_line_chart() {
+        let chart = line_chart().with_reference_line(ReferenceLine::new(60.0).label("Target"));
+        let (shapes, lines) = chart.reference_marks();
+        let (plot_y, plot_h) = (1_000_000.0 + 0.16 * 4_000_000.0, 0.68 * 4_000_000.0);
+        // Bounds are 0..100, so 60 is 40% down from the plot area's top
+        let y = (plot_y + 0.4 * plot_h) as i64;
+        assert_eq!((lines[0].start_y.0, lines[0].end_y.0), (y, y));
+        assert_eq!(lines[0].line.dash, LineDash::Dash);
+        assert_eq!(shapes[0].rich_text[0].text, "Target");
+    }
+
+    #[test]
+    fn test_band_is_vertical_on_bar_chart() {
+        let chart = Chart::new("Scores", ChartType::Bar, vec!["A".into()], 0, 0, 1_000_000, 1_000_000)
+            .add_series(ChartSeries::new("S", vec![100.0]))
+            .with_target_band(TargetBand::new(50.0, 75.0));
+        let (shapes, lines) = chart.reference_marks();
+        assert!(lines.is_empty());
+        let band = &shapes[0];
+        assert_eq!((band.x.0, band.width.0), (120_000 + 320_000, 160_000));
+        assert_eq!((band.y.0, band.height.0), (160_000, 680_000));
+    }
+
+    #[test]
+    fn test_pie_chart_has_no_marks() {
+        let chart = Chart::new("Mix", ChartType::Pie, vec!["A".into()], 0, 0, 1_000_000, 1_000_000)
+            .add_series(ChartSeries::new("S", vec![1.0]))
+            .with_reference_line(ReferenceLine::new(0.5));
+        assert!(chart.reference_marks().0.is_empty());
+        assert!(chart.validate().is_err());
+    }
+}
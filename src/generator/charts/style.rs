@@ -0,0 +1,179 @@
+//! Shared chart styling (typography, gridlines, plot area)
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::escape_xml_attr;
+use crate::elements::ColorInput;
+
+/// Chart-wide styling shared across many charts
+///
+/// Apply the same `ChartStyle` to every chart in a deck through
+/// `ChartBuilder::style()` so titles, axis labels, legends and gridlines
+/// use consistent typography and colors.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChartStyle {
+    /// Font family used for all chart text (e.g., "Calibri")
+    pub font: String,
+    /// Chart title size in points
+    pub title_size: u32,
+    /// Axis, legend and series label size in points
+    pub label_size: u32,
+    /// Major gridline color (RGB hex), `None` keeps the application default
+    pub gridline_color: Option<String>,
+    /// Plot area background color (RGB hex), `None` leaves it transparent
+    pub plot_area_fill: Option<String>,
+}
+
+impl Default for ChartStyle {
+    fn default() -> Self {
+        ChartStyle {
+            font: "Calibri".to_string(),
+            title_size: 18,
+            label_size: 10,
+            gridline_color: None,
+            plot_area_fill: None,
+        }
+    }
+}
+
+impl ChartStyle {
+    /// Create a chart style with default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set font family
+    pub fn font(mut self, font: &str) -> Self {
+        self.font = font.to_string();
+        self
+    }
+
+    /// Set title size in points
+    pub fn title_size(mut self, size: u32) -> Self {
+        self.title_size = size;
+        self
+    }
+
+    /// Set label size in points
+    pub fn label_size(mut self, size: u32) -> Self {
+        self.label_size = size;
+        self
+    }
+
+    /// Set major gridline color (RGB hex)
+    pub fn gridline_color(mut self, hex_color: impl Into<ColorInput>) -> Self {
+        self.gridline_color = Some(hex_color.into().to_hex());
+        self
+    }
+
+    /// Set plot area fill color (RGB hex)
+    pub fn plot_area_fill(mut self, hex_color: impl Into<ColorInput>) -> Self {
+        self.plot_area_fill = Some(hex_color.into().to_hex());
+        self
+    }
+
+    /// Generate `c:txPr` for text at the given size (in points)
+    pub fn text_properties_xml(&self, size: u32) -> String {
+        self.rotated_text_properties_xml(size, None)
+    }
+
+    /// Generate `c:txPr` for text at the given size, optionally rotated
+    /// by `rotation` degrees (emitted as `a:bodyPr rot` in 60000ths of a degree)
+    ///
+    /// Text can't be turned past vertical, so the angle is clamped to ±90°.
+    pub fn rotated_text_properties_xml(&self, size: u32, rotation: Option<i32>) -> String {
+        let body_pr = match rotation {
+            Some(deg) => format!(r#"<a:bodyPr rot="{}" vert="horz"/>"#, i64::from(deg.clamp(-90, 90)) * 60000),
+            None => "<a:bodyPr/>".to_string(),
+        };
+        format!(
+            r#"
+<c:txPr>
+{}
+<a:lstStyle/>
+<a:p>
+<a:pPr>
+<a:defRPr sz="{}">
+<a:latin typeface="{}"/>
+</a:defRPr>
+</a:pPr>
+<a:endParaRPr lang="en-US"/>
+</a:p>
+</c:txPr>"#,
+            body_pr,
+            size * 100,
+            escape_xml_attr(&self.font)
+        )
+    }
+
+    /// Generate `c:txPr` for label text (axes, legend)
+    pub fn label_text_xml(&self) -> String {
+        self.text_properties_xml(self.label_size)
+    }
+
+    /// Generate `c:majorGridlines`, colored when a gridline color is set
+    pub fn major_gridlines_xml(&self) -> String {
+        match &self.gridline_color {
+            Some(color) => format!(
+                r#"
+<c:majorGridlines>
+<c:spPr>
+<a:ln w="9525">
+<a:solidFill>
+<a:srgbClr val="{}"/>
+</a:solidFill>
+</a:ln>
+</c:spPr>
+</c:majorGridlines>"#,
+                escape_xml_attr(color)
+            ),
+            None => "\n<c:majorGridlines/>".to_string(),
+        }
+    }
+
+    /// Generate plot area `c:spPr` if a fill is set
+    pub fn plot_area_xml(&self) -> String {
+        match &self.plot_area_fill {
+            Some(color) => format!(
+                r#"
+<c:spPr>
+<a:solidFill>
+<a:srgbClr val="{}"/>
+</a:solidFill>
+</c:spPr>"#,
+                escape_xml_attr(color)
+            ),
+            None => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chart_style_defaults() {
+        let style = ChartStyle::default();
+        assert_eq!(style.font, "Calibri");
+        assert_eq!(style.title_size, 18);
+        assert_eq!(style.label_size, 10);
+        assert!(style.gridline_color.is_none());
+        assert!(style.plot_area_xml().is_empty());
+    }
+
+    #[test]
+    fn test_chart_style_xml() {
+        let style = ChartStyle::new()
+            .font("Arial")
+            .label_size(12)
+            .gridline_color("#d9d9d9")
+            .plot_area_fill("F2F2F2");
+
+        assert!(style.label_text_xml().contains(r#"sz="1200""#));
+        assert!(style.label_text_xml().contains(r#"typeface="Arial""#));
+        assert!(style.major_gridlines_xml().contains("D9D9D9"));
+        assert!(style.plot_area_xml().contains("F2F2F2"));
+    }
+}
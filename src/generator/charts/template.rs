@@ -0,0 +1,236 @@
+//! Reusable named chart templates
+//!
+//! A [`ChartTemplate`] bundles a [`ChartStyle`] with the builder settings a
+//! team wants on every chart (series palette, category label handling), much
+//! like a PowerPoint `.crtx` file. Templates serialize to JSON, so they can
+//! be kept next to the brand assets and shared between decks:
+//!
+//! ```json
+//! {
+//!   "name": "corporate",
+//!   "style": { "font": "Arial", "title_size": 20, "gridline_color": "D9D9D9" },
+//!   "series_colors": ["1565C0", "FF6F00", "2E7D32"],
+//!   "category_label_rotation": -45
+//! }
+//! ```
+//!
+//! Apply one with [`ChartBuilder::template`](super::ChartBuilder::template)
+//! or [`ChartTemplate::apply_to`]; settings a chart makes itself win over
+//! the template's.
+
+use serde::{Deserialize, Serialize};
+
+use super::data::Chart;
+use super::style::ChartStyle;
+use super::legend::{LegendOverflow, LegendPosition};
+use super::data_table::DataTable;
+use super::units::DisplayUnit;
+use crate::elements::ColorInput;
+use crate::exc::{PptxError, Result};
+
+/// Named chart style and builder settings applied across many charts
+///
+/// ```
+/// use ppt_rs::generator::{ChartBuilder, ChartSeries, ChartStyle, ChartTemplate, ChartType};
+///
+/// let corporate = ChartTemplate::new("corporate")
+///     .style(ChartStyle::new().font("Arial"))
+///     .series_colors(["1565C0", "FF6F00"]);
+///
+/// let chart = ChartBuilder::new("Revenue", ChartType::Bar)
+///     .template(&corporate)
+///     .add_series(ChartSeries::new("2024", vec![1.0, 2.0]))
+///     .add_series(ChartSeries::new("2025", vec![2.0, 3.0]).with_color("000000"))
+///     .build();
+/// assert_eq!(chart.series[0].color.as_deref(), Some("1565C0"));
+/// assert_eq!(chart.series[1].color.as_deref(), Some("000000"));
+/// assert_eq!(ChartTemplate::from_json(&corporate.to_json()).unwrap(), corporate);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChartTemplate {
+    /// Name the template is known by, e.g. `corporate`
+    pub name: String,
+    /// Typography, gridlines and plot area
+    pub style: Option<ChartStyle>,
+    /// Colors (hex or CSS names) for series without their own, in series order and repeated
+    pub series_colors: Vec<String>,
+    /// Category axis label rotation in degrees
+    pub category_label_rotation: Option<i32>,
+    /// Wrap category labels after this many characters
+    pub category_label_wrap: Option<usize>,
+    /// Truncate category labels longer than this many characters
+    pub category_label_max_len: Option<usize>,
+    /// Side the legend is drawn on
+    pub legend_position: Option<LegendPosition>,
+    /// Handling of legends too long for their position
+    pub legend_overflow: Option<LegendOverflow>,
+    /// Data table under the plot area
+    pub data_table: Option<DataTable>,
+    /// Power of ten the value axis labels are shown in
+    pub display_units: Option<DisplayUnit>,
+    /// Value axis unit label replacing the unit's name
+    pub unit_label: Option<String>,
+}
+
+impl ChartTemplate {
+    /// Empty template called `name`
+    pub fn new(name: &str) -> Self {
+        ChartTemplate {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Parse a template from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| PptxError::InvalidValue(format!("invalid chart template: {e}")))
+    }
+
+    /// Read a JSON template from a file
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// The template as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("chart templates always serialize")
+    }
+
+    /// Write the template to a JSON file
+    pub fn save<P: AsRef<std::path::Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path, self.to_json())?;
+        Ok(())
+    }
+
+    /// Set the chart style
+    pub fn style(mut self, style: ChartStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Set the series palette
+    pub fn series_colors<C: Into<ColorInput>>(mut self, colors: impl IntoIterator<Item = C>) -> Self {
+        self.series_colors = colors.into_iter().map(|c| c.into().to_hex()).collect();
+        self
+    }
+
+    /// Rotate category axis labels by `degrees`
+    pub fn category_label_rotation(mut self, degrees: i32) -> Self {
+        self.category_label_rotation = Some(degrees);
+        self
+    }
+
+    /// Wrap category labels after `width` characters
+    ///
+    /// Shortens the category text itself; see [`Chart::display_categories`].
+    pub fn wrap_category_labels(mut self, width: usize) -> Self {
+        self.category_label_wrap = Some(width);
+        self
+    }
+
+    /// Truncate category labels longer than `max_len` characters
+    ///
+    /// Shortens the category text itself; see [`Chart::display_categories`].
+    pub fn truncate_category_labels(mut self, max_len: usize) -> Self {
+        self.category_label_max_len = Some(max_len);
+        self
+    }
+
+    /// Draw the legend on the given side of the chart
+    pub fn legend_position(mut self, position: LegendPosition) -> Self {
+        self.legend_position = Some(position);
+        self
+    }
+
+    /// Choose how a legend with more entries than fit is laid out
+    pub fn legend_overflow(mut self, overflow: LegendOverflow) -> Self {
+        self.legend_overflow = Some(overflow);
+        self
+    }
+
+    /// Show the values in a data table under the plot area
+    pub fn data_table(mut self, table: DataTable) -> Self {
+        self.data_table = Some(table);
+        self
+    }
+
+    /// Label the value axis in the given unit
+    pub fn display_units(mut self, unit: DisplayUnit) -> Self {
+        self.display_units = Some(unit);
+        self
+    }
+
+    /// Replace the display unit's name on the value axis
+    pub fn unit_label(mut self, label: &str) -> Self {
+        self.unit_label = Some(label.to_string());
+        self
+    }
+
+    /// Fill in what `chart` doesn't set itself from the template
+    pub fn apply_to(&self, chart: &mut Chart) {
+        if chart.style.is_none() {
+            chart.style = self.style.clone();
+        }
+        chart.category_label_rotation = chart.category_label_rotation.or(self.category_label_rotation);
+        chart.category_label_wrap = chart.category_label_wrap.or(self.category_label_wrap);
+        chart.category_label_max_len = chart.category_label_max_len.or(self.category_label_max_len);
+        chart.legend_position = chart.legend_position.or(self.legend_position);
+        chart.legend_overflow = chart.legend_overflow.or(self.legend_overflow);
+        chart.data_table = chart.data_table.or(self.data_table);
+        if chart.display_units.is_none() {
+            chart.display_units = self.display_units;
+            chart.unit_label = chart.unit_label.take().or_else(|| self.unit_label.clone());
+        }
+        if !self.series_colors.is_empty() {
+            for (series, color) in chart.series.iter_mut().zip(self.series_colors.iter().cycle()) {
+                if series.color.is_none() {
+                    series.color = Some(ColorInput::from(color.as_str()).to_hex());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::charts::{ChartBuilder, ChartSeries, ChartType};
+
+    #[test]
+    fn test_template_from_json() {
+        let template = ChartTemplate::from_json(r#"{
+            "name": "corporate",
+            "style": { "font": "Arial", "gridline_color": "D9D9D9" },
+            "series_colors": ["navy"],
+            "category_label_rotation": -45
+        }"#).unwrap();
+        let style = template.style.as_ref().unwrap();
+        assert_eq!((style.font.as_str(), style.title_size), ("Arial", 18));
+        assert!(ChartTemplate::from_json(r#"{"name": "x", "colour": "red"}"#).is_err());
+
+        let mut chart = ChartBuilder::new("Revenue", ChartType::Bar)
+            .category_label_rotation(30)
+            .add_series(ChartSeries::new("A", vec![1.0]))
+            .add_series(ChartSeries::new("B", vec![2.0]))
+            .build();
+        template.apply_to(&mut chart);
+        assert_eq!(chart.style.as_ref().map(|s| s.font.as_str()), Some("Arial"));
+        assert_eq!(chart.category_label_rotation, Some(30));
+        assert_eq!(chart.series[0].color, chart.series[1].color);
+        assert_eq!(chart.series[0].color.as_deref(), Some("000080"));
+    }
+
+    #[test]
+    fn test_template_round_trips_through_json() {
+        let template = ChartTemplate::new("board")
+            .style(ChartStyle::new().label_size(12).plot_area_fill("F2F2F2"))
+            .series_colors(["#1565c0"])
+            .wrap_category_labels(12)
+            .legend_overflow(LegendOverflow::Truncate);
+        let json = template.to_json();
+        assert!(json.contains(r#""name": "board""#));
+        assert!(json.contains(r#""legend_overflow": "truncate""#));
+        assert_eq!(ChartTemplate::from_json(&json).unwrap(), template);
+    }
+}
@@ -48,6 +48,22 @@ pub enum ChartType {
 }
 
 impl ChartType {
+    /// Every chart type, in declaration order
+    pub const ALL: [ChartType; 21] = [
+        ChartType::Bar, ChartType::BarHorizontal, ChartType::BarStacked, ChartType::BarStacked100,
+        ChartType::Line, ChartType::LineMarkers, ChartType::LineStacked,
+        ChartType::Pie, ChartType::Doughnut,
+        ChartType::Area, ChartType::AreaStacked, ChartType::AreaStacked100,
+        ChartType::Scatter, ChartType::ScatterLines, ChartType::ScatterSmooth, ChartType::Bubble,
+        ChartType::Radar, ChartType::RadarFilled,
+        ChartType::StockHLC, ChartType::StockOHLC, ChartType::Combo,
+    ];
+
+    /// Chart type for an [`as_str`](Self::as_str) name such as `"barStacked"`
+    pub fn from_name(name: &str) -> Option<ChartType> {
+        Self::ALL.into_iter().find(|t| t.as_str() == name)
+    }
+
     /// Get string representation
     pub fn as_str(&self) -> &str {
         match self {
@@ -158,6 +174,14 @@ mod tests {
         assert_eq!(ChartType::Radar.as_str(), "radar");
     }
 
+    #[test]
+    fn test_chart_type_from_name() {
+        for chart_type in ChartType::ALL {
+            assert_eq!(ChartType::from_name(chart_type.as_str()), Some(chart_type));
+        }
+        assert_eq!(ChartType::from_name("histogram"), None);
+    }
+
     #[test]
     fn test_chart_type_xml_element() {
         assert_eq!(ChartType::Bar.xml_element(), "c:barChart");
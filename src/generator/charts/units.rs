@@ -0,0 +1,126 @@
+//! Value axis display units
+//!
+//! Display units (`c:dispUnits`) scale the value axis labels without
+//! touching the data: with millions, 1,200,000 is labelled 1.2 and the axis
+//! gets a unit label such as "Millions" or a custom "($M)".
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::Escaped;
+use super::data::Chart;
+use super::types::ChartType;
+
+/// Power of ten value axis labels are divided by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DisplayUnit {
+    Hundreds,
+    Thousands,
+    TenThousands,
+    HundredThousands,
+    Millions,
+    TenMillions,
+    HundredMillions,
+    Billions,
+    Trillions,
+}
+
+impl DisplayUnit {
+    /// `c:builtInUnit` value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplayUnit::Hundreds => "hundreds",
+            DisplayUnit::Thousands => "thousands",
+            DisplayUnit::TenThousands => "tenThousands",
+            DisplayUnit::HundredThousands => "hundredThousands",
+            DisplayUnit::Millions => "millions",
+            DisplayUnit::TenMillions => "tenMillions",
+            DisplayUnit::HundredMillions => "hundredMillions",
+            DisplayUnit::Billions => "billions",
+            DisplayUnit::Trillions => "trillions",
+        }
+    }
+
+    /// What values are divided by for their labels
+    pub fn divisor(&self) -> f64 {
+        match self {
+            DisplayUnit::Hundreds => 1e2,
+            DisplayUnit::Thousands => 1e3,
+            DisplayUnit::TenThousands => 1e4,
+            DisplayUnit::HundredThousands => 1e5,
+            DisplayUnit::Millions => 1e6,
+            DisplayUnit::TenMillions => 1e7,
+            DisplayUnit::HundredMillions => 1e8,
+            DisplayUnit::Billions => 1e9,
+            DisplayUnit::Trillions => 1e12,
+        }
+    }
+}
+
+impl Chart {
+    /// `c:dispUnits` for the value axis at `ax_pos`, closing the axis
+    ///
+    /// XY charts have two value axes; only the vertical one, which carries
+    /// the series values, is scaled. The unit label reads sideways along a
+    /// vertical axis, as PowerPoint draws it.
+    pub(crate) fn display_units_xml(&self, ax_pos: &str) -> String {
+        let Some(unit) = self.display_units else {
+            return String::new();
+        };
+        let xy = matches!(
+            self.chart_type,
+            ChartType::Scatter | ChartType::ScatterLines | ChartType::ScatterSmooth | ChartType::Bubble
+        );
+        if xy && ax_pos == "b" {
+            return String::new();
+        }
+        let text = match &self.unit_label {
+            Some(label) => {
+                let body = if matches!(ax_pos, "l" | "r") { r#"<a:bodyPr rot="-5400000" vert="horz"/>"# } else { "<a:bodyPr/>" };
+                format!(
+                    "<c:tx><c:rich>{body}<a:lstStyle/><a:p><a:r><a:t>{}</a:t></a:r></a:p></c:rich></c:tx>",
+                    Escaped(label)
+                )
+            }
+            None => String::new(),
+        };
+        let text_properties = self.style.as_ref().map(|s| s.label_text_xml()).unwrap_or_default();
+        format!(
+            "\n<c:dispUnits><c:builtInUnit val=\"{}\"/><c:dispUnitsLbl>{text}{text_properties}</c:dispUnitsLbl></c:dispUnits>",
+            unit.as_str()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::charts::{generate_chart_part_xml, ChartBuilder, ChartSeries};
+
+    #[test]
+    fn test_display_units_with_label() {
+        let chart = ChartBuilder::new("Revenue", ChartType::Line)
+            .categories(vec!["2024", "2025"])
+            .add_series(ChartSeries::new("Revenue", vec![1_200_000.0, 1_800_000.0]))
+            .display_units(DisplayUnit::Millions)
+            .unit_label("($M) & more")
+            .build();
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains(
+            r#"<c:crosses val="autoZero"/>
+<c:dispUnits><c:builtInUnit val="millions"/><c:dispUnitsLbl><c:tx><c:rich><a:bodyPr rot="-5400000" vert="horz"/>"#
+        ));
+        assert!(xml.contains("<a:t>($M) &amp; more</a:t></a:r></a:p></c:rich></c:tx></c:dispUnitsLbl></c:dispUnits>\n</c:valAx>"));
+        assert_eq!(DisplayUnit::Millions.divisor(), 1e6);
+    }
+
+    #[test]
+    fn test_xy_charts_scale_only_the_vertical_axis() {
+        let chart = ChartBuilder::new("Spread", ChartType::Scatter)
+            .add_series(ChartSeries::new("Deals", vec![25_000.0, 40_000.0]))
+            .display_units(DisplayUnit::Thousands)
+            .build();
+        let xml = generate_chart_part_xml(&chart);
+        assert_eq!(xml.matches(r#"<c:builtInUnit val="thousands"/><c:dispUnitsLbl></c:dispUnitsLbl>"#).count(), 1);
+    }
+}
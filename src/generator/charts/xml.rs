@@ -1,31 +1,43 @@
 //! Chart XML generation
 
+use std::fmt::Display;
+
+use crate::core::{Escaped, EscapedAttr, XmlWriter};
 use super::types::ChartType;
 use super::data::Chart;
-use super::escape_xml;
+
+/// ID of the category axis, or of the X axis of XY charts
+const CATEGORY_AXIS_ID: u32 = 1;
+/// ID of the value axis
+const VALUE_AXIS_ID: u32 = 2;
 
 /// Generate chart XML content (for ppt/charts/chartN.xml)
 pub fn generate_chart_part_xml(chart: &Chart) -> String {
+    let points: usize = chart.series.iter().map(|s| s.values.len()).sum();
+    let mut xml = XmlWriter::with_capacity(4096 + 64 * (points + chart.category_count()));
+    write_chart_part_header(&mut xml, chart);
     match chart.chart_type {
         ChartType::Bar | ChartType::BarHorizontal | ChartType::BarStacked | ChartType::BarStacked100 => {
-            generate_bar_chart_xml(chart)
+            write_bar_chart_xml(&mut xml, chart)
         }
         ChartType::Line | ChartType::LineMarkers | ChartType::LineStacked => {
-            generate_line_chart_xml(chart)
+            write_line_chart_xml(&mut xml, chart)
         }
-        ChartType::Pie => generate_pie_chart_xml(chart),
-        ChartType::Doughnut => generate_doughnut_chart_xml(chart),
+        ChartType::Pie => write_pie_chart_xml(&mut xml, chart),
+        ChartType::Doughnut => write_doughnut_chart_xml(&mut xml, chart),
         ChartType::Area | ChartType::AreaStacked | ChartType::AreaStacked100 => {
-            generate_area_chart_xml(chart)
+            write_area_chart_xml(&mut xml, chart)
         }
         ChartType::Scatter | ChartType::ScatterLines | ChartType::ScatterSmooth => {
-            generate_scatter_chart_xml(chart)
+            write_scatter_chart_xml(&mut xml, chart)
         }
-        ChartType::Bubble => generate_bubble_chart_xml(chart),
-        ChartType::Radar | ChartType::RadarFilled => generate_radar_chart_xml(chart),
-        ChartType::StockHLC | ChartType::StockOHLC => generate_stock_chart_xml(chart),
-        ChartType::Combo => generate_combo_chart_xml(chart),
+        ChartType::Bubble => write_bubble_chart_xml(&mut xml, chart),
+        ChartType::Radar | ChartType::RadarFilled => write_radar_chart_xml(&mut xml, chart),
+        ChartType::StockHLC | ChartType::StockOHLC => write_stock_chart_xml(&mut xml, chart),
+        ChartType::Combo => write_combo_chart_xml(&mut xml, chart),
     }
+    write_chart_part_footer(&mut xml, chart);
+    xml.finish()
 }
 
 /// Generate chart reference XML for slide (p:graphicFrame)
@@ -51,9 +63,11 @@ pub fn generate_chart_ref_xml(chart: &Chart, r_id: &str, shape_id: usize) -> Str
     )
 }
 
-/// Generate the chart part header
-fn chart_part_header(chart: &Chart) -> String {
-    format!(
+/// Write the chart part header
+fn write_chart_part_header(xml: &mut XmlWriter, chart: &Chart) {
+    let style = chart.effective_style();
+    write!(
+        xml,
         r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
 <c:date1904 val="0"/>
@@ -70,11 +84,11 @@ fn chart_part_header(chart: &Chart) -> String {
 <a:defRPr/>
 </a:pPr>
 <a:r>
-<a:rPr lang="en-US" sz="1800" b="0" i="0" u="none" strike="noStrike">
+<a:rPr lang="en-US" sz="{}" b="0" i="0" u="none" strike="noStrike">
 <a:solidFill>
 <a:srgbClr val="595959"/>
 </a:solidFill>
-<a:latin typeface="Calibri"/>
+<a:latin typeface="{}"/>
 </a:rPr>
 <a:t>{}</a:t>
 </a:r>
@@ -85,638 +99,483 @@ fn chart_part_header(chart: &Chart) -> String {
 <c:overlay val="0"/>
 </c:title>
 <c:autoTitleDeleted val="0"/>
-<c:plotArea>
-<c:layout/>"#,
-        escape_xml(&chart.title)
-    )
+<c:plotArea>"#,
+        style.title_size * 100,
+        EscapedAttr(&style.font),
+        Escaped(&chart.title)
+    );
+    // Reference marks are drawn over the chart, so they need the plot area
+    // where they expect it rather than wherever PowerPoint would put it;
+    // a rearranged legend needs room taken from it
+    if let Some((x, y, w, h)) = chart.plot_area() {
+        write!(
+            xml,
+            r#"
+<c:layout><c:manualLayout><c:layoutTarget val="inner"/><c:xMode val="edge"/><c:yMode val="edge"/><c:x val="{x}"/><c:y val="{y}"/><c:w val="{w}"/><c:h val="{h}"/></c:manualLayout></c:layout>"#
+        );
+    } else {
+        xml.raw("\n<c:layout/>");
+    }
 }
 
-/// Generate the chart part footer
-fn chart_part_footer() -> &'static str {
-    r#"</c:plotArea>
-<c:legend>
-<c:legendPos val="r"/>
-<c:layout/>
-<c:overlay val="0"/>
-</c:legend>
+/// Write the chart part footer
+///
+/// Closes the plot area (after its data table) and emits the legend. When
+/// the chart has a `ChartStyle`, plot area fill and legend/chart-wide text
+/// properties are emitted as well.
+fn write_chart_part_footer(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(&chart.data_table_xml());
+    if let Some(style) = &chart.style {
+        xml.raw(&style.plot_area_xml());
+    }
+    xml.raw("</c:plotArea>");
+
+    if chart.shows_legend() {
+        xml.raw("\n<c:legend>");
+        chart.write_legend_layout(xml);
+        xml.raw("\n<c:overlay val=\"0\"/>");
+        if let Some(style) = &chart.style {
+            xml.raw(&style.label_text_xml());
+        }
+        xml.raw("\n</c:legend>");
+    }
+
+    xml.raw(
+        r#"
 <c:plotVisOnly val="1"/>
 <c:dispBlanksAs val="gap"/>
 <c:showDLblsOverMax val="0"/>
-</c:chart>
-</c:chartSpace>"#
+</c:chart>"#
+    );
+
+    if let Some(style) = &chart.style {
+        xml.raw(&style.label_text_xml());
+    }
+
+    if chart.data_source.is_some() {
+        xml.raw("\n<c:externalData r:id=\"rId1\"><c:autoUpdate val=\"0\"/></c:externalData>");
+    }
+
+    xml.raw("\n</c:chartSpace>");
+}
+
+/// Write cached points (`c:pt`), escaping each value
+fn write_points<T: Display>(xml: &mut XmlWriter, values: impl IntoIterator<Item = T>) {
+    for (idx, value) in values.into_iter().enumerate() {
+        xml.raw("\n");
+        xml.open("c:pt").attr("idx", idx).close();
+        xml.raw("\n");
+        xml.leaf("c:v", value);
+        xml.raw("\n");
+        xml.end_element("c:pt");
+    }
+}
+
+/// `Sheet1` reference to `rows` cells below the header row of `column`
+fn sheet1_column(column: char, rows: usize) -> String {
+    format!("Sheet1!${column}$2:${column}${}", 1 + rows)
+}
+
+/// Formula for column `col` of the chart's data range (0 = categories,
+/// 1.. = series), or `fallback` when no workbook is embedded
+fn data_ref(chart: &Chart, col: usize, rows: usize, fallback: impl FnOnce() -> String) -> String {
+    chart.data_source.as_ref().map_or_else(fallback, |source| source.column_ref(col, rows))
+}
+
+/// Formula for the header cell of column `col` of the chart's data range,
+/// or the fixed `Sheet1!$B$1` when no workbook is embedded
+fn header_ref(chart: &Chart, col: usize) -> String {
+    chart.data_source.as_ref().map_or_else(|| "Sheet1!$B$1".to_string(), |source| source.header_ref(col))
 }
 
-/// Generate series data XML
-fn generate_series_data(_chart: &Chart, idx: usize, series_name: &str, values: &[f64]) -> String {
-    let mut xml = format!(
+/// Write the category cache (`c:strRef`) of a series' `c:cat`
+fn write_category_cache(xml: &mut XmlWriter, chart: &Chart) {
+    let count = chart.category_count();
+    write!(
+        xml,
+        r#"
+<c:strRef>
+<c:f>{}</c:f>
+<c:strCache>
+<c:ptCount val="{}"/>"#,
+        Escaped(&data_ref(chart, 0, count, || sheet1_column('A', count))),
+        count
+    );
+
+    write_points(xml, chart.display_categories());
+
+    xml.raw(
+        r#"
+</c:strCache>
+</c:strRef>"#
+    );
+}
+
+/// Write a series name as a one-cell string reference (`c:tx`)
+fn write_series_tx(xml: &mut XmlWriter, formula: &str, name: &str) {
+    write!(
+        xml,
         r#"
-<c:ser>
-<c:idx val="{}"/>
-<c:order val="{}"/>
-<c:title>
 <c:tx>
-<c:rich>
-<a:bodyPr/>
-<a:lstStyle/>
-<a:p>
-<a:r>
-<a:rPr lang="en-US" sz="1000"/>
-<a:t>{}</a:t>
-</a:r>
-</a:p>
-</c:rich>
-</c:tx>
-</c:title>
-<c:dLbls>
-<c:showVal val="0"/>
-</c:dLbls>
-<c:val>
+<c:strRef>
+<c:f>{}</c:f>
+<c:strCache>
+<c:ptCount val="1"/>
+<c:pt idx="0"><c:v>{}</c:v></c:pt>
+</c:strCache>
+</c:strRef>
+</c:tx>"#,
+        Escaped(formula),
+        Escaped(name)
+    );
+}
+
+/// Write a numeric data reference (`c:xVal`, `c:yVal`, `c:bubbleSize`, ...)
+fn write_num_ref<T: Display>(xml: &mut XmlWriter, element: &str, formula: &str, values: impl IntoIterator<Item = T>) {
+    write!(
+        xml,
+        r#"
+<{element}>
 <c:numRef>
-<c:f>Sheet1!$B${}:$B${}</c:f>
+<c:f>{}</c:f>
 <c:numCache>
 <c:formatCode>General</c:formatCode>"#,
-        idx, idx, escape_xml(series_name), 2 + idx, 2 + idx + values.len()
+        Escaped(formula)
     );
 
-    for value in values {
-        xml.push_str(&format!(
-            r#"
-<c:pt idx="0">
-<c:v>{}</c:v>
-</c:pt>"#,
-            value
-        ));
-    }
+    write_points(xml, values);
 
-    xml.push_str(
+    write!(
+        xml,
         r#"
 </c:numCache>
 </c:numRef>
-</c:val>
-</c:ser>"#
+</{element}>"#
     );
+}
+
+/// Write series data XML
+///
+/// Follows the schema order shared by bar, line, area, radar and stock
+/// series: name, color, labels, categories, values.
+fn write_series_data(xml: &mut XmlWriter, chart: &Chart, idx: usize, series: &super::ChartSeries) {
+    write!(xml, "\n<c:ser>\n<c:idx val=\"{idx}\"/>\n<c:order val=\"{idx}\"/>");
+    write_series_tx(xml, &header_ref(chart, 1 + idx), &series.name);
+    write_series_color(xml, series);
+    xml.raw(
+        r#"
+<c:dLbls>
+<c:showVal val="0"/>
+</c:dLbls>"#,
+    );
+    write_series_categories(xml, chart);
+    let values_ref = data_ref(chart, 1 + idx, series.values.len(), || {
+        format!("Sheet1!$B${}:$B${}", 2 + idx, 2 + idx + series.values.len())
+    });
+    write_num_ref(xml, "c:val", &values_ref, &series.values);
+    xml.raw("\n</c:ser>");
+}
+
+/// Write a series' categories (`c:cat`)
+fn write_series_categories(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw("\n<c:cat>");
+    write_category_cache(xml, chart);
+    xml.raw("\n</c:cat>");
+}
+
+/// Write a series' explicit color (`c:spPr`) as both its fill and its line
+fn write_series_color(xml: &mut XmlWriter, series: &super::ChartSeries) {
+    if let Some(color) = &series.color {
+        write!(
+            xml,
+            "\n<c:spPr><a:solidFill><a:srgbClr val=\"{0}\"/></a:solidFill><a:ln><a:solidFill><a:srgbClr val=\"{0}\"/></a:solidFill></a:ln></c:spPr>",
+            EscapedAttr(color)
+        );
+    }
+}
+
+/// Write all series of the chart, numbered from `first_idx`
+fn write_all_series<'a>(xml: &mut XmlWriter, chart: &Chart, first_idx: usize, series: impl Iterator<Item = &'a super::ChartSeries>) {
+    for (idx, series) in series.enumerate() {
+        write_series_data(xml, chart, first_idx + idx, series);
+    }
+}
 
-    xml
+/// Write the IDs of the two axes a chart type element plots on
+///
+/// They close the chart type element; the axes themselves follow it in the
+/// plot area (see [`write_category_axis`] and [`write_value_axis`]).
+fn write_axis_ids(xml: &mut XmlWriter) {
+    write!(xml, "\n<c:axId val=\"{CATEGORY_AXIS_ID}\"/>\n<c:axId val=\"{VALUE_AXIS_ID}\"/>\n");
 }
 
-/// Generate category axis XML
-fn generate_category_axis(chart: &Chart, ax_pos: &str) -> String {
-    let mut xml = format!(
+/// Write category axis XML
+///
+/// The axis holds no data: the category labels are in each series' `c:cat`.
+fn write_category_axis(xml: &mut XmlWriter, chart: &Chart, ax_pos: &str) {
+    let style = chart.effective_style();
+    write!(
+        xml,
         r#"
 <c:catAx>
-<c:axId val="1"/>
+<c:axId val="{CATEGORY_AXIS_ID}"/>
 <c:scaling>
 <c:orientation val="minMax"/>
 </c:scaling>
 <c:delete val="0"/>
-<c:axPos val="{}"/>
-<c:majorGridlines/>
+<c:axPos val="{}"/>{}
 <c:numFmt formatCode="General" sourceLinked="1"/>
-<c:tickLblPos val="low"/>
-<c:crossAx val="2"/>
+<c:tickLblPos val="low"/>{}
+<c:crossAx val="{VALUE_AXIS_ID}"/>
 <c:crosses val="autoZero"/>
-<c:strRef>
-<c:f>Sheet1!$A$2:$A${}</c:f>
-<c:strCache>
-<c:ptCount val="{}"/>"#,
-        ax_pos, 1 + chart.category_count(), chart.category_count()
-    );
-
-    for (idx, cat) in chart.categories.iter().enumerate() {
-        xml.push_str(&format!(
-            r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-            idx, escape_xml(cat)
-        ));
-    }
-
-    xml.push_str(
-        r#"
-</c:strCache>
-</c:strRef>
-</c:catAx>"#
+</c:catAx>"#,
+        ax_pos,
+        style.major_gridlines_xml(),
+        category_axis_text_xml(chart),
     );
-
-    xml
 }
 
-/// Generate value axis XML
-fn generate_value_axis(ax_pos: &str) -> String {
-    format!(
+/// Write value axis XML with ID `ax_id`, crossing the other axis
+///
+/// The bounds are fixed when the chart has reference marks to line up with.
+fn write_value_axis(xml: &mut XmlWriter, chart: &Chart, ax_id: u32, ax_pos: &str) {
+    let cross_ax = if ax_id == VALUE_AXIS_ID { CATEGORY_AXIS_ID } else { VALUE_AXIS_ID };
+    let bounds = chart.value_axis_bounds()
+        .map(|(min, max)| format!("\n<c:max val=\"{max}\"/>\n<c:min val=\"{min}\"/>"))
+        .unwrap_or_default();
+    write!(
+        xml,
         r#"
 <c:valAx>
-<c:axId val="2"/>
+<c:axId val="{ax_id}"/>
 <c:scaling>
-<c:orientation val="minMax"/>
+<c:orientation val="minMax"/>{}
 </c:scaling>
 <c:delete val="0"/>
-<c:axPos val="{}"/>
-<c:majorGridlines/>
+<c:axPos val="{}"/>{}
 <c:numFmt formatCode="General" sourceLinked="1"/>
-<c:tickLblPos val="low"/>
-<c:crossAx val="1"/>
-<c:crosses val="autoZero"/>
+<c:tickLblPos val="low"/>{}
+<c:crossAx val="{cross_ax}"/>
+<c:crosses val="autoZero"/>{}
 </c:valAx>"#,
-        ax_pos
-    )
+        bounds,
+        ax_pos,
+        chart.effective_style().major_gridlines_xml(),
+        axis_text_xml(chart),
+        chart.display_units_xml(ax_pos)
+    );
 }
 
-/// Generate bar chart XML
-fn generate_bar_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:barChart>
-<c:barDir val="bar"/>
-<c:grouping val="clustered"/>"#);
+/// Axis label text properties, emitted only for explicitly styled charts
+fn axis_text_xml(chart: &Chart) -> String {
+    chart.style.as_ref().map(|s| s.label_text_xml()).unwrap_or_default()
+}
 
-    for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
+/// Category axis label text properties, including label rotation
+fn category_axis_text_xml(chart: &Chart) -> String {
+    if chart.style.is_none() && chart.category_label_rotation.is_none() {
+        return String::new();
     }
+    let style = chart.effective_style();
+    style.rotated_text_properties_xml(style.label_size, chart.category_label_rotation)
+}
 
-    xml.push_str(&generate_category_axis(chart, "l"));
-    xml.push_str(&generate_value_axis("b"));
-    xml.push_str("</c:barChart>");
-    xml.push_str(chart_part_footer());
+/// Write bar chart XML
+fn write_bar_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(r#"<c:barChart>
+<c:barDir val="bar"/>
+<c:grouping val="clustered"/>"#);
 
-    xml
-}
+    write_all_series(xml, chart, 0, chart.series.iter());
 
-/// Generate line chart XML
-fn generate_line_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:lineChart>
-<c:grouping val="lineMarkers"/>"#);
+    write_axis_ids(xml);
+    xml.raw("</c:barChart>");
+    write_category_axis(xml, chart, "l");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "b");
+}
 
-    for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
-    }
+/// Write line chart XML
+fn write_line_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    let grouping = chart.chart_type.grouping().unwrap_or("standard");
+    write!(xml, r#"<c:lineChart>
+<c:grouping val="{}"/>"#, grouping);
 
-    xml.push_str(&generate_category_axis(chart, "b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:lineChart>");
-    xml.push_str(chart_part_footer());
+    write_all_series(xml, chart, 0, chart.series.iter());
 
-    xml
+    if chart.chart_type.has_markers() {
+        xml.raw("\n<c:marker val=\"1\"/>");
+    }
+    write_axis_ids(xml);
+    xml.raw("</c:lineChart>");
+    write_category_axis(xml, chart, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
 }
 
-/// Generate pie chart XML
-fn generate_pie_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:pieChart>
-<c:varyColors val="1"/>"#);
-
-    // Pie chart uses first series only
-    if let Some(series) = chart.series.first() {
-        xml.push_str(&format!(
-            r#"
-<c:ser>
-<c:idx val="0"/>
-<c:order val="0"/>
-<c:title>
-<c:tx>
-<c:rich>
-<a:bodyPr/>
-<a:lstStyle/>
-<a:p>
-<a:r>
-<a:rPr lang="en-US" sz="1000"/>
-<a:t>{}</a:t>
-</a:r>
-</a:p>
-</c:rich>
-</c:tx>
-</c:title>
+/// Write the single series of a pie or doughnut chart, with category names and percentages as labels
+fn write_pie_series(xml: &mut XmlWriter, chart: &Chart, series: &super::ChartSeries) {
+    xml.raw("\n<c:ser>\n<c:idx val=\"0\"/>\n<c:order val=\"0\"/>");
+    write_series_tx(xml, &header_ref(chart, 1), &series.name);
+    xml.raw(
+        r#"
 <c:dLbls>
 <c:showCatName val="1"/>
 <c:showPercent val="1"/>
-</c:dLbls>
-<c:val>
-<c:numRef>
-<c:f>Sheet1!$B$2:$B${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            escape_xml(&series.name),
-            1 + series.values.len()
-        ));
-
-        for (idx, value) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                idx, value
-            ));
-        }
+</c:dLbls>"#,
+    );
+    write_series_categories(xml, chart);
+    let len = series.values.len();
+    write_num_ref(xml, "c:val", &data_ref(chart, 1, len, || sheet1_column('B', len)), &series.values);
+    xml.raw("\n</c:ser>");
+}
 
-        xml.push_str(&format!(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:val>
-<c:cat>
-<c:strRef>
-<c:f>Sheet1!$A$2:$A${}</c:f>
-<c:strCache>
-<c:ptCount val="{}"/>"#,
-            1 + chart.category_count(),
-            chart.category_count()
-        ));
-
-        for (idx, cat) in chart.categories.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                idx, escape_xml(cat)
-            ));
-        }
+/// Write pie chart XML
+fn write_pie_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(r#"<c:pieChart>
+<c:varyColors val="1"/>"#);
 
-        xml.push_str(
-            r#"
-</c:strCache>
-</c:strRef>
-</c:cat>
-</c:ser>"#
-        );
+    // Pie chart uses first series only
+    if let Some(series) = chart.series.first() {
+        write_pie_series(xml, chart, series);
     }
 
-    xml.push_str("</c:pieChart>");
-    xml.push_str(chart_part_footer());
-
-    xml
+    xml.raw("</c:pieChart>");
 }
 
-/// Generate doughnut chart XML
-fn generate_doughnut_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:doughnutChart>
-<c:varyColors val="1"/>
-<c:holeSize val="50"/>"#);
+/// Write doughnut chart XML
+fn write_doughnut_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(r#"<c:doughnutChart>
+<c:varyColors val="1"/>"#);
 
     // Doughnut chart uses first series only (like pie)
     if let Some(series) = chart.series.first() {
-        xml.push_str(&format!(
-            r#"
-<c:ser>
-<c:idx val="0"/>
-<c:order val="0"/>
-<c:tx>
-<c:strRef>
-<c:f>Sheet1!$B$1</c:f>
-<c:strCache>
-<c:ptCount val="1"/>
-<c:pt idx="0"><c:v>{}</c:v></c:pt>
-</c:strCache>
-</c:strRef>
-</c:tx>
-<c:dLbls>
-<c:showCatName val="1"/>
-<c:showPercent val="1"/>
-</c:dLbls>
-<c:val>
-<c:numRef>
-<c:f>Sheet1!$B$2:$B${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            escape_xml(&series.name),
-            1 + series.values.len()
-        ));
-
-        for (idx, value) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                idx, value
-            ));
-        }
-
-        xml.push_str(&format!(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:val>
-<c:cat>
-<c:strRef>
-<c:f>Sheet1!$A$2:$A${}</c:f>
-<c:strCache>
-<c:ptCount val="{}"/>"#,
-            1 + chart.category_count(),
-            chart.category_count()
-        ));
-
-        for (idx, cat) in chart.categories.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                idx, escape_xml(cat)
-            ));
-        }
-
-        xml.push_str(
-            r#"
-</c:strCache>
-</c:strRef>
-</c:cat>
-</c:ser>"#
-        );
+        write_pie_series(xml, chart, series);
     }
 
-    xml.push_str("</c:doughnutChart>");
-    xml.push_str(chart_part_footer());
-
-    xml
+    xml.raw("\n<c:holeSize val=\"50\"/>\n</c:doughnutChart>");
 }
 
-/// Generate area chart XML
-fn generate_area_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
+/// Write area chart XML
+fn write_area_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
     let grouping = chart.chart_type.grouping().unwrap_or("standard");
-    xml.push_str(&format!(r#"<c:areaChart>
-<c:grouping val="{}"/>"#, grouping));
+    write!(xml, r#"<c:areaChart>
+<c:grouping val="{}"/>"#, grouping);
 
-    for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
-    }
+    write_all_series(xml, chart, 0, chart.series.iter());
+
+    write_axis_ids(xml);
+    xml.raw("</c:areaChart>");
+    write_category_axis(xml, chart, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
+}
 
-    xml.push_str(&generate_category_axis(chart, "b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:areaChart>");
-    xml.push_str(chart_part_footer());
+/// Write the `c:ser` of an XY (scatter or bubble) chart; X values are the point indices
+fn write_xy_series(xml: &mut XmlWriter, chart: &Chart, idx: usize, series: &super::ChartSeries, bubble_sizes: bool) {
+    let len = series.values.len();
+    write!(xml, "\n<c:ser>\n<c:idx val=\"{idx}\"/>\n<c:order val=\"{idx}\"/>");
+    write_series_tx(xml, &header_ref(chart, 1 + idx), &series.name);
+    write_series_color(xml, series);
+    write_num_ref(xml, "c:xVal", &data_ref(chart, 0, len, || sheet1_column('A', len)), 1..len + 1);
+    let values_ref = data_ref(chart, 1 + idx, len, || sheet1_column('B', len));
+    write_num_ref(xml, "c:yVal", &values_ref, &series.values);
+    if bubble_sizes {
+        // Bubble sizes (use values as sizes)
+        let sizes_ref = data_ref(chart, 1 + idx, len, || sheet1_column('C', len));
+        write_num_ref(xml, "c:bubbleSize", &sizes_ref, series.values.iter().map(|v| v.abs()));
+    }
+    xml.raw("\n</c:ser>");
+}
 
-    xml
+/// Write the two value axes of an XY chart: X (bottom) and Y (left)
+fn write_xy_axes(xml: &mut XmlWriter, chart: &Chart) {
+    write_value_axis(xml, chart, CATEGORY_AXIS_ID, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
 }
 
-/// Generate scatter chart XML
-fn generate_scatter_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
+/// Write scatter chart XML
+fn write_scatter_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
     let scatter_style = chart.chart_type.scatter_style().unwrap_or("lineMarker");
-    xml.push_str(&format!(r#"<c:scatterChart>
-<c:scatterStyle val="{}"/>"#, scatter_style));
+    write!(xml, r#"<c:scatterChart>
+<c:scatterStyle val="{}"/>"#, scatter_style);
 
     for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&format!(
-            r#"
-<c:ser>
-<c:idx val="{}"/>
-<c:order val="{}"/>
-<c:tx>
-<c:strRef>
-<c:f>Sheet1!$B$1</c:f>
-<c:strCache>
-<c:ptCount val="1"/>
-<c:pt idx="0"><c:v>{}</c:v></c:pt>
-</c:strCache>
-</c:strRef>
-</c:tx>
-<c:xVal>
-<c:numRef>
-<c:f>Sheet1!$A$2:$A${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            idx, idx, escape_xml(&series.name), 1 + series.values.len()
-        ));
-
-        // X values (use index as X)
-        for (i, _) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                i, i + 1
-            ));
-        }
-
-        xml.push_str(&format!(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:xVal>
-<c:yVal>
-<c:numRef>
-<c:f>Sheet1!$B$2:$B${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            1 + series.values.len()
-        ));
-
-        for (i, value) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                i, value
-            ));
-        }
-
-        xml.push_str(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:yVal>
-</c:ser>"#
-        );
+        write_xy_series(xml, chart, idx, series, false);
     }
 
-    xml.push_str(&generate_value_axis("b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:scatterChart>");
-    xml.push_str(chart_part_footer());
-
-    xml
+    write_axis_ids(xml);
+    xml.raw("</c:scatterChart>");
+    write_xy_axes(xml, chart);
 }
 
-/// Generate bubble chart XML
-fn generate_bubble_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:bubbleChart>
-<c:varyColors val="0"/>
-<c:bubbleScale val="100"/>"#);
+/// Write bubble chart XML
+fn write_bubble_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(r#"<c:bubbleChart>
+<c:varyColors val="0"/>"#);
 
     for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&format!(
-            r#"
-<c:ser>
-<c:idx val="{}"/>
-<c:order val="{}"/>
-<c:tx>
-<c:strRef>
-<c:f>Sheet1!$B$1</c:f>
-<c:strCache>
-<c:ptCount val="1"/>
-<c:pt idx="0"><c:v>{}</c:v></c:pt>
-</c:strCache>
-</c:strRef>
-</c:tx>
-<c:xVal>
-<c:numRef>
-<c:f>Sheet1!$A$2:$A${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            idx, idx, escape_xml(&series.name), 1 + series.values.len()
-        ));
-
-        for (i, _) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                i, i + 1
-            ));
-        }
-
-        xml.push_str(&format!(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:xVal>
-<c:yVal>
-<c:numRef>
-<c:f>Sheet1!$B$2:$B${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            1 + series.values.len()
-        ));
-
-        for (i, value) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                i, value
-            ));
-        }
-
-        xml.push_str(&format!(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:yVal>
-<c:bubbleSize>
-<c:numRef>
-<c:f>Sheet1!$C$2:$C${}</c:f>
-<c:numCache>
-<c:formatCode>General</c:formatCode>"#,
-            1 + series.values.len()
-        ));
-
-        // Bubble sizes (use values as sizes)
-        for (i, value) in series.values.iter().enumerate() {
-            xml.push_str(&format!(
-                r#"
-<c:pt idx="{}">
-<c:v>{}</c:v>
-</c:pt>"#,
-                i, value.abs()
-            ));
-        }
-
-        xml.push_str(
-            r#"
-</c:numCache>
-</c:numRef>
-</c:bubbleSize>
-</c:ser>"#
-        );
+        write_xy_series(xml, chart, idx, series, true);
     }
 
-    xml.push_str(&generate_value_axis("b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:bubbleChart>");
-    xml.push_str(chart_part_footer());
-
-    xml
+    xml.raw("\n<c:bubbleScale val=\"100\"/>");
+    write_axis_ids(xml);
+    xml.raw("</c:bubbleChart>");
+    write_xy_axes(xml, chart);
 }
 
-/// Generate radar chart XML
-fn generate_radar_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
+/// Write radar chart XML
+fn write_radar_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
     let radar_style = chart.chart_type.radar_style().unwrap_or("marker");
-    xml.push_str(&format!(r#"<c:radarChart>
-<c:radarStyle val="{}"/>"#, radar_style));
-
-    for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
-    }
+    write!(xml, r#"<c:radarChart>
+<c:radarStyle val="{}"/>"#, radar_style);
 
-    xml.push_str(&generate_category_axis(chart, "b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:radarChart>");
-    xml.push_str(chart_part_footer());
+    write_all_series(xml, chart, 0, chart.series.iter());
 
-    xml
+    write_axis_ids(xml);
+    xml.raw("</c:radarChart>");
+    write_category_axis(xml, chart, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
 }
 
-/// Generate stock chart XML
-fn generate_stock_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
-    xml.push_str(r#"<c:stockChart>"#);
+/// Write stock chart XML
+fn write_stock_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
+    xml.raw(r#"<c:stockChart>"#);
 
     // Stock charts need High, Low, Close (and optionally Open) series
-    for (idx, series) in chart.series.iter().enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
-    }
-
-    xml.push_str(&generate_category_axis(chart, "b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:stockChart>");
-    xml.push_str(chart_part_footer());
+    write_all_series(xml, chart, 0, chart.series.iter());
 
-    xml
+    write_axis_ids(xml);
+    xml.raw("</c:stockChart>");
+    write_category_axis(xml, chart, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
 }
 
-/// Generate combo chart XML (bar + line)
-fn generate_combo_chart_xml(chart: &Chart) -> String {
-    let mut xml = chart_part_header(chart);
-    
+/// Write combo chart XML (bar + line)
+///
+/// Both chart type elements plot on the same pair of axes.
+fn write_combo_chart_xml(xml: &mut XmlWriter, chart: &Chart) {
     // First half of series as bars
-    xml.push_str(r#"<c:barChart>
+    xml.raw(r#"<c:barChart>
 <c:barDir val="col"/>
 <c:grouping val="clustered"/>"#);
 
     let mid = chart.series.len() / 2;
-    for (idx, series) in chart.series.iter().take(mid.max(1)).enumerate() {
-        xml.push_str(&generate_series_data(chart, idx, &series.name, &series.values));
-    }
+    write_all_series(xml, chart, 0, chart.series.iter().take(mid.max(1)));
 
-    xml.push_str(&generate_category_axis(chart, "b"));
-    xml.push_str(&generate_value_axis("l"));
-    xml.push_str("</c:barChart>");
+    write_axis_ids(xml);
+    xml.raw("</c:barChart>");
 
     // Second half as lines
     if chart.series.len() > 1 {
-        xml.push_str(r#"<c:lineChart>
+        xml.raw(r#"<c:lineChart>
 <c:grouping val="standard"/>"#);
 
-        for (idx, series) in chart.series.iter().skip(mid.max(1)).enumerate() {
-            xml.push_str(&generate_series_data(chart, mid + idx, &series.name, &series.values));
-        }
+        write_all_series(xml, chart, mid, chart.series.iter().skip(mid.max(1)));
 
-        xml.push_str("</c:lineChart>");
+        write_axis_ids(xml);
+        xml.raw("</c:lineChart>");
     }
 
-    xml.push_str(chart_part_footer());
-
-    xml
+    write_category_axis(xml, chart, "b");
+    write_value_axis(xml, chart, VALUE_AXIS_ID, "l");
 }
 
 #[cfg(test)]
@@ -733,11 +592,72 @@ mod tests {
             0, 0, 5000000, 3750000,
         ).add_series(ChartSeries::new("2024", vec![100.0, 150.0]));
 
-        let xml = generate_bar_chart_xml(&chart);
+        let xml = generate_chart_part_xml(&chart);
         assert!(xml.contains("barChart"));
         assert!(xml.contains("Sales"));
     }
 
+    #[test]
+    fn test_axes_follow_chart_element_and_categories_live_in_series() {
+        let chart = |chart_type| {
+            let chart = Chart::new("Sales", chart_type, vec!["Q1".to_string(), "Q2".to_string()], 0, 0, 5000000, 3750000)
+                .add_series(ChartSeries::new("2024", vec![100.0, 150.0]))
+                .add_series(ChartSeries::new("2025", vec![120.0, 160.0]));
+            generate_chart_part_xml(&chart)
+        };
+
+        let xml = chart(ChartType::Bar);
+        let cat_ax = &xml[xml.find("<c:catAx>").unwrap()..xml.find("</c:catAx>").unwrap()];
+        assert!(!cat_ax.contains("c:strRef"));
+        assert!(xml.find("</c:barChart>").unwrap() < xml.find("<c:catAx>").unwrap());
+        assert!(xml.contains("<c:axId val=\"1\"/>\n<c:axId val=\"2\"/>\n</c:barChart>"));
+        assert_eq!(xml.matches("<c:cat>").count(), 2);
+        assert!(xml.contains("<c:order val=\"0\"/>\n<c:tx>\n<c:strRef>"));
+
+        // Both halves of a combo chart share one pair of axes
+        let xml = chart(ChartType::Combo);
+        assert_eq!((xml.matches("<c:catAx>").count(), xml.matches("<c:valAx>").count()), (1, 1));
+        assert!(xml.find("</c:lineChart>").unwrap() < xml.find("<c:catAx>").unwrap());
+
+        // XY charts have two value axes with distinct IDs
+        let xml = chart(ChartType::Scatter);
+        assert!(xml.contains("<c:valAx>\n<c:axId val=\"1\"/>") && xml.contains("<c:valAx>\n<c:axId val=\"2\"/>"));
+
+        let xml = chart(ChartType::Doughnut);
+        assert!(xml.find("<c:cat>").unwrap() < xml.find("<c:val>").unwrap());
+        assert!(xml.contains("</c:ser>\n<c:holeSize val=\"50\"/>"));
+    }
+
+    #[test]
+    fn test_series_points_are_indexed() {
+        let chart = Chart::new(
+            "Sales",
+            ChartType::Line,
+            vec!["Q1".to_string(), "Q2".to_string()],
+            0, 0, 5000000, 3750000,
+        ).add_series(ChartSeries::new("A & B", vec![100.0, 150.0]));
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains("<c:pt idx=\"1\">\n<c:v>150</c:v>"));
+        assert!(xml.contains("<c:v>A &amp; B</c:v>"));
+    }
+
+    #[test]
+    fn test_series_color() {
+        let chart = Chart::new(
+            "Sales",
+            ChartType::Bar,
+            vec!["Q1".to_string(), "Q2".to_string()],
+            0, 0, 5000000, 3750000,
+        )
+        .add_series(ChartSeries::new("2024", vec![100.0, 150.0]).with_color("#1F4E79"))
+        .add_series(ChartSeries::new("2025", vec![120.0, 160.0]));
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains("<c:spPr><a:solidFill><a:srgbClr val=\"1F4E79\"/></a:solidFill>"));
+        assert_eq!(xml.matches("<c:spPr><a:solidFill>").count(), 1);
+    }
+
     #[test]
     fn test_generate_line_chart_xml() {
         let chart = Chart::new(
@@ -747,7 +667,7 @@ mod tests {
             0, 0, 5000000, 3750000,
         ).add_series(ChartSeries::new("Revenue", vec![1000.0, 1200.0]));
 
-        let xml = generate_line_chart_xml(&chart);
+        let xml = generate_chart_part_xml(&chart);
         assert!(xml.contains("lineChart"));
     }
 
@@ -760,7 +680,93 @@ mod tests {
             0, 0, 5000000, 3750000,
         ).add_series(ChartSeries::new("Data", vec![30.0, 70.0]));
 
-        let xml = generate_pie_chart_xml(&chart);
+        let xml = generate_chart_part_xml(&chart);
         assert!(xml.contains("pieChart"));
     }
+
+    #[test]
+    fn test_chart_style_applied() {
+        use crate::generator::charts::ChartStyle;
+
+        let chart = Chart::new(
+            "Styled",
+            ChartType::Bar,
+            vec!["Q1".to_string(), "Q2".to_string()],
+            0, 0, 5000000, 3750000,
+        )
+        .add_series(ChartSeries::new("2024", vec![100.0, 150.0]))
+        .with_style(
+            ChartStyle::new()
+                .font("Georgia")
+                .title_size(24)
+                .label_size(12)
+                .gridline_color("D9D9D9")
+                .plot_area_fill("FAFAFA"),
+        );
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains(r#"sz="2400""#));
+        assert!(xml.contains(r#"<a:latin typeface="Georgia"/>"#));
+        assert!(xml.contains(r#"<a:defRPr sz="1200">"#));
+        assert!(xml.contains(r#"<a:srgbClr val="D9D9D9"/>"#));
+        assert!(xml.contains(r#"<a:srgbClr val="FAFAFA"/>"#));
+        assert!(xml.contains("</c:chart>\n<c:txPr>"));
+        assert!(xml.ends_with("</c:chartSpace>"));
+    }
+
+    #[test]
+    fn test_category_label_rotation() {
+        let mut chart = Chart::new(
+            "Rotated",
+            ChartType::Bar,
+            vec!["A very long category name".to_string()],
+            0, 0, 5000000, 3750000,
+        ).add_series(ChartSeries::new("S", vec![1.0]));
+        chart.category_label_rotation = Some(-45);
+        chart.category_label_max_len = Some(12);
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains(r#"<a:bodyPr rot="-2700000" vert="horz"/>"#));
+        assert!(xml.contains("<c:v>A very long…</c:v>"));
+
+        // Out-of-range angles fail validation and never overflow the XML value
+        chart.category_label_rotation = Some(40_000);
+        assert!(chart.validate().is_err());
+        assert!(generate_chart_part_xml(&chart).contains(r#"<a:bodyPr rot="5400000" vert="horz"/>"#));
+        chart.category_label_rotation = Some(-90);
+        assert!(chart.validate().is_ok());
+    }
+
+    #[test]
+    fn test_unstyled_chart_has_no_txpr() {
+        let chart = Chart::new(
+            "Plain",
+            ChartType::Line,
+            vec!["A".to_string()],
+            0, 0, 5000000, 3750000,
+        ).add_series(ChartSeries::new("S", vec![1.0]));
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(!xml.contains("<c:txPr>"));
+        assert!(xml.contains(r#"<a:latin typeface="Calibri"/>"#));
+    }
+
+    #[test]
+    fn test_reference_marks_fix_plot_area_and_axis_bounds() {
+        use crate::generator::charts::ReferenceLine;
+
+        let chart = Chart::new(
+            "Target",
+            ChartType::Line,
+            vec!["A".to_string(), "B".to_string()],
+            0, 0, 5000000, 3750000,
+        )
+        .add_series(ChartSeries::new("S", vec![40.0, 90.0]))
+        .with_reference_line(ReferenceLine::new(100.0));
+
+        let xml = generate_chart_part_xml(&chart);
+        assert!(xml.contains(r#"<c:layoutTarget val="inner"/>"#));
+        assert!(xml.contains(r#"<c:x val="0.12"/>"#));
+        assert!(xml.contains("<c:orientation val=\"minMax\"/>\n<c:max val=\"100\"/>\n<c:min val=\"0\"/>"));
+    }
 }
@@ -0,0 +1,190 @@
+//! Compatibility profiles for other presentation apps
+//!
+//! Google Slides and Keynote open `.pptx` files but import some constructs
+//! poorly: transitions they do not have are dropped, action buttons and a
+//! few preset geometries come through as plain boxes or not at all, and
+//! stock charts lose their high-low lines. A [`CompatProfile`] set with
+//! [`Presentation::compat`](crate::api::Presentation::compat) replaces them
+//! with the nearest equivalent the app renders:
+//!
+//! | Construct | Google Slides | Keynote |
+//! |-----------|---------------|---------|
+//! | Morph transition | fade | fade |
+//! | Wipe, split, reveal, zoom transitions | fade | split becomes wipe |
+//! | Cover transition | push | kept |
+//! | Action buttons | rectangle | rectangle |
+//! | Cone, cylinder | can | can |
+//! | Seals | star with as many points | star with as many points |
+//! | Stock charts | line with markers | line with markers |
+//!
+//! Each replacement is reported as a [`WarningKind::Substituted`] by
+//! [`build_with_warnings`](crate::api::Presentation::build_with_warnings).
+//! The generator writes no `cx:` (chartex) charts, so there are none to
+//! substitute.
+
+use super::charts::ChartType;
+use super::shapes::ShapeType;
+use super::slide_content::{SlideContent, TransitionType};
+use super::warnings::{Warning, WarningKind, Warnings};
+
+/// App the deck should import cleanly into
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CompatProfile {
+    /// Everything this crate writes; no substitutions
+    #[default]
+    PowerPoint,
+    GoogleSlides,
+    Keynote,
+}
+
+impl CompatProfile {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompatProfile::PowerPoint => "PowerPoint",
+            CompatProfile::GoogleSlides => "Google Slides",
+            CompatProfile::Keynote => "Keynote",
+        }
+    }
+
+    /// Transition to write instead of `transition`
+    pub fn transition(&self, transition: TransitionType) -> TransitionType {
+        match (self, transition) {
+            (CompatProfile::PowerPoint, _) => transition,
+            (_, TransitionType::Morph(_)) => TransitionType::Fade,
+            (
+                CompatProfile::GoogleSlides,
+                TransitionType::Wipe | TransitionType::Split | TransitionType::Reveal | TransitionType::Zoom,
+            ) => TransitionType::Fade,
+            (CompatProfile::GoogleSlides, TransitionType::Cover) => TransitionType::Push,
+            (CompatProfile::Keynote, TransitionType::Split) => TransitionType::Wipe,
+            _ => transition,
+        }
+    }
+
+    /// Preset geometry to write instead of `shape`
+    pub fn shape_type(&self, shape: ShapeType) -> ShapeType {
+        if *self == CompatProfile::PowerPoint {
+            return shape;
+        }
+        match shape {
+            ShapeType::ActionButtonBlank
+            | ShapeType::ActionButtonHome
+            | ShapeType::ActionButtonHelp
+            | ShapeType::ActionButtonInformation
+            | ShapeType::ActionButtonForwardNext
+            | ShapeType::ActionButtonBackPrevious
+            | ShapeType::ActionButtonBeginning
+            | ShapeType::ActionButtonEnd
+            | ShapeType::ActionButtonReturn
+            | ShapeType::ActionButtonDocument
+            | ShapeType::ActionButtonSound
+            | ShapeType::ActionButtonMovie => ShapeType::Rectangle,
+            ShapeType::Cone | ShapeType::Cylinder => ShapeType::Can,
+            ShapeType::Seal4 => ShapeType::Star4,
+            ShapeType::Seal8 => ShapeType::Star8,
+            ShapeType::Seal | ShapeType::Seal16 => ShapeType::Star16,
+            ShapeType::Seal32 => ShapeType::Star32,
+            _ => shape,
+        }
+    }
+
+    /// Chart type to write instead of `chart_type`
+    pub fn chart_type(&self, chart_type: ChartType) -> ChartType {
+        match (self, chart_type) {
+            (CompatProfile::PowerPoint, _) => chart_type,
+            (_, ChartType::StockHLC | ChartType::StockOHLC) => ChartType::LineMarkers,
+            _ => chart_type,
+        }
+    }
+
+    /// Apply the substitutions to `slides`, with a warning for each one
+    pub fn apply(&self, slides: &mut [SlideContent]) -> Warnings {
+        let mut warnings = Warnings::new();
+        let app = self.as_str().to_string();
+        for (i, slide) in slides.iter_mut().enumerate() {
+            let mut substituted = |original: String, replacement: String| {
+                let kind = WarningKind::Substituted { original, replacement, app: app.clone() };
+                warnings.push(Warning::new(Some(i + 1), kind));
+            };
+            let transition = self.transition(slide.transition);
+            if transition != slide.transition {
+                substituted(
+                    format!("{} transition", slide.transition.as_str()),
+                    format!("{} transition", transition.as_str()),
+                );
+                slide.transition = transition;
+            }
+            for shape in &mut slide.shapes {
+                let shape_type = self.shape_type(shape.shape_type);
+                if shape_type != shape.shape_type {
+                    substituted(shape.shape_type.display_name().to_string(), shape_type.display_name().to_string());
+                    shape.shape_type = shape_type;
+                }
+            }
+            for chart in &mut slide.charts {
+                let chart_type = self.chart_type(chart.chart_type);
+                if chart_type != chart.chart_type {
+                    substituted(
+                        format!("{} chart", chart.chart_type.as_str()),
+                        format!("{} chart", chart_type.as_str()),
+                    );
+                    chart.chart_type = chart_type;
+                }
+            }
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{ChartBuilder, ChartSeries, MorphOption, Shape};
+
+    #[test]
+    fn test_power_point_profile_keeps_everything() {
+        let profile = CompatProfile::PowerPoint;
+        assert_eq!(profile.transition(TransitionType::Morph(MorphOption::ByWord)), TransitionType::Morph(MorphOption::ByWord));
+        assert_eq!(profile.shape_type(ShapeType::ActionButtonHome), ShapeType::ActionButtonHome);
+        assert_eq!(profile.chart_type(ChartType::StockOHLC), ChartType::StockOHLC);
+    }
+
+    #[test]
+    fn test_apply_substitutes_and_warns() {
+        let chart = ChartBuilder::new("Prices", ChartType::StockHLC)
+            .categories(vec!["Mon", "Tue"])
+            .add_series(ChartSeries::new("High", vec![3.0, 4.0]))
+            .add_series(ChartSeries::new("Low", vec![1.0, 2.0]))
+            .add_series(ChartSeries::new("Close", vec![2.0, 3.0]))
+            .build();
+        let mut slides = vec![
+            SlideContent::new("One")
+                .with_transition(TransitionType::Cover)
+                .add_shape(Shape::new(ShapeType::ActionButtonHome, 0, 0, 100, 100))
+                .add_shape(Shape::new(ShapeType::Star5, 0, 0, 100, 100)),
+            SlideContent::new("Two").with_transition(TransitionType::Fade).add_chart(chart),
+        ];
+
+        let warnings = CompatProfile::GoogleSlides.apply(&mut slides);
+        assert_eq!(slides[0].transition, TransitionType::Push);
+        assert_eq!(slides[0].shapes[0].shape_type, ShapeType::Rectangle);
+        assert_eq!(slides[0].shapes[1].shape_type, ShapeType::Star5);
+        assert_eq!(slides[1].transition, TransitionType::Fade);
+        assert_eq!(slides[1].charts[0].chart_type, ChartType::LineMarkers);
+
+        let messages: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+        assert_eq!(messages, [
+            "slide 1: cover transition replaced by push transition for Google Slides",
+            "slide 1: Action Button (Home) replaced by Rectangle for Google Slides",
+            "slide 2: stockHLC chart replaced by lineMarkers chart for Google Slides",
+        ]);
+    }
+
+    #[test]
+    fn test_keynote_transitions() {
+        let profile = CompatProfile::Keynote;
+        assert_eq!(profile.transition(TransitionType::Split), TransitionType::Wipe);
+        assert_eq!(profile.transition(TransitionType::Cover), TransitionType::Cover);
+        assert_eq!(profile.transition(TransitionType::Morph(MorphOption::ByObject)), TransitionType::Fade);
+    }
+}
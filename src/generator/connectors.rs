@@ -2,7 +2,10 @@
 //!
 //! Provides connector types and XML generation for connecting shapes.
 
-use crate::core::escape_xml;
+use crate::core::{escape_xml, Emu, Positioned, ElementSized, Styled};
+use crate::elements::ColorInput;
+use crate::generator::geometry::{CustomGeometry, GeometryPath};
+use crate::generator::locks::Locks;
 
 /// Connector types available in PPTX
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
@@ -148,9 +151,9 @@ impl Default for ConnectorLine {
 
 impl ConnectorLine {
     /// Create new connector line
-    pub fn new(color: &str, width: u32) -> Self {
+    pub fn new(color: impl Into<ColorInput>, width: u32) -> Self {
         ConnectorLine {
-            color: color.trim_start_matches('#').to_uppercase(),
+            color: color.into().to_hex(),
             width,
             dash: LineDash::Solid,
         }
@@ -195,14 +198,14 @@ impl LineDash {
 pub struct Connector {
     /// Connector type
     pub connector_type: ConnectorType,
-    /// Start X position in EMU
-    pub start_x: u32,
-    /// Start Y position in EMU
-    pub start_y: u32,
-    /// End X position in EMU
-    pub end_x: u32,
-    /// End Y position in EMU
-    pub end_y: u32,
+    /// Start X position; negative values lie left of the slide
+    pub start_x: Emu,
+    /// Start Y position; negative values lie above the slide
+    pub start_y: Emu,
+    /// End X position
+    pub end_x: Emu,
+    /// End Y position
+    pub end_y: Emu,
     /// Line style
     pub line: ConnectorLine,
     /// Start arrow
@@ -221,23 +224,28 @@ pub struct Connector {
     pub end_site: Option<ConnectionSite>,
     /// Optional label text
     pub label: Option<String>,
+    /// Editing locks (`a:cxnSpLocks`)
+    pub locks: Locks,
+    /// Bend points between start and end; when set, the connector is
+    /// drawn as a polyline through them instead of its preset shape
+    pub waypoints: Vec<(Emu, Emu)>,
 }
 
 impl Connector {
     /// Create a new connector
     pub fn new(
         connector_type: ConnectorType,
-        start_x: u32,
-        start_y: u32,
-        end_x: u32,
-        end_y: u32,
+        start_x: impl Into<Emu>,
+        start_y: impl Into<Emu>,
+        end_x: impl Into<Emu>,
+        end_y: impl Into<Emu>,
     ) -> Self {
         Connector {
             connector_type,
-            start_x,
-            start_y,
-            end_x,
-            end_y,
+            start_x: start_x.into(),
+            start_y: start_y.into(),
+            end_x: end_x.into(),
+            end_y: end_y.into(),
             line: ConnectorLine::default(),
             start_arrow: ArrowType::None,
             end_arrow: ArrowType::None,
@@ -247,21 +255,23 @@ impl Connector {
             end_shape_id: None,
             end_site: None,
             label: None,
+            locks: Locks::NONE,
+            waypoints: Vec::new(),
         }
     }
 
     /// Create a straight connector
-    pub fn straight(start_x: u32, start_y: u32, end_x: u32, end_y: u32) -> Self {
+    pub fn straight(start_x: impl Into<Emu>, start_y: impl Into<Emu>, end_x: impl Into<Emu>, end_y: impl Into<Emu>) -> Self {
         Self::new(ConnectorType::Straight, start_x, start_y, end_x, end_y)
     }
 
     /// Create an elbow connector
-    pub fn elbow(start_x: u32, start_y: u32, end_x: u32, end_y: u32) -> Self {
+    pub fn elbow(start_x: impl Into<Emu>, start_y: impl Into<Emu>, end_x: impl Into<Emu>, end_y: impl Into<Emu>) -> Self {
         Self::new(ConnectorType::Elbow, start_x, start_y, end_x, end_y)
     }
 
     /// Create a curved connector
-    pub fn curved(start_x: u32, start_y: u32, end_x: u32, end_y: u32) -> Self {
+    pub fn curved(start_x: impl Into<Emu>, start_y: impl Into<Emu>, end_x: impl Into<Emu>, end_y: impl Into<Emu>) -> Self {
         Self::new(ConnectorType::Curved, start_x, start_y, end_x, end_y)
     }
 
@@ -272,8 +282,8 @@ impl Connector {
     }
 
     /// Set line color
-    pub fn with_color(mut self, color: &str) -> Self {
-        self.line.color = color.trim_start_matches('#').to_uppercase();
+    pub fn with_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.line.color = color.into().to_hex();
         self
     }
 
@@ -328,22 +338,24 @@ impl Connector {
         self
     }
 
-    /// Calculate width for XML
-    fn width(&self) -> u32 {
-        if self.end_x > self.start_x {
-            self.end_x - self.start_x
-        } else {
-            self.start_x - self.end_x
-        }
+    /// Lock the connector against selecting, moving, resizing, etc.
+    pub fn lock(mut self, locks: Locks) -> Self {
+        self.locks |= locks;
+        self
     }
 
-    /// Calculate height for XML
-    fn height(&self) -> u32 {
-        if self.end_y > self.start_y {
-            self.end_y - self.start_y
-        } else {
-            self.start_y - self.end_y
-        }
+    /// Draw the connector through these bend points (see [`crate::generator::routing`])
+    pub fn with_waypoints<X: Into<Emu>, Y: Into<Emu>>(mut self, waypoints: impl IntoIterator<Item = (X, Y)>) -> Self {
+        self.waypoints = waypoints.into_iter().map(|(x, y)| (x.into(), y.into())).collect();
+        self
+    }
+
+    /// Start, bend and end points in drawing order
+    pub fn points(&self) -> Vec<(Emu, Emu)> {
+        let mut points = vec![(self.start_x, self.start_y)];
+        points.extend(&self.waypoints);
+        points.push((self.end_x, self.end_y));
+        points
     }
 
     /// Check if connector is flipped horizontally
@@ -357,22 +369,38 @@ impl Connector {
     }
 }
 
+/// Preset geometry, or a polyline through the waypoints in the frame at (x, y)
+fn geometry_xml(connector: &Connector, x: Emu, y: Emu, cx: Emu, cy: Emu) -> String {
+    if connector.waypoints.is_empty() {
+        return format!("<a:prstGeom prst=\"{}\">\n<a:avLst/>\n</a:prstGeom>", connector.connector_type.preset_name());
+    }
+    let mut path = GeometryPath::new();
+    for (i, (px, py)) in connector.points().into_iter().enumerate() {
+        let (px, py) = ((px - x).get(), (py - y).get());
+        path = if i == 0 { path.move_to(px, py) } else { path.line_to(px, py) };
+    }
+    CustomGeometry::new(cx.get().max(1), cy.get().max(1)).path(path).to_xml()
+}
+
 /// Generate connector XML for a slide
 pub fn generate_connector_xml(connector: &Connector, shape_id: usize) -> String {
-    let x = connector.start_x.min(connector.end_x);
-    let y = connector.start_y.min(connector.end_y);
-    let cx = connector.width();
-    let cy = connector.height();
+    let points = connector.points();
+    let x = points.iter().map(|p| p.0).min().unwrap_or(Emu::ZERO);
+    let y = points.iter().map(|p| p.1).min().unwrap_or(Emu::ZERO);
+    let cx = points.iter().map(|p| p.0).max().unwrap_or(Emu::ZERO) - x;
+    let cy = points.iter().map(|p| p.1).max().unwrap_or(Emu::ZERO) - y;
 
-    let flip_h = if connector.flip_h() { " flipH=\"1\"" } else { "" };
-    let flip_v = if connector.flip_v() { " flipV=\"1\"" } else { "" };
+    // A polyline is drawn in its own frame, so only presets are flipped
+    let routed = !connector.waypoints.is_empty();
+    let flip_h = if connector.flip_h() && !routed { " flipH=\"1\"" } else { "" };
+    let flip_v = if connector.flip_v() && !routed { " flipV=\"1\"" } else { "" };
 
     let mut xml = format!(
         r#"<p:cxnSp>
 <p:nvCxnSpPr>
 <p:cNvPr id="{}" name="Connector {}"/>
-<p:cNvCxnSpPr>"#,
-        shape_id, shape_id
+<p:cNvCxnSpPr>{}"#,
+        shape_id, shape_id, connector.locks.to_xml("a:cxnSpLocks")
     );
 
     // Add connection references if connected to shapes
@@ -402,9 +430,7 @@ pub fn generate_connector_xml(connector: &Connector, shape_id: usize) -> String
 <a:off x="{}" y="{}"/>
 <a:ext cx="{}" cy="{}"/>
 </a:xfrm>
-<a:prstGeom prst="{}">
-<a:avLst/>
-</a:prstGeom>
+{}
 <a:ln w="{}">
 <a:solidFill>
 <a:srgbClr val="{}"/>
@@ -412,7 +438,7 @@ pub fn generate_connector_xml(connector: &Connector, shape_id: usize) -> String
 <a:prstDash val="{}"/>"#,
         flip_h, flip_v,
         x, y, cx, cy,
-        connector.connector_type.preset_name(),
+        geometry_xml(connector, x, y, cx, cy),
         connector.line.width,
         connector.line.color,
         connector.line.dash.xml_value()
@@ -467,6 +493,69 @@ pub fn generate_connector_xml(connector: &Connector, shape_id: usize) -> String
     xml
 }
 
+/// The connector's bounding box; moving or resizing it keeps its direction
+impl Positioned for Connector {
+    fn x(&self) -> Emu {
+        self.start_x.min(self.end_x)
+    }
+
+    fn y(&self) -> Emu {
+        self.start_y.min(self.end_y)
+    }
+
+    fn set_position(&mut self, x: Emu, y: Emu) {
+        let dx = x - Positioned::x(self);
+        let dy = y - Positioned::y(self);
+        self.start_x += dx;
+        self.end_x += dx;
+        self.start_y += dy;
+        self.end_y += dy;
+        for point in &mut self.waypoints {
+            *point = (point.0 + dx, point.1 + dy);
+        }
+    }
+}
+
+impl ElementSized for Connector {
+    fn width(&self) -> Emu {
+        Emu(self.start_x.get().abs_diff(self.end_x.get()) as i64)
+    }
+
+    fn height(&self) -> Emu {
+        Emu(self.start_y.get().abs_diff(self.end_y.get()) as i64)
+    }
+
+    /// Negative sizes collapse the connector to a point on that axis
+    fn set_size(&mut self, width: Emu, height: Emu) {
+        let (left, top) = (Positioned::x(self), Positioned::y(self));
+        let (width, height) = (width.max(Emu::ZERO), height.max(Emu::ZERO));
+        let (start_x, end_x) = if self.start_x <= self.end_x { (left, left + width) } else { (left + width, left) };
+        let (start_y, end_y) = if self.start_y <= self.end_y { (top, top + height) } else { (top + height, top) };
+        // Bend points keep their place relative to the box
+        let scale = |v: Emu, origin: Emu, from: Emu, to: Emu| {
+            if from == Emu::ZERO { return v; }
+            let offset = (v - origin).get() as i128 * to.get() as i128 / from.get() as i128;
+            origin + Emu(offset as i64)
+        };
+        let (old_width, old_height) = (ElementSized::width(self), ElementSized::height(self));
+        for point in &mut self.waypoints {
+            *point = (scale(point.0, left, old_width, width), scale(point.1, top, old_height, height));
+        }
+        (self.start_x, self.end_x, self.start_y, self.end_y) = (start_x, end_x, start_y, end_y);
+    }
+}
+
+/// The line color
+impl Styled for Connector {
+    fn color(&self) -> Option<&str> {
+        Some(&self.line.color)
+    }
+
+    fn set_color(&mut self, color: &str) {
+        self.line.color = ColorInput::from(color).to_hex();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -518,6 +607,18 @@ mod tests {
         assert!(xml.contains("tailEnd"));
     }
 
+    #[test]
+    fn test_generate_routed_connector_xml() {
+        let conn = Connector::elbow(1000, 1000, 5000, 3000).with_waypoints(vec![(1000, 500), (5000, 500)]);
+        let xml = generate_connector_xml(&conn, 1);
+        assert!(xml.contains(r#"<a:off x="1000" y="500"/>"#));
+        assert!(xml.contains(r#"<a:ext cx="4000" cy="2500"/>"#));
+        assert!(xml.contains(r#"<a:path w="4000" h="2500"><a:moveTo><a:pt x="0" y="500"/></a:moveTo>"#));
+        assert!(xml.contains(r#"<a:lnTo><a:pt x="4000" y="2500"/></a:lnTo></a:path>"#));
+        assert!(!xml.contains("prstGeom"));
+        assert!(!xml.contains("flip"));
+    }
+
     #[test]
     fn test_connector_with_label() {
         let conn = Connector::straight(0, 0, 1000000, 500000)
@@ -536,6 +637,19 @@ mod tests {
         assert_eq!(LineDash::DashDot.xml_value(), "dashDot");
     }
 
+    #[test]
+    fn test_connector_positioned_keeps_direction() {
+        let mut conn = Connector::new(ConnectorType::Straight, 500, 100, 100, 300);
+        assert_eq!((Positioned::x(&conn), ElementSized::width(&conn)), (Emu(100), Emu(400)));
+
+        conn.set_position(Emu(0), Emu(0));
+        conn.set_size(Emu(200), Emu(100));
+        assert_eq!((conn.start_x.0, conn.start_y.0, conn.end_x.0, conn.end_y.0), (200, 0, 0, 100));
+
+        conn.set_color("#ff0000");
+        assert_eq!(conn.color(), Some("FF0000"));
+    }
+
     #[test]
     fn test_connection_site_index() {
         assert_eq!(ConnectionSite::Top.index(), 0);
@@ -543,4 +657,15 @@ mod tests {
         assert_eq!(ConnectionSite::Bottom.index(), 2);
         assert_eq!(ConnectionSite::Left.index(), 3);
     }
+
+    #[test]
+    fn test_locked_connector_xml() {
+        let conn = Connector::straight(0, 0, 100, 100)
+            .connect_start(2, ConnectionSite::Right)
+            .lock(Locks::MOVE);
+        let xml = generate_connector_xml(&conn, 5);
+
+        // Locks come before the connection references
+        assert!(xml.contains("<p:cNvCxnSpPr><a:cxnSpLocks noMove=\"1\"/>\n<a:stCxn id=\"2\""));
+    }
 }
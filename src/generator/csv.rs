@@ -0,0 +1,330 @@
+//! CSV/TSV ingestion for tables and charts
+//!
+//! [`Table::from_csv`] and [`ChartBuilder::from_csv`] read exported data
+//! files directly:
+//!
+//! ```rust,ignore
+//! let table = Table::from_csv(File::open("sales.csv")?, CsvOptions::default())?;
+//!
+//! let chart = ChartBuilder::from_csv(
+//!     "Revenue",
+//!     ChartType::Bar,
+//!     File::open("sales.tsv")?,
+//!     CsvOptions::tsv().with_category("Quarter").with_values(["2024", "2025"]),
+//! )?
+//! .build();
+//! ```
+//!
+//! Fields follow RFC 4180: quoted fields may contain the delimiter, doubled
+//! quotes and line breaks.
+
+use std::io::Read;
+
+use crate::exc::{PptxError, Result};
+use crate::generator::charts::{ChartBuilder, ChartSeries, ChartType};
+use crate::generator::slide_content::Region;
+use crate::generator::tables::{Table, TableCell, TableRow};
+
+/// Column picked by name (needs a header row) or 0-based index
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvColumn {
+    Index(usize),
+    Name(String),
+}
+
+impl From<usize> for CsvColumn {
+    fn from(index: usize) -> Self {
+        CsvColumn::Index(index)
+    }
+}
+
+impl From<&str> for CsvColumn {
+    fn from(name: &str) -> Self {
+        CsvColumn::Name(name.to_string())
+    }
+}
+
+impl From<String> for CsvColumn {
+    fn from(name: String) -> Self {
+        CsvColumn::Name(name)
+    }
+}
+
+/// How to read a CSV file and which columns feed a chart
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvOptions {
+    /// First record holds column names (table header row, series names)
+    pub headers: bool,
+    /// Field separator, `,` for CSV and `\t` for TSV
+    pub delimiter: char,
+    /// Chart category column
+    pub category_column: CsvColumn,
+    /// Chart value columns, one series each; empty uses every other column
+    pub value_columns: Vec<CsvColumn>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            headers: true,
+            delimiter: ',',
+            category_column: CsvColumn::Index(0),
+            value_columns: Vec::new(),
+        }
+    }
+}
+
+impl CsvOptions {
+    /// Tab-separated values with a header row
+    pub fn tsv() -> Self {
+        CsvOptions { delimiter: '\t', ..Self::default() }
+    }
+
+    /// Set whether the first record is a header row
+    pub fn with_headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Set the field separator
+    pub fn with_delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the chart category column
+    pub fn with_category(mut self, column: impl Into<CsvColumn>) -> Self {
+        self.category_column = column.into();
+        self
+    }
+
+    /// Set the chart value columns
+    pub fn with_values<C: Into<CsvColumn>>(mut self, columns: impl IntoIterator<Item = C>) -> Self {
+        self.value_columns = columns.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// Parsed CSV file
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CsvData {
+    /// Column names, empty without a header row
+    pub headers: Vec<String>,
+    /// Data records
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvData {
+    /// Read all records from `reader`
+    pub fn read(mut reader: impl Read, options: &CsvOptions) -> Result<Self> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        let mut rows = parse_records(text.strip_prefix('\u{feff}').unwrap_or(&text), options.delimiter)?;
+        let headers = if options.headers && !rows.is_empty() { rows.remove(0) } else { Vec::new() };
+        Ok(CsvData { headers, rows })
+    }
+
+    /// Number of columns in the widest record
+    pub fn column_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).chain([self.headers.len()]).max().unwrap_or(0)
+    }
+
+    /// Resolve a column to its index
+    pub fn column_index(&self, column: &CsvColumn) -> Result<usize> {
+        let index = match column {
+            CsvColumn::Index(index) => Some(*index).filter(|i| *i < self.column_count()),
+            CsvColumn::Name(name) => self.headers.iter().position(|h| h.trim() == name),
+        };
+        index.ok_or_else(|| PptxError::InvalidValue(format!("CSV has no column {column:?}")))
+    }
+
+    /// Field of a record, empty if the record is short
+    fn field(&self, row: usize, column: usize) -> &str {
+        self.rows[row].get(column).map_or("", String::as_str)
+    }
+}
+
+/// Split `text` into records of fields
+fn parse_records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    let mut in_quotes = false;
+    let mut quote_line = 0;
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push(c);
+                }
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                quote_line = line;
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                line += 1;
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(PptxError::InvalidValue(format!("CSV line {quote_line}: unterminated quoted field")));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    // Blank lines carry no data
+    records.retain(|r| !(r.len() == 1 && r[0].is_empty()));
+    Ok(records)
+}
+
+impl Table {
+    /// Table with one row per CSV record, filling the content area
+    ///
+    /// A header row is bolded; theme defaults color it like other header
+    /// rows. Short records are padded with empty cells.
+    pub fn from_csv(reader: impl Read, options: CsvOptions) -> Result<Table> {
+        let data = CsvData::read(reader, &options)?;
+        Table::from_records(&data.headers, &data.rows)
+    }
+
+    /// Table of text records under an optional bold header row
+    pub(crate) fn from_records(headers: &[String], records: &[Vec<String>]) -> Result<Table> {
+        let columns = records.iter().map(Vec::len).chain([headers.len()]).max().unwrap_or(0);
+        if columns == 0 {
+            return Err(PptxError::InvalidValue("no data for table".to_string()));
+        }
+        let (x, y, width, _) = Region::Content.bounds();
+        let to_row = |fields: &[String], header: bool| {
+            let cells = (0..columns)
+                .map(|c| {
+                    let cell = TableCell::new(fields.get(c).map_or("", String::as_str));
+                    if header { cell.bold() } else { cell }
+                })
+                .collect();
+            TableRow::new(cells)
+        };
+
+        let mut rows = Vec::with_capacity(records.len() + 1);
+        if !headers.is_empty() {
+            rows.push(to_row(headers, true));
+        }
+        rows.extend(records.iter().map(|r| to_row(r, false)));
+        Ok(Table::new(rows, vec![width / columns as u32; columns], x, y))
+    }
+}
+
+impl ChartBuilder {
+    /// Chart builder with categories and series from CSV columns
+    ///
+    /// Categories come from `options.category_column`, one series from each
+    /// of `options.value_columns` (default: every other column), named after
+    /// its header. Value cells must be numbers.
+    pub fn from_csv(title: &str, chart_type: ChartType, reader: impl Read, options: CsvOptions) -> Result<ChartBuilder> {
+        let data = CsvData::read(reader, &options)?;
+        let category = data.column_index(&options.category_column)?;
+        let value_columns = if options.value_columns.is_empty() {
+            (0..data.column_count()).filter(|c| *c != category).collect()
+        } else {
+            options.value_columns.iter().map(|c| data.column_index(c)).collect::<Result<Vec<_>>>()?
+        };
+
+        let categories: Vec<&str> = (0..data.rows.len()).map(|r| data.field(r, category)).collect();
+        let mut builder = ChartBuilder::new(title, chart_type).categories(categories);
+        let first_record = if options.headers { 2 } else { 1 };
+        for column in value_columns {
+            let name = data.headers.get(column).cloned().unwrap_or_else(|| format!("Series {}", column + 1));
+            let values = (0..data.rows.len())
+                .map(|r| {
+                    let field = data.field(r, column).trim();
+                    field.parse::<f64>().map_err(|_| {
+                        PptxError::InvalidValue(format!(
+                            "CSV record {}, column {}: \"{field}\" is not a number",
+                            r + first_record,
+                            column + 1
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<f64>>>()?;
+            builder = builder.add_series(ChartSeries::new(name.trim(), values));
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SALES: &str = "Quarter,Region,2024,2025\r\nQ1,\"North, East\",100,120\nQ2,\"Say \"\"hi\"\"\",150,160.5\n\n";
+
+    #[test]
+    fn test_parse_quoted_fields() {
+        let data = CsvData::read(SALES.as_bytes(), &CsvOptions::default()).unwrap();
+        assert_eq!(data.headers, ["Quarter", "Region", "2024", "2025"]);
+        assert_eq!(data.rows.len(), 2);
+        assert_eq!(data.rows[0][1], "North, East");
+        assert_eq!(data.rows[1][1], "Say \"hi\"");
+        assert!(CsvData::read("a,\"b".as_bytes(), &CsvOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_table_from_csv() {
+        let table = Table::from_csv(SALES.as_bytes(), CsvOptions::default()).unwrap();
+        assert_eq!((table.row_count(), table.column_count()), (3, 4));
+        assert!(table.rows[0].cells[0].bold);
+        assert!(!table.rows[1].cells[0].bold);
+
+        let tsv = "a\tb\n1\n";
+        let table = Table::from_csv(tsv.as_bytes(), CsvOptions::tsv().with_headers(false)).unwrap();
+        assert_eq!((table.row_count(), table.column_count()), (2, 2));
+        assert_eq!(table.rows[1].cells[1].text, "");
+        assert!(table.validate().is_ok());
+    }
+
+    #[test]
+    fn test_chart_from_csv() {
+        let options = CsvOptions::default().with_category("Quarter").with_values(["2025", "2024"]);
+        let chart = ChartBuilder::from_csv("Sales", ChartType::Bar, SALES.as_bytes(), options).unwrap().build();
+        assert_eq!(chart.categories, ["Q1", "Q2"]);
+        assert_eq!(chart.series[0].name, "2025");
+        assert_eq!(chart.series[0].values, [120.0, 160.5]);
+        assert_eq!(chart.series[1].name, "2024");
+    }
+
+    #[test]
+    fn test_chart_from_csv_errors() {
+        // "Region" is not numeric
+        let Err(err) = ChartBuilder::from_csv("Sales", ChartType::Bar, SALES.as_bytes(), CsvOptions::default()) else {
+            panic!("text column parsed as values");
+        };
+        assert!(err.to_string().contains("record 2, column 2"));
+
+        let options = CsvOptions::default().with_values(["Missing"]);
+        assert!(ChartBuilder::from_csv("Sales", ChartType::Bar, SALES.as_bytes(), options).is_err());
+
+        let options = CsvOptions::default().with_headers(false).with_values([1usize]);
+        let chart = ChartBuilder::from_csv("Raw", ChartType::Line, "x,1\ny,2".as_bytes(), options).unwrap().build();
+        assert_eq!(chart.series[0].name, "Series 2");
+        assert_eq!(chart.series[0].values, [1.0, 2.0]);
+    }
+}
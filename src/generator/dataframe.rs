@@ -0,0 +1,222 @@
+//! Polars DataFrame integration (`polars` feature)
+//!
+//! ```rust,ignore
+//! let table = Table::from_dataframe(&df)?;
+//! let table = Table::from_dataframe_with(&df, &NumberFormat::new().decimals(1).thousands().prefix("$"))?;
+//!
+//! let chart = ChartBuilder::new("Revenue", ChartType::Line)
+//!     .categories(vec!["Q1", "Q2", "Q3"])
+//!     .add_series(ChartSeries::from_series(df.column("revenue")?.as_materialized_series())?)
+//!     .build();
+//! ```
+
+use polars::prelude::{AnyValue, DataFrame, DataType, Series};
+
+use crate::exc::{PptxError, Result};
+use crate::generator::charts::ChartSeries;
+use crate::generator::slide_content::Region;
+use crate::generator::tables::{Table, TableCell, TableRow};
+
+/// How numeric DataFrame cells are written into table text
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Fixed number of decimals; integers and floats print as-is when unset
+    pub decimals: Option<usize>,
+    /// Group the integer part in thousands with `,`
+    pub thousands: bool,
+    /// Text before the number, e.g. `$`
+    pub prefix: String,
+    /// Text after the number, e.g. `%`
+    pub suffix: String,
+}
+
+impl NumberFormat {
+    /// Numbers as Polars prints them
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Round to a fixed number of decimals
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Group the integer part in thousands (`1,234,567`)
+    pub fn thousands(mut self) -> Self {
+        self.thousands = true;
+        self
+    }
+
+    /// Text before the number
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Text after the number
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Format an already-rendered number (`-1234.5`)
+    fn apply(&self, number: &str) -> String {
+        let (sign, digits) = match number.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", number),
+        };
+        let (int, frac) = match digits.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (digits, None),
+        };
+        let mut out = format!("{sign}{}", self.prefix);
+        if self.thousands && int.chars().all(|c| c.is_ascii_digit()) {
+            for (i, c) in int.chars().enumerate() {
+                if i > 0 && (int.len() - i) % 3 == 0 {
+                    out.push(',');
+                }
+                out.push(c);
+            }
+        } else {
+            out.push_str(int);
+        }
+        if let Some(frac) = frac {
+            out.push('.');
+            out.push_str(frac);
+        }
+        out.push_str(&self.suffix);
+        out
+    }
+}
+
+fn polars_error(e: polars::prelude::PolarsError) -> PptxError {
+    PptxError::InvalidValue(e.to_string())
+}
+
+/// Text of one cell; nulls are empty
+fn cell_text(series: &Series, row: usize, format: &NumberFormat) -> Result<String> {
+    let value = series.get(row).map_err(polars_error)?;
+    if matches!(value, AnyValue::Null) {
+        return Ok(String::new());
+    }
+    let dtype = series.dtype();
+    if !dtype.is_primitive_numeric() {
+        return Ok(value.str_value().into_owned());
+    }
+    let number = match (format.decimals, value.extract::<f64>()) {
+        (Some(decimals), Some(v)) => format!("{v:.decimals$}"),
+        _ => value.str_value().into_owned(),
+    };
+    Ok(format.apply(&number))
+}
+
+impl Table {
+    /// Table with a bold header row of column names and one row per
+    /// DataFrame row, filling the content area
+    pub fn from_dataframe(df: &DataFrame) -> Result<Table> {
+        Self::from_dataframe_with(df, &NumberFormat::default())
+    }
+
+    /// Like [`Table::from_dataframe`], formatting numeric columns with
+    /// `format` and right-aligning them
+    pub fn from_dataframe_with(df: &DataFrame, format: &NumberFormat) -> Result<Table> {
+        let columns = df.get_columns();
+        if columns.is_empty() {
+            return Err(PptxError::InvalidValue("DataFrame has no columns".to_string()));
+        }
+        let series: Vec<&Series> = columns.iter().map(|c| c.as_materialized_series()).collect();
+
+        let mut rows = Vec::with_capacity(df.height() + 1);
+        rows.push(TableRow::new(series.iter().map(|s| TableCell::new(s.name()).bold()).collect()));
+        for row in 0..df.height() {
+            let cells = series
+                .iter()
+                .map(|s| {
+                    let cell = TableCell::new(&cell_text(s, row, format)?);
+                    Ok(if s.dtype().is_primitive_numeric() { cell.align_right() } else { cell })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(TableRow::new(cells));
+        }
+
+        let (x, y, width, _) = Region::Content.bounds();
+        let count = series.len();
+        Ok(Table::new(rows, vec![width / count as u32; count], x, y))
+    }
+}
+
+impl ChartSeries {
+    /// Chart series named after a numeric Polars series
+    ///
+    /// Fails on non-numeric series and on nulls, which a chart cannot plot.
+    pub fn from_series(series: &Series) -> Result<ChartSeries> {
+        if !series.dtype().is_primitive_numeric() {
+            return Err(PptxError::InvalidValue(format!(
+                "series \"{}\" has non-numeric type {}",
+                series.name(),
+                series.dtype()
+            )));
+        }
+        if series.null_count() > 0 {
+            return Err(PptxError::InvalidValue(format!(
+                "series \"{}\" has {} null values",
+                series.name(),
+                series.null_count()
+            )));
+        }
+        let values = series.cast(&DataType::Float64).map_err(polars_error)?;
+        let values = values.f64().map_err(polars_error)?.into_no_null_iter().collect();
+        Ok(ChartSeries::new(series.name(), values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::df;
+
+    fn sales() -> DataFrame {
+        df!(
+            "region" => ["North", "South", "West"],
+            "units" => [1200i64, 950, 1234567],
+            "share" => [Some(0.4567), None, Some(0.1)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_table_from_dataframe() {
+        let table = Table::from_dataframe(&sales()).unwrap();
+        assert_eq!((table.row_count(), table.column_count()), (4, 3));
+        assert_eq!(table.rows[0].cells[1].text, "units");
+        assert!(table.rows[0].cells[1].bold);
+        assert_eq!(table.rows[1].cells[0].text, "North");
+        assert_eq!(table.rows[3].cells[1].text, "1234567");
+        assert_eq!(table.rows[2].cells[2].text, "");
+        assert!(table.validate().is_ok());
+    }
+
+    #[test]
+    fn test_number_format() {
+        let format = NumberFormat::new().decimals(2).thousands().prefix("$");
+        let table = Table::from_dataframe_with(&sales(), &format).unwrap();
+        assert_eq!(table.rows[3].cells[1].text, "$1,234,567.00");
+        assert_eq!(table.rows[1].cells[2].text, "$0.46");
+        assert_eq!(table.rows[1].cells[0].text, "North");
+
+        assert_eq!(NumberFormat::new().thousands().suffix("%").apply("-1234.5"), "-1,234.5%");
+        assert_eq!(NumberFormat::new().thousands().apply("999"), "999");
+    }
+
+    #[test]
+    fn test_chart_series_from_series() {
+        let df = sales();
+        let units = ChartSeries::from_series(df.column("units").unwrap().as_materialized_series()).unwrap();
+        assert_eq!(units.name, "units");
+        assert_eq!(units.values, [1200.0, 950.0, 1234567.0]);
+
+        assert!(ChartSeries::from_series(df.column("share").unwrap().as_materialized_series()).is_err());
+        assert!(ChartSeries::from_series(df.column("region").unwrap().as_materialized_series()).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Funnel diagrams
+//!
+//! [`Funnel`] draws a conversion funnel as native shapes: one trapezoid per
+//! stage, stacked top to bottom, whose top edge is as wide as the stage's
+//! value and whose bottom edge meets the next stage. Stage names sit beside
+//! the funnel, with the conversion rate between neighbouring stages under
+//! them. Widths never drop below a fifth of the funnel so small stages keep
+//! room for their value. Add it to a slide with
+//! [`SlideContent::funnel`](super::SlideContent::funnel).
+
+use crate::elements::ColorInput;
+use super::geometry::{CustomGeometry, GeometryPath};
+use super::shapes::{Shape, ShapeFill, ShapeType};
+use super::shapes_xml::get_text_color;
+use super::slide_content::Region;
+use super::text::FormattedText;
+
+/// Share of the frame width taken by the funnel; the rest holds the labels
+const FUNNEL_SHARE: f64 = 0.6;
+/// Narrowest stage edge as a share of the funnel width
+const MIN_WIDTH: f64 = 0.2;
+/// Vertical gap between stages (0.08 inch)
+const STAGE_GAP: u32 = 73152;
+/// Gap between the funnel and the label column (0.2 inch)
+const LABEL_GAP: u32 = 182880;
+/// Stage name and conversion label height (0.3 inch)
+const LABEL_HEIGHT: u32 = 274320;
+/// Stage value and name font size in points
+const STAGE_FONT_SIZE: u32 = 14;
+/// Conversion rate font size in points
+const CONVERSION_FONT_SIZE: u32 = 11;
+
+/// One named stage of a [`Funnel`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunnelStage {
+    pub label: String,
+    pub value: f64,
+}
+
+/// Stacked trapezoids sized by value, with conversion rates between stages
+///
+/// ```
+/// use ppt_rs::generator::{Funnel, SlideContent};
+///
+/// let funnel = Funnel::stages(&[("Visitors", 10000), ("Signups", 1200), ("Paid", 300)]);
+/// assert_eq!(funnel.conversions(), vec![Some(12.0), Some(25.0)]);
+///
+/// let slide = SlideContent::new("Acquisition").funnel(&funnel);
+/// assert!(slide.shapes.iter().any(|s| s.name.as_deref() == Some("Conversion: Signups → Paid")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Funnel {
+    stages: Vec<FunnelStage>,
+    colors: Vec<String>,
+    show_conversion: bool,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Funnel {
+    /// Funnel with one stage per `(label, value)`, filling the content area
+    pub fn stages<T: Copy + Into<f64>>(stages: &[(&str, T)]) -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
+        Funnel {
+            stages: stages.iter()
+                .map(|&(label, value)| FunnelStage { label: label.to_string(), value: value.into() })
+                .collect(),
+            colors: ["0D47A1", "1565C0", "1E88E5", "42A5F5", "90CAF9"].iter().map(|c| c.to_string()).collect(),
+            show_conversion: true,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Append a stage below the last one
+    pub fn stage(mut self, label: &str, value: impl Into<f64>) -> Self {
+        self.stages.push(FunnelStage { label: label.to_string(), value: value.into() });
+        self
+    }
+
+    /// Stage fills from top to bottom, repeated when there are more stages
+    pub fn colors<C: Into<ColorInput>>(mut self, colors: impl IntoIterator<Item = C>) -> Self {
+        let colors: Vec<String> = colors.into_iter().map(|c| c.into().to_hex()).collect();
+        if !colors.is_empty() {
+            self.colors = colors;
+        }
+        self
+    }
+
+    /// Show the conversion rate between stages (on by default)
+    pub fn show_conversion(mut self, show: bool) -> Self {
+        self.show_conversion = show;
+        self
+    }
+
+    pub fn position(mut self, x: u32, y: u32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn stage_list(&self) -> &[FunnelStage] {
+        &self.stages
+    }
+
+    /// Percentage of each stage that reached the next one
+    ///
+    /// `None` where a stage's value is zero or negative.
+    pub fn conversions(&self) -> Vec<Option<f64>> {
+        self.stages.windows(2)
+            .map(|pair| (pair[0].value > 0.0).then(|| pair[1].value / pair[0].value * 100.0))
+            .collect()
+    }
+
+    /// Stage trapezoids, stage names and conversion labels
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let count = self.stages.len() as u32;
+        if count == 0 {
+            return Vec::new();
+        }
+        let funnel_width = (self.width as f64 * FUNNEL_SHARE) as u32;
+        let center = self.x + funnel_width / 2;
+        let stage_height = self.height.saturating_sub(STAGE_GAP * (count - 1)) / count;
+        let label_x = self.x + funnel_width + LABEL_GAP;
+        let label_width = self.width.saturating_sub(funnel_width + LABEL_GAP);
+
+        let max = self.stages.iter().map(|s| s.value).fold(0.0f64, f64::max);
+        let edge = |value: f64| {
+            let share = if max > 0.0 { (value / max).clamp(0.0, 1.0) } else { 1.0 };
+            (funnel_width as f64 * (MIN_WIDTH + (1.0 - MIN_WIDTH) * share)) as u32
+        };
+        let clear = || ShapeFill::new("FFFFFF").with_transparency(100);
+
+        let mut shapes = Vec::new();
+        let mut labels = Vec::new();
+        for (i, stage) in self.stages.iter().enumerate() {
+            let top = edge(stage.value);
+            // The last stage keeps its width instead of narrowing to nothing
+            let bottom = self.stages.get(i + 1).map_or(top, |next| edge(next.value));
+            let frame = top.max(bottom);
+            let y = self.y + i as u32 * (stage_height + STAGE_GAP);
+            let (w, h) = (frame as i64, stage_height as i64);
+            let (t, b) = (top as i64, bottom as i64);
+            let outline = GeometryPath::new().polygon(&[
+                ((w - t) / 2, 0), ((w + t) / 2, 0), ((w + b) / 2, h), ((w - b) / 2, h),
+            ]);
+            let color = &self.colors[i % self.colors.len()];
+            shapes.push(Shape::new(ShapeType::Rectangle, center - frame / 2, y, frame, stage_height)
+                .with_geometry(CustomGeometry::new(w, h).path(outline))
+                .with_fill(ShapeFill::new(color.as_str()))
+                .with_rich_text(vec![FormattedText::new(&format_value(stage.value))
+                    .bold()
+                    .color(get_text_color(Some(color)))
+                    .font_size(STAGE_FONT_SIZE)])
+                .with_name(&format!("Stage: {}", stage.label)));
+
+            let middle = y + stage_height / 2;
+            labels.push(Shape::new(ShapeType::Rectangle, label_x, middle.saturating_sub(LABEL_HEIGHT / 2), label_width, LABEL_HEIGHT)
+                .with_fill(clear())
+                .with_rich_text(vec![FormattedText::new(&stage.label).bold().color("424242").font_size(STAGE_FONT_SIZE)])
+                .with_name(&format!("Stage Label: {}", stage.label)));
+        }
+
+        if self.show_conversion {
+            for (i, rate) in self.conversions().into_iter().enumerate() {
+                let Some(rate) = rate else { continue };
+                let boundary = self.y + (i as u32 + 1) * (stage_height + STAGE_GAP) - STAGE_GAP / 2;
+                let (from, to) = (&self.stages[i].label, &self.stages[i + 1].label);
+                labels.push(Shape::new(ShapeType::Rectangle, label_x, boundary.saturating_sub(LABEL_HEIGHT / 2), label_width, LABEL_HEIGHT)
+                    .with_fill(clear())
+                    .with_rich_text(vec![FormattedText::new(&format!("↓ {rate:.1}%")).italic().color("757575").font_size(CONVERSION_FONT_SIZE)])
+                    .with_name(&format!("Conversion: {from} → {to}")));
+            }
+        }
+        shapes.extend(labels);
+        shapes
+    }
+}
+
+/// `10000` as `10,000`; fractions keep up to two decimals
+fn format_value(value: f64) -> String {
+    let text = if value.fract() == 0.0 { format!("{value:.0}") } else { format!("{value:.2}").trim_end_matches('0').to_string() };
+    let (sign, digits) = text.strip_prefix('-').map_or(("", text.as_str()), |d| ("-", d));
+    let (int, frac) = digits.split_once('.').map_or((digits, None), |(i, f)| (i, Some(f)));
+    let mut out = sign.to_string();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    if let Some(frac) = frac {
+        out.push('.');
+        out.push_str(frac);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn funnel() -> Funnel {
+        Funnel::stages(&[("Visitors", 10000), ("Signups", 1200), ("Paid", 300)])
+            .position(0, 0)
+            .size(10_000_000, 3_000_000)
+    }
+
+    #[test]
+    fn test_stage_widths_follow_values() {
+        let shapes = funnel().to_shapes();
+        let stages: Vec<&Shape> = shapes.iter().filter(|s| s.name.as_deref().is_some_and(|n| n.starts_with("Stage: "))).collect();
+        assert_eq!(stages.len(), 3);
+        // The first stage spans the funnel, centered in it
+        assert_eq!((stages[0].x.0, stages[0].width.0), (0, 6_000_000));
+        assert!(stages[1].width.0 < stages[0].width.0);
+        // Small stages are clamped to the minimum width
+        assert!(stages[2].width.0 >= 1_200_000);
+        assert_eq!(stages[0].rich_text[0].text, "10,000");
+        assert!(stages.iter().all(|s| s.geometry.is_some()));
+    }
+
+    #[test]
+    fn test_conversion_labels() {
+        let shapes = funnel().to_shapes();
+        let conversions: Vec<&str> = shapes.iter()
+            .filter(|s| s.name.as_deref().is_some_and(|n| n.starts_with("Conversion: ")))
+            .map(|s| s.rich_text[0].text.as_str())
+            .collect();
+        assert_eq!(conversions, vec!["↓ 12.0%", "↓ 25.0%"]);
+        assert_eq!(funnel().show_conversion(false).to_shapes().len(), 6);
+        assert!(Funnel::stages::<f64>(&[]).to_shapes().is_empty());
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(1234567.0), "1,234,567");
+        assert_eq!(format_value(-950.0), "-950");
+        assert_eq!(format_value(12.5), "12.5");
+    }
+}
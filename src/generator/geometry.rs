@@ -0,0 +1,124 @@
+//! Custom shape geometry (`a:custGeom`)
+//!
+//! A [`CustomGeometry`] is a set of paths in its own coordinate space,
+//! stretched to the shape's frame. Set it with
+//! [`Shape::with_geometry`](super::Shape::with_geometry); it replaces the
+//! shape type's preset outline.
+
+use std::fmt::Write;
+
+/// One drawing command of a [`GeometryPath`], in path coordinates
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    MoveTo(i64, i64),
+    LineTo(i64, i64),
+    /// Cubic Bézier through two control points to the end point
+    CubicTo((i64, i64), (i64, i64), (i64, i64)),
+    Close,
+}
+
+/// Outline filled as one piece; subpaths wound the other way cut holes
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GeometryPath {
+    pub commands: Vec<PathCommand>,
+}
+
+impl GeometryPath {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn move_to(mut self, x: i64, y: i64) -> Self {
+        self.commands.push(PathCommand::MoveTo(x, y));
+        self
+    }
+
+    pub fn line_to(mut self, x: i64, y: i64) -> Self {
+        self.commands.push(PathCommand::LineTo(x, y));
+        self
+    }
+
+    pub fn cubic_to(mut self, c1: (i64, i64), c2: (i64, i64), end: (i64, i64)) -> Self {
+        self.commands.push(PathCommand::CubicTo(c1, c2, end));
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    /// Closed polygon through `points`
+    pub fn polygon(mut self, points: &[(i64, i64)]) -> Self {
+        for (i, &(x, y)) in points.iter().enumerate() {
+            self.commands.push(if i == 0 { PathCommand::MoveTo(x, y) } else { PathCommand::LineTo(x, y) });
+        }
+        self.close()
+    }
+}
+
+/// Paths drawn in a `width` × `height` coordinate space
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomGeometry {
+    pub width: i64,
+    pub height: i64,
+    pub paths: Vec<GeometryPath>,
+}
+
+impl CustomGeometry {
+    pub fn new(width: i64, height: i64) -> Self {
+        CustomGeometry { width, height, paths: Vec::new() }
+    }
+
+    pub fn path(mut self, path: GeometryPath) -> Self {
+        self.paths.push(path);
+        self
+    }
+
+    /// `a:custGeom` element
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            r#"<a:custGeom><a:avLst/><a:gdLst/><a:ahLst/><a:cxnLst/><a:rect l="0" t="0" r="r" b="b"/><a:pathLst>"#,
+        );
+        for path in &self.paths {
+            let _ = write!(xml, r#"<a:path w="{}" h="{}">"#, self.width, self.height);
+            for command in &path.commands {
+                match *command {
+                    PathCommand::MoveTo(x, y) => {
+                        let _ = write!(xml, r#"<a:moveTo><a:pt x="{x}" y="{y}"/></a:moveTo>"#);
+                    }
+                    PathCommand::LineTo(x, y) => {
+                        let _ = write!(xml, r#"<a:lnTo><a:pt x="{x}" y="{y}"/></a:lnTo>"#);
+                    }
+                    PathCommand::CubicTo((x1, y1), (x2, y2), (x, y)) => {
+                        let _ = write!(
+                            xml,
+                            r#"<a:cubicBezTo><a:pt x="{x1}" y="{y1}"/><a:pt x="{x2}" y="{y2}"/><a:pt x="{x}" y="{y}"/></a:cubicBezTo>"#
+                        );
+                    }
+                    PathCommand::Close => xml.push_str("<a:close/>"),
+                }
+            }
+            xml.push_str("</a:path>");
+        }
+        xml.push_str("</a:pathLst></a:custGeom>");
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_geometry_xml() {
+        let geometry = CustomGeometry::new(100, 50)
+            .path(GeometryPath::new().polygon(&[(0, 0), (100, 0), (50, 50)]))
+            .path(GeometryPath::new().move_to(0, 50).cubic_to((10, 40), (20, 40), (30, 50)).close());
+        let xml = geometry.to_xml();
+        assert!(xml.starts_with("<a:custGeom>"));
+        assert_eq!(xml.matches(r#"<a:path w="100" h="50">"#).count(), 2);
+        assert!(xml.contains(r#"<a:lnTo><a:pt x="50" y="50"/></a:lnTo><a:close/>"#));
+        assert!(xml.contains(r#"<a:cubicBezTo><a:pt x="10" y="40"/><a:pt x="20" y="40"/><a:pt x="30" y="50"/></a:cubicBezTo>"#));
+    }
+}
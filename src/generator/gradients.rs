@@ -2,6 +2,8 @@
 //!
 //! Provides gradient types and XML generation for shape fills.
 
+use crate::elements::ColorInput;
+
 /// Gradient types
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
 pub enum GradientType {
@@ -68,10 +70,10 @@ pub struct GradientStop {
 
 impl GradientStop {
     /// Create a new gradient stop
-    pub fn new(position: u32, color: &str) -> Self {
+    pub fn new(position: u32, color: impl Into<ColorInput>) -> Self {
         GradientStop {
             position: position.min(100000),
-            color: color.trim_start_matches('#').to_uppercase(),
+            color: color.into().to_hex(),
             transparency: None,
         }
     }
@@ -0,0 +1,328 @@
+//! Heatmap tables
+//!
+//! [`Heatmap`] turns a numeric matrix into a table whose cell backgrounds
+//! are interpolated from a [`Palette`], for risk matrices and correlation
+//! tables. Cells show their values by default, in black or white depending
+//! on the background, and a gradient legend under the table maps colors
+//! back to values. Add it to a slide with
+//! [`SlideContent::heatmap`](super::SlideContent::heatmap).
+
+use crate::elements::{ColorInput, RgbColor};
+use super::shapes::{GradientDirection, GradientFill, GradientStop, Shape, ShapeFill, ShapeType};
+use super::shapes_xml::get_text_color;
+use super::slide_content::Region;
+use super::tables::{Table, TableCell, TableRow};
+use super::text::FormattedText;
+
+/// Height of the legend strip under the table (0.4 inch)
+const LEGEND_HEIGHT: u32 = 365760;
+/// Width of the legend's color bar (2.5 inches)
+const LEGEND_BAR_WIDTH: u32 = 2286000;
+/// Width of the min/max labels beside the bar (0.9 inch)
+const LEGEND_LABEL_WIDTH: u32 = 822960;
+/// Legend label font size in points
+const LEGEND_FONT_SIZE: u32 = 11;
+
+/// Color scale from the lowest to the highest value
+#[derive(Clone, Debug, PartialEq)]
+pub enum Palette {
+    /// Red, yellow, green: low values are bad
+    RdYlGn,
+    /// Green, yellow, red: low values are good (risk scores)
+    GnYlRd,
+    /// Red, white, blue: diverging around the middle (correlations)
+    RdBu,
+    /// White to dark blue
+    Blues,
+    /// Evenly spaced stops, lowest value first (RGB hex)
+    Custom(Vec<String>),
+}
+
+impl Palette {
+    /// Custom palette through `colors`, lowest value first
+    pub fn custom<C: Into<ColorInput>>(colors: impl IntoIterator<Item = C>) -> Self {
+        Palette::Custom(colors.into_iter().map(|c| c.into().to_hex()).collect())
+    }
+
+    /// Color stops (RGB hex), lowest value first
+    pub fn stops(&self) -> Vec<String> {
+        let preset: &[&str] = match self {
+            Palette::RdYlGn => &["D73027", "FEE08B", "1A9850"],
+            Palette::GnYlRd => &["1A9850", "FEE08B", "D73027"],
+            Palette::RdBu => &["B2182B", "F7F7F7", "2166AC"],
+            Palette::Blues => &["F7FBFF", "6BAED6", "08306B"],
+            Palette::Custom(colors) => return colors.clone(),
+        };
+        preset.iter().map(|c| c.to_string()).collect()
+    }
+
+    /// Color at `t` (0.0 for the lowest value, 1.0 for the highest)
+    pub fn color_at(&self, t: f64) -> String {
+        let stops: Vec<RgbColor> = self.stops().iter().filter_map(|c| RgbColor::from_hex(c)).collect();
+        match stops.len() {
+            0 => return "FFFFFF".to_string(),
+            1 => return stops[0].to_hex(),
+            _ => {}
+        }
+        let scaled = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = (scaled.floor() as usize).min(stops.len() - 2);
+        let f = scaled - i as f64;
+        let (a, b) = (&stops[i], &stops[i + 1]);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        RgbColor::new(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b)).to_hex()
+    }
+}
+
+/// Table of values colored by magnitude
+///
+/// ```
+/// use ppt_rs::generator::{Heatmap, Palette, SlideContent};
+///
+/// let risks = Heatmap::from_matrix(vec![
+///     vec![1.0, 2.0, 3.0],
+///     vec![2.0, 4.0, 6.0],
+///     vec![3.0, 6.0, 9.0],
+/// ])
+/// .palette(Palette::GnYlRd)
+/// .row_labels(["Unlikely", "Possible", "Likely"])
+/// .column_labels(["Minor", "Moderate", "Severe"]);
+///
+/// let table = risks.to_table();
+/// assert_eq!((table.row_count(), table.column_count()), (4, 4));
+/// assert_eq!(table.rows[3].cells[3].background_color.as_deref(), Some("D73027"));
+///
+/// let slide = SlideContent::new("Risk Matrix").heatmap(&risks);
+/// assert!(slide.table.is_some());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Heatmap {
+    values: Vec<Vec<f64>>,
+    row_labels: Vec<String>,
+    column_labels: Vec<String>,
+    palette: Palette,
+    range: Option<(f64, f64)>,
+    show_values: bool,
+    decimals: Option<usize>,
+    legend: bool,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Heatmap {
+    /// Heatmap of `values`, one inner vector per row, filling the content area
+    pub fn from_matrix(values: Vec<Vec<f64>>) -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
+        Heatmap {
+            values,
+            row_labels: Vec::new(),
+            column_labels: Vec::new(),
+            palette: Palette::RdYlGn,
+            range: None,
+            show_values: true,
+            decimals: None,
+            legend: true,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Labels in a first column, one per row
+    pub fn row_labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.row_labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Labels in a header row, one per column
+    pub fn column_labels<S: Into<String>>(mut self, labels: impl IntoIterator<Item = S>) -> Self {
+        self.column_labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Map colors over `min`..`max` instead of the matrix's own range
+    ///
+    /// Useful to compare several heatmaps, or to center a diverging palette
+    /// (e.g. -1..1 for correlations). Values outside are clamped.
+    pub fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Show each value in its cell (on by default)
+    pub fn show_values(mut self, show: bool) -> Self {
+        self.show_values = show;
+        self
+    }
+
+    /// Format values with a fixed number of decimals instead of as written
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Draw the color legend under the table (on by default)
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    pub fn position(mut self, x: u32, y: u32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    /// Set the size of the table and legend together
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Lowest and highest value colors are mapped over
+    pub fn value_range(&self) -> (f64, f64) {
+        self.range.unwrap_or_else(|| {
+            let finite = self.values.iter().flatten().copied().filter(|v| v.is_finite());
+            finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)))
+        })
+    }
+
+    /// Background color for `value`
+    pub fn color_for(&self, value: f64) -> String {
+        let (min, max) = self.value_range();
+        let t = if max > min { (value - min) / (max - min) } else { 0.5 };
+        self.palette.color_at(t)
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self.decimals {
+            Some(decimals) => format!("{value:.decimals$}"),
+            None => value.to_string(),
+        }
+    }
+
+    /// The matrix as a table, with the labels in a header row and first column
+    ///
+    /// Short rows are padded with blank cells; non-finite values are blank too.
+    pub fn to_table(&self) -> Table {
+        let columns = self.values.iter().map(Vec::len).chain([self.column_labels.len()]).max().unwrap_or(0);
+        let label_column = !self.row_labels.is_empty();
+        let header = !self.column_labels.is_empty();
+
+        let mut rows = Vec::new();
+        if header {
+            let mut cells: Vec<TableCell> = Vec::new();
+            if label_column {
+                cells.push(TableCell::new(""));
+            }
+            cells.extend((0..columns).map(|c| {
+                TableCell::new(self.column_labels.get(c).map_or("", String::as_str)).bold().align_center()
+            }));
+            rows.push(TableRow::new(cells));
+        }
+        let row_count = self.values.len().max(self.row_labels.len());
+        for r in 0..row_count {
+            let mut cells = Vec::new();
+            if label_column {
+                cells.push(TableCell::new(self.row_labels.get(r).map_or("", String::as_str)).bold());
+            }
+            cells.extend((0..columns).map(|c| {
+                match self.values.get(r).and_then(|row| row.get(c)).filter(|v| v.is_finite()) {
+                    Some(&value) => {
+                        let fill = self.color_for(value);
+                        let text = if self.show_values { self.format(value) } else { String::new() };
+                        TableCell::new(&text).align_center().text_color(get_text_color(Some(&fill))).background_color(fill.as_str())
+                    }
+                    None => TableCell::new(""),
+                }
+            }));
+            rows.push(TableRow::new(cells));
+        }
+
+        let total_columns = columns + label_column as usize;
+        let height = if self.legend { self.height.saturating_sub(LEGEND_HEIGHT) } else { self.height };
+        let row_height = height / rows.len().max(1) as u32;
+        let rows = rows.into_iter().map(|row| row.with_height(row_height)).collect();
+        Table::new(rows, vec![self.width / total_columns.max(1) as u32; total_columns], self.x, self.y)
+    }
+
+    /// Color bar with the lowest value on its left and the highest on its right
+    ///
+    /// Empty if the legend is off or the matrix has no finite values.
+    pub fn legend_shapes(&self) -> Vec<Shape> {
+        let (min, max) = self.value_range();
+        if !self.legend || !min.is_finite() || !max.is_finite() {
+            return Vec::new();
+        }
+        let stops = self.palette.stops();
+        let last = stops.len().saturating_sub(1).max(1) as u32;
+        let gradient = GradientFill {
+            stops: stops.iter().enumerate().map(|(i, color)| GradientStop::new(color.as_str(), i as u32 * 100 / last)).collect(),
+            direction: GradientDirection::Horizontal,
+        };
+
+        let y = self.y + self.height.saturating_sub(LEGEND_HEIGHT) + LEGEND_HEIGHT / 4;
+        let bar_x = self.x + LEGEND_LABEL_WIDTH;
+        let label = |text: String, x: u32| {
+            Shape::new(ShapeType::Rectangle, x, y, LEGEND_LABEL_WIDTH, LEGEND_HEIGHT / 2)
+                .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+                .with_rich_text(vec![FormattedText::new(&text).color("595959").font_size(LEGEND_FONT_SIZE)])
+                .with_name("Heatmap Legend Label")
+        };
+        vec![
+            label(self.format(min), self.x),
+            Shape::new(ShapeType::Rectangle, bar_x, y, LEGEND_BAR_WIDTH, LEGEND_HEIGHT / 2)
+                .with_gradient(gradient)
+                .with_name("Heatmap Legend"),
+            label(self.format(max), bar_x + LEGEND_BAR_WIDTH),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_interpolation() {
+        assert_eq!(Palette::RdYlGn.color_at(0.0), "D73027");
+        assert_eq!(Palette::RdYlGn.color_at(1.0), "1A9850");
+        assert_eq!(Palette::custom(["000000", "FFFFFF"]).color_at(0.5), "808080");
+        assert_eq!(Palette::Blues.color_at(7.0), "08306B");
+    }
+
+    #[test]
+    fn test_cells_colored_and_labelled() {
+        let table = Heatmap::from_matrix(vec![vec![-1.0, 0.0], vec![1.0, f64::NAN]])
+            .palette(Palette::RdBu)
+            .decimals(2)
+            .to_table();
+        assert_eq!(table.row_count(), 2);
+        let cell = &table.rows[0].cells[0];
+        assert_eq!((cell.text.as_str(), cell.background_color.as_deref()), ("-1.00", Some("B2182B")));
+        assert_eq!(cell.text_color.as_deref(), Some("FFFFFF"));
+        assert_eq!(table.rows[0].cells[1].text_color.as_deref(), Some("000000"));
+        assert_eq!(table.rows[1].cells[1].background_color, None);
+    }
+
+    #[test]
+    fn test_legend_under_table() {
+        let heatmap = Heatmap::from_matrix(vec![vec![0.5, 2.0]]).position(0, 0).size(4_000_000, 2_000_000);
+        let table = heatmap.to_table();
+        assert_eq!(table.height(), 2_000_000 - LEGEND_HEIGHT);
+        let legend = heatmap.legend_shapes();
+        assert_eq!(legend.len(), 3);
+        assert!(legend[1].gradient.is_some());
+        assert!(legend[1].y.0 >= table.height() as i64);
+        assert_eq!(legend[2].rich_text[0].text, "2");
+        assert!(heatmap.legend(false).legend_shapes().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 //! Hyperlink support for PPTX elements
 //!
-//! Provides hyperlink types for shapes, text, and images.
+//! Provides hyperlink types for shapes, text, and images, including the
+//! slideshow action settings (jump to slide, end show, run program, play
+//! sound) that PowerPoint stores as `a:hlinkClick`/`a:hlinkHover`.
 
-use crate::core::escape_xml;
+use crate::core::escape_xml_attr;
 
 /// Hyperlink action types
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -25,6 +27,10 @@ pub enum HyperlinkAction {
     Email { address: String, subject: Option<String> },
     /// Link to file
     File(String),
+    /// Run an external program
+    RunProgram(String),
+    /// No navigation; only play the hyperlink's sound
+    PlaySound,
 }
 
 impl HyperlinkAction {
@@ -59,6 +65,47 @@ impl HyperlinkAction {
         HyperlinkAction::File(path.to_string())
     }
 
+    /// Create run-program action
+    pub fn run_program(path: &str) -> Self {
+        HyperlinkAction::RunProgram(path.to_string())
+    }
+
+    /// Parse an action from its `ppaction://` string and relationship target
+    ///
+    /// `target` is the hyperlink's relationship target, if it has one.
+    /// Returns `None` for actions this module does not model (macros, OLE verbs).
+    pub fn from_ppaction(action: Option<&str>, target: Option<&str>) -> Option<Self> {
+        match action.unwrap_or("") {
+            "ppaction://hlinkshowjump?jump=firstslide" => Some(HyperlinkAction::FirstSlide),
+            "ppaction://hlinkshowjump?jump=lastslide" => Some(HyperlinkAction::LastSlide),
+            "ppaction://hlinkshowjump?jump=nextslide" => Some(HyperlinkAction::NextSlide),
+            "ppaction://hlinkshowjump?jump=previousslide" => Some(HyperlinkAction::PreviousSlide),
+            "ppaction://hlinkshowjump?jump=endshow" => Some(HyperlinkAction::EndShow),
+            "ppaction://noaction" => Some(HyperlinkAction::PlaySound),
+            "ppaction://hlinksldjump" => {
+                let file = target?.rsplit('/').next()?;
+                let num = file.strip_prefix("slide")?.strip_suffix(".xml")?;
+                num.parse().ok().map(HyperlinkAction::Slide)
+            }
+            "ppaction://program" => Some(HyperlinkAction::RunProgram(file_path(target?))),
+            "" => {
+                let target = target?;
+                if let Some(mailto) = target.strip_prefix("mailto:") {
+                    let (address, subject) = match mailto.split_once("?subject=") {
+                        Some((address, subject)) => (address, Some(subject.to_string())),
+                        None => (mailto, None),
+                    };
+                    Some(HyperlinkAction::Email { address: address.to_string(), subject })
+                } else if target.starts_with("file:") {
+                    Some(HyperlinkAction::File(file_path(target)))
+                } else {
+                    Some(HyperlinkAction::Url(target.to_string()))
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Get the relationship target for this action
     pub fn relationship_target(&self) -> String {
         match self {
@@ -76,7 +123,10 @@ impl HyperlinkAction {
                 }
                 mailto
             }
-            HyperlinkAction::File(path) => format!("file:///{}", path.replace('\\', "/")),
+            HyperlinkAction::File(path) | HyperlinkAction::RunProgram(path) => {
+                format!("file:///{}", path.replace('\\', "/"))
+            }
+            HyperlinkAction::PlaySound => String::new(),
         }
     }
 
@@ -84,10 +134,18 @@ impl HyperlinkAction {
     pub fn is_external(&self) -> bool {
         matches!(
             self,
-            HyperlinkAction::Url(_) | HyperlinkAction::Email { .. } | HyperlinkAction::File(_)
+            HyperlinkAction::Url(_)
+                | HyperlinkAction::Email { .. }
+                | HyperlinkAction::File(_)
+                | HyperlinkAction::RunProgram(_)
         )
     }
 
+    /// Check if this action references a relationship (external target or slide)
+    pub fn needs_relationship(&self) -> bool {
+        self.is_external() || matches!(self, HyperlinkAction::Slide(_))
+    }
+
     /// Get the action type for internal links
     pub fn action_type(&self) -> Option<&'static str> {
         match self {
@@ -96,11 +154,51 @@ impl HyperlinkAction {
             HyperlinkAction::NextSlide => Some("ppaction://hlinkshowjump?jump=nextslide"),
             HyperlinkAction::PreviousSlide => Some("ppaction://hlinkshowjump?jump=previousslide"),
             HyperlinkAction::EndShow => Some("ppaction://hlinkshowjump?jump=endshow"),
+            HyperlinkAction::Slide(_) => Some("ppaction://hlinksldjump"),
+            HyperlinkAction::RunProgram(_) => Some("ppaction://program"),
+            HyperlinkAction::PlaySound => Some("ppaction://noaction"),
             _ => None,
         }
     }
 }
 
+/// Strip the `file:` scheme from a relationship target
+fn file_path(target: &str) -> String {
+    target
+        .strip_prefix("file:///")
+        .or_else(|| target.strip_prefix("file:"))
+        .unwrap_or(target)
+        .to_string()
+}
+
+/// Sound played when a hyperlink or action is triggered (WAV data)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionSound {
+    /// Display name, e.g. "chime.wav"
+    pub name: String,
+    /// WAV file contents
+    pub data: Vec<u8>,
+}
+
+impl ActionSound {
+    /// Create a sound from WAV data
+    pub fn new(name: &str, data: Vec<u8>) -> Self {
+        ActionSound {
+            name: name.to_string(),
+            data,
+        }
+    }
+}
+
+/// Relationship IDs used by one hyperlink (set during package generation)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ActionRelIds {
+    /// Hyperlink or slide relationship, for actions that need one
+    pub link: Option<String>,
+    /// Embedded sound relationship
+    pub sound: Option<String>,
+}
+
 /// Hyperlink definition
 #[derive(Clone, Debug)]
 pub struct Hyperlink {
@@ -112,6 +210,10 @@ pub struct Hyperlink {
     pub highlight_click: bool,
     /// Relationship ID (set during XML generation)
     pub r_id: Option<String>,
+    /// Sound played when triggered
+    pub sound: Option<ActionSound>,
+    /// Sound relationship ID (set during XML generation)
+    pub sound_r_id: Option<String>,
 }
 
 impl Hyperlink {
@@ -122,6 +224,8 @@ impl Hyperlink {
             tooltip: None,
             highlight_click: true,
             r_id: None,
+            sound: None,
+            sound_r_id: None,
         }
     }
 
@@ -140,6 +244,27 @@ impl Hyperlink {
         Self::new(HyperlinkAction::email(address))
     }
 
+    /// Create end-show action
+    pub fn end_show() -> Self {
+        Self::new(HyperlinkAction::EndShow)
+    }
+
+    /// Create run-program action
+    pub fn run_program(path: &str) -> Self {
+        Self::new(HyperlinkAction::run_program(path))
+    }
+
+    /// Create an action that only plays a sound
+    pub fn play_sound(sound: ActionSound) -> Self {
+        Self::new(HyperlinkAction::PlaySound).with_sound(sound)
+    }
+
+    /// Play a sound when the hyperlink is triggered
+    pub fn with_sound(mut self, sound: ActionSound) -> Self {
+        self.sound = Some(sound);
+        self
+    }
+
     /// Set tooltip
     pub fn with_tooltip(mut self, tooltip: &str) -> Self {
         self.tooltip = Some(tooltip.to_string());
@@ -157,6 +282,22 @@ impl Hyperlink {
         self.r_id = Some(r_id.to_string());
         self
     }
+
+    /// Apply relationship IDs assigned by the package builder
+    pub fn with_rel_ids(mut self, rel_ids: &ActionRelIds) -> Self {
+        if let Some(link) = &rel_ids.link {
+            self.r_id = Some(link.clone());
+        }
+        if let Some(sound) = &rel_ids.sound {
+            self.sound_r_id = Some(sound.clone());
+        }
+        self
+    }
+
+    /// Check if the hyperlink can be written: its relationship (if any) is assigned
+    pub fn is_resolved(&self) -> bool {
+        self.r_id.is_some() || !self.action.needs_relationship()
+    }
 }
 
 /// Generate hyperlink XML for text run
@@ -164,7 +305,7 @@ pub fn generate_text_hyperlink_xml(hyperlink: &Hyperlink, r_id: &str) -> String
     let mut xml = format!(r#"<a:hlinkClick r:id="{}""#, r_id);
 
     if let Some(tooltip) = &hyperlink.tooltip {
-        xml.push_str(&format!(r#" tooltip="{}""#, escape_xml(tooltip)));
+        xml.push_str(&format!(r#" tooltip="{}""#, escape_xml_attr(tooltip)));
     }
 
     if hyperlink.highlight_click {
@@ -182,10 +323,19 @@ pub fn generate_text_hyperlink_xml(hyperlink: &Hyperlink, r_id: &str) -> String
 
 /// Generate hyperlink XML for shape
 pub fn generate_shape_hyperlink_xml(hyperlink: &Hyperlink, r_id: &str) -> String {
-    let mut xml = format!(r#"<a:hlinkClick r:id="{}""#, r_id);
+    generate_shape_action_xml("hlinkClick", hyperlink, r_id)
+}
+
+/// Generate hover action XML for shape
+pub fn generate_shape_hover_xml(hyperlink: &Hyperlink, r_id: &str) -> String {
+    generate_shape_action_xml("hlinkHover", hyperlink, r_id)
+}
+
+fn generate_shape_action_xml(element: &str, hyperlink: &Hyperlink, r_id: &str) -> String {
+    let mut xml = format!(r#"<a:{} r:id="{}""#, element, r_id);
 
     if let Some(tooltip) = &hyperlink.tooltip {
-        xml.push_str(&format!(r#" tooltip="{}""#, escape_xml(tooltip)));
+        xml.push_str(&format!(r#" tooltip="{}""#, escape_xml_attr(tooltip)));
     }
 
     if hyperlink.highlight_click {
@@ -196,7 +346,17 @@ pub fn generate_shape_hyperlink_xml(hyperlink: &Hyperlink, r_id: &str) -> String
         xml.push_str(&format!(r#" action="{}""#, action));
     }
 
-    xml.push_str("/>");
+    match (&hyperlink.sound, &hyperlink.sound_r_id) {
+        (Some(sound), Some(sound_r_id)) => {
+            xml.push_str(&format!(
+                r#"><a:snd r:embed="{}" name="{}"/></a:{}>"#,
+                sound_r_id,
+                escape_xml_attr(&sound.name),
+                element
+            ));
+        }
+        _ => xml.push_str("/>"),
+    }
     xml
 }
 
@@ -208,11 +368,16 @@ pub fn generate_hyperlink_relationship_xml(hyperlink: &Hyperlink, r_id: &str) ->
     } else {
         ""
     };
+    let rel_type = match hyperlink.action {
+        HyperlinkAction::Slide(_) => "slide",
+        _ => "hyperlink",
+    };
 
     format!(
-        r#"<Relationship Id="{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="{}"{}/>"#,
+        r#"<Relationship Id="{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{}" Target="{}"{}/>"#,
         r_id,
-        escape_xml(&target),
+        rel_type,
+        escape_xml_attr(&target),
         target_mode
     )
 }
@@ -278,6 +443,52 @@ mod tests {
         assert!(xml.contains("External"));
     }
 
+    #[test]
+    fn test_slide_jump_uses_slide_relationship() {
+        let link = Hyperlink::slide(4);
+        assert_eq!(link.action.action_type(), Some("ppaction://hlinksldjump"));
+        let rel = generate_hyperlink_relationship_xml(&link, "rId7");
+        assert!(rel.contains(r#"relationships/slide" Target="slide4.xml""#));
+        assert!(!rel.contains("External"));
+    }
+
+    #[test]
+    fn test_action_settings_xml() {
+        let end = Hyperlink::end_show();
+        assert!(end.is_resolved());
+        let xml = generate_shape_hyperlink_xml(&end, "");
+        assert!(xml.contains(r#"action="ppaction://hlinkshowjump?jump=endshow""#));
+
+        let program = Hyperlink::run_program(r"C:\Tools\demo.exe");
+        assert!(!program.is_resolved());
+        assert_eq!(program.action.relationship_target(), "file:///C:/Tools/demo.exe");
+
+        let sound = Hyperlink::play_sound(ActionSound::new("chime.wav", vec![0; 4]))
+            .with_rel_ids(&ActionRelIds { link: None, sound: Some("rId9".to_string()) });
+        let xml = generate_shape_hover_xml(&sound, "");
+        assert!(xml.starts_with(r#"<a:hlinkHover r:id="""#));
+        assert!(xml.contains(r#"<a:snd r:embed="rId9" name="chime.wav"/></a:hlinkHover>"#));
+    }
+
+    #[test]
+    fn test_from_ppaction_round_trip() {
+        let actions = vec![
+            HyperlinkAction::EndShow,
+            HyperlinkAction::NextSlide,
+            HyperlinkAction::Slide(3),
+            HyperlinkAction::run_program("C:/Tools/demo.exe"),
+            HyperlinkAction::url("https://example.com"),
+            HyperlinkAction::email_with_subject("a@example.com", "Hi"),
+            HyperlinkAction::PlaySound,
+        ];
+        for action in actions {
+            let target = action.needs_relationship().then(|| action.relationship_target());
+            let parsed = HyperlinkAction::from_ppaction(action.action_type(), target.as_deref());
+            assert_eq!(parsed, Some(action));
+        }
+        assert_eq!(HyperlinkAction::from_ppaction(Some("ppaction://macro?name=Go"), None), None);
+    }
+
     #[test]
     fn test_email_with_subject() {
         let action = HyperlinkAction::email_with_subject("test@example.com", "Hello");
@@ -0,0 +1,208 @@
+//! Built-in vector icons
+//!
+//! A small set of icons drawn as custom-geometry shapes, so status and
+//! dashboard slides don't need image files. They scale without blurring and
+//! take any fill color:
+//!
+//! ```
+//! use ppt_rs::core::Inches;
+//! use ppt_rs::generator::{Icon, SlideContent};
+//!
+//! let warning = Icon::named("warning").unwrap()
+//!     .color("#E65100")
+//!     .size(Inches(0.5))
+//!     .position(Inches(1.0), Inches(2.0));
+//! let slide = SlideContent::new("Status").add_icon(warning);
+//! assert!(slide.shapes[0].geometry.is_some());
+//! ```
+
+use crate::core::Emu;
+use crate::elements::ColorInput;
+use crate::exc::{PptxError, Result};
+use super::geometry::{CustomGeometry, GeometryPath};
+use super::shapes::{Shape, ShapeFill, ShapeType};
+
+/// Icons are drawn on a 24 × 24 grid, in hundredths
+const GRID: i64 = 2400;
+/// Default icon size (0.5 inch)
+const DEFAULT_SIZE: u32 = 457200;
+/// Control point distance for a quarter circle, relative to the radius
+const KAPPA: f64 = 0.552_284_7;
+
+/// Names accepted by [`Icon::named`]
+pub const ICON_NAMES: [&str; 5] = ["check", "warning", "gear", "people", "cloud"];
+
+/// A built-in icon
+#[derive(Clone, Debug)]
+pub struct Icon {
+    name: &'static str,
+    x: Emu,
+    y: Emu,
+    size: u32,
+    color: String,
+}
+
+impl Icon {
+    /// Look up an icon by name (see [`ICON_NAMES`])
+    pub fn named(name: &str) -> Result<Self> {
+        let name = ICON_NAMES.iter().find(|n| n.eq_ignore_ascii_case(name)).ok_or_else(|| {
+            PptxError::InvalidValue(format!("unknown icon '{name}' (expected one of {})", ICON_NAMES.join(", ")))
+        })?;
+        Ok(Icon { name, x: Emu::ZERO, y: Emu::ZERO, size: DEFAULT_SIZE, color: "404040".to_string() })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = color.into().to_hex();
+        self
+    }
+
+    /// Set the width and height; a negative size is 0
+    pub fn size(mut self, size: impl Into<Emu>) -> Self {
+        self.size = size.into().to_u32_saturating();
+        self
+    }
+
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    /// Outline of the icon on its 24 × 24 grid
+    pub fn geometry(&self) -> CustomGeometry {
+        let path = match self.name {
+            "check" => check(),
+            "warning" => warning(),
+            "gear" => gear(),
+            "people" => people(),
+            _ => cloud(),
+        };
+        CustomGeometry::new(GRID, GRID).path(path)
+    }
+
+    /// Filled shape named after the icon
+    pub fn to_shape(&self) -> Shape {
+        Shape::new(ShapeType::Rectangle, self.x, self.y, self.size, self.size)
+            .with_geometry(self.geometry())
+            .with_fill(ShapeFill::new(self.color.as_str()))
+            .with_name(&format!("Icon: {}", self.name))
+    }
+}
+
+/// Grid point from 24 × 24 coordinates
+fn p(x: f64, y: f64) -> (i64, i64) {
+    ((x * 100.0).round() as i64, (y * 100.0).round() as i64)
+}
+
+fn polygon(path: GeometryPath, points: &[(f64, f64)]) -> GeometryPath {
+    let points: Vec<_> = points.iter().map(|&(x, y)| p(x, y)).collect();
+    path.polygon(&points)
+}
+
+/// Circle as four Bézier quarters; `clockwise` on screen, or the other way to cut a hole
+fn circle(path: GeometryPath, cx: f64, cy: f64, r: f64, clockwise: bool) -> GeometryPath {
+    let k = r * KAPPA;
+    let s = if clockwise { 1.0 } else { -1.0 };
+    let top = p(cx, cy - r);
+    // Start at the top and sweep through right (or left), bottom, left (or right)
+    path.move_to(top.0, top.1)
+        .cubic_to(p(cx + s * k, cy - r), p(cx + s * r, cy - k), p(cx + s * r, cy))
+        .cubic_to(p(cx + s * r, cy + k), p(cx + s * k, cy + r), p(cx, cy + r))
+        .cubic_to(p(cx - s * k, cy + r), p(cx - s * r, cy + k), p(cx - s * r, cy))
+        .cubic_to(p(cx - s * r, cy - k), p(cx - s * k, cy - r), top)
+        .close()
+}
+
+fn check() -> GeometryPath {
+    polygon(GeometryPath::new(), &[(2.5, 12.5), (5.0, 10.0), (9.5, 14.5), (19.0, 5.0), (21.5, 7.5), (9.5, 19.5)])
+}
+
+/// Triangle with the exclamation mark cut out
+fn warning() -> GeometryPath {
+    let path = polygon(GeometryPath::new(), &[(12.0, 2.0), (23.0, 21.5), (1.0, 21.5)]);
+    let path = polygon(path, &[(11.0, 8.5), (11.0, 15.0), (13.0, 15.0), (13.0, 8.5)]);
+    polygon(path, &[(11.0, 17.0), (11.0, 19.0), (13.0, 19.0), (13.0, 17.0)])
+}
+
+/// Eight-tooth gear with a round hole
+fn gear() -> GeometryPath {
+    let mut points = Vec::new();
+    for tooth in 0..8 {
+        let center = tooth as f64 * 45.0;
+        for (offset, radius) in [(-16.0, 8.0), (-8.0, 11.0), (8.0, 11.0), (16.0, 8.0)] {
+            let angle = (center + offset).to_radians();
+            points.push((12.0 + radius * angle.sin(), 12.0 - radius * angle.cos()));
+        }
+    }
+    circle(polygon(GeometryPath::new(), &points), 12.0, 12.0, 3.5, false)
+}
+
+/// Two head-and-shoulders figures side by side
+fn people() -> GeometryPath {
+    let path = circle(GeometryPath::new(), 8.0, 7.0, 3.5, true);
+    let (a, b) = (p(1.0, 20.0), p(15.0, 20.0));
+    let path = path.move_to(a.0, a.1)
+        .cubic_to(p(1.0, 14.0), p(4.0, 12.0), p(8.0, 12.0))
+        .cubic_to(p(12.0, 12.0), p(15.0, 14.0), b)
+        .close();
+    let path = circle(path, 18.0, 8.5, 2.8, true);
+    let (a, b) = (p(16.0, 20.0), p(23.0, 20.0));
+    path.move_to(a.0, a.1)
+        .cubic_to(p(16.0, 16.0), p(16.5, 13.0), p(19.0, 13.0))
+        .cubic_to(p(22.0, 13.0), p(23.0, 16.0), b)
+        .close()
+}
+
+fn cloud() -> GeometryPath {
+    let start = p(6.0, 19.0);
+    let end = p(18.0, 19.0);
+    GeometryPath::new()
+        .move_to(start.0, start.1)
+        .line_to(end.0, end.1)
+        .cubic_to(p(20.8, 19.0), p(23.0, 16.8), p(23.0, 14.0))
+        .cubic_to(p(23.0, 11.4), p(21.0, 9.3), p(18.5, 9.1))
+        .cubic_to(p(17.7, 5.6), p(14.6, 3.0), p(11.0, 3.0))
+        .cubic_to(p(7.1, 3.0), p(3.9, 6.0), p(3.5, 9.8))
+        .cubic_to(p(1.5, 10.7), p(0.5, 12.6), p(0.5, 14.5))
+        .cubic_to(p(0.5, 17.0), p(2.9, 19.0), start)
+        .close()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::geometry::PathCommand;
+
+    #[test]
+    fn test_every_icon_stays_on_its_grid() {
+        for name in ICON_NAMES {
+            let geometry = Icon::named(name).unwrap().geometry();
+            let points = geometry.paths.iter().flat_map(|path| &path.commands).flat_map(|c| match *c {
+                PathCommand::MoveTo(x, y) | PathCommand::LineTo(x, y) => vec![(x, y)],
+                PathCommand::CubicTo(a, b, c) => vec![a, b, c],
+                PathCommand::Close => vec![],
+            });
+            for (x, y) in points {
+                assert!((0..=GRID).contains(&x) && (0..=GRID).contains(&y), "{name}: ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_icon_shape() {
+        let shape = Icon::named("Gear").unwrap().color("#1565C0").size(914400).position(100, 200).to_shape();
+        assert_eq!(shape.name.as_deref(), Some("Icon: gear"));
+        assert_eq!(shape.fill.unwrap().color, "1565C0");
+        assert_eq!((shape.x.0, shape.y.0, shape.width.0), (100, 200, 914400));
+    }
+
+    #[test]
+    fn test_unknown_icon() {
+        let error = Icon::named("rocket").unwrap_err().to_string();
+        assert!(error.contains("unknown icon 'rocket'"), "{error}");
+    }
+}
@@ -0,0 +1,215 @@
+//! Tiled photo-grid slides
+//!
+//! [`ImageGrid`] lays any number of images out in equal cells separated by
+//! a uniform gutter, each fitted to its cell (cropped to fill by default)
+//! with an optional caption underneath. Add it to a slide with
+//! [`SlideContent::image_grid`](super::SlideContent::image_grid).
+
+use crate::core::Emu;
+use super::images::{Fit, Image};
+use super::shapes::{Shape, ShapeType};
+use super::slide_content::Region;
+use super::text::FormattedText;
+
+/// Default space between cells (0.1 inch)
+const DEFAULT_GUTTER: u32 = 91440;
+/// Height of the caption strip under each image (0.3 inch)
+const CAPTION_HEIGHT: u32 = 274320;
+/// Caption font size in points
+const CAPTION_SIZE: u32 = 12;
+/// Aspect ratio assumed for images without a size
+const DEFAULT_RATIO: f64 = 4.0 / 3.0;
+
+/// Grid of images with optional captions
+///
+/// ```
+/// use ppt_rs::generator::{Image, ImageGrid, SlideContent};
+///
+/// let grid = ImageGrid::new()
+///     .image(Image::new("team.jpg", 400, 300, "jpg"))
+///     .captioned(Image::new("launch.jpg", 400, 300, "jpg"), "Launch day")
+///     .image(Image::new("offsite.jpg", 400, 300, "jpg"))
+///     .image(Image::new("award.jpg", 400, 300, "jpg"));
+/// assert_eq!(grid.dimensions(), (2, 2));
+///
+/// let slide = SlideContent::new("Our year").image_grid(grid);
+/// assert_eq!(slide.images.len(), 4);
+/// assert_eq!(slide.shapes.len(), 1); // the caption
+/// ```
+#[derive(Clone, Debug)]
+pub struct ImageGrid {
+    cells: Vec<(Image, Option<String>)>,
+    columns: Option<usize>,
+    gutter: u32,
+    region: Region,
+    fit: Fit,
+}
+
+impl Default for ImageGrid {
+    fn default() -> Self {
+        ImageGrid {
+            cells: Vec::new(),
+            columns: None,
+            gutter: DEFAULT_GUTTER,
+            region: Region::Content,
+            fit: Fit::Cover,
+        }
+    }
+}
+
+impl ImageGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an image without a caption
+    pub fn image(mut self, image: Image) -> Self {
+        self.cells.push((image, None));
+        self
+    }
+
+    /// Add an image with a caption under it
+    pub fn captioned(mut self, image: Image, caption: &str) -> Self {
+        self.cells.push((image, Some(caption.to_string())));
+        self
+    }
+
+    /// Use exactly `columns` columns instead of choosing them
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Space between cells in EMU
+    pub fn gutter(mut self, gutter: u32) -> Self {
+        self.gutter = gutter;
+        self
+    }
+
+    /// Area the grid fills (the content area by default)
+    pub fn region(mut self, region: Region) -> Self {
+        self.region = region;
+        self
+    }
+
+    /// How images fill their cells (`Fit::Cover` by default)
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Number of images
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// `(columns, rows)` of the grid
+    ///
+    /// Without fixed columns, picks the count whose cells are closest to the
+    /// images' average aspect ratio, so the least is cropped, while avoiding
+    /// empty cells in the last row.
+    pub fn dimensions(&self) -> (usize, usize) {
+        let count = self.cells.len().max(1);
+        let columns = self.columns.unwrap_or_else(|| {
+            let ratio = self.average_ratio();
+            (1..=count)
+                .map(|columns| {
+                    let rows = count.div_ceil(columns);
+                    let (width, height) = self.cell_size(columns, rows);
+                    let mismatch = (width as f64 / height.max(1) as f64 / ratio).ln().abs();
+                    let empty = (columns * rows - count) as f64 / count as f64;
+                    (columns, mismatch + empty)
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map_or(1, |(columns, _)| columns)
+        });
+        let columns = columns.min(count);
+        (columns, count.div_ceil(columns))
+    }
+
+    /// Positioned images and caption shapes, in reading order
+    pub fn layout(&self) -> (Vec<Image>, Vec<Shape>) {
+        let (columns, rows) = self.dimensions();
+        let (x, y, _, _) = self.region.bounds();
+        let (cell_width, cell_height) = self.cell_size(columns, rows);
+        let captioned = self.cells.iter().any(|(_, caption)| caption.is_some());
+        let caption_height = if captioned { CAPTION_HEIGHT.min(cell_height / 2) } else { 0 };
+        let image_height = cell_height - caption_height;
+
+        let mut images = Vec::with_capacity(self.cells.len());
+        let mut shapes = Vec::new();
+        for (i, (image, caption)) in self.cells.iter().enumerate() {
+            let cell_x = x + (i % columns) as u32 * (cell_width + self.gutter);
+            let cell_y = y + (i / columns) as u32 * (cell_height + self.gutter);
+            images.push(image.clone().position(cell_x, cell_y).fit(self.fit, cell_width, image_height));
+            if let Some(caption) = caption {
+                shapes.push(
+                    Shape::new(ShapeType::Rectangle, cell_x, cell_y + image_height, cell_width, caption_height)
+                        .with_name(&format!("Caption {}", i + 1))
+                        .with_rich_text(vec![FormattedText::new(caption).font_size(CAPTION_SIZE)]),
+                );
+            }
+        }
+        (images, shapes)
+    }
+
+    /// Width and height of each cell for a `columns` × `rows` grid
+    fn cell_size(&self, columns: usize, rows: usize) -> (u32, u32) {
+        let (_, _, width, height) = self.region.bounds();
+        let gaps = |n: usize| self.gutter.saturating_mul(n.saturating_sub(1) as u32);
+        (
+            width.saturating_sub(gaps(columns)) / columns.max(1) as u32,
+            height.saturating_sub(gaps(rows)) / rows.max(1) as u32,
+        )
+    }
+
+    fn average_ratio(&self) -> f64 {
+        let ratios: Vec<f64> = self.cells.iter()
+            .filter(|(image, _)| image.width > Emu::ZERO && image.height > Emu::ZERO)
+            .map(|(image, _)| image.aspect_ratio())
+            .collect();
+        if ratios.is_empty() {
+            DEFAULT_RATIO
+        } else {
+            ratios.iter().sum::<f64>() / ratios.len() as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn photos(count: usize, width: u32, height: u32) -> ImageGrid {
+        (0..count).fold(ImageGrid::new(), |grid, i| grid.image(Image::new(&format!("p{i}.jpg"), width, height, "jpg")))
+    }
+
+    #[test]
+    fn test_dimensions_follow_image_shape() {
+        assert_eq!(photos(1, 4, 3).dimensions(), (1, 1));
+        assert_eq!(photos(4, 4, 3).dimensions(), (2, 2));
+        assert_eq!(photos(6, 4, 3).dimensions(), (3, 2));
+        // Tall portraits sit side by side
+        assert_eq!(photos(3, 2, 3).dimensions(), (3, 1));
+        assert_eq!(photos(5, 4, 3).columns(5).dimensions(), (5, 1));
+        assert_eq!(ImageGrid::new().dimensions(), (1, 1));
+    }
+
+    #[test]
+    fn test_layout_uses_uniform_gutters() {
+        let region = Region::Custom { x: 0, y: 0, width: 1_000, height: 500 };
+        let grid = photos(3, 4, 3).region(region).gutter(100).columns(2).captioned(Image::new("last.jpg", 0, 0, "jpg"), "Last");
+        let (images, shapes) = grid.layout();
+
+        let boxes: Vec<_> = images.iter().map(|i| (i.x.0, i.y.0, i.width.0, i.height.0)).collect();
+        // 450 × 200 cells, each with a 100 EMU caption strip (capped at half the cell)
+        assert_eq!(boxes, [(0, 0, 450, 100), (550, 0, 450, 100), (0, 300, 450, 100), (550, 300, 450, 100)]);
+        assert!(images[0].crop.is_some());
+        assert_eq!(shapes.len(), 1);
+        assert_eq!((shapes[0].x.0, shapes[0].y.0, shapes[0].height.0), (550, 400, 100));
+    }
+}
@@ -4,6 +4,9 @@
 
 use std::path::Path;
 
+use crate::core::{Emu, Positioned, ElementSized};
+use crate::generator::locks::Locks;
+
 /// Image data source
 #[derive(Clone, Debug)]
 pub enum ImageSource {
@@ -43,14 +46,27 @@ pub enum ImageEffect {
     Reflection,
 }
 
+/// How an image is sized into a target box
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// Scale to fit inside the box, keeping the aspect ratio, and center it
+    Contain,
+    /// Fill the box, keeping the aspect ratio, and crop the overflow evenly
+    Cover,
+    /// Fill the box exactly, distorting the image if the ratios differ
+    Stretch,
+}
+
 /// Image metadata and properties
 #[derive(Clone, Debug)]
 pub struct Image {
     pub filename: String,
-    pub width: u32,      // in EMU
-    pub height: u32,     // in EMU
-    pub x: u32,          // Position X in EMU
-    pub y: u32,          // Position Y in EMU
+    pub width: Emu,
+    pub height: Emu,
+    /// Position X; negative values place the image partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the image partly above the slide
+    pub y: Emu,
     pub format: String,  // PNG, JPG, GIF, etc.
     /// Image data source (file path, base64, or bytes)
     pub source: Option<ImageSource>,
@@ -58,25 +74,107 @@ pub struct Image {
     pub crop: Option<Crop>,
     /// Image effects
     pub effects: Vec<ImageEffect>,
+    /// Editing locks (`a:picLocks`)
+    pub locks: Locks,
+    /// Box drawn instead when the image data cannot be read
+    pub placeholder: Option<ImagePlaceholder>,
+    /// Relationship ID of the embedded picture (set during XML generation)
+    pub r_id: Option<String>,
+}
+
+/// Box drawn in place of an image whose data cannot be read
+///
+/// ```
+/// use ppt_rs::generator::ImagePlaceholder;
+///
+/// let missing = ImagePlaceholder::new().icon("⚠").fill("FFF3E0").line_color("E65100");
+/// assert_eq!(missing.label("chart.png"), "⚠ chart.png");
+/// assert_eq!(missing.show_file_name(false).label("chart.png"), "⚠");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImagePlaceholder {
+    /// Text shown before the file name, e.g. an emoji
+    pub icon: String,
+    /// Fill color (RGB hex)
+    pub fill: String,
+    /// Outline color (RGB hex)
+    pub line_color: String,
+    /// Whether the image's file name is shown
+    pub show_file_name: bool,
+}
+
+impl Default for ImagePlaceholder {
+    fn default() -> Self {
+        ImagePlaceholder {
+            icon: "📷".to_string(),
+            fill: "E0E0E0".to_string(),
+            line_color: "808080".to_string(),
+            show_file_name: true,
+        }
+    }
+}
+
+impl ImagePlaceholder {
+    /// Grey box with a camera icon and the file name
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the icon (empty for none)
+    pub fn icon(mut self, icon: &str) -> Self {
+        self.icon = icon.to_string();
+        self
+    }
+
+    /// Set the fill color
+    pub fn fill(mut self, color: &str) -> Self {
+        self.fill = color.trim_start_matches('#').to_uppercase();
+        self
+    }
+
+    /// Set the outline color
+    pub fn line_color(mut self, color: &str) -> Self {
+        self.line_color = color.trim_start_matches('#').to_uppercase();
+        self
+    }
+
+    /// Show or hide the file name
+    pub fn show_file_name(mut self, show: bool) -> Self {
+        self.show_file_name = show;
+        self
+    }
+
+    /// Text drawn in the box for `filename`
+    pub fn label(&self, filename: &str) -> String {
+        match (self.icon.is_empty(), self.show_file_name) {
+            (_, false) => self.icon.clone(),
+            (true, true) => filename.to_string(),
+            (false, true) => format!("{} {filename}", self.icon),
+        }
+    }
 }
 
 impl Image {
     /// Create a new image
-    pub fn new(filename: &str, width: u32, height: u32, format: &str) -> Self {
+    pub fn new(filename: &str, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         Image {
             filename: filename.to_string(),
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format.to_uppercase(),
             source: Some(ImageSource::File(filename.to_string())),
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
         }
     }
 
     /// Create an image from a file path, automatically detecting dimensions
+    #[cfg(all(feature = "media", not(target_arch = "wasm32")))]
     pub fn from_path<P: AsRef<Path>>(path: P) -> std::result::Result<Self, String> {
         let path = path.as_ref();
         let filename = path.file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| "image.png".to_string());
@@ -99,14 +197,17 @@ impl Image {
         
         Ok(Image {
             filename,
-            width: w_emu,
-            height: h_emu,
-            x: 0,
-            y: 0,
+            width: w_emu.into(),
+            height: h_emu.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format,
             source: Some(ImageSource::File(path_str)),
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
         })
     }
     
@@ -114,17 +215,18 @@ impl Image {
     ///
     /// # Example
     /// ```rust
+    /// use ppt_rs::core::Emu;
     /// use ppt_rs::generator::Image;
     ///
     /// let base64_data = "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==";
     /// let img = Image::from_base64(base64_data, 100, 100, "PNG")
     ///     .position(1000000, 1000000);
     ///
-    /// assert_eq!(img.width, 100);
-    /// assert_eq!(img.height, 100);
+    /// assert_eq!(img.width, Emu(100));
+    /// assert_eq!(img.height, Emu(100));
     /// assert_eq!(img.format, "PNG");
     /// ```
-    pub fn from_base64(data: &str, width: u32, height: u32, format: &str) -> Self {
+    pub fn from_base64(data: &str, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         let format_upper = format.to_uppercase();
         let ext = match format_upper.as_str() {
             "JPEG" => "jpg",
@@ -134,19 +236,22 @@ impl Image {
         
         Image {
             filename,
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format_upper,
             source: Some(ImageSource::Base64(data.to_string())),
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
         }
     }
     
     /// Create an image from raw bytes
-    pub fn from_bytes(data: Vec<u8>, width: u32, height: u32, format: &str) -> Self {
+    pub fn from_bytes(data: Vec<u8>, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         let format_upper = format.to_uppercase();
         let ext = match format_upper.as_str() {
             "JPEG" => "jpg",
@@ -156,20 +261,60 @@ impl Image {
         
         Image {
             filename,
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format_upper,
             source: Some(ImageSource::Bytes(data)),
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
+        }
+    }
+
+    /// Create an image from any source, sized from its pixels (at 96 DPI)
+    ///
+    /// The size is 0 × 0 when the data cannot be read or decoded, e.g. a
+    /// missing file or a URL (which is only fetched when the deck is built);
+    /// [`Self::fit`] then fills its box. Without the `media` feature the
+    /// size is always 0 × 0.
+    pub fn from_source(filename: &str, source: ImageSource) -> Self {
+        let extension = Path::new(filename).extension().and_then(|ext| ext.to_str()).unwrap_or("png");
+        let mut image = Image::new(filename, 0, 0, extension);
+        image.source = Some(source);
+        #[cfg(feature = "media")]
+        image.read_size();
+        image
+    }
+
+    /// Set the format and size from the decoded image data
+    #[cfg(feature = "media")]
+    fn read_size(&mut self) {
+        let data = match &self.source {
+            #[cfg(feature = "web2ppt")]
+            Some(ImageSource::Url(_)) => None,
+            _ => self.get_bytes(),
+        };
+        let reader = data.as_deref()
+            .and_then(|data| ::image::io::Reader::new(std::io::Cursor::new(data)).with_guessed_format().ok());
+        if let Some(reader) = reader {
+            if let Some(format) = reader.format() {
+                self.format = format!("{format:?}").to_uppercase();
+            }
+            if let Ok((w, h)) = reader.into_dimensions() {
+                // 1 pixel = 9525 EMU
+                self.width = Emu::from(w) * 9525;
+                self.height = Emu::from(h) * 9525;
+            }
         }
     }
 
     /// Create an image from URL
     #[cfg(feature = "web2ppt")]
-    pub fn from_url(url: &str, width: u32, height: u32, format: &str) -> Self {
+    pub fn from_url(url: &str, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         let format_upper = format.to_uppercase();
         let ext = match format_upper.as_str() {
             "JPEG" => "jpg",
@@ -179,14 +324,17 @@ impl Image {
         
         Image {
             filename,
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format_upper,
             source: Some(ImageSource::Url(url.to_string())),
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
         }
     }
     
@@ -198,9 +346,13 @@ impl Image {
                 base64_decode(data).ok()
             }
             Some(ImageSource::Bytes(data)) => Some(data.clone()),
+            #[cfg(not(target_arch = "wasm32"))]
             Some(ImageSource::File(path)) => {
                 std::fs::read(path).ok()
             }
+            // No file system in the browser; use `Bytes` or `Base64` instead
+            #[cfg(target_arch = "wasm32")]
+            Some(ImageSource::File(_)) => None,
             #[cfg(feature = "web2ppt")]
             Some(ImageSource::Url(url)) => {
                 // Use blocking client to fetch image
@@ -225,10 +377,33 @@ impl Image {
         }
     }
 
+    /// Read the image data into memory so it can be embedded
+    ///
+    /// Returns `false`, and clears the source, when it cannot be read (or is
+    /// empty); the image is then drawn as its [`ImagePlaceholder`].
+    pub fn load(&mut self) -> bool {
+        if self.is_loaded() {
+            return true;
+        }
+        self.source = self.get_bytes().filter(|data| !data.is_empty()).map(ImageSource::Bytes);
+        self.is_loaded()
+    }
+
+    /// Whether the image data is in memory and will be embedded
+    pub fn is_loaded(&self) -> bool {
+        matches!(&self.source, Some(ImageSource::Bytes(data)) if !data.is_empty())
+    }
+
+    /// Set the box drawn if the image data cannot be read
+    pub fn with_placeholder(mut self, placeholder: ImagePlaceholder) -> Self {
+        self.placeholder = Some(placeholder);
+        self
+    }
+
     /// Set image position
-    pub fn position(mut self, x: u32, y: u32) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
         self
     }
 
@@ -238,6 +413,12 @@ impl Image {
         self
     }
 
+    /// Lock the image against selecting, moving, resizing, etc.
+    pub fn lock(mut self, locks: Locks) -> Self {
+        self.locks |= locks;
+        self
+    }
+
     /// Add an image effect
     pub fn with_effect(mut self, effect: ImageEffect) -> Self {
         self.effects.push(effect);
@@ -246,22 +427,77 @@ impl Image {
 
     /// Get aspect ratio
     pub fn aspect_ratio(&self) -> f64 {
-        self.width as f64 / self.height as f64
+        self.width.get() as f64 / self.height.get() as f64
     }
 
     /// Scale image to width while maintaining aspect ratio
-    pub fn scale_to_width(mut self, width: u32) -> Self {
+    pub fn scale_to_width(mut self, width: impl Into<Emu>) -> Self {
         let ratio = self.aspect_ratio();
-        self.width = width;
-        self.height = (width as f64 / ratio) as u32;
+        self.width = width.into();
+        self.height = Emu((self.width.get() as f64 / ratio) as i64);
         self
     }
 
     /// Scale image to height while maintaining aspect ratio
-    pub fn scale_to_height(mut self, height: u32) -> Self {
+    pub fn scale_to_height(mut self, height: impl Into<Emu>) -> Self {
         let ratio = self.aspect_ratio();
-        self.height = height;
-        self.width = (height as f64 * ratio) as u32;
+        self.height = height.into();
+        self.width = Emu((self.height.get() as f64 * ratio) as i64);
+        self
+    }
+
+    /// Size the image into a `box_width` × `box_height` box at its current position
+    ///
+    /// The current width and height give the aspect ratio. `Contain`
+    /// letterboxes by moving the image to the center of the box, and `Cover`
+    /// replaces any crop, so position and crop should be set first.
+    ///
+    /// ```
+    /// use ppt_rs::core::Emu;
+    /// use ppt_rs::generator::{Fit, Image};
+    ///
+    /// // 3:2 photo into a 3:1 banner: the middle half of its height shows
+    /// let img = Image::new("photo.jpg", 300, 200, "jpg").fit(Fit::Cover, 900, 300);
+    /// assert_eq!((img.width, img.height), (Emu(900), Emu(300)));
+    /// let crop = img.crop.unwrap();
+    /// assert_eq!((crop.left, crop.top, crop.bottom), (0.0, 0.25, 0.25));
+    /// ```
+    pub fn fit(mut self, fit: Fit, box_width: impl Into<Emu>, box_height: impl Into<Emu>) -> Self {
+        let (box_width, box_height) = (box_width.into(), box_height.into());
+        if [self.width, self.height, box_width, box_height].iter().any(|side| side.get() <= 0) {
+            self.width = box_width;
+            self.height = box_height;
+            return self;
+        }
+        let image_ratio = self.aspect_ratio();
+        let box_ratio = box_width.get() as f64 / box_height.get() as f64;
+        match fit {
+            Fit::Contain => {
+                let scale = (box_width.get() as f64 / self.width.get() as f64)
+                    .min(box_height.get() as f64 / self.height.get() as f64);
+                let width = self.width.scale(scale).min(box_width);
+                let height = self.height.scale(scale).min(box_height);
+                self.x += (box_width - width) / 2;
+                self.y += (box_height - height) / 2;
+                self.width = width;
+                self.height = height;
+            }
+            Fit::Cover => {
+                self.crop = Some(if image_ratio > box_ratio {
+                    let side = (1.0 - box_ratio / image_ratio) / 2.0;
+                    Crop::new(side, 0.0, side, 0.0)
+                } else {
+                    let side = (1.0 - image_ratio / box_ratio) / 2.0;
+                    Crop::new(0.0, side, 0.0, side)
+                });
+                self.width = box_width;
+                self.height = box_height;
+            }
+            Fit::Stretch => {
+                self.width = box_width;
+                self.height = box_height;
+            }
+        }
         self
     }
 
@@ -289,7 +525,7 @@ impl Image {
 }
 
 /// Decode base64 string to bytes
-fn base64_decode(input: &str) -> Result<Vec<u8>, std::io::Error> {
+pub(crate) fn base64_decode(input: &str) -> Result<Vec<u8>, std::io::Error> {
     // Simple base64 decoder
     const DECODE_TABLE: [i8; 128] = [
         -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1,
@@ -345,17 +581,18 @@ fn base64_decode(input: &str) -> Result<Vec<u8>, std::io::Error> {
 /// Image builder for fluent API
 pub struct ImageBuilder {
     filename: String,
-    width: u32,
-    height: u32,
-    x: u32,
-    y: u32,
+    width: Emu,
+    height: Emu,
+    x: Emu,
+    y: Emu,
     format: String,
     source: Option<ImageSource>,
+    fit: Option<(Fit, Emu, Emu)>,
 }
 
 impl ImageBuilder {
     /// Create a new image builder from file
-    pub fn new(filename: &str, width: u32, height: u32) -> Self {
+    pub fn new(filename: &str, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
         let format = Path::new(filename)
             .extension()
             .and_then(|ext| ext.to_str())
@@ -364,17 +601,18 @@ impl ImageBuilder {
 
         ImageBuilder {
             filename: filename.to_string(),
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format,
             source: Some(ImageSource::File(filename.to_string())),
+            fit: None,
         }
     }
     
     /// Create image builder from base64 data
-    pub fn from_base64(data: &str, width: u32, height: u32, format: &str) -> Self {
+    pub fn from_base64(data: &str, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         let format_upper = format.to_uppercase();
         let ext = match format_upper.as_str() {
             "JPEG" => "jpg",
@@ -383,17 +621,18 @@ impl ImageBuilder {
         
         ImageBuilder {
             filename: format!("image.{}", ext),
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format_upper,
             source: Some(ImageSource::Base64(data.to_string())),
+            fit: None,
         }
     }
     
     /// Create image builder from bytes
-    pub fn from_bytes(data: Vec<u8>, width: u32, height: u32, format: &str) -> Self {
+    pub fn from_bytes(data: Vec<u8>, width: impl Into<Emu>, height: impl Into<Emu>, format: &str) -> Self {
         let format_upper = format.to_uppercase();
         let ext = match format_upper.as_str() {
             "JPEG" => "jpg",
@@ -402,19 +641,20 @@ impl ImageBuilder {
         
         ImageBuilder {
             filename: format!("image.{}", ext),
-            width,
-            height,
-            x: 0,
-            y: 0,
+            width: width.into(),
+            height: height.into(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
             format: format_upper,
             source: Some(ImageSource::Bytes(data)),
+            fit: None,
         }
     }
 
     /// Set image position
-    pub fn position(mut self, x: u32, y: u32) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
         self
     }
 
@@ -425,24 +665,45 @@ impl ImageBuilder {
     }
 
     /// Scale to width
-    pub fn scale_to_width(mut self, width: u32) -> Self {
-        let ratio = self.width as f64 / self.height as f64;
-        self.width = width;
-        self.height = (width as f64 / ratio) as u32;
+    pub fn scale_to_width(mut self, width: impl Into<Emu>) -> Self {
+        let ratio = self.width.get() as f64 / self.height.get() as f64;
+        self.width = width.into();
+        self.height = Emu((self.width.get() as f64 / ratio) as i64);
         self
     }
 
     /// Scale to height
-    pub fn scale_to_height(mut self, height: u32) -> Self {
-        let ratio = self.width as f64 / self.height as f64;
-        self.height = height;
-        self.width = (height as f64 * ratio) as u32;
+    pub fn scale_to_height(mut self, height: impl Into<Emu>) -> Self {
+        let ratio = self.width.get() as f64 / self.height.get() as f64;
+        self.height = height.into();
+        self.width = Emu((self.height.get() as f64 * ratio) as i64);
+        self
+    }
+
+    /// Size the image into a `box_width` × `box_height` box at its position
+    ///
+    /// Applied when the image is built, so it may come before or after
+    /// [`Self::position`]. See [`Image::fit`].
+    ///
+    /// ```
+    /// use ppt_rs::core::Emu;
+    /// use ppt_rs::generator::{Fit, ImageBuilder};
+    ///
+    /// // 2:1 image letterboxed into a square at (100, 100)
+    /// let img = ImageBuilder::new("wide.png", 200, 100)
+    ///     .fit(Fit::Contain, 1000, 1000)
+    ///     .position(100, 100)
+    ///     .build();
+    /// assert_eq!((img.x, img.y, img.width, img.height), (Emu(100), Emu(350), Emu(1000), Emu(500)));
+    /// ```
+    pub fn fit(mut self, fit: Fit, box_width: impl Into<Emu>, box_height: impl Into<Emu>) -> Self {
+        self.fit = Some((fit, box_width.into(), box_height.into()));
         self
     }
 
     /// Build the image
     pub fn build(self) -> Image {
-        Image {
+        let image = Image {
             filename: self.filename,
             width: self.width,
             height: self.height,
@@ -452,10 +713,47 @@ impl ImageBuilder {
             source: self.source,
             crop: None,
             effects: Vec::new(),
+            locks: Locks::NONE,
+            placeholder: None,
+            r_id: None,
+        };
+        match self.fit {
+            Some((fit, box_width, box_height)) => image.fit(fit, box_width, box_height),
+            None => image,
         }
     }
 }
 
+impl Positioned for Image {
+    fn x(&self) -> Emu {
+        self.x
+    }
+
+    fn y(&self) -> Emu {
+        self.y
+    }
+
+    fn set_position(&mut self, x: Emu, y: Emu) {
+        self.x = x;
+        self.y = y;
+    }
+}
+
+impl ElementSized for Image {
+    fn width(&self) -> Emu {
+        self.width
+    }
+
+    fn height(&self) -> Emu {
+        self.height
+    }
+
+    fn set_size(&mut self, width: Emu, height: Emu) {
+        self.width = width;
+        self.height = height;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -464,16 +762,16 @@ mod tests {
     fn test_image_creation() {
         let img = Image::new("test.png", 1920, 1080, "PNG");
         assert_eq!(img.filename, "test.png");
-        assert_eq!(img.width, 1920);
-        assert_eq!(img.height, 1080);
+        assert_eq!(img.width, Emu(1920));
+        assert_eq!(img.height, Emu(1080));
     }
 
     #[test]
     fn test_image_position() {
         let img = Image::new("test.png", 1920, 1080, "PNG")
             .position(500000, 1000000);
-        assert_eq!(img.x, 500000);
-        assert_eq!(img.y, 1000000);
+        assert_eq!(img.x, Emu(500000));
+        assert_eq!(img.y, Emu(1000000));
     }
 
     #[test]
@@ -487,16 +785,16 @@ mod tests {
     fn test_image_scale_to_width() {
         let img = Image::new("test.png", 1920, 1080, "PNG")
             .scale_to_width(960);
-        assert_eq!(img.width, 960);
-        assert_eq!(img.height, 540);
+        assert_eq!(img.width, Emu(960));
+        assert_eq!(img.height, Emu(540));
     }
 
     #[test]
     fn test_image_scale_to_height() {
         let img = Image::new("test.png", 1920, 1080, "PNG")
             .scale_to_height(540);
-        assert_eq!(img.width, 960);
-        assert_eq!(img.height, 540);
+        assert_eq!(img.width, Emu(960));
+        assert_eq!(img.height, Emu(540));
     }
 
     #[test]
@@ -529,10 +827,10 @@ mod tests {
             .build();
 
         assert_eq!(img.filename, "photo.png");
-        assert_eq!(img.width, 960);
-        assert_eq!(img.height, 540);
-        assert_eq!(img.x, 500000);
-        assert_eq!(img.y, 1000000);
+        assert_eq!(img.width, Emu(960));
+        assert_eq!(img.height, Emu(540));
+        assert_eq!(img.x, Emu(500000));
+        assert_eq!(img.y, Emu(1000000));
     }
 
     #[test]
@@ -588,10 +886,36 @@ mod tests {
             .position(1000, 2000)
             .build();
         
-        assert_eq!(img.width, 200);
-        assert_eq!(img.height, 150);
-        assert_eq!(img.x, 1000);
-        assert_eq!(img.y, 2000);
+        assert_eq!(img.width, Emu(200));
+        assert_eq!(img.height, Emu(150));
+        assert_eq!(img.x, Emu(1000));
+        assert_eq!(img.y, Emu(2000));
         assert_eq!(img.format, "JPEG");
     }
+
+    #[test]
+    fn test_fit_modes() {
+        // 4:3 image into a 2:1 box at (1000, 1000)
+        let img = || Image::new("photo.jpg", 4000, 3000, "jpg").position(1000, 1000);
+
+        let contain = img().fit(Fit::Contain, 8000, 4000);
+        assert_eq!((contain.width.0, contain.height.0), (5333, 4000));
+        assert_eq!((contain.x.0, contain.y.0), (2333, 1000));
+        assert!(contain.crop.is_none());
+
+        let cover = img().fit(Fit::Cover, 8000, 4000);
+        assert_eq!((cover.x.0, cover.y.0, cover.width.0, cover.height.0), (1000, 1000, 8000, 4000));
+        let crop = cover.crop.unwrap();
+        assert_eq!((crop.left, crop.right), (0.0, 0.0));
+        assert!((crop.top - 1.0 / 6.0).abs() < 1e-9 && (crop.bottom - 1.0 / 6.0).abs() < 1e-9);
+
+        let stretch = img().fit(Fit::Stretch, 8000, 4000);
+        assert_eq!((stretch.x.0, stretch.width.0, stretch.height.0), (1000, 8000, 4000));
+        assert!(stretch.crop.is_none());
+
+        // Landscape into portrait crops the sides instead
+        let wide = Image::new("wide.png", 200, 100, "png").fit(Fit::Cover, 100, 200);
+        let crop = wide.crop.unwrap();
+        assert!((crop.left - 0.375).abs() < 1e-9 && crop.top == 0.0);
+    }
 }
@@ -2,11 +2,17 @@
 //!
 //! Generates proper PPTX XML for image embedding and display
 
+use crate::core::escape_xml_attr;
 use crate::generator::images::{Image, ImageEffect};
+use crate::generator::locks::Locks;
 
 /// Generate image XML for a slide
 pub fn generate_image_xml(image: &Image, shape_id: usize, rel_id: usize) -> String {
-    let rel_id_str = format!("rId{}", rel_id);
+    generate_image_xml_with_rid(image, shape_id, &format!("rId{}", rel_id))
+}
+
+/// Generate image XML embedding the picture related as `rel_id_str`
+pub fn generate_image_xml_with_rid(image: &Image, shape_id: usize, rel_id_str: &str) -> String {
     
     // Generate blipFill content (including crop)
     let blip_fill = if let Some(crop) = &image.crop {
@@ -59,7 +65,7 @@ pub fn generate_image_xml(image: &Image, shape_id: usize, rel_id: usize) -> Stri
 <p:nvPicPr>
 <p:cNvPr id="{}" name="{}"/>
 <p:cNvPicPr>
-<a:picLocks noChangeAspect="1"/>
+{}
 </p:cNvPicPr>
 <p:nvPr/>
 </p:nvPicPr>
@@ -76,7 +82,8 @@ pub fn generate_image_xml(image: &Image, shape_id: usize, rel_id: usize) -> Stri
 </p:spPr>
 </p:pic>"#,
         shape_id,
-        escape_xml(&image.filename),
+        escape_xml_attr(&image.filename),
+        (image.locks | Locks::ASPECT).to_xml("a:picLocks"),
         blip_fill,
         image.x,
         image.y,
@@ -88,10 +95,15 @@ pub fn generate_image_xml(image: &Image, shape_id: usize, rel_id: usize) -> Stri
 
 /// Generate image relationship XML
 pub fn generate_image_relationship(rel_id: usize, image_path: &str) -> String {
+    generate_image_relationship_with_rid(&format!("rId{}", rel_id), image_path)
+}
+
+/// Generate image relationship XML for the relationship ID `rel_id`
+pub fn generate_image_relationship_with_rid(rel_id: &str, image_path: &str) -> String {
     format!(
-        r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="{}"/>"#,
-        rel_id,
-        escape_xml(image_path)
+        r#"<Relationship Id="{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="{}"/>"#,
+        escape_xml_attr(rel_id),
+        escape_xml_attr(image_path)
     )
 }
 
@@ -114,15 +126,6 @@ pub fn generate_image_content_type(extension: &str) -> String {
     )
 }
 
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace("&", "&amp;")
-        .replace("<", "&lt;")
-        .replace(">", "&gt;")
-        .replace("\"", "&quot;")
-        .replace("'", "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,6 +141,14 @@ mod tests {
         assert!(xml.contains("rId1"));
     }
 
+    #[test]
+    fn test_locked_image_keeps_aspect_lock() {
+        let img = Image::new("logo.png", 914400, 457200, "PNG").lock(Locks::MOVE | Locks::SELECT);
+        let xml = generate_image_xml(&img, 1, 1);
+
+        assert!(xml.contains(r#"<a:picLocks noSelect="1" noChangeAspect="1" noMove="1"/>"#));
+    }
+
     #[test]
     fn test_generate_image_with_position() {
         let img = Image::new("photo.png", 1920000, 1080000, "PNG")
@@ -0,0 +1,367 @@
+//! Intermediate presentation model
+//!
+//! [`SlideContent`] is an authoring model: a layout plus lists of bullets,
+//! shapes, charts and so on, with the layout's geometry implied. Lowering it
+//! with [`Slide::from_content`] resolves that geometry once, giving the
+//! positioned elements the slide is drawn from, back to front:
+//!
+//! ```
+//! use ppt_rs::generator::{SlideContent, SlideSize};
+//! use ppt_rs::generator::ir::{Element, Slide, TextRole};
+//!
+//! let content = SlideContent::new("Roadmap").add_bullet("Q1").add_bullet("Q2");
+//! let slide = Slide::from_content(&content, SlideSize::STANDARD);
+//! let Element::Text(body) = &slide.elements[1] else { panic!() };
+//! assert_eq!(body.role, TextRole::Body);
+//! assert_eq!(body.paragraphs.len(), 2);
+//! ```
+//!
+//! Every output format is a backend over this model: the OOXML slide writer
+//! ([`slide_xml`](super::slide_xml)), the SVG renderer and the HTML exporter
+//! (`crate::export`). Slide zooms, slide numbers and narration reference
+//! other package parts and are added by the OOXML backend itself.
+
+use crate::core::Emu;
+use super::charts::Chart;
+use super::connectors::Connector;
+use super::constants::{
+    CENTERED_TITLE_HEIGHT, CENTERED_TITLE_Y, CONTENT_HEIGHT, CONTENT_HEIGHT_BIG, CONTENT_WIDTH, CONTENT_X,
+    CONTENT_Y_START, CONTENT_Y_START_BIG, SLIDE_WIDTH, TITLE_HEIGHT, TITLE_HEIGHT_BIG, TITLE_WIDTH, TITLE_X,
+    TITLE_Y,
+};
+use super::gradients::GradientFill;
+use super::images::Image;
+use super::placement::SlideSize;
+use super::shapes::Shape;
+use super::slide_content::{BulletPoint, CodeBlock, SlideContent, SlideLayout, TransitionType};
+use super::tables::Table;
+
+/// Width of each column of the two-column layout
+const COLUMN_WIDTH: u32 = 4115100;
+
+/// Left edge of the right column of the two-column layout
+const RIGHT_COLUMN_X: u32 = 4572300;
+
+/// Top and height of the band behind section titles
+const BAND_Y: u32 = 2286000;
+const BAND_HEIGHT: u32 = 2286000;
+
+/// Position and size in EMU
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Frame {
+    pub x: Emu,
+    pub y: Emu,
+    pub width: Emu,
+    pub height: Emu,
+}
+
+impl Frame {
+    pub fn new(x: impl Into<Emu>, y: impl Into<Emu>, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
+        Frame { x: x.into(), y: y.into(), width: width.into(), height: height.into() }
+    }
+}
+
+/// What a text box is for, which backends map to their own structure
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextRole {
+    Title,
+    Body,
+    LeftColumn,
+    RightColumn,
+}
+
+/// Horizontal alignment of a text box's paragraphs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Center,
+}
+
+/// Character formatting that applies unless a paragraph overrides it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextStyle {
+    /// Font size in points
+    pub size: u32,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    /// Hex RGB, without `#`
+    pub color: Option<String>,
+}
+
+/// Title or body text of a layout
+#[derive(Clone, Debug)]
+pub struct TextBox {
+    pub role: TextRole,
+    pub frame: Frame,
+    pub align: Align,
+    /// Text is centered vertically instead of starting at the top
+    pub anchor_middle: bool,
+    pub style: TextStyle,
+    /// Paragraphs; a title has one, without a bullet
+    pub paragraphs: Vec<BulletPoint>,
+}
+
+impl TextBox {
+    /// Text of all paragraphs, one per line
+    pub fn text(&self) -> String {
+        self.paragraphs.iter().map(|p| p.text.as_str()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Positioned element of a slide
+#[derive(Clone, Debug)]
+pub enum Element {
+    /// Gradient band behind a section title
+    Band { frame: Frame, fill: GradientFill },
+    Text(TextBox),
+    Table(Table),
+    /// Shape with its placement resolved to `x`/`y`
+    Shape(Box<Shape>),
+    Picture(Image),
+    Code(CodeBlock),
+    Connector(Connector),
+    Chart(Chart),
+}
+
+/// A slide as positioned elements, back to front
+#[derive(Clone, Debug)]
+pub struct Slide {
+    pub elements: Vec<Element>,
+    pub transition: TransitionType,
+    pub notes: Option<String>,
+}
+
+impl Slide {
+    /// Lower `content`, resolving its layout and shape placements for a slide of `size`
+    pub fn from_content(content: &SlideContent, size: SlideSize) -> Self {
+        let placed = |shape: &Shape| {
+            let mut shape = shape.clone();
+            shape.resolve_placement(size);
+            Element::Shape(Box::new(shape))
+        };
+        let mut elements = layout_elements(content, size);
+        elements.extend(content.shapes.iter().map(placed));
+        elements.extend(content.images.iter().cloned().map(Element::Picture));
+        elements.extend(content.overlays.iter().map(placed));
+        elements.extend(content.code_blocks.iter().cloned().map(Element::Code));
+        elements.extend(content.connectors.iter().cloned().map(Element::Connector));
+        for chart in &content.charts {
+            let (marks, lines) = chart.reference_marks();
+            elements.push(Element::Chart(chart.clone()));
+            elements.extend(marks.into_iter().chain(chart.legend_note()).map(|shape| Element::Shape(Box::new(shape))));
+            elements.extend(lines.into_iter().map(Element::Connector));
+        }
+        Slide { elements, transition: content.transition, notes: content.notes.clone() }
+    }
+
+    /// Text boxes of the layout, in drawing order
+    pub fn text_boxes(&self) -> impl Iterator<Item = &TextBox> {
+        self.elements.iter().filter_map(|e| match e {
+            Element::Text(text) => Some(text),
+            _ => None,
+        })
+    }
+
+    /// The title text box, if the layout has one
+    pub fn title(&self) -> Option<&TextBox> {
+        self.text_boxes().find(|t| t.role == TextRole::Title)
+    }
+}
+
+/// Band, title and body elements the slide's layout places
+///
+/// Layout geometry is designed for the standard slide and scaled to `size`.
+fn layout_elements(content: &SlideContent, size: SlideSize) -> Vec<Element> {
+    let scaled = |x, y, width, height| {
+        let (x, y, width, height) = size.scale_bounds((x, y, width, height));
+        Frame::new(x, y, width, height)
+    };
+    let mut elements = Vec::new();
+    match content.layout {
+        SlideLayout::Blank => {}
+        SlideLayout::TitleOnly => {
+            elements.push(Element::Text(title(content, scaled(TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT), 44)));
+        }
+        SlideLayout::CenteredTitle | SlideLayout::SectionHeader => {
+            if let Some(band) = &content.section_band {
+                let frame = scaled(0, BAND_Y, SLIDE_WIDTH, BAND_HEIGHT);
+                elements.push(Element::Band { frame, fill: band.clone() });
+            }
+            let (x, align) = match content.layout {
+                SlideLayout::CenteredTitle => (TITLE_X, Align::Center),
+                _ => (685800, Align::Left),
+            };
+            let frame = scaled(x, CENTERED_TITLE_Y, SLIDE_WIDTH - 2 * x, CENTERED_TITLE_HEIGHT);
+            let mut title = title(content, frame, 54);
+            title.align = align;
+            // Light title text over the band unless a color was set explicitly
+            if title.style.color.is_none() && content.section_band.is_some() {
+                title.style.color = Some("FFFFFF".to_string());
+            }
+            elements.push(Element::Text(title));
+        }
+        SlideLayout::TitleAndBigContent => {
+            elements.push(Element::Text(title(content, scaled(TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT_BIG), 44)));
+            let paragraphs = paragraphs(content);
+            if !paragraphs.is_empty() {
+                let frame = scaled(CONTENT_X, CONTENT_Y_START_BIG, CONTENT_WIDTH, CONTENT_HEIGHT_BIG);
+                elements.push(Element::Text(body(content, TextRole::Body, frame, 28, paragraphs)));
+            }
+        }
+        SlideLayout::TwoColumn => {
+            elements.push(Element::Text(title(content, scaled(TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT_BIG), 44)));
+            let mut left = paragraphs(content);
+            if !left.is_empty() {
+                let right = left.split_off(left.len().div_ceil(2));
+                let frame = scaled(CONTENT_X, CONTENT_Y_START_BIG, COLUMN_WIDTH, CONTENT_HEIGHT_BIG);
+                elements.push(Element::Text(body(content, TextRole::LeftColumn, frame, 24, left)));
+                if !right.is_empty() {
+                    let frame = scaled(RIGHT_COLUMN_X, CONTENT_Y_START_BIG, COLUMN_WIDTH, CONTENT_HEIGHT_BIG);
+                    elements.push(Element::Text(body(content, TextRole::RightColumn, frame, 24, right)));
+                }
+            }
+        }
+        SlideLayout::TitleAndContent => {
+            elements.push(Element::Text(title(content, scaled(TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT), 44)));
+            let paragraphs = paragraphs(content);
+            if let Some(table) = &content.table {
+                elements.push(Element::Table(table.clone()));
+            } else if !paragraphs.is_empty() {
+                let frame = scaled(CONTENT_X, CONTENT_Y_START, CONTENT_WIDTH, CONTENT_HEIGHT);
+                elements.push(Element::Text(body(content, TextRole::Body, frame, 28, paragraphs)));
+            }
+        }
+    }
+    elements
+}
+
+fn title(content: &SlideContent, frame: Frame, default_size: u32) -> TextBox {
+    TextBox {
+        role: TextRole::Title,
+        frame,
+        align: Align::Left,
+        anchor_middle: true,
+        style: TextStyle {
+            size: content.title_size.unwrap_or(default_size),
+            bold: content.title_bold,
+            italic: content.title_italic,
+            underline: content.title_underline,
+            color: content.title_color.clone(),
+        },
+        paragraphs: vec![BulletPoint::new(&content.title)],
+    }
+}
+
+fn body(content: &SlideContent, role: TextRole, frame: Frame, default_size: u32, paragraphs: Vec<BulletPoint>) -> TextBox {
+    TextBox {
+        role,
+        frame,
+        align: Align::Left,
+        anchor_middle: false,
+        style: TextStyle {
+            size: content.content_size.unwrap_or(default_size),
+            bold: content.content_bold,
+            italic: content.content_italic,
+            underline: false,
+            color: content.content_color.clone(),
+        },
+        paragraphs,
+    }
+}
+
+/// Styled bullets if there are any, otherwise the plain content lines,
+/// spaced by the slide's density where they don't set their own spacing
+fn paragraphs(content: &SlideContent) -> Vec<BulletPoint> {
+    let mut paragraphs: Vec<BulletPoint> = if content.bullets.is_empty() {
+        content.content.iter().map(|text| BulletPoint::new(text).with_style(content.bullet_style)).collect()
+    } else {
+        content.bullets.clone()
+    };
+    if let Some(density) = content.density {
+        for paragraph in &mut paragraphs {
+            paragraph.spacing_before.get_or_insert(density.spacing_before());
+            paragraph.line_spacing.get_or_insert(density.line_spacing());
+        }
+    }
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{Anchor, ShapeType, TableBuilder};
+
+    #[test]
+    fn test_two_columns_split_paragraphs() {
+        let content = SlideContent::new("Compare")
+            .layout(SlideLayout::TwoColumn)
+            .add_bullet("a")
+            .add_bullet("b")
+            .add_bullet("c");
+        let slide = Slide::from_content(&content, SlideSize::STANDARD);
+        let boxes: Vec<_> = slide.text_boxes().map(|t| (t.role, t.text(), t.frame.x)).collect();
+        assert_eq!(boxes, [
+            (TextRole::Title, "Compare".to_string(), Emu::from(TITLE_X)),
+            (TextRole::LeftColumn, "a\nb".to_string(), Emu::from(CONTENT_X)),
+            (TextRole::RightColumn, "c".to_string(), Emu::from(RIGHT_COLUMN_X)),
+        ]);
+    }
+
+    #[test]
+    fn test_density_spaces_paragraphs() {
+        use crate::generator::Density;
+
+        let content = SlideContent::new("Dense")
+            .density(Density::Compact)
+            .add_bullet("a")
+            .add_bullet_point(BulletPoint::new("b").spacing(Density::Comfortable));
+        let slide = Slide::from_content(&content, SlideSize::STANDARD);
+        let body = slide.text_boxes().find(|t| t.role == TextRole::Body).unwrap();
+        assert_eq!(body.style.size, 18);
+        let spacing: Vec<_> = body.paragraphs.iter().map(|p| (p.spacing_before, p.line_spacing)).collect();
+        assert_eq!(spacing, [(Some(200), Some(90)), (Some(1200), Some(110))]);
+    }
+
+    #[test]
+    fn test_table_replaces_body() {
+        let table = TableBuilder::new(vec![914400]).add_simple_row(vec!["x"]).build();
+        let content = SlideContent::new("Data").add_bullet("hidden").table(table);
+        let slide = Slide::from_content(&content, SlideSize::STANDARD);
+        assert_eq!(slide.elements.len(), 2);
+        assert!(matches!(slide.elements[1], Element::Table(_)));
+    }
+
+    #[test]
+    fn test_section_title_over_band() {
+        let content = SlideContent::new("Part 2")
+            .layout(SlideLayout::SectionHeader)
+            .section_band(GradientFill::two_color("1565C0", "0D47A1"));
+        let slide = Slide::from_content(&content, SlideSize::STANDARD);
+        assert!(matches!(slide.elements[0], Element::Band { .. }));
+        let title = slide.title().unwrap();
+        assert_eq!(title.style.color.as_deref(), Some("FFFFFF"));
+        assert_eq!(title.frame, Frame::new(685800, CENTERED_TITLE_Y, SLIDE_WIDTH - 2 * 685800, CENTERED_TITLE_HEIGHT));
+    }
+
+    #[test]
+    fn test_layout_scales_to_widescreen() {
+        let content = SlideContent::new("Part 2")
+            .layout(SlideLayout::SectionHeader)
+            .section_band(GradientFill::two_color("1565C0", "0D47A1"));
+        let slide = Slide::from_content(&content, SlideSize::WIDESCREEN);
+        let Element::Band { frame, .. } = &slide.elements[0] else { panic!() };
+        assert_eq!(*frame, Frame::new(0, BAND_Y, 12192000, BAND_HEIGHT));
+        assert_eq!(slide.title().unwrap().frame.width, Emu::from((SLIDE_WIDTH - 2 * 685800) * 4 / 3));
+    }
+
+    #[test]
+    fn test_shape_placement_resolved() {
+        let shape = Shape::new(ShapeType::Rectangle, 0, 0, 914400, 914400).at(Anchor::BottomRight.offset(0, 0));
+        let content = SlideContent::new("").layout(SlideLayout::Blank).add_shape(shape);
+        let slide = Slide::from_content(&content, SlideSize::STANDARD);
+        let Element::Shape(shape) = &slide.elements[0] else { panic!() };
+        assert_eq!((shape.x.0, shape.y.0), (9144000 - 914400, 6858000 - 914400));
+    }
+}
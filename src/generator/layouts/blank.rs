@@ -1,6 +1,7 @@
 //! Blank slide layout
 
 use super::common::SlideXmlBuilder;
+use crate::generator::placement::SlideSize;
 
 /// Blank slide layout generator
 pub struct BlankLayout;
@@ -8,7 +9,13 @@ pub struct BlankLayout;
 impl BlankLayout {
     /// Generate blank slide XML
     pub fn generate() -> String {
+        Self::generate_with_size(SlideSize::STANDARD)
+    }
+
+    /// Generate blank slide XML for a slide of `size`
+    pub fn generate_with_size(size: SlideSize) -> String {
         SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .end_sp_tree()
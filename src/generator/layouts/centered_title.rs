@@ -5,6 +5,7 @@ use crate::generator::slide_content::SlideContent;
 use crate::generator::constants::{
     TITLE_X, CENTERED_TITLE_Y, TITLE_WIDTH, CENTERED_TITLE_HEIGHT, TITLE_FONT_SIZE,
 };
+use crate::generator::placement::SlideSize;
 
 /// Centered title slide layout generator
 pub struct CenteredTitleLayout;
@@ -12,6 +13,11 @@ pub struct CenteredTitleLayout;
 impl CenteredTitleLayout {
     /// Generate centered title slide XML
     pub fn generate(content: &SlideContent) -> String {
+        Self::generate_with_size(content, SlideSize::STANDARD)
+    }
+
+    /// Generate centered title slide XML for a slide of `size`
+    pub fn generate_with_size(content: &SlideContent, size: SlideSize) -> String {
         let title_size = content.title_size.unwrap_or((TITLE_FONT_SIZE / 100) as u32) * 100;
         let title_props = generate_text_props(
             title_size,
@@ -22,6 +28,7 @@ impl CenteredTitleLayout {
         );
 
         SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .add_centered_title(2, TITLE_X, CENTERED_TITLE_Y, TITLE_WIDTH, CENTERED_TITLE_HEIGHT, &content.title, &title_props)
@@ -1,10 +1,10 @@
 //! Common utilities for slide XML generation
 
 use crate::core::XmlWriter;
-use crate::generator::constants::{
-    SLIDE_WIDTH, SLIDE_HEIGHT,
-};
+use crate::generator::placement::SlideSize;
 use crate::generator::slide_content::BulletStyle;
+pub use crate::core::escape_xml;
+use crate::core::escape_xml_attr;
 
 /// XML declaration and namespaces
 pub const XML_DECL: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#;
@@ -83,6 +83,7 @@ impl ExtendedTextProps {
         }
         
         if let Some(ref font) = self.font_family {
+            let font = escape_xml_attr(font);
             attrs.push_str(&format!(
                 r#"<a:latin typeface="{font}"/><a:cs typeface="{font}"/>"#
             ));
@@ -110,17 +111,28 @@ pub fn generate_text_props_extended(props: &ExtendedTextProps) -> String {
 }
 
 /// Builder for slide XML with common structure
+///
+/// Shape bounds are given for the standard slide and scaled to the
+/// builder's slide size.
 pub struct SlideXmlBuilder {
     writer: XmlWriter,
+    size: SlideSize,
 }
 
 impl SlideXmlBuilder {
     pub fn new() -> Self {
         Self {
             writer: XmlWriter::new(),
+            size: SlideSize::STANDARD,
         }
     }
 
+    /// Lay the slide out for `size`
+    pub fn with_size(mut self, size: SlideSize) -> Self {
+        self.size = size;
+        self
+    }
+
     /// Start slide with background
     pub fn start_slide_with_bg(mut self) -> Self {
         self.writer.raw(XML_DECL);
@@ -135,14 +147,16 @@ impl SlideXmlBuilder {
     pub fn start_sp_tree(mut self) -> Self {
         self.writer.raw("<p:spTree>\n");
         self.writer.raw("<p:nvGrpSpPr><p:cNvPr id=\"1\" name=\"\"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr>\n");
+        let (cx, cy) = (self.size.width, self.size.height);
         self.writer.raw(&format!(
-            "<p:grpSpPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{SLIDE_WIDTH}\" cy=\"{SLIDE_HEIGHT}\"/><a:chOff x=\"0\" y=\"0\"/><a:chExt cx=\"{SLIDE_WIDTH}\" cy=\"{SLIDE_HEIGHT}\"/></a:xfrm></p:grpSpPr>\n"
+            "<p:grpSpPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{cx}\" cy=\"{cy}\"/><a:chOff x=\"0\" y=\"0\"/><a:chExt cx=\"{cx}\" cy=\"{cy}\"/></a:xfrm></p:grpSpPr>\n"
         ));
         self
     }
 
     /// Add title shape
     pub fn add_title(mut self, id: u32, x: u32, y: u32, cx: u32, cy: u32, text: &str, props: &str, ph_type: &str) -> Self {
+        let (x, y, cx, cy) = self.size.scale_bounds((x, y, cx, cy));
         self.writer.raw(&format!(
             r#"<p:sp>
 <p:nvSpPr>
@@ -174,6 +188,7 @@ impl SlideXmlBuilder {
 
     /// Add centered title
     pub fn add_centered_title(mut self, id: u32, x: u32, y: u32, cx: u32, cy: u32, text: &str, props: &str) -> Self {
+        let (x, y, cx, cy) = self.size.scale_bounds((x, y, cx, cy));
         self.writer.raw(&format!(
             r#"<p:sp>
 <p:nvSpPr>
@@ -206,6 +221,7 @@ impl SlideXmlBuilder {
 
     /// Start content body shape
     pub fn start_content_body(mut self, id: u32, x: u32, y: u32, cx: u32, cy: u32) -> Self {
+        let (x, y, cx, cy) = self.size.scale_bounds((x, y, cx, cy));
         self.writer.raw(&format!(
             r#"<p:sp>
 <p:nvSpPr>
@@ -290,15 +306,6 @@ impl Default for SlideXmlBuilder {
     }
 }
 
-/// Escape XML special characters
-pub fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -16,16 +16,22 @@ pub use centered_title::CenteredTitleLayout;
 pub use title_content::{TitleContentLayout, TitleBigContentLayout};
 pub use two_column::TwoColumnLayout;
 
+use super::placement::SlideSize;
 use super::slide_content::{SlideContent, SlideLayout};
 
 /// Generate slide XML based on layout type
 pub fn create_slide_xml_for_layout(content: &SlideContent) -> String {
+    create_slide_xml_for_layout_with_size(content, SlideSize::STANDARD)
+}
+
+/// Generate slide XML based on layout type for a slide of `size`
+pub fn create_slide_xml_for_layout_with_size(content: &SlideContent, size: SlideSize) -> String {
     match content.layout {
-        SlideLayout::Blank => BlankLayout::generate(),
-        SlideLayout::TitleOnly => TitleOnlyLayout::generate(content),
-        SlideLayout::CenteredTitle => CenteredTitleLayout::generate(content),
-        SlideLayout::TitleAndBigContent => TitleBigContentLayout::generate(content),
-        SlideLayout::TwoColumn => TwoColumnLayout::generate(content),
-        SlideLayout::TitleAndContent => TitleContentLayout::generate(content),
+        SlideLayout::Blank => BlankLayout::generate_with_size(size),
+        SlideLayout::TitleOnly => TitleOnlyLayout::generate_with_size(content, size),
+        SlideLayout::CenteredTitle | SlideLayout::SectionHeader => CenteredTitleLayout::generate_with_size(content, size),
+        SlideLayout::TitleAndBigContent => TitleBigContentLayout::generate_with_size(content, size),
+        SlideLayout::TwoColumn => TwoColumnLayout::generate_with_size(content, size),
+        SlideLayout::TitleAndContent => TitleContentLayout::generate_with_size(content, size),
     }
 }
@@ -9,6 +9,7 @@ use crate::generator::constants::{
     CONTENT_WIDTH, CONTENT_HEIGHT, CONTENT_HEIGHT_BIG,
     TITLE_FONT_SIZE, CONTENT_FONT_SIZE,
 };
+use crate::generator::placement::SlideSize;
 
 /// Standard title and content layout
 pub struct TitleContentLayout;
@@ -16,6 +17,11 @@ pub struct TitleContentLayout;
 impl TitleContentLayout {
     /// Generate title and content slide XML
     pub fn generate(content: &SlideContent) -> String {
+        Self::generate_with_size(content, SlideSize::STANDARD)
+    }
+
+    /// Generate title and content slide XML for a slide of `size`
+    pub fn generate_with_size(content: &SlideContent, size: SlideSize) -> String {
         let title_size = content.title_size.unwrap_or((TITLE_FONT_SIZE / 100) as u32) * 100;
         let content_size = content.content_size.unwrap_or((CONTENT_FONT_SIZE / 100) as u32) * 100;
 
@@ -36,6 +42,7 @@ impl TitleContentLayout {
         );
 
         let mut builder = SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .add_title(2, TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT, &content.title, &title_props, "title");
@@ -119,6 +126,11 @@ pub struct TitleBigContentLayout;
 impl TitleBigContentLayout {
     /// Generate title and big content slide XML
     pub fn generate(content: &SlideContent) -> String {
+        Self::generate_with_size(content, SlideSize::STANDARD)
+    }
+
+    /// Generate title and big content slide XML for a slide of `size`
+    pub fn generate_with_size(content: &SlideContent, size: SlideSize) -> String {
         let title_size = content.title_size.unwrap_or((TITLE_FONT_SIZE / 100) as u32) * 100;
         let content_size = content.content_size.unwrap_or((CONTENT_FONT_SIZE / 100) as u32) * 100;
 
@@ -139,6 +151,7 @@ impl TitleBigContentLayout {
         );
 
         let mut builder = SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .add_title(2, TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT_BIG, &content.title, &title_props, "title");
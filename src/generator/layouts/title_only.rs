@@ -5,6 +5,7 @@ use crate::generator::slide_content::SlideContent;
 use crate::generator::constants::{
     TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT, TITLE_FONT_SIZE,
 };
+use crate::generator::placement::SlideSize;
 
 /// Title-only slide layout generator
 pub struct TitleOnlyLayout;
@@ -12,6 +13,11 @@ pub struct TitleOnlyLayout;
 impl TitleOnlyLayout {
     /// Generate title-only slide XML
     pub fn generate(content: &SlideContent) -> String {
+        Self::generate_with_size(content, SlideSize::STANDARD)
+    }
+
+    /// Generate title-only slide XML for a slide of `size`
+    pub fn generate_with_size(content: &SlideContent, size: SlideSize) -> String {
         let title_size = content.title_size.unwrap_or((TITLE_FONT_SIZE / 100) as u32) * 100;
         let title_props = generate_text_props(
             title_size,
@@ -22,6 +28,7 @@ impl TitleOnlyLayout {
         );
 
         SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .add_title(2, TITLE_X, TITLE_Y, TITLE_WIDTH, TITLE_HEIGHT, &content.title, &title_props, "title")
@@ -2,6 +2,7 @@
 
 use super::common::{SlideXmlBuilder, generate_text_props};
 use crate::generator::slide_content::SlideContent;
+use crate::generator::placement::SlideSize;
 
 /// Two-column slide layout generator
 pub struct TwoColumnLayout;
@@ -10,6 +11,11 @@ impl TwoColumnLayout {
     /// Generate two-column slide XML
     /// Bullets are automatically split between left and right columns
     pub fn generate(content: &SlideContent) -> String {
+        Self::generate_with_size(content, SlideSize::STANDARD)
+    }
+
+    /// Generate two-column slide XML for a slide of `size`
+    pub fn generate_with_size(content: &SlideContent, size: SlideSize) -> String {
         let title_size = content.title_size.unwrap_or(44) * 100;
         let content_size = content.content_size.unwrap_or(24) * 100;
 
@@ -30,6 +36,7 @@ impl TwoColumnLayout {
         );
 
         let mut builder = SlideXmlBuilder::new()
+            .with_size(size)
             .start_slide_with_bg()
             .start_sp_tree()
             .add_title(2, 457200, 274638, 8230200, 914400, &content.title, &title_props, "title");
@@ -42,7 +49,8 @@ impl TwoColumnLayout {
             let mid = bullet_count.div_ceil(2);
 
             // Left column
-            builder = builder.raw(r#"
+            let (x, y, cx, cy) = size.scale_bounds((457200, 1189200, 4115100, 5668800));
+            builder = builder.raw(&format!(r#"
 <p:sp>
 <p:nvSpPr>
 <p:cNvPr id="3" name="Left Content"/>
@@ -51,8 +59,8 @@ impl TwoColumnLayout {
 </p:nvSpPr>
 <p:spPr>
 <a:xfrm>
-<a:off x="457200" y="1189200"/>
-<a:ext cx="4115100" cy="5668800"/>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{cx}" cy="{cy}"/>
 </a:xfrm>
 <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
 <a:noFill/>
@@ -60,7 +68,7 @@ impl TwoColumnLayout {
 <p:txBody>
 <a:bodyPr/>
 <a:lstStyle/>
-"#);
+"#));
 
             if use_styled_bullets {
                 for bullet in &content.bullets[..mid] {
@@ -75,7 +83,8 @@ impl TwoColumnLayout {
 
             // Right column
             if mid < bullet_count {
-                builder = builder.raw(r#"
+                let (x, y, cx, cy) = size.scale_bounds((4572300, 1189200, 4115100, 5668800));
+                builder = builder.raw(&format!(r#"
 <p:sp>
 <p:nvSpPr>
 <p:cNvPr id="4" name="Right Content"/>
@@ -84,8 +93,8 @@ impl TwoColumnLayout {
 </p:nvSpPr>
 <p:spPr>
 <a:xfrm>
-<a:off x="4572300" y="1189200"/>
-<a:ext cx="4115100" cy="5668800"/>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{cx}" cy="{cy}"/>
 </a:xfrm>
 <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
 <a:noFill/>
@@ -93,7 +102,7 @@ impl TwoColumnLayout {
 <p:txBody>
 <a:bodyPr/>
 <a:lstStyle/>
-"#);
+"#));
 
                 if use_styled_bullets {
                     for bullet in &content.bullets[mid..] {
@@ -0,0 +1,110 @@
+//! DrawingML locks
+//!
+//! Locks are written as `a:spLocks`, `a:picLocks` or `a:cxnSpLocks` in the
+//! element's non-visual properties. PowerPoint honours them in the UI, so
+//! a template can keep recipients from nudging logos or branding bars.
+//! They are not a security feature: anyone can remove them in the XML.
+
+use core::ops::{BitOr, BitOrAssign};
+
+/// Set of editing locks, combined with `|`
+///
+/// ```
+/// use ppt_rs::generator::{Locks, Shape, ShapeType};
+///
+/// let logo = Shape::new(ShapeType::Rectangle, 0, 0, 914400, 457200)
+///     .lock(Locks::MOVE | Locks::RESIZE | Locks::SELECT);
+/// assert!(logo.locks.contains(Locks::MOVE));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Locks(u16);
+
+impl Locks {
+    /// No locks
+    pub const NONE: Locks = Locks(0);
+    /// Element can't be selected (`noSelect`)
+    pub const SELECT: Locks = Locks(1);
+    /// Element can't be moved (`noMove`)
+    pub const MOVE: Locks = Locks(1 << 1);
+    /// Element can't be resized (`noResize`)
+    pub const RESIZE: Locks = Locks(1 << 2);
+    /// Element can't be rotated (`noRot`)
+    pub const ROTATE: Locks = Locks(1 << 3);
+    /// Resizing keeps the aspect ratio (`noChangeAspect`)
+    pub const ASPECT: Locks = Locks(1 << 4);
+    /// Element can't be grouped (`noGrp`)
+    pub const GROUP: Locks = Locks(1 << 5);
+    /// Shape text can't be edited (`noTextEdit`, shapes only)
+    pub const TEXT: Locks = Locks(1 << 6);
+
+    /// Attribute written for each lock, in schema order
+    const ATTRIBUTES: [(Locks, &'static str); 7] = [
+        (Locks::GROUP, "noGrp"),
+        (Locks::SELECT, "noSelect"),
+        (Locks::ROTATE, "noRot"),
+        (Locks::ASPECT, "noChangeAspect"),
+        (Locks::MOVE, "noMove"),
+        (Locks::RESIZE, "noResize"),
+        (Locks::TEXT, "noTextEdit"),
+    ];
+
+    /// Whether no lock is set
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every lock in `other` is set
+    pub fn contains(&self, other: Locks) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Lock element, e.g. `a:spLocks`, or an empty string when no lock is set
+    ///
+    /// [`Locks::TEXT`] is only written for `a:spLocks`, the one element
+    /// that allows it.
+    pub fn to_xml(&self, element: &str) -> String {
+        let attrs: String = Locks::ATTRIBUTES.iter()
+            .filter(|(lock, name)| self.contains(*lock) && (*name != "noTextEdit" || element == "a:spLocks"))
+            .map(|(_, name)| format!(" {name}=\"1\""))
+            .collect();
+        if attrs.is_empty() {
+            String::new()
+        } else {
+            format!("<{element}{attrs}/>")
+        }
+    }
+}
+
+impl BitOr for Locks {
+    type Output = Locks;
+
+    fn bitor(self, rhs: Locks) -> Locks {
+        Locks(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Locks {
+    fn bitor_assign(&mut self, rhs: Locks) {
+        self.0 |= rhs.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locks_xml() {
+        let locks = Locks::MOVE | Locks::RESIZE | Locks::SELECT;
+        assert_eq!(locks.to_xml("a:spLocks"), r#"<a:spLocks noSelect="1" noMove="1" noResize="1"/>"#);
+        assert_eq!(Locks::NONE.to_xml("a:spLocks"), "");
+    }
+
+    #[test]
+    fn test_text_lock_only_on_shapes() {
+        let locks = Locks::TEXT | Locks::MOVE;
+        assert!(locks.to_xml("a:spLocks").contains("noTextEdit"));
+        assert_eq!(locks.to_xml("a:cxnSpLocks"), r#"<a:cxnSpLocks noMove="1"/>"#);
+        assert_eq!(Locks::TEXT.to_xml("a:picLocks"), "");
+    }
+}
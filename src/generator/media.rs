@@ -2,7 +2,7 @@
 //!
 //! Provides types and XML generation for embedding videos and audio files.
 
-use crate::core::escape_xml;
+use crate::core::{escape_xml_attr, Emu};
 
 /// Video format types
 #[derive(Clone, Debug, Copy, PartialEq, Eq)]
@@ -247,14 +247,14 @@ pub struct Video {
     pub source: String,
     /// Video format
     pub format: VideoFormat,
-    /// Position X in EMU
-    pub x: u32,
-    /// Position Y in EMU
-    pub y: u32,
-    /// Width in EMU
-    pub width: u32,
-    /// Height in EMU
-    pub height: u32,
+    /// Position X; negative values place the video partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the video partly above the slide
+    pub y: Emu,
+    /// Width
+    pub width: Emu,
+    /// Height
+    pub height: Emu,
     /// Playback options
     pub options: VideoOptions,
     /// Poster image (thumbnail)
@@ -265,14 +265,14 @@ pub struct Video {
 
 impl Video {
     /// Create a new video element
-    pub fn new(source: &str, format: VideoFormat, x: u32, y: u32, width: u32, height: u32) -> Self {
+    pub fn new(source: &str, format: VideoFormat, x: impl Into<Emu>, y: impl Into<Emu>, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
         Video {
             source: source.to_string(),
             format,
-            x,
-            y,
-            width,
-            height,
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
             options: VideoOptions::default(),
             poster: None,
             alt_text: None,
@@ -280,7 +280,7 @@ impl Video {
     }
 
     /// Create from file path (auto-detect format)
-    pub fn from_file(path: &str, x: u32, y: u32, width: u32, height: u32) -> Option<Self> {
+    pub fn from_file(path: &str, x: impl Into<Emu>, y: impl Into<Emu>, width: impl Into<Emu>, height: impl Into<Emu>) -> Option<Self> {
         let ext = path.rsplit('.').next()?;
         let format = VideoFormat::from_extension(ext)?;
         Some(Self::new(path, format, x, y, width, height))
@@ -312,14 +312,14 @@ pub struct Audio {
     pub source: String,
     /// Audio format
     pub format: AudioFormat,
-    /// Position X in EMU (for icon)
-    pub x: u32,
-    /// Position Y in EMU (for icon)
-    pub y: u32,
-    /// Icon width in EMU
-    pub width: u32,
-    /// Icon height in EMU
-    pub height: u32,
+    /// Position X of the icon
+    pub x: Emu,
+    /// Position Y of the icon
+    pub y: Emu,
+    /// Icon width
+    pub width: Emu,
+    /// Icon height
+    pub height: Emu,
     /// Playback options
     pub options: AudioOptions,
     /// Alt text
@@ -328,21 +328,21 @@ pub struct Audio {
 
 impl Audio {
     /// Create a new audio element
-    pub fn new(source: &str, format: AudioFormat, x: u32, y: u32, width: u32, height: u32) -> Self {
+    pub fn new(source: &str, format: AudioFormat, x: impl Into<Emu>, y: impl Into<Emu>, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
         Audio {
             source: source.to_string(),
             format,
-            x,
-            y,
-            width,
-            height,
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
             options: AudioOptions::default(),
             alt_text: None,
         }
     }
 
     /// Create from file path (auto-detect format)
-    pub fn from_file(path: &str, x: u32, y: u32, width: u32, height: u32) -> Option<Self> {
+    pub fn from_file(path: &str, x: impl Into<Emu>, y: impl Into<Emu>, width: impl Into<Emu>, height: impl Into<Emu>) -> Option<Self> {
         let ext = path.rsplit('.').next()?;
         let format = AudioFormat::from_extension(ext)?;
         Some(Self::new(path, format, x, y, width, height))
@@ -399,7 +399,7 @@ pub fn generate_video_xml(video: &Video, shape_id: usize, video_r_id: &str, _ima
 </a:prstGeom>
 </p:spPr>
 </p:pic>"#,
-        shape_id, shape_id, escape_xml(alt_text),
+        shape_id, shape_id, escape_xml_attr(alt_text),
         video_r_id, video_r_id, video_r_id,
         video.x, video.y, video.width, video.height
     )
@@ -443,12 +443,233 @@ pub fn generate_audio_xml(audio: &Audio, shape_id: usize, audio_r_id: &str) -> S
 </a:prstGeom>
 </p:spPr>
 </p:pic>"#,
-        shape_id, shape_id, escape_xml(alt_text),
+        shape_id, shape_id, escape_xml_attr(alt_text),
         audio_r_id, audio_r_id, audio_r_id,
         audio.x, audio.y, audio.width, audio.height
     )
 }
 
+/// Transparent 1×1 PNG used as the (hidden) narration icon
+pub const NARRATION_ICON_PNG: &[u8] = &[
+    0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D,
+    0x49, 0x48, 0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01,
+    0x08, 0x06, 0x00, 0x00, 0x00, 0x1F, 0x15, 0xC4, 0x89, 0x00, 0x00, 0x00,
+    0x0B, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0x60, 0x00, 0x02, 0x00,
+    0x00, 0x05, 0x00, 0x01, 0x7A, 0x5E, 0xAB, 0x3F, 0x00, 0x00, 0x00, 0x00,
+    0x49, 0x45, 0x4E, 0x44, 0xAE, 0x42, 0x60, 0x82,
+];
+
+/// Relationship IDs a slide uses to reference an embedded audio clip
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MediaRelIds {
+    /// `audio` relationship (`a:audioFile r:link`)
+    pub audio: String,
+    /// `media` relationship (`p14:media r:embed`)
+    pub media: String,
+    /// Icon image relationship (`a:blip r:embed`)
+    pub image: String,
+}
+
+/// Narration audio played automatically when a slide is shown
+#[derive(Clone, Debug, PartialEq)]
+pub struct Narration {
+    /// Encoded audio bytes
+    pub data: Vec<u8>,
+    /// Audio format
+    pub format: AudioFormat,
+    /// Clip duration in milliseconds; detected from the data when `None`
+    pub duration_ms: Option<u32>,
+}
+
+impl Narration {
+    /// Create narration from encoded audio bytes
+    pub fn new(data: Vec<u8>, format: AudioFormat) -> Self {
+        Narration {
+            data,
+            format,
+            duration_ms: None,
+        }
+    }
+
+    /// Set the clip duration explicitly
+    pub fn with_duration_ms(mut self, ms: u32) -> Self {
+        self.duration_ms = Some(ms);
+        self
+    }
+
+    /// Clip duration in milliseconds
+    ///
+    /// Uses the explicit duration if set, otherwise reads it from WAV headers
+    /// or estimates it from the first MP3 frame (exact for constant bitrate).
+    pub fn clip_duration_ms(&self) -> Option<u32> {
+        self.duration_ms.or_else(|| match self.format {
+            AudioFormat::Wav => wav_duration_ms(&self.data),
+            AudioFormat::Mp3 => mp3_duration_ms(&self.data),
+            _ => None,
+        })
+    }
+}
+
+/// Read the duration of a PCM WAV file from its `fmt ` and `data` chunks
+fn wav_duration_ms(data: &[u8]) -> Option<u32> {
+    if data.len() < 12 || &data[0..4] != b"RIFF" || &data[8..12] != b"WAVE" {
+        return None;
+    }
+
+    let mut byte_rate = None;
+    let mut pos = 12;
+    while pos + 8 <= data.len() {
+        let id = &data[pos..pos + 4];
+        let size = u32::from_le_bytes(data[pos + 4..pos + 8].try_into().ok()?) as usize;
+        let body = pos + 8;
+        if id == b"fmt " && body + 12 <= data.len() {
+            byte_rate = Some(u32::from_le_bytes(data[body + 8..body + 12].try_into().ok()?));
+        } else if id == b"data" {
+            let rate = byte_rate.filter(|r| *r > 0)?;
+            let len = size.min(data.len() - body) as u64;
+            return Some((len * 1000 / rate as u64) as u32);
+        }
+        pos = body + size + (size & 1);
+    }
+    None
+}
+
+/// Estimate MP3 duration from the first frame header (constant bitrate)
+fn mp3_duration_ms(data: &[u8]) -> Option<u32> {
+    // Skip an ID3v2 tag (10-byte header + syncsafe size)
+    let mut start = 0;
+    if data.len() >= 10 && &data[0..3] == b"ID3" {
+        let size = data[6..10].iter().fold(0usize, |acc, b| (acc << 7) | (*b & 0x7F) as usize);
+        start = 10 + size;
+    }
+
+    let frame = (start..data.len().saturating_sub(3))
+        .find(|&i| data[i] == 0xFF && data[i + 1] & 0xE0 == 0xE0)?;
+    let version = (data[frame + 1] >> 3) & 0x03; // 3 = MPEG1
+    let layer = (data[frame + 1] >> 1) & 0x03; // 1 = Layer III
+    let bitrate_index = (data[frame + 2] >> 4) as usize;
+    if layer != 1 || version == 1 || bitrate_index == 0 || bitrate_index == 15 {
+        return None;
+    }
+
+    const MPEG1_L3: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+    const MPEG2_L3: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+    let kbps = if version == 3 { MPEG1_L3[bitrate_index] } else { MPEG2_L3[bitrate_index] };
+
+    let audio_bytes = (data.len() - frame) as u64;
+    Some((audio_bytes * 8 / kbps as u64) as u32)
+}
+
+/// Generate the picture element for a narration clip
+///
+/// The icon sits in the bottom-right corner and is hidden during the show.
+pub fn generate_narration_xml(shape_id: usize, rel_ids: &MediaRelIds) -> String {
+    format!(
+        r#"<p:pic>
+<p:nvPicPr>
+<p:cNvPr id="{id}" name="Narration {id}" descr="Narration">
+<a:hlinkClick r:id="" action="ppaction://media"/>
+</p:cNvPr>
+<p:cNvPicPr>
+<a:picLocks noChangeAspect="1"/>
+</p:cNvPicPr>
+<p:nvPr>
+<a:audioFile r:link="{audio}"/>
+<p:extLst>
+<p:ext uri="{{DAA4B4D4-6D71-4841-9C94-3DE7FCFB9230}}">
+<p14:media xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" r:embed="{media}"/>
+</p:ext>
+</p:extLst>
+</p:nvPr>
+</p:nvPicPr>
+<p:blipFill>
+<a:blip r:embed="{image}"/>
+<a:stretch>
+<a:fillRect/>
+</a:stretch>
+</p:blipFill>
+<p:spPr>
+<a:xfrm>
+<a:off x="8534400" y="6248400"/>
+<a:ext cx="304800" cy="304800"/>
+</a:xfrm>
+<a:prstGeom prst="rect">
+<a:avLst/>
+</a:prstGeom>
+</p:spPr>
+</p:pic>"#,
+        id = shape_id,
+        audio = escape_xml_attr(&rel_ids.audio),
+        media = escape_xml_attr(&rel_ids.media),
+        image = escape_xml_attr(&rel_ids.image),
+    )
+}
+
+/// Generate slide timing that starts a narration clip as soon as the slide
+/// appears and keeps its icon hidden
+pub fn generate_narration_timing_xml(shape_id: usize, duration_ms: Option<u32>) -> String {
+    let dur = duration_ms.map_or_else(|| "indefinite".to_string(), |ms| ms.to_string());
+    format!(
+        r#"<p:timing>
+<p:tnLst>
+<p:par>
+<p:cTn id="1" dur="indefinite" restart="never" nodeType="tmRoot">
+<p:childTnLst>
+<p:par>
+<p:cTn id="2" fill="hold">
+<p:stCondLst>
+<p:cond delay="0"/>
+</p:stCondLst>
+<p:childTnLst>
+<p:par>
+<p:cTn id="3" presetID="1" presetClass="mediacall" presetSubtype="0" fill="hold" nodeType="withEffect">
+<p:stCondLst>
+<p:cond delay="0"/>
+</p:stCondLst>
+<p:childTnLst>
+<p:cmd type="call" cmd="playFrom(0.0)">
+<p:cBhvr>
+<p:cTn id="4" dur="{dur}" fill="hold"/>
+<p:tgtEl>
+<p:spTgt spid="{id}"/>
+</p:tgtEl>
+</p:cBhvr>
+</p:cmd>
+</p:childTnLst>
+</p:cTn>
+</p:par>
+</p:childTnLst>
+</p:cTn>
+</p:par>
+<p:audio>
+<p:cMediaNode vol="80000" showWhenStopped="0">
+<p:cTn id="5" fill="hold" display="0">
+<p:stCondLst>
+<p:cond delay="indefinite"/>
+</p:stCondLst>
+<p:endCondLst>
+<p:cond evt="onStopAudio" delay="0">
+<p:tgtEl>
+<p:sldTgt/>
+</p:tgtEl>
+</p:cond>
+</p:endCondLst>
+</p:cTn>
+<p:tgtEl>
+<p:spTgt spid="{id}"/>
+</p:tgtEl>
+</p:cMediaNode>
+</p:audio>
+</p:childTnLst>
+</p:cTn>
+</p:par>
+</p:tnLst>
+</p:timing>"#,
+        id = shape_id,
+        dur = dur,
+    )
+}
+
 /// Generate content type for video
 pub fn video_content_type(format: VideoFormat) -> String {
     format!(
@@ -546,6 +767,62 @@ mod tests {
         assert!(xml.contains("videoFile"));
     }
 
+    fn wav_bytes(byte_rate: u32, data_len: usize) -> Vec<u8> {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&((36 + data_len) as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&(byte_rate / 2).to_le_bytes()); // sample rate
+        wav.extend_from_slice(&byte_rate.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data_len as u32).to_le_bytes());
+        wav.extend(std::iter::repeat_n(0u8, data_len));
+        wav
+    }
+
+    #[test]
+    fn test_narration_wav_duration() {
+        let narration = Narration::new(wav_bytes(16000, 24000), AudioFormat::Wav);
+        assert_eq!(narration.clip_duration_ms(), Some(1500));
+        assert_eq!(narration.with_duration_ms(900).clip_duration_ms(), Some(900));
+    }
+
+    #[test]
+    fn test_narration_mp3_duration() {
+        // MPEG1 Layer III, 128 kbps, 44.1 kHz frame header behind a small ID3 tag
+        let mut mp3 = b"ID3\x03\x00\x00\x00\x00\x00\x04TAG!".to_vec();
+        mp3.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x64]);
+        mp3.resize(14 + 32000, 0);
+        let narration = Narration::new(mp3, AudioFormat::Mp3);
+        assert_eq!(narration.clip_duration_ms(), Some(2000));
+
+        let unknown = Narration::new(vec![0; 100], AudioFormat::Ogg);
+        assert_eq!(unknown.clip_duration_ms(), None);
+    }
+
+    #[test]
+    fn test_generate_narration_xml() {
+        let rel_ids = MediaRelIds {
+            audio: "rId2".to_string(),
+            media: "rId3".to_string(),
+            image: "rId4".to_string(),
+        };
+        let xml = generate_narration_xml(12, &rel_ids);
+        assert!(xml.contains(r#"<a:audioFile r:link="rId2"/>"#));
+        assert!(xml.contains(r#"r:embed="rId3""#));
+        assert!(xml.contains(r#"<a:blip r:embed="rId4"/>"#));
+
+        let timing = generate_narration_timing_xml(12, Some(4500));
+        assert!(timing.contains(r#"<p:spTgt spid="12"/>"#));
+        assert!(timing.contains(r#"dur="4500""#));
+        assert!(timing.contains(r#"showWhenStopped="0""#));
+    }
+
     #[test]
     fn test_generate_audio_xml() {
         let audio = Audio::new("audio.mp3", AudioFormat::Mp3, 0, 0, 500000, 500000);
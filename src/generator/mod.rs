@@ -24,6 +24,9 @@ pub mod text;
 
 pub mod shapes;
 pub mod shapes_xml;
+pub mod geometry;
+pub mod icons;
+pub mod annotations;
 
 // Table module (modularized)
 pub mod table;
@@ -33,6 +36,17 @@ pub mod tables_xml;
 
 pub mod images;
 pub mod images_xml;
+pub mod image_grid;
+pub mod heatmap;
+pub mod quadrant;
+pub mod swimlanes;
+pub mod calendar;
+pub mod funnel;
+pub mod stepper;
+pub mod table_rules;
+pub mod table_columns;
+#[cfg(feature = "qr")]
+pub mod qr;
 
 // Charts module (modularized)
 #[path = "charts/mod.rs"]
@@ -46,27 +60,93 @@ pub mod slide;
 
 // New element modules
 pub mod connectors;
+pub mod routing;
+pub mod stable_ids;
 pub mod hyperlinks;
 pub mod gradients;
 pub mod media;
-
-pub use builder::{create_pptx, create_pptx_with_content};
+pub mod zoom;
+pub mod slide_number;
+pub mod locks;
+pub mod provenance;
+pub mod tags;
+pub mod print;
+pub mod placement;
+pub mod validate;
+pub mod snippet;
+pub mod csv;
+pub mod xlsx;
+pub mod viz;
+pub mod slide_spec;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+pub mod progress;
+pub mod warnings;
+pub mod compat;
+pub mod ir;
+
+pub use builder::{
+    create_pptx, create_pptx_with_content, create_pptx_with_print_settings, create_pptx_with_progress,
+    write_pptx_with_content, write_pptx_with_progress,
+};
+pub use progress::{BuildProgress, CancelToken, BuildCancelled, BudgetExceeded};
+pub use warnings::{Warning, WarningKind, Warnings, GenerationOutput};
+pub use compat::CompatProfile;
+pub use placement::{SlideSize, Anchor, Placement};
+pub use validate::ValidationError;
+pub use snippet::Snippet;
+pub use csv::{CsvOptions, CsvColumn, CsvData};
+pub use xlsx::{Workbook, SheetRange};
+pub use viz::{VizSpec, Visualization};
+pub use slide_spec::{SlideSpec, BulletSpec};
+#[cfg(feature = "polars")]
+pub use dataframe::NumberFormat;
+pub use print::{PrintSettings, PrintWhat, PrintColorMode, create_pres_props_xml};
+pub use zoom::{SlideZoom, generate_slide_zoom_xml, generate_zoom_thumbnail, generate_zoom_thumbnail_with_size};
+pub use locks::Locks;
+pub use theme_xml::ThemeFonts;
+pub use provenance::Provenance;
+pub use tags::Tags;
+pub use slide_number::{SlideNumbering, SlideNumberFormat, SlideNumber, generate_slide_number_xml};
 pub use notes_xml::{create_notes_xml, create_notes_rels_xml, create_notes_master_xml, create_notes_master_rels_xml};
 pub use xml::{SlideContent, SlideLayout};
-pub use slide_content::{CodeBlock, BulletStyle, BulletPoint, BulletTextFormat, TransitionType};
-pub use text::{TextFormat, FormattedText, TextFrame, Paragraph, Run, TextAlign, TextAnchor};
+pub use slide_content::{CodeBlock, Density, BulletStyle, BulletPoint, BulletTextFormat, TransitionType, MorphOption, Region, CustomLayout, PicturePlaceholder};
+pub use text::{TextFormat, FormattedText, TextFrame, Paragraph, Run, TextAlign, TextAnchor, Field, DateTimeFormat};
 pub use shapes::{Shape, ShapeType, ShapeFill, ShapeLine, GradientFill as ShapeGradientFill, GradientStop as ShapeGradientStop, GradientDirection as ShapeGradientDirection, FillType, emu_to_inches, inches_to_emu, cm_to_emu};
-pub use shapes_xml::{generate_shape_xml, generate_shapes_xml, generate_connector_xml};
-pub use tables::{Table, TableRow, TableCell, TableBuilder, CellAlign, CellVAlign};
-pub use images::{Image, ImageBuilder, ImageSource};
+pub use shapes_xml::{generate_shape_xml, write_shape_xml, generate_shapes_xml, generate_connector_xml};
+pub use tables::{Table, TableRow, TableCell, TableBuilder, CellAlign, CellVAlign, CellMargins, TextDirection};
+pub use images::{Image, ImageBuilder, ImageSource, ImagePlaceholder, Fit};
+pub use image_grid::ImageGrid;
+pub use heatmap::{Heatmap, Palette};
+pub use quadrant::{QuadrantChart, QuadrantPoint};
+pub use swimlanes::{Swimlanes, SwimlaneStep};
+pub use calendar::Calendar;
+pub use funnel::{Funnel, FunnelStage};
+pub use stepper::{Stepper, StepState};
+pub use routing::{route_connectors, route_orthogonal};
+pub use stable_ids::assign_stable_ids;
+pub use table_rules::{RowFormat, RowValues};
+pub use table_columns::Column;
+pub use geometry::{CustomGeometry, GeometryPath, PathCommand};
+pub use icons::Icon;
+pub use annotations::Annotations;
+#[cfg(feature = "qr")]
+pub use qr::QrCode;
 pub use images_xml::{generate_image_xml, generate_image_relationship, generate_image_content_type};
-pub use charts::{Chart, ChartType, ChartSeries, ChartBuilder, generate_chart_part_xml, generate_chart_ref_xml};
+pub use charts::{Chart, ChartType, ChartSeries, ChartBuilder, ChartStyle, ChartTemplate, ChartDataSource, ReferenceLine, TargetBand, LegendPosition, LegendOverflow, DataTable, DisplayUnit, generate_chart_part_xml, generate_chart_ref_xml};
 
 // New element exports
 pub use connectors::{Connector, ConnectorType, ConnectorLine, ArrowType, ArrowSize, ConnectionSite, LineDash, generate_connector_xml as generate_cxn_xml};
-pub use hyperlinks::{Hyperlink, HyperlinkAction, generate_text_hyperlink_xml, generate_shape_hyperlink_xml, generate_hyperlink_relationship_xml};
+pub use hyperlinks::{
+    Hyperlink, HyperlinkAction, ActionSound, ActionRelIds,
+    generate_text_hyperlink_xml, generate_shape_hyperlink_xml, generate_shape_hover_xml,
+    generate_hyperlink_relationship_xml,
+};
 pub use gradients::{GradientFill, GradientType, GradientDirection, GradientStop, PresetGradients, generate_gradient_fill_xml};
-pub use media::{Video, Audio, VideoFormat, AudioFormat, VideoOptions, AudioOptions, generate_video_xml, generate_audio_xml};
+pub use media::{
+    Video, Audio, VideoFormat, AudioFormat, VideoOptions, AudioOptions, Narration,
+    generate_video_xml, generate_audio_xml,
+};
 
 #[cfg(test)]
 mod tests {
@@ -1,13 +1,7 @@
 //! Package-level XML generation (content types, relationships, presentation)
 
-/// Escape special XML characters
-pub fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
+pub use crate::core::escape_xml;
+use super::placement::SlideSize;
 
 /// Create [Content_Types].xml
 pub fn create_content_types_xml(slides: usize) -> String {
@@ -61,8 +55,47 @@ pub fn create_presentation_rels_xml(slides: usize) -> String {
     xml
 }
 
+/// `p:sldId/@id` of a slide in presentation.xml (1-based slide number)
+pub fn slide_id(slide_num: usize) -> usize {
+    256 + slide_num
+}
+
+/// `p14:creationId` of the slide whose `p:sldId/@id` is `slide_id`
+///
+/// PowerPoint identifies slide zoom targets by this ID as well as by the
+/// slide ID. Generated IDs are derived from the slide ID, so they are
+/// stable and unique within a deck (multiplying by an odd constant is a
+/// bijection on `u32`).
+pub fn slide_creation_id(slide_id: usize) -> u32 {
+    (slide_id as u32).wrapping_mul(0x9E37_79B1)
+}
+
+/// `p:extLst` recording a slide's creation ID, the last child of `p:sld`
+pub fn slide_creation_id_xml(slide_id: usize) -> String {
+    format!(
+        r#"<p:extLst><p:ext uri="{{BB962C8B-B14F-4D97-AF65-F5344CB8AC3E}}"><p14:creationId xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main" val="{}"/></p:ext></p:extLst>"#,
+        slide_creation_id(slide_id)
+    )
+}
+
 /// Create ppt/presentation.xml
-pub fn create_presentation_xml(_title: &str, slides: usize) -> String {
+pub fn create_presentation_xml(title: &str, slides: usize) -> String {
+    create_presentation_xml_with_size(title, slides, SlideSize::STANDARD)
+}
+
+/// Create ppt/presentation.xml for a given slide size
+pub fn create_presentation_xml_with_size(title: &str, slides: usize, size: SlideSize) -> String {
+    create_presentation_xml_numbered(title, slides, size, 1)
+}
+
+/// Create ppt/presentation.xml whose first slide is numbered `first_slide_num`
+pub fn create_presentation_xml_numbered(_title: &str, slides: usize, size: SlideSize, first_slide_num: u32) -> String {
+    let ids: Vec<usize> = (1..=slides).map(slide_id).collect();
+    create_presentation_xml_with_ids(&ids, size, first_slide_num)
+}
+
+/// Create ppt/presentation.xml with one `p:sldId` per entry of `slide_ids`
+pub fn create_presentation_xml_with_ids(slide_ids: &[usize], size: SlideSize, first_slide_num: u32) -> String {
     let mut xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" saveSubsetFonts="1">
 <p:sldMasterIdLst>
@@ -70,17 +103,20 @@ pub fn create_presentation_xml(_title: &str, slides: usize) -> String {
 </p:sldMasterIdLst>
 <p:sldIdLst>"#.to_string();
 
-    for i in 1..=slides {
-        let id = 256 + i;
-        let rid = i + 2;
+    for (i, id) in slide_ids.iter().enumerate() {
+        let rid = i + 3;
         xml.push_str(&format!("\n<p:sldId id=\"{id}\" r:id=\"rId{rid}\"/>"));
     }
 
+    xml.push_str("\n</p:sldIdLst>\n");
+    let preset = size.preset_name().map(|name| format!(" type=\"{name}\"")).unwrap_or_default();
+    xml.push_str(&format!("<p:sldSz cx=\"{}\" cy=\"{}\"{preset}/>", size.width, size.height));
     xml.push_str(r#"
-</p:sldIdLst>
-<p:sldSz cx="9144000" cy="6858000" type="screen4x3"/>
 <p:notesSz cx="6858000" cy="9144000"/>
 </p:presentation>"#);
+    if first_slide_num != 1 {
+        xml = xml.replacen(" saveSubsetFonts=\"1\"", &format!(" firstSlideNum=\"{first_slide_num}\" saveSubsetFonts=\"1\""), 1);
+    }
     xml
 }
 
@@ -118,6 +154,46 @@ pub fn create_content_types_xml_with_notes_and_charts(
         }
     }
 
+    // Add media defaults for embedded narration (audio clips plus icon), action
+    // sounds and zoom thumbnails
+    if let Some(slides_vec) = custom_slides {
+        let mut formats: Vec<super::media::AudioFormat> = slides_vec.iter()
+            .filter_map(|s| s.narration.as_ref())
+            .map(|n| n.format)
+            .collect();
+        let has_narration = !formats.is_empty();
+        let has_action_sounds = slides_vec.iter()
+            .flat_map(|s| &s.shapes)
+            .flat_map(|shape| shape.hyperlink.iter().chain(&shape.hover))
+            .any(|h| h.sound.is_some());
+        if has_action_sounds {
+            formats.push(super::media::AudioFormat::Wav);
+        }
+        let mut extensions: Vec<&str> = Vec::new();
+        for format in formats {
+            if !extensions.contains(&format.extension()) {
+                extensions.push(format.extension());
+                xml.push('\n');
+                xml.push_str(&super::media::audio_content_type(format));
+            }
+        }
+        let has_zooms = slides_vec.iter().any(|s| !s.slide_zooms.is_empty());
+        let mut image_extensions: Vec<String> = Vec::new();
+        if has_narration || has_zooms {
+            image_extensions.push("png".to_string());
+        }
+        for image in slides_vec.iter().flat_map(|s| &s.images).filter(|image| image.is_loaded()) {
+            let extension = image.extension();
+            if !image_extensions.contains(&extension) {
+                image_extensions.push(extension);
+            }
+        }
+        for extension in image_extensions {
+            xml.push('\n');
+            xml.push_str(&super::images_xml::generate_image_content_type(&extension));
+        }
+    }
+
     // Add chart content types
     for i in 1..=chart_count {
         xml.push_str(&format!(
@@ -161,6 +237,25 @@ pub fn create_presentation_rels_xml_with_notes(slides: usize) -> String {
 
 /// Create slide relationship XML with notes and charts
 pub fn create_slide_rels_xml_extended(slide_num: usize, has_notes: bool, chart_rels: &[(String, String)]) -> String {
+    create_slide_rels_xml_with_media(slide_num, has_notes, chart_rels, None, &[], &[], &[])
+}
+
+/// Create slide relationship XML with notes, charts, an embedded audio clip,
+/// slide zooms and shape actions
+///
+/// `audio` pairs the relationship IDs with the media target (e.g. `../media/narration1.mp3`)
+/// and the icon target. `zooms` pairs each zoom's IDs with its target slide
+/// number and thumbnail target. `actions` pairs each shape action with its
+/// IDs and sound target (empty when it has no sound).
+pub fn create_slide_rels_xml_with_media(
+    slide_num: usize,
+    has_notes: bool,
+    chart_rels: &[(String, String)],
+    audio: Option<(&super::media::MediaRelIds, &str, &str)>,
+    zooms: &[(&super::zoom::ZoomRelIds, usize, String)],
+    actions: &[(&super::hyperlinks::Hyperlink, &super::hyperlinks::ActionRelIds, String)],
+    images: &[(String, String)],
+) -> String {
     let mut xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
 <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>"#.to_string();
@@ -179,6 +274,50 @@ pub fn create_slide_rels_xml_extended(slide_num: usize, has_notes: bool, chart_r
         ));
     }
 
+    if let Some((rel_ids, media_target, icon_target)) = audio {
+        xml.push_str(&format!(
+            "\n<Relationship Id=\"{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio\" Target=\"{}\"/>",
+            rel_ids.audio, media_target
+        ));
+        xml.push_str(&format!(
+            "\n<Relationship Id=\"{}\" Type=\"http://schemas.microsoft.com/office/2007/relationships/media\" Target=\"{}\"/>",
+            rel_ids.media, media_target
+        ));
+        xml.push_str(&format!(
+            "\n<Relationship Id=\"{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/image\" Target=\"{}\"/>",
+            rel_ids.image, icon_target
+        ));
+    }
+
+    for (rel_ids, target_slide, thumbnail_target) in zooms {
+        xml.push_str(&format!(
+            "\n<Relationship Id=\"{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide\" Target=\"slide{}.xml\"/>",
+            rel_ids.slide, target_slide
+        ));
+        xml.push_str(&format!(
+            "\n<Relationship Id=\"{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/image\" Target=\"{}\"/>",
+            rel_ids.image, thumbnail_target
+        ));
+    }
+
+    for (hyperlink, rel_ids, sound_target) in actions {
+        if let Some(link) = &rel_ids.link {
+            xml.push('\n');
+            xml.push_str(&super::hyperlinks::generate_hyperlink_relationship_xml(hyperlink, link));
+        }
+        if let Some(sound) = &rel_ids.sound {
+            xml.push_str(&format!(
+                "\n<Relationship Id=\"{}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio\" Target=\"{}\"/>",
+                sound, sound_target
+            ));
+        }
+    }
+
+    for (rid, target) in images {
+        xml.push('\n');
+        xml.push_str(&super::images_xml::generate_image_relationship_with_rid(rid, target));
+    }
+
     xml.push_str("\n</Relationships>");
     xml
 }
@@ -0,0 +1,173 @@
+//! Slide size and size-independent placement
+//!
+//! A [`Placement`] describes where a shape goes relative to the slide
+//! (`Position::percent(0.1, 0.5)`, `Anchor::TopRight.offset(Inches(0.2), Inches(0.2))`)
+//! instead of as fixed EMU. Placements are resolved against the
+//! presentation's [`SlideSize`] when it is built, so the same slide code
+//! works on 4:3 and 16:9 decks.
+
+use crate::core::Emu;
+use crate::elements::Position;
+
+/// Slide dimensions (`p:sldSz`)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SlideSize {
+    pub width: Emu,
+    pub height: Emu,
+}
+
+impl SlideSize {
+    /// 10" × 7.5" (4:3), the generator's default
+    pub const STANDARD: SlideSize = SlideSize { width: Emu(9144000), height: Emu(6858000) };
+    /// 13.333" × 7.5" (16:9), PowerPoint's default for new decks
+    pub const WIDESCREEN: SlideSize = SlideSize { width: Emu(12192000), height: Emu(6858000) };
+
+    /// Custom slide size
+    pub fn new(width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
+        SlideSize { width: width.into(), height: height.into() }
+    }
+
+    /// Map `(x, y, width, height)` laid out on the standard slide onto this one
+    ///
+    /// Built-in layouts and slide regions are designed for 10" × 7.5" and
+    /// stretch proportionally, margins included, to other slide sizes.
+    pub fn scale_bounds(&self, (x, y, width, height): (u32, u32, u32, u32)) -> (u32, u32, u32, u32) {
+        let sx = |v: u32| scale(v, self.width, Self::STANDARD.width);
+        let sy = |v: u32| scale(v, self.height, Self::STANDARD.height);
+        (sx(x), sy(y), sx(width), sy(height))
+    }
+
+    /// `type` attribute of `p:sldSz`, if the size is a named preset
+    pub fn preset_name(&self) -> Option<&'static str> {
+        (*self == Self::STANDARD).then_some("screen4x3")
+    }
+}
+
+impl Default for SlideSize {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// `value * to / from`, clamped to the `u32` range
+fn scale(value: u32, to: Emu, from: Emu) -> u32 {
+    (i128::from(value) * i128::from(to.get()) / i128::from(from.get().max(1))).clamp(0, u32::MAX.into()) as u32
+}
+
+/// Point of the slide an [`Anchor::offset`] placement is measured from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Place an element `dx`/`dy` inward from this anchor
+    ///
+    /// The element's matching corner or edge sits on the anchor, so
+    /// `TopRight.offset(Inches(0.2), Inches(0.2))` keeps a 0.2" margin to the
+    /// top and right slide edges. For centered axes the offset moves the
+    /// element right/down.
+    pub fn offset(self, dx: impl Into<Emu>, dy: impl Into<Emu>) -> Placement {
+        Placement::Anchored { anchor: self, dx: dx.into(), dy: dy.into() }
+    }
+
+    /// Horizontal and vertical alignment as 0 (start), 1 (center) or 2 (end)
+    fn alignment(self) -> (i64, i64) {
+        match self {
+            Anchor::TopLeft => (0, 0),
+            Anchor::Top => (1, 0),
+            Anchor::TopRight => (2, 0),
+            Anchor::Left => (0, 1),
+            Anchor::Center => (1, 1),
+            Anchor::Right => (2, 1),
+            Anchor::BottomLeft => (0, 2),
+            Anchor::Bottom => (1, 2),
+            Anchor::BottomRight => (2, 2),
+        }
+    }
+}
+
+/// Position relative to the slide, resolved at build time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    /// Top-left corner at fractions (0.0–1.0) of the slide width and height
+    Percent { x: f64, y: f64 },
+    /// Offset inward from an anchor point
+    Anchored { anchor: Anchor, dx: Emu, dy: Emu },
+}
+
+impl Placement {
+    /// Resolve the top-left corner of an element of the given size
+    pub fn resolve(&self, slide: SlideSize, width: Emu, height: Emu) -> (Emu, Emu) {
+        match *self {
+            Placement::Percent { x, y } => (
+                Emu((slide.width.get() as f64 * x).round() as i64),
+                Emu((slide.height.get() as f64 * y).round() as i64),
+            ),
+            Placement::Anchored { anchor, dx, dy } => {
+                let (h, v) = anchor.alignment();
+                (
+                    align(h, slide.width, width, dx),
+                    align(v, slide.height, height, dy),
+                )
+            }
+        }
+    }
+}
+
+/// Start of an element of length `size` aligned within `extent`
+fn align(alignment: i64, extent: Emu, size: Emu, offset: Emu) -> Emu {
+    match alignment {
+        0 => offset,
+        1 => (extent - size) / 2 + offset,
+        _ => extent - size - offset,
+    }
+}
+
+impl Position {
+    /// Place an element at fractions (0.0–1.0) of the slide width and height
+    ///
+    /// Resolved against the presentation's slide size at build time; see
+    /// [`Shape::at`](crate::generator::Shape::at).
+    pub fn percent(x: f64, y: f64) -> Placement {
+        Placement::Percent { x, y }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Inches;
+
+    #[test]
+    fn test_percent_follows_slide_size() {
+        let placement = Position::percent(0.1, 0.5);
+        let size = Emu(100);
+        assert_eq!(placement.resolve(SlideSize::STANDARD, size, size), (Emu(914400), Emu(3429000)));
+        assert_eq!(placement.resolve(SlideSize::WIDESCREEN, size, size), (Emu(1219200), Emu(3429000)));
+    }
+
+    #[test]
+    fn test_anchor_offsets_are_inward() {
+        let (w, h) = (Emu(1000000), Emu(500000));
+        let top_right = Anchor::TopRight.offset(Inches(0.2), Inches(0.2));
+        assert_eq!(
+            top_right.resolve(SlideSize::WIDESCREEN, w, h),
+            (Emu(12192000 - 1000000 - 182880), Emu(182880))
+        );
+
+        let center = Anchor::Center.offset(Emu(0), Emu(0));
+        assert_eq!(center.resolve(SlideSize::STANDARD, w, h), (Emu(4072000), Emu(3179000)));
+
+        let bottom_left = Anchor::BottomLeft.offset(Emu(10), Emu(20));
+        assert_eq!(bottom_left.resolve(SlideSize::STANDARD, w, h), (Emu(10), Emu(6858000 - 500000 - 20)));
+    }
+}
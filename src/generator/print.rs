@@ -0,0 +1,168 @@
+//! Print settings
+//!
+//! Print settings are stored in the presentation properties part
+//! (`ppt/presProps.xml`) as `p:prnPr`. PowerPoint uses them as the defaults
+//! for its print dialog and for PDF export through automation, so a deck
+//! meant to be distributed as 6-up grayscale handouts can carry that intent.
+
+/// What PowerPoint prints (`p:prnPr/@prnWhat`)
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum PrintWhat {
+    /// One slide per page
+    #[default]
+    Slides,
+    /// Handouts with 1 slide per page
+    Handouts1,
+    /// Handouts with 2 slides per page
+    Handouts2,
+    /// Handouts with 3 slides per page
+    Handouts3,
+    /// Handouts with 4 slides per page
+    Handouts4,
+    /// Handouts with 6 slides per page
+    Handouts6,
+    /// Handouts with 9 slides per page
+    Handouts9,
+    /// Notes pages
+    Notes,
+    /// Outline view
+    Outline,
+}
+
+impl PrintWhat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrintWhat::Slides => "slides",
+            PrintWhat::Handouts1 => "handouts1",
+            PrintWhat::Handouts2 => "handouts2",
+            PrintWhat::Handouts3 => "handouts3",
+            PrintWhat::Handouts4 => "handouts4",
+            PrintWhat::Handouts6 => "handouts6",
+            PrintWhat::Handouts9 => "handouts9",
+            PrintWhat::Notes => "notes",
+            PrintWhat::Outline => "outline",
+        }
+    }
+}
+
+/// Print color mode (`p:prnPr/@clrMode`)
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum PrintColorMode {
+    /// Full color
+    #[default]
+    Color,
+    /// Grayscale
+    Grayscale,
+    /// Pure black and white
+    BlackAndWhite,
+}
+
+impl PrintColorMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PrintColorMode::Color => "clr",
+            PrintColorMode::Grayscale => "gray",
+            PrintColorMode::BlackAndWhite => "bw",
+        }
+    }
+}
+
+/// Default print settings for a presentation
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct PrintSettings {
+    /// What to print
+    pub what: PrintWhat,
+    /// Color mode
+    pub color_mode: PrintColorMode,
+    /// Draw a thin frame around each slide
+    pub frame_slides: bool,
+    /// Scale slides to fit the paper
+    pub scale_to_fit_paper: bool,
+    /// Include hidden slides
+    pub hidden_slides: bool,
+}
+
+impl PrintSettings {
+    /// Create print settings for full-color slides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Print handouts with 6 slides per page
+    pub fn handouts_6_up() -> Self {
+        Self::new().what(PrintWhat::Handouts6)
+    }
+
+    /// Set what to print
+    pub fn what(mut self, what: PrintWhat) -> Self {
+        self.what = what;
+        self
+    }
+
+    /// Set color mode
+    pub fn color_mode(mut self, color_mode: PrintColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Print in grayscale
+    pub fn grayscale(self) -> Self {
+        self.color_mode(PrintColorMode::Grayscale)
+    }
+
+    /// Frame each slide
+    pub fn frame_slides(mut self, frame: bool) -> Self {
+        self.frame_slides = frame;
+        self
+    }
+
+    /// Scale slides to fit the paper
+    pub fn scale_to_fit_paper(mut self, scale: bool) -> Self {
+        self.scale_to_fit_paper = scale;
+        self
+    }
+
+    /// Include hidden slides
+    pub fn hidden_slides(mut self, include: bool) -> Self {
+        self.hidden_slides = include;
+        self
+    }
+
+    /// Generate the `p:prnPr` element
+    pub fn to_xml(&self) -> String {
+        format!(
+            r#"<p:prnPr prnWhat="{}" clrMode="{}" hiddenSlides="{}" scaleToFitPaper="{}" frameSlides="{}"/>"#,
+            self.what.as_str(),
+            self.color_mode.as_str(),
+            self.hidden_slides as u8,
+            self.scale_to_fit_paper as u8,
+            self.frame_slides as u8,
+        )
+    }
+}
+
+/// Create presentation properties XML (ppt/presProps.xml)
+pub fn create_pres_props_xml(print: &PrintSettings) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentationPr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+{}
+</p:presentationPr>"#,
+        print.to_xml()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handout_print_settings_xml() {
+        let print = PrintSettings::handouts_6_up().grayscale().frame_slides(true);
+        assert_eq!(
+            print.to_xml(),
+            r#"<p:prnPr prnWhat="handouts6" clrMode="gray" hiddenSlides="0" scaleToFitPaper="0" frameSlides="1"/>"#
+        );
+        assert!(create_pres_props_xml(&print).contains("<p:presentationPr"));
+    }
+}
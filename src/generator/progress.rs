@@ -0,0 +1,246 @@
+//! Progress reporting and cancellation for long builds
+//!
+//! Generation is synchronous and CPU-bound. In an async web service, run it
+//! on a blocking thread and share a [`CancelToken`] with the request. A token
+//! can also carry a deadline, which bounds runaway conversions (e.g. huge
+//! Mermaid graphs in `cli::markdown::parse_markdown_with_cancel`):
+//!
+//! ```rust,ignore
+//! let token = CancelToken::with_timeout(Duration::from_secs(30));
+//! let build_token = token.clone();
+//! let pres = pres.clone();
+//! let handle = tokio::task::spawn_blocking(move || {
+//!     pres.build_with_progress(
+//!         BuildProgress::new()
+//!             .on_progress(|done, total| tracing::info!("slide {done}/{total}"))
+//!             .cancel_token(build_token),
+//!     )
+//! });
+//! // On client disconnect: token.cancel();
+//! ```
+//!
+//! A [`BuildProgress::memory_budget`] caps the bytes of slide XML and
+//! embedded media a single build may produce, so one oversized user deck
+//! cannot exhaust a multi-tenant service.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Shared flag (and optional deadline) that asks a running build to stop
+///
+/// Clones share the same flag, so one can be kept by the request handler
+/// while another is moved into the build.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    /// Create a token that is not cancelled and never expires
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a token that expires at `deadline`
+    pub fn with_deadline(deadline: Instant) -> Self {
+        CancelToken { deadline: Some(deadline), ..Self::default() }
+    }
+
+    /// Create a token that expires `timeout` from now
+    ///
+    /// Not for `wasm32-unknown-unknown`, where `Instant::now` is unavailable.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    /// Ask the build to stop at its next checkpoint
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether cancellation has been requested or the deadline has passed
+    pub fn is_cancelled(&self) -> bool {
+        self.check().is_err()
+    }
+
+    /// `Ok` while work may continue, otherwise why it must stop
+    pub fn check(&self) -> Result<(), BuildCancelled> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            Err(BuildCancelled::Requested)
+        } else if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            Err(BuildCancelled::DeadlineExceeded)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Error returned when a build is stopped through its [`CancelToken`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BuildCancelled {
+    /// [`CancelToken::cancel`] was called
+    Requested,
+    /// The token's deadline passed
+    DeadlineExceeded,
+}
+
+impl fmt::Display for BuildCancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildCancelled::Requested => f.write_str("build cancelled"),
+            BuildCancelled::DeadlineExceeded => f.write_str("build deadline exceeded"),
+        }
+    }
+}
+
+impl std::error::Error for BuildCancelled {}
+
+impl From<BuildCancelled> for crate::exc::PptxError {
+    fn from(reason: BuildCancelled) -> Self {
+        match reason {
+            BuildCancelled::Requested => crate::exc::PptxError::Cancelled,
+            BuildCancelled::DeadlineExceeded => crate::exc::PptxError::DeadlineExceeded,
+        }
+    }
+}
+
+/// Error returned when a build exceeds its memory budget
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    /// Bytes accounted when the cap was hit, including the part that hit it
+    pub used: usize,
+    /// Configured cap in bytes
+    pub limit: usize,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "memory budget exceeded: {} of {} bytes", self.used, self.limit)
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+impl From<BudgetExceeded> for crate::exc::PptxError {
+    fn from(e: BudgetExceeded) -> Self {
+        crate::exc::PptxError::BudgetExceeded { used: e.used, limit: e.limit }
+    }
+}
+
+/// Progress callback, cancellation and memory budget for a build
+#[derive(Default)]
+pub struct BuildProgress<'a> {
+    on_progress: Option<Box<dyn FnMut(usize, usize) + Send + 'a>>,
+    cancel: Option<CancelToken>,
+    budget: Option<usize>,
+    used: usize,
+    skip_validation: bool,
+}
+
+impl<'a> BuildProgress<'a> {
+    /// Create a build observer that reports nothing and never cancels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `callback(done, total)` after each slide is written
+    pub fn on_progress(mut self, callback: impl FnMut(usize, usize) + Send + 'a) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Stop the build (with [`BuildCancelled`]) once `token` is cancelled or expires
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Stop the build (with [`BudgetExceeded`]) once slide XML and embedded
+    /// media add up to more than `bytes`
+    ///
+    /// Each part is accounted before it is written, so the build fails before
+    /// the part that would break the cap reaches the archive.
+    pub fn memory_budget(mut self, bytes: usize) -> Self {
+        self.budget = Some(bytes);
+        self
+    }
+
+    /// Write slides without validating them first
+    ///
+    /// Out-of-range values are then clamped or dropped as the builders see
+    /// fit instead of failing the build with a
+    /// [`ValidationError`](super::validate::ValidationError).
+    pub fn skip_validation(mut self) -> Self {
+        self.skip_validation = true;
+        self
+    }
+
+    /// Whether slides should be validated before they are written
+    pub(crate) fn validates(&self) -> bool {
+        !self.skip_validation
+    }
+
+    /// Account `bytes` against the memory budget
+    pub(crate) fn charge(&mut self, bytes: usize) -> Result<(), BudgetExceeded> {
+        self.used = self.used.saturating_add(bytes);
+        match self.budget {
+            Some(limit) if self.used > limit => Err(BudgetExceeded { used: self.used, limit }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fail if cancellation has been requested
+    pub(crate) fn check_cancelled(&self) -> Result<(), BuildCancelled> {
+        self.cancel.as_ref().map_or(Ok(()), CancelToken::check)
+    }
+
+    /// Report that `done` of `total` slides have been written
+    pub(crate) fn report(&mut self, done: usize, total: usize) {
+        if let Some(callback) = self.on_progress.as_mut() {
+            callback(done, total);
+        }
+    }
+}
+
+impl fmt::Debug for BuildProgress<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BuildProgress")
+            .field("on_progress", &self.on_progress.is_some())
+            .field("cancel", &self.cancel)
+            .field("budget", &self.budget)
+            .field("used", &self.used)
+            .field("skip_validation", &self.skip_validation)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_token_reasons() {
+        let token = CancelToken::new();
+        assert_eq!(token.check(), Ok(()));
+        token.clone().cancel();
+        assert_eq!(token.check(), Err(BuildCancelled::Requested));
+
+        let expired = CancelToken::with_deadline(Instant::now());
+        assert_eq!(expired.check(), Err(BuildCancelled::DeadlineExceeded));
+        assert!(!CancelToken::with_timeout(Duration::from_secs(60)).is_cancelled());
+    }
+
+    #[test]
+    fn test_memory_budget_charge() {
+        let mut unlimited = BuildProgress::new();
+        assert_eq!(unlimited.charge(usize::MAX), Ok(()));
+
+        let mut progress = BuildProgress::new().memory_budget(100);
+        assert_eq!(progress.charge(60), Ok(()));
+        assert_eq!(progress.charge(40), Ok(()));
+        assert_eq!(progress.charge(1), Err(BudgetExceeded { used: 101, limit: 100 }));
+    }
+}
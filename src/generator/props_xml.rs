@@ -1,7 +1,10 @@
 //! Document properties XML generation
 
+use crate::core::Escaped;
+
 /// Create core properties XML (docProps/core.xml)
 pub fn create_core_props_xml(title: &str) -> String {
+    let title = Escaped(title);
     let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
     format!(
         r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -0,0 +1,164 @@
+//! Content provenance
+//!
+//! Machine-generated decks can carry a record of how they were made: the
+//! generator and its version, a SHA-256 hash of the source (markdown,
+//! template, JSON spec) and the build time. It is stored as a custom XML
+//! part (`customXml/item1.xml`) related from `ppt/presentation.xml`, which
+//! PowerPoint preserves on save but never shows, and is read back with
+//! [`PresentationReader::provenance`](crate::oxml::PresentationReader::provenance).
+
+use sha2::{Digest, Sha256};
+
+use crate::core::escape_xml;
+use crate::oxml::xmlchemy::XmlParser;
+
+/// Namespace of the provenance custom XML part
+pub const PROVENANCE_NAMESPACE: &str = "urn:ppt-rs:provenance";
+
+/// Path of the provenance part
+pub const PROVENANCE_PART: &str = "customXml/item1.xml";
+
+/// Data store ID of the provenance part (`ds:datastoreItem/@ds:itemID`)
+const PROVENANCE_ITEM_ID: &str = "{5E1B7A2C-8F3D-4C6E-9A0B-2D4F6C8E1A3B}";
+
+/// How a presentation was generated
+///
+/// ```
+/// use ppt_rs::generator::Provenance;
+///
+/// let provenance = Provenance::new().source(b"# Quarterly review");
+/// assert_eq!(provenance.generator, "ppt-rs");
+/// assert_eq!(provenance.source_hash.as_ref().unwrap().len(), 64);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    /// Tool that built the deck
+    pub generator: String,
+    /// Version of that tool
+    pub version: String,
+    /// Lowercase hex SHA-256 of the source the deck was built from
+    pub source_hash: Option<String>,
+    /// Build time (RFC 3339, UTC)
+    pub built_at: String,
+}
+
+impl Default for Provenance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Provenance {
+    /// Provenance for this crate and version, built now
+    pub fn new() -> Self {
+        Provenance {
+            generator: "ppt-rs".to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            source_hash: None,
+            built_at: chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        }
+    }
+
+    /// Record the tool that built the deck (e.g. a service wrapping this crate)
+    pub fn generator(mut self, name: &str, version: &str) -> Self {
+        self.generator = name.to_string();
+        self.version = version.to_string();
+        self
+    }
+
+    /// Hash the source the deck was built from
+    pub fn source(mut self, source: &[u8]) -> Self {
+        self.source_hash = Some(sha256_hex(source));
+        self
+    }
+
+    /// Set a precomputed source hash
+    pub fn source_hash(mut self, hash: &str) -> Self {
+        self.source_hash = Some(hash.to_lowercase());
+        self
+    }
+
+    /// Set the build time (RFC 3339), e.g. for reproducible builds
+    pub fn built_at(mut self, timestamp: &str) -> Self {
+        self.built_at = timestamp.to_string();
+        self
+    }
+
+    /// Content of the provenance part
+    pub fn to_xml(&self) -> String {
+        let source = self.source_hash.as_ref()
+            .map(|hash| format!("\n<source sha256=\"{}\"/>", escape_xml(hash)))
+            .unwrap_or_default();
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<provenance xmlns="{PROVENANCE_NAMESPACE}">
+<generator name="{}" version="{}"/>{source}
+<builtAt>{}</builtAt>
+</provenance>"#,
+            escape_xml(&self.generator),
+            escape_xml(&self.version),
+            escape_xml(&self.built_at),
+        )
+    }
+
+    /// Parse a provenance part; `None` for other custom XML
+    pub fn from_xml(xml: &str) -> Option<Self> {
+        let root = XmlParser::parse_str(xml).ok()?;
+        if !root.is("provenance") || root.namespace.as_deref() != Some(PROVENANCE_NAMESPACE) {
+            return None;
+        }
+        let generator = root.find("generator")?;
+        Some(Provenance {
+            generator: generator.attr("name")?.to_string(),
+            version: generator.attr("version").unwrap_or_default().to_string(),
+            source_hash: root.find("source").and_then(|s| s.attr("sha256")).map(str::to_string),
+            built_at: root.find("builtAt").map(|e| e.text_content()).unwrap_or_default(),
+        })
+    }
+}
+
+/// Lowercase hex SHA-256 of `data`
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Custom XML properties part (`customXml/itemProps1.xml`)
+pub fn create_provenance_item_props_xml() -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<ds:datastoreItem ds:itemID="{PROVENANCE_ITEM_ID}" xmlns:ds="http://schemas.openxmlformats.org/officeDocument/2006/customXml">
+<ds:schemaRefs><ds:schemaRef ds:uri="{PROVENANCE_NAMESPACE}"/></ds:schemaRefs>
+</ds:datastoreItem>"#
+    )
+}
+
+/// Relationships of the provenance part (`customXml/_rels/item1.xml.rels`)
+pub fn create_provenance_rels_xml() -> String {
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps" Target="itemProps1.xml"/>
+</Relationships>"#.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_provenance_xml_round_trip() {
+        let provenance = Provenance::new()
+            .generator("deck-service", "2.1.0")
+            .source(b"# Title")
+            .built_at("2026-10-16T09:30:00Z");
+
+        let xml = provenance.to_xml();
+        assert!(xml.contains(r#"<generator name="deck-service" version="2.1.0"/>"#));
+        assert_eq!(Provenance::from_xml(&xml), Some(provenance));
+        assert_eq!(Provenance::from_xml("<root xmlns=\"urn:other\"/>"), None);
+    }
+}
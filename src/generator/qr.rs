@@ -0,0 +1,196 @@
+//! QR codes drawn as native shapes
+//!
+//! [`QrCode`] encodes text (typically a feedback or download link) and draws
+//! it as rectangles, one per run of dark modules in a row, over a light
+//! square that includes the quiet zone scanners need. Add it to a slide with
+//! [`SlideContent::qr_code`](super::SlideContent::qr_code). With the `media`
+//! feature, [`QrCode::to_image`] renders it as an embedded PNG instead.
+
+use crate::core::Emu;
+use crate::elements::ColorInput;
+use crate::exc::{PptxError, Result};
+use super::shapes::{Shape, ShapeFill, ShapeType};
+#[cfg(feature = "media")]
+use super::images::Image;
+#[cfg(feature = "media")]
+use crate::elements::RgbColor;
+
+/// Default side length, quiet zone included (1.2 inch)
+const DEFAULT_SIZE: u32 = 1097280;
+/// Light modules around the code, as the QR specification requires
+const QUIET_ZONE: usize = 4;
+/// Pixels per module in [`QrCode::to_image`]
+#[cfg(feature = "media")]
+const PNG_MODULE_PIXELS: u32 = 8;
+
+/// QR code with error correction level M
+///
+/// ```
+/// use ppt_rs::core::Inches;
+/// use ppt_rs::generator::{QrCode, SlideContent};
+///
+/// let qr = QrCode::new("https://example.com/feedback").unwrap()
+///     .size(Inches(1.2))
+///     .position(Inches(8.0), Inches(5.5));
+/// assert_eq!(qr.modules(), 29); // version 3
+///
+/// let slide = SlideContent::new("Thank You").qr_code(&qr);
+/// assert_eq!(slide.shapes.len(), qr.to_shapes().len());
+/// ```
+#[derive(Clone, Debug)]
+pub struct QrCode {
+    dark: Vec<bool>,
+    modules: usize,
+    x: Emu,
+    y: Emu,
+    size: u32,
+    color: String,
+    background: String,
+}
+
+impl QrCode {
+    /// Encode `data`
+    ///
+    /// Fails with [`PptxError::InvalidValue`] if `data` is too long for a
+    /// QR code (about 2,300 bytes at level M).
+    pub fn new(data: &str) -> Result<Self> {
+        let code = qrcode::QrCode::with_error_correction_level(data, qrcode::EcLevel::M)
+            .map_err(|e| PptxError::InvalidValue(format!("cannot encode QR code: {e}")))?;
+        Ok(QrCode {
+            modules: code.width(),
+            dark: code.into_colors().into_iter().map(|c| c == qrcode::Color::Dark).collect(),
+            x: Emu::ZERO,
+            y: Emu::ZERO,
+            size: DEFAULT_SIZE,
+            color: "000000".to_string(),
+            background: "FFFFFF".to_string(),
+        })
+    }
+
+    /// Set the side length, quiet zone included; a negative size is 0
+    pub fn size(mut self, size: impl Into<Emu>) -> Self {
+        self.size = size.into().to_u32_saturating();
+        self
+    }
+
+    /// Set the top-left corner of the quiet zone
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    /// Set the color of the dark modules
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = color.into().to_hex();
+        self
+    }
+
+    /// Set the color of the light modules and quiet zone
+    pub fn background(mut self, color: impl Into<ColorInput>) -> Self {
+        self.background = color.into().to_hex();
+        self
+    }
+
+    /// Modules per side, without the quiet zone
+    pub fn modules(&self) -> usize {
+        self.modules
+    }
+
+    /// Whether the module at column `x`, row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.modules && y < self.modules && self.dark[y * self.modules + x]
+    }
+
+    /// Background square followed by one rectangle per horizontal run of dark modules
+    ///
+    /// The module size is rounded down to whole EMU, so the code may come out
+    /// a few EMU smaller than [`Self::size`].
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let pitch = self.size / (self.modules + 2 * QUIET_ZONE) as u32;
+        let side = pitch * (self.modules + 2 * QUIET_ZONE) as u32;
+        let origin_x = self.x + Emu::from(pitch * QUIET_ZONE as u32);
+        let origin_y = self.y + Emu::from(pitch * QUIET_ZONE as u32);
+
+        let mut shapes = vec![Shape::new(ShapeType::Rectangle, self.x, self.y, side, side)
+            .with_fill(ShapeFill::new(self.background.as_str()))
+            .with_name("QR Code")];
+        for row in 0..self.modules {
+            let mut column = 0;
+            while column < self.modules {
+                if !self.is_dark(column, row) {
+                    column += 1;
+                    continue;
+                }
+                let start = column;
+                while self.is_dark(column, row) {
+                    column += 1;
+                }
+                let x = origin_x + Emu::from(pitch * start as u32);
+                let y = origin_y + Emu::from(pitch * row as u32);
+                shapes.push(Shape::new(ShapeType::Rectangle, x, y, pitch * (column - start) as u32, pitch)
+                    .with_fill(ShapeFill::new(self.color.as_str())));
+            }
+        }
+        shapes
+    }
+
+    /// Render as a PNG image at the code's position and size
+    #[cfg(feature = "media")]
+    pub fn to_image(&self) -> Image {
+        use std::io::Cursor;
+
+        let rgb = |hex: &str, fallback: RgbColor| {
+            let color = RgbColor::from_hex(hex).unwrap_or(fallback);
+            image::Rgb([color.r, color.g, color.b])
+        };
+        let dark = rgb(&self.color, RgbColor::new(0, 0, 0));
+        let light = rgb(&self.background, RgbColor::new(255, 255, 255));
+        let pixels = (self.modules + 2 * QUIET_ZONE) as u32 * PNG_MODULE_PIXELS;
+        let img = image::RgbImage::from_fn(pixels, pixels, |px, py| {
+            let module = |p: u32| (p / PNG_MODULE_PIXELS) as usize;
+            let (x, y) = (module(px).wrapping_sub(QUIET_ZONE), module(py).wrapping_sub(QUIET_ZONE));
+            if self.is_dark(x, y) { dark } else { light }
+        });
+
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+            .expect("encoding an in-memory PNG cannot fail");
+        Image::from_bytes(png, self.size, self.size, "png").position(self.x, self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes_cover_dark_modules_inside_quiet_zone() {
+        let qr = QrCode::new("https://example.com").unwrap().size(33 * 9525).position(100, 200);
+        assert_eq!(qr.modules(), 25);
+        let shapes = qr.to_shapes();
+        let background = &shapes[0];
+        assert_eq!((background.x.0, background.width.0), (100, 33 * 9525));
+
+        let dark_modules: u32 = shapes[1..].iter().map(|s| s.width.to_u32_saturating() / 9525).sum();
+        let expected = (0..25).flat_map(|y| (0..25).map(move |x| (x, y))).filter(|&(x, y)| qr.is_dark(x, y)).count();
+        assert_eq!(dark_modules as usize, expected);
+        // The top-left finder pattern starts after the four quiet modules
+        assert_eq!((shapes[1].x.0, shapes[1].y.0), (100 + 4 * 9525, 200 + 4 * 9525));
+        assert_eq!(shapes[1].width.0, 7 * 9525);
+    }
+
+    #[test]
+    fn test_overlong_data_is_rejected() {
+        assert!(matches!(QrCode::new(&"x".repeat(4000)), Err(PptxError::InvalidValue(_))));
+    }
+
+    #[cfg(feature = "media")]
+    #[test]
+    fn test_png_rendering() {
+        let image = QrCode::new("hello").unwrap().size(914400).to_image();
+        assert!(image.is_loaded());
+        assert_eq!((image.width.0, image.height.0, image.format.as_str()), (914400, 914400, "PNG"));
+    }
+}
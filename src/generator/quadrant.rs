@@ -0,0 +1,352 @@
+//! Quadrant charts (2 × 2 matrices)
+//!
+//! [`QuadrantChart`] draws the classic prioritization or risk matrix as
+//! native shapes: four tinted quadrants with optional titles, labelled axes
+//! and plotted points. Point coordinates run from `0.0` to `1.0`, with `y`
+//! growing upwards. Each point's label goes to the first side of its dot
+//! (right, left, above, below) where it overlaps no other label or dot and
+//! stays inside the chart, so clustered points stay readable. Add it to a
+//! slide with [`SlideContent::quadrant_chart`](super::SlideContent::quadrant_chart).
+
+use crate::core::Emu;
+use crate::elements::ColorInput;
+use super::shapes::{Shape, ShapeFill, ShapeLine, ShapeType};
+use super::slide_content::Region;
+use super::text::FormattedText;
+
+/// Space for the axis labels left of and under the quadrants (0.4 inch)
+const AXIS_MARGIN: u32 = 365760;
+/// Height of axis, quadrant and point labels (0.3 inch)
+const LABEL_HEIGHT: u32 = 274320;
+/// Point diameter (0.15 inch)
+const DOT_SIZE: u32 = 137160;
+/// Gap between a dot and its label
+const LABEL_GAP: u32 = 45720;
+/// Point label font size in points
+const POINT_FONT_SIZE: u32 = 11;
+/// Axis and quadrant title font size in points
+const TITLE_FONT_SIZE: u32 = 13;
+/// Quadrant outline width (1 pt)
+const LINE_WIDTH: u32 = 12700;
+
+/// Labelled point on a [`QuadrantChart`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct QuadrantPoint {
+    pub label: String,
+    /// 0.0 (left) to 1.0 (right)
+    pub x: f64,
+    /// 0.0 (bottom) to 1.0 (top)
+    pub y: f64,
+    /// Dot color (RGB hex); the chart's point color when unset
+    pub color: Option<String>,
+}
+
+/// Rectangle as (left, top, right, bottom) in EMU
+type Bounds = (i64, i64, i64, i64);
+
+/// 2 × 2 matrix with plotted points
+///
+/// ```
+/// use ppt_rs::generator::{QuadrantChart, SlideContent};
+///
+/// let matrix = QuadrantChart::new()
+///     .x_axis("Low effort", "High effort")
+///     .y_axis("Low impact", "High impact")
+///     .quadrant_labels(["Quick wins", "Major projects", "Fill-ins", "Thankless tasks"])
+///     .point("SSO", 0.2, 0.85)
+///     .point("Dark mode", 0.25, 0.8)
+///     .point("Rewrite", 0.9, 0.3);
+///
+/// let slide = SlideContent::new("Roadmap Priorities").quadrant_chart(&matrix);
+/// assert!(slide.shapes.iter().any(|s| s.name.as_deref() == Some("Point: SSO")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct QuadrantChart {
+    x: Emu,
+    y: Emu,
+    width: u32,
+    height: u32,
+    x_axis: (String, String),
+    y_axis: (String, String),
+    /// Top-left, top-right, bottom-left, bottom-right
+    labels: [String; 4],
+    colors: [String; 4],
+    point_color: String,
+    points: Vec<QuadrantPoint>,
+}
+
+impl Default for QuadrantChart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QuadrantChart {
+    /// Empty matrix filling the content area
+    pub fn new() -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
+        QuadrantChart {
+            x: x.into(),
+            y: y.into(),
+            width,
+            height,
+            x_axis: (String::new(), String::new()),
+            y_axis: (String::new(), String::new()),
+            labels: Default::default(),
+            colors: ["BBDEFB", "C8E6C9", "FFECB3", "FFCDD2"].map(String::from),
+            point_color: "1565C0".to_string(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Set the position of the matrix and its axis labels
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    /// Set the size of the matrix and its axis labels; negative sizes are 0
+    pub fn size(mut self, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
+        self.width = width.into().to_u32_saturating();
+        self.height = height.into().to_u32_saturating();
+        self
+    }
+
+    /// Labels under the left and right halves
+    pub fn x_axis(mut self, low: &str, high: &str) -> Self {
+        self.x_axis = (low.to_string(), high.to_string());
+        self
+    }
+
+    /// Labels beside the bottom and top halves
+    pub fn y_axis(mut self, low: &str, high: &str) -> Self {
+        self.y_axis = (low.to_string(), high.to_string());
+        self
+    }
+
+    /// Quadrant titles: top-left, top-right, bottom-left, bottom-right
+    pub fn quadrant_labels(mut self, labels: [&str; 4]) -> Self {
+        self.labels = labels.map(String::from);
+        self
+    }
+
+    /// Quadrant background colors: top-left, top-right, bottom-left, bottom-right
+    pub fn quadrant_colors<C: Into<ColorInput>>(mut self, colors: [C; 4]) -> Self {
+        self.colors = colors.map(|c| c.into().to_hex());
+        self
+    }
+
+    /// Set the default dot color
+    pub fn point_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.point_color = color.into().to_hex();
+        self
+    }
+
+    /// Plot a point; coordinates are clamped to 0.0-1.0
+    pub fn point(self, label: &str, x: f64, y: f64) -> Self {
+        self.add_point(QuadrantPoint { label: label.to_string(), x, y, color: None })
+    }
+
+    pub fn add_point(mut self, point: QuadrantPoint) -> Self {
+        self.points.push(point);
+        self
+    }
+
+    pub fn points(&self) -> &[QuadrantPoint] {
+        &self.points
+    }
+
+    /// Quadrant area (left, top, width, height) relative to the chart's
+    /// top-left corner, inside the axis labels
+    fn grid(&self) -> (u32, u32, u32, u32) {
+        (
+            AXIS_MARGIN,
+            0,
+            self.width.saturating_sub(AXIS_MARGIN),
+            self.height.saturating_sub(AXIS_MARGIN),
+        )
+    }
+
+    /// Quadrants, their titles, axis labels, then each point's dot and label
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let mut shapes = self.layout();
+        for shape in &mut shapes {
+            shape.x += self.x;
+            shape.y += self.y;
+        }
+        shapes
+    }
+
+    /// [`Self::to_shapes`] with the chart's top-left corner at (0, 0)
+    fn layout(&self) -> Vec<Shape> {
+        let (gx, gy, gw, gh) = self.grid();
+        let (half_w, half_h) = (gw / 2, gh / 2);
+        let text = |text: &str, size: u32, bold: bool| {
+            let run = FormattedText::new(text).color("424242").font_size(size);
+            vec![if bold { run.bold() } else { run }]
+        };
+        let clear = || ShapeFill::new("FFFFFF").with_transparency(100);
+
+        let mut shapes = Vec::new();
+        let origins = [(gx, gy), (gx + half_w, gy), (gx, gy + half_h), (gx + half_w, gy + half_h)];
+        for (i, &(qx, qy)) in origins.iter().enumerate() {
+            shapes.push(Shape::new(ShapeType::Rectangle, qx, qy, half_w, half_h)
+                .with_fill(ShapeFill::new(self.colors[i].as_str()))
+                .with_line(ShapeLine::new("9E9E9E", LINE_WIDTH))
+                .with_name("Quadrant"));
+        }
+        for (i, &(qx, qy)) in origins.iter().enumerate() {
+            if !self.labels[i].is_empty() {
+                shapes.push(Shape::new(ShapeType::Rectangle, qx, qy, half_w, LABEL_HEIGHT)
+                    .with_fill(clear())
+                    .with_rich_text(text(&self.labels[i], TITLE_FONT_SIZE, true))
+                    .with_name("Quadrant Label"));
+            }
+        }
+
+        let below = gy + gh + (AXIS_MARGIN - LABEL_HEIGHT) / 2;
+        for (label, lx) in [(&self.x_axis.0, gx), (&self.x_axis.1, gx + half_w)] {
+            if !label.is_empty() {
+                shapes.push(Shape::new(ShapeType::Rectangle, lx, below, half_w, LABEL_HEIGHT)
+                    .with_fill(clear())
+                    .with_rich_text(text(label, TITLE_FONT_SIZE, false))
+                    .with_name("Axis Label"));
+            }
+        }
+        // Rotated about their centers to read bottom to top beside the quadrants
+        let center_x = AXIS_MARGIN / 2;
+        for (label, center_y) in [(&self.y_axis.0, gy + half_h + half_h / 2), (&self.y_axis.1, gy + half_h / 2)] {
+            if !label.is_empty() {
+                let left = center_x.saturating_sub(half_h / 2);
+                let top = center_y.saturating_sub(LABEL_HEIGHT / 2);
+                shapes.push(Shape::new(ShapeType::Rectangle, left, top, half_h, LABEL_HEIGHT)
+                    .with_fill(clear())
+                    .with_rich_text(text(label, TITLE_FONT_SIZE, false))
+                    .with_rotation(270)
+                    .with_name("Axis Label"));
+            }
+        }
+
+        let dots: Vec<Bounds> = self.points.iter().map(|p| {
+            let (cx, cy) = self.point_center(p);
+            let r = DOT_SIZE as i64 / 2;
+            (cx - r, cy - r, cx + r, cy + r)
+        }).collect();
+        let inside = (gx as i64, gy as i64, (gx + gw) as i64, (gy + gh) as i64);
+        // Point labels also keep clear of the quadrant titles
+        let mut placed: Vec<Bounds> = shapes.iter()
+            .filter(|s| s.name.as_deref() == Some("Quadrant Label"))
+            .map(|s| (s.x.0, s.y.0, s.x.0 + s.width.0, s.y.0 + s.height.0))
+            .collect();
+        for (point, dot) in self.points.iter().zip(&dots) {
+            let color = point.color.as_deref().unwrap_or(&self.point_color);
+            shapes.push(Shape::new(ShapeType::Ellipse, dot.0.max(0) as u32, dot.1.max(0) as u32, DOT_SIZE, DOT_SIZE)
+                .with_fill(ShapeFill::new(color))
+                .with_name(&format!("Point: {}", point.label)));
+
+            let label = place_label(label_width(&point.label), *dot, inside, &dots, &placed);
+            placed.push(label);
+            shapes.push(Shape::new(ShapeType::Rectangle, label.0.max(0) as u32, label.1.max(0) as u32, (label.2 - label.0) as u32, LABEL_HEIGHT)
+                .with_fill(clear())
+                .with_rich_text(text(&point.label, POINT_FONT_SIZE, false))
+                .with_name(&format!("Point Label: {}", point.label)));
+        }
+        shapes
+    }
+
+    /// Center of a point's dot relative to the chart's top-left corner
+    fn point_center(&self, point: &QuadrantPoint) -> (i64, i64) {
+        let (gx, gy, gw, gh) = self.grid();
+        let cx = gx as f64 + point.x.clamp(0.0, 1.0) * gw as f64;
+        let cy = gy as f64 + (1.0 - point.y.clamp(0.0, 1.0)) * gh as f64;
+        (cx.round() as i64, cy.round() as i64)
+    }
+}
+
+/// Estimated width of a point label, from an average glyph width of 0.55 em
+fn label_width(text: &str) -> i64 {
+    let em = (POINT_FONT_SIZE * 12700) as f64;
+    (text.chars().count() as f64 * 0.55 * em) as i64 + 2 * LABEL_GAP as i64
+}
+
+fn overlap((l1, t1, r1, b1): Bounds, (l2, t2, r2, b2): Bounds) -> i64 {
+    (r1.min(r2) - l1.max(l2)).max(0) * (b1.min(b2) - t1.max(t2)).max(0)
+}
+
+/// First label position around `dot` that is inside `area` and clear of the
+/// other dots and `placed` labels, or the least crowded one
+fn place_label(width: i64, dot: Bounds, area: Bounds, dots: &[Bounds], placed: &[Bounds]) -> Bounds {
+    let (height, gap) = (LABEL_HEIGHT as i64, LABEL_GAP as i64);
+    let (cx, cy) = ((dot.0 + dot.2) / 2, (dot.1 + dot.3) / 2);
+    let candidates = [
+        (dot.2 + gap, cy - height / 2),
+        (dot.0 - gap - width, cy - height / 2),
+        (cx - width / 2, dot.1 - gap - height),
+        (cx - width / 2, dot.3 + gap),
+    ];
+    let crowding = |(left, top): (i64, i64)| {
+        let bounds = (left, top, left + width, top + height);
+        let outside = width * height - overlap(bounds, area);
+        let covered: i64 = dots.iter().filter(|d| **d != dot).chain(placed).map(|other| overlap(bounds, *other)).sum();
+        outside + covered
+    };
+    let (left, top) = candidates.iter()
+        .find(|c| crowding(**c) == 0)
+        .or_else(|| candidates.iter().min_by_key(|c| crowding(**c)))
+        .copied()
+        .unwrap_or(candidates[0]);
+    (left, top, left + width, top + height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label_bounds(shapes: &[Shape], label: &str) -> Bounds {
+        let shape = shapes.iter().find(|s| s.name.as_deref() == Some(&format!("Point Label: {label}"))).unwrap();
+        (shape.x.0, shape.y.0, shape.x.0 + shape.width.0, shape.y.0 + shape.height.0)
+    }
+
+    #[test]
+    fn test_points_map_to_quadrants() {
+        let chart = QuadrantChart::new().position(0, 0).size(AXIS_MARGIN + 4_000_000, AXIS_MARGIN + 2_000_000)
+            .point("A", 1.0, 0.0);
+        let shapes = chart.to_shapes();
+        let dot = shapes.iter().find(|s| s.name.as_deref() == Some("Point: A")).unwrap();
+        let r = DOT_SIZE as i64 / 2;
+        assert_eq!((dot.x.0, dot.y.0), (AXIS_MARGIN as i64 + 4_000_000 - r, 2_000_000 - r));
+        assert_eq!(shapes.iter().filter(|s| s.name.as_deref() == Some("Quadrant")).count(), 4);
+    }
+
+    #[test]
+    fn test_close_labels_do_not_overlap() {
+        let chart = QuadrantChart::new().point("Alpha", 0.4, 0.5).point("Beta", 0.42, 0.5).point("Gamma", 0.41, 0.52);
+        let shapes = chart.to_shapes();
+        let labels: Vec<_> = ["Alpha", "Beta", "Gamma"].iter().map(|l| label_bounds(&shapes, l)).collect();
+        for (i, a) in labels.iter().enumerate() {
+            for b in &labels[i + 1..] {
+                assert_eq!(overlap(*a, *b), 0, "{a:?} overlaps {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_label_flips_left_at_right_edge() {
+        let chart = QuadrantChart::new().point("Edge case", 1.0, 0.5);
+        let shapes = chart.to_shapes();
+        let label = label_bounds(&shapes, "Edge case");
+        let dot = shapes.iter().find(|s| s.name.as_deref() == Some("Point: Edge case")).unwrap();
+        assert!(label.2 <= dot.x.0);
+    }
+
+    #[test]
+    fn test_axis_and_quadrant_labels() {
+        let shapes = QuadrantChart::new().x_axis("Low", "High").y_axis("Rare", "Often")
+            .quadrant_labels(["A", "", "C", "D"]).to_shapes();
+        assert_eq!(shapes.iter().filter(|s| s.name.as_deref() == Some("Quadrant Label")).count(), 3);
+        let axes: Vec<_> = shapes.iter().filter(|s| s.name.as_deref() == Some("Axis Label")).collect();
+        assert_eq!(axes.len(), 4);
+        assert_eq!(axes[2].rotation, Some(270));
+    }
+}
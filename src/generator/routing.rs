@@ -0,0 +1,346 @@
+//! Orthogonal connector routing
+//!
+//! Elbow connectors take the preset three-segment path between their ends,
+//! which runs straight through any shape in the way. [`route_connectors`]
+//! finds the connectors whose default path crosses another connected shape
+//! and gives them bend points that go around it instead: an A* search over
+//! a coarse grid whose lines run along the shapes' edges, a little way out,
+//! preferring short paths with few bends.
+//!
+//! ```
+//! use ppt_rs::generator::{ConnectionSite, Connector, Shape, ShapeType, SlideContent};
+//!
+//! // C sits between A and B, so the straight connector would cross it
+//! let slide = SlideContent::new("Flow")
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 1_000_000, 500_000).with_id(10))
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 4_000_000, 0, 1_000_000, 500_000).with_id(11))
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 2_000_000, 0, 1_000_000, 500_000).with_id(12))
+//!     .add_connector(Connector::elbow(1_000_000, 250_000, 4_000_000, 250_000)
+//!         .connect_start(10, ConnectionSite::Right)
+//!         .connect_end(11, ConnectionSite::Left))
+//!     .add_connector(Connector::straight(2_500_000, 500_000, 2_500_000, 1_500_000).connect_start(12, ConnectionSite::Bottom))
+//!     .route_connectors();
+//! assert!(!slide.connectors[0].waypoints.is_empty());
+//! ```
+//!
+//! Shapes no connector is glued to, such as backgrounds and group frames,
+//! are not obstacles. Curved connectors and connectors not glued at both
+//! ends are left as they are.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::core::Emu;
+use super::connectors::{ConnectionSite, Connector, ConnectorType};
+use super::shapes::Shape;
+
+/// Space kept between a route and the shapes it passes (0.1 inch)
+const CLEARANCE: i64 = 91440;
+/// Extra cost of a bend, as a length (0.3 inch)
+const BEND_COST: i64 = 274320;
+
+/// Obstacle rectangle as `(x, y, width, height)`
+pub type Obstacle = (Emu, Emu, Emu, Emu);
+
+type Point = (i64, i64);
+
+/// Edges of a rectangle as `(left, top, right, bottom)`
+type Bounds = (i64, i64, i64, i64);
+
+/// Unit step a connector leaves a shape in from `site`
+fn direction(site: ConnectionSite) -> Option<Point> {
+    match site {
+        ConnectionSite::Top | ConnectionSite::TopLeft | ConnectionSite::TopRight => Some((0, -1)),
+        ConnectionSite::Bottom | ConnectionSite::BottomLeft | ConnectionSite::BottomRight => Some((0, 1)),
+        ConnectionSite::Left => Some((-1, 0)),
+        ConnectionSite::Right => Some((1, 0)),
+        ConnectionSite::Center => None,
+    }
+}
+
+fn strictly_inside((x, y): Point, (left, top, right, bottom): Bounds) -> bool {
+    x > left && x < right && y > top && y < bottom
+}
+
+/// Whether the segment `a`–`b` passes through the inside of `bounds`
+///
+/// Liang–Barsky clipping; touching an edge or a corner doesn't count.
+fn crosses(a: Point, b: Point, (left, top, right, bottom): Bounds) -> bool {
+    let (left, top, right, bottom) = (left + 1, top + 1, right - 1, bottom - 1);
+    if left >= right || top >= bottom {
+        return false;
+    }
+    let (dx, dy) = ((b.0 - a.0) as f64, (b.1 - a.1) as f64);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for (p, q) in [
+        (-dx, (a.0 - left) as f64),
+        (dx, (right - a.0) as f64),
+        (-dy, (a.1 - top) as f64),
+        (dy, (bottom - a.1) as f64),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return false;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    t0 < t1
+}
+
+/// Path PowerPoint draws for an unrouted connector, as a polyline
+fn default_path(connector: &Connector) -> Vec<Point> {
+    let (x0, y0) = (connector.start_x.get(), connector.start_y.get());
+    let (x1, y1) = (connector.end_x.get(), connector.end_y.get());
+    match connector.connector_type {
+        ConnectorType::Elbow => vec![(x0, y0), ((x0 + x1) / 2, y0), ((x0 + x1) / 2, y1), (x1, y1)],
+        _ => vec![(x0, y0), (x1, y1)],
+    }
+}
+
+/// Bend points of an orthogonal route from `start` to `end` around `obstacles`
+///
+/// The route leaves `start` and enters `end` in the direction of their
+/// connection sites and keeps [`CLEARANCE`] from every obstacle. Returns
+/// `None` when the obstacles close off every path.
+pub fn route_orthogonal(
+    start: (Emu, Emu),
+    start_site: ConnectionSite,
+    end: (Emu, Emu),
+    end_site: ConnectionSite,
+    obstacles: &[Obstacle],
+) -> Option<Vec<(Emu, Emu)>> {
+    let p0 = (start.0.get(), start.1.get());
+    let p1 = (end.0.get(), end.1.get());
+    let blocked: Vec<Bounds> = obstacles.iter()
+        .map(|&(x, y, w, h)| {
+            let (x, y, w, h) = (x.get(), y.get(), w.get(), h.get());
+            (x - CLEARANCE, y - CLEARANCE, x + w + CLEARANCE, y + h + CLEARANCE)
+        })
+        .collect();
+
+    // Leave each end straight out of its shape before turning
+    let stub = |p: Point, site| direction(site).map_or(p, |(dx, dy)| (p.0 + dx * 2 * CLEARANCE, p.1 + dy * 2 * CLEARANCE));
+    let (s0, s1) = (stub(p0, start_site), stub(p1, end_site));
+
+    // Grid lines along the obstacle edges and the ends, plus the channels between them
+    let lines = |coords: Vec<i64>| {
+        let mut coords = coords;
+        coords.sort_unstable();
+        coords.dedup();
+        let mids: Vec<i64> = coords.windows(2).map(|w| (w[0] + w[1]) / 2).collect();
+        coords.extend(mids);
+        coords.sort_unstable();
+        coords.dedup();
+        coords
+    };
+    let xs = lines(blocked.iter().flat_map(|b| [b.0, b.2]).chain([s0.0, s1.0]).collect());
+    let ys = lines(blocked.iter().flat_map(|b| [b.1, b.3]).chain([s0.1, s1.1]).collect());
+    let point = |(i, j): (usize, usize)| (xs[i], ys[j]);
+    let index = |p: Point| Some((xs.binary_search(&p.0).ok()?, ys.binary_search(&p.1).ok()?));
+    let (start_node, goal_node) = (index(s0)?, index(s1)?);
+    let open = |p: Point| p == s0 || p == s1 || !blocked.iter().any(|&b| strictly_inside(p, b));
+
+    const STEPS: [Point; 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    let start_dir = direction(start_site).and_then(|d| STEPS.iter().position(|&s| s == d));
+    // The last leg runs from the stub back into the end's shape
+    let end_dir = direction(end_site).and_then(|(dx, dy)| STEPS.iter().position(|&s| s == (-dx, -dy)));
+    let bend = |from: Option<usize>, to: usize| if from.is_some_and(|f| f != to) { BEND_COST } else { 0 };
+    let heuristic = |(i, j): (usize, usize)| (xs[i] - s1.0).abs() + (ys[j] - s1.1).abs();
+
+    // A* over (node, heading) so bends can be charged
+    type State = ((usize, usize), Option<usize>);
+    let mut best: HashMap<State, i64> = HashMap::new();
+    let mut parent: HashMap<State, State> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    best.insert((start_node, start_dir), 0);
+    queue.push(Reverse((heuristic(start_node), 0i64, start_node, start_dir)));
+    let mut finish = None;
+    while let Some(Reverse((_, cost, node, heading))) = queue.pop() {
+        if best.get(&(node, heading)).is_some_and(|&b| b < cost) {
+            continue;
+        }
+        if node == goal_node {
+            finish = Some((node, heading));
+            break;
+        }
+        for (d, &(dx, dy)) in STEPS.iter().enumerate() {
+            let (i, j) = (node.0 as i64 + dx, node.1 as i64 + dy);
+            if i < 0 || j < 0 || i as usize >= xs.len() || j as usize >= ys.len() {
+                continue;
+            }
+            let next = (i as usize, j as usize);
+            let (a, b) = (point(node), point(next));
+            let middle = ((a.0 + b.0) / 2, (a.1 + b.1) / 2);
+            if !open(b) || blocked.iter().any(|&r| strictly_inside(middle, r)) {
+                continue;
+            }
+            let mut next_cost = cost + (b.0 - a.0).abs() + (b.1 - a.1).abs() + bend(heading, d);
+            if next == goal_node {
+                next_cost += end_dir.map_or(0, |e| bend(Some(d), e));
+            }
+            if best.get(&(next, Some(d))).is_none_or(|&b| next_cost < b) {
+                best.insert((next, Some(d)), next_cost);
+                parent.insert((next, Some(d)), (node, heading));
+                queue.push(Reverse((next_cost + heuristic(next), next_cost, next, Some(d))));
+            }
+        }
+    }
+
+    let mut state = finish?;
+    let mut corners = vec![p1, point(state.0)];
+    while let Some(&previous) = parent.get(&state) {
+        corners.push(point(previous.0));
+        state = previous;
+    }
+    corners.push(p0);
+    corners.reverse();
+    Some(simplify(&corners)[1..].split_last().map_or(Vec::new(), |(_, inner)| inner.to_vec())
+        .into_iter()
+        .map(|(x, y)| (Emu(x), Emu(y)))
+        .collect())
+}
+
+/// Drop repeated points and points in the middle of a straight run
+fn simplify(points: &[Point]) -> Vec<Point> {
+    let mut out: Vec<Point> = Vec::new();
+    for &p in points {
+        if out.last() == Some(&p) {
+            continue;
+        }
+        if let [.., a, b] = out[..] {
+            let collinear = (b.0 - a.0) * (p.1 - b.1) == (b.1 - a.1) * (p.0 - b.0);
+            if collinear {
+                out.pop();
+            }
+        }
+        out.push(p);
+    }
+    out
+}
+
+/// Route the connectors whose default path crosses a shape
+///
+/// Obstacles are the shapes some connector is glued to, other than the
+/// connector's own ends; a shape's id is its `id` or, as when the slide is
+/// written, its index plus 10. Routed connectors become elbow connectors
+/// with [`Connector::waypoints`] set.
+pub fn route_connectors(shapes: &[Shape], connectors: &mut [Connector]) {
+    // Explicit ids win over implied ones when the two collide
+    let mut by_id: HashMap<u32, &Shape> = shapes.iter().filter_map(|s| Some((s.id?, s))).collect();
+    for (i, shape) in shapes.iter().enumerate() {
+        if shape.id.is_none() {
+            by_id.entry(i as u32 + 10).or_insert(shape);
+        }
+    }
+    let mut glued: Vec<u32> = connectors.iter()
+        .flat_map(|c| [c.start_shape_id, c.end_shape_id])
+        .flatten()
+        .filter(|id| by_id.contains_key(id))
+        .collect();
+    glued.sort_unstable();
+    glued.dedup();
+
+    for connector in connectors.iter_mut() {
+        let (Some(start_id), Some(end_id)) = (connector.start_shape_id, connector.end_shape_id) else { continue };
+        if connector.connector_type == ConnectorType::Curved || !connector.waypoints.is_empty() {
+            continue;
+        }
+        let obstacles: Vec<Obstacle> = glued.iter()
+            .filter(|&&id| id != start_id && id != end_id)
+            .map(|id| {
+                let shape = by_id[id];
+                (shape.x, shape.y, shape.width, shape.height)
+            })
+            .collect();
+        let outlines: Vec<Bounds> = obstacles.iter()
+            .map(|&(x, y, w, h)| (x.get(), y.get(), (x + w).get(), (y + h).get()))
+            .collect();
+        let path = default_path(connector);
+        let blocked = path.windows(2).any(|seg| outlines.iter().any(|&b| crosses(seg[0], seg[1], b)));
+        if !blocked {
+            continue;
+        }
+        let start_site = connector.start_site.unwrap_or(ConnectionSite::Center);
+        let end_site = connector.end_site.unwrap_or(ConnectionSite::Center);
+        let start = (connector.start_x, connector.start_y);
+        let end = (connector.end_x, connector.end_y);
+        if let Some(waypoints) = route_orthogonal(start, start_site, end, end_site, &obstacles) {
+            connector.connector_type = ConnectorType::Elbow;
+            connector.waypoints = waypoints;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::shapes::ShapeType;
+
+    fn emu((x, y): (i64, i64)) -> (Emu, Emu) {
+        (Emu(x), Emu(y))
+    }
+
+    fn route_points(start: (Emu, Emu), end: (Emu, Emu), waypoints: &[(Emu, Emu)]) -> Vec<Point> {
+        std::iter::once(start).chain(waypoints.iter().copied()).chain([end])
+            .map(|(x, y)| (x.get(), y.get()))
+            .collect()
+    }
+
+    #[test]
+    fn test_route_avoids_obstacle() {
+        let obstacle = (Emu(2_000_000), Emu(0), Emu(1_000_000), Emu(500_000));
+        let (start, end) = (emu((1_000_000, 250_000)), emu((4_000_000, 250_000)));
+        let waypoints = route_orthogonal(start, ConnectionSite::Right, end, ConnectionSite::Left, &[obstacle]).unwrap();
+        let points = route_points(start, end, &waypoints);
+        let bounds = (2_000_000, 0, 3_000_000, 500_000);
+        assert!(points.windows(2).all(|s| !crosses(s[0], s[1], bounds)));
+        // Every segment is horizontal or vertical
+        assert!(points.windows(2).all(|s| s[0].0 == s[1].0 || s[0].1 == s[1].1));
+        // Out of the right side and into the left side
+        assert_eq!(points[1].1, 250_000);
+        assert_eq!(points[points.len() - 2].1, 250_000);
+    }
+
+    #[test]
+    fn test_clear_route_is_direct() {
+        let waypoints = route_orthogonal(emu((0, 0)), ConnectionSite::Right, emu((1_000_000, 0)), ConnectionSite::Left, &[]).unwrap();
+        assert!(waypoints.is_empty());
+    }
+
+    #[test]
+    fn test_crosses() {
+        let bounds = (10, 10, 20, 20);
+        assert!(crosses((0, 15), (30, 15), bounds));
+        assert!(crosses((0, 0), (30, 30), bounds));
+        assert!(!crosses((0, 10), (30, 10), bounds));
+        assert!(!crosses((0, 0), (5, 30), bounds));
+    }
+
+    #[test]
+    fn test_only_blocked_connectors_are_routed() {
+        let shapes = vec![
+            Shape::new(ShapeType::Rectangle, 0, 0, 1_000_000, 500_000),
+            Shape::new(ShapeType::Rectangle, 0, 2_000_000, 1_000_000, 500_000),
+            Shape::new(ShapeType::Rectangle, 0, 1_000_000, 1_000_000, 500_000),
+        ];
+        let mut connectors = vec![
+            // Through the middle shape
+            Connector::straight(500_000, 500_000, 500_000, 2_000_000)
+                .connect_start(10, ConnectionSite::Bottom)
+                .connect_end(11, ConnectionSite::Top),
+            // Into the middle shape, nothing in the way
+            Connector::straight(500_000, 500_000, 500_000, 1_000_000)
+                .connect_start(10, ConnectionSite::Bottom)
+                .connect_end(12, ConnectionSite::Top),
+        ];
+        route_connectors(&shapes, &mut connectors);
+        assert_eq!(connectors[0].connector_type, ConnectorType::Elbow);
+        assert!(!connectors[0].waypoints.is_empty());
+        assert!(connectors[1].waypoints.is_empty());
+        assert_eq!(connectors[1].connector_type, ConnectorType::Straight);
+    }
+}
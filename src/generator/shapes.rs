@@ -2,6 +2,16 @@
 //!
 //! Provides shape types, fills, lines, and builders for creating shapes in slides.
 
+pub use crate::core::units::{emu_to_inches, inches_to_emu, cm_to_emu};
+use crate::core::{Emu, Positioned, ElementSized, Styled};
+use crate::generator::placement::{Placement, SlideSize};
+use crate::generator::validate::{ValidationError, check_color, check_geometry};
+use crate::generator::text::FormattedText;
+use crate::generator::locks::Locks;
+use crate::generator::geometry::CustomGeometry;
+use crate::generator::tags::Tags;
+use crate::elements::ColorInput;
+
 /// Shape types available in PPTX
 #[derive(Clone, Debug, Copy, PartialEq)]
 pub enum ShapeType {
@@ -462,9 +472,9 @@ pub struct GradientStop {
 
 impl GradientStop {
     /// Create a gradient stop at a position (0-100%)
-    pub fn new(color: &str, position_percent: u32) -> Self {
+    pub fn new(color: impl Into<ColorInput>, position_percent: u32) -> Self {
         GradientStop {
-            color: color.trim_start_matches('#').to_uppercase(),
+            color: color.into().to_hex(),
             position: position_percent.min(100) * 1000,
             transparency: None,
         }
@@ -529,22 +539,33 @@ pub enum FillType {
 #[derive(Clone, Debug)]
 pub struct ShapeFill {
     pub color: String, // RGB hex color (e.g., "FF0000")
-    pub transparency: Option<u32>, // 0-100000 (100000 = fully transparent)
+    pub transparency: Option<u32>, // alpha 0-100000 (100000 = opaque)
+    /// Out-of-range percentage passed to `with_transparency`, reported by validation
+    pub(crate) rejected_transparency: Option<u32>,
 }
 
 impl ShapeFill {
     /// Create new shape fill with color
-    pub fn new(color: &str) -> Self {
+    ///
+    /// Accepts hex (`"#1F4E79"`, `"#F80"`), CSS names (`"teal"`) or a
+    /// [`Color`]; the alpha of `#RRGGBBAA` becomes the fill's transparency.
+    pub fn new(color: impl Into<ColorInput>) -> Self {
+        let color = color.into();
         ShapeFill {
-            color: color.trim_start_matches('#').to_uppercase(),
-            transparency: None,
+            transparency: color.alpha().map(|alpha| alpha as u32 * 100000 / 255),
+            color: color.to_hex(),
+            rejected_transparency: None,
         }
     }
 
     /// Set transparency (0-100 percent)
+    ///
+    /// Values above 100 are written as fully transparent, but fail
+    /// [`SlideContent::validate`](crate::generator::SlideContent::validate).
     pub fn with_transparency(mut self, percent: u32) -> Self {
-        let alpha = ((100 - percent.min(100)) * 1000) as u32;
+        let alpha = (100 - percent.min(100)) * 1000;
         self.transparency = Some(alpha);
+        self.rejected_transparency = (percent > 100).then_some(percent);
         self
     }
     
@@ -563,9 +584,9 @@ pub struct ShapeLine {
 
 impl ShapeLine {
     /// Create new shape line with color and width
-    pub fn new(color: &str, width: u32) -> Self {
+    pub fn new(color: impl Into<ColorInput>, width: u32) -> Self {
         ShapeLine {
-            color: color.trim_start_matches('#').to_uppercase(),
+            color: color.into().to_hex(),
             width,
         }
     }
@@ -575,47 +596,125 @@ impl ShapeLine {
 #[derive(Clone, Debug)]
 pub struct Shape {
     pub shape_type: ShapeType,
-    pub x: u32,      // Position X in EMU
-    pub y: u32,      // Position Y in EMU
-    pub width: u32,  // Width in EMU
-    pub height: u32, // Height in EMU
+    /// Position X; negative values place the shape partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the shape partly above the slide
+    pub y: Emu,
+    pub width: Emu,
+    pub height: Emu,
     pub fill: Option<ShapeFill>,
     pub gradient: Option<GradientFill>,
     pub line: Option<ShapeLine>,
     pub text: Option<String>,
+    /// Formatted runs and fields written as one paragraph instead of `text`
+    pub rich_text: Vec<FormattedText>,
     /// Optional fixed shape ID for connector anchoring
     pub id: Option<u32>,
+    /// Optional shape name (defaults to "Shape {id}"); the Morph transition pairs shapes by name
+    pub name: Option<String>,
     /// Rotation in degrees (0-360)
     pub rotation: Option<i32>,
     /// Optional hyperlink
     pub hyperlink: Option<crate::generator::hyperlinks::Hyperlink>,
+    /// Optional action when the mouse hovers over the shape
+    pub hover: Option<crate::generator::hyperlinks::Hyperlink>,
+    /// Slide-relative position that replaces `x`/`y` at build time
+    pub placement: Option<Placement>,
+    /// Editing locks (`a:spLocks`)
+    pub locks: Locks,
+    /// Style sheet style for the text, applied at build time (see [`crate::stylesheet`])
+    pub style_name: Option<String>,
+    /// Outline drawn instead of the shape type's preset geometry
+    pub geometry: Option<CustomGeometry>,
+    /// Custom tags (`p:custDataLst`), see [`Self::tag`]
+    pub tags: Tags,
 }
 
 impl Shape {
     /// Create a new shape
-    pub fn new(shape_type: ShapeType, x: u32, y: u32, width: u32, height: u32) -> Self {
+    ///
+    /// Coordinates are EMU as integers or [`Emu`], or converted from
+    /// [`Pt`](crate::core::Pt), [`Inches`](crate::core::Inches) or
+    /// [`Cm`](crate::core::Cm).
+    pub fn new(
+        shape_type: ShapeType,
+        x: impl Into<Emu>,
+        y: impl Into<Emu>,
+        width: impl Into<Emu>,
+        height: impl Into<Emu>,
+    ) -> Self {
         Shape {
             shape_type,
-            x,
-            y,
-            width,
-            height,
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
             fill: None,
             gradient: None,
             line: None,
             text: None,
+            rich_text: Vec::new(),
             id: None,
+            name: None,
             rotation: None,
             hyperlink: None,
+            hover: None,
+            placement: None,
+            locks: Locks::NONE,
+            style_name: None,
+            geometry: None,
+            tags: Tags::new(),
+        }
+    }
+
+    /// Position the shape relative to the slide instead of at fixed EMU
+    ///
+    /// `x`/`y` are replaced when the presentation is built, using its slide
+    /// size, e.g. `.at(Anchor::TopRight.offset(Inches(0.2), Inches(0.2)))`.
+    pub fn at(mut self, placement: Placement) -> Self {
+        self.placement = Some(placement);
+        self
+    }
+
+    /// Replace `x`/`y` with the resolved placement, if any
+    ///
+    /// The placement is consumed, so resolving again keeps the position.
+    pub fn resolve_placement(&mut self, slide: SlideSize) {
+        if let Some(placement) = self.placement.take() {
+            (self.x, self.y) = placement.resolve(slide, self.width, self.height);
         }
     }
 
+    /// Draw `geometry` instead of the shape type's outline
+    pub fn with_geometry(mut self, geometry: CustomGeometry) -> Self {
+        self.geometry = Some(geometry);
+        self
+    }
+
     /// Set shape ID for connector anchoring
     pub fn with_id(mut self, id: u32) -> Self {
         self.id = Some(id);
         self
     }
 
+    /// Set shape name
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Set a custom tag, e.g. to mark the shape as managed by a tool
+    pub fn tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.set(name, value);
+        self
+    }
+
+    /// Lock the shape against selecting, moving, resizing, etc.
+    pub fn lock(mut self, locks: Locks) -> Self {
+        self.locks |= locks;
+        self
+    }
+
     /// Set shape rotation in degrees
     pub fn with_rotation(mut self, degrees: i32) -> Self {
         self.rotation = Some(degrees);
@@ -628,6 +727,12 @@ impl Shape {
         self
     }
 
+    /// Set action triggered by hovering over the shape
+    pub fn with_hover_action(mut self, action: crate::generator::hyperlinks::Hyperlink) -> Self {
+        self.hover = Some(action);
+        self
+    }
+
     /// Set shape fill (solid color)
     pub fn with_fill(mut self, fill: ShapeFill) -> Self {
         self.fill = Some(fill);
@@ -653,26 +758,134 @@ impl Shape {
         self.text = Some(text.to_string());
         self
     }
+
+    /// Set shape text from formatted runs, which may include fields
+    ///
+    /// ```
+    /// use ppt_rs::generator::{DateTimeFormat, Field, FormattedText, Shape, ShapeType};
+    ///
+    /// let footer = Shape::new(ShapeType::Rectangle, 457200, 6356350, 2895600, 365125)
+    ///     .with_rich_text(vec![
+    ///         FormattedText::new("Updated ").font_size(10),
+    ///         FormattedText::field(Field::DateTime(DateTimeFormat::LongDate)).font_size(10),
+    ///     ]);
+    /// assert_eq!(footer.rich_text.len(), 2);
+    /// ```
+    pub fn with_rich_text(mut self, runs: Vec<FormattedText>) -> Self {
+        self.rich_text = runs;
+        self
+    }
+
+    /// Format the text with the named style of the presentation's style sheet
+    pub fn style(mut self, name: &str) -> Self {
+        self.style_name = Some(name.to_string());
+        self
+    }
+
+    /// Check geometry, colors and transparency
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        check_geometry(self.x, self.y, self.width, self.height)?;
+        if let Some(fill) = &self.fill {
+            check_color("fill color", &fill.color)?;
+            if let Some(percent) = fill.rejected_transparency {
+                return Err(ValidationError::new(format!("fill transparency {percent}% is above 100%")));
+            }
+        }
+        if let Some(gradient) = &self.gradient {
+            for stop in &gradient.stops {
+                check_color("gradient color", &stop.color)?;
+            }
+        }
+        if let Some(line) = &self.line {
+            check_color("line color", &line.color)?;
+        }
+        Ok(())
+    }
 }
 
-/// Convert EMU (English Metric Units) to inches
-pub fn emu_to_inches(emu: u32) -> f64 {
-    emu as f64 / 914400.0
+impl Positioned for Shape {
+    fn x(&self) -> Emu {
+        self.x
+    }
+
+    fn y(&self) -> Emu {
+        self.y
+    }
+
+    fn set_position(&mut self, x: Emu, y: Emu) {
+        self.x = x;
+        self.y = y;
+        self.placement = None;
+    }
 }
 
-/// Convert inches to EMU
-pub fn inches_to_emu(inches: f64) -> u32 {
-    (inches * 914400.0) as u32
+impl ElementSized for Shape {
+    fn width(&self) -> Emu {
+        self.width
+    }
+
+    fn height(&self) -> Emu {
+        self.height
+    }
+
+    fn set_size(&mut self, width: Emu, height: Emu) {
+        self.width = width;
+        self.height = height;
+    }
 }
 
-/// Convert centimeters to EMU
-pub fn cm_to_emu(cm: f64) -> u32 {
-    (cm * 360000.0) as u32
+/// The solid fill color; setting it replaces a gradient
+impl Styled for Shape {
+    fn color(&self) -> Option<&str> {
+        self.fill.as_ref().map(|fill| fill.color.as_str())
+    }
+
+    fn set_color(&mut self, color: &str) {
+        let transparency = self.fill.as_ref().and_then(|fill| fill.transparency);
+        self.fill = Some(ShapeFill { transparency, ..ShapeFill::new(color) });
+        self.gradient = None;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::elements::Color;
+
+    #[test]
+    fn test_arrange_mixed_elements() {
+        use crate::core::arrange::{self, Align};
+        use crate::generator::Image;
+
+        let mut shape = Shape::new(ShapeType::Rectangle, 100, 500, 400, 200);
+        let mut image = Image::new("logo.png", 200, 100, "PNG").position(300, 100);
+        let mut items: [&mut dyn Positioned; 2] = [&mut shape, &mut image];
+        arrange::align(&mut items, Align::Top);
+        arrange::translate(&mut items, Emu(-100), Emu(0));
+
+        assert_eq!((shape.x, shape.y), (Emu(0), Emu(100)));
+        assert_eq!((image.x, image.y), (Emu(200), Emu(100)));
+
+        shape.set_color("#00ff00");
+        assert_eq!(shape.color(), Some("00FF00"));
+    }
+
+    #[test]
+    fn test_arrange_keeps_negative_offsets() {
+        use crate::core::arrange;
+        use crate::generator::{Chart, ChartType, Image, Table, TableCell, TableRow};
+
+        let mut image = Image::new("logo.png", 200, 100, "PNG").position(50, 50);
+        let row = TableRow::new(vec![TableCell::new("A")]);
+        let mut table = Table::new(vec![row], vec![100], 50, 50);
+        let mut chart = Chart::new("Sales", ChartType::Bar, vec![], 50, 50, 400, 300);
+        let mut items: [&mut dyn Positioned; 3] = [&mut image, &mut table, &mut chart];
+        arrange::translate(&mut items, Emu(-150), Emu(-250));
+
+        assert_eq!((image.x, image.y), (Emu(-100), Emu(-200)));
+        assert_eq!((table.x, table.y), (Emu(-100), Emu(-200)));
+        assert_eq!((chart.x, chart.y), (Emu(-100), Emu(-200)));
+    }
 
     #[test]
     fn test_shape_type_names() {
@@ -690,6 +903,28 @@ mod tests {
         assert_eq!(fill.transparency, Some(50000));
     }
 
+    #[test]
+    fn test_color_setters_accept_names_and_alpha() {
+        let fill = ShapeFill::new("#1F4E7980");
+        assert_eq!((fill.color.as_str(), fill.transparency), ("1F4E79", Some(50196)));
+        assert_eq!(ShapeLine::new("teal", 100).color, "008080");
+        assert_eq!(ShapeFill::new(Color::rgb(1, 2, 3)).color, "010203");
+
+        let invalid = Shape::new(ShapeType::Rectangle, 0, 0, 10, 10).with_fill(ShapeFill::new("redd"));
+        assert_eq!(invalid.validate().unwrap_err().message, "fill color \"redd\" is not a 6-digit hex color");
+    }
+
+    #[test]
+    fn test_shape_validate() {
+        let shape = Shape::new(ShapeType::Rectangle, 0, 0, 1000, 1000);
+        assert!(shape.clone().with_fill(ShapeFill::new("#1f4e79").with_transparency(40)).validate().is_ok());
+
+        let err = shape.clone().with_fill(ShapeFill::new("FF0000").transparency(150)).validate().unwrap_err();
+        assert_eq!(err.message, "fill transparency 150% is above 100%");
+        assert!(shape.clone().with_line(ShapeLine::new("reddish", 100)).validate().is_err());
+        assert!(Shape::new(ShapeType::Rectangle, 0, 0, Emu(-1), 10).validate().is_err());
+    }
+
     #[test]
     fn test_shape_builder() {
         let shape = Shape::new(ShapeType::Rectangle, 0, 0, 1000000, 500000)
@@ -697,8 +932,8 @@ mod tests {
             .with_line(ShapeLine::new("000000", 25400))
             .with_text("Hello");
 
-        assert_eq!(shape.x, 0);
-        assert_eq!(shape.width, 1000000);
+        assert_eq!(shape.x, Emu(0));
+        assert_eq!(shape.width, Emu(1000000));
         assert_eq!(shape.text, Some("Hello".to_string()));
     }
 
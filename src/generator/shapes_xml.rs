@@ -3,148 +3,182 @@
 //! Generates XML for shapes embedded in slides.
 
 use super::shapes::{Shape, ShapeFill, ShapeLine, GradientFill};
-use crate::generator::hyperlinks::generate_shape_hyperlink_xml;
-
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
+use crate::core::{Emu, XmlWriter};
+use crate::generator::hyperlinks::{generate_shape_hyperlink_xml, generate_shape_hover_xml};
+use crate::generator::text::{FormattedText, Run};
 
 /// Generate XML for a shape
 pub fn generate_shape_xml(shape: &Shape, shape_id: u32) -> String {
-    // Generate fill XML - gradient takes precedence over solid fill
-    let fill_xml = if let Some(gradient) = &shape.gradient {
-        generate_gradient_xml(gradient)
-    } else {
-        generate_fill_xml(&shape.fill)
-    };
-    let line_xml = generate_line_xml(&shape.line);
-    let fill_color = shape.fill.as_ref().map(|f| f.color.as_str());
-    let text_xml = generate_text_xml_with_autofit(&shape.text, shape.width, shape.height, fill_color);
-    
-    let rot_attr = if let Some(rot) = shape.rotation {
-        format!(r#" rot="{}""#, rot * 60000)
-    } else {
-        String::new()
+    let mut xml = XmlWriter::with_capacity(1024);
+    write_shape_xml(&mut xml, shape, shape_id);
+    xml.finish()
+}
+
+/// Write XML for a shape into `xml`
+pub fn write_shape_xml(xml: &mut XmlWriter, shape: &Shape, shape_id: u32) {
+    xml.raw("<p:sp>\n<p:nvSpPr>\n");
+    xml.open("p:cNvPr").attr("id", shape_id);
+    match &shape.name {
+        Some(name) => xml.attr("name", name),
+        None => xml.attr("name", format_args!("Shape {shape_id}")),
     };
 
-    let cnvpr_xml = if let Some(h) = &shape.hyperlink {
-        if let Some(rid) = &h.r_id {
-             format!(r#"<p:cNvPr id="{}" name="Shape {}">{}</p:cNvPr>"#, shape_id, shape_id, generate_shape_hyperlink_xml(h, rid))
-        } else {
-             format!(r#"<p:cNvPr id="{}" name="Shape {}"/>"#, shape_id, shape_id)
-        }
+    // Actions are written once their relationships (if any) have been assigned
+    let hyperlink = shape.hyperlink.as_ref().filter(|h| h.is_resolved());
+    let hover = shape.hover.as_ref().filter(|h| h.is_resolved());
+    if hyperlink.is_none() && hover.is_none() {
+        xml.close_empty();
     } else {
-        format!(r#"<p:cNvPr id="{}" name="Shape {}"/>"#, shape_id, shape_id)
-    };
+        xml.close();
+        if let Some(h) = hyperlink {
+            xml.raw(&generate_shape_hyperlink_xml(h, h.r_id.as_deref().unwrap_or("")));
+        }
+        if let Some(h) = hover {
+            xml.raw(&generate_shape_hover_xml(h, h.r_id.as_deref().unwrap_or("")));
+        }
+        xml.end_element("p:cNvPr");
+    }
 
-    format!(
-        r#"<p:sp>
-<p:nvSpPr>
-{}
-<p:cNvSpPr/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm{}>
+    if shape.locks.is_empty() {
+        xml.raw("\n<p:cNvSpPr/>");
+    } else {
+        xml.raw("\n<p:cNvSpPr>");
+        xml.raw(&shape.locks.to_xml("a:spLocks"));
+        xml.raw("</p:cNvSpPr>");
+    }
+    match shape.tags.cust_data_xml() {
+        Some(cust_data) => {
+            xml.raw("\n<p:nvPr>");
+            xml.raw(&cust_data);
+            xml.raw("</p:nvPr>");
+        }
+        None => {
+            xml.raw("\n<p:nvPr/>");
+        }
+    }
+    xml.raw("\n</p:nvSpPr>\n<p:spPr>\n<a:xfrm");
+    if let Some(rot) = shape.rotation {
+        xml.attr("rot", rot * 60000);
+    }
+    write!(
+        xml,
+        r#">
 <a:off x="{}" y="{}"/>
 <a:ext cx="{}" cy="{}"/>
 </a:xfrm>
-<a:prstGeom prst="{}">
-<a:avLst/>
-</a:prstGeom>
-{}{}
-</p:spPr>
-{}
-</p:sp>"#,
-        cnvpr_xml,
-        rot_attr,
+"#,
         shape.x,
         shape.y,
         shape.width,
         shape.height,
-        shape.shape_type.preset_name(),
-        fill_xml,
-        line_xml,
-        text_xml,
-    )
+    );
+    match &shape.geometry {
+        Some(geometry) => {
+            xml.raw(&geometry.to_xml());
+            xml.raw("\n");
+        }
+        None => write!(xml, "<a:prstGeom prst=\"{}\">\n<a:avLst/>\n</a:prstGeom>\n", shape.shape_type.preset_name()),
+    }
+
+    // Gradient takes precedence over solid fill
+    if let Some(gradient) = &shape.gradient {
+        write_gradient_xml(xml, gradient);
+    } else {
+        write_fill_xml(xml, &shape.fill);
+    }
+    write_line_xml(xml, &shape.line);
+    xml.raw("\n</p:spPr>\n");
+
+    // Text contrasts with the gradient's middle, which it usually sits on
+    let fill_color = match &shape.gradient {
+        Some(gradient) => gradient.stops.iter().min_by_key(|s| s.position.abs_diff(50000)).map(|s| s.color.as_str()),
+        None => shape.fill.as_ref().map(|f| f.color.as_str()),
+    };
+    if shape.rich_text.is_empty() {
+        write_text_xml_with_autofit(xml, &shape.text, shape.width, shape.height, fill_color);
+    } else {
+        write_rich_text_xml(xml, &shape.rich_text, fill_color);
+    }
+    xml.raw("\n</p:sp>");
 }
 
-/// Generate fill XML for solid color
-fn generate_fill_xml(fill: &Option<ShapeFill>) -> String {
-    match fill {
-        Some(f) => {
-            let alpha = f.transparency
-                .map(|t| format!(r#"<a:alpha val="{}"/>"#, t))
-                .unwrap_or_default();
-            
-            format!(
-                r#"<a:solidFill>
-<a:srgbClr val="{}">{}</a:srgbClr>
-</a:solidFill>"#,
-                f.color, alpha
-            )
+/// Write formatted runs (and fields) as a single centered paragraph
+fn write_rich_text_xml(xml: &mut XmlWriter, runs: &[FormattedText], fill_color: Option<&str>) {
+    xml.raw("<p:txBody>\n<a:bodyPr wrap=\"square\" rtlCol=\"0\" anchor=\"ctr\"/>\n<a:lstStyle/>\n<a:p>\n<a:pPr algn=\"ctr\"/>\n");
+    for text in runs {
+        let mut run = Run::from(text.clone());
+        if run.format.color.is_none() {
+            run.format.color = Some(get_text_color(fill_color).to_string());
         }
-        None => String::new(),
+        xml.raw(&run.to_xml());
     }
+    xml.raw("\n</a:p>\n</p:txBody>");
 }
 
-/// Generate gradient fill XML
-fn generate_gradient_xml(gradient: &GradientFill) -> String {
-    let mut stops_xml = String::new();
+/// Write fill XML for solid color
+fn write_fill_xml(xml: &mut XmlWriter, fill: &Option<ShapeFill>) {
+    if let Some(f) = fill {
+        xml.raw("<a:solidFill>\n");
+        xml.open("a:srgbClr").attr("val", &f.color).close();
+        if let Some(t) = f.transparency {
+            xml.open("a:alpha").attr("val", t).close_empty();
+        }
+        xml.end_element("a:srgbClr");
+        xml.raw("\n</a:solidFill>");
+    }
+}
+
+/// Write gradient fill XML
+fn write_gradient_xml(xml: &mut XmlWriter, gradient: &GradientFill) {
+    xml.raw("<a:gradFill>\n<a:gsLst>\n");
     
     for stop in &gradient.stops {
-        let alpha = stop.transparency
-            .map(|t| format!(r#"<a:alpha val="{}"/>"#, t))
-            .unwrap_or_default();
-        
-        stops_xml.push_str(&format!(
-            r#"<a:gs pos="{}">
-<a:srgbClr val="{}">{}</a:srgbClr>
-</a:gs>"#,
-            stop.position, stop.color, alpha
-        ));
+        xml.open("a:gs").attr("pos", stop.position).close();
+        xml.raw("\n");
+        xml.open("a:srgbClr").attr("val", &stop.color).close();
+        if let Some(t) = stop.transparency {
+            xml.open("a:alpha").attr("val", t).close_empty();
+        }
+        xml.end_element("a:srgbClr");
+        xml.raw("\n</a:gs>");
     }
     
-    format!(
-        r#"<a:gradFill>
-<a:gsLst>
-{}
+    write!(
+        xml,
+        r#"
 </a:gsLst>
 <a:lin ang="{}" scaled="1"/>
 </a:gradFill>"#,
-        stops_xml,
         gradient.direction.to_angle()
-    )
+    );
 }
 
-/// Generate line XML
-fn generate_line_xml(line: &Option<ShapeLine>) -> String {
-    match line {
-        Some(l) => {
-            format!(
-                r#"<a:ln w="{}">
-<a:solidFill>
-<a:srgbClr val="{}"/>
-</a:solidFill>
-</a:ln>"#,
-                l.width, l.color
-            )
-        }
-        None => String::new(),
+/// Write line XML
+fn write_line_xml(xml: &mut XmlWriter, line: &Option<ShapeLine>) {
+    if let Some(l) = line {
+        xml.open("a:ln").attr("w", l.width).close();
+        xml.raw("\n<a:solidFill>\n");
+        xml.open("a:srgbClr").attr("val", &l.color).close_empty();
+        xml.raw("\n</a:solidFill>\n</a:ln>");
     }
 }
 
 /// Calculate optimal font size based on shape dimensions and text content
-fn calculate_font_size(text: &str, width_emu: u32, height_emu: u32) -> u32 {
-    // Convert EMU to approximate character width
-    // 1 inch = 914400 EMU, average char width at 18pt ≈ 0.1 inch
-    let width_inches = width_emu as f64 / 914400.0;
-    let height_inches = height_emu as f64 / 914400.0;
+fn calculate_font_size(text: &str, width: Emu, height: Emu) -> u32 {
+    // Clamp to reasonable range: 800 (8pt) to 4400 (44pt)
+    fitted_font_size(text, width, height).clamp(800.0, 4400.0) as u32
+}
+
+/// Whether `text` fits the shape at the smallest auto-fit size (8pt)
+pub(crate) fn text_fits(text: &str, width: Emu, height: Emu) -> bool {
+    fitted_font_size(text, width, height) >= 800.0
+}
+
+/// Largest font size (hundredths of a point, at most 44pt) at which `text` fits
+fn fitted_font_size(text: &str, width: Emu, height: Emu) -> f64 {
+    // Average char width at 18pt ≈ 0.1 inch
+    let width_inches = width.to_inches();
+    let height_inches = height.to_inches();
     
     // Account for padding (roughly 10% on each side)
     let usable_width = width_inches * 0.8;
@@ -169,10 +203,7 @@ fn calculate_font_size(text: &str, width_emu: u32, height_emu: u32) -> u32 {
     let font_from_height = (1800.0 * height_scale).min(4400.0);
     
     // Use the smaller of the two to ensure text fits
-    let optimal_size = font_from_width.min(font_from_height);
-    
-    // Clamp to reasonable range: 800 (8pt) to 4400 (44pt)
-    (optimal_size.max(800.0).min(4400.0)) as u32
+    font_from_width.min(font_from_height)
 }
 
 /// Calculate if a color is dark (needs white text) or light (needs black text)
@@ -192,15 +223,15 @@ fn is_dark_color(hex_color: &str) -> bool {
 }
 
 /// Get contrasting text color for a given background
-fn get_text_color(fill_color: Option<&str>) -> &'static str {
+pub(crate) fn get_text_color(fill_color: Option<&str>) -> &'static str {
     match fill_color {
         Some(color) if is_dark_color(color) => "FFFFFF", // White text on dark background
         _ => "000000", // Black text on light/no background
     }
 }
 
-/// Generate text body XML for shape with auto-fit font sizing
-fn generate_text_xml_with_autofit(text: &Option<String>, width: u32, height: u32, fill_color: Option<&str>) -> String {
+/// Write text body XML for shape with auto-fit font sizing
+fn write_text_xml_with_autofit(xml: &mut XmlWriter, text: &Option<String>, width: Emu, height: Emu, fill_color: Option<&str>) {
     match text {
         Some(t) => {
             // Check if this is code (starts with [ and contains language tag)
@@ -208,27 +239,24 @@ fn generate_text_xml_with_autofit(text: &Option<String>, width: u32, height: u32
             
             if is_code {
                 // Code block: use monospace font, left align, smaller size
-                let mut paragraphs = String::new();
+                xml.raw(
+                    r#"<p:txBody>
+<a:bodyPr wrap="square" rtlCol="0" anchor="t" lIns="91440" tIns="45720" rIns="91440" bIns="45720"/>
+<a:lstStyle/>
+"#,
+                );
                 for line in t.lines() {
-                    let escaped = escape_xml(line);
-                    paragraphs.push_str(&format!(
+                    xml.raw(
                         r#"<a:p>
 <a:pPr algn="l"/>
 <a:r>
 <a:rPr lang="en-US" sz="1200" dirty="0"><a:latin typeface="Consolas"/><a:solidFill><a:srgbClr val="FFFFFF"/></a:solidFill></a:rPr>
-<a:t>{}</a:t>
-</a:r>
-</a:p>"#,
-                        escaped
-                    ));
+"#,
+                    );
+                    xml.leaf("a:t", line);
+                    xml.raw("\n</a:r>\n</a:p>");
                 }
-                format!(
-                    r#"<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0" anchor="t" lIns="91440" tIns="45720" rIns="91440" bIns="45720"/>
-<a:lstStyle/>
-{}</p:txBody>"#,
-                    paragraphs
-                )
+                xml.raw("</p:txBody>");
             } else {
                 // Calculate optimal font size based on shape dimensions
                 let font_size = calculate_font_size(t, width, height);
@@ -242,7 +270,8 @@ fn generate_text_xml_with_autofit(text: &Option<String>, width: u32, height: u32
                 let anchor = if is_multiline { "t" } else { "ctr" };
                 
                 // Use PowerPoint's auto-fit feature for additional safety
-                format!(
+                write!(
+                    xml,
                     r#"<p:txBody>
 <a:bodyPr wrap="square" rtlCol="0" anchor="{}">
 <a:normAutofit/>
@@ -252,36 +281,39 @@ fn generate_text_xml_with_autofit(text: &Option<String>, width: u32, height: u32
 <a:pPr algn="{}"/>
 <a:r>
 <a:rPr lang="en-US" sz="{}" dirty="0"><a:solidFill><a:srgbClr val="{}"/></a:solidFill></a:rPr>
-<a:t>{}</a:t>
-</a:r>
-</a:p>
-</p:txBody>"#,
+"#,
                     anchor,
                     alignment,
                     font_size,
                     text_color,
-                    escape_xml(t)
-                )
+                );
+                xml.leaf("a:t", t);
+                xml.raw("\n</a:r>\n</a:p>\n</p:txBody>");
             }
         }
         None => {
             // Empty text body required for shapes
-            r#"<p:txBody>
+            xml.raw(
+                r#"<p:txBody>
 <a:bodyPr/>
 <a:lstStyle/>
 <a:p/>
-</p:txBody>"#.to_string()
+</p:txBody>"#,
+            );
         }
     }
 }
 
 /// Generate XML for multiple shapes
 pub fn generate_shapes_xml(shapes: &[Shape], start_id: u32) -> String {
-    shapes.iter()
-        .enumerate()
-        .map(|(i, shape)| generate_shape_xml(shape, start_id + i as u32))
-        .collect::<Vec<_>>()
-        .join("\n")
+    let mut xml = XmlWriter::with_capacity(1024 * shapes.len());
+    for (i, shape) in shapes.iter().enumerate() {
+        if i > 0 {
+            xml.raw("\n");
+        }
+        write_shape_xml(&mut xml, shape, start_id + i as u32);
+    }
+    xml.finish()
 }
 
 /// Generate connector shape XML (for arrows connecting shapes)
@@ -344,6 +376,56 @@ mod tests {
         assert!(xml.contains("FF0000"));
     }
 
+    #[test]
+    fn test_locked_shape_xml() {
+        use crate::generator::locks::Locks;
+
+        let locked = Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).lock(Locks::MOVE | Locks::RESIZE);
+        assert!(generate_shape_xml(&locked, 10).contains(r#"<p:cNvSpPr><a:spLocks noMove="1" noResize="1"/></p:cNvSpPr>"#));
+
+        let free = Shape::new(ShapeType::Rectangle, 0, 0, 100, 100);
+        assert!(generate_shape_xml(&free, 10).contains("<p:cNvSpPr/>"));
+    }
+
+    #[test]
+    fn test_custom_geometry_replaces_preset() {
+        use crate::generator::geometry::{CustomGeometry, GeometryPath};
+
+        let triangle = CustomGeometry::new(10, 10).path(GeometryPath::new().polygon(&[(5, 0), (10, 10), (0, 10)]));
+        let xml = generate_shape_xml(&Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_geometry(triangle), 10);
+        assert!(xml.contains("<a:custGeom>"));
+        assert!(!xml.contains("prstGeom"));
+    }
+
+    #[test]
+    fn test_rich_text_with_field() {
+        use crate::generator::text::{DateTimeFormat, Field};
+
+        let shape = Shape::new(ShapeType::Rectangle, 0, 0, 2895600, 365125)
+            .with_fill(ShapeFill::new("003366"))
+            .with_rich_text(vec![
+                FormattedText::new("Updated "),
+                FormattedText::field(Field::DateTime(DateTimeFormat::ShortDate)),
+            ]);
+
+        let xml = generate_shape_xml(&shape, 10);
+        assert!(xml.contains("<a:t>Updated </a:t></a:r>"));
+        assert!(xml.contains(r#"<a:fld id="{0C1D4C5E-2A7B-4F3D-9E61-8B5A3D27F4C0}" type="datetime1">"#));
+        assert_eq!(xml.matches(r#"<a:srgbClr val="FFFFFF"/>"#).count(), 2);
+        assert!(!xml.contains("normAutofit"));
+    }
+
+    #[test]
+    fn test_shape_units_and_negative_offset() {
+        use crate::core::{Cm, Inches};
+
+        let shape = Shape::new(ShapeType::Rectangle, Emu(-100), Inches(1.0), Cm(2.0), Emu(300000));
+        let xml = generate_shape_xml(&shape, 10);
+
+        assert!(xml.contains(r#"<a:off x="-100" y="914400"/>"#));
+        assert!(xml.contains(r#"<a:ext cx="720000" cy="300000"/>"#));
+    }
+
     #[test]
     fn test_generate_shape_with_text() {
         let shape = Shape::new(ShapeType::Rectangle, 0, 0, 1000000, 500000)
@@ -403,7 +485,7 @@ mod tests {
     #[test]
     fn test_font_size_autofit_small_shape() {
         // Small shape with long text should get smaller font
-        let font_size = calculate_font_size("This is a very long text that needs to fit", 500_000, 300_000);
+        let font_size = calculate_font_size("This is a very long text that needs to fit", Emu(500_000), Emu(300_000));
         assert!(font_size < 1800, "Font should be smaller than 18pt for small shape with long text");
         assert!(font_size >= 800, "Font should not be smaller than 8pt");
     }
@@ -411,14 +493,14 @@ mod tests {
     #[test]
     fn test_font_size_autofit_large_shape() {
         // Large shape with short text should get larger font
-        let font_size = calculate_font_size("Hi", 3_000_000, 2_000_000);
+        let font_size = calculate_font_size("Hi", Emu(3_000_000), Emu(2_000_000));
         assert!(font_size >= 1800, "Font should be at least 18pt for large shape with short text");
     }
 
     #[test]
     fn test_font_size_autofit_multiline() {
         // Multi-line text should account for height
-        let font_size = calculate_font_size("Line 1\nLine 2\nLine 3\nLine 4", 2_000_000, 500_000);
+        let font_size = calculate_font_size("Line 1\nLine 2\nLine 3\nLine 4", Emu(2_000_000), Emu(500_000));
         assert!(font_size < 1800, "Font should be smaller for multi-line text in short shape");
     }
 
@@ -461,4 +543,15 @@ mod tests {
         let xml2 = generate_shape_xml(&shape2, 1);
         assert!(xml2.contains("000000"), "Light fill should have black text");
     }
+
+    #[test]
+    fn test_text_color_contrasts_with_gradient() {
+        use super::super::shapes::{GradientDirection, GradientFill};
+
+        let shape = Shape::new(ShapeType::Rectangle, 0, 0, 1_000_000, 500_000)
+            .with_gradient(GradientFill::three_color("FFFFFF", "0D47A1", "FFFFFF", GradientDirection::Horizontal))
+            .with_text("Test");
+        let xml = generate_shape_xml(&shape, 1);
+        assert!(xml.contains(r#"<a:solidFill><a:srgbClr val="FFFFFF"/></a:solidFill></a:rPr>"#));
+    }
 }
@@ -1,5 +1,8 @@
 //! Bullet point types and formatting
 
+use crate::elements::ColorInput;
+use super::density::Density;
+
 /// Bullet style for lists
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
 pub enum BulletStyle {
@@ -97,13 +100,13 @@ impl BulletTextFormat {
         self
     }
     
-    pub fn color(mut self, hex: &str) -> Self {
-        self.color = Some(hex.trim_start_matches('#').to_uppercase());
+    pub fn color(mut self, hex: impl Into<ColorInput>) -> Self {
+        self.color = Some(hex.into().to_hex());
         self
     }
     
-    pub fn highlight(mut self, hex: &str) -> Self {
-        self.highlight = Some(hex.trim_start_matches('#').to_uppercase());
+    pub fn highlight(mut self, hex: impl Into<ColorInput>) -> Self {
+        self.highlight = Some(hex.into().to_hex());
         self
     }
     
@@ -125,6 +128,16 @@ pub struct BulletPoint {
     pub level: u32,
     pub style: BulletStyle,
     pub format: Option<BulletTextFormat>,
+    /// Style sheet style applied at build time (see [`crate::stylesheet`])
+    pub style_name: Option<String>,
+    /// Space before the paragraph in hundredths of a point
+    pub spacing_before: Option<u32>,
+    /// Space after the paragraph in hundredths of a point
+    pub spacing_after: Option<u32>,
+    /// Line spacing as a percentage of single spacing
+    pub line_spacing: Option<u32>,
+    /// Citation keys, shown as superscript markers after the text
+    pub citations: Vec<String>,
 }
 
 impl BulletPoint {
@@ -134,6 +147,11 @@ impl BulletPoint {
             level: 0,
             style: BulletStyle::Bullet,
             format: None,
+            style_name: None,
+            spacing_before: None,
+            spacing_after: None,
+            line_spacing: None,
+            citations: Vec::new(),
         }
     }
     
@@ -151,7 +169,26 @@ impl BulletPoint {
         self.format = Some(format);
         self
     }
+
+    /// Format with the named style of the presentation's style sheet
+    pub fn style(mut self, name: &str) -> Self {
+        self.style_name = Some(name.to_string());
+        self
+    }
     
+    /// Use the line spacing and space before of a density preset
+    pub fn spacing(mut self, density: Density) -> Self {
+        self.spacing_before = Some(density.spacing_before());
+        self.line_spacing = Some(density.line_spacing());
+        self
+    }
+
+    /// Cite the source registered under `key` (see [`crate::citations`])
+    pub fn cite(mut self, key: &str) -> Self {
+        self.citations.push(key.to_string());
+        self
+    }
+
     pub fn bold(mut self) -> Self {
         self.format = Some(self.format.unwrap_or_default().bold());
         self
@@ -177,12 +214,12 @@ impl BulletPoint {
         self
     }
     
-    pub fn highlight(mut self, color: &str) -> Self {
+    pub fn highlight(mut self, color: impl Into<ColorInput>) -> Self {
         self.format = Some(self.format.unwrap_or_default().highlight(color));
         self
     }
     
-    pub fn color(mut self, hex: &str) -> Self {
+    pub fn color(mut self, hex: impl Into<ColorInput>) -> Self {
         self.format = Some(self.format.unwrap_or_default().color(hex));
         self
     }
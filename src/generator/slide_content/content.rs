@@ -2,15 +2,36 @@
 
 use crate::generator::tables::Table;
 use crate::generator::shapes::Shape;
-use crate::generator::images::Image;
+use crate::generator::images::{Fit, Image, ImageSource};
+use crate::generator::image_grid::ImageGrid;
+use crate::generator::heatmap::Heatmap;
+use crate::generator::quadrant::QuadrantChart;
+use crate::generator::swimlanes::Swimlanes;
+use crate::generator::calendar::Calendar;
+use crate::generator::funnel::Funnel;
+use crate::generator::stepper::Stepper;
+use crate::generator::icons::Icon;
+use crate::generator::annotations::Annotations;
+#[cfg(feature = "qr")]
+use crate::generator::qr::QrCode;
 use crate::generator::connectors::Connector;
-use crate::generator::media::{Video, Audio};
+use crate::generator::media::{Video, Audio, AudioFormat, Narration};
 use crate::generator::charts::Chart;
+use crate::generator::gradients::GradientFill;
+use crate::generator::zoom::SlideZoom;
+use crate::generator::slide_number::SlideNumber;
+use crate::generator::validate::ValidationError;
+use crate::generator::tags::Tags;
 
 use super::bullet::{BulletStyle, BulletPoint};
 use super::layout::SlideLayout;
 use super::code_block::CodeBlock;
-use super::transition::TransitionType;
+use super::density::Density;
+use super::transition::{TransitionType, MorphOption};
+use super::region::Region;
+use super::picture::PicturePlaceholder;
+use crate::generator::placement::SlideSize;
+use crate::elements::ColorInput;
 
 /// Slide content for more complex presentations
 #[derive(Clone, Debug)]
@@ -51,6 +72,24 @@ pub struct SlideContent {
     pub charts: Vec<Chart>,
     /// Code blocks with syntax highlighting
     pub code_blocks: Vec<CodeBlock>,
+    /// Gradient band drawn behind the title on section/centered-title slides
+    pub section_band: Option<GradientFill>,
+    /// Narration audio that plays automatically and advances the slide
+    pub narration: Option<Narration>,
+    /// Slide zooms (clickable thumbnails of other slides)
+    pub slide_zooms: Vec<SlideZoom>,
+    /// Slide number field (set by `Presentation::slide_numbers`)
+    pub slide_number: Option<SlideNumber>,
+    /// Named picture areas, filled with [`Self::set_picture`]
+    pub picture_placeholders: Vec<PicturePlaceholder>,
+    /// Shapes drawn above the images, such as screenshot annotations
+    pub overlays: Vec<Shape>,
+    /// Line and bullet spacing preset (see [`Self::density`])
+    pub density: Option<Density>,
+    /// Fixed `p:sldId` in presentation.xml (from the slide's position if unset)
+    pub slide_id: Option<u32>,
+    /// Custom tags (`p:custDataLst`), see [`Self::tag`]
+    pub tags: Tags,
 }
 
 impl SlideContent {
@@ -84,6 +123,15 @@ impl SlideContent {
             audios: Vec::new(),
             charts: Vec::new(),
             code_blocks: Vec::new(),
+            section_band: None,
+            narration: None,
+            slide_zooms: Vec::new(),
+            slide_number: None,
+            picture_placeholders: Vec::new(),
+            overlays: Vec::new(),
+            density: None,
+            slide_id: None,
+            tags: Tags::new(),
         }
     }
 
@@ -100,6 +148,13 @@ impl SlideContent {
         self
     }
     
+    /// Add a bullet point with its own level, style and formatting
+    pub fn add_bullet_point(mut self, bullet: BulletPoint) -> Self {
+        self.content.push(bullet.text.clone());
+        self.bullets.push(bullet);
+        self
+    }
+    
     /// Add a bullet point with specific style
     pub fn add_styled_bullet(mut self, text: &str, style: BulletStyle) -> Self {
         self.content.push(text.to_string());
@@ -134,6 +189,16 @@ impl SlideContent {
         self
     }
 
+    /// Set font sizes, line spacing and bullet spacing from a preset
+    ///
+    /// Bullets that set their own spacing keep it.
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = Some(density);
+        self.title_size = Some(density.title_size());
+        self.content_size = Some(density.content_size());
+        self
+    }
+
     pub fn title_size(mut self, size: u32) -> Self {
         self.title_size = Some(size);
         self
@@ -174,13 +239,13 @@ impl SlideContent {
         self
     }
 
-    pub fn title_color(mut self, color: &str) -> Self {
-        self.title_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn title_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.title_color = Some(color.into().to_hex());
         self
     }
 
-    pub fn content_color(mut self, color: &str) -> Self {
-        self.content_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn content_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.content_color = Some(color.into().to_hex());
         self
     }
 
@@ -189,6 +254,7 @@ impl SlideContent {
         self
     }
 
+    #[deprecated(since = "0.2.4", note = "use `add_chart()` to embed a chart; it can be called repeatedly")]
     pub fn with_chart(mut self) -> Self {
         self.has_chart = true;
         self
@@ -204,6 +270,15 @@ impl SlideContent {
         self
     }
 
+    /// Draw a gradient band behind the title
+    ///
+    /// Only rendered for `SlideLayout::CenteredTitle` and `SlideLayout::SectionHeader`.
+    /// Use `Theme::section_gradient()` for a band derived from a theme.
+    pub fn section_band(mut self, gradient: GradientFill) -> Self {
+        self.section_band = Some(gradient);
+        self
+    }
+
     pub fn table(mut self, table: Table) -> Self {
         self.table = Some(table);
         self.has_table = true;
@@ -216,6 +291,26 @@ impl SlideContent {
         self
     }
 
+    /// Duplicate this slide as the next step of a Morph animation
+    ///
+    /// Every shape in the copy keeps the ID (and so the name) it has on this
+    /// slide, which is what PowerPoint uses to pair shapes up. `edit` moves,
+    /// resizes or restyles them; shapes added by `edit` get fresh IDs. The
+    /// copy uses the Morph transition (by object).
+    pub fn morph_copy(&self, edit: impl FnOnce(SlideContent) -> SlideContent) -> SlideContent {
+        let mut copy = self.clone();
+        for (i, shape) in copy.shapes.iter_mut().enumerate() {
+            shape.id.get_or_insert((i + 10) as u32);
+        }
+
+        let mut copy = edit(copy.with_transition(TransitionType::Morph(MorphOption::ByObject)));
+        let first_free = copy.shapes.iter().filter_map(|s| s.id).max().map_or(10, |id| id + 1);
+        for (id, shape) in (first_free..).zip(copy.shapes.iter_mut().filter(|s| s.id.is_none())) {
+            shape.id = Some(id);
+        }
+        copy
+    }
+
     /// Add multiple shapes to the slide
     pub fn with_shapes(mut self, shapes: Vec<Shape>) -> Self {
         self.shapes.extend(shapes);
@@ -236,12 +331,133 @@ impl SlideContent {
         self
     }
 
+    /// Tile the grid's images (and their captions) over the slide
+    pub fn image_grid(self, grid: ImageGrid) -> Self {
+        let (images, captions) = grid.layout();
+        self.with_images(images).with_shapes(captions)
+    }
+
+    /// Use the heatmap as the slide's table and draw its legend (see [`Heatmap`])
+    pub fn heatmap(self, heatmap: &Heatmap) -> Self {
+        self.table(heatmap.to_table()).with_shapes(heatmap.legend_shapes())
+    }
+
+    /// Use the month grid as the slide's table (see [`Calendar`])
+    pub fn calendar(self, calendar: &Calendar) -> Self {
+        self.table(calendar.to_table())
+    }
+
+    /// Draw a 2 × 2 matrix with its points (see [`QuadrantChart`])
+    pub fn quadrant_chart(self, chart: &QuadrantChart) -> Self {
+        self.with_shapes(chart.to_shapes())
+    }
+
+    /// Draw a conversion funnel (see [`Funnel`])
+    pub fn funnel(self, funnel: &Funnel) -> Self {
+        self.with_shapes(funnel.to_shapes())
+    }
+
+    /// Draw a progress stepper (see [`Stepper`])
+    pub fn stepper(self, stepper: &Stepper) -> Self {
+        self.with_shapes(stepper.to_shapes())
+    }
+
+    /// Draw a swimlane process diagram after the slide's shapes (see [`Swimlanes`])
+    pub fn swimlanes(self, diagram: &Swimlanes) -> Self {
+        let (shapes, connectors) = diagram.layout(self.shapes.len() as u32 + 10);
+        self.with_shapes(shapes).with_connectors(connectors)
+    }
+
+    /// Add a built-in icon (see [`Icon`])
+    pub fn add_icon(self, icon: Icon) -> Self {
+        self.add_shape(icon.to_shape())
+    }
+
+    /// Draw markers, highlight boxes and arrows over an image (see [`Annotations`])
+    pub fn annotate(mut self, annotations: Annotations) -> Self {
+        let (shapes, arrows) = annotations.layout();
+        self.overlays.extend(shapes);
+        self.with_connectors(arrows)
+    }
+
+    /// Draw the QR code as shapes (see [`QrCode::to_shapes`])
+    #[cfg(feature = "qr")]
+    pub fn qr_code(self, qr: &QrCode) -> Self {
+        self.with_shapes(qr.to_shapes())
+    }
+
+    /// Add an empty picture placeholder called `name` covering `region`
+    ///
+    /// Until [`Self::set_picture`] fills it, it is drawn as a labelled box.
+    pub fn picture_placeholder(mut self, name: &str, region: Region) -> Self {
+        let (x, y, width, height) = region.bounds();
+        let mut image = Image::new(name, width, height, "png").position(x, y);
+        image.source = None;
+        self.picture_placeholders.push(PicturePlaceholder {
+            name: name.to_string(),
+            region,
+            image: self.images.len(),
+            filled: false,
+        });
+        self.images.push(image);
+        self.has_image = true;
+        self
+    }
+
+    /// Put a picture into the placeholder called `name`, cover-fitted to its area
+    ///
+    /// Errors if the slide has no placeholder with that name.
+    pub fn set_picture(mut self, name: &str, source: ImageSource) -> Result<Self, ValidationError> {
+        let placeholder = self.picture_placeholders.iter_mut()
+            .find(|p| p.name == name)
+            .ok_or_else(|| ValidationError::new(format!("no picture placeholder named \"{name}\"")))?;
+        let filename = match &source {
+            ImageSource::File(path) => path.clone(),
+            _ => name.to_string(),
+        };
+        let image = &mut self.images[placeholder.image];
+        *image = cover_picture(image, &filename, source, placeholder.region.bounds());
+        placeholder.filled = true;
+        Ok(self)
+    }
+
+    /// Resolve shape placements, chart regions and picture placeholders for a slide of `size`
+    pub(crate) fn resolve_for_size(&mut self, size: SlideSize) {
+        for shape in self.shapes.iter_mut().chain(&mut self.overlays) {
+            shape.resolve_placement(size);
+        }
+        for chart in &mut self.charts {
+            chart.resolve_region(size);
+        }
+        if size == SlideSize::STANDARD {
+            return;
+        }
+        for placeholder in &self.picture_placeholders {
+            let image = &mut self.images[placeholder.image];
+            match image.source.clone().filter(|_| placeholder.filled) {
+                Some(source) => {
+                    *image = cover_picture(image, &image.filename.clone(), source, placeholder.region.bounds_on(size))
+                }
+                None => (image.x, image.y, image.width, image.height) = placeholder.region.emu_bounds_on(size),
+            }
+        }
+    }
+
     /// Add speaker notes to the slide
     pub fn notes(mut self, notes: &str) -> Self {
         self.notes = Some(notes.to_string());
         self
     }
 
+    /// Set a custom tag on the slide, e.g. `.tag("owner", "finance")`
+    ///
+    /// Tags are kept by PowerPoint but not shown; automation tools use them
+    /// to find the slides they manage (see [`Tags`]).
+    pub fn tag(mut self, name: &str, value: &str) -> Self {
+        self.tags.set(name, value);
+        self
+    }
+
     /// Check if slide has speaker notes
     pub fn has_notes(&self) -> bool {
         self.notes.is_some()
@@ -259,6 +475,13 @@ impl SlideContent {
         self
     }
 
+    /// Bend connectors around the shapes their default path crosses
+    /// (see [`crate::generator::routing`])
+    pub fn route_connectors(mut self) -> Self {
+        crate::generator::routing::route_connectors(&self.shapes, &mut self.connectors);
+        self
+    }
+
     /// Add a video to the slide
     pub fn add_video(mut self, video: Video) -> Self {
         self.videos.push(video);
@@ -283,7 +506,41 @@ impl SlideContent {
         self
     }
 
+    /// Embed narration audio for the slide
+    ///
+    /// The clip starts automatically when the slide is shown, its icon is
+    /// hidden, and the slide advances once the clip ends. The duration is
+    /// read from WAV/MP3 data; use `with_narration` to set it explicitly.
+    pub fn narration(self, data: Vec<u8>, format: AudioFormat) -> Self {
+        self.with_narration(Narration::new(data, format))
+    }
+
+    /// Embed pre-configured narration audio for the slide
+    pub fn with_narration(mut self, narration: Narration) -> Self {
+        self.narration = Some(narration);
+        self
+    }
+
+    /// Add a slide zoom that jumps to another slide when clicked
+    pub fn add_slide_zoom(mut self, zoom: SlideZoom) -> Self {
+        self.slide_zooms.push(zoom);
+        self
+    }
+
+    /// Fill the content area with a grid of zooms to `targets` (1-based slide numbers)
+    ///
+    /// Builds a summary/agenda slide: each thumbnail jumps to its slide and
+    /// returns here afterwards.
+    pub fn summary_zoom(mut self, targets: &[usize]) -> Self {
+        let columns = (targets.len() as f64).sqrt().ceil() as usize;
+        self.slide_zooms.extend(SlideZoom::grid(targets, columns, Region::Content.bounds()));
+        self
+    }
+
     /// Add a chart to the slide
+    ///
+    /// Can be called repeatedly; each chart gets its own chart part and
+    /// relationship, and can share the slide with a table and shapes.
     pub fn add_chart(mut self, chart: Chart) -> Self {
         self.charts.push(chart);
         self.has_chart = true;
@@ -299,12 +556,38 @@ impl SlideContent {
 
     /// Check if slide has any media
     pub fn has_media(&self) -> bool {
-        !self.videos.is_empty() || !self.audios.is_empty()
+        !self.videos.is_empty() || !self.audios.is_empty() || self.narration.is_some()
     }
 
     /// Check if slide has connectors
     pub fn has_connectors(&self) -> bool {
         !self.connectors.is_empty()
     }
+
+    /// Check the slide's shapes, table and charts for invalid values
+    ///
+    /// The error's location names the element, e.g. `shape 2`.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (i, shape) in self.shapes.iter().enumerate() {
+            shape.validate().map_err(|e| e.within(format!("shape {}", i + 1)))?;
+        }
+        if let Some(table) = &self.table {
+            table.validate().map_err(|e| e.within("table"))?;
+        }
+        for (i, chart) in self.charts.iter().enumerate() {
+            chart.validate().map_err(|e| e.within(format!("chart {}", i + 1)))?;
+        }
+        Ok(())
+    }
 }
 
+
+/// `source` cover-fitted to `bounds`, keeping `previous`'s placeholder and locks
+fn cover_picture(previous: &Image, filename: &str, source: ImageSource, (x, y, width, height): (u32, u32, u32, u32)) -> Image {
+    let mut image = Image::from_source(filename, source)
+        .position(x, y)
+        .fit(Fit::Cover, width, height);
+    image.placeholder = previous.placeholder.clone();
+    image.locks = previous.locks;
+    image
+}
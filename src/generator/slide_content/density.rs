@@ -0,0 +1,111 @@
+//! Text density presets
+//!
+//! A [`Density`] sets a slide's title and body font sizes, line spacing and
+//! space between bullets together, so a slide with a lot of content can be
+//! tightened in one step:
+//!
+//! ```
+//! use ppt_rs::generator::{Density, SlideContent};
+//!
+//! let slide = SlideContent::new("Findings")
+//!     .density(Density::Compact)
+//!     .add_bullet("Churn fell in every region");
+//! assert_eq!(slide.content_size, Some(18));
+//! ```
+//!
+//! Sizes set after the density win over the preset, and so do the spacing
+//! values of bullets that set their own.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// How tightly a slide's text is set
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Density {
+    /// Small text, tight lines, little space between bullets
+    Compact,
+    /// Between the two
+    #[default]
+    Cozy,
+    /// The default sizes with open lines and generous space between bullets
+    Comfortable,
+}
+
+impl Density {
+    /// Title font size in points
+    pub fn title_size(&self) -> u32 {
+        match self {
+            Density::Compact => 32,
+            Density::Cozy => 40,
+            Density::Comfortable => 44,
+        }
+    }
+
+    /// Body font size in points
+    pub fn content_size(&self) -> u32 {
+        match self {
+            Density::Compact => 18,
+            Density::Cozy => 24,
+            Density::Comfortable => 28,
+        }
+    }
+
+    /// Line spacing as a percentage of single spacing
+    pub fn line_spacing(&self) -> u32 {
+        match self {
+            Density::Compact => 90,
+            Density::Cozy => 100,
+            Density::Comfortable => 110,
+        }
+    }
+
+    /// Space before each bullet in hundredths of a point
+    pub fn spacing_before(&self) -> u32 {
+        match self {
+            Density::Compact => 200,
+            Density::Cozy => 600,
+            Density::Comfortable => 1200,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Density::Compact => "compact",
+            Density::Cozy => "cozy",
+            Density::Comfortable => "comfortable",
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Density {
+    type Err = String;
+
+    /// `compact`, `cozy` or `comfortable` (case-insensitive)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "compact" => Ok(Density::Compact),
+            "cozy" => Ok(Density::Cozy),
+            "comfortable" => Ok(Density::Comfortable),
+            _ => Err(format!("Unknown density '{s}' (available: compact, cozy, comfortable)")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_density() {
+        assert_eq!("Compact".parse::<Density>(), Ok(Density::Compact));
+        assert_eq!(" comfortable ".parse::<Density>(), Ok(Density::Comfortable));
+        assert!("dense".parse::<Density>().is_err());
+        assert_eq!(Density::Cozy.to_string(), "cozy");
+    }
+}
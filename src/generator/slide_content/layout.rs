@@ -15,9 +15,27 @@ pub enum SlideLayout {
     CenteredTitle,
     /// Two columns: title on left, content on right
     TwoColumn,
+    /// Section divider: left-aligned title across the middle of the slide
+    SectionHeader,
 }
 
 impl SlideLayout {
+    /// Every layout
+    pub const ALL: [SlideLayout; 7] = [
+        SlideLayout::TitleOnly,
+        SlideLayout::TitleAndContent,
+        SlideLayout::TitleAndBigContent,
+        SlideLayout::Blank,
+        SlideLayout::CenteredTitle,
+        SlideLayout::TwoColumn,
+        SlideLayout::SectionHeader,
+    ];
+
+    /// Layout for an [`as_str`](Self::as_str) name such as `"twoColumn"`
+    pub fn from_name(name: &str) -> Option<SlideLayout> {
+        Self::ALL.into_iter().find(|layout| layout.as_str() == name)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             SlideLayout::TitleOnly => "titleOnly",
@@ -26,7 +44,13 @@ impl SlideLayout {
             SlideLayout::Blank => "blank",
             SlideLayout::CenteredTitle => "centeredTitle",
             SlideLayout::TwoColumn => "twoColumn",
+            SlideLayout::SectionHeader => "sectionHeader",
         }
     }
+
+    /// Whether this layout is a title/divider slide that can carry a section band
+    pub fn is_section_layout(&self) -> bool {
+        matches!(self, SlideLayout::CenteredTitle | SlideLayout::SectionHeader)
+    }
 }
 
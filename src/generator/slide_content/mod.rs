@@ -5,18 +5,27 @@
 //! - `BulletPoint` - Individual bullet point with formatting
 //! - `BulletTextFormat` - Text formatting for bullet points
 //! - `SlideLayout` - Layout types (title only, title and content, etc.)
+//! - `Region` - Named slide areas for placing charts and other elements
+//! - `CustomLayout` - Built-in layout with named picture placeholders
 //! - `SlideContent` - Complete slide content builder
 //! - `CodeBlock` - Code block with syntax highlighting
+//! - `Density` - Compact, cozy or comfortable text presets
 
 mod bullet;
 mod layout;
+mod region;
+mod picture;
 mod code_block;
+mod density;
 mod content;
 pub mod transition;
 
 pub use bullet::{BulletStyle, BulletPoint, BulletTextFormat};
 pub use layout::SlideLayout;
+pub use region::Region;
+pub use picture::{CustomLayout, PicturePlaceholder};
 pub use code_block::CodeBlock;
+pub use density::Density;
 pub use content::SlideContent;
-pub use transition::TransitionType;
+pub use transition::{TransitionType, MorphOption};
 
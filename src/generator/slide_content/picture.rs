@@ -0,0 +1,116 @@
+//! Named picture placeholders and the custom layouts that define them
+//!
+//! A [`CustomLayout`] adds named picture areas ("hero", "logo") to one of
+//! the built-in layouts. Slides made from it show a labelled placeholder
+//! in each area until [`SlideContent::set_picture`] drops an image in,
+//! cover-fitted to the area, so a template can be filled by name.
+
+use super::content::SlideContent;
+use super::layout::SlideLayout;
+use super::region::Region;
+
+/// Named picture area of a slide, backed by one of its images
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PicturePlaceholder {
+    pub name: String,
+    pub region: Region,
+    /// Index of the image drawn in the area, in `SlideContent::images`
+    pub image: usize,
+    /// Whether a picture has been set
+    pub filled: bool,
+}
+
+/// A built-in layout plus named picture placeholders
+///
+/// ```
+/// use ppt_rs::generator::{CustomLayout, ImageSource, Region, SlideLayout};
+///
+/// let layout = CustomLayout::new("Product", SlideLayout::TitleOnly)
+///     .picture("hero", Region::LeftHalf)
+///     .picture("logo", Region::Custom { x: 7_772_400, y: 6_172_200, width: 914_400, height: 457_200 });
+///
+/// let slide = layout.slide("Our new phone")
+///     .set_picture("hero", ImageSource::File("phone.jpg".into()))
+///     .unwrap();
+/// assert!(slide.picture_placeholders[0].filled);
+/// assert!(!slide.picture_placeholders[1].filled);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomLayout {
+    pub name: String,
+    /// Layout used for the title and body
+    pub base: SlideLayout,
+    /// Picture placeholder names and areas, in drawing order
+    pub pictures: Vec<(String, Region)>,
+}
+
+impl CustomLayout {
+    pub fn new(name: &str, base: SlideLayout) -> Self {
+        CustomLayout {
+            name: name.to_string(),
+            base,
+            pictures: Vec::new(),
+        }
+    }
+
+    /// Add a picture placeholder called `name` covering `region`
+    pub fn picture(mut self, name: &str, region: Region) -> Self {
+        self.pictures.push((name.to_string(), region));
+        self
+    }
+
+    /// A new slide with this layout and empty picture placeholders
+    pub fn slide(&self, title: &str) -> SlideContent {
+        self.pictures.iter().fold(
+            SlideContent::new(title).layout(self.base),
+            |slide, (name, region)| slide.picture_placeholder(name, *region),
+        )
+    }
+}
+
+#[cfg(all(test, feature = "media"))]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::generator::ImageSource;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = Vec::new();
+        image::RgbImage::new(width, height)
+            .write_to(&mut Cursor::new(&mut data), image::ImageOutputFormat::Png)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_set_picture_covers_placeholder() {
+        let region = Region::Custom { x: 100, y: 200, width: 2_000_000, height: 1_000_000 };
+        let slide = CustomLayout::new("Banner", SlideLayout::TitleOnly)
+            .picture("logo", Region::TopHalf)
+            .picture("hero", region)
+            .slide("Launch")
+            .set_picture("hero", ImageSource::Bytes(png(40, 40)))
+            .unwrap();
+
+        let hero = &slide.picture_placeholders[1];
+        assert!(hero.filled);
+        let image = &slide.images[hero.image];
+        assert_eq!((image.x.0, image.y.0, image.width.0, image.height.0), (100, 200, 2_000_000, 1_000_000));
+        assert_eq!(image.format, "PNG");
+        let crop = image.crop.as_ref().unwrap();
+        assert_eq!((crop.left, crop.top, crop.bottom), (0.0, 0.25, 0.25));
+
+        let logo = &slide.images[slide.picture_placeholders[0].image];
+        assert!(logo.source.is_none());
+        assert_eq!(logo.filename, "logo");
+    }
+
+    #[test]
+    fn test_set_picture_unknown_name() {
+        let err = SlideContent::new("Plain")
+            .set_picture("hero", ImageSource::Bytes(png(1, 1)))
+            .unwrap_err();
+        assert!(err.to_string().contains("no picture placeholder named \"hero\""));
+    }
+}
@@ -0,0 +1,113 @@
+//! Named slide regions for placing elements
+
+use crate::core::Emu;
+use crate::generator::constants::{
+    SLIDE_WIDTH, SLIDE_HEIGHT, CONTENT_X, CONTENT_Y_START, CONTENT_WIDTH, CONTENT_HEIGHT,
+};
+use crate::generator::placement::SlideSize;
+
+/// Gap between side-by-side or stacked halves of the content area (0.25 inch)
+const REGION_GAP: u32 = 228600;
+
+/// Named area of a slide that an element can be placed into
+///
+/// Regions resolve to EMU bounds `(x, y, width, height)` on the standard
+/// 10" × 7.5" slide, or on any slide size with [`Region::bounds_on`]. The
+/// content area sits under the title, matching the body placeholder of the
+/// title-and-content layout. Charts and picture placeholders placed into a
+/// region are re-resolved for the presentation's slide size when it is built.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Region {
+    /// Content area under the title
+    #[default]
+    Content,
+    /// Left half of the content area
+    LeftHalf,
+    /// Right half of the content area
+    RightHalf,
+    /// Top half of the content area
+    TopHalf,
+    /// Bottom half of the content area
+    BottomHalf,
+    /// Entire slide
+    FullSlide,
+    /// Explicit bounds in EMU
+    Custom { x: u32, y: u32, width: u32, height: u32 },
+}
+
+impl Region {
+    /// Resolve the region to `(x, y, width, height)` in EMU on a slide of `size`
+    ///
+    /// Named regions scale with the slide like the layouts do; custom
+    /// bounds are kept as given.
+    pub fn bounds_on(&self, size: SlideSize) -> (u32, u32, u32, u32) {
+        match *self {
+            Region::Custom { x, y, width, height } => (x, y, width, height),
+            region => size.scale_bounds(region.bounds()),
+        }
+    }
+
+    /// [`Self::bounds_on`] as [`Emu`], for elements that store signed geometry
+    pub(crate) fn emu_bounds_on(&self, size: SlideSize) -> (Emu, Emu, Emu, Emu) {
+        let (x, y, width, height) = self.bounds_on(size);
+        (x.into(), y.into(), width.into(), height.into())
+    }
+
+    /// Resolve the region to `(x, y, width, height)` in EMU on the standard slide
+    pub fn bounds(&self) -> (u32, u32, u32, u32) {
+        let half_width = (CONTENT_WIDTH - REGION_GAP) / 2;
+        let half_height = (CONTENT_HEIGHT - REGION_GAP) / 2;
+        match *self {
+            Region::Content => (CONTENT_X, CONTENT_Y_START, CONTENT_WIDTH, CONTENT_HEIGHT),
+            Region::LeftHalf => (CONTENT_X, CONTENT_Y_START, half_width, CONTENT_HEIGHT),
+            Region::RightHalf => (
+                CONTENT_X + half_width + REGION_GAP,
+                CONTENT_Y_START,
+                half_width,
+                CONTENT_HEIGHT,
+            ),
+            Region::TopHalf => (CONTENT_X, CONTENT_Y_START, CONTENT_WIDTH, half_height),
+            Region::BottomHalf => (
+                CONTENT_X,
+                CONTENT_Y_START + half_height + REGION_GAP,
+                CONTENT_WIDTH,
+                half_height,
+            ),
+            Region::FullSlide => (0, 0, SLIDE_WIDTH, SLIDE_HEIGHT),
+            Region::Custom { x, y, width, height } => (x, y, width, height),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_region_is_under_title() {
+        let (x, y, w, h) = Region::Content.bounds();
+        assert_eq!((x, y, w, h), (CONTENT_X, CONTENT_Y_START, CONTENT_WIDTH, CONTENT_HEIGHT));
+        assert_eq!(Region::default(), Region::Content);
+    }
+
+    #[test]
+    fn test_half_regions_do_not_overlap() {
+        let (lx, _, lw, _) = Region::LeftHalf.bounds();
+        let (rx, _, rw, _) = Region::RightHalf.bounds();
+        assert!(lx + lw < rx);
+        assert_eq!(rx + rw, CONTENT_X + CONTENT_WIDTH);
+
+        let (_, ty, _, th) = Region::TopHalf.bounds();
+        let (_, by, _, _) = Region::BottomHalf.bounds();
+        assert!(ty + th < by);
+    }
+
+    #[test]
+    fn test_regions_scale_to_slide_size() {
+        assert_eq!(Region::FullSlide.bounds_on(SlideSize::WIDESCREEN), (0, 0, 12192000, 6858000));
+        let (x, y, w, h) = Region::Content.bounds_on(SlideSize::WIDESCREEN);
+        assert_eq!((x, y, w, h), (CONTENT_X * 4 / 3, CONTENT_Y_START, CONTENT_WIDTH * 4 / 3, CONTENT_HEIGHT));
+        let custom = Region::Custom { x: 1, y: 2, width: 3, height: 4 };
+        assert_eq!(custom.bounds_on(SlideSize::WIDESCREEN), (1, 2, 3, 4));
+    }
+}
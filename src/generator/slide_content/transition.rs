@@ -1,5 +1,27 @@
 //! Slide transition types
 
+/// How the Morph transition matches content between slides
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum MorphOption {
+    /// Match whole objects by name
+    #[default]
+    ByObject,
+    /// Also morph matching words within text
+    ByWord,
+    /// Also morph matching characters within text
+    ByChar,
+}
+
+impl MorphOption {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MorphOption::ByObject => "byObject",
+            MorphOption::ByWord => "byWord",
+            MorphOption::ByChar => "byChar",
+        }
+    }
+}
+
 /// Slide transition effects
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
 pub enum TransitionType {
@@ -13,21 +35,82 @@ pub enum TransitionType {
     Reveal,
     Cover,
     Zoom,
+    /// PowerPoint 2019+ Morph; shapes with the same name animate between slides
+    Morph(MorphOption),
 }
 
 impl TransitionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransitionType::None => "none",
+            TransitionType::Fade => "fade",
+            TransitionType::Cut => "cut",
+            TransitionType::Push => "push",
+            TransitionType::Wipe => "wipe",
+            TransitionType::Split => "split",
+            TransitionType::Reveal => "reveal",
+            TransitionType::Cover => "cover",
+            TransitionType::Zoom => "zoom",
+            TransitionType::Morph(_) => "morph",
+        }
+    }
+
     /// Generate XML for the transition
     pub fn to_xml(&self) -> String {
+        self.to_xml_with_advance(None)
+    }
+
+    /// Generate XML for the transition, advancing automatically after
+    /// `advance_ms` milliseconds when set
+    pub fn to_xml_with_advance(&self, advance_ms: Option<u32>) -> String {
+        if let TransitionType::Morph(option) = self {
+            return morph_transition_xml(*option, advance_ms);
+        }
+
+        let effect = self.effect_xml();
+        match advance_ms {
+            Some(ms) => format!(r#"<p:transition advTm="{}">{}</p:transition>"#, ms, effect),
+            None if effect.is_empty() => String::new(),
+            None => format!("<p:transition>{}</p:transition>", effect),
+        }
+    }
+
+    /// Effect element inside `p:transition` (empty for an instant cut)
+    fn effect_xml(&self) -> &'static str {
         match self {
-            TransitionType::None => String::new(),
-            TransitionType::Cut => String::new(), // Default is cut/instant
-            TransitionType::Fade => r#"<p:transition><p:fade/></p:transition>"#.to_string(),
-            TransitionType::Push => r#"<p:transition><p:push dir="r"/></p:transition>"#.to_string(), // Default right
-            TransitionType::Wipe => r#"<p:transition><p:wipe dir="r"/></p:transition>"#.to_string(), // Default right
-            TransitionType::Split => r#"<p:transition><p:split dir="out" orient="horz"/></p:transition>"#.to_string(),
-            TransitionType::Reveal => r#"<p:transition><p:reveal dir="r"/></p:transition>"#.to_string(),
-            TransitionType::Cover => r#"<p:transition><p:cover dir="r"/></p:transition>"#.to_string(),
-            TransitionType::Zoom => r#"<p:transition><p:zoom dir="in"/></p:transition>"#.to_string(),
+            TransitionType::None => "",
+            TransitionType::Cut => "", // Default is cut/instant
+            TransitionType::Fade => "<p:fade/>",
+            TransitionType::Push => r#"<p:push dir="r"/>"#, // Default right
+            TransitionType::Wipe => r#"<p:wipe dir="r"/>"#, // Default right
+            TransitionType::Split => r#"<p:split dir="out" orient="horz"/>"#,
+            TransitionType::Reveal => r#"<p:reveal dir="r"/>"#,
+            TransitionType::Cover => r#"<p:cover dir="r"/>"#,
+            TransitionType::Zoom => r#"<p:zoom dir="in"/>"#,
+            TransitionType::Morph(_) => "<p:fade/>", // Fallback for older readers
         }
     }
 }
+
+/// Morph transition with a fade fallback for readers without Morph support
+fn morph_transition_xml(option: MorphOption, advance_ms: Option<u32>) -> String {
+    let advance = advance_ms.map(|ms| format!(r#" advTm="{}""#, ms)).unwrap_or_default();
+    format!(
+        r#"<mc:AlternateContent xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006"><mc:Choice xmlns:p159="http://schemas.microsoft.com/office/powerpoint/2015/09/main" Requires="p159"><p:transition spd="slow"{advance}><p159:morph option="{}"/></p:transition></mc:Choice><mc:Fallback><p:transition spd="slow"{advance}><p:fade/></p:transition></mc:Fallback></mc:AlternateContent>"#,
+        option.as_str()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_morph_transition_xml() {
+        let xml = TransitionType::Morph(MorphOption::ByWord).to_xml_with_advance(Some(3000));
+        assert!(xml.contains(r#"<p159:morph option="byWord"/>"#));
+        assert!(xml.contains(r#"Requires="p159""#));
+        assert_eq!(xml.matches(r#"advTm="3000""#).count(), 2);
+        assert!(xml.contains("<mc:Fallback><p:transition spd=\"slow\" advTm=\"3000\"><p:fade/>"));
+    }
+}
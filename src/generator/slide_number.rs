@@ -0,0 +1,214 @@
+//! Slide numbers
+//!
+//! Slide numbers are written as a `sldNum` placeholder holding an
+//! `a:fld type="slidenum"` field, not as literal text, so PowerPoint
+//! renumbers the slides when they are reordered, inserted or deleted.
+//! The starting number is stored in `ppt/presentation.xml` as
+//! `p:presentation/@firstSlideNum`.
+//!
+//! The text generated here is only the field's cached value. PowerPoint
+//! always recomputes `slidenum` fields as Arabic numerals, so the Roman
+//! formats show until the field is refreshed, and the total in
+//! [`SlideNumberFormat::OfTotal`] is literal text that is not updated.
+
+use crate::generator::slide_content::SlideContent;
+use crate::generator::text::SLIDE_NUMBER_FIELD_ID;
+
+/// How a slide number is displayed
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum SlideNumberFormat {
+    /// 1, 2, 3
+    #[default]
+    Arabic,
+    /// I, II, III
+    Roman,
+    /// i, ii, iii
+    RomanLower,
+    /// 3 / 24
+    OfTotal,
+}
+
+/// Slide numbering settings for a presentation
+///
+/// # Example
+///
+/// ```
+/// use ppt_rs::generator::{SlideNumbering, SlideNumberFormat};
+///
+/// let numbering = SlideNumbering::new(SlideNumberFormat::OfTotal)
+///     .skip_title(true)
+///     .start_at(0);
+/// assert_eq!(numbering.number_for(2), 1);
+/// ```
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct SlideNumbering {
+    pub format: SlideNumberFormat,
+    /// Leave the number off the first slide (it still counts)
+    pub skip_title: bool,
+    /// Number shown on the first slide (`firstSlideNum`)
+    pub start_at: u32,
+}
+
+impl Default for SlideNumbering {
+    fn default() -> Self {
+        SlideNumbering::new(SlideNumberFormat::Arabic)
+    }
+}
+
+impl SlideNumbering {
+    /// Number every slide from 1 in `format`
+    pub fn new(format: SlideNumberFormat) -> Self {
+        SlideNumbering {
+            format,
+            skip_title: false,
+            start_at: 1,
+        }
+    }
+
+    /// Leave the number off the first slide
+    ///
+    /// Like PowerPoint's "Don't show on title slide", the title slide still
+    /// counts; use `start_at(0)` to number the next slide 1.
+    pub fn skip_title(mut self, skip: bool) -> Self {
+        self.skip_title = skip;
+        self
+    }
+
+    /// Set the number of the first slide
+    pub fn start_at(mut self, first: u32) -> Self {
+        self.start_at = first;
+        self
+    }
+
+    /// Number of the slide at `position` (1-based)
+    pub fn number_for(&self, position: usize) -> u32 {
+        self.start_at + position as u32 - 1
+    }
+
+    /// Give each slide its number, leaving the title slide unnumbered if requested
+    pub fn apply(&self, slides: &mut [SlideContent]) {
+        let total = self.number_for(slides.len());
+        for (i, slide) in slides.iter_mut().enumerate() {
+            slide.slide_number = (i > 0 || !self.skip_title).then(|| SlideNumber {
+                value: self.number_for(i + 1),
+                format: self.format,
+                total,
+                first: self.start_at,
+            });
+        }
+    }
+}
+
+/// Slide number placed on a single slide
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct SlideNumber {
+    /// Number shown (the field's cached value)
+    pub value: u32,
+    pub format: SlideNumberFormat,
+    /// Number of the last slide, shown by [`SlideNumberFormat::OfTotal`]
+    pub total: u32,
+    /// Number of the presentation's first slide
+    pub first: u32,
+}
+
+impl SlideNumber {
+    /// Cached text of the slide number field
+    pub fn field_text(&self) -> String {
+        match self.format {
+            SlideNumberFormat::Arabic | SlideNumberFormat::OfTotal => self.value.to_string(),
+            SlideNumberFormat::Roman => to_roman(self.value),
+            SlideNumberFormat::RomanLower => to_roman(self.value).to_lowercase(),
+        }
+    }
+
+    /// Text following the field (` / N` for [`SlideNumberFormat::OfTotal`])
+    pub fn suffix(&self) -> Option<String> {
+        (self.format == SlideNumberFormat::OfTotal).then(|| format!(" / {}", self.total))
+    }
+}
+
+/// Roman numeral for `n`, or Arabic when `n` has none (0)
+fn to_roman(mut n: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+        (100, "C"), (90, "XC"), (50, "L"), (40, "XL"),
+        (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+    ];
+    if n == 0 {
+        return "0".to_string();
+    }
+    let mut roman = String::new();
+    for &(value, numeral) in &NUMERALS {
+        while n >= value {
+            roman.push_str(numeral);
+            n -= value;
+        }
+    }
+    roman
+}
+
+/// Generate the slide number placeholder shape
+pub fn generate_slide_number_xml(number: &SlideNumber, shape_id: usize) -> String {
+    let suffix = number.suffix()
+        .map(|text| format!("<a:r><a:rPr lang=\"en-US\" sz=\"1200\"/><a:t>{text}</a:t></a:r>"))
+        .unwrap_or_default();
+    format!(
+        r#"<p:sp>
+<p:nvSpPr>
+<p:cNvPr id="{shape_id}" name="Slide Number Placeholder {shape_id}"/>
+<p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr>
+<p:nvPr><p:ph type="sldNum" sz="quarter" idx="12"/></p:nvPr>
+</p:nvSpPr>
+<p:spPr>
+<a:xfrm>
+<a:off x="6553200" y="6356350"/>
+<a:ext cx="2133600" cy="365125"/>
+</a:xfrm>
+</p:spPr>
+<p:txBody>
+<a:bodyPr/>
+<a:lstStyle/>
+<a:p>
+<a:pPr algn="r"/>
+<a:fld id="{SLIDE_NUMBER_FIELD_ID}" type="slidenum"><a:rPr lang="en-US" sz="1200"/><a:t>{}</a:t></a:fld>{suffix}
+</a:p>
+</p:txBody>
+</p:sp>"#,
+        number.field_text()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roman_numerals() {
+        assert_eq!(to_roman(4), "IV");
+        assert_eq!(to_roman(14), "XIV");
+        assert_eq!(to_roman(1999), "MCMXCIX");
+        assert_eq!(to_roman(0), "0");
+    }
+
+    #[test]
+    fn test_apply_skips_title_and_offsets() {
+        let mut slides = vec![SlideContent::new("Title"), SlideContent::new("A"), SlideContent::new("B")];
+        SlideNumbering::new(SlideNumberFormat::OfTotal).skip_title(true).start_at(0).apply(&mut slides);
+
+        assert!(slides[0].slide_number.is_none());
+        let second = slides[1].slide_number.unwrap();
+        assert_eq!(second.field_text(), "1");
+        assert_eq!(second.suffix().as_deref(), Some(" / 2"));
+        assert_eq!(second.first, 0);
+    }
+
+    #[test]
+    fn test_slide_number_uses_field() {
+        let number = SlideNumber { value: 3, format: SlideNumberFormat::Roman, total: 5, first: 1 };
+        let xml = generate_slide_number_xml(&number, 12);
+
+        assert!(xml.contains(r#"<p:ph type="sldNum" sz="quarter" idx="12"/>"#));
+        assert!(xml.contains(r#"type="slidenum"><a:rPr lang="en-US" sz="1200"/><a:t>III</a:t></a:fld>"#));
+        assert!(!xml.contains(" / 5"));
+    }
+}
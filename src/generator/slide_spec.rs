@@ -0,0 +1,122 @@
+//! JSON slide descriptions
+//!
+//! Callers outside Rust (the C API, services) describe a slide as JSON
+//! instead of constructing a [`SlideContent`]:
+//!
+//! ```json
+//! {
+//!   "title": "Revenue",
+//!   "layout": "titleAndContent",
+//!   "bullets": ["Up 25% year on year", {"text": "EMEA leads", "level": 1}],
+//!   "notes": "Open with the headline",
+//!   "visualizations": [
+//!     {"type": "bar", "data": [{"q": "Q1", "v": 10}], "encoding": {"x": "q", "y": "v"}}
+//!   ]
+//! }
+//! ```
+//!
+//! `layout` is a [`SlideLayout`] name (`titleOnly`, `twoColumn`, ...) and
+//! each visualization is a [`VizSpec`](super::VizSpec).
+
+use serde::Deserialize;
+
+use crate::exc::{PptxError, Result};
+use crate::generator::slide_content::{SlideContent, SlideLayout};
+use crate::generator::viz::VizSpec;
+
+/// Slide described by a JSON spec
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SlideSpec {
+    #[serde(default)]
+    pub title: String,
+    /// Layout name; the default layout when absent
+    #[serde(default)]
+    pub layout: Option<String>,
+    #[serde(default)]
+    pub bullets: Vec<BulletSpec>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Charts and tables
+    #[serde(default)]
+    pub visualizations: Vec<VizSpec>,
+}
+
+/// Bullet text, or text with an indent level (0 or 1)
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum BulletSpec {
+    Text(String),
+    Leveled {
+        text: String,
+        #[serde(default)]
+        level: u32,
+    },
+}
+
+impl SlideSpec {
+    /// Parse a spec from JSON
+    pub fn from_json(json: &str) -> Result<SlideSpec> {
+        serde_json::from_str(json).map_err(|e| PptxError::InvalidValue(format!("invalid slide spec: {e}")))
+    }
+
+    /// Build the slide the spec describes
+    pub fn build(&self) -> Result<SlideContent> {
+        let mut slide = SlideContent::new(&self.title);
+        if let Some(name) = &self.layout {
+            let layout = SlideLayout::from_name(name)
+                .ok_or_else(|| PptxError::InvalidValue(format!("unknown slide layout \"{name}\"")))?;
+            slide = slide.layout(layout);
+        }
+        for bullet in &self.bullets {
+            slide = match bullet {
+                BulletSpec::Text(text) | BulletSpec::Leveled { text, level: 0 } => slide.add_bullet(text),
+                BulletSpec::Leveled { text, level: 1 } => slide.add_sub_bullet(text),
+                BulletSpec::Leveled { level, .. } => {
+                    return Err(PptxError::InvalidValue(format!("bullet level must be 0 or 1, got {level}")));
+                }
+            };
+        }
+        if let Some(notes) = &self.notes {
+            slide = slide.notes(notes);
+        }
+        for visualization in &self.visualizations {
+            slide = slide.add_visualization(visualization.build()?);
+        }
+        Ok(slide)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slide_spec() {
+        let slide = SlideSpec::from_json(r#"{
+            "title": "Revenue",
+            "layout": "twoColumn",
+            "bullets": ["Up", {"text": "EMEA", "level": 1}, {"text": "Down"}],
+            "notes": "Headline first",
+            "visualizations": [
+                {"type": "pie", "data": [{"q": "Q1", "v": 10}, {"q": "Q2", "v": 5}], "encoding": {"x": "q", "y": "v"}}
+            ]
+        }"#).unwrap().build().unwrap();
+
+        assert_eq!(slide.title, "Revenue");
+        assert_eq!(slide.layout, SlideLayout::TwoColumn);
+        let bullets: Vec<_> = slide.bullets.iter().map(|b| (b.text.as_str(), b.level)).collect();
+        assert_eq!(bullets, [("Up", 0), ("EMEA", 1), ("Down", 0)]);
+        assert_eq!(slide.notes.as_deref(), Some("Headline first"));
+        assert_eq!(slide.charts.len(), 1);
+    }
+
+    #[test]
+    fn test_slide_spec_errors() {
+        assert!(SlideSpec::from_json(r#"{"title": "x", "bulets": []}"#).is_err());
+        let layout = SlideSpec::from_json(r#"{"layout": "sideways"}"#).unwrap().build().unwrap_err();
+        assert!(layout.to_string().contains("unknown slide layout \"sideways\""));
+        let level = SlideSpec::from_json(r#"{"bullets": [{"text": "deep", "level": 3}]}"#).unwrap().build().unwrap_err();
+        assert!(level.to_string().contains("bullet level must be 0 or 1"));
+    }
+}
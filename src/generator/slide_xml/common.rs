@@ -1,7 +1,12 @@
 //! Common XML templates and utilities for slide generation
 
-/// Standard slide header with background
-pub const SLIDE_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+use crate::core::Emu;
+use crate::generator::placement::SlideSize;
+
+/// Slide header with background, for a slide of `size`
+pub fn slide_header(size: SlideSize) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
 <p:cSld>
 <p:bg>
@@ -18,11 +23,15 @@ pub const SLIDE_HEADER: &str = r#"<?xml version="1.0" encoding="UTF-8" standalon
 <p:grpSpPr>
 <a:xfrm>
 <a:off x="0" y="0"/>
-<a:ext cx="9144000" cy="6858000"/>
+<a:ext cx="{cx}" cy="{cy}"/>
 <a:chOff x="0" y="0"/>
-<a:chExt cx="9144000" cy="6858000"/>
+<a:chExt cx="{cx}" cy="{cy}"/>
 </a:xfrm>
-</p:grpSpPr>"#;
+</p:grpSpPr>"#,
+        cx = size.width,
+        cy = size.height,
+    )
+}
 
 /// Standard slide footer
 pub const SLIDE_FOOTER: &str = r#"
@@ -45,12 +54,13 @@ pub fn create_slide_rels_xml() -> String {
 pub fn generate_title_shape(
     title_text: &str,
     title_props: &str,
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+    x: impl Into<Emu>,
+    y: impl Into<Emu>,
+    width: impl Into<Emu>,
+    height: impl Into<Emu>,
     align: &str,
 ) -> String {
+    let (x, y, width, height) = (x.into(), y.into(), width.into(), height.into());
     format!(
         r#"<p:sp>
 <p:nvSpPr>
@@ -1,63 +1,130 @@
-//! Additional content rendering (shapes, images, code blocks, connectors)
+//! Slide element rendering (layout text, shapes, images, code blocks, connectors, charts)
 
 use crate::generator::slide_content::SlideContent;
-use crate::generator::shapes_xml::generate_shape_xml;
+use crate::generator::shapes::Shape;
+use crate::generator::ir::{Element, Slide};
+use crate::core::{escape_xml, XmlWriter};
+use crate::generator::images_xml::generate_image_xml_with_rid;
+use crate::generator::shapes_xml::write_shape_xml;
+use super::layouts::{generate_section_band, generate_text_box};
 
-/// Render additional content elements (shapes, images, code blocks, connectors, charts)
-pub fn render_additional_content(xml: &mut String, content: &SlideContent, chart_rids: &[String]) {
-    // Render shapes - use shape's fixed ID if set, otherwise auto-assign
-    for (i, shape) in content.shapes.iter().enumerate() {
-        xml.push('\n');
-        let shape_id = shape.id.unwrap_or((i + 10) as u32);
-        xml.push_str(&generate_shape_xml(shape, shape_id));
-    }
+/// Render the slide's elements in drawing order, one per line
+pub fn render_elements(xml: &mut String, slide: &Slide, chart_rids: &[String]) {
+    let shapes = slide.elements.iter().filter_map(|e| match e {
+        Element::Shape(shape) => Some(shape.as_ref()),
+        _ => None,
+    });
+    // Shapes use their fixed ID if set, otherwise one from their index; the
+    // remaining elements get sequential IDs after the highest shape ID so
+    // every element on the slide has a unique cNvPr id
+    let mut next_id = first_sequential_id(shapes);
+    let mut shape_index = 0;
+    let mut chart_rids = chart_rids.iter();
 
-    // Render image placeholders
-    let image_start_id = 20 + content.shapes.len();
-    for (i, image) in content.images.iter().enumerate() {
-        xml.push('\n');
-        xml.push_str(&generate_image_placeholder(image_start_id + i, image));
+    for element in &slide.elements {
+        match element {
+            Element::Band { frame, fill } => {
+                xml.push('\n');
+                xml.push_str(&generate_section_band(*frame, fill));
+            }
+            Element::Text(text) => {
+                xml.push('\n');
+                xml.push_str(&generate_text_box(text));
+            }
+            Element::Table(table) => {
+                xml.push('\n');
+                xml.push_str(&crate::generator::tables_xml::generate_table_xml(table, 3));
+            }
+            Element::Shape(shape) => {
+                let mut writer = XmlWriter::from_string(std::mem::take(xml));
+                writer.raw("\n");
+                write_shape_xml(&mut writer, shape, shape_id(shape, shape_index));
+                *xml = writer.finish();
+                shape_index += 1;
+            }
+            // Embedded pictures once related, placeholders otherwise
+            Element::Picture(image) => {
+                xml.push('\n');
+                match &image.r_id {
+                    Some(r_id) => xml.push_str(&generate_image_xml_with_rid(image, next_id, r_id)),
+                    None => xml.push_str(&generate_image_placeholder(next_id, image)),
+                }
+                next_id += 1;
+            }
+            Element::Code(code_block) => {
+                xml.push('\n');
+                xml.push_str(&generate_code_block(next_id, code_block));
+                next_id += 1;
+            }
+            Element::Connector(connector) => {
+                xml.push('\n');
+                xml.push_str(&crate::generator::connectors::generate_connector_xml(connector, next_id));
+                next_id += 1;
+            }
+            // One graphicFrame per chart, each with its own relationship
+            Element::Chart(chart) => {
+                if let Some(r_id) = chart_rids.next() {
+                    xml.push('\n');
+                    xml.push_str(&crate::generator::charts::generate_chart_ref_xml(chart, r_id, next_id));
+                    next_id += 1;
+                }
+            }
+        }
     }
+}
 
-    // Render code blocks with syntax highlighting
-    let code_start_id = 30 + content.shapes.len() + content.images.len();
-    for (i, code_block) in content.code_blocks.iter().enumerate() {
-        xml.push('\n');
-        xml.push_str(&generate_code_block(code_start_id + i, code_block));
-    }
+fn shape_id(shape: &Shape, index: usize) -> u32 {
+    shape.id.unwrap_or((index + 10) as u32)
+}
 
-    // Render connectors
-    let connector_start_id = 50 + content.shapes.len() + content.images.len() + content.code_blocks.len();
-    for (i, connector) in content.connectors.iter().enumerate() {
-        xml.push('\n');
-        let id = connector_start_id + i;
-        xml.push_str(&crate::generator::connectors::generate_connector_xml(connector, id));
-    }
+/// First ID handed out after the slide's shapes
+fn first_sequential_id<'a>(shapes: impl Iterator<Item = &'a Shape>) -> usize {
+    shapes.enumerate()
+        .map(|(i, shape)| shape_id(shape, i) as usize)
+        .max()
+        .unwrap_or(9)
+        .max(9) + 1
+}
 
-    // Render charts
-    let chart_start_id = 100 + content.shapes.len() + content.images.len() + content.code_blocks.len() + content.connectors.len();
-    for (i, chart) in content.charts.iter().enumerate() {
-        if i < chart_rids.len() {
-            xml.push('\n');
-            let r_id = &chart_rids[i];
-            xml.push_str(&crate::generator::charts::generate_chart_ref_xml(chart, r_id, chart_start_id + i));
-        }
-    }
+/// First cNvPr ID not used by `render_elements`
+pub fn next_free_id(content: &SlideContent, chart_rids: &[String]) -> usize {
+    let (marks, lines): (Vec<_>, Vec<_>) = content.charts.iter()
+        .map(|c| {
+            let (mut marks, lines) = c.reference_marks();
+            marks.extend(c.legend_note());
+            (marks, lines)
+        })
+        .unzip();
+    first_sequential_id(content.shapes.iter().chain(&content.overlays).chain(marks.iter().flatten()))
+        + content.images.len()
+        + content.code_blocks.len()
+        + content.connectors.len()
+        + lines.iter().map(Vec::len).sum::<usize>()
+        + content.charts.len().min(chart_rids.len())
 }
 
 /// Generate image placeholder XML
 fn generate_image_placeholder(id: usize, image: &crate::generator::images::Image) -> String {
-    let filename = &image.filename;
+    let style = image.placeholder.clone().unwrap_or_default();
+    let filename = escape_xml(&image.filename);
+    let label = escape_xml(&style.label(&image.filename));
+    let fill = escape_xml(&style.fill);
+    let line_color = escape_xml(&style.line_color);
     let x = image.x;
     let y = image.y;
     let width = image.width;
     let height = image.height;
+    let locks = if image.locks.is_empty() {
+        "<p:cNvSpPr/>".to_string()
+    } else {
+        format!("<p:cNvSpPr>{}</p:cNvSpPr>", image.locks.to_xml("a:spLocks"))
+    };
     
     format!(
         r#"<p:sp>
 <p:nvSpPr>
 <p:cNvPr id="{id}" name="Image Placeholder: {filename}"/>
-<p:cNvSpPr/>
+{locks}
 <p:nvPr/>
 </p:nvSpPr>
 <p:spPr>
@@ -66,8 +133,8 @@ fn generate_image_placeholder(id: usize, image: &crate::generator::images::Image
 <a:ext cx="{width}" cy="{height}"/>
 </a:xfrm>
 <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:solidFill><a:srgbClr val="E0E0E0"/></a:solidFill>
-<a:ln w="12700"><a:solidFill><a:srgbClr val="808080"/></a:solidFill></a:ln>
+<a:solidFill><a:srgbClr val="{fill}"/></a:solidFill>
+<a:ln w="12700"><a:solidFill><a:srgbClr val="{line_color}"/></a:solidFill></a:ln>
 </p:spPr>
 <p:txBody>
 <a:bodyPr wrap="square" rtlCol="0" anchor="ctr"/>
@@ -76,7 +143,7 @@ fn generate_image_placeholder(id: usize, image: &crate::generator::images::Image
 <a:pPr algn="ctr"/>
 <a:r>
 <a:rPr lang="en-US" sz="1400"/>
-<a:t>📷 {filename}</a:t>
+<a:t>{label}</a:t>
 </a:r>
 </a:p>
 </p:txBody>
@@ -1,19 +1,20 @@
-//! Slide layout implementations
+//! Slide layout elements: section band, title and body text boxes
 
-use crate::generator::slide_content::{SlideContent, BulletStyle, BulletPoint, BulletTextFormat};
+use crate::generator::slide_content::{BulletPoint, BulletTextFormat};
 use crate::generator::package_xml::escape_xml;
 use crate::generator::slide::formatting::generate_text_props;
-use super::common::{SLIDE_HEADER, SLIDE_FOOTER, generate_title_shape};
+use super::common::generate_title_shape;
 use crate::generator::layouts::ExtendedTextProps;
-use super::content::render_additional_content;
+use crate::generator::gradients::{GradientFill, generate_gradient_fill_xml};
+use crate::generator::ir::{Align, Frame, TextBox, TextRole};
 
 /// Generate text properties XML for a bullet, merging slide defaults with bullet-specific format
 fn generate_bullet_text_props(
     default_props: &ExtendedTextProps,
     bullet_format: Option<&BulletTextFormat>,
-) -> String {
+) -> ExtendedTextProps {
     if let Some(fmt) = bullet_format {
-        let props = ExtendedTextProps {
+        ExtendedTextProps {
             size: fmt.font_size.map(|s| s * 100).unwrap_or(default_props.size),
             bold: fmt.bold || default_props.bold,
             italic: fmt.italic || default_props.italic,
@@ -24,35 +25,12 @@ fn generate_bullet_text_props(
             color: fmt.color.clone().or_else(|| default_props.color.clone()),
             highlight: fmt.highlight.clone(),
             font_family: fmt.font_family.clone().or_else(|| default_props.font_family.clone()),
-        };
-        props.to_xml()
+        }
     } else {
-        default_props.to_xml()
+        default_props.clone()
     }
 }
 
-/// Generate a bullet paragraph with style
-#[allow(dead_code)]
-fn generate_bullet_paragraph(text: &str, level: u32, style: BulletStyle, text_props: &str) -> String {
-    let indent = 457200 + (level * 457200);
-    let margin_left = level * 457200 + indent;
-    let bullet_xml = style.to_xml();
-    
-    format!(
-        r#"
-<a:p>
-<a:pPr lvl="{}" marL="{}" indent="-{}">
-{}
-</a:pPr>
-<a:r>
-{}
-<a:t>{}</a:t>
-</a:r>
-</a:p>"#,
-        level, margin_left, indent, bullet_xml, text_props, escape_xml(text)
-    )
-}
-
 /// Generate a bullet paragraph from a BulletPoint with full formatting
 fn generate_bullet_paragraph_from_point(
     bullet: &BulletPoint,
@@ -60,9 +38,28 @@ fn generate_bullet_paragraph_from_point(
 ) -> String {
     let indent = 457200 + (bullet.level * 457200);
     let margin_left = bullet.level * 457200 + indent;
-    let bullet_xml = bullet.style.to_xml();
+    let mut paragraph_props = String::new();
+    if let Some(percent) = bullet.line_spacing {
+        paragraph_props.push_str(&format!(r#"<a:lnSpc><a:spcPct val="{}"/></a:lnSpc>"#, percent * 1000));
+    }
+    if let Some(before) = bullet.spacing_before {
+        paragraph_props.push_str(&format!(r#"<a:spcBef><a:spcPts val="{before}"/></a:spcBef>"#));
+    }
+    if let Some(after) = bullet.spacing_after {
+        paragraph_props.push_str(&format!(r#"<a:spcAft><a:spcPts val="{after}"/></a:spcAft>"#));
+    }
+    paragraph_props.push_str(&bullet.style.to_xml());
     let text_props = generate_bullet_text_props(default_props, bullet.format.as_ref());
-    
+    let mut citation = String::new();
+    if !bullet.citations.is_empty() {
+        let marker_props = ExtendedTextProps { superscript: true, subscript: false, ..text_props.clone() };
+        citation = format!(
+            "\n<a:r>\n{}\n<a:t>{}</a:t>\n</a:r>",
+            marker_props.to_xml(),
+            escape_xml(&bullet.citations.join(","))
+        );
+    }
+
     format!(
         r#"
 <a:p>
@@ -72,137 +69,42 @@ fn generate_bullet_paragraph_from_point(
 <a:r>
 {}
 <a:t>{}</a:t>
-</a:r>
+</a:r>{}
 </a:p>"#,
-        bullet.level, margin_left, indent, bullet_xml, text_props, escape_xml(&bullet.text)
+        bullet.level, margin_left, indent, paragraph_props, text_props.to_xml(), escape_xml(&bullet.text), citation
     )
 }
 
-/// Create a blank slide
-pub fn create_blank_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let mut xml = String::from(SLIDE_HEADER);
-    render_additional_content(&mut xml, content, chart_rids);
-    xml.push_str(SLIDE_FOOTER);
-    xml
-}
-
-/// Create a title-only slide
-pub fn create_title_only_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let title_size = content.title_size.unwrap_or(44) * 100;
-    let title_props = generate_text_props(
-        title_size,
-        content.title_bold,
-        content.title_italic,
-        content.title_underline,
-        content.title_color.as_deref(),
-    );
-    let title_text = escape_xml(&content.title);
-
-    let title_shape = generate_title_shape(
-        &title_text,
-        &title_props,
-        457200,   // x
-        274638,   // y
-        8230200,  // width
-        1143000,  // height
-        "l",      // align left
-    );
-
-    let mut xml = format!("{}\n{}", SLIDE_HEADER, title_shape);
-    render_additional_content(&mut xml, content, chart_rids);
-    xml.push_str(SLIDE_FOOTER);
-    xml
-}
-
-/// Create a centered title slide
-pub fn create_centered_title_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let title_size = content.title_size.unwrap_or(54) * 100;
-    let title_props = generate_text_props(
-        title_size,
-        content.title_bold,
-        content.title_italic,
-        content.title_underline,
-        content.title_color.as_deref(),
-    );
-    let title_text = escape_xml(&content.title);
-
-    let title_shape = generate_title_shape(
-        &title_text,
-        &title_props,
-        457200,   // x
-        2743200,  // y (centered vertically)
-        8230200,  // width
-        1371600,  // height
-        "ctr",    // align center
-    );
-
-    let mut xml = format!("{}\n{}", SLIDE_HEADER, title_shape);
-    render_additional_content(&mut xml, content, chart_rids);
-    xml.push_str(SLIDE_FOOTER);
-    xml
-}
-
-/// Create a title and big content slide
-pub fn create_title_and_big_content_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let title_size = content.title_size.unwrap_or(44) * 100;
-    let content_size = content.content_size.unwrap_or(28) * 100;
-
-    let title_props = generate_text_props(
-        title_size,
-        content.title_bold,
-        content.title_italic,
-        content.title_underline,
-        content.title_color.as_deref(),
-    );
-    let title_text = escape_xml(&content.title);
-
-    let mut xml = String::from(SLIDE_HEADER);
-    
-    // Title shape
-    xml.push_str(&format!(
-        r#"
-<p:sp>
-<p:nvSpPr>
-<p:cNvPr id="2" name="Title"/>
-<p:cNvSpPr txBox="1"/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm>
-<a:off x="457200" y="274638"/>
-<a:ext cx="8230200" cy="914400"/>
-</a:xfrm>
-<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
-</p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0" anchor="ctr"/>
-<a:lstStyle/>
-<a:p>
-<a:pPr algn="l"/>
-<a:r>
-{title_props}
-<a:t>{title_text}</a:t>
-</a:r>
-</a:p>
-</p:txBody>
-</p:sp>"#
-    ));
+/// Generate a title or body text box
+pub fn generate_text_box(text: &TextBox) -> String {
+    let Frame { x, y, width, height } = text.frame;
+    if text.role == TextRole::Title {
+        let style = &text.style;
+        let title_props =
+            generate_text_props(style.size * 100, style.bold, style.italic, style.underline, style.color.as_deref());
+        let align = match text.align {
+            Align::Left => "l",
+            Align::Center => "ctr",
+        };
+        return generate_title_shape(&escape_xml(&text.text()), &title_props, x, y, width, height, align);
+    }
 
-    // Content
-    if !content.bullets.is_empty() || !content.content.is_empty() {
-        xml.push_str(
-            r#"
-<p:sp>
+    let (id, name) = match text.role {
+        TextRole::RightColumn => (4, "Right Content"),
+        TextRole::LeftColumn => (3, "Left Content"),
+        _ => (3, "Content"),
+    };
+    let mut xml = format!(
+        r#"<p:sp>
 <p:nvSpPr>
-<p:cNvPr id="3" name="Content"/>
+<p:cNvPr id="{id}" name="{name}"/>
 <p:cNvSpPr txBox="1"/>
 <p:nvPr/>
 </p:nvSpPr>
 <p:spPr>
 <a:xfrm>
-<a:off x="457200" y="1189200"/>
-<a:ext cx="8230200" cy="5668800"/>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{width}" cy="{height}"/>
 </a:xfrm>
 <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
 <a:noFill/>
@@ -210,292 +112,43 @@ pub fn create_title_and_big_content_slide(content: &SlideContent, chart_rids: &[
 <p:txBody>
 <a:bodyPr wrap="square" rtlCol="0"/>
 <a:lstStyle/>"#
-        );
-
-        let default_props = ExtendedTextProps::with_basic(
-            content_size,
-            content.content_bold,
-            content.content_italic,
-            false,
-            content.content_color.as_deref(),
-        );
-
-        // Use styled bullets if available, otherwise use plain content
-        if !content.bullets.is_empty() {
-            for bullet in &content.bullets {
-                xml.push_str(&generate_bullet_paragraph_from_point(bullet, &default_props));
-            }
-        } else {
-            for bullet in &content.content {
-                let bp = BulletPoint::new(bullet).with_style(content.bullet_style);
-                xml.push_str(&generate_bullet_paragraph_from_point(&bp, &default_props));
-            }
-        }
+    );
 
-        xml.push_str(
-            r#"
-</p:txBody>
-</p:sp>"#
-        );
+    let style = &text.style;
+    let default_props =
+        ExtendedTextProps::with_basic(style.size * 100, style.bold, style.italic, style.underline, style.color.as_deref());
+    for paragraph in &text.paragraphs {
+        xml.push_str(&generate_bullet_paragraph_from_point(paragraph, &default_props));
     }
 
-    render_additional_content(&mut xml, content, chart_rids);
-    xml.push_str(SLIDE_FOOTER);
-    xml
-}
-
-/// Create a two-column slide
-pub fn create_two_column_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let title_size = content.title_size.unwrap_or(44) * 100;
-    let content_size = content.content_size.unwrap_or(24) * 100;
-
-    let title_props = generate_text_props(
-        title_size,
-        content.title_bold,
-        content.title_italic,
-        content.title_underline,
-        content.title_color.as_deref(),
-    );
-    let title_text = escape_xml(&content.title);
-
-    let mut xml = String::from(SLIDE_HEADER);
-    
-    // Title
-    xml.push_str(&format!(
+    xml.push_str(
         r#"
-<p:sp>
-<p:nvSpPr>
-<p:cNvPr id="2" name="Title"/>
-<p:cNvSpPr txBox="1"/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm>
-<a:off x="457200" y="274638"/>
-<a:ext cx="8230200" cy="914400"/>
-</a:xfrm>
-<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
-</p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0" anchor="ctr"/>
-<a:lstStyle/>
-<a:p>
-<a:pPr algn="l"/>
-<a:r>
-{title_props}
-<a:t>{title_text}</a:t>
-</a:r>
-</a:p>
 </p:txBody>
 </p:sp>"#
-    ));
-
-    let default_props = ExtendedTextProps::with_basic(
-        content_size,
-        content.content_bold,
-        content.content_italic,
-        false,
-        content.content_color.as_deref(),
     );
-
-    // Determine which bullets to use
-    let use_styled_bullets = !content.bullets.is_empty();
-    let bullet_count = if use_styled_bullets { content.bullets.len() } else { content.content.len() };
-
-    if bullet_count > 0 {
-        let mid = bullet_count.div_ceil(2);
-
-        // Left column
-        xml.push_str(
-            r#"
-<p:sp>
-<p:nvSpPr>
-<p:cNvPr id="3" name="Left Content"/>
-<p:cNvSpPr txBox="1"/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm>
-<a:off x="457200" y="1189200"/>
-<a:ext cx="4115100" cy="5668800"/>
-</a:xfrm>
-<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
-</p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0"/>
-<a:lstStyle/>"#
-        );
-
-        if use_styled_bullets {
-            for bullet in &content.bullets[..mid] {
-                xml.push_str(&generate_bullet_paragraph_from_point(bullet, &default_props));
-            }
-        } else {
-            for bullet in &content.content[..mid] {
-                let bp = BulletPoint::new(bullet).with_style(content.bullet_style);
-                xml.push_str(&generate_bullet_paragraph_from_point(&bp, &default_props));
-            }
-        }
-
-        xml.push_str(
-            r#"
-</p:txBody>
-</p:sp>"#
-        );
-
-        // Right column
-        if mid < bullet_count {
-            xml.push_str(
-                r#"
-<p:sp>
-<p:nvSpPr>
-<p:cNvPr id="4" name="Right Content"/>
-<p:cNvSpPr txBox="1"/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm>
-<a:off x="4572300" y="1189200"/>
-<a:ext cx="4115100" cy="5668800"/>
-</a:xfrm>
-<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
-</p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0"/>
-<a:lstStyle/>"#
-            );
-
-            if use_styled_bullets {
-                for bullet in &content.bullets[mid..] {
-                    xml.push_str(&generate_bullet_paragraph_from_point(bullet, &default_props));
-                }
-            } else {
-                for bullet in &content.content[mid..] {
-                    let bp = BulletPoint::new(bullet).with_style(content.bullet_style);
-                    xml.push_str(&generate_bullet_paragraph_from_point(&bp, &default_props));
-                }
-            }
-
-            xml.push_str(
-                r#"
-</p:txBody>
-</p:sp>"#
-            );
-        }
-    }
-
-    render_additional_content(&mut xml, content, chart_rids);
-    xml.push_str(SLIDE_FOOTER);
     xml
 }
 
-/// Create a title and content slide (most common layout)
-pub fn create_title_and_content_slide(content: &SlideContent, chart_rids: &[String]) -> String {
-    let title_size = content.title_size.unwrap_or(44) * 100;
-    let content_size = content.content_size.unwrap_or(28) * 100;
-
-    let title_props = generate_text_props(
-        title_size,
-        content.title_bold,
-        content.title_italic,
-        content.title_underline,
-        content.title_color.as_deref(),
-    );
-    let title_text = escape_xml(&content.title);
-
-    let mut xml = String::from(SLIDE_HEADER);
-    
-    // Title
-    xml.push_str(&format!(
-        r#"
-<p:sp>
-<p:nvSpPr>
-<p:cNvPr id="2" name="Title"/>
-<p:cNvSpPr txBox="1"/>
-<p:nvPr/>
-</p:nvSpPr>
-<p:spPr>
-<a:xfrm>
-<a:off x="457200" y="274638"/>
-<a:ext cx="8230200" cy="1143000"/>
-</a:xfrm>
-<a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
-</p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0" anchor="ctr"/>
-<a:lstStyle/>
-<a:p>
-<a:pPr algn="l"/>
-<a:r>
-{title_props}
-<a:t>{title_text}</a:t>
-</a:r>
-</a:p>
-</p:txBody>
-</p:sp>"#
-    ));
-
-    // Render table if present
-    if let Some(ref table) = content.table {
-        xml.push('\n');
-        xml.push_str(&crate::generator::tables_xml::generate_table_xml(table, 3));
-    } else if !content.bullets.is_empty() || !content.content.is_empty() {
-        // Render bullets if no table
-        xml.push_str(
-            r#"
-<p:sp>
+/// Full-width gradient band behind the title of a section slide
+pub fn generate_section_band(frame: Frame, gradient: &GradientFill) -> String {
+    let Frame { x, y, width, height } = frame;
+    format!(
+        r#"<p:sp>
 <p:nvSpPr>
-<p:cNvPr id="3" name="Content"/>
-<p:cNvSpPr txBox="1"/>
+<p:cNvPr id="3" name="Section Band"/>
+<p:cNvSpPr/>
 <p:nvPr/>
 </p:nvSpPr>
 <p:spPr>
 <a:xfrm>
-<a:off x="457200" y="1600200"/>
-<a:ext cx="8230200" cy="4572000"/>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{width}" cy="{height}"/>
 </a:xfrm>
 <a:prstGeom prst="rect"><a:avLst/></a:prstGeom>
-<a:noFill/>
+{}
+<a:ln><a:noFill/></a:ln>
 </p:spPr>
-<p:txBody>
-<a:bodyPr wrap="square" rtlCol="0"/>
-<a:lstStyle/>"#
-        );
-
-        let default_props = ExtendedTextProps::with_basic(
-            content_size,
-            content.content_bold,
-            content.content_italic,
-            false,
-            content.content_color.as_deref(),
-        );
-
-        // Use styled bullets if available, otherwise use plain content
-        if !content.bullets.is_empty() {
-            for bullet in &content.bullets {
-                xml.push_str(&generate_bullet_paragraph_from_point(bullet, &default_props));
-            }
-        } else {
-            for bullet in &content.content {
-                let bp = BulletPoint::new(bullet).with_style(content.bullet_style);
-                xml.push_str(&generate_bullet_paragraph_from_point(&bp, &default_props));
-            }
-        }
-
-        xml.push_str(
-            r#"
-</p:txBody>
-</p:sp>"#
-        );
-    }
-
-    // Render additional content (shapes, images, code blocks, connectors)
-    render_additional_content(&mut xml, content, chart_rids);
-
-    xml.push_str(SLIDE_FOOTER);
-    xml
+</p:sp>"#,
+        generate_gradient_fill_xml(gradient)
+    )
 }
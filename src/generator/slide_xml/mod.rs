@@ -1,18 +1,24 @@
-//! Slide XML generation for different layouts
+//! Slide XML generation
 //!
-//! This module handles generating XML for various slide layouts:
-//! - Blank slides
-//! - Title only slides
-//! - Centered title slides
-//! - Title and content slides
-//! - Two column slides
-//! - Title and big content slides
+//! The OOXML backend of the [intermediate model](super::ir): slides are
+//! lowered to positioned elements, which are written in drawing order, then
+//! the elements that live in other parts (zooms, narration) and the
+//! transition are added.
 
 mod common;
 mod layouts;
 mod content;
 
-use super::slide_content::{SlideContent, SlideLayout};
+use super::slide_content::SlideContent;
+use super::ir::Slide;
+use super::placement::SlideSize;
+use super::media::{MediaRelIds, generate_narration_xml, generate_narration_timing_xml};
+use super::zoom::{ZoomRelIds, generate_slide_zoom_xml};
+use super::slide_number::generate_slide_number_xml;
+use super::hyperlinks::ActionRelIds;
+use super::package_xml::{slide_id, slide_creation_id_xml};
+
+use common::{slide_header, SLIDE_FOOTER};
 
 pub use common::create_slide_rels_xml;
 
@@ -74,23 +80,134 @@ pub fn create_slide_xml(slide_num: usize, title: &str) -> String {
     )
 }
 
+/// Relationship IDs referenced from a slide's XML, assigned by the package builder
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SlideRelIds {
+    /// One ID per chart, in chart order
+    pub charts: Vec<String>,
+    /// Narration clip IDs, when the slide has narration
+    pub narration: Option<MediaRelIds>,
+    /// One entry per slide zoom, in zoom order
+    pub zooms: Vec<ZoomRelIds>,
+    /// One entry per shape, in shape order (empty when no shape has actions)
+    pub shape_actions: Vec<ShapeActionRelIds>,
+    /// One entry per image, in image order; `None` for images drawn as placeholders
+    pub images: Vec<Option<String>>,
+    /// Tag list of the slide, when it has tags
+    pub tags: Option<String>,
+    /// One entry per shape, in shape order (empty when no shape has tags)
+    pub shape_tags: Vec<Option<String>>,
+}
+
+/// Relationship IDs for a shape's click and hover actions
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShapeActionRelIds {
+    pub click: ActionRelIds,
+    pub hover: ActionRelIds,
+}
+
+/// Copy of `content` with action and tag relationship IDs applied to its
+/// shapes and picture relationship IDs to its images
+fn with_rel_ids(content: &SlideContent, rel_ids: &SlideRelIds) -> SlideContent {
+    let mut content = content.clone();
+    for (shape, rel_ids) in content.shapes.iter_mut().zip(&rel_ids.shape_actions) {
+        shape.hyperlink = shape.hyperlink.take().map(|h| h.with_rel_ids(&rel_ids.click));
+        shape.hover = shape.hover.take().map(|h| h.with_rel_ids(&rel_ids.hover));
+    }
+    for (shape, r_id) in content.shapes.iter_mut().zip(&rel_ids.shape_tags) {
+        shape.tags.r_id = r_id.clone();
+    }
+    for (image, r_id) in content.images.iter_mut().zip(&rel_ids.images) {
+        image.r_id = r_id.clone();
+    }
+    content.tags.r_id = rel_ids.tags.clone();
+    content
+}
+
 /// Create slide XML with content based on layout
-pub fn create_slide_xml_with_content(_slide_num: usize, content: &SlideContent, chart_rids: &[String]) -> String {
-    let mut xml = match content.layout {
-        SlideLayout::Blank => layouts::create_blank_slide(content, chart_rids),
-        SlideLayout::TitleOnly => layouts::create_title_only_slide(content, chart_rids),
-        SlideLayout::CenteredTitle => layouts::create_centered_title_slide(content, chart_rids),
-        SlideLayout::TitleAndBigContent => layouts::create_title_and_big_content_slide(content, chart_rids),
-        SlideLayout::TwoColumn => layouts::create_two_column_slide(content, chart_rids),
-        SlideLayout::TitleAndContent => layouts::create_title_and_content_slide(content, chart_rids),
+pub fn create_slide_xml_with_content(slide_num: usize, content: &SlideContent, chart_rids: &[String]) -> String {
+    let rel_ids = SlideRelIds {
+        charts: chart_rids.to_vec(),
+        ..Default::default()
+    };
+    create_slide_xml_with_rels(slide_num, content, &rel_ids)
+}
+
+/// Create slide XML with content, including elements that live in other parts
+///
+/// Narration, slide zooms, shape actions, pictures and tags that reference other
+/// parts are only rendered when their relationship IDs are given, since those
+/// parts must exist in the slide's relationships.
+pub fn create_slide_xml_with_rels(slide_num: usize, content: &SlideContent, rel_ids: &SlideRelIds) -> String {
+    create_slide_xml_with_size(slide_num, content, rel_ids, SlideSize::STANDARD)
+}
+
+/// [`create_slide_xml_with_rels`] for a slide of `size`
+///
+/// The layout's title, body and section band are scaled to `size`.
+pub fn create_slide_xml_with_size(slide_num: usize, content: &SlideContent, rel_ids: &SlideRelIds, size: SlideSize) -> String {
+    let resolved;
+    let content = if rel_ids.shape_actions.is_empty()
+        && rel_ids.images.iter().all(Option::is_none)
+        && rel_ids.tags.is_none()
+        && rel_ids.shape_tags.is_empty()
+    {
+        content
+    } else {
+        resolved = with_rel_ids(content, rel_ids);
+        &resolved
     };
 
-    // Inject transition if present
-    let transition_xml = content.transition.to_xml();
-    if !transition_xml.is_empty() {
-        if let Some(pos) = xml.rfind("</p:sld>") {
-            xml.insert_str(pos, &transition_xml);
-        }
+    let chart_rids = rel_ids.charts.as_slice();
+    let slide = Slide::from_content(content, size);
+    let mut xml = slide_header(size);
+    content::render_elements(&mut xml, &slide, chart_rids);
+    xml.push_str(SLIDE_FOOTER);
+
+    let mut next_id = content::next_free_id(content, chart_rids);
+    let mut extra_shapes = String::new();
+
+    for (zoom, zoom_rids) in content.slide_zooms.iter().zip(&rel_ids.zooms) {
+        extra_shapes.push('\n');
+        extra_shapes.push_str(&generate_slide_zoom_xml(zoom, next_id, zoom_rids, zoom.target_slide_id.map_or(slide_id(zoom.target_slide), |id| id as usize)));
+        next_id += 1;
+    }
+
+    if let Some(number) = &content.slide_number {
+        extra_shapes.push('\n');
+        extra_shapes.push_str(&generate_slide_number_xml(number, next_id));
+        next_id += 1;
+    }
+
+    let mut advance_ms = None;
+    let mut timing_xml = String::new();
+    if let Some((narration, narration_rids)) = content.narration.as_ref().zip(rel_ids.narration.as_ref()) {
+        extra_shapes.push('\n');
+        extra_shapes.push_str(&generate_narration_xml(next_id, narration_rids));
+        advance_ms = narration.clip_duration_ms();
+        timing_xml = generate_narration_timing_xml(next_id, advance_ms);
+    }
+
+    if !extra_shapes.is_empty()
+        && let Some(pos) = xml.rfind("\n</p:spTree>")
+    {
+        xml.insert_str(pos, &extra_shapes);
+    }
+
+    // Slide tags follow the shape tree in the common slide data
+    if let Some(cust_data) = content.tags.cust_data_xml()
+        && let Some(pos) = xml.find("</p:spTree>")
+    {
+        xml.insert_str(pos + "</p:spTree>".len(), &format!("\n{cust_data}"));
+    }
+
+    // Inject transition (and narration timing) if present
+    let transition_xml = content.transition.to_xml_with_advance(advance_ms);
+    // The creation ID lets slide zooms find the slide
+    let own_id = content.slide_id.map_or(slide_id(slide_num), |id| id as usize);
+    let trailer = format!("{}{}{}", transition_xml, timing_xml, slide_creation_id_xml(own_id));
+    if let Some(pos) = xml.rfind("</p:sld>") {
+        xml.insert_str(pos, &trailer);
     }
     
     xml
@@ -137,6 +254,17 @@ mod tests {
         assert!(segments[1].code);
     }
 
+    #[test]
+    fn test_citation_markers_are_superscript() {
+        use crate::generator::{BulletPoint, SlideContent};
+
+        let slide = SlideContent::new("Market").add_bullet_point(BulletPoint::new("Spend grows").cite("1").cite("3"));
+        let xml = super::create_slide_xml_with_content(1, &slide, &[]);
+        let marker = xml.find("<a:t>1,3</a:t>").expect("marker run");
+        let run_start = xml[..marker].rfind("<a:rPr").unwrap();
+        assert!(xml[run_start..marker].contains(r#"baseline="30000""#));
+    }
+
     #[test]
     fn test_parse_inline_formatting_mixed() {
         let segments = parse_inline_formatting("**bold** and *italic*");
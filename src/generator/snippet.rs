@@ -0,0 +1,160 @@
+//! Reusable slide snippets
+//!
+//! A [`Snippet`] is a group of shapes, images and connectors laid out
+//! relative to its own top-left corner — a footer band with a logo, page
+//! number and classification label, say. It is defined once and stamped
+//! onto slides with [`SlideContent::include`], which offsets every element
+//! by the snippet's origin and fills `{name}` placeholders in shape text:
+//!
+//! ```rust,ignore
+//! let footer = Snippet::new()
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 9144000, 400000).with_fill(ShapeFill::new("1F4E79")))
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 8200000, 50000, 800000, 300000).with_text("{page}"))
+//!     .at(0, 6458000);
+//!
+//! let slide = SlideContent::new("Results").include(&footer.with_param("page", "3"));
+//! ```
+
+use crate::core::{Emu, Positioned};
+use crate::generator::connectors::Connector;
+use crate::generator::images::Image;
+use crate::generator::shapes::Shape;
+use crate::generator::slide_content::SlideContent;
+
+/// Group of elements positioned relative to a common origin
+#[derive(Clone, Debug, Default)]
+pub struct Snippet {
+    /// Shapes, positioned relative to the snippet origin
+    pub shapes: Vec<Shape>,
+    /// Images, positioned relative to the snippet origin
+    pub images: Vec<Image>,
+    /// Connectors, positioned relative to the snippet origin
+    pub connectors: Vec<Connector>,
+    /// Slide position of the snippet's top-left corner
+    pub x: Emu,
+    pub y: Emu,
+    /// Values for `{name}` placeholders in shape text
+    pub params: Vec<(String, String)>,
+}
+
+impl Snippet {
+    /// Empty snippet at the slide origin
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a shape
+    pub fn add_shape(mut self, shape: Shape) -> Self {
+        self.shapes.push(shape);
+        self
+    }
+
+    /// Add an image
+    pub fn add_image(mut self, image: Image) -> Self {
+        self.images.push(image);
+        self
+    }
+
+    /// Add a connector
+    pub fn add_connector(mut self, connector: Connector) -> Self {
+        self.connectors.push(connector);
+        self
+    }
+
+    /// Place the snippet's top-left corner on the slide
+    pub fn at(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    /// Set the value substituted for `{name}` in shape text
+    ///
+    /// Setting the same name again replaces the earlier value.
+    pub fn with_param(mut self, name: &str, value: impl Into<String>) -> Self {
+        let value = value.into();
+        match self.params.iter_mut().find(|(n, _)| n == name) {
+            Some(param) => param.1 = value,
+            None => self.params.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    /// Replace the `{name}` placeholders in `text`; unknown names are kept
+    pub fn substitute(&self, text: &str) -> String {
+        self.params
+            .iter()
+            .fold(text.to_string(), |text, (name, value)| text.replace(&format!("{{{name}}}"), value))
+    }
+}
+
+/// Move an element from snippet to slide coordinates
+fn offset(item: &mut impl Positioned, dx: Emu, dy: Emu) {
+    let (x, y) = (item.x(), item.y());
+    item.set_position(x + dx, y + dy);
+}
+
+impl SlideContent {
+    /// Stamp a copy of `snippet` onto the slide
+    ///
+    /// Elements are offset by the snippet's origin and `{name}` placeholders
+    /// in shape text are filled from its parameters. Shapes with a
+    /// slide-relative [`Placement`](crate::generator::Placement) keep it.
+    pub fn include(mut self, snippet: &Snippet) -> Self {
+        let (dx, dy) = (snippet.x, snippet.y);
+        for shape in &snippet.shapes {
+            let mut shape = shape.clone();
+            offset(&mut shape, dx, dy);
+            shape.text = shape.text.map(|text| snippet.substitute(&text));
+            self.shapes.push(shape);
+        }
+        for image in &snippet.images {
+            let mut image = image.clone();
+            offset(&mut image, dx, dy);
+            self = self.add_image(image);
+        }
+        for connector in &snippet.connectors {
+            let mut connector = connector.clone();
+            offset(&mut connector, dx, dy);
+            self.connectors.push(connector);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::shapes::ShapeType;
+
+    fn footer() -> Snippet {
+        Snippet::new()
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 9144000, 400000))
+            .add_shape(Shape::new(ShapeType::Rectangle, 8200000, 50000, 800000, 300000).with_text("Page {page} · {label}"))
+            .add_image(Image::new("logo.png", 300000, 300000, "PNG").position(100000, 50000))
+            .at(0, 6458000)
+            .with_param("label", "Internal")
+    }
+
+    #[test]
+    fn test_include_offsets_elements() {
+        let slide = SlideContent::new("Results").include(&footer().with_param("page", "3"));
+        assert_eq!(slide.shapes.len(), 2);
+        assert_eq!((slide.shapes[1].x, slide.shapes[1].y), (Emu(8200000), Emu(6508000)));
+        assert_eq!((slide.images[0].x.0, slide.images[0].y.0), (100000, 6508000));
+        assert!(slide.has_image);
+    }
+
+    #[test]
+    fn test_include_substitutes_params() {
+        let snippet = footer();
+        let first = SlideContent::new("One").include(&snippet.clone().with_param("page", "1"));
+        let second = SlideContent::new("Two").include(&snippet.clone().with_param("page", "2"));
+        assert_eq!(first.shapes[1].text.as_deref(), Some("Page 1 · Internal"));
+        assert_eq!(second.shapes[1].text.as_deref(), Some("Page 2 · Internal"));
+
+        // The snippet itself is left untouched; unknown names are kept
+        assert_eq!(snippet.shapes[1].text.as_deref(), Some("Page {page} · {label}"));
+        assert_eq!(snippet.with_param("label", "Public").substitute("{label} {x}"), "Public {x}");
+    }
+}
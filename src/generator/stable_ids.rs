@@ -0,0 +1,186 @@
+//! Content-derived element IDs
+//!
+//! Shape IDs normally come from a shape's position in the slide's list and
+//! slide IDs from the slide's position in the deck, so inserting a slide or
+//! a shape renumbers everything after it. [`assign_stable_ids`] derives them
+//! from content instead: a slide's `p:sldId` from its title and a shape's
+//! `cNvPr` id from its type, name and text. Regenerating a deck from the same
+//! source gives the same IDs, so diffs between builds, localization IDs such
+//! as `slide3/48211/1` and Morph pairings survive edits elsewhere in the deck.
+//!
+//! ```
+//! use ppt_rs::generator::{assign_stable_ids, Shape, ShapeType, SlideContent};
+//!
+//! let slide = || SlideContent::new("Roadmap")
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_text("Q1"));
+//! let mut before = vec![slide()];
+//! let mut after = vec![SlideContent::new("Agenda"), slide()];
+//! assign_stable_ids(&mut before);
+//! assign_stable_ids(&mut after);
+//! assert_eq!(before[0].slide_id, after[1].slide_id);
+//! assert_eq!(before[0].shapes[0].id, after[1].shapes[0].id);
+//! ```
+//!
+//! IDs already set are kept. Elements that have the same content get the
+//! next free ID in order, so identical shapes keep theirs as long as their
+//! order doesn't change. Connectors glued to shapes by their position-based
+//! IDs are moved to the new ones.
+
+use std::collections::{HashMap, HashSet};
+
+use super::shapes::Shape;
+use super::slide_content::SlideContent;
+
+/// Smallest `p:sldId/@id` allowed by the schema
+const FIRST_SLIDE_ID: u32 = 256;
+/// Slide IDs must stay below this
+const SLIDE_ID_LIMIT: u32 = 2_147_483_648;
+/// Shape IDs start here, clear of placeholder and position-based IDs
+const FIRST_SHAPE_ID: u32 = 10_000;
+/// Number of shape IDs hashes are spread over
+const SHAPE_ID_RANGE: u32 = 1 << 24;
+
+/// 64-bit FNV-1a, stable across platforms and Rust versions
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+/// Hands out IDs in `first..first + range` derived from content keys,
+/// probing past IDs that are taken
+struct IdPool {
+    first: u32,
+    range: u32,
+    taken: HashSet<u32>,
+    seen: HashMap<String, u32>,
+}
+
+impl IdPool {
+    fn new(first: u32, range: u32, taken: impl IntoIterator<Item = u32>) -> Self {
+        IdPool { first, range, taken: taken.into_iter().collect(), seen: HashMap::new() }
+    }
+
+    fn id(&mut self, key: &str) -> u32 {
+        // The n-th element with this content hashes differently from the first
+        let occurrence = self.seen.entry(key.to_string()).or_insert(0);
+        let mut offset = (fnv1a(&format!("{key}\u{0}{occurrence}")) % self.range as u64) as u32;
+        *occurrence += 1;
+        while !self.taken.insert(self.first + offset) {
+            offset = (offset + 1) % self.range;
+        }
+        self.first + offset
+    }
+}
+
+/// What a shape's ID is derived from
+fn shape_key(shape: &Shape) -> String {
+    let text = match &shape.text {
+        Some(text) => text.clone(),
+        None => shape.rich_text.iter().map(|run| run.text.as_str()).collect(),
+    };
+    format!("{:?}\u{0}{}\u{0}{text}", shape.shape_type, shape.name.as_deref().unwrap_or_default())
+}
+
+impl SlideContent {
+    /// Give shapes and overlays without a fixed ID one derived from their
+    /// content (see [`assign_stable_ids`])
+    pub fn stable_shape_ids(mut self) -> Self {
+        assign_shape_ids(&mut self);
+        self
+    }
+}
+
+fn assign_shape_ids(slide: &mut SlideContent) {
+    let fixed: Vec<u32> = slide.shapes.iter().chain(&slide.overlays).filter_map(|s| s.id).collect();
+    let mut pool = IdPool::new(FIRST_SHAPE_ID, SHAPE_ID_RANGE, fixed.iter().copied());
+    let mut moved = HashMap::new();
+    for (index, shape) in slide.shapes.iter_mut().chain(slide.overlays.iter_mut()).enumerate() {
+        if shape.id.is_none() {
+            let id = pool.id(&shape_key(shape));
+            moved.insert(index as u32 + 10, id);
+            shape.id = Some(id);
+        }
+    }
+    // A fixed ID wins over a position-based one, as when gluing
+    for id in &fixed {
+        moved.remove(id);
+    }
+    for connector in &mut slide.connectors {
+        for id in [&mut connector.start_shape_id, &mut connector.end_shape_id].into_iter().flatten() {
+            if let Some(&new) = moved.get(id) {
+                *id = new;
+            }
+        }
+    }
+}
+
+/// Derive slide IDs from slide titles and shape IDs from shape content
+///
+/// Slide zooms are pointed at their targets' new IDs.
+pub fn assign_stable_ids(slides: &mut [SlideContent]) {
+    let taken = slides.iter().filter_map(|s| s.slide_id);
+    let mut pool = IdPool::new(FIRST_SLIDE_ID, SLIDE_ID_LIMIT - FIRST_SLIDE_ID, taken);
+    for slide in slides.iter_mut() {
+        if slide.slide_id.is_none() {
+            slide.slide_id = Some(pool.id(&slide.title));
+        }
+        assign_shape_ids(slide);
+    }
+
+    let ids: Vec<Option<u32>> = slides.iter().map(|s| s.slide_id).collect();
+    for zoom in slides.iter_mut().flat_map(|s| s.slide_zooms.iter_mut()) {
+        if let Some(&id) = zoom.target_slide.checked_sub(1).and_then(|i| ids.get(i)) {
+            zoom.target_slide_id = id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{ConnectionSite, Connector, ConnectorType, ShapeType, SlideZoom};
+
+    fn shape(text: &str) -> Shape {
+        Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_text(text)
+    }
+
+    #[test]
+    fn test_duplicate_content_gets_distinct_ids() {
+        let slide = SlideContent::new("Flow")
+            .add_shape(shape("Step"))
+            .add_shape(shape("Step").with_id(42))
+            .add_shape(shape("Step"))
+            .stable_shape_ids();
+        let ids: Vec<u32> = slide.shapes.iter().filter_map(|s| s.id).collect();
+        assert_eq!(ids[1], 42);
+        assert_ne!(ids[0], ids[2]);
+        assert!(ids[0] >= FIRST_SHAPE_ID && ids[2] >= FIRST_SHAPE_ID);
+    }
+
+    #[test]
+    fn test_connectors_follow_their_shapes() {
+        let slide = SlideContent::new("Flow")
+            .add_shape(shape("A"))
+            .add_shape(shape("B"))
+            .add_connector(Connector::new(ConnectorType::Straight, 0, 0, 1, 1)
+                .connect_start(10, ConnectionSite::Right)
+                .connect_end(11, ConnectionSite::Left))
+            .stable_shape_ids();
+        assert_eq!(slide.connectors[0].start_shape_id, slide.shapes[0].id);
+        assert_eq!(slide.connectors[0].end_shape_id, slide.shapes[1].id);
+    }
+
+    #[test]
+    fn test_slide_ids_and_zoom_targets() {
+        let mut slides = vec![
+            SlideContent::new("Summary").add_slide_zoom(SlideZoom::new(3, 0, 0, 100, 75)),
+            SlideContent::new("Detail"),
+            SlideContent::new("Detail"),
+        ];
+        assign_stable_ids(&mut slides);
+        let ids: Vec<u32> = slides.iter().filter_map(|s| s.slide_id).collect();
+        assert_eq!(ids.len(), 3);
+        assert_ne!(ids[1], ids[2]);
+        assert!(ids.iter().all(|id| (FIRST_SLIDE_ID..SLIDE_ID_LIMIT).contains(id)));
+        assert_eq!(slides[0].slide_zooms[0].target_slide_id, Some(ids[2]));
+    }
+}
@@ -0,0 +1,206 @@
+//! Progress steppers
+//!
+//! [`Stepper`] draws a row of numbered circles joined by a line, with each
+//! step's label under its circle. Steps before the current one are styled
+//! as completed, the current one is highlighted and the rest are outlined.
+//! By default it is a thin strip along the top of the slide, above the
+//! title. Add it to one slide with
+//! [`SlideContent::stepper`](super::SlideContent::stepper), or to every
+//! slide of every section with `Presentation::section_stepper`, which picks
+//! the current step from the section each slide is in.
+
+use crate::elements::ColorInput;
+use super::constants::{CONTENT_WIDTH, CONTENT_X};
+use super::shapes::{Shape, ShapeFill, ShapeLine, ShapeType};
+use super::text::FormattedText;
+
+/// Top of the default strip (0.05 inch)
+const STRIP_Y: u32 = 45720;
+/// Default strip height: a circle and its label (0.5 inch)
+const STRIP_HEIGHT: u32 = 457200;
+/// Circle diameter (0.22 inch)
+const CIRCLE_SIZE: u32 = 201168;
+/// Connecting line thickness (2 pt)
+const LINE_WIDTH: u32 = 25400;
+/// Outline of upcoming steps (1 pt)
+const OUTLINE_WIDTH: u32 = 12700;
+/// Step number font size in points
+const NUMBER_FONT_SIZE: u32 = 10;
+/// Step label font size in points
+const LABEL_FONT_SIZE: u32 = 9;
+/// Color of upcoming steps' outlines, text and line
+const UPCOMING_COLOR: &str = "9E9E9E";
+
+/// How a step is drawn relative to the current step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StepState {
+    Completed,
+    Current,
+    Upcoming,
+}
+
+/// Numbered steps with completed, current and upcoming styling
+///
+/// ```
+/// use ppt_rs::generator::{SlideContent, StepState, Stepper};
+///
+/// let stepper = Stepper::new(["Discover", "Design", "Build", "Launch"]).current(2);
+/// assert_eq!(stepper.state(1), StepState::Completed);
+/// assert_eq!(stepper.state(2), StepState::Current);
+///
+/// let slide = SlideContent::new("Build").stepper(&stepper);
+/// assert!(slide.shapes.iter().any(|s| s.name.as_deref() == Some("Step 3: Build")));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Stepper {
+    labels: Vec<String>,
+    current: Option<usize>,
+    completed_color: String,
+    current_color: String,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Stepper {
+    /// Steps with these labels, all upcoming, along the top of the slide
+    pub fn new<S: Into<String>>(labels: impl IntoIterator<Item = S>) -> Self {
+        Stepper {
+            labels: labels.into_iter().map(Into::into).collect(),
+            current: None,
+            completed_color: "2E7D32".to_string(),
+            current_color: "1565C0".to_string(),
+            x: CONTENT_X,
+            y: STRIP_Y,
+            width: CONTENT_WIDTH,
+            height: STRIP_HEIGHT,
+        }
+    }
+
+    /// Mark step `index` (0-based) as current and the ones before it as
+    /// completed; an index past the last step completes them all
+    pub fn current(mut self, index: usize) -> Self {
+        self.current = Some(index);
+        self
+    }
+
+    pub fn completed_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.completed_color = color.into().to_hex();
+        self
+    }
+
+    pub fn current_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.current_color = color.into().to_hex();
+        self
+    }
+
+    pub fn position(mut self, x: u32, y: u32) -> Self {
+        self.x = x;
+        self.y = y;
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Replace the step labels, keeping the styling and position
+    pub(crate) fn with_labels(mut self, labels: Vec<String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn state(&self, index: usize) -> StepState {
+        match self.current {
+            Some(current) if index < current => StepState::Completed,
+            Some(current) if index == current => StepState::Current,
+            _ => StepState::Upcoming,
+        }
+    }
+
+    /// Connecting lines, circles and labels, in drawing order
+    pub fn to_shapes(&self) -> Vec<Shape> {
+        let count = self.labels.len() as u32;
+        if count == 0 {
+            return Vec::new();
+        }
+        let slot = self.width / count;
+        let center = |i: u32| self.x + slot * i + slot / 2;
+        let circle_y = self.y;
+        let label_y = circle_y + CIRCLE_SIZE;
+        let label_height = self.height.saturating_sub(CIRCLE_SIZE);
+
+        let mut shapes = Vec::new();
+        // Lines first so the circles cover their ends; a segment is completed
+        // once the step it leads to has been reached
+        for i in 1..count {
+            let reached = self.state(i as usize) != StepState::Upcoming;
+            let color = if reached { self.completed_color.as_str() } else { UPCOMING_COLOR };
+            shapes.push(Shape::new(ShapeType::Rectangle, center(i - 1), circle_y + (CIRCLE_SIZE - LINE_WIDTH) / 2, slot, LINE_WIDTH)
+                .with_fill(ShapeFill::new(color))
+                .with_name("Step Line"));
+        }
+
+        for (i, label) in self.labels.iter().enumerate() {
+            let state = self.state(i);
+            let (fill, number_color, label_color) = match state {
+                StepState::Completed => (self.completed_color.as_str(), "FFFFFF", "424242"),
+                StepState::Current => (self.current_color.as_str(), "FFFFFF", self.current_color.as_str()),
+                StepState::Upcoming => ("FFFFFF", UPCOMING_COLOR, UPCOMING_COLOR),
+            };
+            let mut circle = Shape::new(ShapeType::Ellipse, center(i as u32) - CIRCLE_SIZE / 2, circle_y, CIRCLE_SIZE, CIRCLE_SIZE)
+                .with_fill(ShapeFill::new(fill))
+                .with_rich_text(vec![FormattedText::new(&(i + 1).to_string()).bold().color(number_color).font_size(NUMBER_FONT_SIZE)])
+                .with_name(&format!("Step {}: {label}", i + 1));
+            if state == StepState::Upcoming {
+                circle = circle.with_line(ShapeLine::new(UPCOMING_COLOR, OUTLINE_WIDTH));
+            }
+            shapes.push(circle);
+
+            let text = FormattedText::new(label).color(label_color).font_size(LABEL_FONT_SIZE);
+            shapes.push(Shape::new(ShapeType::Rectangle, center(i as u32) - slot / 2, label_y, slot, label_height)
+                .with_fill(ShapeFill::new("FFFFFF").with_transparency(100))
+                .with_rich_text(vec![if state == StepState::Current { text.bold() } else { text }])
+                .with_name("Step Label"));
+        }
+        shapes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_states() {
+        let stepper = Stepper::new(["A", "B", "C"]);
+        assert_eq!(stepper.state(0), StepState::Upcoming);
+        let stepper = stepper.current(1);
+        assert_eq!(
+            (0..3).map(|i| stepper.state(i)).collect::<Vec<_>>(),
+            vec![StepState::Completed, StepState::Current, StepState::Upcoming]
+        );
+        assert_eq!(stepper.current(5).state(2), StepState::Completed);
+    }
+
+    #[test]
+    fn test_layout() {
+        let shapes = Stepper::new(["A", "B", "C"]).position(0, 0).size(3_000_000, 500_000).current(1).to_shapes();
+        // Two lines, then a circle and a label per step
+        assert_eq!(shapes.len(), 2 + 3 * 2);
+        let circles: Vec<&Shape> = shapes.iter().filter(|s| s.shape_type == ShapeType::Ellipse).collect();
+        assert_eq!(circles[1].x.0, 1_500_000 - CIRCLE_SIZE as i64 / 2);
+        assert_eq!(circles[2].rich_text[0].text, "3");
+        // The line into the current step is completed, the one after is not
+        assert_eq!(shapes[0].fill.as_ref().unwrap().color, "2E7D32");
+        assert_eq!(shapes[1].fill.as_ref().unwrap().color, UPCOMING_COLOR);
+        assert!(Stepper::new(Vec::<String>::new()).to_shapes().is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+//! Swimlane process diagrams
+//!
+//! [`Swimlanes`] lays out a process across lanes (teams, systems, roles):
+//! each step goes in its lane's row and in a column given by the order it
+//! was added, so a step never starts before the one it follows. Lanes are
+//! drawn as labelled bands and steps are joined by connectors glued to the
+//! step shapes, bent where they cross lanes. Add it to a slide with
+//! [`SlideContent::swimlanes`](super::SlideContent::swimlanes).
+
+use crate::core::{Emu, Positioned};
+use crate::elements::ColorInput;
+use super::connectors::{ArrowType, ConnectionSite, Connector, ConnectorLine, ConnectorType};
+use super::shapes::{Shape, ShapeFill, ShapeLine, ShapeType};
+use super::slide_content::Region;
+use super::text::FormattedText;
+
+/// Width of the lane title column (1.1 inch)
+const HEADER_WIDTH: u32 = 1005840;
+/// Largest step box (1.8 × 0.8 inch)
+const MAX_STEP_SIZE: (u32, u32) = (1645920, 731520);
+/// Lane title font size in points
+const LANE_FONT_SIZE: u32 = 14;
+/// Step font size in points
+const STEP_FONT_SIZE: u32 = 12;
+/// Band outline width (1 pt)
+const LINE_WIDTH: u32 = 12700;
+/// Connector width (1.5 pt)
+const CONNECTOR_WIDTH: u32 = 19050;
+
+/// One step of a [`Swimlanes`] diagram
+#[derive(Clone, Debug, PartialEq)]
+pub struct SwimlaneStep {
+    pub label: String,
+    /// Index into the diagram's lanes
+    pub lane: usize,
+    /// 0-based column, left to right
+    pub column: usize,
+}
+
+/// Process diagram with one row per lane
+///
+/// ```
+/// use ppt_rs::generator::{SlideContent, Swimlanes};
+///
+/// let process = Swimlanes::new(["Sales", "Legal", "Finance"])
+///     .step("Sales", "Draft quote")
+///     .step("Legal", "Review terms")
+///     .step("Finance", "Approve discount")
+///     .step("Sales", "Send contract");
+/// assert_eq!(process.columns(), 4);
+///
+/// let slide = SlideContent::new("Deal Desk").swimlanes(&process);
+/// assert_eq!(slide.connectors.len(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Swimlanes {
+    lanes: Vec<String>,
+    steps: Vec<SwimlaneStep>,
+    links: Vec<(usize, usize)>,
+    sequential: bool,
+    x: Emu,
+    y: Emu,
+    width: u32,
+    height: u32,
+    lane_color: String,
+    step_color: String,
+}
+
+impl Swimlanes {
+    /// Diagram with `lanes` from top to bottom, filling the content area
+    pub fn new<S: Into<String>>(lanes: impl IntoIterator<Item = S>) -> Self {
+        let (x, y, width, height) = Region::Content.bounds();
+        Swimlanes {
+            lanes: lanes.into_iter().map(Into::into).collect(),
+            steps: Vec::new(),
+            links: Vec::new(),
+            sequential: true,
+            x: x.into(),
+            y: y.into(),
+            width,
+            height,
+            lane_color: "455A64".to_string(),
+            step_color: "1565C0".to_string(),
+        }
+    }
+
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
+        self
+    }
+
+    /// Set the width and height; negative sizes are 0
+    pub fn size(mut self, width: impl Into<Emu>, height: impl Into<Emu>) -> Self {
+        self.width = width.into().to_u32_saturating();
+        self.height = height.into().to_u32_saturating();
+        self
+    }
+
+    /// Set the fill of the lane titles
+    pub fn lane_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.lane_color = color.into().to_hex();
+        self
+    }
+
+    /// Set the fill of the steps
+    pub fn step_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.step_color = color.into().to_hex();
+        self
+    }
+
+    /// Add a step to `lane` in the next column
+    ///
+    /// A lane not passed to [`Self::new`] is added at the bottom.
+    pub fn step(self, lane: &str, label: &str) -> Self {
+        let column = self.columns();
+        self.step_at(lane, column, label)
+    }
+
+    /// Add a step to `lane` in `column`, e.g. beside a step it runs in parallel with
+    pub fn step_at(mut self, lane: &str, column: usize, label: &str) -> Self {
+        let lane = match self.lanes.iter().position(|l| l == lane) {
+            Some(index) => index,
+            None => {
+                self.lanes.push(lane.to_string());
+                self.lanes.len() - 1
+            }
+        };
+        self.steps.push(SwimlaneStep { label: label.to_string(), lane, column });
+        self
+    }
+
+    /// Connect step `from` to step `to` (0-based, in the order added)
+    pub fn connect(mut self, from: usize, to: usize) -> Self {
+        self.links.push((from, to));
+        self
+    }
+
+    /// Connect every step to the next one added (on by default)
+    pub fn sequential(mut self, sequential: bool) -> Self {
+        self.sequential = sequential;
+        self
+    }
+
+    pub fn lanes(&self) -> &[String] {
+        &self.lanes
+    }
+
+    pub fn steps(&self) -> &[SwimlaneStep] {
+        &self.steps
+    }
+
+    /// Number of step columns
+    pub fn columns(&self) -> usize {
+        self.steps.iter().map(|s| s.column + 1).max().unwrap_or(0)
+    }
+
+    /// Sequential links followed by explicit ones, skipping unknown steps and repeats
+    fn all_links(&self) -> Vec<(usize, usize)> {
+        let sequence = (1..self.steps.len()).filter(|_| self.sequential).map(|i| (i - 1, i));
+        let mut links: Vec<(usize, usize)> = Vec::new();
+        for (from, to) in sequence.chain(self.links.iter().copied()) {
+            if from != to && from < self.steps.len() && to < self.steps.len() && !links.contains(&(from, to)) {
+                links.push((from, to));
+            }
+        }
+        links
+    }
+
+    /// Lane bands and titles, then steps, with IDs counting up from `first_id`,
+    /// and the connectors glued to the steps
+    ///
+    /// Pass the ID the first shape would get on the slide (10 plus the
+    /// number of shapes already there) so the glue IDs stay valid.
+    pub fn layout(&self, first_id: u32) -> (Vec<Shape>, Vec<Connector>) {
+        let lanes = self.lanes.len().max(1) as u32;
+        let columns = self.columns().max(1) as u32;
+        let lane_height = self.height / lanes;
+        // Laid out from (0, 0), then moved to the diagram's position
+        let body_x = HEADER_WIDTH;
+        let column_width = self.width.saturating_sub(HEADER_WIDTH) / columns;
+        let step_width = (column_width * 4 / 5).min(MAX_STEP_SIZE.0);
+        let step_height = (lane_height * 11 / 20).min(MAX_STEP_SIZE.1);
+
+        let mut shapes = Vec::new();
+        for (i, lane) in self.lanes.iter().enumerate() {
+            let top = i as u32 * lane_height;
+            let band = if i % 2 == 0 { "F5F5F5" } else { "FFFFFF" };
+            shapes.push(Shape::new(ShapeType::Rectangle, body_x, top, column_width * columns, lane_height)
+                .with_fill(ShapeFill::new(band))
+                .with_line(ShapeLine::new("BDBDBD", LINE_WIDTH))
+                .with_name(&format!("Lane: {lane}")));
+            shapes.push(Shape::new(ShapeType::Rectangle, 0, top, HEADER_WIDTH, lane_height)
+                .with_fill(ShapeFill::new(self.lane_color.as_str()))
+                .with_line(ShapeLine::new("BDBDBD", LINE_WIDTH))
+                .with_rich_text(vec![FormattedText::new(lane).bold().color("FFFFFF").font_size(LANE_FONT_SIZE)])
+                .with_name(&format!("Lane Title: {lane}")));
+        }
+
+        // Top-left corner of each step, centered in its lane and column
+        let corners: Vec<(u32, u32)> = self.steps.iter().map(|step| {
+            let cx = body_x + step.column as u32 * column_width + column_width / 2;
+            let cy = step.lane as u32 * lane_height + lane_height / 2;
+            (cx - step_width / 2, cy - step_height / 2)
+        }).collect();
+        let step_ids: Vec<u32> = (0..self.steps.len() as u32).map(|i| first_id + shapes.len() as u32 + i).collect();
+        for (step, &(x, y)) in self.steps.iter().zip(&corners) {
+            shapes.push(Shape::new(ShapeType::RoundedRectangle, x, y, step_width, step_height)
+                .with_fill(ShapeFill::new(self.step_color.as_str()))
+                .with_rich_text(vec![FormattedText::new(&step.label).color("FFFFFF").font_size(STEP_FONT_SIZE)])
+                .with_name(&format!("Step: {}", step.label)));
+        }
+        for (i, shape) in shapes.iter_mut().enumerate() {
+            shape.id = Some(first_id + i as u32);
+        }
+
+        let site = |(x, y): (u32, u32), site: ConnectionSite| match site {
+            ConnectionSite::Left => (x, y + step_height / 2),
+            ConnectionSite::Right => (x + step_width, y + step_height / 2),
+            ConnectionSite::Top => (x + step_width / 2, y),
+            _ => (x + step_width / 2, y + step_height),
+        };
+        let mut connectors: Vec<Connector> = self.all_links().into_iter().map(|(from, to)| {
+            let (a, b) = (&self.steps[from], &self.steps[to]);
+            let (start, end) = if a.column < b.column {
+                (ConnectionSite::Right, ConnectionSite::Left)
+            } else if a.column > b.column {
+                (ConnectionSite::Left, ConnectionSite::Right)
+            } else if a.lane <= b.lane {
+                (ConnectionSite::Bottom, ConnectionSite::Top)
+            } else {
+                (ConnectionSite::Top, ConnectionSite::Bottom)
+            };
+            let kind = if a.lane == b.lane || a.column == b.column { ConnectorType::Straight } else { ConnectorType::Elbow };
+            let ((x1, y1), (x2, y2)) = (site(corners[from], start), site(corners[to], end));
+            Connector::new(kind, x1, y1, x2, y2)
+                .with_line(ConnectorLine::new("616161", CONNECTOR_WIDTH))
+                .with_end_arrow(ArrowType::Triangle)
+                .connect_start(step_ids[from], start)
+                .connect_end(step_ids[to], end)
+        }).collect();
+
+        for shape in &mut shapes {
+            shape.x += self.x;
+            shape.y += self.y;
+        }
+        for connector in &mut connectors {
+            let (x, y) = (connector.x(), connector.y());
+            connector.set_position(x + self.x, y + self.y);
+        }
+        (shapes, connectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process() -> Swimlanes {
+        Swimlanes::new(["Sales", "Legal"])
+            .position(0u32, 0u32)
+            .size(HEADER_WIDTH + 3_000_000, 2_000_000)
+            .step("Sales", "Quote")
+            .step("Legal", "Review")
+            .step_at("Sales", 1, "Check credit")
+            .step("Sales", "Sign")
+    }
+
+    #[test]
+    fn test_steps_placed_by_lane_and_column() {
+        let diagram = process();
+        assert_eq!(diagram.columns(), 3);
+        let (shapes, _) = diagram.layout(10);
+        let step = |label: &str| shapes.iter().find(|s| s.name.as_deref() == Some(&format!("Step: {label}"))).unwrap();
+        // 1,000,000-wide columns, 1,000,000-high lanes
+        let (review, sign) = (step("Review"), step("Sign"));
+        assert_eq!(review.x.0 + review.width.0 / 2, HEADER_WIDTH as i64 + 1_500_000);
+        assert_eq!(review.y.0 + review.height.0 / 2, 1_500_000);
+        assert_eq!(sign.x.0 + sign.width.0 / 2, HEADER_WIDTH as i64 + 2_500_000);
+        assert_eq!(step("Check credit").x, review.x);
+    }
+
+    #[test]
+    fn test_connectors_glued_to_steps() {
+        let (shapes, connectors) = process().layout(20);
+        let ids: Vec<u32> = shapes.iter().map(|s| s.id.unwrap()).collect();
+        assert_eq!(ids, (20..20 + shapes.len() as u32).collect::<Vec<_>>());
+
+        let quote = shapes.iter().find(|s| s.name.as_deref() == Some("Step: Quote")).unwrap();
+        assert_eq!(connectors.len(), 3);
+        assert_eq!(connectors[0].start_shape_id, quote.id);
+        assert_eq!(connectors[0].connector_type, ConnectorType::Elbow);
+        // Review and the credit check share a column: straight up a lane
+        assert_eq!(connectors[1].connector_type, ConnectorType::Straight);
+        assert_eq!((connectors[1].start_site, connectors[1].end_site), (Some(ConnectionSite::Top), Some(ConnectionSite::Bottom)));
+    }
+
+    #[test]
+    fn test_unknown_lane_is_added() {
+        let diagram = Swimlanes::new(["Sales"]).step("Ops", "Provision").sequential(false).connect(0, 5);
+        assert_eq!(diagram.lanes(), ["Sales", "Ops"]);
+        assert!(diagram.layout(10).1.is_empty());
+        let repeated = diagram.step("Sales", "Invoice").sequential(true).connect(0, 1);
+        assert_eq!(repeated.layout(10).1.len(), 1);
+    }
+}
@@ -1,6 +1,7 @@
 //! Table and TableBuilder for constructing tables
 
 use super::row::TableRow;
+use crate::core::Emu;
 
 /// Table definition with rows and positioning
 #[derive(Clone, Debug)]
@@ -9,15 +10,15 @@ pub struct Table {
     pub rows: Vec<TableRow>,
     /// Column widths in EMU
     pub column_widths: Vec<u32>,
-    /// X position in EMU
-    pub x: u32,
-    /// Y position in EMU
-    pub y: u32,
+    /// X position; negative values place the table partly left of the slide
+    pub x: Emu,
+    /// Y position; negative values place the table partly above the slide
+    pub y: Emu,
 }
 
 impl Table {
     /// Create a table from raw data (2D string array)
-    pub fn from_data(data: Vec<Vec<&str>>, column_widths: Vec<u32>, x: u32, y: u32) -> Self {
+    pub fn from_data(data: Vec<Vec<&str>>, column_widths: Vec<u32>, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
         use super::cell::TableCell;
         
         let rows = data
@@ -30,8 +31,8 @@ impl Table {
         Table {
             rows,
             column_widths,
-            x,
-            y,
+            x: x.into(),
+            y: y.into(),
         }
     }
 
@@ -64,8 +65,8 @@ impl Table {
 pub struct TableBuilder {
     column_widths: Vec<u32>,
     rows: Vec<TableRow>,
-    x: u32,
-    y: u32,
+    x: Emu,
+    y: Emu,
 }
 
 impl TableBuilder {
@@ -74,8 +75,8 @@ impl TableBuilder {
         TableBuilder {
             column_widths,
             rows: Vec::new(),
-            x: 0,
-            y: 0,
+            x: Emu::ZERO,
+            y: Emu::ZERO,
         }
     }
 
@@ -86,9 +87,9 @@ impl TableBuilder {
     }
 
     /// Set table position
-    pub fn position(mut self, x: u32, y: u32) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
         self
     }
 
@@ -146,7 +147,7 @@ mod tests {
             .build();
 
         assert_eq!(table.row_count(), 2);
-        assert_eq!(table.x, 500000);
-        assert_eq!(table.y, 1000000);
+        assert_eq!(table.x, Emu(500000));
+        assert_eq!(table.y, Emu(1000000));
     }
 }
@@ -1,5 +1,7 @@
 //! Table cell definition and formatting
 
+use crate::elements::ColorInput;
+
 /// Horizontal text alignment
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum CellAlign {
@@ -106,14 +108,14 @@ impl TableCell {
     }
 
     /// Set cell text color (RGB hex format, e.g., "FF0000" or "#FF0000")
-    pub fn text_color(mut self, color: &str) -> Self {
-        self.text_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn text_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.text_color = Some(color.into().to_hex());
         self
     }
 
     /// Set cell background color (RGB hex format, e.g., "FF0000" or "#FF0000")
-    pub fn background_color(mut self, color: &str) -> Self {
-        self.background_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn background_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.background_color = Some(color.into().to_hex());
         self
     }
 
@@ -3,6 +3,7 @@
 use super::builder::Table;
 use super::row::TableRow;
 use super::cell::TableCell;
+use crate::core::{escape_xml, escape_xml_attr};
 
 /// Generate table XML for a slide
 pub fn generate_table_xml(table: &Table, shape_id: usize) -> String {
@@ -103,7 +104,7 @@ fn generate_cell_xml(cell: &TableCell) -> String {
             xml.push_str(&format!(r#"<a:solidFill><a:srgbClr val="{color}"/></a:solidFill>"#));
         }
         if let Some(ref font) = cell.font_family {
-            xml.push_str(&format!(r#"<a:latin typeface="{font}"/>"#));
+            xml.push_str(&format!(r#"<a:latin typeface="{}"/>"#, escape_xml_attr(font)));
         }
         xml.push_str("</a:rPr>");
     } else {
@@ -130,15 +131,6 @@ fn generate_cell_xml(cell: &TableCell) -> String {
     xml
 }
 
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
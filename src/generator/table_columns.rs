@@ -0,0 +1,206 @@
+//! Numeric table columns
+//!
+//! A [`Column`] formats the numbers of one table column when the table is
+//! built: fixed decimals, thousands separators, a prefix or suffix, and
+//! right alignment so the digits line up. Cells that don't hold a number,
+//! such as the header, keep their text and only take the alignment.
+//!
+//! With [`Column::decimal_tab`] the cells are aligned on a decimal tab
+//! stop instead, so values with different numbers of decimals still line
+//! up on their decimal points.
+//!
+//! ```
+//! use ppt_rs::generator::{Column, TableBuilder};
+//!
+//! let table = TableBuilder::new(vec![2_000_000, 1_500_000])
+//!     .add_simple_row(vec!["Region", "Revenue"])
+//!     .add_simple_row(vec!["EMEA", "1234567.891"])
+//!     .add_simple_row(vec!["APAC", "98000"])
+//!     .column(1, Column::numeric().decimals(1).prefix("$"))
+//!     .build();
+//! assert_eq!(table.rows[1].cells[1].text, "$1,234,567.9");
+//! assert_eq!(table.rows[2].cells[1].text, "$98,000.0");
+//! assert_eq!(table.rows[0].cells[1].text, "Revenue");
+//! ```
+
+use super::tables::{CellAlign, Table, TableCell};
+
+/// Left plus right text inset of a table cell (0.1 inch each)
+const CELL_INSETS: u32 = 182880;
+/// Font size PowerPoint uses for table text without an explicit size
+const DEFAULT_FONT_SIZE: u32 = 18;
+/// Width of one digit as a share of the font size
+const DIGIT_WIDTH: f64 = 0.6;
+/// EMU per point
+const EMU_PER_POINT: f64 = 12700.0;
+
+/// How the cells of one table column are formatted and aligned
+#[derive(Clone, Debug, PartialEq)]
+pub struct Column {
+    numeric: bool,
+    align: CellAlign,
+    decimals: Option<usize>,
+    thousands: bool,
+    prefix: String,
+    suffix: String,
+    decimal_tab: bool,
+}
+
+impl Column {
+    /// Left-aligned text column
+    pub fn text() -> Self {
+        Column {
+            numeric: false,
+            align: CellAlign::Left,
+            decimals: None,
+            thousands: false,
+            prefix: String::new(),
+            suffix: String::new(),
+            decimal_tab: false,
+        }
+    }
+
+    /// Right-aligned numbers grouped in thousands, decimals as written
+    pub fn numeric() -> Self {
+        Column { numeric: true, align: CellAlign::Right, thousands: true, ..Self::text() }
+    }
+
+    /// Round numbers to a fixed number of decimals
+    pub fn decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    /// Group the integer part in thousands with `,` (on for numeric columns)
+    pub fn thousands(mut self, thousands: bool) -> Self {
+        self.thousands = thousands;
+        self
+    }
+
+    /// Text before each number, e.g. `$`
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Text after each number, e.g. `%`
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Set the alignment of every cell in the column
+    pub fn align(mut self, align: CellAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Line numbers up on their decimal points with a decimal tab stop
+    pub fn decimal_tab(mut self) -> Self {
+        self.decimal_tab = true;
+        self
+    }
+
+    /// `text` formatted as a number of this column, or `None` if it isn't one
+    ///
+    /// Commas and the column's own prefix and suffix are ignored when
+    /// reading, so formatting twice gives the same text.
+    pub fn format(&self, text: &str) -> Option<String> {
+        if !self.numeric {
+            return None;
+        }
+        let trimmed = text.trim();
+        let bare = trimmed.strip_prefix(self.prefix.as_str()).unwrap_or(trimmed);
+        let bare = bare.strip_suffix(self.suffix.as_str()).unwrap_or(bare).replace(',', "");
+        let value: f64 = bare.parse().ok().filter(|v: &f64| v.is_finite())?;
+        let number = match self.decimals {
+            Some(decimals) => format!("{value:.decimals$}"),
+            None => bare,
+        };
+        let (sign, digits) = number.strip_prefix('-').map_or(("", number.as_str()), |d| ("-", d));
+        let (int, frac) = digits.split_once('.').map_or((digits, None), |(i, f)| (i, Some(f)));
+
+        let mut out = format!("{sign}{}", self.prefix);
+        for (i, c) in int.chars().enumerate() {
+            if self.thousands && i > 0 && (int.len() - i) % 3 == 0 {
+                out.push(',');
+            }
+            out.push(c);
+        }
+        if let Some(frac) = frac {
+            out.push('.');
+            out.push_str(frac);
+        }
+        out.push_str(&self.suffix);
+        Some(out)
+    }
+
+    /// Decimal tab stop shared by the `cells` of a column `width` EMU wide
+    ///
+    /// Leaves room right of the stop for the longest decimals and suffix.
+    fn tab_stop(width: u32, cells: &[&TableCell]) -> u32 {
+        let reserve = cells.iter().map(|cell| {
+            let after_point = cell.text.split_once('.').map_or(0, |(_, decimals)| decimals.chars().count() + 1);
+            after_point as f64 * cell.font_size.unwrap_or(DEFAULT_FONT_SIZE) as f64 * DIGIT_WIDTH * EMU_PER_POINT
+        }).fold(0.0, f64::max);
+        width.saturating_sub(CELL_INSETS).saturating_sub(reserve as u32)
+    }
+}
+
+impl Table {
+    /// Format the cells of column `index` (0-based)
+    pub fn format_column(mut self, index: usize, column: &Column) -> Self {
+        let mut numbers = Vec::new();
+        for (r, row) in self.rows.iter_mut().enumerate() {
+            let Some(cell) = row.cells.get_mut(index) else { continue };
+            cell.align = column.align.clone();
+            if let Some(text) = column.format(&cell.text) {
+                cell.text = text;
+                numbers.push(r);
+            }
+        }
+        if column.decimal_tab && !numbers.is_empty() {
+            let width = self.column_widths.get(index).copied().unwrap_or(0);
+            let cells: Vec<&TableCell> = numbers.iter().map(|&r| &self.rows[r].cells[index]).collect();
+            let stop = Column::tab_stop(width, &cells);
+            for r in numbers {
+                let cell = &mut self.rows[r].cells[index];
+                cell.align = CellAlign::Left;
+                cell.decimal_tab = Some(stop);
+            }
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_numbers() {
+        let column = Column::numeric();
+        assert_eq!(column.format("1234567").as_deref(), Some("1,234,567"));
+        assert_eq!(column.format("-1234.50").as_deref(), Some("-1,234.50"));
+        assert_eq!(column.format("Revenue"), None);
+        assert_eq!(Column::text().format("12"), None);
+
+        let money = Column::numeric().decimals(2).prefix("$").suffix("k");
+        assert_eq!(money.format("1,000.5").as_deref(), Some("$1,000.50k"));
+        assert_eq!(money.format("$1,000.50k").as_deref(), Some("$1,000.50k"));
+        assert_eq!(Column::numeric().thousands(false).format("5000").as_deref(), Some("5000"));
+    }
+
+    #[test]
+    fn test_decimal_tab() {
+        let table = Table::from_data(vec![vec!["Total"], vec!["12.5"], vec!["1000"]], vec![2_000_000], 0, 0)
+            .format_column(0, &Column::numeric().decimal_tab());
+        assert_eq!(table.rows[0].cells[0].decimal_tab, None);
+        assert_eq!(table.rows[0].cells[0].align, CellAlign::Right);
+        let stops: Vec<Option<u32>> = table.rows[1..].iter().map(|r| r.cells[0].decimal_tab).collect();
+        assert!(stops[0].is_some_and(|stop| stop < 2_000_000 - CELL_INSETS));
+        // One stop for the whole column, with room for the longest decimals
+        assert_eq!(stops[0], stops[1]);
+        assert_eq!(table.rows[2].cells[0].text, "1,000");
+    }
+}
@@ -0,0 +1,174 @@
+//! Row-level conditional formatting for tables
+//!
+//! A rule pairs a condition on a row's values with a [`RowFormat`] applied
+//! to every cell of the rows it matches, so a status table colors itself:
+//!
+//! ```
+//! use ppt_rs::generator::{RowFormat, TableBuilder};
+//!
+//! let table = TableBuilder::new(vec![2_000_000, 1_500_000])
+//!     .add_simple_row(vec!["task", "status"])
+//!     .add_simple_row(vec!["Design", "Done"])
+//!     .add_simple_row(vec!["Build", "Late"])
+//!     .conditional(|row| row["status"] == "Late", RowFormat::new().background("C62828").text_color("FFFFFF"))
+//!     .build();
+//! assert_eq!(table.rows[2].cells[0].background_color.as_deref(), Some("C62828"));
+//! assert_eq!(table.rows[1].cells[0].background_color, None);
+//! ```
+//!
+//! The first row is the header: its cell texts name the columns and it is
+//! never formatted. Rules apply in the order given, so a later rule wins
+//! for the properties both set.
+
+use std::ops::Index;
+
+use crate::elements::ColorInput;
+use super::tables::{Table, TableCell};
+
+/// Condition a [`TableBuilder`](super::TableBuilder) keeps until it builds
+pub(crate) type RowCondition = Box<dyn Fn(&RowValues) -> bool>;
+
+/// Returned for columns a row doesn't have
+static EMPTY: String = String::new();
+
+/// Cell properties set on the rows a rule matches
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RowFormat {
+    pub background: Option<String>,
+    pub text_color: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+impl RowFormat {
+    /// Format that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn background(mut self, color: impl Into<ColorInput>) -> Self {
+        self.background = Some(color.into().to_hex());
+        self
+    }
+
+    pub fn text_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.text_color = Some(color.into().to_hex());
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    fn apply(&self, cell: &mut TableCell) {
+        if let Some(color) = &self.background {
+            cell.background_color = Some(color.clone());
+        }
+        if let Some(color) = &self.text_color {
+            cell.text_color = Some(color.clone());
+        }
+        cell.bold |= self.bold;
+        cell.italic |= self.italic;
+    }
+}
+
+/// A data row's cell texts, by header name or by position
+///
+/// Indexing with an unknown column or position gives an empty string.
+pub struct RowValues<'a> {
+    headers: &'a [String],
+    cells: &'a [TableCell],
+}
+
+impl RowValues<'_> {
+    /// Text of the cell under the header `column`
+    pub fn get(&self, column: &str) -> Option<&str> {
+        let index = self.headers.iter().position(|h| h == column)?;
+        self.cells.get(index).map(|c| c.text.as_str())
+    }
+
+    /// Text of the cell under `column`, parsed as a number
+    pub fn number(&self, column: &str) -> Option<f64> {
+        self.get(column)?.trim().replace(',', "").parse().ok()
+    }
+}
+
+impl Index<&str> for RowValues<'_> {
+    type Output = String;
+
+    fn index(&self, column: &str) -> &String {
+        self.headers.iter()
+            .position(|h| h == column)
+            .and_then(|i| self.cells.get(i))
+            .map_or(&EMPTY, |c| &c.text)
+    }
+}
+
+impl Index<usize> for RowValues<'_> {
+    type Output = String;
+
+    fn index(&self, index: usize) -> &String {
+        self.cells.get(index).map_or(&EMPTY, |c| &c.text)
+    }
+}
+
+impl Table {
+    /// Format every data row for which `condition` holds
+    ///
+    /// See the [module docs](self) for how rows are read.
+    pub fn conditional(mut self, condition: impl Fn(&RowValues) -> bool, format: RowFormat) -> Self {
+        self.apply_rule(&condition, &format);
+        self
+    }
+
+    pub(crate) fn apply_rule(&mut self, condition: &dyn Fn(&RowValues) -> bool, format: &RowFormat) {
+        let Some((header, rows)) = self.rows.split_first_mut() else { return };
+        let headers: Vec<String> = header.cells.iter().map(|c| c.text.clone()).collect();
+        for row in rows {
+            if condition(&RowValues { headers: &headers, cells: &row.cells }) {
+                row.cells.iter_mut().for_each(|cell| format.apply(cell));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        Table::from_data(
+            vec![vec!["task", "status", "days"], vec!["Design", "Done", "3"], vec!["Build", "Late", "1,200"]],
+            vec![1, 1, 1],
+            0,
+            0,
+        )
+    }
+
+    #[test]
+    fn test_later_rules_win() {
+        let table = table()
+            .conditional(|_| true, RowFormat::new().background("EEEEEE").bold())
+            .conditional(|row| row[1] == "Late", RowFormat::new().background("C62828"));
+        assert!(!table.rows[0].cells[0].bold);
+        assert_eq!(table.rows[1].cells[2].background_color.as_deref(), Some("EEEEEE"));
+        assert_eq!(table.rows[2].cells[2].background_color.as_deref(), Some("C62828"));
+        assert!(table.rows[2].cells[0].bold);
+    }
+
+    #[test]
+    fn test_row_values() {
+        let table = table().conditional(
+            |row| row.number("days").is_some_and(|d| d > 100.0) && row["missing"].is_empty(),
+            RowFormat::new().italic(),
+        );
+        assert!(table.rows[2].cells[0].italic);
+        assert!(!table.rows[1].cells[0].italic);
+    }
+}
@@ -1,5 +1,13 @@
 //! Table creation support for PPTX generation
 
+use crate::core::{Emu, Positioned, ElementSized, Styled};
+use crate::generator::validate::{ValidationError, check_color};
+use crate::elements::ColorInput;
+use crate::generator::shapes_xml::get_text_color;
+use crate::generator::slide_content::BulletStyle;
+use crate::generator::table_columns::Column;
+use crate::generator::table_rules::{RowCondition, RowFormat, RowValues};
+
 /// Horizontal text alignment
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum CellAlign {
@@ -42,9 +50,63 @@ impl CellVAlign {
     }
 }
 
+/// Direction of the text in a cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextDirection {
+    #[default]
+    Horizontal,
+    /// Rotated 90° clockwise, reading top to bottom
+    Vert,
+    /// Rotated 270° clockwise, reading bottom to top
+    Vert270,
+    /// Upright letters stacked top to bottom
+    Stacked,
+}
+
+impl TextDirection {
+    /// Get the OOXML `vert` value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TextDirection::Horizontal => "horz",
+            TextDirection::Vert => "vert",
+            TextDirection::Vert270 => "vert270",
+            TextDirection::Stacked => "wordArtVert",
+        }
+    }
+}
+
+/// Space between a cell's border and its text, in EMU
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellMargins {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl CellMargins {
+    pub fn new(left: u32, right: u32, top: u32, bottom: u32) -> Self {
+        CellMargins { left, right, top, bottom }
+    }
+
+    /// The same margin on all four sides
+    pub fn uniform(margin: u32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+
+    /// Margins in proportion to the text: 0.4 em at the sides and 0.2 em
+    /// above and below, PowerPoint's own 0.1" × 0.05" at 18 pt
+    pub fn for_font_size(points: u32) -> Self {
+        let side = points * 12700 * 2 / 5;
+        let vertical = points * 12700 / 5;
+        Self::new(side, side, vertical, vertical)
+    }
+}
+
 /// Table cell content
 #[derive(Clone, Debug)]
 pub struct TableCell {
+    /// Cell text; each line is written as its own paragraph
     pub text: String,
     pub bold: bool,
     pub italic: bool,
@@ -55,11 +117,21 @@ pub struct TableCell {
     pub font_family: Option<String>,     // Font family name
     pub align: CellAlign,                // Horizontal alignment
     pub valign: CellVAlign,              // Vertical alignment
+    pub text_direction: TextDirection,
+    /// Bullet for each line of `text`; two leading spaces per nesting level
+    pub bullet: Option<BulletStyle>,
+    /// Text insets; PowerPoint's defaults when unset
+    pub margins: Option<CellMargins>,
     pub wrap_text: bool,                 // Text wrapping
+    /// Decimal tab stop, in EMU from the cell's left text inset; the text
+    /// is written after a tab so its decimal point lines up on the stop
+    pub decimal_tab: Option<u32>,
     pub row_span: u32,
     pub col_span: u32,
     pub v_merge: bool,
     pub h_merge: bool,
+    /// Style sheet style applied at build time (see [`crate::stylesheet`])
+    pub style_name: Option<String>,
 }
 
 impl TableCell {
@@ -76,14 +148,33 @@ impl TableCell {
             font_family: None,
             align: CellAlign::Center,
             valign: CellVAlign::Middle,
+            text_direction: TextDirection::Horizontal,
+            bullet: None,
+            margins: None,
             wrap_text: true,
+            decimal_tab: None,
             row_span: 1,
             col_span: 1,
             v_merge: false,
             h_merge: false,
+            style_name: None,
         }
     }
 
+    /// Create a cell with one bulleted paragraph per item
+    ///
+    /// Each two leading spaces of an item nest it one level deeper:
+    /// `TableCell::with_bullets(&["Scope", "  API", "  UI"])`.
+    pub fn with_bullets(items: &[&str]) -> Self {
+        TableCell::new(&items.join("\n")).bullet_style(BulletStyle::Bullet).align_left()
+    }
+
+    /// Bullet each line of the cell's text with `style`
+    pub fn bullet_style(mut self, style: BulletStyle) -> Self {
+        self.bullet = Some(style);
+        self
+    }
+
     /// Set cell text as bold
     pub fn bold(mut self) -> Self {
         self.bold = true;
@@ -104,14 +195,14 @@ impl TableCell {
 
 
     /// Set cell text color (RGB hex format, e.g., "FF0000" or "#FF0000")
-    pub fn text_color(mut self, color: &str) -> Self {
-        self.text_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn text_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.text_color = Some(color.into().to_hex());
         self
     }
 
     /// Set cell background color (RGB hex format, e.g., "FF0000" or "#FF0000")
-    pub fn background_color(mut self, color: &str) -> Self {
-        self.background_color = Some(color.trim_start_matches('#').to_uppercase());
+    pub fn background_color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.background_color = Some(color.into().to_hex());
         self
     }
 
@@ -127,6 +218,12 @@ impl TableCell {
         self
     }
 
+    /// Format with the named style of the presentation's style sheet
+    pub fn style(mut self, name: &str) -> Self {
+        self.style_name = Some(name.to_string());
+        self
+    }
+
     /// Set horizontal text alignment
     pub fn align(mut self, align: CellAlign) -> Self {
         self.align = align;
@@ -169,12 +266,31 @@ impl TableCell {
         self
     }
 
+    /// Set the text direction, e.g. `TextDirection::Vert270` for narrow header columns
+    pub fn vert(mut self, direction: TextDirection) -> Self {
+        self.text_direction = direction;
+        self
+    }
+
+    /// Set the space between the cell's border and its text
+    pub fn margins(mut self, margins: CellMargins) -> Self {
+        self.margins = Some(margins);
+        self
+    }
+
     /// Enable or disable text wrapping
     pub fn wrap(mut self, wrap: bool) -> Self {
         self.wrap_text = wrap;
         self
     }
 
+    /// Line the decimal point up on a tab stop `position` EMU from the
+    /// cell's left text inset
+    pub fn decimal_tab(mut self, position: u32) -> Self {
+        self.decimal_tab = Some(position);
+        self
+    }
+
     /// Set row span (number of rows to merge down)
     pub fn with_row_span(mut self, span: u32) -> Self {
         self.row_span = span;
@@ -228,18 +344,20 @@ impl TableRow {
 pub struct Table {
     pub rows: Vec<TableRow>,
     pub column_widths: Vec<u32>, // in EMU
-    pub x: u32,                  // Position X in EMU
-    pub y: u32,                  // Position Y in EMU
+    /// Position X; negative values place the table partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the table partly above the slide
+    pub y: Emu,
 }
 
 impl Table {
     /// Create a new table
-    pub fn new(rows: Vec<TableRow>, column_widths: Vec<u32>, x: u32, y: u32) -> Self {
+    pub fn new(rows: Vec<TableRow>, column_widths: Vec<u32>, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
         Table {
             rows,
             column_widths,
-            x,
-            y,
+            x: x.into(),
+            y: y.into(),
         }
     }
 
@@ -267,7 +385,7 @@ impl Table {
     }
 
     /// Create a simple table from 2D data
-    pub fn from_data(data: Vec<Vec<&str>>, column_widths: Vec<u32>, x: u32, y: u32) -> Self {
+    pub fn from_data(data: Vec<Vec<&str>>, column_widths: Vec<u32>, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
         let rows = data
             .into_iter()
             .map(|row| {
@@ -282,9 +400,69 @@ impl Table {
         Table {
             rows,
             column_widths,
-            x,
-            y,
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Set the margins of every cell that doesn't set its own
+    pub fn cell_margins(mut self, margins: CellMargins) -> Self {
+        for cell in self.rows.iter_mut().flat_map(|r| r.cells.iter_mut()) {
+            cell.margins.get_or_insert(margins);
+        }
+        self
+    }
+
+    /// Raise every row to at least `height` EMU
+    ///
+    /// Rows without a height count as the default 400000 EMU; use it with
+    /// large margins or multi-line cells so text isn't clipped.
+    pub fn min_row_height(mut self, height: u32) -> Self {
+        for row in &mut self.rows {
+            row.height = Some(row.height.unwrap_or(400000).max(height));
+        }
+        self
+    }
+
+    /// Give cells with a background but no text color white or black
+    /// text, whichever reads better on the background
+    pub fn contrast_text(mut self) -> Self {
+        for cell in self.rows.iter_mut().flat_map(|row| row.cells.iter_mut()) {
+            if cell.text_color.is_none()
+                && let Some(background) = &cell.background_color
+            {
+                cell.text_color = Some(get_text_color(Some(background)).to_string());
+            }
         }
+        self
+    }
+
+    /// Check that every row fills the table's columns and colors are valid
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.column_widths.is_empty() {
+            return Err(ValidationError::new("table has no columns"));
+        }
+        for (r, row) in self.rows.iter().enumerate() {
+            // Either one cell per column, or spanning cells without their continuations
+            let spanned: u32 = row.cells.iter().map(|c| c.col_span.max(1)).sum();
+            if row.cells.len() != self.column_widths.len() && spanned as usize != self.column_widths.len() {
+                return Err(ValidationError::new(format!(
+                    "row {} has {} cells but the table has {} columns",
+                    r + 1,
+                    row.cells.len(),
+                    self.column_widths.len()
+                )));
+            }
+            for cell in &row.cells {
+                let colors = [("text color", &cell.text_color), ("background color", &cell.background_color)];
+                for (what, color) in colors {
+                    if let Some(color) = color {
+                        check_color(what, color).map_err(|e| e.within(format!("row {}", r + 1)))?;
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 }
 
@@ -292,8 +470,10 @@ impl Table {
 pub struct TableBuilder {
     rows: Vec<TableRow>,
     column_widths: Vec<u32>,
-    x: u32,
-    y: u32,
+    x: Emu,
+    y: Emu,
+    columns: Vec<(usize, Column)>,
+    rules: Vec<(RowCondition, RowFormat)>,
 }
 
 impl TableBuilder {
@@ -302,15 +482,17 @@ impl TableBuilder {
         TableBuilder {
             rows: Vec::new(),
             column_widths,
-            x: 0,
-            y: 0,
+            x: Emu::ZERO,
+            y: Emu::ZERO,
+            columns: Vec::new(),
+            rules: Vec::new(),
         }
     }
 
     /// Set table position
-    pub fn position(mut self, x: u32, y: u32) -> Self {
-        self.x = x;
-        self.y = y;
+    pub fn position(mut self, x: impl Into<Emu>, y: impl Into<Emu>) -> Self {
+        self.x = x.into();
+        self.y = y.into();
         self
     }
 
@@ -332,14 +514,83 @@ impl TableBuilder {
         self
     }
 
+    /// Format column `index` (0-based) when the table is built
+    pub fn column(mut self, index: usize, column: Column) -> Self {
+        self.columns.push((index, column));
+        self
+    }
+
+    /// Format the data rows matching `condition` when the table is built
+    ///
+    /// The first row names the columns (see [`crate::generator::table_rules`]).
+    pub fn conditional(mut self, condition: impl Fn(&RowValues) -> bool + 'static, format: RowFormat) -> Self {
+        self.rules.push((Box::new(condition), format));
+        self
+    }
+
     /// Build the table
     pub fn build(self) -> Table {
-        Table {
+        let mut table = Table {
             rows: self.rows,
             column_widths: self.column_widths,
             x: self.x,
             y: self.y,
+        };
+        for (index, column) in &self.columns {
+            table = table.format_column(*index, column);
+        }
+        for (condition, format) in &self.rules {
+            table.apply_rule(condition.as_ref(), format);
         }
+        table
+    }
+}
+
+impl Positioned for Table {
+    fn x(&self) -> Emu {
+        self.x
+    }
+
+    fn y(&self) -> Emu {
+        self.y
+    }
+
+    fn set_position(&mut self, x: Emu, y: Emu) {
+        self.x = x;
+        self.y = y;
+    }
+}
+
+/// Resizing scales every column width and row height proportionally
+impl ElementSized for Table {
+    fn width(&self) -> Emu {
+        Emu::from(Table::width(self))
+    }
+
+    fn height(&self) -> Emu {
+        Emu::from(Table::height(self))
+    }
+
+    fn set_size(&mut self, width: Emu, height: Emu) {
+        let sx = width.get() as f64 / Table::width(self).max(1) as f64;
+        let sy = height.get() as f64 / Table::height(self).max(1) as f64;
+        for column in &mut self.column_widths {
+            *column = Emu::from(*column).scale(sx).to_u32_saturating();
+        }
+        for row in &mut self.rows {
+            row.height = Some(Emu::from(row.height.unwrap_or(400000)).scale(sy).to_u32_saturating());
+        }
+    }
+}
+
+/// The cell background color
+impl Styled for TableCell {
+    fn color(&self) -> Option<&str> {
+        self.background_color.as_deref()
+    }
+
+    fn set_color(&mut self, color: &str) {
+        self.background_color = Some(ColorInput::from(color).to_hex());
     }
 }
 
@@ -367,6 +618,54 @@ mod tests {
         assert_eq!(cell.font_family, Some("Arial".to_string()));
     }
 
+    #[test]
+    fn test_table_set_size_scales_columns_and_rows() {
+        let mut table = Table::from_data(vec![vec!["a", "b"], vec!["c", "d"]], vec![1000, 3000], 0, 0);
+        table.set_size(Emu(2000), Emu(400000));
+        assert_eq!(table.column_widths, vec![500, 1500]);
+        assert_eq!(table.rows[0].height, Some(200000));
+        assert_eq!(ElementSized::width(&table), Emu(2000));
+    }
+
+    #[test]
+    fn test_cell_margins_and_min_row_height() {
+        assert_eq!(CellMargins::for_font_size(18), CellMargins::new(91440, 91440, 45720, 45720));
+        let own = CellMargins::uniform(0);
+        let table = Table::new(
+            vec![
+                TableRow::new(vec![TableCell::new("a").margins(own), TableCell::new("b")]),
+                TableRow::new(vec![TableCell::new("c"), TableCell::new("d")]).with_height(600000),
+            ],
+            vec![1000, 1000],
+            0,
+            0,
+        )
+        .cell_margins(CellMargins::for_font_size(10))
+        .min_row_height(500000);
+        assert_eq!(table.rows[0].cells[0].margins, Some(own));
+        assert_eq!(table.rows[0].cells[1].margins, Some(CellMargins::new(50800, 50800, 25400, 25400)));
+        assert_eq!(table.rows[0].height, Some(500000));
+        assert_eq!(table.rows[1].height, Some(600000));
+    }
+
+    #[test]
+    fn test_contrast_text() {
+        let table = Table::new(
+            vec![TableRow::new(vec![
+                TableCell::new("dark").background_color("1F497D"),
+                TableCell::new("light").background_color("F2F2F2"),
+                TableCell::new("own").background_color("1F497D").text_color("FFC000"),
+                TableCell::new("plain"),
+            ])],
+            vec![1000; 4],
+            0,
+            0,
+        )
+        .contrast_text();
+        let colors: Vec<Option<&str>> = table.rows[0].cells.iter().map(|c| c.text_color.as_deref()).collect();
+        assert_eq!(colors, vec![Some("FFFFFF"), Some("000000"), Some("FFC000"), None]);
+    }
+
     #[test]
     fn test_table_row() {
         let cells = vec![TableCell::new("A"), TableCell::new("B")];
@@ -397,7 +696,7 @@ mod tests {
 
         assert_eq!(table.row_count(), 2);
         assert_eq!(table.column_count(), 2);
-        assert_eq!(table.x, 100000);
-        assert_eq!(table.y, 200000);
+        assert_eq!(table.x, Emu(100000));
+        assert_eq!(table.y, Emu(200000));
     }
 }
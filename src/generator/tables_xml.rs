@@ -2,15 +2,27 @@
 //!
 //! Generates proper PPTX XML for tables with cells, rows, and formatting
 
-use crate::generator::tables::{Table, TableRow, TableCell};
+use crate::core::XmlWriter;
+use crate::generator::tables::{Table, TableRow, TableCell, CellAlign, TextDirection};
+
+/// Bullet indent per level inside a cell (0.2 inch)
+const BULLET_INDENT: u32 = 182880;
 
 /// Generate table XML for a slide
 pub fn generate_table_xml(table: &Table, shape_id: usize) -> String {
+    let mut xml = XmlWriter::with_capacity(1024 + 512 * table.rows.len() * table.column_count());
+    write_table_xml(&mut xml, table, shape_id);
+    xml.finish()
+}
+
+/// Write table XML for a slide into `xml`
+pub fn write_table_xml(xml: &mut XmlWriter, table: &Table, shape_id: usize) {
     let x = table.x;
     let y = table.y;
     let width = table.width();
     let height = table.height();
-    let mut xml = format!(
+    write!(
+        xml,
         r#"<p:graphicFrame>
 <p:nvGraphicFramePr>
 <p:cNvPr id="{shape_id}" name="Table {shape_id}"/>
@@ -30,134 +42,193 @@ pub fn generate_table_xml(table: &Table, shape_id: usize) -> String {
 
     // Add column widths
     for width in &table.column_widths {
-        xml.push_str(&format!(r#"<a:gridCol w="{width}"/>"#));
+        xml.open("a:gridCol").attr("w", width).close_empty();
     }
 
-    xml.push_str("</a:tblGrid>");
+    xml.raw("</a:tblGrid>");
 
     // Add rows
     for row in &table.rows {
-        xml.push_str(&generate_row_xml(row));
+        write_row_xml(xml, row);
     }
 
-    xml.push_str(
+    xml.raw(
         r#"</a:tbl>
 </a:graphicData>
 </a:graphic>
 </p:graphicFrame>"#
     );
-
-    xml
 }
 
-/// Generate row XML
-fn generate_row_xml(row: &TableRow) -> String {
-    let height = row.height.unwrap_or(400000);
-    
-    let mut xml = format!(r#"<a:tr h="{height}">"#);
+/// Write row XML
+fn write_row_xml(xml: &mut XmlWriter, row: &TableRow) {
+    xml.open("a:tr").attr("h", row.height.unwrap_or(400000)).close();
 
     for cell in &row.cells {
-        xml.push_str(&generate_cell_xml(cell));
+        write_cell_xml(xml, cell);
     }
 
-    xml.push_str("</a:tr>");
-    xml
+    xml.end_element("a:tr");
 }
 
-/// Generate cell XML with formatting
+/// Write cell XML with formatting
 /// Based on reference PPTX structure: txBody comes BEFORE tcPr
-fn generate_cell_xml(cell: &TableCell) -> String {
-    let mut xml = String::from("<a:tc");
+fn write_cell_xml(xml: &mut XmlWriter, cell: &TableCell) {
+    xml.open("a:tc");
     
     // Add merge attributes
     if cell.row_span > 1 {
-        xml.push_str(&format!(r#" rowSpan="{}""#, cell.row_span));
+        xml.attr("rowSpan", cell.row_span);
     }
     if cell.col_span > 1 {
-        xml.push_str(&format!(r#" gridSpan="{}""#, cell.col_span));
+        xml.attr("gridSpan", cell.col_span);
     }
     if cell.v_merge {
-        xml.push_str(r#" vMerge="1""#);
+        xml.attr("vMerge", 1);
     }
     if cell.h_merge {
-        xml.push_str(r#" hMerge="1""#);
+        xml.attr("hMerge", 1);
     }
     
-    xml.push_str(">");
+    xml.close();
 
     // === TEXT BODY (must come first!) ===
-    xml.push_str(r#"<a:txBody><a:bodyPr/><a:lstStyle/><a:p>"#);
-    
-    // Text run with simple properties (like reference PPTX)
-    xml.push_str("<a:r>");
-    
+    // One paragraph per line of the cell's text, or per bullet
+    xml.raw(r#"<a:txBody><a:bodyPr/><a:lstStyle/>"#);
+    for line in cell.text.split('\n') {
+        let (level, line) = match cell.bullet {
+            Some(_) => bullet_level(line),
+            None => (0, line),
+        };
+        xml.raw("<a:p>");
+        write_paragraph_properties(xml, cell, level);
+        xml.raw("<a:r>");
+        write_run_properties(xml, cell);
+        match cell.decimal_tab {
+            Some(_) => xml.leaf("a:t", format_args!("\t{line}")),
+            None => xml.leaf("a:t", line),
+        };
+        xml.raw("</a:r></a:p>");
+    }
+    xml.raw("</a:txBody>");
+
+    // === CELL PROPERTIES (comes after txBody) ===
+    // Table cells take their text direction from tcPr, not bodyPr
+    xml.open("a:tcPr");
+    if let Some(margins) = cell.margins {
+        xml.attr("marL", margins.left).attr("marR", margins.right).attr("marT", margins.top).attr("marB", margins.bottom);
+    }
+    if cell.text_direction != TextDirection::Horizontal {
+        xml.attr("vert", cell.text_direction.as_str());
+    }
+    if let Some(color) = &cell.background_color {
+        xml.close();
+        write_solid_fill(xml, color);
+        xml.end_element("a:tcPr");
+    } else {
+        xml.close_empty();
+    }
+
+    xml.end_element("a:tc");
+}
+
+/// Write the paragraph properties (`a:pPr`) for the alignment, bullet and decimal tab
+///
+/// Centered cells are written without `algn`, leaving PowerPoint's
+/// alignment in place as it always has been.
+fn write_paragraph_properties(xml: &mut XmlWriter, cell: &TableCell, level: u32) {
+    let align = match cell.align {
+        CellAlign::Center => None,
+        ref align => Some(align.as_str()),
+    };
+    if align.is_none() && cell.bullet.is_none() && cell.decimal_tab.is_none() {
+        return;
+    }
+    xml.open("a:pPr");
+    if let Some(align) = align {
+        xml.attr("algn", align);
+    }
+    if cell.bullet.is_some() {
+        // Hanging indent: the bullet at the level's indent, the text one step in
+        xml.attr("marL", (level + 1) * BULLET_INDENT).attr("indent", -(BULLET_INDENT as i64));
+        if level > 0 {
+            xml.attr("lvl", level);
+        }
+    }
+    if cell.bullet.is_none() && cell.decimal_tab.is_none() {
+        xml.close_empty();
+        return;
+    }
+    xml.close();
+    if let Some(bullet) = &cell.bullet {
+        xml.raw(&bullet.to_xml());
+    }
+    if let Some(position) = cell.decimal_tab {
+        xml.raw("<a:tabLst>");
+        xml.open("a:tab").attr("pos", position).attr("algn", "dec").close_empty();
+        xml.raw("</a:tabLst>");
+    }
+    xml.end_element("a:pPr");
+}
+
+/// Split a bulleted line into its level (two leading spaces each) and text
+fn bullet_level(line: &str) -> (u32, &str) {
+    let text = line.trim_start_matches(' ');
+    let level = ((line.len() - text.len()) / 2).min(8) as u32;
+    (level, text)
+}
+
+/// Write the run properties (`a:rPr`) shared by every line of a cell
+fn write_run_properties(xml: &mut XmlWriter, cell: &TableCell) {
     // Run properties - keep it simple like the reference
-    xml.push_str(r#"<a:rPr lang="en-US" dirty="0""#);
-    
+    xml.open("a:rPr").attr("lang", "en-US").attr("dirty", 0);
+
     // Add optional formatting attributes
     if cell.bold {
-        xml.push_str(r#" b="1""#);
+        xml.attr("b", 1);
     }
     if cell.italic {
-        xml.push_str(r#" i="1""#);
+        xml.attr("i", 1);
     }
     if cell.underline {
-        xml.push_str(r#" u="sng""#);
+        xml.attr("u", "sng");
     }
     if let Some(size) = cell.font_size {
-        xml.push_str(&format!(r#" sz="{}""#, size * 100));
+        xml.attr("sz", size * 100);
     }
-    
+
     // Check if we need child elements
-    let has_color = cell.text_color.is_some();
-    let has_font = cell.font_family.is_some();
-    
-    if has_color || has_font {
-        xml.push_str(">");
-        if let Some(ref color) = cell.text_color {
-            xml.push_str(&format!(r#"<a:solidFill><a:srgbClr val="{color}"/></a:solidFill>"#));
+    if cell.text_color.is_some() || cell.font_family.is_some() {
+        xml.close();
+        if let Some(color) = &cell.text_color {
+            write_solid_fill(xml, color);
         }
-        if let Some(ref font) = cell.font_family {
-            xml.push_str(&format!(r#"<a:latin typeface="{font}"/>"#));
+        if let Some(font) = &cell.font_family {
+            xml.open("a:latin").attr("typeface", font).close_empty();
         }
-        xml.push_str("</a:rPr>");
+        xml.end_element("a:rPr");
     } else {
-        xml.push_str("/>");
+        xml.close_empty();
     }
-    
-    // Text content
-    let text = escape_xml(&cell.text);
-    xml.push_str(&format!(r#"<a:t>{text}</a:t>"#));
-    
-    xml.push_str("</a:r></a:p></a:txBody>");
-
-    // === CELL PROPERTIES (comes after txBody) ===
-    if cell.background_color.is_some() {
-        let color = cell.background_color.as_ref().unwrap();
-        xml.push_str(&format!(
-            r#"<a:tcPr><a:solidFill><a:srgbClr val="{color}"/></a:solidFill></a:tcPr>"#
-        ));
-    } else {
-        xml.push_str("<a:tcPr/>");
-    }
-
-    xml.push_str("</a:tc>");
-    xml
 }
 
-/// Escape XML special characters
-fn escape_xml(s: &str) -> String {
-    s.replace("&", "&amp;")
-        .replace("<", "&lt;")
-        .replace(">", "&gt;")
-        .replace("\"", "&quot;")
-        .replace("'", "&apos;")
+/// Write `<a:solidFill>` with an sRGB color
+fn write_solid_fill(xml: &mut XmlWriter, color: &str) {
+    xml.raw("<a:solidFill>");
+    xml.open("a:srgbClr").attr("val", color).close_empty();
+    xml.raw("</a:solidFill>");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn generate_cell_xml(cell: &TableCell) -> String {
+        let mut xml = XmlWriter::new();
+        write_cell_xml(&mut xml, cell);
+        xml.finish()
+    }
+
     #[test]
     fn test_generate_simple_table_xml() {
         let table = Table::from_data(
@@ -187,6 +258,42 @@ mod tests {
         assert!(xml.contains("FF0000"));
     }
 
+    #[test]
+    fn test_generate_cell_alignment_and_decimal_tab() {
+        assert!(!generate_cell_xml(&TableCell::new("1")).contains("<a:pPr"));
+        let xml = generate_cell_xml(&TableCell::new("1,234.5").align_right());
+        assert!(xml.contains(r#"<a:pPr algn="r"/>"#));
+        let xml = generate_cell_xml(&TableCell::new("12.5").align_left().decimal_tab(1_000_000));
+        assert!(xml.contains(r#"<a:pPr algn="l"><a:tabLst><a:tab pos="1000000" algn="dec"/></a:tabLst></a:pPr>"#));
+        assert!(xml.contains("<a:t>\t12.5</a:t>"));
+    }
+
+    #[test]
+    fn test_generate_rotated_cell() {
+        assert!(generate_cell_xml(&TableCell::new("Plain")).contains("<a:tcPr/>"));
+        let xml = generate_cell_xml(&TableCell::new("Feature").vert(TextDirection::Vert270));
+        assert!(xml.contains(r#"<a:tcPr vert="vert270"/>"#));
+        let xml = generate_cell_xml(&TableCell::new("Feature").vert(TextDirection::Vert).background_color("4472C4"));
+        assert!(xml.contains(r#"<a:tcPr vert="vert"><a:solidFill>"#));
+    }
+
+    #[test]
+    fn test_generate_cell_bullets() {
+        let xml = generate_cell_xml(&TableCell::with_bullets(&["Scope", "  API", "Timeline"]));
+        assert_eq!(xml.matches("<a:p>").count(), 3);
+        assert_eq!(xml.matches(r#"<a:buChar char="•"/>"#).count(), 3);
+        assert!(xml.contains(r#"<a:pPr algn="l" marL="365760" indent="-182880" lvl="1"><a:buChar"#));
+        assert!(xml.contains("<a:t>API</a:t>"));
+    }
+
+    #[test]
+    fn test_generate_cell_margins() {
+        use crate::generator::tables::CellMargins;
+
+        let cell = TableCell::new("Dense").margins(CellMargins::new(1, 2, 3, 4));
+        assert!(generate_cell_xml(&cell).contains(r#"<a:tcPr marL="1" marR="2" marT="3" marB="4"/>"#));
+    }
+
     #[test]
     fn test_generate_cell_with_italic() {
         let cell = TableCell::new("Italic").italic();
@@ -255,10 +362,12 @@ mod tests {
 
     #[test]
     fn test_generate_cell_with_multiline() {
-        let cell = TableCell::new("Line 1\nLine 2\nLine 3");
+        let cell = TableCell::new("Line 1\nLine 2\nLine 3").bold();
         let xml = generate_cell_xml(&cell);
-        // Text content should be preserved (newlines escaped or kept)
-        assert!(xml.contains("Line 1"));
+        // Each line is its own paragraph with the cell's formatting
+        assert_eq!(xml.matches("<a:p>").count(), 3);
+        assert_eq!(xml.matches(r#"b="1""#).count(), 3);
+        assert!(xml.contains("<a:t>Line 2</a:t>"));
         // Structure should be valid
         assert!(xml.contains("<a:txBody>"));
         assert!(xml.contains("</a:txBody>"));
@@ -273,4 +382,11 @@ mod tests {
         let tcpr_pos = xml.find("<a:tcPr>").unwrap();
         assert!(txbody_pos < tcpr_pos, "txBody must come before tcPr");
     }
+
+    #[test]
+    fn test_font_family_is_escaped() {
+        let cell = TableCell::new("Text").font_family("Tom & Jerry");
+        let xml = generate_cell_xml(&cell);
+        assert!(xml.contains(r#"typeface="Tom &amp; Jerry""#));
+    }
 }
@@ -0,0 +1,160 @@
+//! Custom tags on slides and shapes
+//!
+//! Tags are name/value pairs that PowerPoint stores but never shows;
+//! automation tools use them to mark the slides and shapes they manage.
+//! Each tagged slide or shape gets its own tag list part
+//! (`ppt/tags/tagN.xml`), referenced from a `p:custDataLst` in the slide's
+//! common data or the shape's `p:nvPr`. PowerPoint upper-cases tag names,
+//! so names are compared case-insensitively.
+
+use crate::core::escape_xml_attr;
+use crate::exc::PptxError;
+use crate::oxml::xmlchemy::XmlParser;
+
+/// Relationship type of a tag list part
+pub const TAGS_RELATIONSHIP_TYPE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/tags";
+
+/// Content type of a tag list part
+pub const TAGS_CONTENT_TYPE: &str = "application/vnd.openxmlformats-officedocument.presentationml.tags+xml";
+
+/// Name/value tags of a slide or shape
+///
+/// ```
+/// use ppt_rs::generator::Tags;
+///
+/// let tags = Tags::new().with("owner", "finance").with("OWNER", "ops");
+/// assert_eq!(tags.get("Owner"), Some("ops"));
+/// assert_eq!(tags.len(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tags {
+    /// Tags in insertion order
+    pub entries: Vec<(String, String)>,
+    /// Relationship ID of the tag list part (set during package generation)
+    pub r_id: Option<String>,
+}
+
+impl Tags {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a tag, replacing any tag with the same name
+    pub fn set(&mut self, name: &str, value: &str) {
+        match self.entries.iter_mut().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.entries.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Builder form of [`Self::set`]
+    pub fn with(mut self, name: &str, value: &str) -> Self {
+        self.set(name, value);
+        self
+    }
+
+    /// Value of the tag called `name`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Remove the tag called `name`, returning its value
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self.entries.iter().position(|(n, _)| n.eq_ignore_ascii_case(name))?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Tags as (name, value) pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Read a tag list part (`p:tagLst`)
+    pub fn from_xml(xml: &str) -> Result<Self, PptxError> {
+        let root = XmlParser::parse_str(xml)?;
+        let mut tags = Tags::new();
+        for tag in root.find_all("tag") {
+            if let Some(name) = tag.attr("name") {
+                tags.set(name, tag.attr("val").unwrap_or_default());
+            }
+        }
+        Ok(tags)
+    }
+
+    /// The tag list part (`p:tagLst`)
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:tagLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">"#,
+        );
+        for (name, value) in self.iter() {
+            xml.push_str(&format!("\n<p:tag name=\"{}\" val=\"{}\"/>", escape_xml_attr(name), escape_xml_attr(value)));
+        }
+        xml.push_str("\n</p:tagLst>");
+        xml
+    }
+
+    /// `p:custDataLst` referencing the tag list part, once its relationship is assigned
+    pub fn cust_data_xml(&self) -> Option<String> {
+        let r_id = self.r_id.as_deref().filter(|_| !self.is_empty())?;
+        Some(cust_data_xml(r_id))
+    }
+}
+
+/// `p:custDataLst` referencing the tag list part `r_id`
+pub(crate) fn cust_data_xml(r_id: &str) -> String {
+    format!(r#"<p:custDataLst><p:tags r:id="{r_id}"/></p:custDataLst>"#)
+}
+
+/// Slide relationship to the tag list part `tags{number}.xml`
+pub(crate) fn tags_relationship_xml(r_id: &str, number: usize) -> String {
+    format!(r#"<Relationship Id="{r_id}" Type="{TAGS_RELATIONSHIP_TYPE}" Target="../tags/tag{number}.xml"/>"#)
+}
+
+/// Content type override for the tag list part `tags{number}.xml`
+pub(crate) fn tags_content_type_xml(number: usize) -> String {
+    format!(r#"<Override PartName="/ppt/tags/tag{number}.xml" ContentType="{TAGS_CONTENT_TYPE}"/>"#)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_round_trip_through_xml() {
+        let mut tags = Tags::new().with("owner", "finance").with("region", "R&D <EU>");
+        assert_eq!(tags.remove("REGION").as_deref(), Some("R&D <EU>"));
+        tags.set("region", "EMEA");
+
+        let xml = tags.to_xml();
+        assert!(xml.contains(r#"<p:tag name="owner" val="finance"/>"#));
+        assert_eq!(Tags::from_xml(&xml).unwrap(), tags);
+    }
+
+    #[test]
+    fn test_tag_values_keep_line_breaks() {
+        let tags = Tags::new().with("notes", "line one\nline two\ttabbed");
+        let xml = tags.to_xml();
+        assert!(xml.contains(r#"val="line one&#10;line two&#9;tabbed""#));
+        assert_eq!(Tags::from_xml(&xml).unwrap(), tags);
+    }
+
+    #[test]
+    fn test_cust_data_needs_tags_and_relationship() {
+        let mut tags = Tags::new();
+        tags.r_id = Some("rId4".to_string());
+        assert_eq!(tags.cust_data_xml(), None);
+        tags.set("owner", "finance");
+        assert_eq!(tags.cust_data_xml().unwrap(), r#"<p:custDataLst><p:tags r:id="rId4"/></p:custDataLst>"#);
+    }
+}
@@ -0,0 +1,156 @@
+//! Field codes - text PowerPoint recomputes when the file is opened
+//!
+//! A field is written as `<a:fld>` in place of a run. The text inside is
+//! only a cached value; PowerPoint replaces it with the current date/time or
+//! slide number, so a footer built from fields never shows a stale date.
+//! DrawingML has no field types for the file name or author, so those
+//! have to be written as plain text.
+
+use chrono::{DateTime, Local};
+
+/// Field GUID PowerPoint uses for slide number fields
+pub(crate) const SLIDE_NUMBER_FIELD_ID: &str = "{B6F15528-21DE-4FAA-801E-634DDDAF4B2B}";
+
+/// Field GUID used for date/time fields
+const DATE_TIME_FIELD_ID: &str = "{0C1D4C5E-2A7B-4F3D-9E61-8B5A3D27F4C0}";
+
+/// Date and time formats offered by PowerPoint's "Date and time" dialog
+///
+/// Each maps to one of the `datetime1`..`datetime13` field types; the
+/// examples are for the en-US locale.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub enum DateTimeFormat {
+    /// 10/16/2026
+    #[default]
+    ShortDate,
+    /// Friday, October 16, 2026
+    LongDate,
+    /// 16 October 2026
+    DayMonthYear,
+    /// October 16, 2026
+    MonthDayYear,
+    /// 16-Oct-26
+    DayMonthAbbrev,
+    /// October 26
+    MonthYear,
+    /// Oct-26
+    MonthAbbrevYear,
+    /// 10/16/2026 4:05 PM
+    DateTime12,
+    /// 10/16/2026 4:05:09 PM
+    DateTimeSeconds12,
+    /// 16:05
+    Time24,
+    /// 16:05:09
+    TimeSeconds24,
+    /// 4:05 PM
+    Time12,
+    /// 4:05:09 PM
+    TimeSeconds12,
+}
+
+impl DateTimeFormat {
+    /// Field type (`a:fld/@type`)
+    pub fn field_type(&self) -> &'static str {
+        match self {
+            DateTimeFormat::ShortDate => "datetime1",
+            DateTimeFormat::LongDate => "datetime2",
+            DateTimeFormat::DayMonthYear => "datetime3",
+            DateTimeFormat::MonthDayYear => "datetime4",
+            DateTimeFormat::DayMonthAbbrev => "datetime5",
+            DateTimeFormat::MonthYear => "datetime6",
+            DateTimeFormat::MonthAbbrevYear => "datetime7",
+            DateTimeFormat::DateTime12 => "datetime8",
+            DateTimeFormat::DateTimeSeconds12 => "datetime9",
+            DateTimeFormat::Time24 => "datetime10",
+            DateTimeFormat::TimeSeconds24 => "datetime11",
+            DateTimeFormat::Time12 => "datetime12",
+            DateTimeFormat::TimeSeconds12 => "datetime13",
+        }
+    }
+
+    /// `strftime` pattern producing the same text as PowerPoint (en-US)
+    fn pattern(&self) -> &'static str {
+        match self {
+            DateTimeFormat::ShortDate => "%-m/%-d/%Y",
+            DateTimeFormat::LongDate => "%A, %B %-d, %Y",
+            DateTimeFormat::DayMonthYear => "%-d %B %Y",
+            DateTimeFormat::MonthDayYear => "%B %-d, %Y",
+            DateTimeFormat::DayMonthAbbrev => "%-d-%b-%y",
+            DateTimeFormat::MonthYear => "%B %y",
+            DateTimeFormat::MonthAbbrevYear => "%b-%y",
+            DateTimeFormat::DateTime12 => "%-m/%-d/%Y %-I:%M %p",
+            DateTimeFormat::DateTimeSeconds12 => "%-m/%-d/%Y %-I:%M:%S %p",
+            DateTimeFormat::Time24 => "%-H:%M",
+            DateTimeFormat::TimeSeconds24 => "%-H:%M:%S",
+            DateTimeFormat::Time12 => "%-I:%M %p",
+            DateTimeFormat::TimeSeconds12 => "%-I:%M:%S %p",
+        }
+    }
+
+    /// Format `time` the way PowerPoint displays this field
+    pub fn format(&self, time: &DateTime<Local>) -> String {
+        time.format(self.pattern()).to_string()
+    }
+}
+
+/// A field PowerPoint fills in when the presentation is opened
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Field {
+    /// Current date and/or time
+    DateTime(DateTimeFormat),
+    /// Number of the slide the text is on
+    SlideNumber,
+}
+
+impl Field {
+    /// Field type (`a:fld/@type`)
+    pub fn field_type(&self) -> &'static str {
+        match self {
+            Field::DateTime(format) => format.field_type(),
+            Field::SlideNumber => "slidenum",
+        }
+    }
+
+    /// Field GUID (`a:fld/@id`)
+    pub fn id(&self) -> &'static str {
+        match self {
+            Field::DateTime(_) => DATE_TIME_FIELD_ID,
+            Field::SlideNumber => SLIDE_NUMBER_FIELD_ID,
+        }
+    }
+
+    /// Text cached in the file, shown by readers that don't evaluate fields
+    ///
+    /// Date/time fields cache the build time; slide numbers use PowerPoint's
+    /// `‹#›` placeholder since the slide isn't known while formatting text.
+    pub fn cached_text(&self) -> String {
+        match self {
+            Field::DateTime(format) => format.format(&Local::now()),
+            Field::SlideNumber => "‹#›".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_date_time_formats() {
+        let time = Local.with_ymd_and_hms(2026, 3, 5, 16, 7, 9).unwrap();
+        assert_eq!(DateTimeFormat::ShortDate.format(&time), "3/5/2026");
+        assert_eq!(DateTimeFormat::LongDate.format(&time), "Thursday, March 5, 2026");
+        assert_eq!(DateTimeFormat::DayMonthAbbrev.format(&time), "5-Mar-26");
+        assert_eq!(DateTimeFormat::Time24.format(&time), "16:07");
+        assert_eq!(DateTimeFormat::TimeSeconds12.format(&time), "4:07:09 PM");
+    }
+
+    #[test]
+    fn test_field_types() {
+        assert_eq!(Field::DateTime(DateTimeFormat::LongDate).field_type(), "datetime2");
+        assert_eq!(Field::DateTime(DateTimeFormat::TimeSeconds12).field_type(), "datetime13");
+        assert_eq!(Field::SlideNumber.field_type(), "slidenum");
+    }
+}
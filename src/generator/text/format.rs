@@ -1,5 +1,9 @@
 //! Text formatting options
 
+use crate::elements::ColorInput;
+use super::field::Field;
+use super::run::Run;
+
 /// Text formatting options
 #[derive(Clone, Debug, Default)]
 pub struct TextFormat {
@@ -46,14 +50,14 @@ impl TextFormat {
     }
 
     /// Set text color (RGB hex format)
-    pub fn color(mut self, hex_color: &str) -> Self {
-        self.color = Some(hex_color.trim_start_matches('#').to_uppercase());
+    pub fn color(mut self, hex_color: impl Into<ColorInput>) -> Self {
+        self.color = Some(hex_color.into().to_hex());
         self
     }
     
     /// Set highlight/background color (RGB hex format)
-    pub fn highlight(mut self, hex_color: &str) -> Self {
-        self.highlight = Some(hex_color.trim_start_matches('#').to_uppercase());
+    pub fn highlight(mut self, hex_color: impl Into<ColorInput>) -> Self {
+        self.highlight = Some(hex_color.into().to_hex());
         self
     }
 
@@ -131,6 +135,8 @@ impl TextFormat {
 pub struct FormattedText {
     pub text: String,
     pub format: TextFormat,
+    /// Field written instead of `text` (see [`Field`])
+    pub field: Option<Field>,
 }
 
 impl FormattedText {
@@ -139,6 +145,19 @@ impl FormattedText {
         FormattedText {
             text: text.to_string(),
             format: TextFormat::default(),
+            field: None,
+        }
+    }
+
+    /// Create a field PowerPoint updates on open
+    ///
+    /// `text` holds the cached value, e.g. today's date for
+    /// `FormattedText::field(Field::DateTime(DateTimeFormat::LongDate))`.
+    pub fn field(field: Field) -> Self {
+        FormattedText {
+            text: field.cached_text(),
+            format: TextFormat::default(),
+            field: Some(field),
         }
     }
 
@@ -173,13 +192,13 @@ impl FormattedText {
     }
 
     /// Builder method for color
-    pub fn color(mut self, hex_color: &str) -> Self {
+    pub fn color(mut self, hex_color: impl Into<ColorInput>) -> Self {
         self.format = self.format.color(hex_color);
         self
     }
     
     /// Builder method for highlight
-    pub fn highlight(mut self, hex_color: &str) -> Self {
+    pub fn highlight(mut self, hex_color: impl Into<ColorInput>) -> Self {
         self.format = self.format.highlight(hex_color);
         self
     }
@@ -201,6 +220,11 @@ impl FormattedText {
         self.format = self.format.superscript();
         self
     }
+
+    /// Generate the run (or field) XML
+    pub fn to_xml(&self) -> String {
+        Run::from(self.clone()).to_xml()
+    }
 }
 
 /// Generate XML color element
@@ -296,6 +320,17 @@ mod tests {
         assert_eq!(text.format.highlight, Some("FFFF00".to_string()));
     }
     
+    #[test]
+    fn test_formatted_text_field() {
+        use super::super::DateTimeFormat;
+
+        let text = FormattedText::field(Field::DateTime(DateTimeFormat::LongDate)).font_size(10);
+        let xml = text.to_xml();
+        assert!(xml.contains(r#"type="datetime2""#));
+        assert!(xml.contains(r#"sz="1000""#));
+        assert!(xml.contains(&format!("<a:t>{}</a:t></a:fld>", text.text)));
+    }
+
     #[test]
     fn test_formatted_text_subscript_superscript() {
         let sub = FormattedText::new("2").subscript();
@@ -5,16 +5,21 @@
 //! - `run` - A run of text with consistent formatting
 //! - `paragraph` - A paragraph with alignment and spacing
 //! - `frame` - Container for text content
+//! - `field` - Field codes (date/time, slide number) PowerPoint fills in
 
 mod format;
 mod run;
 mod paragraph;
 mod frame;
+mod field;
 
 pub use format::{TextFormat, FormattedText, color_to_xml};
 pub use run::Run;
 pub use paragraph::Paragraph;
 pub use frame::TextFrame;
+pub use field::{Field, DateTimeFormat};
+pub(crate) use field::SLIDE_NUMBER_FIELD_ID;
+pub(crate) use crate::core::escape_xml;
 
 /// Text alignment options
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
@@ -58,16 +63,6 @@ impl TextAnchor {
     }
 }
 
-/// Escape XML special characters
-pub(crate) fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
-
 #[cfg(test)]
 mod tests {
     use super::*;
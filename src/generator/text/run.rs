@@ -1,13 +1,18 @@
 //! Text run - a span of text with consistent formatting
 
-use super::format::TextFormat;
+use super::format::{TextFormat, FormattedText};
+use super::field::Field;
 use super::escape_xml;
+use crate::core::escape_xml_attr;
+use crate::elements::ColorInput;
 
 /// A run of text with consistent formatting
 #[derive(Clone, Debug)]
 pub struct Run {
     pub text: String,
     pub format: TextFormat,
+    /// Field written instead of `text` (see [`Field`])
+    pub field: Option<Field>,
 }
 
 impl Run {
@@ -16,6 +21,16 @@ impl Run {
         Run {
             text: text.to_string(),
             format: TextFormat::default(),
+            field: None,
+        }
+    }
+
+    /// Create a run holding a field, e.g. `Run::field(Field::SlideNumber)`
+    pub fn field(field: Field) -> Self {
+        Run {
+            text: field.cached_text(),
+            format: TextFormat::default(),
+            field: Some(field),
         }
     }
 
@@ -44,8 +59,8 @@ impl Run {
     }
 
     /// Set color
-    pub fn color(mut self, hex: &str) -> Self {
-        self.format.color = Some(hex.trim_start_matches('#').to_uppercase());
+    pub fn color(mut self, hex: impl Into<ColorInput>) -> Self {
+        self.format.color = Some(hex.into().to_hex());
         self
     }
 
@@ -73,13 +88,30 @@ impl Run {
             .unwrap_or_default();
         
         let font_xml = self.format.font_family.as_ref()
-            .map(|f| format!(r#"<a:latin typeface="{}"/>"#, escape_xml(f)))
+            .map(|f| format!(r#"<a:latin typeface="{}"/>"#, escape_xml_attr(f)))
             .unwrap_or_default();
 
-        format!(
-            r#"<a:r><a:rPr lang="en-US" sz="{}" b="{}" i="{}"{} dirty="0">{}{}</a:rPr><a:t>{}</a:t></a:r>"#,
-            size, bold, italic, underline, color_xml, font_xml, escape_xml(&self.text)
-        )
+        let rpr = format!(
+            r#"<a:rPr lang="en-US" sz="{}" b="{}" i="{}"{} dirty="0">{}{}</a:rPr>"#,
+            size, bold, italic, underline, color_xml, font_xml
+        );
+        match &self.field {
+            Some(field) => format!(
+                r#"<a:fld id="{}" type="{}">{}<a:t>{}</a:t></a:fld>"#,
+                field.id(), field.field_type(), rpr, escape_xml(&self.text)
+            ),
+            None => format!("<a:r>{}<a:t>{}</a:t></a:r>", rpr, escape_xml(&self.text)),
+        }
+    }
+}
+
+impl From<FormattedText> for Run {
+    fn from(text: FormattedText) -> Self {
+        Run {
+            text: text.text,
+            format: text.format,
+            field: text.field,
+        }
     }
 }
 
@@ -105,4 +137,13 @@ mod tests {
         
         assert!(xml.contains("typeface=\"Arial\""));
     }
+
+    #[test]
+    fn test_field_run() {
+        let xml = Run::field(Field::SlideNumber).size(12).to_xml();
+
+        assert!(xml.starts_with(r#"<a:fld id="{B6F15528-21DE-4FAA-801E-634DDDAF4B2B}" type="slidenum"><a:rPr lang="en-US" sz="1200""#));
+        assert!(xml.ends_with("<a:t>‹#›</a:t></a:fld>"));
+        assert!(!xml.contains("<a:r>"));
+    }
 }
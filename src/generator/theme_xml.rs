@@ -1,5 +1,7 @@
 //! Theme, master, and layout XML generation
 
+use crate::core::escape_xml_attr;
+
 /// Create slide layout XML
 pub fn create_slide_layout_xml() -> String {
     r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -79,7 +81,36 @@ pub fn create_master_rels_xml() -> String {
 
 /// Create theme XML
 pub fn create_theme_xml() -> String {
-    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+    create_theme_xml_with_fonts(&ThemeFonts::default())
+}
+
+/// Heading and body fonts of the theme's font scheme
+///
+/// Text that doesn't set a font follows these (`+mj-lt` for titles,
+/// `+mn-lt` for body text), so changing them restyles a whole deck.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThemeFonts {
+    /// Major (heading) font
+    pub heading: String,
+    /// Minor (body) font
+    pub body: String,
+}
+
+impl Default for ThemeFonts {
+    fn default() -> Self {
+        ThemeFonts::new("Calibri", "Calibri")
+    }
+}
+
+impl ThemeFonts {
+    pub fn new(heading: &str, body: &str) -> Self {
+        ThemeFonts { heading: heading.to_string(), body: body.to_string() }
+    }
+}
+
+/// Create theme XML with the given heading and body fonts
+pub fn create_theme_xml_with_fonts(fonts: &ThemeFonts) -> String {
+    format!(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="Office Theme">
 <a:themeElements>
 <a:clrScheme name="Office">
@@ -98,12 +129,12 @@ pub fn create_theme_xml() -> String {
 </a:clrScheme>
 <a:fontScheme name="Office">
 <a:majorFont>
-<a:latin typeface="Calibri"/>
+<a:latin typeface="{}"/>
 <a:ea typeface=""/>
 <a:cs typeface=""/>
 </a:majorFont>
 <a:minorFont>
-<a:latin typeface="Calibri"/>
+<a:latin typeface="{}"/>
 <a:ea typeface=""/>
 <a:cs typeface=""/>
 </a:minorFont>
@@ -133,5 +164,8 @@ pub fn create_theme_xml() -> String {
 </a:themeElements>
 <a:objectDefaults/>
 <a:extraClrSchemeLst/>
-</a:theme>"#.to_string()
+</a:theme>"#,
+        escape_xml_attr(&fonts.heading),
+        escape_xml_attr(&fonts.body),
+    )
 }
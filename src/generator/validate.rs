@@ -0,0 +1,370 @@
+//! Validation of slide content before it is written
+//!
+//! Builders accept out-of-range input (a transparency of 150%, a series
+//! with more values than categories) and would otherwise clamp or drop it
+//! silently. `create_pptx_with_content` and `Presentation::build` validate
+//! every slide first and fail with a [`ValidationError`] naming the slide,
+//! element and problem; `BuildProgress::skip_validation` opts out.
+//!
+//! [`validate_package`] checks the written package instead: every XML part
+//! parses, every part has a content type, every relationship target and
+//! `r:id` reference resolves, and shape IDs are unique per slide. These are
+//! the faults that make PowerPoint offer to repair a file.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::{Cursor, Read};
+
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::opc::packuri::{rels_source_dir, resolve_part_path};
+
+use crate::core::Emu;
+
+/// Largest coordinate PowerPoint accepts (`ST_Coordinate`), about 746 km
+pub const MAX_COORDINATE: Emu = Emu(27_273_042_316_900);
+
+/// Invalid value found by a `validate()` method
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Where the value is, e.g. `slide 2, chart 1` (empty for the element itself)
+    pub location: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl ValidationError {
+    /// Error for the element being validated
+    pub fn new(message: impl Into<String>) -> Self {
+        ValidationError { location: String::new(), message: message.into() }
+    }
+
+    /// Prefix the location with the enclosing element, e.g. `chart 1`
+    pub fn within(mut self, outer: impl Into<String>) -> Self {
+        let outer = outer.into();
+        self.location = if self.location.is_empty() {
+            outer
+        } else {
+            format!("{outer}, {}", self.location)
+        };
+        self
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.location, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for crate::exc::PptxError {
+    fn from(e: ValidationError) -> Self {
+        crate::exc::PptxError::Validation { location: e.location, message: e.message }
+    }
+}
+
+/// Fail unless `color` is a 6-digit RGB hex value
+pub(crate) fn check_color(what: &str, color: &str) -> Result<(), ValidationError> {
+    if color.len() == 6 && color.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(format!("{what} \"{color}\" is not a 6-digit hex color")))
+    }
+}
+
+/// Fail unless a position/size lies within PowerPoint's coordinate range
+pub(crate) fn check_geometry(x: Emu, y: Emu, width: Emu, height: Emu) -> Result<(), ValidationError> {
+    if width < Emu::ZERO || height < Emu::ZERO {
+        return Err(ValidationError::new(format!("size {width}x{height} EMU is negative")));
+    }
+    let out_of_range = |v: Emu| v > MAX_COORDINATE || v < -MAX_COORDINATE;
+    if [x, y, width, height].into_iter().any(out_of_range) {
+        return Err(ValidationError::new(format!(
+            "bounds ({x}, {y}, {width}, {height}) EMU exceed the slide coordinate range"
+        )));
+    }
+    Ok(())
+}
+
+/// Namespace of `r:id`, `r:embed` and other relationship references
+const RELATIONSHIPS_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const MARKUP_COMPATIBILITY_NS: &str = "http://schemas.openxmlformats.org/markup-compatibility/2006";
+
+/// Parts every presentation package needs
+const REQUIRED_PARTS: [&str; 4] = [
+    "[Content_Types].xml",
+    "_rels/.rels",
+    "ppt/presentation.xml",
+    "ppt/_rels/presentation.xml.rels",
+];
+
+/// Strictly check a PPTX package
+///
+/// The error's location names the offending part, e.g. `ppt/slides/slide2.xml`.
+///
+/// ```
+/// use ppt_rs::{Presentation, SlideContent};
+/// use ppt_rs::generator::validate::validate_package;
+///
+/// let data = Presentation::with_title("Deck").add_slide(SlideContent::new("Hi")).build().unwrap();
+/// assert!(validate_package(&data).is_ok());
+/// assert!(validate_package(b"not a zip").is_err());
+/// ```
+pub fn validate_package(data: &[u8]) -> Result<(), ValidationError> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| ValidationError::new(format!("not a ZIP archive: {e}")))?;
+    let mut parts = BTreeMap::new();
+    for i in 0..archive.len() {
+        let mut file = archive.by_index(i).map_err(|e| ValidationError::new(format!("unreadable entry: {e}")))?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).map_err(|e| ValidationError::new(e.to_string()).within(name.as_str()))?;
+        if parts.insert(name.clone(), content).is_some() {
+            return Err(ValidationError::new("duplicate ZIP entry").within(name));
+        }
+    }
+    for required in REQUIRED_PARTS {
+        if !parts.contains_key(required) {
+            return Err(ValidationError::new("required part is missing").within(required));
+        }
+    }
+
+    let mut parsed = BTreeMap::new();
+    for (name, content) in &parts {
+        if name.ends_with(".xml") || name.ends_with(".rels") {
+            let elements = parse_part(content).map_err(|e| e.within(name.as_str()))?;
+            parsed.insert(name.as_str(), elements);
+        }
+    }
+
+    check_content_types(&parsed["[Content_Types].xml"], &parts)?;
+    for (name, elements) in &parsed {
+        if name.ends_with(".rels") {
+            continue;
+        }
+        let rels_name = rels_part_name(name);
+        let ids = match parsed.get(rels_name.as_str()) {
+            Some(rels) => check_relationships(&rels_name, rels, &parts)?,
+            None => BTreeSet::new(),
+        };
+        check_references(elements, &ids).map_err(|e| e.within(*name))?;
+        if name.starts_with("ppt/slides/slide") {
+            check_shape_ids(elements).map_err(|e| e.within(*name))?;
+        }
+    }
+    if let Some(rels) = parsed.get("_rels/.rels") {
+        check_relationships("_rels/.rels", rels, &parts)?;
+    }
+    Ok(())
+}
+
+/// Start tag of a parsed part: local name and `(namespace, local name, value)` attributes
+struct Element {
+    name: String,
+    attributes: Vec<(Option<String>, String, String)>,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.iter()
+            .find(|(ns, local, _)| ns.is_none() && local == name)
+            .map(|(_, _, value)| value.as_str())
+    }
+}
+
+/// Start tags of a well-formed XML part, in document order
+///
+/// Elements inside `mc:Fallback` are skipped: a consumer reads either the
+/// `mc:Choice` or the fallback, so the two may repeat shape IDs.
+fn parse_part(content: &[u8]) -> Result<Vec<Element>, ValidationError> {
+    let mut elements = Vec::new();
+    let mut fallback_depth = 0;
+    for event in EventReader::new(content) {
+        match event {
+            Ok(XmlEvent::StartElement { name, .. }) if fallback_depth > 0 || is_fallback(&name) => {
+                fallback_depth += 1;
+            }
+            Ok(XmlEvent::EndElement { .. }) if fallback_depth > 0 => fallback_depth -= 1,
+            Ok(XmlEvent::StartElement { name, attributes, .. }) => elements.push(Element {
+                name: name.local_name,
+                attributes: attributes.into_iter()
+                    .map(|a| (a.name.namespace, a.name.local_name, a.value))
+                    .collect(),
+            }),
+            Ok(_) => {}
+            Err(e) => return Err(ValidationError::new(format!("malformed XML: {e}"))),
+        }
+    }
+    Ok(elements)
+}
+
+fn is_fallback(name: &xml::name::OwnedName) -> bool {
+    name.local_name == "Fallback" && name.namespace.as_deref() == Some(MARKUP_COMPATIBILITY_NS)
+}
+
+/// Every part has a `Default` or `Override` content type and every override names a part
+fn check_content_types(types: &[Element], parts: &BTreeMap<String, Vec<u8>>) -> Result<(), ValidationError> {
+    let location = "[Content_Types].xml";
+    let mut defaults = BTreeSet::new();
+    let mut overrides = BTreeSet::new();
+    for element in types {
+        match element.name.as_str() {
+            "Default" => {
+                defaults.insert(element.attribute("Extension").unwrap_or_default().to_lowercase());
+            }
+            "Override" => {
+                let part = element.attribute("PartName").unwrap_or_default().trim_start_matches('/');
+                if !parts.contains_key(part) {
+                    return Err(ValidationError::new(format!("override for missing part /{part}")).within(location));
+                }
+                overrides.insert(part.to_string());
+            }
+            _ => {}
+        }
+    }
+    for name in parts.keys().filter(|name| name.as_str() != location) {
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase()).unwrap_or_default();
+        if !overrides.contains(name) && !defaults.contains(&extension) {
+            return Err(ValidationError::new(format!("no content type for /{name}")).within(location));
+        }
+    }
+    Ok(())
+}
+
+/// Relationship IDs of a `.rels` part, after checking they are unique and
+/// that internal targets exist
+fn check_relationships(
+    rels_name: &str,
+    rels: &[Element],
+    parts: &BTreeMap<String, Vec<u8>>,
+) -> Result<BTreeSet<String>, ValidationError> {
+    let base = rels_source_dir(rels_name);
+    let mut ids = BTreeSet::new();
+    for rel in rels.iter().filter(|e| e.name == "Relationship") {
+        let id = rel.attribute("Id").unwrap_or_default();
+        if !ids.insert(id.to_string()) {
+            return Err(ValidationError::new(format!("duplicate relationship ID {id}")).within(rels_name));
+        }
+        if rel.attribute("TargetMode") == Some("External") {
+            continue;
+        }
+        let target = rel.attribute("Target").unwrap_or_default();
+        let resolved = resolve_part_path(base, target);
+        if !parts.contains_key(&resolved) {
+            return Err(ValidationError::new(format!("{id} targets missing part /{resolved}")).within(rels_name));
+        }
+    }
+    Ok(ids)
+}
+
+/// Every `r:*` attribute names a relationship of the part
+fn check_references(elements: &[Element], ids: &BTreeSet<String>) -> Result<(), ValidationError> {
+    for element in elements {
+        for (ns, local, value) in &element.attributes {
+            if ns.as_deref() == Some(RELATIONSHIPS_NS) && !value.is_empty() && !ids.contains(value) {
+                return Err(ValidationError::new(format!(
+                    "<{}> r:{local}=\"{value}\" has no relationship",
+                    element.name
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Shape IDs (`cNvPr/@id`) are unique within a slide
+fn check_shape_ids(elements: &[Element]) -> Result<(), ValidationError> {
+    let mut seen = BTreeSet::new();
+    for element in elements.iter().filter(|e| e.name == "cNvPr") {
+        let id = element.attribute("id").unwrap_or_default();
+        if !seen.insert(id) {
+            return Err(ValidationError::new(format!("shape ID {id} is used twice")));
+        }
+    }
+    Ok(())
+}
+
+/// `dir/_rels/file.rels` for the part `dir/file`
+fn rels_part_name(part: &str) -> String {
+    match part.rsplit_once('/') {
+        Some((dir, file)) => format!("{dir}/_rels/{file}.rels"),
+        None => format!("_rels/{part}.rels"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_error_location() {
+        let err = ValidationError::new("bad").within("chart 1").within("slide 2");
+        assert_eq!(err.to_string(), "slide 2, chart 1: bad");
+        assert_eq!(ValidationError::new("bad").to_string(), "bad");
+    }
+
+    #[test]
+    fn test_check_color_and_geometry() {
+        assert!(check_color("fill", "1F4E79").is_ok());
+        assert!(check_color("fill", "blue").is_err());
+        assert!(check_geometry(Emu(-10), Emu(0), Emu(5), Emu(5)).is_ok());
+        assert!(check_geometry(Emu(0), Emu(0), Emu(-5), Emu(5)).is_err());
+        assert!(check_geometry(Emu(i64::MAX), Emu(0), Emu(5), Emu(5)).is_err());
+    }
+
+    #[test]
+    fn test_rels_part_name() {
+        assert_eq!(rels_part_name("ppt/slides/slide1.xml"), "ppt/slides/_rels/slide1.xml.rels");
+        assert_eq!(rels_part_name("[Content_Types].xml"), "_rels/[Content_Types].xml.rels");
+    }
+
+    #[test]
+    fn test_shape_ids_ignore_fallback() {
+        let xml = br#"<p:sld xmlns:p="p" xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006">
+<mc:AlternateContent><mc:Choice Requires="x"><p:cNvPr id="4"/></mc:Choice>
+<mc:Fallback><p:pic><p:cNvPr id="4"/></p:pic></mc:Fallback></mc:AlternateContent>
+<p:cNvPr id="5"/></p:sld>"#;
+        let elements = parse_part(xml).unwrap();
+        assert!(check_shape_ids(&elements).is_ok());
+        assert_eq!(elements.iter().filter(|e| e.name == "cNvPr").count(), 2);
+    }
+
+    #[test]
+    fn test_validate_package_reports_dangling_reference() {
+        use std::io::Write;
+
+        let data = crate::api::Presentation::with_title("Deck")
+            .add_slide(crate::generator::SlideContent::new("One"))
+            .build()
+            .unwrap();
+        validate_package(&data).unwrap();
+
+        // Rewrite slide 1 to point at a relationship that does not exist
+        let mut archive = zip::ZipArchive::new(Cursor::new(&data[..])).unwrap();
+        let mut out = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).unwrap();
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).unwrap();
+            if file.name() == "ppt/slides/slide1.xml" {
+                let xml = String::from_utf8(content).unwrap();
+                content = xml.replacen("</p:cSld>", "</p:cSld><p:custDataLst><p:custData r:id=\"rId99\"/></p:custDataLst>", 1).into_bytes();
+            }
+            out.start_file(file.name(), zip::write::FileOptions::default()).unwrap();
+            out.write_all(&content).unwrap();
+        }
+        let broken = out.finish().unwrap().into_inner();
+        let err = validate_package(&broken).unwrap_err();
+        assert_eq!(err.location, "ppt/slides/slide1.xml");
+        assert!(err.message.contains("rId99"), "{err}");
+    }
+}
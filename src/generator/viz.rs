@@ -0,0 +1,435 @@
+//! Compact JSON visualization specs
+//!
+//! Services can describe a chart or table as JSON — a small subset of the
+//! Vega-Lite vocabulary — instead of constructing Rust objects:
+//!
+//! ```json
+//! {
+//!   "type": "bar",
+//!   "title": "Revenue",
+//!   "data": [
+//!     {"quarter": "Q1", "revenue": 100, "cost": 80},
+//!     {"quarter": "Q2", "revenue": 150, "cost": 90}
+//!   ],
+//!   "encoding": {"x": "quarter", "y": ["revenue", {"field": "cost", "title": "Cost"}]},
+//!   "colors": ["#1F4E79", "teal"]
+//! }
+//! ```
+//!
+//! `type` is a [`ChartType`] name (`bar`, `lineMarkers`, `pie`, ...) or
+//! `table`. Charts take categories from `x` and one series per `y` field;
+//! with a `color` field the records are pivoted instead — one series per
+//! distinct `color` value, summing `y` per category. Tables show
+//! `encoding.columns` (default: every field). [`json_schema`] describes the
+//! format for clients.
+
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::exc::{PptxError, Result};
+use crate::generator::charts::{Chart, ChartBuilder, ChartSeries, ChartType};
+use crate::generator::slide_content::SlideContent;
+use crate::generator::tables::Table;
+
+/// Visualization described by a JSON spec
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VizSpec {
+    /// Chart type name or `table`
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub title: String,
+    /// Data records
+    pub data: Vec<Record>,
+    #[serde(default)]
+    pub encoding: Encoding,
+    /// Series colors (cycled), or header background and text for tables
+    #[serde(default)]
+    pub colors: Vec<String>,
+}
+
+/// Which fields feed which part of the visualization
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Encoding {
+    /// Category field
+    #[serde(default)]
+    pub x: Option<FieldDef>,
+    /// Value field(s), one series each
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub y: Vec<FieldDef>,
+    /// Field whose distinct values become series
+    #[serde(default)]
+    pub color: Option<FieldDef>,
+    /// Table columns
+    #[serde(default)]
+    pub columns: Vec<FieldDef>,
+}
+
+/// Field reference, written as `"revenue"` or `{"field": "revenue", "title": "Revenue"}`
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(from = "FieldRepr")]
+pub struct FieldDef {
+    pub field: String,
+    /// Display name (series name, column header); the field name if unset
+    pub title: Option<String>,
+}
+
+impl FieldDef {
+    /// Display name
+    pub fn label(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.field)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FieldRepr {
+    Name(String),
+    Full { field: String, #[serde(default)] title: Option<String> },
+}
+
+impl From<FieldRepr> for FieldDef {
+    fn from(repr: FieldRepr) -> Self {
+        match repr {
+            FieldRepr::Name(field) => FieldDef { field, title: None },
+            FieldRepr::Full { field, title } => FieldDef { field, title },
+        }
+    }
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<FieldDef>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(FieldDef),
+        Many(Vec<FieldDef>),
+    }
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(field) => vec![field],
+        OneOrMany::Many(fields) => fields,
+    })
+}
+
+/// Data record, keeping its fields in document order
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Record(pub Vec<(String, Value)>);
+
+impl Record {
+    /// Value of a field
+    pub fn get(&self, field: &str) -> Option<&Value> {
+        self.0.iter().find(|(name, _)| name == field).map(|(_, value)| value)
+    }
+
+    /// Field as display text; missing and null fields are empty
+    fn text(&self, field: &str) -> String {
+        match self.get(field) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+        }
+    }
+
+    /// Field as a number (numbers or numeric strings)
+    fn number(&self, field: &str, index: usize) -> Result<f64> {
+        let value = self.get(field);
+        value
+            .and_then(|v| v.as_f64().or_else(|| v.as_str().and_then(|s| s.trim().parse().ok())))
+            .ok_or_else(|| {
+                PptxError::InvalidValue(format!(
+                    "record {}: field \"{field}\" is {}, not a number",
+                    index + 1,
+                    value.map_or("missing".to_string(), Value::to_string)
+                ))
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct RecordVisitor;
+
+        impl<'de> Visitor<'de> for RecordVisitor {
+            type Value = Record;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a data record object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<Record, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(entry) = map.next_entry::<String, Value>()? {
+                    fields.push(entry);
+                }
+                Ok(Record(fields))
+            }
+        }
+
+        deserializer.deserialize_map(RecordVisitor)
+    }
+}
+
+/// Chart or table built from a [`VizSpec`]
+#[derive(Clone, Debug)]
+pub enum Visualization {
+    Chart(Box<Chart>),
+    Table(Table),
+}
+
+impl VizSpec {
+    /// Parse a spec from JSON
+    pub fn from_json(json: &str) -> Result<VizSpec> {
+        serde_json::from_str(json).map_err(|e| PptxError::InvalidValue(format!("invalid visualization spec: {e}")))
+    }
+
+    /// Build the chart or table the spec describes
+    pub fn build(&self) -> Result<Visualization> {
+        if self.kind == "table" {
+            return self.build_table().map(Visualization::Table);
+        }
+        let chart_type = ChartType::from_name(&self.kind).ok_or_else(|| {
+            PptxError::InvalidValue(format!("unknown visualization type \"{}\"", self.kind))
+        })?;
+        self.build_chart(chart_type).map(|chart| Visualization::Chart(Box::new(chart)))
+    }
+
+    fn build_chart(&self, chart_type: ChartType) -> Result<Chart> {
+        let x = self.encoding.x.as_ref().ok_or_else(|| missing("encoding.x"))?;
+        if self.encoding.y.is_empty() {
+            return Err(missing("encoding.y"));
+        }
+
+        let (categories, series) = match &self.encoding.color {
+            Some(color) => self.pivot(x, color)?,
+            None => {
+                let categories = self.data.iter().map(|r| r.text(&x.field)).collect();
+                let series = self
+                    .encoding
+                    .y
+                    .iter()
+                    .map(|y| {
+                        let values = self.data.iter().enumerate().map(|(i, r)| r.number(&y.field, i)).collect::<Result<_>>()?;
+                        Ok(ChartSeries::new(y.label(), values))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                (categories, series)
+            }
+        };
+
+        let mut builder = ChartBuilder::new(&self.title, chart_type)
+            .categories(categories.iter().map(String::as_str).collect());
+        for (i, series) in series.into_iter().enumerate() {
+            builder = builder.add_series(match self.colors.get(i % self.colors.len().max(1)) {
+                Some(color) => series.with_color(color),
+                None => series,
+            });
+        }
+        Ok(builder.build())
+    }
+
+    /// One series per distinct `color` value, summing `y` per category
+    fn pivot(&self, x: &FieldDef, color: &FieldDef) -> Result<(Vec<String>, Vec<ChartSeries>)> {
+        let [y] = self.encoding.y.as_slice() else {
+            return Err(PptxError::InvalidValue("encoding.color needs exactly one encoding.y field".to_string()));
+        };
+        let mut categories: Vec<String> = Vec::new();
+        let mut names: Vec<String> = Vec::new();
+        let mut cells: Vec<(usize, usize, f64)> = Vec::new();
+        for (i, record) in self.data.iter().enumerate() {
+            let index_of = |list: &mut Vec<String>, value: String| {
+                list.iter().position(|v| *v == value).unwrap_or_else(|| {
+                    list.push(value);
+                    list.len() - 1
+                })
+            };
+            let category = index_of(&mut categories, record.text(&x.field));
+            let series = index_of(&mut names, record.text(&color.field));
+            cells.push((series, category, record.number(&y.field, i)?));
+        }
+
+        let mut values = vec![vec![0.0; categories.len()]; names.len()];
+        for (series, category, value) in cells {
+            values[series][category] += value;
+        }
+        let series = names.iter().zip(values).map(|(name, values)| ChartSeries::new(name, values)).collect();
+        Ok((categories, series))
+    }
+
+    fn build_table(&self) -> Result<Table> {
+        let columns = if self.encoding.columns.is_empty() {
+            let mut fields: Vec<FieldDef> = Vec::new();
+            for (name, _) in self.data.iter().flat_map(|r| &r.0) {
+                if !fields.iter().any(|f| f.field == *name) {
+                    fields.push(FieldDef { field: name.clone(), title: None });
+                }
+            }
+            fields
+        } else {
+            self.encoding.columns.clone()
+        };
+
+        let headers: Vec<String> = columns.iter().map(|c| c.label().to_string()).collect();
+        let rows: Vec<Vec<String>> = self
+            .data
+            .iter()
+            .map(|r| columns.iter().map(|c| r.text(&c.field)).collect())
+            .collect();
+        let mut table = Table::from_records(&headers, &rows)?;
+        if let Some(header) = table.rows.first_mut() {
+            for cell in &mut header.cells {
+                if let Some(background) = self.colors.first() {
+                    *cell = cell.clone().background_color(background);
+                }
+                if let Some(text) = self.colors.get(1) {
+                    *cell = cell.clone().text_color(text);
+                }
+            }
+        }
+        Ok(table)
+    }
+}
+
+fn missing(what: &str) -> PptxError {
+    PptxError::InvalidValue(format!("visualization spec needs {what}"))
+}
+
+/// JSON Schema (draft 2020-12) of the spec format
+pub fn json_schema() -> Value {
+    let mut types: Vec<&str> = ChartType::ALL.iter().map(|t| t.as_str()).collect();
+    types.push("table");
+    let field = serde_json::json!({
+        "oneOf": [
+            {"type": "string"},
+            {
+                "type": "object",
+                "properties": {"field": {"type": "string"}, "title": {"type": "string"}},
+                "required": ["field"],
+            },
+        ],
+    });
+    serde_json::json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "ppt-rs visualization spec",
+        "type": "object",
+        "properties": {
+            "type": {"enum": types},
+            "title": {"type": "string"},
+            "data": {"type": "array", "items": {"type": "object"}},
+            "encoding": {
+                "type": "object",
+                "properties": {
+                    "x": field,
+                    "y": {"oneOf": [field, {"type": "array", "items": field}]},
+                    "color": field,
+                    "columns": {"type": "array", "items": field},
+                },
+                "additionalProperties": false,
+            },
+            "colors": {"type": "array", "items": {"type": "string"}},
+        },
+        "required": ["type", "data"],
+        "additionalProperties": false,
+    })
+}
+
+impl SlideContent {
+    /// Add a chart or table built from a [`VizSpec`]
+    pub fn add_visualization(self, visualization: Visualization) -> Self {
+        match visualization {
+            Visualization::Chart(chart) => self.add_chart(*chart),
+            Visualization::Table(table) => self.table(table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REVENUE: &str = r##"{
+        "type": "bar",
+        "title": "Revenue",
+        "data": [
+            {"quarter": "Q1", "revenue": 100, "cost": "80"},
+            {"quarter": "Q2", "revenue": 150.5, "cost": 90}
+        ],
+        "encoding": {"x": "quarter", "y": ["revenue", {"field": "cost", "title": "Cost"}]},
+        "colors": ["#1F4E79"]
+    }"##;
+
+    fn chart(json: &str) -> Chart {
+        match VizSpec::from_json(json).unwrap().build().unwrap() {
+            Visualization::Chart(chart) => *chart,
+            Visualization::Table(_) => panic!("expected a chart"),
+        }
+    }
+
+    #[test]
+    fn test_chart_spec() {
+        let chart = chart(REVENUE);
+        assert_eq!((chart.title.as_str(), chart.chart_type), ("Revenue", ChartType::Bar));
+        assert_eq!(chart.categories, ["Q1", "Q2"]);
+        assert_eq!(chart.series[0].name, "revenue");
+        assert_eq!(chart.series[1].name, "Cost");
+        assert_eq!(chart.series[1].values, [80.0, 90.0]);
+        assert_eq!(chart.series[1].color.as_deref(), Some("1F4E79"));
+        assert!(chart.validate().is_ok());
+    }
+
+    #[test]
+    fn test_color_pivot() {
+        let chart = chart(r#"{
+            "type": "lineMarkers",
+            "data": [
+                {"month": "Jan", "region": "North", "sales": 10},
+                {"month": "Jan", "region": "South", "sales": 5},
+                {"month": "Feb", "region": "North", "sales": 12},
+                {"month": "Feb", "region": "North", "sales": 3}
+            ],
+            "encoding": {"x": "month", "y": "sales", "color": "region"}
+        }"#);
+        assert_eq!(chart.categories, ["Jan", "Feb"]);
+        assert_eq!(chart.series[0].name, "North");
+        assert_eq!(chart.series[0].values, [10.0, 15.0]);
+        assert_eq!(chart.series[1].values, [5.0, 0.0]);
+    }
+
+    #[test]
+    fn test_table_spec_keeps_field_order() {
+        let spec = VizSpec::from_json(r#"{
+            "type": "table",
+            "data": [{"name": "Ada", "score": 9.5}, {"name": "Grace", "score": 8, "team": null}],
+            "colors": ["1F4E79", "FFFFFF"]
+        }"#).unwrap();
+        let Visualization::Table(table) = spec.build().unwrap() else { panic!("expected a table") };
+        let header: Vec<&str> = table.rows[0].cells.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(header, ["name", "score", "team"]);
+        assert_eq!(table.rows[1].cells[1].text, "9.5");
+        assert_eq!(table.rows[2].cells[2].text, "");
+        assert_eq!(table.rows[0].cells[0].background_color.as_deref(), Some("1F4E79"));
+
+        let slide = SlideContent::new("Scores").add_visualization(Visualization::Table(table));
+        assert!(slide.has_table);
+    }
+
+    #[test]
+    fn test_spec_errors() {
+        assert!(VizSpec::from_json(r#"{"type": "bar", "data": [], "encodng": {}}"#).is_err());
+        let unknown = VizSpec::from_json(r#"{"type": "histogram", "data": []}"#).unwrap();
+        assert!(unknown.build().is_err());
+        let no_y = VizSpec::from_json(r#"{"type": "bar", "data": [], "encoding": {"x": "a"}}"#).unwrap();
+        assert!(no_y.build().is_err());
+        let text = VizSpec::from_json(r#"{"type": "pie", "data": [{"a": "x", "b": "n/a"}], "encoding": {"x": "a", "y": "b"}}"#).unwrap();
+        assert!(text.build().unwrap_err().to_string().contains("record 1: field \"b\""));
+    }
+
+    #[test]
+    fn test_json_schema_lists_types() {
+        let schema = json_schema();
+        let types = schema["properties"]["type"]["enum"].as_array().unwrap();
+        assert!(types.contains(&Value::from("stockOHLC")));
+        assert!(types.contains(&Value::from("table")));
+    }
+}
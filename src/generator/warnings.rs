@@ -0,0 +1,368 @@
+//! Non-fatal generation warnings
+//!
+//! Some problems do not stop a build but are worth telling the author
+//! about: body text that will likely spill out of its box, an image whose
+//! file is missing, a markdown construct that has no slide equivalent.
+//! [`Presentation::build_with_warnings`](crate::api::Presentation::build_with_warnings)
+//! returns them in a [`GenerationOutput`] next to the package bytes.
+//!
+//! Overflow is estimated from character counts and font sizes, so it is a
+//! hint rather than a measurement. Elements that reach off the slide are
+//! reported by [`check_bounds`] and left where they are.
+
+use std::fmt;
+
+use crate::core::{ElementSized, Emu, Positioned};
+use super::placement::SlideSize;
+use super::shapes_xml::text_fits;
+use super::slide_content::{PicturePlaceholder, SlideContent, SlideLayout};
+
+/// Average glyph width as a fraction of the font size
+const CHAR_WIDTH_EM: f64 = 0.5;
+/// Line height as a multiple of the font size
+const LINE_SPACING: f64 = 1.2;
+/// Width lost to the bullet and indent of each list level, in inches
+const INDENT_INCHES: f64 = 0.5;
+
+/// What a [`Warning`] is about
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// Text is likely to overflow `element` (e.g. `body text`, `shape "Note"`)
+    TextOverflow { element: String },
+    /// An image is drawn as a labelled placeholder instead of the picture
+    ImagePlaceholder { image: String },
+    /// An image's data could not be read, so its placeholder is drawn
+    MissingImage { image: String },
+    /// A named picture placeholder was never given a picture
+    EmptyPicture { name: String },
+    /// A markdown construct was dropped or simplified
+    UnsupportedMarkdown { feature: String },
+    /// `original` was written as `replacement` for a compatibility profile's `app`
+    Substituted { original: String, replacement: String, app: String },
+    /// An element refers to a style the style sheet doesn't define
+    UnknownStyle { name: String },
+    /// A bullet cites a key no source is registered under
+    UnknownCitation { key: String },
+    /// `element` reaches past the listed `edges` of the slide
+    OffSlide { element: String, edges: Vec<String> },
+}
+
+/// A non-fatal issue, with the 1-based slide it concerns
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Warning {
+    pub slide: Option<usize>,
+    pub kind: WarningKind,
+}
+
+impl Warning {
+    pub fn new(slide: Option<usize>, kind: WarningKind) -> Self {
+        Warning { slide, kind }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(slide) = self.slide {
+            write!(f, "slide {slide}: ")?;
+        }
+        match &self.kind {
+            WarningKind::TextOverflow { element } => write!(f, "{element} likely overflows its box"),
+            WarningKind::ImagePlaceholder { image } => write!(f, "image '{image}' is shown as a placeholder"),
+            WarningKind::MissingImage { image } => write!(f, "image '{image}' could not be read, showing a placeholder"),
+            WarningKind::EmptyPicture { name } => write!(f, "picture placeholder '{name}' was not filled"),
+            WarningKind::UnsupportedMarkdown { feature } => write!(f, "unsupported markdown skipped: {feature}"),
+            WarningKind::Substituted { original, replacement, app } => {
+                write!(f, "{original} replaced by {replacement} for {app}")
+            }
+            WarningKind::UnknownStyle { name } => write!(f, "unknown style '{name}'"),
+            WarningKind::UnknownCitation { key } => write!(f, "no source registered for citation '{key}'"),
+            WarningKind::OffSlide { element, edges } => {
+                let plural = if edges.len() == 1 { "edge" } else { "edges" };
+                write!(f, "{element} extends past the {} {plural} of the slide", edges.join(" and "))
+            }
+        }
+    }
+}
+
+/// Warnings collected while generating, in slide order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Warnings {
+    items: Vec<Warning>,
+}
+
+impl Warnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a warning, ignoring exact repeats
+    pub fn push(&mut self, warning: Warning) {
+        if !self.items.contains(&warning) {
+            self.items.push(warning);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Warning> {
+        self.items.iter()
+    }
+
+    /// Warnings about slide `slide`
+    pub fn for_slide(&self, slide: usize) -> impl Iterator<Item = &Warning> {
+        self.items.iter().filter(move |w| w.slide == Some(slide))
+    }
+}
+
+impl Extend<Warning> for Warnings {
+    fn extend<I: IntoIterator<Item = Warning>>(&mut self, iter: I) {
+        for warning in iter {
+            self.push(warning);
+        }
+    }
+}
+
+impl FromIterator<Warning> for Warnings {
+    fn from_iter<I: IntoIterator<Item = Warning>>(iter: I) -> Self {
+        let mut warnings = Warnings::new();
+        warnings.extend(iter);
+        warnings
+    }
+}
+
+impl IntoIterator for Warnings {
+    type Item = Warning;
+    type IntoIter = std::vec::IntoIter<Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Warnings {
+    type Item = &'a Warning;
+    type IntoIter = std::slice::Iter<'a, Warning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// PPTX bytes and the warnings raised while building them
+#[derive(Clone, Debug, Default)]
+pub struct GenerationOutput {
+    pub data: Vec<u8>,
+    pub warnings: Warnings,
+}
+
+/// Warnings for slides about to be written
+///
+/// Images are expected to be loaded already (see [`Image::load`](super::Image::load)).
+pub fn check_slides(slides: &[SlideContent]) -> Warnings {
+    let mut warnings = Warnings::new();
+    for (i, slide) in slides.iter().enumerate() {
+        let number = Some(i + 1);
+        if body_overflows(slide) {
+            warnings.push(Warning::new(number, WarningKind::TextOverflow { element: "body text".to_string() }));
+        }
+        for shape in &slide.shapes {
+            if let Some(text) = &shape.text
+                && !text_fits(text, shape.width, shape.height)
+            {
+                let element = match &shape.name {
+                    Some(name) => format!("shape \"{name}\""),
+                    None => "shape text".to_string(),
+                };
+                warnings.push(Warning::new(number, WarningKind::TextOverflow { element }));
+            }
+        }
+        let empty: Vec<&PicturePlaceholder> = slide.picture_placeholders.iter().filter(|p| !p.filled).collect();
+        for placeholder in &empty {
+            warnings.push(Warning::new(number, WarningKind::EmptyPicture { name: placeholder.name.clone() }));
+        }
+        for (j, image) in slide.images.iter().enumerate() {
+            if !image.is_loaded() && !empty.iter().any(|p| p.image == j) {
+                warnings.push(Warning::new(number, WarningKind::MissingImage { image: image.filename.clone() }));
+            }
+        }
+    }
+    warnings
+}
+
+/// Warnings for elements that reach beyond a slide of `size`
+///
+/// Shapes are checked where their placement puts them on this size, and
+/// connectors by every point they pass through. Elements are named as in
+/// the other warnings, or by kind and 1-based position when unnamed.
+pub fn check_bounds(slides: &[SlideContent], size: SlideSize) -> Warnings {
+    let mut warnings = Warnings::new();
+    for (i, slide) in slides.iter().enumerate() {
+        let mut report = |element: String, x: Emu, y: Emu, width: Emu, height: Emu| {
+            let edges = off_slide_edges(x, y, width, height, size);
+            if !edges.is_empty() {
+                let edges = edges.into_iter().map(String::from).collect();
+                warnings.push(Warning::new(Some(i + 1), WarningKind::OffSlide { element, edges }));
+            }
+        };
+        for (j, shape) in slide.shapes.iter().chain(&slide.overlays).enumerate() {
+            let mut shape = shape.clone();
+            shape.resolve_placement(size);
+            let element = match &shape.name {
+                Some(name) => format!("shape \"{name}\""),
+                None => format!("shape {}", j + 1),
+            };
+            report(element, shape.x, shape.y, shape.width, shape.height);
+        }
+        for image in &slide.images {
+            report(format!("image '{}'", image.filename), image.x(), image.y(), image.width(), image.height());
+        }
+        if let Some(table) = &slide.table {
+            report("table".to_string(), Positioned::x(table), Positioned::y(table), ElementSized::width(table), ElementSized::height(table));
+        }
+        for (j, chart) in slide.charts.iter().enumerate() {
+            let element = if chart.title.is_empty() { format!("chart {}", j + 1) } else { format!("chart \"{}\"", chart.title) };
+            report(element, chart.x(), chart.y(), chart.width(), chart.height());
+        }
+        for (j, block) in slide.code_blocks.iter().enumerate() {
+            report(format!("code block {}", j + 1), Emu(block.x), Emu(block.y), Emu(block.width), Emu(block.height));
+        }
+        for (j, connector) in slide.connectors.iter().enumerate() {
+            let points = connector.points();
+            let (left, right) = points.iter().map(|p| p.0).fold((Emu(i64::MAX), Emu(i64::MIN)), |(lo, hi), x| (lo.min(x), hi.max(x)));
+            let (top, bottom) = points.iter().map(|p| p.1).fold((Emu(i64::MAX), Emu(i64::MIN)), |(lo, hi), y| (lo.min(y), hi.max(y)));
+            report(format!("connector {}", j + 1), left, top, right - left, bottom - top);
+        }
+    }
+    warnings
+}
+
+/// Edges of a slide of `size` that the box at `x`, `y` crosses
+fn off_slide_edges(x: Emu, y: Emu, width: Emu, height: Emu, size: SlideSize) -> Vec<&'static str> {
+    let mut edges = Vec::new();
+    if x < Emu::ZERO {
+        edges.push("left");
+    }
+    if y < Emu::ZERO {
+        edges.push("top");
+    }
+    if x.saturating_add(width) > size.width {
+        edges.push("right");
+    }
+    if y.saturating_add(height) > size.height {
+        edges.push("bottom");
+    }
+    edges
+}
+
+/// Whether the bullets likely need more height than the layout's body box
+fn body_overflows(slide: &SlideContent) -> bool {
+    // Body box of each layout (see `slide_xml::layouts`)
+    let (width, height, columns) = match slide.layout {
+        SlideLayout::TitleAndContent => (Emu(8_230_200), Emu(4_572_000), 1),
+        SlideLayout::TitleAndBigContent => (Emu(8_230_200), Emu(5_668_800), 1),
+        SlideLayout::TwoColumn => (Emu(4_115_100), Emu(5_668_800), 2),
+        _ => return false,
+    };
+    let default_size = slide.content_size.unwrap_or(24) as f64;
+    let paragraphs: Vec<(&str, u32, f64)> = if slide.bullets.is_empty() {
+        slide.content.iter().map(|text| (text.as_str(), 0, default_size)).collect()
+    } else {
+        slide.bullets.iter()
+            .map(|b| (b.text.as_str(), b.level, b.format.as_ref().and_then(|f| f.font_size).map_or(default_size, f64::from)))
+            .collect()
+    };
+    let per_column = paragraphs.len().div_ceil(columns).max(1);
+    paragraphs.chunks(per_column).any(|column| {
+        let needed: f64 = column.iter()
+            .map(|&(text, level, size)| paragraph_height(text, level, size, width.to_inches()))
+            .sum();
+        needed > height.to_inches()
+    })
+}
+
+/// Estimated height in inches of a wrapped paragraph
+fn paragraph_height(text: &str, level: u32, points: f64, box_width: f64) -> f64 {
+    let width = (box_width - INDENT_INCHES * (level + 1) as f64).max(1.0);
+    let chars_per_line = (width * 72.0 / (points * CHAR_WIDTH_EM)).max(1.0);
+    let lines = (text.chars().count() as f64 / chars_per_line).ceil().max(1.0);
+    lines * points * LINE_SPACING / 72.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{CustomLayout, Image, ImageSource, Region, Shape, ShapeType};
+
+    #[test]
+    fn test_check_slides() {
+        let fits = SlideContent::new("Fine").add_bullet("Short point");
+        let mut crowded = SlideContent::new("Crowded");
+        for i in 0..12 {
+            crowded = crowded.add_bullet(&format!("Point {i} with enough words to wrap onto a second line of the body"));
+        }
+        crowded.shapes.push(Shape::new(ShapeType::Rectangle, 0, 0, 300_000, 200_000)
+            .with_name("Note")
+            .with_text("Far too much text for such a tiny rectangle on the slide"));
+        crowded.images.push(Image::new("chart.png", 100, 100, "png"));
+        crowded.images.push(Image::from_bytes(vec![1, 2, 3], 100, 100, "png"));
+
+        let warnings = check_slides(&[fits, crowded]);
+        assert_eq!(warnings.for_slide(1).count(), 0);
+        let messages: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(messages, [
+            "slide 2: body text likely overflows its box",
+            "slide 2: shape \"Note\" likely overflows its box",
+            "slide 2: image 'chart.png' could not be read, showing a placeholder",
+        ]);
+    }
+
+    #[test]
+    fn test_empty_picture_placeholders() {
+        let slide = CustomLayout::new("Hero", SlideLayout::TitleOnly)
+            .picture("hero", Region::FullSlide)
+            .picture("logo", Region::TopHalf)
+            .slide("Launch")
+            .set_picture("hero", ImageSource::File("missing-hero.jpg".into()))
+            .unwrap();
+        let messages: Vec<String> = check_slides(&[slide]).iter().map(|w| w.to_string()).collect();
+        assert_eq!(messages, [
+            "slide 1: picture placeholder 'logo' was not filled",
+            "slide 1: image 'missing-hero.jpg' could not be read, showing a placeholder",
+        ]);
+    }
+
+    #[test]
+    fn test_check_bounds() {
+        use crate::generator::{Anchor, Connector};
+
+        let slide = SlideContent::new("Edges")
+            .add_shape(Shape::new(ShapeType::Rectangle, 8_000_000, 100_000, 2_000_000, 500_000).with_name("Badge"))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914_400, 914_400).at(Anchor::BottomRight.offset(0, 0)))
+            .add_shape(Shape::new(ShapeType::Rectangle, -100_000, 6_500_000, 500_000, 500_000))
+            .add_connector(Connector::elbow(100_000, 100_000, 500_000, 500_000).with_waypoints(vec![(100_000, 7_000_000)]));
+        let messages: Vec<String> = check_bounds(std::slice::from_ref(&slide), SlideSize::STANDARD).iter().map(|w| w.to_string()).collect();
+        assert_eq!(messages, [
+            "slide 1: shape \"Badge\" extends past the right edge of the slide",
+            "slide 1: shape 3 extends past the left and bottom edges of the slide",
+            "slide 1: connector 1 extends past the bottom edge of the slide",
+        ]);
+        // The badge fits on a widescreen slide; anchored shapes follow the size
+        let wide = check_bounds(&[slide], SlideSize::WIDESCREEN);
+        assert!(wide.iter().all(|w| !w.to_string().contains("Badge") && !w.to_string().contains("shape 2")));
+    }
+
+    #[test]
+    fn test_push_ignores_repeats() {
+        let mut warnings = Warnings::new();
+        let warning = Warning::new(Some(1), WarningKind::UnsupportedMarkdown { feature: "raw HTML".into() });
+        warnings.push(warning.clone());
+        warnings.push(warning);
+        assert_eq!(warnings.len(), 1);
+    }
+}
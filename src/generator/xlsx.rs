@@ -0,0 +1,438 @@
+//! Excel (.xlsx) data source for tables and charts
+//!
+//! A light reader for cell values — shared and inline strings, numbers and
+//! booleans; formulas contribute their cached result:
+//!
+//! ```rust,ignore
+//! let table = Table::from_xlsx("data.xlsx", "Sheet1!A1:D20")?;
+//!
+//! // Categories in column A, one series per column B..D, names in row 1
+//! let chart = ChartBuilder::from_xlsx("Revenue", ChartType::Bar, "data.xlsx", "Sheet1!A1:D5")?.build();
+//! ```
+//!
+//! Charts read from a workbook embed it as their data and reference the
+//! range, so "Edit Data" in PowerPoint opens the source numbers.
+
+use std::io::{Cursor, Read};
+use std::path::Path;
+use std::sync::Arc;
+
+use zip::ZipArchive;
+
+use crate::exc::{PptxError, Result};
+use crate::generator::charts::{ChartBuilder, ChartDataSource, ChartSeries, ChartType};
+use crate::generator::tables::Table;
+use crate::oxml::xmlchemy::{XmlElement, XmlParser};
+
+/// Opened workbook: sheet names and shared strings, cells read on demand
+#[derive(Clone)]
+pub struct Workbook {
+    bytes: Arc<[u8]>,
+    /// (sheet name, part path)
+    sheets: Vec<(String, String)>,
+    shared_strings: Vec<String>,
+}
+
+impl std::fmt::Debug for Workbook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Workbook")
+            .field("bytes", &self.bytes.len())
+            .field("sheets", &self.sheet_names())
+            .finish()
+    }
+}
+
+/// Cell values of a rectangular worksheet range
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SheetRange {
+    pub sheet: String,
+    /// 0-based row of the range's top-left cell
+    pub first_row: u32,
+    /// 0-based column of the range's top-left cell
+    pub first_col: u32,
+    /// Values row by row; empty cells are empty strings
+    pub cells: Vec<Vec<String>>,
+}
+
+impl Workbook {
+    /// Read a workbook file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Read a workbook from memory
+    pub fn from_bytes(bytes: impl Into<Arc<[u8]>>) -> Result<Self> {
+        let bytes = bytes.into();
+        let mut zip = ZipArchive::new(Cursor::new(&bytes[..])).map_err(|e| PptxError::Zip(e.to_string()))?;
+
+        let workbook_path = read_part(&mut zip, "_rels/.rels")
+            .ok()
+            .and_then(|rels| rel_target(&rels, "", |t| t.ends_with("/officeDocument")))
+            .unwrap_or_else(|| "xl/workbook.xml".to_string());
+        let dir = workbook_path.rsplit_once('/').map_or("", |(dir, _)| dir).to_string();
+        let rels_path = format!("{dir}/_rels/{}.rels", workbook_path.rsplit('/').next().unwrap_or_default());
+        let workbook = read_part(&mut zip, &workbook_path)?;
+        let rels = read_part(&mut zip, &rels_path)?;
+
+        let mut sheets = Vec::new();
+        for sheet in workbook.find_all_descendants("sheet") {
+            let (Some(name), Some(rid)) = (sheet.attr("name"), sheet.attr("r:id")) else { continue };
+            if let Some(target) = rel_by_id(&rels, &dir, rid) {
+                sheets.push((name.to_string(), target));
+            }
+        }
+        if sheets.is_empty() {
+            return Err(PptxError::InvalidValue("workbook has no worksheets".to_string()));
+        }
+
+        let shared_strings = rel_target(&rels, &dir, |t| t.ends_with("/sharedStrings"))
+            .and_then(|path| read_part(&mut zip, &path).ok())
+            .map(|sst| sst.find_all("si").into_iter().map(rich_text).collect())
+            .unwrap_or_default();
+
+        Ok(Workbook { bytes, sheets, shared_strings })
+    }
+
+    /// Worksheet names in workbook order
+    pub fn sheet_names(&self) -> Vec<&str> {
+        self.sheets.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Raw workbook file, as embedded in charts
+    pub fn bytes(&self) -> &Arc<[u8]> {
+        &self.bytes
+    }
+
+    /// Read a range such as `Sheet1!A1:D20`, `'Q1 Data'!B2:C9` or `A1:D20`
+    /// (first sheet); a bare sheet name reads its used range
+    pub fn range(&self, spec: &str) -> Result<SheetRange> {
+        let (sheet, cells) = match spec.rsplit_once('!') {
+            Some((sheet, cells)) => (unquote_sheet(sheet), Some(cells)),
+            None if parse_area(spec).is_some() => (self.sheets[0].0.clone(), Some(spec)),
+            None => (unquote_sheet(spec), None),
+        };
+        let path = self
+            .sheets
+            .iter()
+            .find(|(name, _)| *name == sheet)
+            .map(|(_, path)| path.clone())
+            .ok_or_else(|| PptxError::NotFound(format!("worksheet \"{sheet}\"")))?;
+        let area = match cells {
+            Some(cells) => Some(
+                parse_area(cells).ok_or_else(|| PptxError::InvalidValue(format!("invalid cell range \"{cells}\"")))?,
+            ),
+            None => None,
+        };
+
+        let mut zip = ZipArchive::new(Cursor::new(&self.bytes[..])).map_err(|e| PptxError::Zip(e.to_string()))?;
+        let worksheet = read_part(&mut zip, &path)?;
+        let values: Vec<((u32, u32), String)> = worksheet
+            .find_all_descendants("c")
+            .into_iter()
+            .filter_map(|c| Some((parse_cell(c.attr("r")?)?, self.cell_value(c))))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+
+        let ((r0, c0), (r1, c1)) = match area {
+            Some(area) => area,
+            None => {
+                let rows = values.iter().map(|((r, _), _)| *r);
+                let cols = values.iter().map(|((_, c), _)| *c);
+                match (rows.clone().min(), rows.max(), cols.clone().min(), cols.max()) {
+                    (Some(r0), Some(r1), Some(c0), Some(c1)) => ((r0, c0), (r1, c1)),
+                    _ => return Err(PptxError::InvalidValue(format!("worksheet \"{sheet}\" is empty"))),
+                }
+            }
+        };
+
+        let mut cells = vec![vec![String::new(); (c1 - c0 + 1) as usize]; (r1 - r0 + 1) as usize];
+        for ((r, c), value) in values {
+            if (r0..=r1).contains(&r) && (c0..=c1).contains(&c) {
+                cells[(r - r0) as usize][(c - c0) as usize] = value;
+            }
+        }
+        Ok(SheetRange { sheet, first_row: r0, first_col: c0, cells })
+    }
+
+    /// Displayed value of a `c` element
+    fn cell_value(&self, cell: &XmlElement) -> String {
+        let v = || cell.find("v").map(|v| v.text.clone()).unwrap_or_default();
+        match cell.attr("t") {
+            Some("s") => v().trim().parse::<usize>().ok().and_then(|i| self.shared_strings.get(i).cloned()).unwrap_or_default(),
+            Some("inlineStr") => cell.find("is").map(rich_text).unwrap_or_default(),
+            Some("b") => if v().trim() == "1" { "TRUE" } else { "FALSE" }.to_string(),
+            _ => v(),
+        }
+    }
+}
+
+/// Text of a shared or inline string (`si`/`is`), skipping phonetic runs
+fn rich_text(si: &XmlElement) -> String {
+    si.children
+        .iter()
+        .map(|child| match child.local_name.as_str() {
+            "t" => child.text.clone(),
+            "r" => child.find("t").map(|t| t.text.clone()).unwrap_or_default(),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+fn read_part(zip: &mut ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<XmlElement> {
+    let mut part = zip
+        .by_name(name.trim_start_matches('/'))
+        .map_err(|_| PptxError::NotFound(format!("workbook part {name}")))?;
+    let mut xml = String::new();
+    part.read_to_string(&mut xml)?;
+    XmlParser::parse_str(&xml)
+}
+
+/// Resolve a relationship target against the directory of its source part
+fn resolve(dir: &str, target: &str) -> String {
+    match target.strip_prefix('/') {
+        Some(absolute) => absolute.to_string(),
+        None if dir.is_empty() => target.to_string(),
+        None => format!("{dir}/{target}"),
+    }
+}
+
+fn rel_target(rels: &XmlElement, dir: &str, is_type: impl Fn(&str) -> bool) -> Option<String> {
+    rels.find_all("Relationship")
+        .into_iter()
+        .find(|r| r.attr("Type").is_some_and(&is_type))
+        .and_then(|r| r.attr("Target"))
+        .map(|target| resolve(dir, target))
+}
+
+fn rel_by_id(rels: &XmlElement, dir: &str, id: &str) -> Option<String> {
+    rels.find_all("Relationship")
+        .into_iter()
+        .find(|r| r.attr("Id") == Some(id))
+        .and_then(|r| r.attr("Target"))
+        .map(|target| resolve(dir, target))
+}
+
+fn unquote_sheet(sheet: &str) -> String {
+    match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        Some(quoted) => quoted.replace("''", "'"),
+        None => sheet.to_string(),
+    }
+}
+
+/// Sheet name as written in a formula, quoted when needed
+pub(crate) fn quote_sheet(sheet: &str) -> String {
+    if !sheet.is_empty() && sheet.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        sheet.to_string()
+    } else {
+        format!("'{}'", sheet.replace('\'', "''"))
+    }
+}
+
+/// Column letters for a 0-based column (`0` → `A`, `27` → `AB`)
+pub(crate) fn column_name(col: u32) -> String {
+    let mut name = Vec::new();
+    let mut n = col + 1;
+    while n > 0 {
+        name.push(b'A' + ((n - 1) % 26) as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// 0-based (row, column) of a cell name such as `B3` or `$B$3`
+fn parse_cell(name: &str) -> Option<(u32, u32)> {
+    let name = name.replace('$', "");
+    let split = name.find(|c: char| c.is_ascii_digit())?;
+    let (letters, digits) = name.split_at(split);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let col = letters
+        .chars()
+        .try_fold(0u32, |acc, c| acc.checked_mul(26)?.checked_add(c.to_ascii_uppercase() as u32 - 'A' as u32 + 1))?;
+    let row: u32 = digits.parse().ok()?;
+    (row > 0).then(|| (row - 1, col - 1))
+}
+
+/// Top-left and bottom-right cells of `A1:D20` (or a single cell)
+fn parse_area(area: &str) -> Option<((u32, u32), (u32, u32))> {
+    let (start, end) = area.split_once(':').unwrap_or((area, area));
+    let (a, b) = (parse_cell(start.trim())?, parse_cell(end.trim())?);
+    Some(((a.0.min(b.0), a.1.min(b.1)), (a.0.max(b.0), a.1.max(b.1))))
+}
+
+impl Table {
+    /// Table from a worksheet range such as `Sheet1!A1:D20`; the first
+    /// row becomes a bold header row
+    pub fn from_xlsx(path: impl AsRef<Path>, range: &str) -> Result<Table> {
+        let range = Workbook::open(path)?.range(range)?;
+        let mut rows = range.cells;
+        let headers = if rows.is_empty() { Vec::new() } else { rows.remove(0) };
+        Table::from_records(&headers, &rows)
+    }
+}
+
+impl ChartBuilder {
+    /// Chart builder reading a worksheet range such as `Sheet1!A1:D5`
+    ///
+    /// The first column holds the categories, the first row the series
+    /// names and the other cells the (numeric) values. The workbook is
+    /// embedded as the chart's data and the chart references the range.
+    pub fn from_xlsx(title: &str, chart_type: ChartType, path: impl AsRef<Path>, range: &str) -> Result<ChartBuilder> {
+        Self::from_workbook(title, chart_type, &Workbook::open(path)?, range)
+    }
+
+    /// Like [`ChartBuilder::from_xlsx`] with an already opened workbook
+    pub fn from_workbook(title: &str, chart_type: ChartType, workbook: &Workbook, range: &str) -> Result<ChartBuilder> {
+        let data = workbook.range(range)?;
+        let columns = data.cells.first().map_or(0, Vec::len);
+        if data.cells.len() < 2 || columns < 2 {
+            return Err(PptxError::InvalidValue(format!(
+                "range \"{range}\" needs a header row, a category column and at least one value"
+            )));
+        }
+
+        let body = &data.cells[1..];
+        let categories: Vec<&str> = body.iter().map(|row| row[0].as_str()).collect();
+        let mut builder = ChartBuilder::new(title, chart_type).categories(categories);
+        for col in 1..columns {
+            let values = body
+                .iter()
+                .enumerate()
+                .map(|(r, row)| {
+                    row[col].trim().parse::<f64>().map_err(|_| {
+                        PptxError::InvalidValue(format!(
+                            "{}!{}{}: \"{}\" is not a number",
+                            quote_sheet(&data.sheet),
+                            column_name(data.first_col + col as u32),
+                            data.first_row + r as u32 + 2,
+                            row[col]
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<f64>>>()?;
+            builder = builder.add_series(ChartSeries::new(&data.cells[0][col], values));
+        }
+        Ok(builder.data_source(ChartDataSource {
+            workbook: workbook.bytes().clone(),
+            sheet: data.sheet,
+            first_row: data.first_row,
+            first_col: data.first_col,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::{FileOptions, ZipWriter};
+
+    /// Minimal workbook: `Sales` sheet with a shared-string header row,
+    /// inline-string categories and numbers, plus an empty second sheet
+    fn sample_workbook() -> Vec<u8> {
+        let parts = [
+            ("_rels/.rels", r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#),
+            ("xl/workbook.xml", r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Sales" sheetId="1" r:id="rId1"/><sheet name="Q1 Data" sheetId="2" r:id="rId2"/></sheets></workbook>"#),
+            ("xl/_rels/workbook.xml.rels", r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="/xl/worksheets/sheet2.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings" Target="sharedStrings.xml"/></Relationships>"#),
+            ("xl/sharedStrings.xml", r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><si><t>Quarter</t></si><si><r><t>20</t></r><r><t>24</t></r></si><si><t>2025</t></si></sst>"#),
+            ("xl/worksheets/sheet1.xml", r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>
+<row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1" t="s"><v>2</v></c></row>
+<row r="2"><c r="A2" t="inlineStr"><is><t>Q1</t></is></c><c r="B2"><v>100</v></c><c r="C2"><f>B2*1.2</f><v>120</v></c></row>
+<row r="3"><c r="A3" t="inlineStr"><is><t>Q2</t></is></c><c r="B3"><v>150.5</v></c><c r="C3" t="b"><v>1</v></c></row>
+</sheetData></worksheet>"#),
+            ("xl/worksheets/sheet2.xml", r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData><row r="4"><c r="C4"><v>7</v></c></row></sheetData></worksheet>"#),
+        ];
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, xml) in parts {
+            zip.start_file(name, FileOptions::default()).unwrap();
+            zip.write_all(xml.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_cell_names() {
+        assert_eq!(parse_cell("B3"), Some((2, 1)));
+        assert_eq!(parse_cell("$AB$10"), Some((9, 27)));
+        assert_eq!(parse_cell("3B"), None);
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(27), "AB");
+        assert_eq!(quote_sheet("Sheet1"), "Sheet1");
+        assert_eq!(quote_sheet("Bob's Data"), "'Bob''s Data'");
+        assert_eq!(parse_area("C5:A1"), Some(((0, 0), (4, 2))));
+    }
+
+    #[test]
+    fn test_read_range() {
+        let workbook = Workbook::from_bytes(sample_workbook()).unwrap();
+        assert_eq!(workbook.sheet_names(), ["Sales", "Q1 Data"]);
+
+        let range = workbook.range("Sales!A1:C3").unwrap();
+        assert_eq!(range.cells, [["Quarter", "2024", "2025"], ["Q1", "100", "120"], ["Q2", "150.5", "TRUE"]]);
+
+        let range = workbook.range("B2:D2").unwrap();
+        assert_eq!((range.sheet.as_str(), range.first_col), ("Sales", 1));
+        assert_eq!(range.cells, [["100", "120", ""]]);
+
+        let used = workbook.range("'Q1 Data'").unwrap();
+        assert_eq!((used.first_row, used.first_col, used.cells.clone()), (3, 2, vec![vec!["7".to_string()]]));
+
+        assert!(workbook.range("Missing!A1").is_err());
+        assert!(workbook.range("Sales!A0").is_err());
+    }
+
+    #[test]
+    fn test_chart_from_workbook() {
+        let workbook = Workbook::from_bytes(sample_workbook()).unwrap();
+        let chart = ChartBuilder::from_workbook("Sales", ChartType::Bar, &workbook, "Sales!A1:B3").unwrap().build();
+        assert_eq!(chart.categories, ["Q1", "Q2"]);
+        assert_eq!(chart.series[0].name, "2024");
+        assert_eq!(chart.series[0].values, [100.0, 150.5]);
+        let source = chart.data_source.as_ref().unwrap();
+        assert_eq!(source.column_ref(0, 2), "Sales!$A$2:$A$3");
+        assert_eq!(source.header_ref(1), "Sales!$B$1");
+
+        // C3 is a boolean
+        let err = ChartBuilder::from_workbook("Sales", ChartType::Bar, &workbook, "Sales!A1:C3").err().unwrap();
+        assert!(err.to_string().contains("Sales!C3"));
+    }
+
+    #[test]
+    fn test_table_from_xlsx() {
+        let path = std::env::temp_dir().join(format!("ppt_rs_xlsx_{}.xlsx", uuid::Uuid::new_v4()));
+        std::fs::write(&path, sample_workbook()).unwrap();
+        let table = Table::from_xlsx(&path, "Sales!A1:C3");
+        std::fs::remove_file(&path).ok();
+
+        let table = table.unwrap();
+        assert_eq!((table.row_count(), table.column_count()), (3, 3));
+        assert!(table.rows[0].cells[0].bold);
+        assert_eq!(table.rows[2].cells[1].text, "150.5");
+    }
+
+    #[test]
+    fn test_chart_embeds_workbook() {
+        use crate::generator::{create_pptx_with_content, SlideContent};
+
+        let workbook = Workbook::from_bytes(sample_workbook()).unwrap();
+        let chart = ChartBuilder::from_workbook("Sales", ChartType::Bar, &workbook, "Sales!A1:B3").unwrap().build();
+        let xml = crate::generator::generate_chart_part_xml(&chart);
+        assert!(xml.contains("<c:f>Sales!$B$2:$B$3</c:f>"));
+        assert!(xml.contains("<c:externalData r:id=\"rId1\">"));
+
+        let pptx = create_pptx_with_content("Data", vec![SlideContent::new("Sales").add_chart(chart)]).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(pptx)).unwrap();
+        let mut embedded = Vec::new();
+        archive.by_name("ppt/embeddings/Microsoft_Excel_Worksheet1.xlsx").unwrap().read_to_end(&mut embedded).unwrap();
+        assert_eq!(embedded, sample_workbook());
+
+        let mut rels = String::new();
+        archive.by_name("ppt/charts/_rels/chart1.xml.rels").unwrap().read_to_string(&mut rels).unwrap();
+        assert!(rels.contains("Target=\"../embeddings/Microsoft_Excel_Worksheet1.xlsx\""));
+        let mut types = String::new();
+        archive.by_name("[Content_Types].xml").unwrap().read_to_string(&mut types).unwrap();
+        assert!(types.contains("<Default Extension=\"xlsx\""));
+    }
+}
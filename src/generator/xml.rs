@@ -14,6 +14,9 @@ pub use super::package_xml::{
 pub use super::slide_xml::{
     create_slide_xml,
     create_slide_xml_with_content,
+    create_slide_xml_with_rels,
+    create_slide_xml_with_size,
+    SlideRelIds, ShapeActionRelIds,
     create_slide_rels_xml,
 };
 pub use super::theme_xml::{
@@ -22,6 +25,8 @@ pub use super::theme_xml::{
     create_slide_master_xml,
     create_master_rels_xml,
     create_theme_xml,
+    create_theme_xml_with_fonts,
+    ThemeFonts,
 };
 pub use super::props_xml::{
     create_core_props_xml,
@@ -0,0 +1,353 @@
+//! Slide Zoom support
+//!
+//! A slide zoom is a clickable thumbnail of another slide. In slideshow,
+//! clicking it zooms into the target slide. Zooms are written as the
+//! PowerPoint 2016 `pslz:sldZm` graphic frame with a hyperlinked picture
+//! fallback for older readers.
+
+#[cfg(feature = "media")]
+use std::io::Cursor;
+
+use crate::core::Emu;
+use crate::generator::package_xml::slide_creation_id;
+use crate::generator::placement::SlideSize;
+use crate::generator::slide_content::SlideContent;
+#[cfg(feature = "media")]
+use crate::generator::slide_content::SlideLayout;
+
+/// Slide zoom element pointing at another slide in the same presentation
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlideZoom {
+    /// Target slide number (1-based)
+    pub target_slide: usize,
+    /// Target's fixed `p:sldId`, when slides don't use position-based IDs
+    pub target_slide_id: Option<u32>,
+    /// Position X; negative values place the zoom partly left of the slide
+    pub x: Emu,
+    /// Position Y; negative values place the zoom partly above the slide
+    pub y: Emu,
+    /// Width
+    pub width: Emu,
+    /// Height
+    pub height: Emu,
+    /// Return to this slide after the target slide is shown
+    pub return_to_parent: bool,
+    /// Zoom transition duration in milliseconds
+    pub transition_duration_ms: u32,
+}
+
+impl SlideZoom {
+    /// Create a zoom to `target_slide` (1-based) at the given bounds
+    pub fn new(
+        target_slide: usize,
+        x: impl Into<Emu>,
+        y: impl Into<Emu>,
+        width: impl Into<Emu>,
+        height: impl Into<Emu>,
+    ) -> Self {
+        SlideZoom {
+            target_slide,
+            target_slide_id: None,
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            return_to_parent: false,
+            transition_duration_ms: 1000,
+        }
+    }
+
+    /// Return to the zoom's slide after the target has been shown
+    pub fn return_to_parent(mut self, return_to_parent: bool) -> Self {
+        self.return_to_parent = return_to_parent;
+        self
+    }
+
+    /// Set zoom transition duration in milliseconds
+    pub fn transition_duration_ms(mut self, ms: u32) -> Self {
+        self.transition_duration_ms = ms;
+        self
+    }
+
+    /// Lay out zooms to several slides as a summary grid
+    ///
+    /// Thumbnails keep the slide's 4:3 aspect ratio and fill the area
+    /// `(x, y, width, height)` in up to `columns` columns. Summary zooms
+    /// return to the summary slide after each target.
+    pub fn grid(targets: &[usize], columns: usize, area: (u32, u32, u32, u32)) -> Vec<SlideZoom> {
+        let columns = columns.clamp(1, targets.len().max(1));
+        let rows = targets.len().div_ceil(columns).max(1);
+        let (ax, ay, aw, ah) = area;
+        let gap = 228600u32;
+
+        let cell_w = (aw.saturating_sub(gap * (columns as u32 - 1))) / columns as u32;
+        let cell_h = (ah.saturating_sub(gap * (rows as u32 - 1))) / rows as u32;
+        let (thumb_w, thumb_h) = if cell_w as u64 * 3 > cell_h as u64 * 4 {
+            (cell_h * 4 / 3, cell_h)
+        } else {
+            (cell_w, cell_w * 3 / 4)
+        };
+
+        targets
+            .iter()
+            .enumerate()
+            .map(|(i, &target)| {
+                let col = (i % columns) as u32;
+                let row = (i / columns) as u32;
+                let x = ax + col * (cell_w + gap) + (cell_w - thumb_w) / 2;
+                let y = ay + row * (cell_h + gap) + (cell_h - thumb_h) / 2;
+                SlideZoom::new(target, x, y, thumb_w, thumb_h).return_to_parent(true)
+            })
+            .collect()
+    }
+}
+
+/// Relationship IDs a slide uses for one zoom
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZoomRelIds {
+    /// Relationship to the target slide
+    pub slide: String,
+    /// Relationship to the thumbnail image
+    pub image: String,
+}
+
+/// Generate the slide zoom element with its picture fallback
+///
+/// `target_slide_id` is the target's `p:sldId/@id` in presentation.xml; the
+/// target's creation ID is derived from it, as for every generated slide.
+pub fn generate_slide_zoom_xml(
+    zoom: &SlideZoom,
+    shape_id: usize,
+    rel_ids: &ZoomRelIds,
+    target_slide_id: usize,
+) -> String {
+    let zoom_guid = format!("{{8C2D4F1A-6B3E-4D7C-9A5F-{:06X}{:06X}}}", shape_id, zoom.target_slide);
+    format!(
+        r#"<mc:AlternateContent xmlns:mc="http://schemas.openxmlformats.org/markup-compatibility/2006">
+<mc:Choice xmlns:pslz="http://schemas.microsoft.com/office/powerpoint/2016/slidezoom" Requires="pslz">
+<p:graphicFrame>
+<p:nvGraphicFramePr>
+<p:cNvPr id="{id}" name="Slide Zoom {id}"/>
+<p:cNvGraphicFramePr>
+<a:graphicFrameLocks noChangeAspect="1"/>
+</p:cNvGraphicFramePr>
+<p:nvPr/>
+</p:nvGraphicFramePr>
+<p:xfrm>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{cx}" cy="{cy}"/>
+</p:xfrm>
+<a:graphic>
+<a:graphicData uri="http://schemas.microsoft.com/office/powerpoint/2016/slidezoom">
+<pslz:sldZm>
+<pslz:sldZmObj sldId="{sld_id}" cId="{c_id}">
+<pslz:zmPr id="{guid}" returnToParent="{ret}" transitionDur="{dur}">
+<p166:blipFill xmlns:p166="http://schemas.microsoft.com/office/powerpoint/2016/6/main">
+<a:blip r:embed="{image}"/>
+<a:stretch>
+<a:fillRect/>
+</a:stretch>
+</p166:blipFill>
+<p166:spPr xmlns:p166="http://schemas.microsoft.com/office/powerpoint/2016/6/main">
+<a:xfrm>
+<a:off x="0" y="0"/>
+<a:ext cx="{cx}" cy="{cy}"/>
+</a:xfrm>
+<a:prstGeom prst="rect">
+<a:avLst/>
+</a:prstGeom>
+<a:ln w="3175">
+<a:solidFill>
+<a:prstClr val="ltGray"/>
+</a:solidFill>
+</a:ln>
+</p166:spPr>
+</pslz:zmPr>
+</pslz:sldZmObj>
+</pslz:sldZm>
+</a:graphicData>
+</a:graphic>
+</p:graphicFrame>
+</mc:Choice>
+<mc:Fallback>
+<p:pic>
+<p:nvPicPr>
+<p:cNvPr id="{id}" name="Slide Zoom {id}">
+<a:hlinkClick r:id="{slide}" action="ppaction://hlinksldjump"/>
+</p:cNvPr>
+<p:cNvPicPr>
+<a:picLocks noGrp="1" noRot="1" noChangeAspect="1" noMove="1" noResize="1" noEditPoints="1" noAdjustHandles="1" noChangeArrowheads="1" noChangeShapeType="1"/>
+</p:cNvPicPr>
+<p:nvPr/>
+</p:nvPicPr>
+<p:blipFill>
+<a:blip r:embed="{image}"/>
+<a:stretch>
+<a:fillRect/>
+</a:stretch>
+</p:blipFill>
+<p:spPr>
+<a:xfrm>
+<a:off x="{x}" y="{y}"/>
+<a:ext cx="{cx}" cy="{cy}"/>
+</a:xfrm>
+<a:prstGeom prst="rect">
+<a:avLst/>
+</a:prstGeom>
+<a:ln w="3175">
+<a:solidFill>
+<a:prstClr val="ltGray"/>
+</a:solidFill>
+</a:ln>
+</p:spPr>
+</p:pic>
+</mc:Fallback>
+</mc:AlternateContent>"#,
+        id = shape_id,
+        x = zoom.x,
+        y = zoom.y,
+        cx = zoom.width,
+        cy = zoom.height,
+        sld_id = target_slide_id,
+        c_id = slide_creation_id(target_slide_id),
+        guid = zoom_guid,
+        ret = if zoom.return_to_parent { 1 } else { 0 },
+        dur = zoom.transition_duration_ms,
+        image = rel_ids.image,
+        slide = rel_ids.slide,
+    )
+}
+
+/// Thumbnail size in pixels (4:3, matching the zoom frame)
+#[cfg(feature = "media")]
+const THUMB_WIDTH: u32 = 320;
+#[cfg(feature = "media")]
+const THUMB_HEIGHT: u32 = 240;
+
+/// Render a schematic PNG thumbnail of a slide
+///
+/// The thumbnail shows the slide's structure rather than its text: a title
+/// bar, one line per bullet, and boxes for shapes, images and charts.
+/// PowerPoint replaces it with a real rendering once the zoom is refreshed.
+pub fn generate_zoom_thumbnail(slide: &SlideContent) -> Vec<u8> {
+    generate_zoom_thumbnail_with_size(slide, SlideSize::STANDARD)
+}
+
+/// [`generate_zoom_thumbnail`] for a deck whose slides are `size`
+///
+/// The picture keeps the 4:3 frame of [`SlideZoom::grid`]; shapes, images
+/// and charts are drawn at the same fraction of it as of the slide.
+#[cfg(feature = "media")]
+pub fn generate_zoom_thumbnail_with_size(slide: &SlideContent, size: SlideSize) -> Vec<u8> {
+    let mut img = image::RgbImage::from_pixel(THUMB_WIDTH, THUMB_HEIGHT, image::Rgb([255, 255, 255]));
+
+    let title_color = image::Rgb([68, 84, 106]);
+    let body_color = image::Rgb([191, 191, 191]);
+    let box_color = image::Rgb([217, 226, 243]);
+
+    let centered = matches!(slide.layout, SlideLayout::CenteredTitle | SlideLayout::SectionHeader);
+    if centered {
+        fill_rect(&mut img, 40, 100, 240, 40, title_color);
+    } else if slide.layout != SlideLayout::Blank {
+        fill_rect(&mut img, 16, 12, 288, 28, title_color);
+    }
+
+    let bullets = slide.bullets.len().max(slide.content.len()).min(10) as u32;
+    for i in 0..bullets {
+        fill_rect(&mut img, 24, 56 + i * 17, 240 - (i % 3) * 40, 7, body_color);
+    }
+
+    let bounds = slide.shapes.iter()
+        .map(|s| (s.x, s.y, s.width, s.height))
+        .chain(slide.images.iter().map(|i| (i.x, i.y, i.width, i.height)))
+        .chain(slide.charts.iter().map(|c| (c.x, c.y, c.width, c.height)));
+    let slide_width = size.width.get().max(1) as u64;
+    let slide_height = size.height.get().max(1) as u64;
+    for (x, y, w, h) in bounds {
+        let sx = |v: Emu| (v.to_u32_saturating() as u64 * THUMB_WIDTH as u64 / slide_width) as u32;
+        let sy = |v: Emu| (v.to_u32_saturating() as u64 * THUMB_HEIGHT as u64 / slide_height) as u32;
+        fill_rect(&mut img, sx(x), sy(y), sx(w).max(2), sy(h).max(2), box_color);
+    }
+
+    let mut png = Vec::new();
+    image::DynamicImage::ImageRgb8(img)
+        .write_to(&mut Cursor::new(&mut png), image::ImageOutputFormat::Png)
+        .expect("encoding an in-memory PNG cannot fail");
+    png
+}
+
+/// Blank thumbnail (a single white pixel) used without the `media` feature
+///
+/// PowerPoint replaces it with a real rendering once the zoom is refreshed.
+#[cfg(not(feature = "media"))]
+pub fn generate_zoom_thumbnail_with_size(_slide: &SlideContent, _size: SlideSize) -> Vec<u8> {
+    const BLANK_PNG: [u8; 69] = [
+        0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00, 0x00, 0x00, 0x0D, 0x49, 0x48, 0x44, 0x52,
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00, 0x90, 0x77, 0x53,
+        0xDE, 0x00, 0x00, 0x00, 0x0C, 0x49, 0x44, 0x41, 0x54, 0x78, 0x9C, 0x63, 0xF8, 0xFF, 0xFF, 0x3F,
+        0x00, 0x05, 0xFE, 0x02, 0xFE, 0x0D, 0xEF, 0x46, 0xB8, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4E,
+        0x44, 0xAE, 0x42, 0x60, 0x82,
+    ];
+    BLANK_PNG.to_vec()
+}
+
+#[cfg(feature = "media")]
+fn fill_rect(img: &mut image::RgbImage, x: u32, y: u32, w: u32, h: u32, color: image::Rgb<u8>) {
+    for py in y..(y + h).min(img.height()) {
+        for px in x..(x + w).min(img.width()) {
+            img.put_pixel(px, py, color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slide_zoom_xml() {
+        let zoom = SlideZoom::new(3, 100, 200, 3048000, 2286000).return_to_parent(true);
+        let rel_ids = ZoomRelIds { slide: "rId2".to_string(), image: "rId3".to_string() };
+        let xml = generate_slide_zoom_xml(&zoom, 7, &rel_ids, 259);
+
+        assert!(xml.contains(&format!(r#"<pslz:sldZmObj sldId="259" cId="{}">"#, slide_creation_id(259))));
+        assert!(xml.contains(r#"returnToParent="1""#));
+        assert!(xml.contains(r#"<a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/>"#));
+        assert_eq!(xml.matches(r#"<a:blip r:embed="rId3"/>"#).count(), 2);
+    }
+
+    #[test]
+    fn test_zoom_grid_layout() {
+        let zooms = SlideZoom::grid(&[2, 3, 4, 5], 2, (457200, 1600200, 8229600, 4525963));
+        assert_eq!(zooms.len(), 4);
+        assert!(zooms.iter().all(|z| z.return_to_parent));
+        assert!(zooms[0].x < zooms[1].x);
+        assert_eq!(zooms[0].y, zooms[1].y);
+        assert!(zooms[2].y > zooms[0].y);
+        // 4:3 thumbnails inside the area
+        assert_eq!(zooms[0].width * 3 / 4, zooms[0].height);
+        assert!(zooms[3].y + zooms[3].height <= Emu(1600200 + 4525963));
+    }
+
+    #[test]
+    #[cfg(feature = "media")]
+    fn test_zoom_thumbnail_is_png() {
+        let slide = SlideContent::new("Target").add_bullet("One").add_bullet("Two");
+        let png = generate_zoom_thumbnail(&slide);
+        assert_eq!(&png[1..4], b"PNG");
+        let decoded = image::load_from_memory(&png).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (THUMB_WIDTH, THUMB_HEIGHT));
+    }
+
+    #[test]
+    #[cfg(feature = "media")]
+    fn test_zoom_thumbnail_scales_to_slide_size() {
+        // A shape covering the right half of a widescreen slide fills the right half of the thumbnail
+        let shape = crate::generator::Shape::new(crate::generator::ShapeType::Rectangle, 6096000, 0, 6096000, 6858000);
+        let slide = SlideContent::new("").layout(SlideLayout::Blank).add_shape(shape);
+        let png = generate_zoom_thumbnail_with_size(&slide, SlideSize::WIDESCREEN);
+        let decoded = image::load_from_memory(&png).unwrap().to_rgb8();
+        assert_eq!(decoded.get_pixel(THUMB_WIDTH / 2 - 1, 120).0, [255, 255, 255]);
+        assert_ne!(decoded.get_pixel(THUMB_WIDTH / 2, 120).0, [255, 255, 255]);
+    }
+}
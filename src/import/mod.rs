@@ -4,16 +4,30 @@ use crate::generator::{SlideContent, Shape, ShapeType, TableBuilder, TableRow, T
 use crate::exc::Result;
 
 /// Import a presentation from a file path
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(path = %path)))]
 pub fn import_pptx(path: &str) -> Result<Presentation> {
-    let reader = PresentationReader::open(path)?;
+    import_reader(&PresentationReader::open(path)?)
+}
+
+/// Import a password-protected presentation from a file path
+#[cfg(feature = "encryption")]
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "info", skip_all, fields(path = %path)))]
+pub fn import_pptx_with_password(path: &str, password: &str) -> Result<Presentation> {
+    import_reader(&PresentationReader::open_with_password(path, password)?)
+}
+
+fn import_reader(reader: &PresentationReader) -> Result<Presentation> {
     let mut presentation = Presentation::new();
     
     if let Some(title) = &reader.info().title {
         presentation = presentation.title(title);
     }
     
-    for parsed_slide in reader.get_all_slides()? {
+    for (index, parsed_slide) in reader.get_all_slides()?.into_iter().enumerate() {
         let mut content = SlideContent::new(parsed_slide.title.as_deref().unwrap_or(""));
+        content.notes = reader.get_slide_notes(index)?;
+        content.tags = reader.slide_tags(index)?;
+        let shape_tags = reader.shape_tags(index)?;
         
         // Add body text as bullets
         for text in parsed_slide.body_text {
@@ -36,6 +50,10 @@ pub fn import_pptx(path: &str) -> Result<Presentation> {
                 if !text.is_empty() {
                     shape = shape.with_text(&text);
                 }
+                if let Some((_, tags)) = shape_tags.iter().find(|(name, _)| *name == parsed_shape.name) {
+                    shape = shape.with_name(&parsed_shape.name);
+                    shape.tags = tags.clone();
+                }
                 
                 content.shapes.push(shape);
             }
@@ -0,0 +1,44 @@
+//! `tracing` hooks
+//!
+//! Key phases - package open, part parse, slide emit, zip write, markdown
+//! parse - open spans and emit events through these macros. With the
+//! `tracing` feature they forward to the `tracing` crate, so a service can
+//! attach its own subscriber and time each build per request; without it
+//! they compile to nothing and the fields are never evaluated.
+//!
+//! | Span / event      | Level | Fields                  |
+//! |-------------------|-------|-------------------------|
+//! | `package_open`    | info  | `parts`, `bytes`        |
+//! | `presentation_open` | info | `path`, `slides`       |
+//! | `parse_part`      | debug | `part`                  |
+//! | `parse_markdown`  | debug | `bytes`, `slides`       |
+//! | `write_pptx`      | info  | `title`, `slides`       |
+//! | `emit_slide`      | debug | `slide`, `bytes`        |
+//! | `zip_write`       | debug | `parts`                 |
+
+/// Emit an event at `tracing::Level::$level`, e.g. `event!(DEBUG, slides = 3, "parsed")`
+macro_rules! event {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        tracing::event!(tracing::Level::$level, $($arg)+);
+    }};
+}
+
+/// Enter a span until the returned guard is dropped, e.g.
+/// `let _span = span!(DEBUG, "emit_slide", slide = 1);`
+macro_rules! span {
+    ($level:ident, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        let span = tracing::span!(tracing::Level::$level, $($arg)+).entered();
+        #[cfg(not(feature = "tracing"))]
+        let span = $crate::instrument::NoSpan;
+        span
+    }};
+}
+
+pub(crate) use event;
+pub(crate) use span;
+
+/// Guard returned by `span!` without the `tracing` feature
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
@@ -1,9 +1,14 @@
 //! Builder types for presentations and slides
 
+use crate::core::Emu;
 use crate::generator;
+use crate::generator::{FormattedText, Image, Shape, ShapeFill, ShapeType, SlideContent, SlideLayout};
+use crate::generator::constants::SLIDE_WIDTH;
+use crate::prelude::themes::Theme;
 use crate::exc::Result;
 use crate::config::Config;
 use crate::constants;
+#[cfg(not(target_arch = "wasm32"))]
 use std::fs;
 
 /// Complete PPTX presentation builder
@@ -42,6 +47,7 @@ impl PresentationBuilder {
     }
 
     /// Save to file
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn save_to_file(&self, path: &str) -> Result<()> {
         let pptx_data = self.build()?;
         fs::write(path, pptx_data)
@@ -49,6 +55,7 @@ impl PresentationBuilder {
     }
 
     /// Save to configured output directory
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn save(&self, filename: &str) -> Result<()> {
         let path = self.config.output_path(filename);
         self.save_to_file(&path)
@@ -56,11 +63,23 @@ impl PresentationBuilder {
 }
 
 /// Presentation metadata
+///
+/// Also the source of the cover and closing slides generated by
+/// [`Presentation::auto_title_slide`](crate::api::Presentation::auto_title_slide)
+/// and [`Presentation::auto_closing_slide`](crate::api::Presentation::auto_closing_slide).
+#[derive(Clone, Debug)]
 pub struct PresentationMetadata {
     pub title: String,
     pub slides: usize,
     pub created: String,
     pub modified: String,
+    /// Line shown under the title on the cover
+    pub subtitle: Option<String>,
+    pub author: Option<String>,
+    /// Date shown on the cover; the date part of `created` when unset
+    pub date: Option<String>,
+    /// Logo placed in the top-right corner of the cover
+    pub logo: Option<Image>,
 }
 
 impl PresentationMetadata {
@@ -72,8 +91,99 @@ impl PresentationMetadata {
             slides,
             created: now.clone(),
             modified: now,
+            subtitle: None,
+            author: None,
+            date: None,
+            logo: None,
         }
     }
+
+    pub fn subtitle(mut self, subtitle: &str) -> Self {
+        self.subtitle = Some(subtitle.to_string());
+        self
+    }
+
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
+    pub fn date(mut self, date: &str) -> Self {
+        self.date = Some(date.to_string());
+        self
+    }
+
+    /// Set the cover logo; it is scaled to 0.75" high
+    pub fn logo(mut self, logo: Image) -> Self {
+        self.logo = Some(logo);
+        self
+    }
+
+    /// Cover slide: title on a band, subtitle, author and date, and the logo
+    ///
+    /// With a theme the band is the theme's section gradient and the text
+    /// takes its palette; without one the cover is plain black on white.
+    pub fn title_slide(&self, theme: Option<&Theme>) -> SlideContent {
+        let mut slide = framed_slide(&self.title, theme);
+        if let Some(subtitle) = &self.subtitle {
+            let color = theme.map(|t| t.light);
+            slide = slide.add_shape(text_line(subtitle, BAND_BOTTOM - LINE_HEIGHT, 24, color, theme));
+        }
+        let date = self.date.clone().unwrap_or_else(|| self.created.chars().take(10).collect());
+        let byline = self.author.iter().chain([&date]).filter(|s| !s.is_empty()).cloned().collect::<Vec<_>>().join(" · ");
+        if !byline.is_empty() {
+            let color = theme.map(|t| t.secondary);
+            slide = slide.add_shape(text_line(&byline, BAND_BOTTOM + LINE_HEIGHT, 18, color, theme));
+        }
+        if let Some(logo) = &self.logo {
+            let logo = logo.clone().scale_to_height(LOGO_HEIGHT);
+            let x = Emu::from(SLIDE_WIDTH) - logo.width - Emu::from(MARGIN);
+            slide = slide.add_image(logo.position(x, MARGIN));
+        }
+        slide
+    }
+
+    /// Closing slide: `heading` on a band with `contact_info` below it
+    ///
+    /// Lines of `contact_info` are joined into one line.
+    pub fn closing_slide(&self, heading: &str, contact_info: &str, theme: Option<&Theme>) -> SlideContent {
+        let slide = framed_slide(heading, theme);
+        let contact = contact_info.lines().map(str::trim).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" · ");
+        if contact.is_empty() {
+            return slide;
+        }
+        let color = theme.map(|t| t.secondary);
+        slide.add_shape(text_line(&contact, BAND_BOTTOM + LINE_HEIGHT, 18, color, theme))
+    }
+}
+
+const MARGIN: u32 = 457200;
+const LOGO_HEIGHT: u32 = 685800;
+const LINE_HEIGHT: u32 = 457200;
+/// Bottom edge of the section band `CenteredTitle` slides draw
+const BAND_BOTTOM: u32 = 4572000;
+
+/// Centered title, on the theme's section band when there is a theme
+fn framed_slide(title: &str, theme: Option<&Theme>) -> SlideContent {
+    let slide = SlideContent::new(title).layout(SlideLayout::CenteredTitle);
+    match theme {
+        Some(theme) => slide.section_band(theme.section_gradient()).title_color(theme.background),
+        None => slide,
+    }
+}
+
+/// Full-width line of centered text with a transparent fill
+///
+/// The fill is set so theme defaults don't paint the line as a shape.
+fn text_line(text: &str, y: u32, size: u32, color: Option<&str>, theme: Option<&Theme>) -> Shape {
+    let mut run = FormattedText::new(text).font_size(size);
+    if let Some(color) = color {
+        run = run.color(color);
+    }
+    let fill = theme.map_or("FFFFFF", |t| t.background);
+    Shape::new(ShapeType::Rectangle, MARGIN, y, SLIDE_WIDTH - 2 * MARGIN, LINE_HEIGHT)
+        .with_fill(ShapeFill::new(fill).with_transparency(100))
+        .with_rich_text(vec![run])
 }
 
 /// Slide builder
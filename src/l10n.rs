@@ -0,0 +1,367 @@
+//! Localization: extract and re-inject translatable strings
+//!
+//! [`extract`] lists the text of every run on slides and speaker notes as
+//! [`TranslationUnit`]s with stable IDs of the form
+//! `<part>/<shape id>/<run>`, e.g. `slide3/4/2` for the second run of the
+//! shape with `cNvPr id="4"` in `ppt/slides/slide3.xml`. The units are
+//! written as XLIFF 1.2 ([`to_xliff`]) or a flat JSON object ([`to_json`])
+//! for translators; [`apply`] replaces the text of each run by ID in place,
+//! so fonts, colors, layout and everything else in the deck are untouched.
+//!
+//! ```rust,ignore
+//! let mut package = Package::open("deck.pptx")?;
+//! std::fs::write("strings.xliff", l10n::to_xliff(&l10n::extract(&package), "deck.pptx", "en"))?;
+//!
+//! let translations = l10n::parse_xliff(&std::fs::read_to_string("strings.fr.xliff")?)?;
+//! let report = l10n::apply(&mut package, &translations)?;
+//! package.save("deck.fr.pptx")?;
+//! ```
+//!
+//! Runs of fields such as slide numbers are not extracted.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::core::escape_xml;
+use crate::exc::{PptxError, Result};
+use crate::opc::Package;
+use crate::oxml::xmlchemy::XmlParser;
+
+/// One translatable run
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranslationUnit {
+    /// Stable ID, e.g. `slide3/4/2`
+    pub id: String,
+    /// Text in the deck
+    pub source: String,
+    /// Translated text, if any
+    pub target: Option<String>,
+}
+
+/// Outcome of [`apply`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ApplyReport {
+    /// Runs whose text was replaced
+    pub translated: usize,
+    /// IDs in the deck without a translation (left as they were)
+    pub missing: Vec<String>,
+    /// Translation IDs that match no run in the deck
+    pub unknown: Vec<String>,
+}
+
+/// `<a:t>` run text found in a part
+struct TextSpan {
+    id: String,
+    /// Byte range of the escaped text between `<a:t>` and `</a:t>`
+    range: Range<usize>,
+    text: String,
+}
+
+/// Slide and notes parts in slide-number order
+fn text_parts(package: &Package) -> Vec<(String, String)> {
+    let mut parts: Vec<(u8, u32, String, String)> = package
+        .part_paths()
+        .into_iter()
+        .filter_map(|path| {
+            let file = path.strip_suffix(".xml")?;
+            let (kind, stem) = if let Some(stem) = file.strip_prefix("ppt/slides/") {
+                (0, stem)
+            } else {
+                (1, file.strip_prefix("ppt/notesSlides/")?)
+            };
+            let number = stem.trim_start_matches(|c: char| !c.is_ascii_digit()).parse().ok()?;
+            Some((kind, number, stem.to_string(), path.to_string()))
+        })
+        .collect();
+    parts.sort();
+    parts.into_iter().map(|(_, _, stem, path)| (stem, path)).collect()
+}
+
+/// Find run text in part XML, attributing each run to the nearest preceding shape
+fn scan(stem: &str, xml: &str) -> Vec<TextSpan> {
+    let mut spans = Vec::new();
+    let mut shape_id = String::from("0");
+    let mut run_counts: HashMap<String, usize> = HashMap::new();
+    let mut in_field = false;
+    let mut pos = 0;
+
+    while let Some(offset) = xml[pos..].find('<') {
+        let start = pos + offset;
+        let Some(end) = xml[start..].find('>').map(|e| start + e) else { break };
+        let tag = &xml[start + 1..end];
+        pos = end + 1;
+        let (name, rest) = tag.split_once(|c: char| c.is_whitespace()).unwrap_or((tag, ""));
+        let local = |name: &str| name.rsplit(':').next().unwrap_or(name).to_string();
+        match local(name.trim_end_matches('/')).as_str() {
+            "cNvPr" if !name.starts_with('/') => {
+                if let Some(id) = attr_value(rest, "id") {
+                    shape_id = id.to_string();
+                }
+            }
+            "fld" => in_field = !name.starts_with('/') && !tag.ends_with('/'),
+            "t" if !name.starts_with('/') && !tag.ends_with('/') && !in_field => {
+                let Some(close) = xml[pos..].find("</").map(|c| pos + c) else { break };
+                let count = run_counts.entry(shape_id.clone()).or_default();
+                *count += 1;
+                spans.push(TextSpan {
+                    id: format!("{stem}/{shape_id}/{count}"),
+                    range: pos..close,
+                    text: unescape(&xml[pos..close]),
+                });
+                pos = close;
+            }
+            _ => {}
+        }
+    }
+    spans
+}
+
+fn attr_value<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let Some(end) = rest.find(';') else { break };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16))
+                .or_else(|| entity.strip_prefix('#').map(str::parse))
+                .and_then(|n| n.ok())
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Every translatable run on slides and notes, in slide order
+pub fn extract(package: &Package) -> Vec<TranslationUnit> {
+    let mut units = Vec::new();
+    for (stem, path) in text_parts(package) {
+        let Some(xml) = package.get_part_string(&path) else { continue };
+        units.extend(
+            scan(&stem, &xml)
+                .into_iter()
+                .filter(|span| !span.text.trim().is_empty())
+                .map(|span| TranslationUnit { id: span.id, source: span.text, target: None }),
+        );
+    }
+    units
+}
+
+/// Replace run text by ID with the targets of `translations`
+///
+/// Units without a target are ignored. Only the text between `<a:t>` and
+/// `</a:t>` changes, so all formatting is preserved.
+pub fn apply(package: &mut Package, translations: &[TranslationUnit]) -> Result<ApplyReport> {
+    let mut targets: HashMap<&str, &str> = translations
+        .iter()
+        .filter_map(|u| u.target.as_deref().map(|t| (u.id.as_str(), t)))
+        .collect();
+    let mut report = ApplyReport::default();
+
+    for (stem, path) in text_parts(package) {
+        let Some(xml) = package.get_part_string(&path) else { continue };
+        let mut out = String::with_capacity(xml.len());
+        let mut copied = 0;
+        for span in scan(&stem, &xml) {
+            match targets.remove(span.id.as_str()) {
+                Some(target) => {
+                    out.push_str(&xml[copied..span.range.start]);
+                    out.push_str(&escape_xml(target));
+                    copied = span.range.end;
+                    report.translated += 1;
+                }
+                None if !span.text.trim().is_empty() => report.missing.push(span.id),
+                None => {}
+            }
+        }
+        if copied > 0 {
+            out.push_str(&xml[copied..]);
+            package.add_part(path, out.into_bytes());
+        }
+    }
+
+    report.unknown = translations
+        .iter()
+        .filter(|u| targets.contains_key(u.id.as_str()))
+        .map(|u| u.id.clone())
+        .collect();
+    Ok(report)
+}
+
+/// XLIFF 1.2 document with one `trans-unit` per run
+pub fn to_xliff(units: &[TranslationUnit], original: &str, source_language: &str) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<xliff version=\"1.2\" xmlns=\"urn:oasis:names:tc:xliff:document:1.2\">\n");
+    xml.push_str(&format!(
+        "  <file original=\"{}\" source-language=\"{}\" datatype=\"plaintext\">\n    <body>\n",
+        crate::core::escape_xml_attr(original),
+        crate::core::escape_xml_attr(source_language)
+    ));
+    for unit in units {
+        xml.push_str(&format!(
+            "      <trans-unit id=\"{}\" xml:space=\"preserve\">\n        <source>{}</source>\n",
+            crate::core::escape_xml_attr(&unit.id),
+            escape_xml(&unit.source)
+        ));
+        if let Some(target) = &unit.target {
+            xml.push_str(&format!("        <target>{}</target>\n", escape_xml(target)));
+        }
+        xml.push_str("      </trans-unit>\n");
+    }
+    xml.push_str("    </body>\n  </file>\n</xliff>\n");
+    xml
+}
+
+/// Translation units of an XLIFF 1.2 document
+pub fn parse_xliff(xml: &str) -> Result<Vec<TranslationUnit>> {
+    let root = XmlParser::parse_str(xml)?;
+    root.find_all_descendants("trans-unit")
+        .into_iter()
+        .map(|unit| {
+            let id = unit
+                .attr("id")
+                .ok_or_else(|| PptxError::InvalidValue("XLIFF trans-unit without id".to_string()))?;
+            Ok(TranslationUnit {
+                id: id.to_string(),
+                source: unit.find("source").map(|s| s.text_content()).unwrap_or_default(),
+                target: unit.find("target").map(|t| t.text_content()),
+            })
+        })
+        .collect()
+}
+
+/// JSON object mapping IDs to text, in extraction order
+pub fn to_json(units: &[TranslationUnit]) -> String {
+    let entries: Vec<String> = units
+        .iter()
+        .map(|u| {
+            let text = u.target.as_ref().unwrap_or(&u.source);
+            format!("  {}: {}", serde_json::Value::from(u.id.as_str()), serde_json::Value::from(text.as_str()))
+        })
+        .collect();
+    format!("{{\n{}\n}}\n", entries.join(",\n"))
+}
+
+/// Translation units of a JSON object mapping IDs to translated text
+pub fn parse_json(json: &str) -> Result<Vec<TranslationUnit>> {
+    let map: HashMap<String, String> = serde_json::from_str(json)
+        .map_err(|e| PptxError::InvalidValue(format!("invalid translations JSON: {e}")))?;
+    Ok(map
+        .into_iter()
+        .map(|(id, text)| TranslationUnit { id, source: String::new(), target: Some(text) })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::Presentation;
+    use crate::generator::{Shape, ShapeType, SlideContent};
+
+    fn package() -> Package {
+        let mut slide = SlideContent::new("Quarterly <Results>").add_bullet("Revenue up").add_bullet("Costs down");
+        slide.notes = Some("Mention the new region".to_string());
+        slide.shapes.push(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 914400).with_text("Q&A"));
+        let pres = Presentation::new().add_slide(slide).add_slide(SlideContent::new("Thanks"));
+        Package::open_reader(std::io::Cursor::new(pres.build().unwrap())).unwrap()
+    }
+
+    fn source<'a>(units: &'a [TranslationUnit], text: &str) -> &'a TranslationUnit {
+        units.iter().find(|u| u.source == text).unwrap_or_else(|| panic!("{text} not extracted: {units:?}"))
+    }
+
+    #[test]
+    fn test_extract_runs_with_stable_ids() {
+        let units = extract(&package());
+        assert!(units[0].id.starts_with("slide1/"));
+        assert_eq!(units[0].source, "Quarterly <Results>");
+        source(&units, "Revenue up");
+        source(&units, "Q&A");
+        assert!(source(&units, "Mention the new region").id.starts_with("notesSlide1/"));
+        assert!(source(&units, "Thanks").id.starts_with("slide2/"));
+
+        let mut ids: Vec<_> = units.iter().map(|u| &u.id).collect();
+        ids.dedup();
+        assert_eq!(ids.len(), units.len());
+        assert_eq!(extract(&package()), units);
+    }
+
+    #[test]
+    fn test_xliff_round_trip_and_apply() {
+        let mut package = package();
+        let mut units = extract(&package);
+        for unit in &mut units {
+            if unit.source == "Revenue up" {
+                unit.target = Some("Chiffre d'affaires en hausse".to_string());
+            }
+            if unit.source == "Quarterly <Results>" {
+                unit.target = Some("Résultats <trimestriels>".to_string());
+            }
+        }
+        let xliff = to_xliff(&units, "deck.pptx", "en");
+        assert!(xliff.contains("<source>Quarterly &lt;Results&gt;</source>"));
+        let parsed = parse_xliff(&xliff).unwrap();
+        assert_eq!(parsed, units);
+
+        let report = apply(&mut package, &parsed).unwrap();
+        assert_eq!(report.translated, 2);
+        assert_eq!(report.missing.len(), units.len() - 2);
+        assert!(report.unknown.is_empty());
+
+        let localized = extract(&package);
+        source(&localized, "Chiffre d'affaires en hausse");
+        source(&localized, "Résultats <trimestriels>");
+        source(&localized, "Costs down");
+        let ids = |units: &[TranslationUnit]| units.iter().map(|u| u.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&localized), ids(&units));
+    }
+
+    #[test]
+    fn test_json_translations() {
+        let mut package = package();
+        let units = extract(&package);
+        let json = to_json(&units);
+        assert!(json.contains(&format!("\"{}\": \"Quarterly <Results>\"", units[0].id)));
+
+        let translations = parse_json(&format!("{{\"{}\": \"Bilan\", \"slide9/1/1\": \"x\"}}", units[0].id)).unwrap();
+        let report = apply(&mut package, &translations).unwrap();
+        assert_eq!(report.translated, 1);
+        assert_eq!(report.unknown, ["slide9/1/1"]);
+        assert_eq!(extract(&package)[0].source, "Bilan");
+        assert!(parse_json("[1, 2]").is_err());
+    }
+
+    #[test]
+    fn test_scan_skips_fields_and_unescapes() {
+        let xml = r#"<p:sp><p:nvSpPr><p:cNvPr id="7" name="x"/></p:nvSpPr><a:p><a:r><a:t>A &amp; B&#x21;</a:t></a:r><a:fld id="{1}" type="slidenum"><a:t>3</a:t></a:fld><a:r><a:t/></a:r><a:r><a:t>C</a:t></a:r></a:p></p:sp>"#;
+        let spans = scan("slide1", xml);
+        let found: Vec<_> = spans.iter().map(|s| (s.id.as_str(), s.text.as_str())).collect();
+        assert_eq!(found, [("slide1/7/1", "A & B!"), ("slide1/7/2", "C")]);
+    }
+}
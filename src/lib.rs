@@ -16,83 +16,202 @@
 //! std::fs::write("output.pptx", pptx_data).unwrap();
 //! ```
 //!
-//! # Module Organization
+//! # Public API
 //!
-//! - **core** - Core traits (`ToXml`, `Positioned`, `Styled`) and utilities
+//! The stable surface is the crate root, [`prelude`] and [`api`], plus the
+//! feature modules they point into:
+//!
+//! - **core** - Core traits (`ToXml`, `Positioned`, `Styled`), XML escaping and EMU math
 //! - **elements** - Unified element types (Color, Position, Size, Transform)
-//! - **generator** - PPTX file generation with ZIP packaging and XML creation
-//! - **parts** - Package parts (SlidePart, ImagePart, ChartPart)
-//! - **integration** - High-level builders for presentations
-//! - **opc** - Open Packaging Convention (ZIP) handling
-//! - **oxml** - Office XML parsing and manipulation
+//! - **generator** - Slide content types and PPTX generation
+//! - **export** / **import** - HTML, PDF, PNG and notes export; PPTX import
+//! - **templates**, **style_guide**, **stylesheet**, **l10n**, **analysis** - Higher-level tooling
 //! - **exc** - Error types
+//!
+//! Low-level modules may change in any minor release while the crate is
+//! 0.x; prefer their root re-exports ([`PresentationReader`],
+//! [`PresentationEditor`], [`Template`], [`Package`], [`PptxRepair`]):
+//!
+//! - **oxml** - Office XML parsing and editing
+//! - **opc** - Open Packaging Convention (ZIP) handling
+//! - **parts** - Package parts (SlidePart, ImagePart, ChartPart)
+//! - **integration**, **enums**, **testdata** - Legacy builders, enumerations, seeded test decks
+//!
+//! Remaining modules are internal and hidden from the docs. See
+//! `docs/API_STABILITY.md` for the semver and deprecation policy.
+//!
+//! # Tracing
+//!
+//! With the `tracing` feature, opening packages, parsing parts, emitting
+//! slides and writing the ZIP are reported as `tracing` spans and events
+//! (see the table in `src/instrument.rs`); install any subscriber to log or
+//! time them.
+//!
+//! # Features
+//!
+//! The defaults build everything. Library-only users embedding deck
+//! generation can keep just `std` and add back what they need:
+//!
+//! - `markdown` - `cli::parse_markdown` and friends, Mermaid diagrams and
+//!   syntax-highlighted code blocks (plain monospace text without it)
+//! - `repair` - `PptxRepair` and `Package::strip_personal_info`
+//! - `media` - `Image::from_path` and pixel sizes in `Image::from_source`;
+//!   slide zoom thumbnails are blank without it
+//! - `encryption` - password-protected files (`Presentation::save_encrypted`,
+//!   `Package::open_encrypted`)
+//! - `cli` - the `pptcli` binary and its command types (implies `markdown` and `media`)
+//! - `web2ppt`, `polars`, `tracing` - see their modules
+//!
+//! # `no_std`
+//!
+//! Without the default `std` feature the crate is `no_std` (needs `alloc`)
+//! and only the dependency-free [`core`] layer is built, for embedded and
+//! serverless targets that just need the XML building blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 // Core traits and utilities
 pub mod core;
 
+// `tracing` spans and events (no-ops without the `tracing` feature)
+#[cfg(feature = "std")]
+mod instrument;
+
 // Unified element types
+#[cfg(feature = "std")]
 pub mod elements;
 
 // Main functionality
+#[cfg(feature = "std")]
 pub mod generator;
+#[cfg(feature = "std")]
 pub mod integration;
+#[cfg(feature = "std")]
 pub mod cli;
 
-// Supporting modules
+// Supporting modules; hidden ones are internal and public only for
+// compatibility (see docs/API_STABILITY.md)
+#[cfg(feature = "std")]
+#[doc(hidden)]
 pub mod config;
+#[cfg(feature = "std")]
+#[doc(hidden)]
 pub mod constants;
+#[cfg(feature = "std")]
 pub mod enums;
+#[cfg(feature = "std")]
 pub mod exc;
+#[cfg(feature = "std")]
+#[doc(hidden)]
 pub mod util;
+#[cfg(feature = "std")]
 pub mod opc;
+#[cfg(feature = "std")]
 pub mod oxml;
+#[cfg(feature = "std")]
 pub mod parts;
 
 // Public API
+#[cfg(feature = "std")]
 pub mod api;
+#[cfg(feature = "std")]
+#[doc(hidden)]
 pub mod types;
+#[cfg(feature = "std")]
+#[doc(hidden)]
 pub mod shared;
 
 // Easy-to-use prelude
+#[cfg(feature = "std")]
 pub mod prelude;
 
 // Templates for common presentations
+#[cfg(feature = "std")]
 pub mod templates;
 
 // Export functionality
+#[cfg(feature = "std")]
 pub mod export;
 
 // Import functionality
+#[cfg(feature = "std")]
 pub mod import;
 
+// Duration and pacing analysis
+#[cfg(feature = "std")]
+pub mod analysis;
+
+// Style guide enforcement and linting
+#[cfg(feature = "std")]
+pub mod style_guide;
+
+// Named text styles referenced from bullets, shapes and table cells
+#[cfg(feature = "std")]
+pub mod stylesheet;
+
+// Footnote-style citations with footnotes or a sources slide
+#[cfg(feature = "std")]
+pub mod citations;
+
+// Localization (extract and re-inject translatable strings)
+#[cfg(feature = "std")]
+pub mod l10n;
+
+// Seeded random decks for examples and property tests
+#[cfg(feature = "std")]
+pub mod testdata;
+
 // Web2PPT module (optional feature)
 #[cfg(feature = "web2ppt")]
 pub mod web2ppt;
 
 // Re-exports for convenience
+#[cfg(feature = "std")]
 pub use api::Presentation;
-pub use core::{ToXml, escape_xml};
-pub use elements::{Color, RgbColor, SchemeColor, Position, Size, Transform};
+pub use core::{ToXml, escape_xml, escape_xml_attr};
+#[cfg(feature = "std")]
+pub use elements::{Color, ColorInput, RgbColor, SchemeColor, ColorParseError, Position, Size, Transform};
+#[cfg(feature = "std")]
 pub use exc::{PptxError, Result};
+#[cfg(feature = "std")]
 pub use generator::{
-    create_pptx, create_pptx_with_content, SlideContent, SlideLayout,
-    TextFormat, FormattedText,
-    Table, TableRow, TableCell, TableBuilder,
-    Shape, ShapeType, ShapeFill, ShapeLine,
-    Image, ImageBuilder, ImageSource,
-    Chart, ChartType, ChartSeries, ChartBuilder,
+    create_pptx, create_pptx_with_content, create_pptx_with_print_settings, write_pptx_with_content, SlideContent, SlideLayout, Region, CustomLayout, PicturePlaceholder, ImageGrid,
+    TextFormat, FormattedText, Field, DateTimeFormat,
+    Table, TableRow, TableCell, TableBuilder, RowFormat, RowValues, Column, Heatmap, Palette, QuadrantChart, QuadrantPoint, Swimlanes, SwimlaneStep, Calendar, Funnel, FunnelStage, Stepper, StepState,
+    Shape, ShapeType, ShapeFill, ShapeLine, CustomGeometry, GeometryPath, Icon, Annotations,
+    Image, ImageBuilder, ImageSource, ImagePlaceholder, Fit,
+    Chart, ChartType, ChartSeries, ChartBuilder, ChartStyle, ChartTemplate, ReferenceLine, TargetBand, LegendPosition, LegendOverflow, DataTable, DisplayUnit,
     // Bullet styles
-    BulletStyle, BulletPoint,
+    BulletStyle, BulletPoint, Density,
     // New element types
-    Connector, ConnectorType, ConnectorLine, ArrowType, ArrowSize, ConnectionSite, LineDash,
-    Hyperlink, HyperlinkAction,
+    Connector, ConnectorType, ConnectorLine, ArrowType, ArrowSize, ConnectionSite, LineDash, Locks,
+    Hyperlink, HyperlinkAction, ActionSound,
     GradientFill, GradientType, GradientDirection, GradientStop, PresetGradients,
     Video, Audio, VideoFormat, AudioFormat, VideoOptions, AudioOptions,
+    PrintSettings, PrintWhat, PrintColorMode,
+    SlideSize, Anchor, Placement, Snippet, ThemeFonts,
+    CsvOptions, CsvColumn, CsvData, Workbook, SheetRange, ChartDataSource,
+    VizSpec, Visualization, SlideSpec,
+    create_pptx_with_progress, BuildProgress, CancelToken, BuildCancelled, BudgetExceeded,
+    Warning, WarningKind, Warnings, GenerationOutput, CompatProfile,
 };
+#[cfg(feature = "polars")]
+pub use generator::NumberFormat;
+#[cfg(feature = "qr")]
+pub use generator::QrCode;
+#[cfg(feature = "std")]
 pub use integration::{PresentationBuilder, SlideBuilder, PresentationMetadata};
+#[cfg(feature = "repair")]
 pub use oxml::repair::{PptxRepair, RepairIssue, RepairResult};
+#[cfg(feature = "std")]
+pub use oxml::{PresentationReader, PresentationInfo, PresentationEditor, SlideParser, Template};
+#[cfg(feature = "std")]
+pub use opc::Package;
 
 // Parts re-exports
+#[cfg(feature = "std")]
 pub use parts::{
     Part, PartType, ContentType,
     PresentationPart, SlidePart, SlideLayoutPart, LayoutType,
@@ -112,4 +231,5 @@ pub use web2ppt::{
     html_to_pptx, html_to_pptx_with_options, url_to_pptx, url_to_pptx_with_options,
 };
 
-pub const VERSION: &str = "1.0.3";
+/// Version of this crate
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -8,6 +8,8 @@ pub mod CONTENT_TYPE {
     pub const PML_TEMPLATE_MAIN: &str = "application/vnd.openxmlformats-officedocument.presentationml.template.main+xml";
     pub const PML_SLIDESHOW_MAIN: &str = "application/vnd.openxmlformats-officedocument.presentationml.slideshow.main+xml";
     pub const OPC_CORE_PROPERTIES: &str = "application/vnd.openxmlformats-package.core-properties+xml";
+    pub const OPC_DIGITAL_SIGNATURE_ORIGIN: &str = "application/vnd.openxmlformats-package.digital-signature-origin";
+    pub const OPC_DIGITAL_SIGNATURE_XMLSIGNATURE: &str = "application/vnd.openxmlformats-package.digital-signature-xmlsignature+xml";
     pub const PML_NOTES_MASTER: &str = "application/vnd.openxmlformats-officedocument.presentationml.notesMaster+xml";
     pub const PML_NOTES_SLIDE: &str = "application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml";
     pub const PML_SLIDE: &str = "application/vnd.openxmlformats-officedocument.presentationml.slide+xml";
@@ -48,6 +50,8 @@ pub mod RELATIONSHIP_TYPE {
     pub const MEDIA: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/media";
     pub const CHART: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
     pub const EMBEDDED_PACKAGE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships/oleObject";
+    pub const DIGITAL_SIGNATURE_ORIGIN: &str = "http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/origin";
+    pub const DIGITAL_SIGNATURE: &str = "http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/signature";
 }
 
 /// XML namespaces
@@ -0,0 +1,493 @@
+//! Password-to-open encryption
+//!
+//! PowerPoint stores a password-protected deck as a compound file (the
+//! container format of legacy `.ppt`) holding the encrypted ZIP package and
+//! the parameters needed to decrypt it. This module writes and reads the
+//! agile encryption scheme of ECMA-376 Part 2 / MS-OFFCRYPTO that Office
+//! uses by default: AES-256 in CBC mode with SHA-512 and 100,000 rounds of
+//! password hashing, plus an HMAC over the encrypted package so tampering
+//! is caught before anything is decrypted.
+//!
+//! ```
+//! use ppt_rs::opc::crypto::{decrypt, encrypt};
+//! use ppt_rs::opc::{is_encrypted, Package};
+//! use ppt_rs::{Presentation, PptxError, SlideContent};
+//!
+//! let pptx = Presentation::new().add_slide(SlideContent::new("Board pack")).build()?;
+//! let locked = encrypt(&pptx, "s3cret")?;
+//! assert!(is_encrypted(&locked));
+//! assert!(matches!(Package::open_reader(std::io::Cursor::new(&locked)), Err(PptxError::Encrypted)));
+//! assert_eq!(decrypt(&locked, "s3cret")?, pptx);
+//! # Ok::<(), PptxError>(())
+//! ```
+//!
+//! Files encrypted with the older standard (RC4 or AES-128 "standard
+//! encryption") scheme are reported as unsupported.
+
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+
+use aes::Aes256;
+use cbc::cipher::block_padding::NoPadding;
+use cbc::cipher::{BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha512};
+
+use crate::exc::{PptxError, Result};
+use crate::export::html::base64_encode;
+use crate::generator::images::base64_decode;
+use crate::oxml::xmlchemy::{XmlElement, XmlParser};
+use super::Package;
+
+/// Rounds of password hashing Office uses
+const SPIN_COUNT: u32 = 100_000;
+/// Most rounds of password hashing accepted from a file, the limit MS-OFFCRYPTO sets
+const MAX_SPIN_COUNT: u32 = 10_000_000;
+const SALT_SIZE: usize = 16;
+const BLOCK_SIZE: usize = 16;
+const KEY_SIZE: usize = 32;
+const HASH_SIZE: usize = 64;
+/// The package is encrypted in segments of this many bytes
+const SEGMENT_SIZE: usize = 4096;
+
+/// Block keys that derive each secret from the password hash or key salt
+const VERIFIER_INPUT_BLOCK: [u8; 8] = [0xfe, 0xa7, 0xd2, 0x76, 0x3b, 0x4b, 0x9e, 0x79];
+const VERIFIER_HASH_BLOCK: [u8; 8] = [0xd7, 0xaa, 0x0f, 0x6d, 0x30, 0x61, 0x34, 0x4e];
+const KEY_VALUE_BLOCK: [u8; 8] = [0x14, 0x6e, 0x0b, 0xe7, 0xab, 0xac, 0xd0, 0xd6];
+const HMAC_KEY_BLOCK: [u8; 8] = [0x5f, 0xb2, 0xad, 0x01, 0x0c, 0xb9, 0xe1, 0xf6];
+const HMAC_VALUE_BLOCK: [u8; 8] = [0xa0, 0x67, 0x7f, 0x02, 0xb2, 0x2c, 0x84, 0x33];
+
+const ENCRYPTION_INFO: &str = "/EncryptionInfo";
+const ENCRYPTED_PACKAGE: &str = "/EncryptedPackage";
+const DATA_SPACES: &str = "/\u{6}DataSpaces";
+const TRANSFORM_ID: &str = "{FF9A3F03-56EF-4613-BDD5-5A41C1D07246}";
+const PASSWORD_KEY_ENCRYPTOR: &str = "http://schemas.microsoft.com/office/2006/keyEncryptor/password";
+
+/// Encrypt the PPTX bytes `package` so it opens only with `password`
+pub fn encrypt(package: &[u8], password: &str) -> Result<Vec<u8>> {
+    encrypt_with_spin_count(package, password, SPIN_COUNT)
+}
+
+/// [`encrypt`] with fewer hashing rounds, to keep tests fast
+pub(crate) fn encrypt_with_spin_count(package: &[u8], password: &str, spin_count: u32) -> Result<Vec<u8>> {
+    let key: [u8; KEY_SIZE] = random()?;
+    let key_salt: [u8; SALT_SIZE] = random()?;
+    let password_salt: [u8; SALT_SIZE] = random()?;
+    let verifier: [u8; SALT_SIZE] = random()?;
+    let hmac_key: [u8; HASH_SIZE] = random()?;
+
+    let hash = password_hash(password, &password_salt, spin_count);
+    let stream = encrypt_segments(&key, &key_salt, package);
+    let info = EncryptionInfo {
+        key_salt: key_salt.to_vec(),
+        encrypted_hmac_key: encrypt_aes(&key, &derive_iv(&key_salt, &HMAC_KEY_BLOCK), &hmac_key),
+        encrypted_hmac_value: encrypt_aes(&key, &derive_iv(&key_salt, &HMAC_VALUE_BLOCK), &hmac(&hmac_key, &stream)),
+        spin_count,
+        password_salt: password_salt.to_vec(),
+        encrypted_verifier_hash_input: encrypt_aes(&derive_key(&hash, &VERIFIER_INPUT_BLOCK), &password_salt, &verifier),
+        encrypted_verifier_hash_value: encrypt_aes(&derive_key(&hash, &VERIFIER_HASH_BLOCK), &password_salt, &sha512(&[&verifier])),
+        encrypted_key_value: encrypt_aes(&derive_key(&hash, &KEY_VALUE_BLOCK), &password_salt, &key),
+    };
+    write_compound_file(&info.to_stream(), &stream)
+}
+
+/// Decrypt a password-protected file back to its PPTX bytes
+///
+/// Returns `PptxError::WrongPassword` if `password` doesn't open it.
+pub fn decrypt(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    let (info, stream) = read_compound_file(data)?;
+    let info = EncryptionInfo::parse(&info)?;
+
+    let hash = password_hash(password, &info.password_salt, info.spin_count);
+    let verifier = decrypt_aes(&derive_key(&hash, &VERIFIER_INPUT_BLOCK), &info.password_salt, &info.encrypted_verifier_hash_input)?;
+    let verifier_hash = decrypt_aes(&derive_key(&hash, &VERIFIER_HASH_BLOCK), &info.password_salt, &info.encrypted_verifier_hash_value)?;
+    let verifier = verifier.get(..SALT_SIZE).ok_or_else(|| corrupt("password verifier"))?;
+    if verifier_hash.get(..HASH_SIZE) != Some(&sha512(&[verifier])[..]) {
+        return Err(PptxError::WrongPassword);
+    }
+
+    let key = decrypt_aes(&derive_key(&hash, &KEY_VALUE_BLOCK), &info.password_salt, &info.encrypted_key_value)?;
+    let key = key.get(..KEY_SIZE).ok_or_else(|| corrupt("package key"))?;
+    let hmac_key = decrypt_aes(key, &derive_iv(&info.key_salt, &HMAC_KEY_BLOCK), &info.encrypted_hmac_key)?;
+    let hmac_value = decrypt_aes(key, &derive_iv(&info.key_salt, &HMAC_VALUE_BLOCK), &info.encrypted_hmac_value)?;
+    let hmac_key = hmac_key.get(..HASH_SIZE).ok_or_else(|| corrupt("integrity key"))?;
+    if hmac_value.get(..HASH_SIZE) != Some(&hmac(hmac_key, &stream)[..]) {
+        return Err(corrupt("package (integrity check failed)"));
+    }
+    decrypt_segments(key, &info.key_salt, &stream)
+}
+
+impl Package {
+    /// Open a password-protected package
+    pub fn open_encrypted<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        Self::open_encrypted_reader(std::fs::File::open(path)?, password)
+    }
+
+    /// Open a password-protected package from a reader
+    pub fn open_encrypted_reader<R: Read>(mut reader: R, password: &str) -> Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+        Self::open_reader(Cursor::new(decrypt(&data, password)?))
+    }
+
+    /// Save the package so it opens only with `password`
+    pub fn save_encrypted<P: AsRef<Path>>(&self, path: P, password: &str) -> Result<()> {
+        self.save_encrypted_writer(std::fs::File::create(path)?, password)
+    }
+
+    /// Write the package, encrypted with `password`, to a writer
+    pub fn save_encrypted_writer<W: Write>(&self, mut writer: W, password: &str) -> Result<()> {
+        let mut zip = Cursor::new(Vec::new());
+        self.save_writer(&mut zip)?;
+        writer.write_all(&encrypt(zip.get_ref(), password)?)?;
+        Ok(())
+    }
+}
+
+/// What the `EncryptionInfo` stream holds
+struct EncryptionInfo {
+    key_salt: Vec<u8>,
+    encrypted_hmac_key: Vec<u8>,
+    encrypted_hmac_value: Vec<u8>,
+    spin_count: u32,
+    password_salt: Vec<u8>,
+    encrypted_verifier_hash_input: Vec<u8>,
+    encrypted_verifier_hash_value: Vec<u8>,
+    encrypted_key_value: Vec<u8>,
+}
+
+impl EncryptionInfo {
+    /// Version 4.4 header and the agile encryption XML
+    fn to_stream(&self) -> Vec<u8> {
+        let cipher = format!(
+            r#"saltSize="{SALT_SIZE}" blockSize="{BLOCK_SIZE}" keyBits="{}" hashSize="{HASH_SIZE}" cipherAlgorithm="AES" cipherChaining="ChainingModeCBC" hashAlgorithm="SHA512""#,
+            KEY_SIZE * 8
+        );
+        let xml = format!(
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\r\n",
+                r#"<encryption xmlns="http://schemas.microsoft.com/office/2006/encryption" xmlns:p="{encryptor}">"#,
+                r#"<keyData {cipher} saltValue="{key_salt}"/>"#,
+                r#"<dataIntegrity encryptedHmacKey="{hmac_key}" encryptedHmacValue="{hmac_value}"/>"#,
+                r#"<keyEncryptors><keyEncryptor uri="{encryptor}"><p:encryptedKey spinCount="{spin_count}" {cipher} saltValue="{password_salt}" "#,
+                r#"encryptedVerifierHashInput="{verifier_input}" encryptedVerifierHashValue="{verifier_hash}" encryptedKeyValue="{key_value}"/>"#,
+                "</keyEncryptor></keyEncryptors></encryption>",
+            ),
+            encryptor = PASSWORD_KEY_ENCRYPTOR,
+            cipher = cipher,
+            key_salt = base64_encode(&self.key_salt),
+            hmac_key = base64_encode(&self.encrypted_hmac_key),
+            hmac_value = base64_encode(&self.encrypted_hmac_value),
+            spin_count = self.spin_count,
+            password_salt = base64_encode(&self.password_salt),
+            verifier_input = base64_encode(&self.encrypted_verifier_hash_input),
+            verifier_hash = base64_encode(&self.encrypted_verifier_hash_value),
+            key_value = base64_encode(&self.encrypted_key_value),
+        );
+        let mut stream = vec![4, 0, 4, 0, 0x40, 0, 0, 0];
+        stream.extend_from_slice(xml.as_bytes());
+        stream
+    }
+
+    fn parse(stream: &[u8]) -> Result<Self> {
+        let (version, xml) = stream.split_at_checked(8).ok_or_else(|| corrupt("EncryptionInfo"))?;
+        if version[..4] != [4, 0, 4, 0] {
+            return Err(PptxError::InvalidValue(format!(
+                "Unsupported encryption version {}.{}; only agile encryption (4.4) is supported",
+                u16::from_le_bytes([version[0], version[1]]),
+                u16::from_le_bytes([version[2], version[3]])
+            )));
+        }
+        let root = XmlParser::parse_str(&String::from_utf8_lossy(xml))?;
+        let key_data = root.find("keyData").ok_or_else(|| corrupt("EncryptionInfo keyData"))?;
+        let integrity = root.find("dataIntegrity").ok_or_else(|| corrupt("EncryptionInfo dataIntegrity"))?;
+        let encrypted_key = root.find("keyEncryptors")
+            .into_iter()
+            .flat_map(|encryptors| encryptors.find_all("keyEncryptor"))
+            .filter(|encryptor| encryptor.attr("uri") == Some(PASSWORD_KEY_ENCRYPTOR))
+            .find_map(|encryptor| encryptor.find("encryptedKey"))
+            .ok_or_else(|| PptxError::InvalidValue("File is not protected by a password".to_string()))?;
+        check_cipher(key_data)?;
+        check_cipher(encrypted_key)?;
+        let spin_count: u32 = encrypted_key.attr("spinCount")
+            .and_then(|count| count.parse().ok())
+            .ok_or_else(|| corrupt("spinCount"))?;
+        if spin_count > MAX_SPIN_COUNT {
+            return Err(PptxError::InvalidValue(format!(
+                "Encrypted file asks for {spin_count} rounds of password hashing; at most {MAX_SPIN_COUNT} are allowed"
+            )));
+        }
+
+        Ok(EncryptionInfo {
+            key_salt: decode_attr(key_data, "saltValue")?,
+            encrypted_hmac_key: decode_attr(integrity, "encryptedHmacKey")?,
+            encrypted_hmac_value: decode_attr(integrity, "encryptedHmacValue")?,
+            spin_count,
+            password_salt: decode_attr(encrypted_key, "saltValue")?,
+            encrypted_verifier_hash_input: decode_attr(encrypted_key, "encryptedVerifierHashInput")?,
+            encrypted_verifier_hash_value: decode_attr(encrypted_key, "encryptedVerifierHashValue")?,
+            encrypted_key_value: decode_attr(encrypted_key, "encryptedKeyValue")?,
+        })
+    }
+}
+
+/// Only the AES-256 / SHA-512 / CBC parameters Office writes are supported
+fn check_cipher(element: &XmlElement) -> Result<()> {
+    let expected = [
+        ("cipherAlgorithm", "AES"),
+        ("cipherChaining", "ChainingModeCBC"),
+        ("hashAlgorithm", "SHA512"),
+        ("keyBits", "256"),
+    ];
+    for (name, value) in expected {
+        let actual = element.attr(name).unwrap_or_default();
+        if actual != value {
+            return Err(PptxError::InvalidValue(format!("Unsupported encryption: {name}=\"{actual}\" (expected {value})")));
+        }
+    }
+    Ok(())
+}
+
+fn decode_attr(element: &XmlElement, name: &str) -> Result<Vec<u8>> {
+    element.attr(name)
+        .and_then(|value| base64_decode(value).ok())
+        .ok_or_else(|| corrupt(name))
+}
+
+fn corrupt(what: &str) -> PptxError {
+    PptxError::InvalidValue(format!("Encrypted file has an invalid {what}"))
+}
+
+fn random<const N: usize>() -> Result<[u8; N]> {
+    let mut bytes = [0; N];
+    getrandom::fill(&mut bytes).map_err(|e| PptxError::Generic(format!("No random source for encryption: {e}")))?;
+    Ok(bytes)
+}
+
+fn sha512(parts: &[&[u8]]) -> [u8; HASH_SIZE] {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> [u8; HASH_SIZE] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+/// Salted password hash, rehashed `spin_count` times
+fn password_hash(password: &str, salt: &[u8], spin_count: u32) -> [u8; HASH_SIZE] {
+    let utf16: Vec<u8> = password.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut hash = sha512(&[salt, &utf16]);
+    for i in 0..spin_count {
+        hash = sha512(&[&i.to_le_bytes(), &hash]);
+    }
+    hash
+}
+
+fn derive_key(hash: &[u8], block: &[u8]) -> [u8; KEY_SIZE] {
+    let mut key = [0; KEY_SIZE];
+    key.copy_from_slice(&sha512(&[hash, block])[..KEY_SIZE]);
+    key
+}
+
+fn derive_iv(salt: &[u8], block: &[u8]) -> [u8; BLOCK_SIZE] {
+    let mut iv = [0; BLOCK_SIZE];
+    iv.copy_from_slice(&sha512(&[salt, block])[..BLOCK_SIZE]);
+    iv
+}
+
+/// AES-256-CBC, zero-padded to whole blocks
+fn encrypt_aes(key: &[u8], iv: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut padded = data.to_vec();
+    padded.resize(data.len().next_multiple_of(BLOCK_SIZE), 0);
+    cbc::Encryptor::<Aes256>::new_from_slices(key, iv)
+        .expect("key and IV sizes are fixed")
+        .encrypt_padded_vec_mut::<NoPadding>(&padded)
+}
+
+fn decrypt_aes(key: &[u8], iv: &[u8], data: &[u8]) -> Result<Vec<u8>> {
+    cbc::Decryptor::<Aes256>::new_from_slices(key, iv)
+        .map_err(|_| corrupt("key"))?
+        .decrypt_padded_vec_mut::<NoPadding>(data)
+        .map_err(|_| corrupt("block length"))
+}
+
+/// `EncryptedPackage` stream: the package size, then its encrypted segments
+fn encrypt_segments(key: &[u8], key_salt: &[u8], package: &[u8]) -> Vec<u8> {
+    let mut stream = (package.len() as u64).to_le_bytes().to_vec();
+    for (i, segment) in package.chunks(SEGMENT_SIZE).enumerate() {
+        stream.extend(encrypt_aes(key, &derive_iv(key_salt, &(i as u32).to_le_bytes()), segment));
+    }
+    stream
+}
+
+fn decrypt_segments(key: &[u8], key_salt: &[u8], stream: &[u8]) -> Result<Vec<u8>> {
+    let (size, segments) = stream.split_first_chunk::<8>().ok_or_else(|| corrupt("EncryptedPackage"))?;
+    let size = u64::from_le_bytes(*size) as usize;
+    let mut package = Vec::with_capacity(segments.len());
+    for (i, segment) in segments.chunks(SEGMENT_SIZE).enumerate() {
+        package.extend(decrypt_aes(key, &derive_iv(key_salt, &(i as u32).to_le_bytes()), segment)?);
+    }
+    if package.len() < size {
+        return Err(corrupt("EncryptedPackage size"));
+    }
+    package.truncate(size);
+    Ok(package)
+}
+
+/// Compound file with the two encryption streams and the data space
+/// description Office expects next to them
+fn write_compound_file(info: &[u8], package: &[u8]) -> Result<Vec<u8>> {
+    let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new()))?;
+    let mut write = |path: &str, data: &[u8]| -> std::io::Result<()> {
+        if let Some((parent, _)) = path.rsplit_once('/').filter(|(parent, _)| !parent.is_empty()) {
+            file.create_storage_all(parent)?;
+        }
+        file.create_stream(path)?.write_all(data)
+    };
+    write(ENCRYPTION_INFO, info)?;
+    write(ENCRYPTED_PACKAGE, package)?;
+    write(&format!("{DATA_SPACES}/Version"), &data_space_version("Microsoft.Container.DataSpaces"))?;
+    write(&format!("{DATA_SPACES}/DataSpaceMap"), &data_space_map())?;
+    write(
+        &format!("{DATA_SPACES}/DataSpaceInfo/StrongEncryptionDataSpace"),
+        &[8u32.to_le_bytes(), 1u32.to_le_bytes()].concat().into_iter().chain(length_prefixed("StrongEncryptionTransform")).collect::<Vec<u8>>(),
+    )?;
+    write(&format!("{DATA_SPACES}/TransformInfo/StrongEncryptionTransform/\u{6}Primary"), &transform_info())?;
+    file.flush()?;
+    Ok(file.into_inner().into_inner())
+}
+
+fn read_compound_file(data: &[u8]) -> Result<(Vec<u8>, Vec<u8>)> {
+    let mut file = cfb::CompoundFile::open(Cursor::new(data))
+        .map_err(|e| PptxError::InvalidValue(format!("Not an encrypted package: {e}")))?;
+    let mut read = |path: &str| -> Result<Vec<u8>> {
+        let mut stream = file.open_stream(path)
+            .map_err(|_| PptxError::InvalidValue(format!("Not an encrypted package: no {} stream", &path[1..])))?;
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data)?;
+        Ok(data)
+    };
+    Ok((read(ENCRYPTION_INFO)?, read(ENCRYPTED_PACKAGE)?))
+}
+
+/// Length-prefixed UTF-16 string padded to 4 bytes
+fn length_prefixed(text: &str) -> Vec<u8> {
+    let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut bytes = (utf16.len() as u32).to_le_bytes().to_vec();
+    bytes.extend(&utf16);
+    bytes.resize(bytes.len().next_multiple_of(4), 0);
+    bytes
+}
+
+/// Feature name followed by reader, updater and writer version 1.0
+fn data_space_version(feature: &str) -> Vec<u8> {
+    let mut bytes = length_prefixed(feature);
+    for _ in 0..3 {
+        bytes.extend([1, 0, 0, 0]);
+    }
+    bytes
+}
+
+/// Maps the `EncryptedPackage` stream to the encryption data space
+fn data_space_map() -> Vec<u8> {
+    let mut entry = 1u32.to_le_bytes().to_vec();
+    entry.extend(0u32.to_le_bytes());
+    entry.extend(length_prefixed("EncryptedPackage"));
+    entry.extend(length_prefixed("StrongEncryptionDataSpace"));
+
+    let mut bytes = [8u32.to_le_bytes(), 1u32.to_le_bytes(), (entry.len() as u32 + 4).to_le_bytes()].concat();
+    bytes.extend(entry);
+    bytes
+}
+
+/// Header of the encryption transform; the cipher details are in `EncryptionInfo`
+fn transform_info() -> Vec<u8> {
+    let id = length_prefixed(TRANSFORM_ID);
+    let mut bytes = (id.len() as u32 + 8).to_le_bytes().to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(id);
+    bytes.extend(data_space_version("Microsoft.Container.EncryptionTransform"));
+    // Empty encryption name, block size, cipher mode, reserved
+    for field in [0u32, 0, 0, 4] {
+        bytes.extend(field.to_le_bytes());
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pptx() -> Vec<u8> {
+        crate::generator::create_pptx("Secret", 3).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_and_wrong_password() {
+        let pptx = pptx();
+        let locked = encrypt_with_spin_count(&pptx, "pässwörd", 10).unwrap();
+        assert!(super::super::is_encrypted(&locked));
+        assert_eq!(decrypt(&locked, "pässwörd").unwrap(), pptx);
+        assert!(matches!(decrypt(&locked, "password"), Err(PptxError::WrongPassword)));
+    }
+
+    #[test]
+    fn test_tampered_package_fails_integrity_check() {
+        let locked = encrypt_with_spin_count(&pptx(), "pw", 1).unwrap();
+        let (info, mut stream) = read_compound_file(&locked).unwrap();
+        assert_eq!(stream.len(), 8 + pptx().len().next_multiple_of(BLOCK_SIZE));
+        stream[20] ^= 1;
+        let tampered = write_compound_file(&info, &stream).unwrap();
+        let error = decrypt(&tampered, "pw").unwrap_err();
+        assert!(error.to_string().contains("integrity"));
+    }
+
+    #[test]
+    fn test_data_space_streams() {
+        assert_eq!(transform_info()[..4], 88u32.to_le_bytes());
+        assert_eq!(length_prefixed("abc").len(), 12);
+        let map = data_space_map();
+        assert_eq!(u32::from_le_bytes(map[8..12].try_into().unwrap()) as usize, map.len() - 8);
+    }
+
+    #[test]
+    fn test_encryption_info_round_trip() {
+        let locked = encrypt_with_spin_count(&pptx(), "pw", 1).unwrap();
+        let (info, _) = read_compound_file(&locked).unwrap();
+        let parsed = EncryptionInfo::parse(&info).unwrap();
+        assert_eq!(parsed.spin_count, 1);
+        assert_eq!(parsed.password_salt.len(), SALT_SIZE);
+        assert_eq!(parsed.encrypted_key_value.len(), KEY_SIZE);
+        assert!(matches!(EncryptionInfo::parse(&[3, 0, 2, 0, 0, 0, 0, 0]), Err(PptxError::InvalidValue(_))));
+    }
+
+    #[test]
+    fn test_spin_count_limit() {
+        let locked = encrypt_with_spin_count(&pptx(), "pw", 1).unwrap();
+        let (info, _) = read_compound_file(&locked).unwrap();
+        let mut parsed = EncryptionInfo::parse(&info).unwrap();
+        parsed.spin_count = MAX_SPIN_COUNT;
+        assert!(EncryptionInfo::parse(&parsed.to_stream()).is_ok());
+        parsed.spin_count = MAX_SPIN_COUNT + 1;
+        let error = EncryptionInfo::parse(&parsed.to_stream()).err().unwrap();
+        assert!(error.to_string().contains("10000001 rounds"));
+    }
+
+    #[test]
+    fn test_other_compound_files_are_not_encrypted_packages() {
+        let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        file.create_stream("/PowerPoint Document").unwrap().write_all(b"EncryptionInfo").unwrap();
+        file.flush().unwrap();
+        let ppt = file.into_inner().into_inner();
+
+        assert!(!super::super::is_encrypted(&ppt));
+        let error = Package::open_reader(Cursor::new(&ppt)).err().unwrap();
+        assert!(error.to_string().contains("Not an OOXML package"));
+        assert!(matches!(Package::open_reader(Cursor::new(encrypt_with_spin_count(&pptx(), "pw", 1).unwrap())), Err(PptxError::Encrypted)));
+    }
+}
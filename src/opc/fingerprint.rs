@@ -0,0 +1,158 @@
+//! Content fingerprints of packages
+//!
+//! [`Package::fingerprint`] hashes the parts of a package after taking out
+//! what changes on every build without changing the deck: the created and
+//! modified timestamps, the provenance build time, numeric element IDs and
+//! generated UUIDs. Two builds of the same content give the same
+//! fingerprint, so a cache can skip uploading a deck that didn't change.
+//! ZIP entry order and timestamps never matter, since parts are hashed by
+//! name.
+
+use std::borrow::Cow;
+
+use sha2::{Digest, Sha256};
+
+use super::Package;
+
+/// Elements whose text is a build timestamp
+const TIMESTAMP_ELEMENTS: &[&str] = &["dcterms:created", "dcterms:modified", "builtAt"];
+
+impl Package {
+    /// SHA-256 (hex) of the parts with timestamps, IDs and UUIDs blanked
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for part in self.parts() {
+            let data = normalize(part.path(), part.data());
+            hasher.update(part.path().as_bytes());
+            hasher.update([0]);
+            hasher.update((data.len() as u64).to_le_bytes());
+            hasher.update(&*data);
+        }
+        hasher.finalize().iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+/// Part content as it is hashed; binary parts are used as they are
+fn normalize<'a>(path: &str, data: &'a [u8]) -> Cow<'a, [u8]> {
+    if !(path.ends_with(".xml") || path.ends_with(".rels")) {
+        return Cow::Borrowed(data);
+    }
+    let Ok(xml) = std::str::from_utf8(data) else { return Cow::Borrowed(data) };
+    let mut xml = xml.to_string();
+    for element in TIMESTAMP_ELEMENTS {
+        xml = blank_element_text(&xml, element);
+    }
+    Cow::Owned(blank_uuids(&blank_numeric_ids(&xml)).into_bytes())
+}
+
+/// Remove the text of every `<element ...>text</element>`
+fn blank_element_text(xml: &str, element: &str) -> String {
+    let open = format!("<{element}");
+    let close = format!("</{element}>");
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let Some(text_start) = rest[start..].find('>').map(|i| start + i + 1) else { break };
+        let Some(text_end) = rest[text_start..].find(&close).map(|i| text_start + i) else { break };
+        out.push_str(&rest[..text_start]);
+        rest = &rest[text_end..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Replace the value of every ` id="<digits>"` attribute with nothing
+fn blank_numeric_ids(xml: &str) -> String {
+    const ATTR: &str = " id=\"";
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find(ATTR) {
+        let value_start = start + ATTR.len();
+        let digits = rest[value_start..].bytes().take_while(u8::is_ascii_digit).count();
+        let numeric = digits > 0 && rest[value_start + digits..].starts_with('"');
+        out.push_str(&rest[..value_start]);
+        rest = &rest[value_start + if numeric { digits } else { 0 }..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Replace every UUID (`8-4-4-4-12` hex digits) with zeros
+fn blank_uuids(xml: &str) -> String {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+    const LEN: usize = 36;
+    let bytes = xml.as_bytes();
+    let is_uuid = |at: usize| {
+        let mut i = at;
+        for (n, len) in GROUPS.iter().enumerate() {
+            if n > 0 {
+                if bytes.get(i) != Some(&b'-') {
+                    return false;
+                }
+                i += 1;
+            }
+            if !bytes.get(i..i + len).is_some_and(|g| g.iter().all(u8::is_ascii_hexdigit)) {
+                return false;
+            }
+            i += len;
+        }
+        true
+    };
+
+    let mut out = String::with_capacity(xml.len());
+    let mut last = 0;
+    let mut i = 0;
+    while i + LEN <= bytes.len() {
+        if is_uuid(i) {
+            out.push_str(&xml[last..i]);
+            out.push_str("00000000-0000-0000-0000-000000000000");
+            i += LEN;
+            last = i;
+        } else {
+            i += 1;
+        }
+    }
+    out.push_str(&xml[last..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(core: &str, slide: &str) -> Package {
+        let mut package = Package::new();
+        package.add_part("docProps/core.xml".to_string(), core.as_bytes().to_vec());
+        package.add_part("ppt/slides/slide1.xml".to_string(), slide.as_bytes().to_vec());
+        package.add_part("ppt/media/image1.png".to_string(), vec![1, 2, 3]);
+        package
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_timestamps_ids_and_uuids() {
+        let a = package(
+            r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-01-01T00:00:00Z</dcterms:created>"#,
+            r#"<p:cNvPr id="10" name="image_0f8e4b2a-1c3d-4e5f-8a9b-0c1d2e3f4a5b.png"/>"#,
+        );
+        let b = package(
+            r#"<dcterms:created xsi:type="dcterms:W3CDTF">2026-10-17T12:34:56Z</dcterms:created>"#,
+            r#"<p:cNvPr id="42" name="image_9a8b7c6d-5e4f-4a3b-2c1d-0e9f8a7b6c5d.png"/>"#,
+        );
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_eq!(a.fingerprint().len(), 64);
+
+        let c = package(
+            r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-01-01T00:00:00Z</dcterms:created>"#,
+            r#"<p:cNvPr id="10" name="Logo"/>"#,
+        );
+        assert_ne!(a.fingerprint(), c.fingerprint());
+    }
+
+    #[test]
+    fn test_blank_numeric_ids_keeps_other_ids() {
+        assert_eq!(
+            blank_numeric_ids(r#"<a id="12"/><b r:id="rId3"/><c id="x1"/>"#),
+            r#"<a id=""/><b r:id="rId3"/><c id="x1"/>"#
+        );
+    }
+}
@@ -0,0 +1,258 @@
+//! Part relationship graph
+//!
+//! [`Package::relationship_graph`] reads every `.rels` part once and returns
+//! the parts as nodes and the relationships as edges, with targets resolved
+//! to part names. Walking it from the package root tells which parts are in
+//! use, which relationships point at missing parts and which parts nothing
+//! points at, and [`RelationshipGraph::to_dot`] draws it with Graphviz:
+//!
+//! ```
+//! use ppt_rs::generator::create_pptx;
+//! use ppt_rs::opc::Package;
+//!
+//! let package = Package::open_reader(std::io::Cursor::new(create_pptx("Deck", 1).unwrap())).unwrap();
+//! let graph = package.relationship_graph();
+//! assert!(graph.targets("ppt/presentation.xml").any(|rel| rel.target == "ppt/slides/slide1.xml"));
+//! assert_eq!(graph.broken().count(), 0);
+//! assert!(graph.to_dot().starts_with("digraph"));
+//! ```
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt;
+
+use crate::oxml::xmlchemy::XmlParser;
+use super::packuri::{rels_source_dir, resolve_part_path};
+use super::Package;
+
+/// Source of the relationships in `_rels/.rels`
+pub const PACKAGE_ROOT: &str = "/";
+/// Parts that are part of the package format rather than its content
+const FORMAT_PARTS: &[&str] = &["[Content_Types].xml"];
+
+/// One relationship from a part (or the package root) to its target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelationshipEdge {
+    /// Part name of the source, or [`PACKAGE_ROOT`]
+    pub source: String,
+    /// Relationship ID, e.g. `rId2`
+    pub id: String,
+    /// Last segment of the relationship type, e.g. `slideLayout`
+    pub kind: String,
+    /// Resolved part name, or the URL of an external target
+    pub target: String,
+    /// `TargetMode="External"`
+    pub external: bool,
+}
+
+/// Parts of a package and the relationships between them
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelationshipGraph {
+    /// Every part except the `.rels` parts, sorted
+    pub parts: BTreeSet<String>,
+    /// Relationships in source, then ID order
+    pub edges: Vec<RelationshipEdge>,
+}
+
+impl RelationshipGraph {
+    /// Graph of `package`; `.rels` parts that don't parse contribute no edges
+    pub fn from_package(package: &Package) -> Self {
+        let mut graph = RelationshipGraph::default();
+        for part in package.parts() {
+            let path = part.path();
+            if !path.ends_with(".rels") {
+                graph.parts.insert(path.to_string());
+                continue;
+            }
+            let Some(source) = rels_source(path) else { continue };
+            let Ok(root) = XmlParser::parse_str(&String::from_utf8_lossy(part.data())) else { continue };
+            let base = rels_source_dir(path);
+            let mut edges: Vec<RelationshipEdge> = root.find_all("Relationship").into_iter()
+                .filter_map(|rel| {
+                    let target = rel.attr("Target")?;
+                    let external = rel.attr("TargetMode") == Some("External");
+                    Some(RelationshipEdge {
+                        source: source.clone(),
+                        id: rel.attr("Id").unwrap_or_default().to_string(),
+                        kind: rel.attr("Type").unwrap_or_default().rsplit('/').next().unwrap_or_default().to_string(),
+                        target: if external { target.to_string() } else { resolve_part_path(base, target) },
+                        external,
+                    })
+                })
+                .collect();
+            edges.sort_by_key(|edge| (edge.id.trim_start_matches("rId").parse::<u32>().unwrap_or(u32::MAX), edge.id.clone()));
+            graph.edges.extend(edges);
+        }
+        graph
+    }
+
+    /// Relationships of the part `source`
+    pub fn targets<'a>(&'a self, source: &'a str) -> impl Iterator<Item = &'a RelationshipEdge> + 'a {
+        self.edges.iter().filter(move |edge| edge.source == source)
+    }
+
+    /// Relationships that point at the part `target`
+    pub fn referrers<'a>(&'a self, target: &'a str) -> impl Iterator<Item = &'a RelationshipEdge> + 'a {
+        self.edges.iter().filter(move |edge| !edge.external && edge.target == target)
+    }
+
+    /// Internal relationships whose target part doesn't exist
+    pub fn broken(&self) -> impl Iterator<Item = &RelationshipEdge> {
+        self.edges.iter().filter(|edge| !edge.external && !self.parts.contains(&edge.target))
+    }
+
+    /// Parts reachable from the package root, in part name order
+    pub fn reachable(&self) -> BTreeSet<&str> {
+        let mut seen = BTreeSet::new();
+        let mut queue = VecDeque::from([PACKAGE_ROOT]);
+        while let Some(source) = queue.pop_front() {
+            for edge in self.targets(source).filter(|edge| !edge.external) {
+                if self.parts.contains(&edge.target) && seen.insert(edge.target.as_str()) {
+                    queue.push_back(&edge.target);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Parts no chain of relationships from the package root leads to
+    pub fn unreachable(&self) -> Vec<&str> {
+        let reachable = self.reachable();
+        self.parts.iter()
+            .map(String::as_str)
+            .filter(|part| !reachable.contains(part) && !FORMAT_PARTS.contains(part))
+            .collect()
+    }
+
+    /// Graphviz DOT source: missing targets are red, unreachable parts grey
+    /// and external targets drawn as notes
+    pub fn to_dot(&self) -> String {
+        let unreachable: BTreeSet<&str> = self.unreachable().into_iter().collect();
+        let mut dot = String::from("digraph package {\n    rankdir=LR;\n    node [shape=box, fontsize=10];\n");
+        dot.push_str(&format!("    {} [label=\"package\", shape=ellipse];\n", quote(PACKAGE_ROOT)));
+        for part in self.parts.iter().filter(|p| !FORMAT_PARTS.contains(&p.as_str())) {
+            let style = if unreachable.contains(part.as_str()) { " [style=filled, fillcolor=lightgrey]" } else { "" };
+            dot.push_str(&format!("    {}{style};\n", quote(part)));
+        }
+        let missing: BTreeSet<&str> = self.broken().map(|edge| edge.target.as_str()).collect();
+        for target in missing {
+            dot.push_str(&format!("    {} [color=red, fontcolor=red, style=dashed];\n", quote(target)));
+        }
+        for target in self.edges.iter().filter(|e| e.external).map(|e| e.target.as_str()).collect::<BTreeSet<_>>() {
+            dot.push_str(&format!("    {} [shape=note];\n", quote(target)));
+        }
+        for edge in &self.edges {
+            dot.push_str(&format!(
+                "    {} -> {} [label={}];\n",
+                quote(&edge.source),
+                quote(&edge.target),
+                quote(&format!("{} {}", edge.id, edge.kind))
+            ));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// One part per line with its relationships indented below it
+impl fmt::Display for RelationshipGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut sources: Vec<&str> = self.edges.iter().map(|edge| edge.source.as_str()).collect();
+        sources.dedup();
+        for source in sources {
+            writeln!(f, "{source}")?;
+            for edge in self.targets(source) {
+                let note = if edge.external {
+                    " (external)"
+                } else if !self.parts.contains(&edge.target) {
+                    " (missing)"
+                } else {
+                    ""
+                };
+                writeln!(f, "  {} {} -> {}{note}", edge.id, edge.kind, edge.target)?;
+            }
+        }
+        for part in self.unreachable() {
+            writeln!(f, "{part} (unreachable)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Part a `.rels` part belongs to: `dir/_rels/file.rels` → `dir/file`
+fn rels_source(rels_path: &str) -> Option<String> {
+    let file = rels_path.rsplit_once("_rels/")?.1.strip_suffix(".rels")?;
+    let dir = rels_source_dir(rels_path);
+    Some(match (dir, file) {
+        (_, "") => PACKAGE_ROOT.to_string(),
+        ("", file) => file.to_string(),
+        (dir, file) => format!("{dir}/{file}"),
+    })
+}
+
+/// DOT string literal
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Package {
+    /// Parts and the relationships between them (see [`RelationshipGraph`])
+    pub fn relationship_graph(&self) -> RelationshipGraph {
+        RelationshipGraph::from_package(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELS: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#;
+
+    fn package() -> Package {
+        let mut package = Package::new();
+        let mut add = |path: &str, xml: String| package.add_part(path.to_string(), xml.into_bytes());
+        add("[Content_Types].xml", "<Types/>".into());
+        add("_rels/.rels", format!(r#"{RELS}<Relationship Id="rId1" Type="http://x/officeDocument" Target="ppt/presentation.xml"/></Relationships>"#));
+        add("ppt/presentation.xml", "<p/>".into());
+        add("ppt/_rels/presentation.xml.rels", format!(
+            r#"{RELS}<Relationship Id="rId10" Type="http://x/slide" Target="slides/slide2.xml"/><Relationship Id="rId2" Type="http://x/slide" Target="slides/slide1.xml"/></Relationships>"#
+        ));
+        add("ppt/slides/slide1.xml", "<s/>".into());
+        add("ppt/slides/_rels/slide1.xml.rels", format!(
+            r#"{RELS}<Relationship Id="rId1" Type="http://x/image" Target="../media/image1.png"/><Relationship Id="rId2" Type="http://x/hyperlink" Target="https://example.com" TargetMode="External"/></Relationships>"#
+        ));
+        add("ppt/media/image1.png", String::new());
+        add("ppt/media/image2.png", String::new());
+        package
+    }
+
+    #[test]
+    fn test_edges_resolve_targets() {
+        let graph = package().relationship_graph();
+        let slides: Vec<(&str, &str)> = graph.targets("ppt/presentation.xml").map(|e| (e.id.as_str(), e.target.as_str())).collect();
+        assert_eq!(slides, vec![("rId2", "ppt/slides/slide1.xml"), ("rId10", "ppt/slides/slide2.xml")]);
+        assert_eq!(graph.targets(PACKAGE_ROOT).next().unwrap().kind, "officeDocument");
+        assert_eq!(graph.referrers("ppt/media/image1.png").count(), 1);
+        assert!(graph.targets("ppt/slides/slide1.xml").any(|e| e.external && e.target == "https://example.com"));
+    }
+
+    #[test]
+    fn test_broken_and_unreachable() {
+        let graph = package().relationship_graph();
+        let broken: Vec<&str> = graph.broken().map(|e| e.target.as_str()).collect();
+        assert_eq!(broken, vec!["ppt/slides/slide2.xml"]);
+        assert_eq!(graph.unreachable(), vec!["ppt/media/image2.png"]);
+        assert!(graph.to_string().contains("rId10 slide -> ppt/slides/slide2.xml (missing)"));
+
+        let dot = graph.to_dot();
+        assert!(dot.contains(r#""ppt/media/image2.png" [style=filled, fillcolor=lightgrey];"#));
+        assert!(dot.contains(r#""ppt/slides/slide2.xml" [color=red"#));
+        assert!(dot.contains(r#""/" -> "ppt/presentation.xml" [label="rId1 officeDocument"];"#));
+    }
+
+    #[test]
+    fn test_rels_source() {
+        assert_eq!(rels_source("_rels/.rels").as_deref(), Some(PACKAGE_ROOT));
+        assert_eq!(rels_source("ppt/slides/_rels/slide1.xml.rels").as_deref(), Some("ppt/slides/slide1.xml"));
+        assert_eq!(rels_source("_rels/a.xml.rels").as_deref(), Some("a.xml"));
+    }
+}
@@ -0,0 +1,285 @@
+//! Document inspector cleanup and signature parts
+//!
+//! `strip_personal_info` removes what PowerPoint's Document Inspector
+//! reports under "Document Properties and Personal Information",
+//! "Comments" (authors) and "Ink". The signature helpers write the OPC
+//! digital signature origin (`_xmlsignatures/origin.sigs`) so external
+//! signing tools can add or countersign `_xmlsignatures/sigN.xml` parts.
+//! Any change to a signed package invalidates its signatures, so strip
+//! personal information before signing.
+
+use regex::Regex;
+
+use super::constants::{CONTENT_TYPE, RELATIONSHIP_TYPE};
+use super::package::Package;
+
+/// Path of the digital signature origin part
+pub const SIGNATURE_ORIGIN_PATH: &str = "_xmlsignatures/origin.sigs";
+
+const SIGNATURE_ORIGIN_RELS_PATH: &str = "_xmlsignatures/_rels/origin.sigs.rels";
+const CONTENT_TYPES_PATH: &str = "[Content_Types].xml";
+const PACKAGE_RELS_PATH: &str = "_rels/.rels";
+
+/// What `Package::strip_personal_info` removed
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PersonalInfoReport {
+    /// Document properties that were cleared, e.g. `dc:creator`
+    pub properties: Vec<String>,
+    /// Comment authors that were anonymized
+    pub comment_authors: usize,
+    /// Ink parts that were removed
+    pub ink_parts: usize,
+}
+
+impl PersonalInfoReport {
+    /// Whether nothing needed removing
+    pub fn is_clean(&self) -> bool {
+        self.properties.is_empty() && self.comment_authors == 0 && self.ink_parts == 0
+    }
+}
+
+impl Package {
+    /// Remove personal information so the package is "inspector-clean"
+    ///
+    /// Clears the author, last-modified-by, company and manager properties,
+    /// renames comment authors to "Author" (legacy and modern comments) and
+    /// deletes ink annotations along with their relationships and parts.
+    pub fn strip_personal_info(&mut self) -> PersonalInfoReport {
+        let mut report = PersonalInfoReport::default();
+
+        for (path, elements) in [
+            ("docProps/core.xml", ["dc:creator", "cp:lastModifiedBy"].as_slice()),
+            ("docProps/app.xml", ["Company", "Manager"].as_slice()),
+        ] {
+            let Some(mut xml) = self.get_part_string(path) else { continue };
+            for element in elements {
+                if remove_element(&mut xml, element) {
+                    report.properties.push(element.to_string());
+                }
+            }
+            self.add_part(path.to_string(), xml.into_bytes());
+        }
+
+        report.comment_authors = self.anonymize_comment_authors();
+        report.ink_parts = self.remove_ink();
+        report
+    }
+
+    /// Add the digital signature origin part if it doesn't exist yet
+    ///
+    /// Returns the origin's path. The part is empty; signatures hang off
+    /// its relationships (see [`Package::add_signature`]).
+    pub fn add_signature_origin(&mut self) -> &'static str {
+        if !self.has_part(SIGNATURE_ORIGIN_PATH) {
+            self.add_part(SIGNATURE_ORIGIN_PATH.to_string(), Vec::new());
+            self.add_content_type_entry(r#"<Default Extension="sigs" ContentType=""#, CONTENT_TYPE::OPC_DIGITAL_SIGNATURE_ORIGIN);
+            let rels = self.get_part_string(PACKAGE_RELS_PATH).unwrap_or_else(empty_rels);
+            let rels = add_relationship(&rels, RELATIONSHIP_TYPE::DIGITAL_SIGNATURE_ORIGIN, SIGNATURE_ORIGIN_PATH);
+            self.add_part(PACKAGE_RELS_PATH.to_string(), rels.into_bytes());
+        }
+        SIGNATURE_ORIGIN_PATH
+    }
+
+    /// Add an XML digital signature produced by an external signing tool
+    ///
+    /// Writes `_xmlsignatures/sigN.xml` and links it from the signature
+    /// origin, creating the origin if needed. Returns the signature's path.
+    pub fn add_signature(&mut self, signature_xml: Vec<u8>) -> String {
+        self.add_signature_origin();
+        let n = (1..).find(|n| !self.has_part(&format!("_xmlsignatures/sig{n}.xml"))).unwrap_or(1);
+        let path = format!("_xmlsignatures/sig{n}.xml");
+        self.add_part(path.clone(), signature_xml);
+        self.add_content_type_entry(
+            &format!(r#"<Override PartName="/{path}" ContentType=""#),
+            CONTENT_TYPE::OPC_DIGITAL_SIGNATURE_XMLSIGNATURE,
+        );
+        let rels = self.get_part_string(SIGNATURE_ORIGIN_RELS_PATH).unwrap_or_else(empty_rels);
+        let rels = add_relationship(&rels, RELATIONSHIP_TYPE::DIGITAL_SIGNATURE, &format!("sig{n}.xml"));
+        self.add_part(SIGNATURE_ORIGIN_RELS_PATH.to_string(), rels.into_bytes());
+        path
+    }
+
+    /// Rename every comment author; returns how many were renamed
+    fn anonymize_comment_authors(&mut self) -> usize {
+        // Legacy `p:cmAuthor` (commentAuthors.xml) and modern `p188:author` (authors.xml)
+        let author = Regex::new(r"<(p:cmAuthor|p188:author)\s[^>]*>").unwrap();
+        let personal = Regex::new(r#"\s(name|initials|userId|providerId)="[^"]*""#).unwrap();
+        let mut count = 0;
+        for path in ["ppt/commentAuthors.xml", "ppt/authors.xml"] {
+            let Some(xml) = self.get_part_string(path) else { continue };
+            let cleaned = author.replace_all(&xml, |caps: &regex::Captures| {
+                let anonymous = personal.replace_all(&caps[0], |attr: &regex::Captures| match &attr[1] {
+                    "name" => r#" name="Author""#.to_string(),
+                    "initials" => r#" initials="A""#.to_string(),
+                    other => format!(r#" {other}="""#),
+                }).into_owned();
+                if anonymous != caps[0] {
+                    count += 1;
+                }
+                anonymous
+            });
+            self.add_part(path.to_string(), cleaned.into_owned().into_bytes());
+        }
+        count
+    }
+
+    /// Delete ink parts and the slide elements and relationships that use them
+    fn remove_ink(&mut self) -> usize {
+        let ink_parts: Vec<String> = self.part_paths().into_iter()
+            .filter(|path| path.starts_with("ppt/ink/"))
+            .map(str::to_string)
+            .collect();
+        if ink_parts.is_empty() {
+            return 0;
+        }
+        for path in &ink_parts {
+            self.remove_part(path);
+        }
+
+        // Ink is a `p:contentPart`, usually wrapped in mc:AlternateContent with a picture fallback
+        let bare = Regex::new(r"(?s)<p:contentPart\b[^>]*?(?:/>|>.*?</p:contentPart>)").unwrap();
+        let ink_rel = Regex::new(r#"<Relationship\b[^>]*Target="[^"]*ink/[^"]*"[^>]*/>"#).unwrap();
+        let ink_override = Regex::new(r#"<Override\b[^>]*PartName="/ppt/ink/[^"]*"[^>]*/>"#).unwrap();
+
+        let slide_parts: Vec<String> = self.slide_parts().map(|slide| slide.path().to_string()).collect();
+        for path in slide_parts {
+            let Some(mut xml) = self.get_part_string(&path) else { continue };
+            if !xml.contains("<p:contentPart") {
+                continue;
+            }
+            xml = remove_alternate_content_with(&xml, "<p:contentPart");
+            xml = bare.replace_all(&xml, "").into_owned();
+            self.add_part(path, xml.into_bytes());
+        }
+        let rels_parts: Vec<String> = self.part_paths().into_iter()
+            .filter(|path| path.ends_with(".rels"))
+            .map(str::to_string)
+            .collect();
+        for path in rels_parts {
+            let Some(xml) = self.get_part_string(&path) else { continue };
+            if ink_rel.is_match(&xml) {
+                self.add_part(path, ink_rel.replace_all(&xml, "").into_owned().into_bytes());
+            }
+        }
+        if let Some(xml) = self.get_part_string(CONTENT_TYPES_PATH) {
+            self.add_part(CONTENT_TYPES_PATH.to_string(), ink_override.replace_all(&xml, "").into_owned().into_bytes());
+        }
+        ink_parts.len()
+    }
+
+    /// Add `<{prefix}{content_type}"/>` to [Content_Types].xml unless `prefix` is already there
+    fn add_content_type_entry(&mut self, prefix: &str, content_type: &str) {
+        let Some(mut xml) = self.get_part_string(CONTENT_TYPES_PATH) else { return };
+        if xml.contains(prefix) {
+            return;
+        }
+        if let Some(pos) = xml.rfind("</Types>") {
+            xml.insert_str(pos, &format!("{prefix}{content_type}\"/>"));
+            self.add_part(CONTENT_TYPES_PATH.to_string(), xml.into_bytes());
+        }
+    }
+}
+
+/// Remove `<element>...</element>` or `<element/>`; returns whether it was present
+fn remove_element(xml: &mut String, element: &str) -> bool {
+    let pattern = format!(r"(?s)<{0}\b[^>]*?(?:/>|>.*?</{0}>)", regex::escape(element));
+    let re = Regex::new(&pattern).unwrap();
+    if !re.is_match(xml) {
+        return false;
+    }
+    *xml = re.replace_all(xml, "").into_owned();
+    true
+}
+
+/// Drop every `mc:AlternateContent` block that contains `needle`
+fn remove_alternate_content_with(xml: &str, needle: &str) -> String {
+    const OPEN: &str = "<mc:AlternateContent";
+    const CLOSE: &str = "</mc:AlternateContent>";
+    let mut out = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find(OPEN) {
+        let Some(len) = rest[start..].find(CLOSE).map(|end| end + CLOSE.len()) else { break };
+        let block = &rest[start..start + len];
+        out.push_str(&rest[..start]);
+        if !block.contains(needle) {
+            out.push_str(block);
+        }
+        rest = &rest[start + len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn empty_rels() -> String {
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"></Relationships>"#.to_string()
+}
+
+/// Append a relationship with the next free `rIdN`
+fn add_relationship(rels: &str, rel_type: &str, target: &str) -> String {
+    let ids = Regex::new(r#"Id="rId(\d+)""#).unwrap();
+    let next = ids.captures_iter(rels).filter_map(|c| c[1].parse::<u32>().ok()).max().unwrap_or(0) + 1;
+    let relationship = format!(r#"<Relationship Id="rId{next}" Type="{rel_type}" Target="{target}"/>"#);
+    match rels.rfind("</Relationships>") {
+        Some(pos) => format!("{}{relationship}{}", &rels[..pos], &rels[pos..]),
+        None => rels.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package_with(parts: &[(&str, &str)]) -> Package {
+        let mut package = Package::new();
+        for (path, xml) in parts {
+            package.add_part(path.to_string(), xml.as_bytes().to_vec());
+        }
+        package
+    }
+
+    #[test]
+    fn test_strip_personal_info() {
+        let mut package = package_with(&[
+            ("docProps/core.xml", "<cp:coreProperties><dc:title>Q3</dc:title><dc:creator>Jane Doe</dc:creator><cp:lastModifiedBy>jdoe</cp:lastModifiedBy></cp:coreProperties>"),
+            ("ppt/commentAuthors.xml", r#"<p:cmAuthorLst><p:cmAuthor id="0" name="Jane Doe" initials="JD" lastIdx="1" clrIdx="0"/></p:cmAuthorLst>"#),
+            ("ppt/slides/slide1.xml", r#"<p:spTree><mc:AlternateContent><mc:Choice Requires="p14"><p:contentPart r:id="rId3"/></mc:Choice><mc:Fallback><p:pic/></mc:Fallback></mc:AlternateContent><p:sp/></p:spTree>"#),
+            ("ppt/slides/_rels/slide1.xml.rels", r#"<Relationships><Relationship Id="rId1" Target="../slideLayouts/slideLayout1.xml"/><Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml" Target="../ink/ink1.xml"/></Relationships>"#),
+            ("ppt/ink/ink1.xml", "<inkml:ink/>"),
+            ("[Content_Types].xml", r#"<Types><Override PartName="/ppt/ink/ink1.xml" ContentType="application/inkml+xml"/></Types>"#),
+        ]);
+
+        let report = package.strip_personal_info();
+        assert_eq!(report.properties, ["dc:creator", "cp:lastModifiedBy"]);
+        assert_eq!((report.comment_authors, report.ink_parts), (1, 1));
+
+        assert_eq!(package.get_part_string("docProps/core.xml").unwrap(), "<cp:coreProperties><dc:title>Q3</dc:title></cp:coreProperties>");
+        let authors = package.get_part_string("ppt/commentAuthors.xml").unwrap();
+        assert!(authors.contains(r#"name="Author" initials="A" lastIdx="1""#));
+        assert_eq!(package.get_part_string("ppt/slides/slide1.xml").unwrap(), "<p:spTree><p:sp/></p:spTree>");
+        assert!(!package.get_part_string("ppt/slides/_rels/slide1.xml.rels").unwrap().contains("ink"));
+        assert_eq!(package.get_part_string("[Content_Types].xml").unwrap(), "<Types></Types>");
+        assert!(!package.has_part("ppt/ink/ink1.xml"));
+        assert!(package.strip_personal_info().is_clean());
+    }
+
+    #[test]
+    fn test_signature_parts() {
+        let mut package = package_with(&[
+            ("[Content_Types].xml", "<Types></Types>"),
+            ("_rels/.rels", r#"<Relationships><Relationship Id="rId1" Target="ppt/presentation.xml"/></Relationships>"#),
+        ]);
+
+        assert_eq!(package.add_signature(b"<Signature/>".to_vec()), "_xmlsignatures/sig1.xml");
+        assert_eq!(package.add_signature(b"<Signature/>".to_vec()), "_xmlsignatures/sig2.xml");
+
+        let rels = package.get_part_string("_rels/.rels").unwrap();
+        assert_eq!(rels.matches("digital-signature/origin").count(), 1);
+        assert!(rels.contains(r#"Id="rId2""#));
+        let origin_rels = package.get_part_string(SIGNATURE_ORIGIN_RELS_PATH).unwrap();
+        assert!(origin_rels.contains(r#"Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/signature" Target="sig2.xml""#));
+        let types = package.get_part_string("[Content_Types].xml").unwrap();
+        assert_eq!(types.matches(r#"Extension="sigs""#).count(), 1);
+        assert!(types.contains(r#"PartName="/_xmlsignatures/sig2.xml""#));
+    }
+}
@@ -1,9 +1,20 @@
 //! OPC (Open Packaging Convention) package handling
 
 pub mod constants;
+#[cfg(feature = "encryption")]
+pub mod crypto;
+pub mod fingerprint;
+pub mod graph;
 pub mod package;
 pub mod packuri;
+pub mod parts;
 pub mod shared;
+#[cfg(feature = "repair")]
+pub mod inspector;
 
-pub use package::Package;
+pub use graph::{RelationshipEdge, RelationshipGraph};
+pub use package::{is_encrypted, Package};
 pub use packuri::PackUri;
+pub use parts::{ChartPartRef, ImagePartRef, PartRef, SlidePartRef};
+#[cfg(feature = "repair")]
+pub use inspector::{PersonalInfoReport, SIGNATURE_ORIGIN_PATH};
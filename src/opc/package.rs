@@ -1,14 +1,48 @@
 //! OPC Package handling
 
-use std::io::Read;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 use std::collections::HashMap;
-use crate::exc::Result;
+use std::sync::Arc;
+use crate::exc::{PptxError, Result};
+use crate::instrument::{event, span};
+
+/// First bytes of a compound file, the container of encrypted packages
+const COMPOUND_FILE_MAGIC: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Size of a compound file directory entry; entries start on these boundaries
+const DIRECTORY_ENTRY_SIZE: usize = 128;
+
+/// Whether `data` is a password-protected OOXML file rather than a ZIP
+///
+/// Encrypted OOXML files are compound files holding an `EncryptionInfo`
+/// stream next to the encrypted package. Other compound files, such as
+/// legacy `.ppt` decks, are not encrypted packages.
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(&COMPOUND_FILE_MAGIC) && has_encryption_info(data)
+}
+
+/// Whether a compound file has a directory entry for an `EncryptionInfo` stream
+///
+/// An entry starts with its UTF-16 name (64 bytes), the name's length in
+/// bytes including the terminator, and the entry type (2 for a stream).
+fn has_encryption_info(data: &[u8]) -> bool {
+    let name: Vec<u8> = "EncryptionInfo\0".encode_utf16().flat_map(u16::to_le_bytes).collect();
+    data.chunks_exact(DIRECTORY_ENTRY_SIZE).any(|entry| {
+        entry.starts_with(&name) && entry[64..66] == (name.len() as u16).to_le_bytes() && entry[66] == 2
+    })
+}
 
 /// Represents an OPC package (ZIP file)
+///
+/// Part data is reference-counted, so cloning a package is cheap and the
+/// clones share it until one of them changes a part. A package is `Send +
+/// Sync`: wrap it (or a [`PresentationReader`](crate::PresentationReader))
+/// in an `Arc` to serve several threads from one opened deck.
+#[derive(Clone, Default)]
 pub struct Package {
     /// Package parts stored as (path, content)
-    parts: HashMap<String, Vec<u8>>,
+    parts: HashMap<String, Arc<Vec<u8>>>,
 }
 
 impl Package {
@@ -27,23 +61,49 @@ impl Package {
     }
 
     /// Open a package from a reader
-    pub fn open_reader<R: Read + std::io::Seek>(reader: R) -> Result<Self> {
+    ///
+    /// Returns `PptxError::Encrypted` for password-protected files, which are
+    /// compound files rather than ZIP archives, and `PptxError::InvalidValue`
+    /// for other compound files such as legacy `.ppt` decks.
+    pub fn open_reader<R: Read + Seek>(mut reader: R) -> Result<Self> {
+        let _span = span!(INFO, "package_open");
+        let start = reader.stream_position()?;
+        let mut magic = Vec::with_capacity(COMPOUND_FILE_MAGIC.len());
+        (&mut reader).take(COMPOUND_FILE_MAGIC.len() as u64).read_to_end(&mut magic)?;
+        reader.seek(SeekFrom::Start(start))?;
+        if magic == COMPOUND_FILE_MAGIC {
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data)?;
+            if is_encrypted(&data) {
+                return Err(PptxError::Encrypted);
+            }
+            return Err(PptxError::InvalidValue(
+                "Not an OOXML package: compound file without an EncryptionInfo stream (a legacy .ppt?)".to_string(),
+            ));
+        }
+
         let mut archive = zip::ZipArchive::new(reader)
-            .map_err(|e| crate::exc::PptxError::Zip(e.to_string()))?;
+            .map_err(|e| PptxError::Zip(e.to_string()))?;
 
         let mut parts = HashMap::new();
 
         for i in 0..archive.len() {
             let mut file = archive
                 .by_index(i)
-                .map_err(|e| crate::exc::PptxError::Zip(e.to_string()))?;
+                .map_err(|e| PptxError::Zip(e.to_string()))?;
 
             if !file.is_dir() {
                 let mut content = Vec::new();
                 file.read_to_end(&mut content)?;
-                parts.insert(file.name().to_string(), content);
+                parts.insert(file.name().to_string(), Arc::new(content));
             }
         }
+        event!(
+            INFO,
+            parts = parts.len(),
+            bytes = parts.values().map(|data| data.len()).sum::<usize>(),
+            "package opened"
+        );
 
         Ok(Package { parts })
     }
@@ -57,19 +117,20 @@ impl Package {
 
     /// Save the package to a writer
     pub fn save_writer<W: std::io::Write + std::io::Seek>(&self, writer: W) -> Result<()> {
+        let _span = span!(DEBUG, "zip_write", parts = self.parts.len());
         let mut archive = zip::ZipWriter::new(writer);
 
         for (path, content) in &self.parts {
             let options = zip::write::FileOptions::default();
             archive
                 .start_file(path, options)
-                .map_err(|e| crate::exc::PptxError::Zip(e.to_string()))?;
+                .map_err(|e| PptxError::Zip(e.to_string()))?;
             std::io::Write::write_all(&mut archive, content)?;
         }
 
         archive
             .finish()
-            .map_err(|e| crate::exc::PptxError::Zip(e.to_string()))?;
+            .map_err(|e| PptxError::Zip(e.to_string()))?;
 
         Ok(())
     }
@@ -81,12 +142,12 @@ impl Package {
 
     /// Add or update a part
     pub fn add_part(&mut self, path: String, content: Vec<u8>) {
-        self.parts.insert(path, content);
+        self.parts.insert(path, Arc::new(content));
     }
 
     /// Remove a part by path
     pub fn remove_part(&mut self, path: &str) -> Option<Vec<u8>> {
-        self.parts.remove(path)
+        self.parts.remove(path).map(Arc::unwrap_or_clone)
     }
 
     /// Check if a part exists
@@ -105,8 +166,10 @@ impl Package {
     }
 
     /// Get mutable reference to part content
+    ///
+    /// The part is copied first if a clone of the package shares it.
     pub fn get_part_mut(&mut self, path: &str) -> Option<&mut Vec<u8>> {
-        self.parts.get_mut(path)
+        self.parts.get_mut(path).map(Arc::make_mut)
     }
 
     /// Get part as string (for XML parts)
@@ -115,12 +178,6 @@ impl Package {
     }
 }
 
-impl Default for Package {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,4 +204,35 @@ mod tests {
         let paths = package.part_paths();
         assert_eq!(paths.len(), 2);
     }
+
+    #[test]
+    fn test_open_encrypted_file_is_reported() {
+        let mut data = COMPOUND_FILE_MAGIC.to_vec();
+        data.resize(512, 0);
+        // A compound file without an EncryptionInfo stream is not an encrypted package
+        assert!(matches!(Package::open_reader(std::io::Cursor::new(&data)), Err(PptxError::InvalidValue(_))));
+
+        let mut entry: Vec<u8> = "EncryptionInfo\0".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        entry.resize(64, 0);
+        entry.extend_from_slice(&[30, 0, 2]);
+        entry.resize(DIRECTORY_ENTRY_SIZE, 0);
+        data.extend_from_slice(&entry);
+        assert!(matches!(Package::open_reader(std::io::Cursor::new(data)), Err(PptxError::Encrypted)));
+        assert!(!is_encrypted(b"PK\x03\x04"));
+    }
+
+    #[test]
+    fn test_clones_share_parts_until_changed() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Package>();
+
+        let mut package = Package::new();
+        package.add_part("ppt/media/image1.png".to_string(), vec![1, 2, 3]);
+        let mut copy = package.clone();
+        assert!(std::ptr::eq(package.get_part("ppt/media/image1.png").unwrap(), copy.get_part("ppt/media/image1.png").unwrap()));
+
+        copy.get_part_mut("ppt/media/image1.png").unwrap().push(4);
+        assert_eq!(package.get_part("ppt/media/image1.png"), Some([1, 2, 3].as_slice()));
+        assert_eq!(copy.remove_part("ppt/media/image1.png"), Some(vec![1, 2, 3, 4]));
+    }
 }
@@ -50,6 +50,36 @@ impl PackUri {
     }
 }
 
+/// Resolve a relationship target relative to the directory of its source part
+///
+/// Returns a part name without a leading slash. Absolute targets
+/// (`/ppt/media/a.png`) ignore `base_dir`; `.` and empty segments are
+/// dropped and `..` never climbs above the package root.
+pub fn resolve_part_path(base_dir: &str, target: &str) -> String {
+    let (mut parts, target) = match target.strip_prefix('/') {
+        Some(absolute) => (Vec::new(), absolute),
+        None => (base_dir.split('/').filter(|p| !p.is_empty()).collect::<Vec<_>>(), target),
+    };
+    for segment in target.split('/') {
+        match segment {
+            ".." => { parts.pop(); }
+            "." | "" => {}
+            _ => parts.push(segment),
+        }
+    }
+    parts.join("/")
+}
+
+/// Directory of the part a `.rels` part belongs to
+///
+/// `ppt/slides/_rels/slide1.xml.rels` → `ppt/slides`, `_rels/.rels` → ``.
+pub fn rels_source_dir(rels_path: &str) -> &str {
+    match rels_path.rsplit_once("_rels/") {
+        Some((dir, _)) => dir.trim_end_matches('/'),
+        None => "",
+    }
+}
+
 impl std::fmt::Display for PackUri {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.uri)
@@ -97,6 +127,13 @@ mod tests {
         assert_eq!(resolved.as_str(), "/ppt/slides/../theme/theme1.xml");
     }
 
+    #[test]
+    fn test_rels_source_dir() {
+        assert_eq!(rels_source_dir("ppt/slides/_rels/slide1.xml.rels"), "ppt/slides");
+        assert_eq!(rels_source_dir("ppt/_rels/presentation.xml.rels"), "ppt");
+        assert_eq!(rels_source_dir("_rels/.rels"), "");
+    }
+
     #[test]
     fn test_packuri_from_str() {
         let uri: PackUri = "/ppt/presentation.xml".into();
@@ -0,0 +1,272 @@
+//! Typed, read-only views of package parts
+//!
+//! [`Package::parts`] walks every part once and tells slides, images and
+//! charts apart by their path, so tools don't each filter
+//! [`Package::part_paths`] by hand. The views borrow the package; XML is
+//! only decoded and parsed when asked for.
+//!
+//! ```
+//! use ppt_rs::generator::create_pptx;
+//! use ppt_rs::opc::{Package, PartRef};
+//!
+//! let package = Package::open_reader(std::io::Cursor::new(create_pptx("Deck", 2).unwrap())).unwrap();
+//! let numbers: Vec<u32> = package.slide_parts().map(|slide| slide.number()).collect();
+//! assert_eq!(numbers, vec![1, 2]);
+//! assert!(package.parts().any(|part| matches!(part, PartRef::Slide(_))));
+//! ```
+
+use std::borrow::Cow;
+
+use crate::exc::Result;
+use crate::oxml::slide::{ParsedSlide, SlideParser};
+use crate::oxml::xmlchemy::{XmlElement, XmlParser};
+use crate::parts::base::ContentType;
+use super::Package;
+
+/// File extensions of `ppt/media` parts that are images
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp", "tif", "tiff", "svg", "emf", "wmf"];
+
+/// One part of a package, by kind
+#[derive(Clone, Copy, Debug)]
+pub enum PartRef<'a> {
+    /// `ppt/slides/slideN.xml`
+    Slide(SlidePartRef<'a>),
+    /// An image in `ppt/media`
+    Image(ImagePartRef<'a>),
+    /// `ppt/charts/chartN.xml`
+    Chart(ChartPartRef<'a>),
+    /// Any other part, relationships included
+    Other { path: &'a str, data: &'a [u8] },
+}
+
+impl<'a> PartRef<'a> {
+    fn new(path: &'a str, data: &'a [u8]) -> Self {
+        if let Some(number) = numbered(path, "ppt/slides/slide") {
+            PartRef::Slide(SlidePartRef { path, data, number })
+        } else if let Some(number) = numbered(path, "ppt/charts/chart") {
+            PartRef::Chart(ChartPartRef { path, data, number })
+        } else if path.starts_with("ppt/media/") && IMAGE_EXTENSIONS.contains(&extension(path).as_str()) {
+            PartRef::Image(ImagePartRef { path, data })
+        } else {
+            PartRef::Other { path, data }
+        }
+    }
+
+    pub fn path(&self) -> &'a str {
+        match self {
+            PartRef::Slide(slide) => slide.path,
+            PartRef::Image(image) => image.path,
+            PartRef::Chart(chart) => chart.path,
+            PartRef::Other { path, .. } => path,
+        }
+    }
+
+    /// Raw bytes of the part
+    pub fn data(&self) -> &'a [u8] {
+        match self {
+            PartRef::Slide(slide) => slide.data,
+            PartRef::Image(image) => image.data,
+            PartRef::Chart(chart) => chart.data,
+            PartRef::Other { data, .. } => data,
+        }
+    }
+}
+
+/// `N` of `<prefix>N.xml`
+fn numbered(path: &str, prefix: &str) -> Option<u32> {
+    path.strip_prefix(prefix)?.strip_suffix(".xml")?.parse().ok()
+}
+
+/// Lowercase file extension of `path`
+fn extension(path: &str) -> String {
+    path.rsplit_once('.').map(|(_, ext)| ext.to_ascii_lowercase()).unwrap_or_default()
+}
+
+/// A slide part
+#[derive(Clone, Copy, Debug)]
+pub struct SlidePartRef<'a> {
+    path: &'a str,
+    data: &'a [u8],
+    number: u32,
+}
+
+impl<'a> SlidePartRef<'a> {
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// `N` of `slideN.xml`, which is not necessarily the slide's position in the deck
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Path of the slide's relationships part
+    pub fn rels_path(&self) -> String {
+        format!("ppt/slides/_rels/slide{}.xml.rels", self.number)
+    }
+
+    pub fn xml(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.data)
+    }
+
+    /// Shapes, text and tables of the slide
+    pub fn parse(&self) -> Result<ParsedSlide> {
+        SlideParser::parse(&self.xml())
+    }
+}
+
+/// An image in `ppt/media`
+#[derive(Clone, Copy, Debug)]
+pub struct ImagePartRef<'a> {
+    path: &'a str,
+    data: &'a [u8],
+}
+
+impl<'a> ImagePartRef<'a> {
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Lowercase file extension, e.g. `png`
+    pub fn extension(&self) -> String {
+        extension(self.path)
+    }
+
+    /// MIME type from the extension
+    pub fn content_type(&self) -> &'static str {
+        match self.extension().as_str() {
+            "emf" => "image/x-emf",
+            "wmf" => "image/x-wmf",
+            "tif" => "image/tiff",
+            ext => ContentType::Image(ext.to_string()).mime_type(),
+        }
+    }
+}
+
+/// A chart part
+#[derive(Clone, Copy, Debug)]
+pub struct ChartPartRef<'a> {
+    path: &'a str,
+    data: &'a [u8],
+    number: u32,
+}
+
+impl<'a> ChartPartRef<'a> {
+    pub fn path(&self) -> &'a str {
+        self.path
+    }
+
+    /// `N` of `chartN.xml`
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn xml(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(self.data)
+    }
+
+    /// The chart space element
+    pub fn parse(&self) -> Result<XmlElement> {
+        XmlParser::parse_str(&self.xml())
+    }
+
+    /// Text of the chart title, if it has one
+    pub fn title(&self) -> Option<String> {
+        let root = self.parse().ok()?;
+        let title = root.find_descendant("title")?;
+        let text: String = title.find_all_descendants("t").iter().map(|t| t.text_content()).collect();
+        Some(text).filter(|t| !t.is_empty())
+    }
+}
+
+impl Package {
+    /// Every part, sorted by path
+    pub fn parts(&self) -> impl Iterator<Item = PartRef<'_>> {
+        let mut paths = self.part_paths();
+        paths.sort_unstable();
+        paths.into_iter().filter_map(|path| Some(PartRef::new(path, self.get_part(path)?)))
+    }
+
+    /// Slide parts in `slideN.xml` number order
+    pub fn slide_parts(&self) -> impl Iterator<Item = SlidePartRef<'_>> {
+        let mut slides: Vec<SlidePartRef> = self.parts()
+            .filter_map(|part| match part {
+                PartRef::Slide(slide) => Some(slide),
+                _ => None,
+            })
+            .collect();
+        slides.sort_by_key(SlidePartRef::number);
+        slides.into_iter()
+    }
+
+    /// Images in `ppt/media`, sorted by path
+    pub fn image_parts(&self) -> impl Iterator<Item = ImagePartRef<'_>> {
+        self.parts().filter_map(|part| match part {
+            PartRef::Image(image) => Some(image),
+            _ => None,
+        })
+    }
+
+    /// Chart parts in `chartN.xml` number order
+    pub fn chart_parts(&self) -> impl Iterator<Item = ChartPartRef<'_>> {
+        let mut charts: Vec<ChartPartRef> = self.parts()
+            .filter_map(|part| match part {
+                PartRef::Chart(chart) => Some(chart),
+                _ => None,
+            })
+            .collect();
+        charts.sort_by_key(ChartPartRef::number);
+        charts.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package() -> Package {
+        let mut package = Package::new();
+        package.add_part("ppt/slides/slide10.xml".to_string(), b"<p:sld/>".to_vec());
+        package.add_part(
+            "ppt/slides/slide2.xml".to_string(),
+            br#"<p:sld xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"/>"#.to_vec(),
+        );
+        package.add_part("ppt/slides/_rels/slide2.xml.rels".to_string(), b"<Relationships/>".to_vec());
+        package.add_part("ppt/media/image1.PNG".to_string(), vec![0x89, b'P']);
+        package.add_part("ppt/media/media1.mp4".to_string(), Vec::new());
+        package.add_part(
+            "ppt/charts/chart1.xml".to_string(),
+            br#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><c:chart><c:title><a:t>Sales</a:t><a:t>2024</a:t></c:title></c:chart></c:chartSpace>"#.to_vec(),
+        );
+        package
+    }
+
+    #[test]
+    fn test_parts_by_kind() {
+        let package = package();
+        let slides: Vec<(u32, String)> = package.slide_parts().map(|s| (s.number(), s.rels_path())).collect();
+        assert_eq!(slides, vec![(2, "ppt/slides/_rels/slide2.xml.rels".to_string()), (10, "ppt/slides/_rels/slide10.xml.rels".to_string())]);
+
+        let images: Vec<ImagePartRef> = package.image_parts().collect();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].content_type(), "image/png");
+
+        let others: Vec<&str> = package.parts()
+            .filter(|part| matches!(part, PartRef::Other { .. }))
+            .map(|part| part.path())
+            .collect();
+        assert_eq!(others, vec!["ppt/media/media1.mp4", "ppt/slides/_rels/slide2.xml.rels"]);
+    }
+
+    #[test]
+    fn test_parse_on_access() {
+        let package = package();
+        let chart = package.chart_parts().next().unwrap();
+        assert_eq!(chart.title().as_deref(), Some("Sales2024"));
+        assert!(package.slide_parts().next().unwrap().parse().is_ok());
+    }
+}
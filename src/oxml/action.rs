@@ -1 +1,64 @@
 //! Action XML elements
+//!
+//! Parses the click and hover actions (`a:hlinkClick`, `a:hlinkHover`)
+//! attached to shapes.
+
+use super::xmlchemy::XmlElement;
+use crate::generator::hyperlinks::HyperlinkAction;
+
+/// Raw action settings read from `a:hlinkClick` or `a:hlinkHover`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAction {
+    /// `ppaction://` string, if any
+    pub action: Option<String>,
+    /// Relationship ID of the hyperlink or slide target, if any
+    pub r_id: Option<String>,
+    /// Tooltip text
+    pub tooltip: Option<String>,
+    /// Name of the sound played when triggered
+    pub sound_name: Option<String>,
+}
+
+impl ParsedAction {
+    /// Read action settings from an `a:hlinkClick`/`a:hlinkHover` element
+    pub fn from_element(element: &XmlElement) -> Self {
+        let non_empty = |name: &str| element.attr(name).filter(|v| !v.is_empty()).map(str::to_string);
+        ParsedAction {
+            action: non_empty("action"),
+            r_id: non_empty("r:id"),
+            tooltip: non_empty("tooltip"),
+            sound_name: element.find("snd").and_then(|snd| snd.attr("name")).map(str::to_string),
+        }
+    }
+
+    /// Resolve to a hyperlink action, given the target of `r_id`
+    pub fn resolve(&self, target: Option<&str>) -> Option<HyperlinkAction> {
+        HyperlinkAction::from_ppaction(self.action.as_deref(), target)
+    }
+}
+
+/// Resolved click and hover actions of one shape
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeAction {
+    /// Shape name (`p:cNvPr/@name`)
+    pub shape_name: String,
+    pub click: Option<HyperlinkAction>,
+    pub hover: Option<HyperlinkAction>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oxml::XmlParser;
+
+    #[test]
+    fn test_parse_action_with_sound() {
+        let xml = r#"<a:hlinkClick xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="" action="ppaction://hlinkshowjump?jump=endshow"><a:snd r:embed="rId4" name="chime.wav"/></a:hlinkClick>"#;
+        let element = XmlParser::parse_str(xml).unwrap();
+        let action = ParsedAction::from_element(&element);
+
+        assert_eq!(action.r_id, None);
+        assert_eq!(action.sound_name.as_deref(), Some("chime.wav"));
+        assert_eq!(action.resolve(None), Some(HyperlinkAction::EndShow));
+    }
+}
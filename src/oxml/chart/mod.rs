@@ -3,6 +3,7 @@
 //! Provides types for parsing and generating DrawingML chart elements.
 
 use super::xmlchemy::XmlElement;
+use crate::core::escape_xml;
 
 /// Chart type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -325,14 +326,6 @@ impl ChartTitle {
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,253 @@
+//! Reading slide comments
+//!
+//! PowerPoint has written two comment formats: legacy comments
+//! (`ppt/comments/commentN.xml` with authors in `ppt/commentAuthors.xml`,
+//! replies linked through `p15:threadingInfo`) and modern threaded comments
+//! (`ppt/comments/modernComment_*.xml` with authors in `ppt/authors.xml`),
+//! which can be anchored to a shape and resolved. [`read_comments`] reads
+//! both into one list of threads for review dashboards; serialize it with
+//! `serde_json` or run `pptcli comments deck.pptx --json`.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::inheritance::related_part;
+use super::presentation::PresentationReader;
+use super::xmlchemy::{XmlElement, XmlParser};
+use crate::opc::Package;
+
+/// Whether a comment thread is still open
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentStatus {
+    /// Open; legacy comments are always active
+    #[default]
+    Active,
+    Resolved,
+    Closed,
+}
+
+impl fmt::Display for CommentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CommentStatus::Active => "active",
+            CommentStatus::Resolved => "resolved",
+            CommentStatus::Closed => "closed",
+        })
+    }
+}
+
+/// A reply in a comment thread
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CommentReply {
+    pub author: String,
+    /// ISO 8601 timestamp
+    pub created: Option<String>,
+    pub text: String,
+}
+
+/// A comment thread on a slide
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SlideComment {
+    /// Slide number, from 1
+    pub slide: usize,
+    pub author: String,
+    pub initials: Option<String>,
+    /// ISO 8601 timestamp
+    pub created: Option<String>,
+    pub text: String,
+    /// Name of the shape the comment is anchored to
+    pub shape: Option<String>,
+    pub status: CommentStatus,
+    pub replies: Vec<CommentReply>,
+}
+
+/// Author name and initials by author ID, from both author lists
+fn authors(package: &Package) -> HashMap<String, (String, Option<String>)> {
+    let mut authors = HashMap::new();
+    for (path, element) in [("ppt/commentAuthors.xml", "cmAuthor"), ("ppt/authors.xml", "author")] {
+        let Some(root) = package.get_part_string(path).and_then(|xml| XmlParser::parse_str(&xml).ok()) else { continue };
+        for author in root.find_all(element) {
+            if let Some(id) = author.attr("id") {
+                let name = author.attr("name").unwrap_or_default().to_string();
+                authors.insert(id.to_string(), (name, author.attr("initials").map(str::to_string)));
+            }
+        }
+    }
+    authors
+}
+
+/// Paragraphs of a text body joined with newlines
+fn body_text(element: &XmlElement) -> String {
+    let Some(body) = element.find("txBody") else { return String::new() };
+    body.find_all("p").into_iter()
+        .map(|p| p.find_all_descendants("t").iter().map(|t| t.text_content()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Every comment thread of the deck, in slide order
+pub fn read_comments(reader: &PresentationReader) -> Vec<SlideComment> {
+    let package = reader.package();
+    let authors = authors(package);
+    let author = |id: Option<&str>| {
+        id.and_then(|id| authors.get(id)).cloned().unwrap_or_else(|| (id.unwrap_or_default().to_string(), None))
+    };
+
+    let mut comments = Vec::new();
+    for (index, slide_path) in reader.slide_paths().iter().enumerate() {
+        let Some(comments_path) = related_part(package, slide_path, "comments").ok().flatten() else { continue };
+        let Some(root) = package.get_part_string(&comments_path).and_then(|xml| XmlParser::parse_str(&xml).ok()) else { continue };
+        let shape_names: HashMap<String, String> = package.get_part_string(slide_path)
+            .and_then(|xml| XmlParser::parse_str(&xml).ok())
+            .map(|slide| {
+                slide.find_all_descendants("cNvPr").into_iter()
+                    .filter_map(|c| Some((c.attr("id")?.to_string(), c.attr("name")?.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Legacy replies point at their parent by author ID and index
+        let mut legacy: Vec<((String, String), SlideComment)> = Vec::new();
+        let mut legacy_replies: Vec<((String, String), CommentReply)> = Vec::new();
+        for cm in root.find_all("cm") {
+            let (name, initials) = author(cm.attr("authorId"));
+            if let Some(text) = cm.find("text") {
+                let created = cm.attr("dt").map(str::to_string);
+                let key = (cm.attr("authorId").unwrap_or_default().to_string(), cm.attr("idx").unwrap_or_default().to_string());
+                if let Some(parent) = cm.find_descendant("parentCm") {
+                    let parent = (parent.attr("authorId").unwrap_or_default().to_string(), parent.attr("idx").unwrap_or_default().to_string());
+                    legacy_replies.push((parent, CommentReply { author: name, created, text: text.text_content() }));
+                } else {
+                    legacy.push((key, SlideComment {
+                        slide: index + 1,
+                        author: name,
+                        initials,
+                        created,
+                        text: text.text_content(),
+                        shape: None,
+                        status: CommentStatus::Active,
+                        replies: Vec::new(),
+                    }));
+                }
+                continue;
+            }
+
+            let replies = cm.find("replyLst").map(|list| {
+                list.find_all("reply").into_iter()
+                    .map(|reply| CommentReply {
+                        author: author(reply.attr("authorId")).0,
+                        created: reply.attr("created").map(str::to_string),
+                        text: body_text(reply),
+                    })
+                    .collect()
+            });
+            comments.push(SlideComment {
+                slide: index + 1,
+                author: name,
+                initials,
+                created: cm.attr("created").map(str::to_string),
+                text: body_text(cm),
+                shape: cm.find_descendant("spMk").and_then(|mk| mk.attr("id")).and_then(|id| shape_names.get(id)).cloned(),
+                status: match cm.attr("status") {
+                    Some("resolved") => CommentStatus::Resolved,
+                    Some("closed") => CommentStatus::Closed,
+                    _ => CommentStatus::Active,
+                },
+                replies: replies.unwrap_or_default(),
+            });
+        }
+        for (parent, reply) in legacy_replies {
+            if let Some((_, thread)) = legacy.iter_mut().find(|(key, _)| *key == parent) {
+                thread.replies.push(reply);
+            }
+        }
+        comments.extend(legacy.into_iter().map(|(_, comment)| comment));
+    }
+    comments
+}
+
+impl PresentationReader {
+    /// Comment threads of every slide (see [`read_comments`])
+    pub fn comments(&self) -> Vec<SlideComment> {
+        read_comments(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: &str = r#"xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main""#;
+    const MODERN: &str = r#"xmlns:p188="http://schemas.microsoft.com/office/powerpoint/2018/8/main" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:ac="http://schemas.microsoft.com/office/drawing/2013/main/command""#;
+
+    fn rels(kind: &str, target: &str) -> String {
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="{kind}" Target="{target}"/></Relationships>"#
+        )
+    }
+
+    fn reader() -> PresentationReader {
+        let mut package = Package::new();
+        let mut add = |path: &str, xml: String| package.add_part(path.to_string(), xml.into_bytes());
+        let slide = format!(r#"<p:sld {P}><p:cSld><p:spTree><p:sp><p:nvSpPr><p:cNvPr id="2" name="Title 1"/></p:nvSpPr></p:sp></p:spTree></p:cSld></p:sld>"#);
+        add("ppt/slides/slide1.xml", slide.clone());
+        add("ppt/slides/slide2.xml", slide);
+
+        add("ppt/commentAuthors.xml", format!(
+            r#"<p:cmAuthorLst {P}><p:cmAuthor id="0" name="Jane Doe" initials="JD"/><p:cmAuthor id="1" name="Sam Lee" initials="SL"/></p:cmAuthorLst>"#
+        ));
+        add("ppt/slides/_rels/slide1.xml.rels", rels("http://schemas.openxmlformats.org/officeDocument/2006/relationships/comments", "../comments/comment1.xml"));
+        add("ppt/comments/comment1.xml", format!(
+            r#"<p:cmLst {P} xmlns:p15="http://schemas.microsoft.com/office/powerpoint/2012/main">
+            <p:cm authorId="0" dt="2024-03-01T10:00:00.000" idx="1"><p:pos x="10" y="10"/><p:text>Check the numbers</p:text></p:cm>
+            <p:cm authorId="1" dt="2024-03-02T09:00:00.000" idx="1"><p:pos x="10" y="10"/><p:text>Fixed</p:text><p:extLst><p:ext uri="{{C676402C-5697-4E1C-873F-D02D1690AC5C}}"><p15:threadingInfo><p15:parentCm authorId="0" idx="1"/></p15:threadingInfo></p:ext></p:extLst></p:cm>
+            </p:cmLst>"#
+        ));
+
+        add("ppt/authors.xml", format!(
+            r#"<p188:authorLst {MODERN}><p188:author id="{{A1}}" name="Ana Ruiz" initials="AR" userId="ana" providerId="AD"/></p188:authorLst>"#
+        ));
+        add("ppt/slides/_rels/slide2.xml.rels", rels("http://schemas.microsoft.com/office/2018/10/relationships/comments", "../comments/modernComment_100_1.xml"));
+        add("ppt/comments/modernComment_100_1.xml", format!(
+            r#"<p188:cmLst {MODERN}><p188:cm id="{{C1}}" authorId="{{A1}}" created="2024-05-01T12:00:00.000" status="resolved">
+            <ac:spMkLst><ac:spMk id="2"/></ac:spMkLst><p188:pos x="0" y="0"/>
+            <p188:replyLst><p188:reply id="{{R1}}" authorId="{{A1}}" created="2024-05-02T12:00:00.000"><p188:txBody><a:p><a:r><a:t>Done</a:t></a:r></a:p></p188:txBody></p188:reply></p188:replyLst>
+            <p188:txBody><a:p><a:r><a:t>Shorter </a:t></a:r><a:r><a:t>title?</a:t></a:r></a:p><a:p><a:r><a:t>Maybe</a:t></a:r></a:p></p188:txBody>
+            </p188:cm></p188:cmLst>"#
+        ));
+        PresentationReader::from_package(package).unwrap()
+    }
+
+    #[test]
+    fn test_legacy_threads() {
+        let comments = reader().comments();
+        let legacy = &comments[0];
+        assert_eq!((legacy.slide, legacy.author.as_str(), legacy.initials.as_deref()), (1, "Jane Doe", Some("JD")));
+        assert_eq!(legacy.text, "Check the numbers");
+        assert_eq!(legacy.status, CommentStatus::Active);
+        assert_eq!(legacy.replies, vec![CommentReply {
+            author: "Sam Lee".to_string(),
+            created: Some("2024-03-02T09:00:00.000".to_string()),
+            text: "Fixed".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_modern_comments() {
+        let comments = reader().comments();
+        assert_eq!(comments.len(), 2);
+        let modern = &comments[1];
+        assert_eq!((modern.slide, modern.author.as_str()), (2, "Ana Ruiz"));
+        assert_eq!(modern.text, "Shorter title?\nMaybe");
+        assert_eq!(modern.shape.as_deref(), Some("Title 1"));
+        assert_eq!(modern.status, CommentStatus::Resolved);
+        assert_eq!(modern.replies[0].text, "Done");
+
+        let json = serde_json::to_value(modern).unwrap();
+        assert_eq!(json["status"], "resolved");
+        assert_eq!(json["created"], "2024-05-01T12:00:00.000");
+    }
+}
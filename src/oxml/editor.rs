@@ -9,7 +9,7 @@
 use super::slide::{ParsedSlide, SlideParser};
 use crate::exc::PptxError;
 use crate::generator::slide_content::SlideContent;
-use crate::generator::slide_xml::{create_slide_xml_with_content, create_slide_rels_xml};
+use crate::generator::slide_xml::{create_slide_xml_with_rels, create_slide_rels_xml};
 use crate::opc::Package;
 
 /// Presentation editor for modifying PPTX files
@@ -30,6 +30,15 @@ impl PresentationEditor {
         })
     }
 
+    /// Edit an already loaded package, e.g. one decrypted in memory
+    pub fn from_package(package: Package) -> Self {
+        let slide_count = Self::count_slides(&package);
+        PresentationEditor {
+            package,
+            slide_count,
+        }
+    }
+
     /// Create a new presentation for editing
     pub fn new() -> Self {
         PresentationEditor {
@@ -55,21 +64,20 @@ impl PresentationEditor {
     }
 
     /// Add a new slide at the end
-    pub fn add_slide(&mut self, content: SlideContent) -> Result<usize, PptxError> {
+    pub fn add_slide(&mut self, mut content: SlideContent) -> Result<usize, PptxError> {
         let new_index = self.slide_count + 1;
         
-        // Generate slide XML
-        let slide_xml = create_slide_xml_with_content(new_index, &content, &[]);
+        // Add slide relationships
         let slide_rels_xml = create_slide_rels_xml();
+        let rels_path = format!("ppt/slides/_rels/slide{new_index}.xml.rels");
+        self.package.add_part(rels_path, slide_rels_xml.into_bytes());
         
-        // Add slide file
+        // Generate slide XML, with tag lists related from it
         let slide_path = format!("ppt/slides/slide{new_index}.xml");
+        let rel_ids = self.tag_rel_ids(&slide_path, &mut content)?;
+        let slide_xml = create_slide_xml_with_rels(new_index, &content, &rel_ids);
         self.package.add_part(slide_path, slide_xml.into_bytes());
         
-        // Add slide relationships
-        let rels_path = format!("ppt/slides/_rels/slide{new_index}.xml.rels");
-        self.package.add_part(rels_path, slide_rels_xml.into_bytes());
-        
         // Update presentation.xml to include new slide
         self.update_presentation_xml(new_index)?;
         
@@ -84,14 +92,17 @@ impl PresentationEditor {
     }
 
     /// Update slide content at index
-    pub fn update_slide(&mut self, index: usize, content: SlideContent) -> Result<(), PptxError> {
+    ///
+    /// The slide keeps its tags; tags set on `content` are added to them.
+    pub fn update_slide(&mut self, index: usize, mut content: SlideContent) -> Result<(), PptxError> {
         if index >= self.slide_count {
             return Err(PptxError::NotFound(format!("Slide {index} not found")));
         }
         
         let slide_num = index + 1;
-        let slide_xml = create_slide_xml_with_content(slide_num, &content, &[]);
         let slide_path = format!("ppt/slides/slide{slide_num}.xml");
+        let rel_ids = self.tag_rel_ids(&slide_path, &mut content)?;
+        let slide_xml = create_slide_xml_with_rels(slide_num, &content, &rel_ids);
         
         self.package.add_part(slide_path, slide_xml.into_bytes());
         Ok(())
@@ -146,11 +157,16 @@ impl PresentationEditor {
 
     // Helper methods
 
+    /// Path of a slide part (0-based index)
+    pub(crate) fn slide_path(&self, index: usize) -> Result<String, PptxError> {
+        if index >= self.slide_count {
+            return Err(PptxError::NotFound(format!("Slide {index} not found")));
+        }
+        Ok(format!("ppt/slides/slide{}.xml", index + 1))
+    }
+
     fn count_slides(package: &Package) -> usize {
-        package.part_paths()
-            .iter()
-            .filter(|p| p.starts_with("ppt/slides/slide") && p.ends_with(".xml") && !p.contains("_rels"))
-            .count()
+        package.slide_parts().count()
     }
 
     fn update_presentation_xml(&mut self, new_slide_count: usize) -> Result<(), PptxError> {
@@ -0,0 +1,319 @@
+//! Text formatting inherited from layouts, masters and themes
+//!
+//! A run on a slide only stores the properties set on it. What PowerPoint
+//! shows comes from the first of these that sets each property:
+//!
+//! 1. the run's own `a:rPr`
+//! 2. the shape's list style (`a:lstStyle`)
+//! 3. the matching placeholder on the slide layout
+//! 4. the matching placeholder on the slide master
+//! 5. the master's text styles: `p:titleStyle` for titles, `p:bodyStyle`
+//!    for other placeholders and `p:otherStyle` for everything else
+//!
+//! Theme fonts (`+mj-lt`, `+mn-lt`) and scheme colors (`tx1`, `accent2`)
+//! are then looked up in the master's color map and the theme.
+//! [`SlideStyles`] holds that chain for one slide; use it with
+//! [`SlideParser::parse_with_styles`](super::slide::SlideParser::parse_with_styles)
+//! or read slides with
+//! [`PresentationReader::get_resolved_slide`](super::presentation::PresentationReader::get_resolved_slide).
+
+use super::slide::TextRun;
+use super::xmlchemy::{XmlElement, XmlParser};
+use crate::exc::PptxError;
+use crate::opc::packuri::resolve_part_path;
+use crate::opc::Package;
+
+/// Run properties where each may be unset
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct RunFormat {
+    bold: Option<bool>,
+    italic: Option<bool>,
+    underline: Option<bool>,
+    font_size: Option<u32>,
+    color: Option<ColorRef>,
+    font_family: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum ColorRef {
+    /// Hex RGB, e.g. `1F497D`
+    Rgb(String),
+    /// Scheme color name, e.g. `tx1`
+    Scheme(String),
+}
+
+impl RunFormat {
+    /// Properties set on an `a:rPr` or `a:defRPr`
+    pub(crate) fn from_rpr(rpr: &XmlElement) -> Self {
+        let flag = |name| rpr.attr(name).map(|v| v == "1" || v == "true");
+        let color = rpr.find("solidFill").and_then(|fill| {
+            if let Some(srgb) = fill.find("srgbClr") {
+                srgb.attr("val").map(|v| ColorRef::Rgb(v.to_string()))
+            } else if let Some(scheme) = fill.find("schemeClr") {
+                scheme.attr("val").map(|v| ColorRef::Scheme(v.to_string()))
+            } else {
+                fill.find("sysClr").and_then(|sys| sys.attr("lastClr")).map(|v| ColorRef::Rgb(v.to_string()))
+            }
+        });
+        RunFormat {
+            bold: flag("b"),
+            italic: flag("i"),
+            underline: rpr.attr("u").map(|u| u != "none"),
+            font_size: rpr.attr("sz").and_then(|v| v.parse().ok()),
+            color,
+            font_family: rpr.find("latin").and_then(|l| l.attr("typeface")).map(|s| s.to_string()),
+        }
+    }
+
+    /// Fill properties unset here from `fallback`
+    fn or(self, fallback: RunFormat) -> Self {
+        RunFormat {
+            bold: self.bold.or(fallback.bold),
+            italic: self.italic.or(fallback.italic),
+            underline: self.underline.or(fallback.underline),
+            font_size: self.font_size.or(fallback.font_size),
+            color: self.color.or(fallback.color),
+            font_family: self.font_family.or(fallback.font_family),
+        }
+    }
+
+    /// Parsed run; unresolved scheme colors are left out
+    pub(crate) fn into_run(self, text: &str) -> TextRun {
+        TextRun {
+            text: text.to_string(),
+            bold: self.bold.unwrap_or(false),
+            italic: self.italic.unwrap_or(false),
+            underline: self.underline.unwrap_or(false),
+            font_size: self.font_size,
+            color: match self.color {
+                Some(ColorRef::Rgb(hex)) => Some(hex),
+                _ => None,
+            },
+            font_family: self.font_family,
+        }
+    }
+}
+
+/// `a:lvlNpPr/a:defRPr` of a list style for the 0-based paragraph `level`
+fn level_format(list_style: Option<&XmlElement>, level: u32) -> RunFormat {
+    list_style
+        .and_then(|style| style.find(&format!("lvl{}pPr", level.min(8) + 1)))
+        .and_then(|ppr| ppr.find("defRPr"))
+        .map(RunFormat::from_rpr)
+        .unwrap_or_default()
+}
+
+/// `p:ph` of a shape: its type (`obj` when unset) and index
+pub(crate) fn placeholder(sp: &XmlElement) -> Option<(&str, Option<&str>)> {
+    let ph = sp.find("nvSpPr")?.find("nvPr")?.find("ph")?;
+    Some((ph.attr("type").unwrap_or("obj"), ph.attr("idx")))
+}
+
+/// Placeholder type as the master knows it
+pub(crate) fn master_type(kind: &str) -> &str {
+    match kind {
+        "ctrTitle" => "title",
+        "subTitle" | "obj" => "body",
+        other => other,
+    }
+}
+
+/// Placeholder shape in `root` matching `(kind, idx)`: by index when both
+/// have one, otherwise by type
+pub(crate) fn find_placeholder<'a>(root: &'a XmlElement, kind: &str, idx: Option<&str>) -> Option<&'a XmlElement> {
+    let shapes = root.find_descendant("spTree")?.find_all("sp");
+    let by_idx = idx.and_then(|idx| {
+        shapes.iter().copied().find(|sp| placeholder(sp).is_some_and(|(_, i)| i == Some(idx)))
+    });
+    by_idx.or_else(|| {
+        shapes.into_iter().find(|sp| {
+            placeholder(sp).is_some_and(|(k, _)| k == kind || master_type(k) == master_type(kind))
+        })
+    })
+}
+
+/// List style of a shape's text body
+fn list_style(sp: &XmlElement) -> Option<&XmlElement> {
+    sp.find("txBody")?.find("lstStyle")
+}
+
+/// Layout, master and theme a slide inherits its formatting from
+#[derive(Clone, Debug, Default)]
+pub struct SlideStyles {
+    layout: Option<XmlElement>,
+    master: Option<XmlElement>,
+    theme: Option<XmlElement>,
+}
+
+impl SlideStyles {
+    /// Styles from the XML of the layout, master and theme parts
+    pub fn from_xml(layout: Option<&str>, master: Option<&str>, theme: Option<&str>) -> Result<Self, PptxError> {
+        let parse = |xml: Option<&str>| xml.map(XmlParser::parse_str).transpose();
+        Ok(SlideStyles { layout: parse(layout)?, master: parse(master)?, theme: parse(theme)? })
+    }
+
+    /// Styles of the slide part `slide_path`, following its relationships
+    /// to the layout, master and theme
+    pub fn for_slide(package: &Package, slide_path: &str) -> Result<Self, PptxError> {
+        let layout = related_part(package, slide_path, "slideLayout")?;
+        let master = layout.as_deref().map(|layout| related_part(package, layout, "slideMaster")).transpose()?.flatten();
+        let theme = master.as_deref().map(|master| related_part(package, master, "theme")).transpose()?.flatten();
+        let xml = |path: Option<String>| path.and_then(|path| package.get_part_string(&path));
+        Self::from_xml(xml(layout).as_deref(), xml(master).as_deref(), xml(theme).as_deref())
+    }
+
+    /// `format` of a run at paragraph `level` in the slide shape `sp`, with
+    /// everything unset filled in from the inheritance chain
+    pub(crate) fn resolve(&self, format: RunFormat, sp: &XmlElement, level: u32) -> RunFormat {
+        let mut format = format.or(level_format(list_style(sp), level));
+        let ph = placeholder(sp);
+        if let Some((kind, idx)) = ph {
+            let layout_sp = self.layout.as_ref().and_then(|layout| find_placeholder(layout, kind, idx));
+            format = format.or(level_format(layout_sp.and_then(list_style), level));
+            let master_sp = self.master.as_ref().and_then(|master| find_placeholder(master, master_type(kind), None));
+            format = format.or(level_format(master_sp.and_then(list_style), level));
+        }
+        let text_style = match ph.map(|(kind, _)| master_type(kind)) {
+            Some("title") => "titleStyle",
+            Some("body") => "bodyStyle",
+            _ => "otherStyle",
+        };
+        let text_styles = self.master.as_ref().and_then(|master| master.find("txStyles"));
+        format = format.or(level_format(text_styles.and_then(|styles| styles.find(text_style)), level));
+        self.resolve_theme(format)
+    }
+
+    /// Replace theme fonts and scheme colors with the theme's values
+    fn resolve_theme(&self, mut format: RunFormat) -> RunFormat {
+        let Some(theme) = &self.theme else { return format };
+        let font_scheme = theme.find_descendant("fontScheme");
+        let theme_font = |slot: &str| {
+            font_scheme?.find(slot)?.find("latin")?.attr("typeface").filter(|f| !f.is_empty()).map(|f| f.to_string())
+        };
+        format.font_family = match format.font_family.as_deref() {
+            Some("+mj-lt") => theme_font("majorFont"),
+            Some("+mn-lt") => theme_font("minorFont"),
+            _ => format.font_family,
+        };
+
+        if let Some(ColorRef::Scheme(name)) = &format.color {
+            // The master's color map names the theme slot, e.g. tx1 -> dk1
+            let slot = self.master.as_ref()
+                .and_then(|master| master.find("clrMap"))
+                .and_then(|map| map.attr(name))
+                .unwrap_or(name);
+            let color = theme.find_descendant("clrScheme")
+                .and_then(|scheme| scheme.find(slot))
+                .and_then(|slot| {
+                    slot.find("srgbClr").and_then(|c| c.attr("val"))
+                        .or_else(|| slot.find("sysClr").and_then(|c| c.attr("lastClr")))
+                });
+            if let Some(hex) = color {
+                format.color = Some(ColorRef::Rgb(hex.to_string()));
+            }
+        }
+        format
+    }
+}
+
+/// Target of the first relationship of `part` whose type ends in `/kind`
+pub(crate) fn related_part(package: &Package, part: &str, kind: &str) -> Result<Option<String>, PptxError> {
+    let (dir, file) = part.rsplit_once('/').unwrap_or(("", part));
+    let Some(rels_xml) = package.get_part_string(&format!("{dir}/_rels/{file}.rels")) else {
+        return Ok(None);
+    };
+    let rels = XmlParser::parse_str(&rels_xml)?;
+    let suffix = format!("/{kind}");
+    Ok(rels.find_all("Relationship").into_iter()
+        .find(|rel| rel.attr("Type").is_some_and(|t| t.ends_with(&suffix)))
+        .and_then(|rel| rel.attr("Target"))
+        .map(|target| resolve_part_path(dir, target)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::oxml::slide::SlideParser;
+
+    const NS: &str = r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main""#;
+
+    fn placeholder_sp(ph: &str, body: &str) -> String {
+        format!(r#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Shape"/><p:cNvSpPr/><p:nvPr>{ph}</p:nvPr></p:nvSpPr><p:spPr/>{body}</p:sp>"#)
+    }
+
+    fn slide_xml() -> String {
+        let title = placeholder_sp(r#"<p:ph type="title"/>"#, r#"<p:txBody><a:p><a:r><a:rPr i="1"/><a:t>Results</a:t></a:r></a:p></p:txBody>"#);
+        let body = placeholder_sp(
+            r#"<p:ph idx="1"/>"#,
+            r#"<p:txBody><a:p><a:r><a:t>Top</a:t></a:r></a:p><a:p><a:pPr lvl="1"/><a:r><a:rPr sz="1000"/><a:t>Detail</a:t></a:r><a:r><a:t>Sub</a:t></a:r></a:p></p:txBody>"#,
+        );
+        let text_box = r#"<p:sp><p:nvSpPr><p:cNvPr id="4" name="Note"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr/><p:txBody><a:p><a:r><a:t>Footnote</a:t></a:r></a:p></p:txBody></p:sp>"#;
+        format!(r#"<p:sld {NS}><p:cSld><p:spTree>{title}{body}{text_box}</p:spTree></p:cSld></p:sld>"#)
+    }
+
+    fn styles() -> SlideStyles {
+        let layout_title = placeholder_sp(r#"<p:ph type="title"/>"#, r#"<p:txBody><a:lstStyle><a:lvl1pPr><a:defRPr b="1"/></a:lvl1pPr></a:lstStyle></p:txBody>"#);
+        let layout_body = placeholder_sp(r#"<p:ph idx="1"/>"#, r#"<p:txBody><a:lstStyle><a:lvl2pPr><a:defRPr><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill></a:defRPr></a:lvl2pPr></a:lstStyle></p:txBody>"#);
+        let layout = format!(r#"<p:sldLayout {NS}><p:cSld><p:spTree>{layout_title}{layout_body}</p:spTree></p:cSld></p:sldLayout>"#);
+        let master = format!(
+            r#"<p:sldMaster {NS}><p:cSld><p:spTree/></p:cSld><p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2"/><p:txStyles>
+            <p:titleStyle><a:lvl1pPr><a:defRPr sz="4400"><a:solidFill><a:schemeClr val="tx2"/></a:solidFill><a:latin typeface="+mj-lt"/></a:defRPr></a:lvl1pPr></p:titleStyle>
+            <p:bodyStyle><a:lvl1pPr><a:defRPr sz="3200"><a:latin typeface="+mn-lt"/></a:defRPr></a:lvl1pPr><a:lvl2pPr><a:defRPr sz="2800"/></a:lvl2pPr></p:bodyStyle>
+            <p:otherStyle><a:lvl1pPr><a:defRPr sz="1800"/></a:lvl1pPr></p:otherStyle>
+            </p:txStyles></p:sldMaster>"#
+        );
+        let theme = r#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:themeElements>
+            <a:clrScheme name="T"><a:dk1><a:sysClr val="windowText" lastClr="000000"/></a:dk1><a:dk2><a:srgbClr val="1F497D"/></a:dk2></a:clrScheme>
+            <a:fontScheme name="T"><a:majorFont><a:latin typeface="Georgia"/></a:majorFont><a:minorFont><a:latin typeface="Verdana"/></a:minorFont></a:fontScheme>
+            </a:themeElements></a:theme>"#;
+        SlideStyles::from_xml(Some(&layout), Some(&master), Some(theme)).unwrap()
+    }
+
+    #[test]
+    fn test_title_inherits_layout_master_and_theme() {
+        let slide = SlideParser::parse_with_styles(&slide_xml(), &styles()).unwrap();
+        let run = &slide.shapes[0].paragraphs[0].runs[0];
+        assert!(run.italic, "set on the slide");
+        assert!(run.bold, "from the layout");
+        assert_eq!(run.font_size, Some(4400));
+        assert_eq!(run.font_family.as_deref(), Some("Georgia"));
+        assert_eq!(run.color.as_deref(), Some("1F497D"));
+
+        let raw = SlideParser::parse(&slide_xml()).unwrap();
+        assert!(!raw.shapes[0].paragraphs[0].runs[0].bold);
+        assert_eq!(raw.shapes[0].paragraphs[0].runs[0].font_size, None);
+    }
+
+    #[test]
+    fn test_body_levels_and_other_text() {
+        let slide = SlideParser::parse_with_styles(&slide_xml(), &styles()).unwrap();
+        let body = &slide.shapes[1];
+        assert_eq!(body.paragraphs[0].runs[0].font_size, Some(3200));
+        assert_eq!(body.paragraphs[0].runs[0].font_family.as_deref(), Some("Verdana"));
+        let detail = &body.paragraphs[1].runs;
+        assert_eq!(detail[0].font_size, Some(1000));
+        assert_eq!(detail[1].font_size, Some(2800));
+        assert_eq!(detail[1].color.as_deref(), Some("00FF00"));
+        assert_eq!(slide.shapes[2].paragraphs[0].runs[0].font_size, Some(1800));
+    }
+
+    #[test]
+    fn test_for_slide_follows_relationships() {
+        let rels = |kind: &str, target: &str| format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{kind}" Target="{target}"/></Relationships>"#
+        );
+        let mut package = Package::new();
+        let mut add = |path: &str, xml: String| package.add_part(path.to_string(), xml.into_bytes());
+        add("ppt/slides/_rels/slide1.xml.rels", rels("slideLayout", "../slideLayouts/slideLayout1.xml"));
+        add("ppt/slideLayouts/slideLayout1.xml", format!(r#"<p:sldLayout {NS}><p:cSld><p:spTree/></p:cSld></p:sldLayout>"#));
+        add("ppt/slideLayouts/_rels/slideLayout1.xml.rels", rels("slideMaster", "../slideMasters/slideMaster1.xml"));
+        add("ppt/slideMasters/slideMaster1.xml", format!(
+            r#"<p:sldMaster {NS}><p:txStyles><p:otherStyle><a:lvl1pPr><a:defRPr sz="1200"/></a:lvl1pPr></p:otherStyle></p:txStyles></p:sldMaster>"#
+        ));
+
+        let styles = SlideStyles::for_slide(&package, "ppt/slides/slide1.xml").unwrap();
+        assert!(styles.layout.is_some() && styles.master.is_some() && styles.theme.is_none());
+        let slide = SlideParser::parse_with_styles(&slide_xml(), &styles).unwrap();
+        assert_eq!(slide.shapes[2].paragraphs[0].runs[0].font_size, Some(1200));
+    }
+}
@@ -4,16 +4,21 @@
 
 pub mod action;
 pub mod chart;
+pub mod comments;
 pub mod coreprops;
 pub mod dml;
 pub mod editor;
+pub mod inheritance;
 pub mod ns;
 pub mod presentation;
+#[cfg(feature = "repair")]
 pub mod repair;
 pub mod shapes;
 pub mod simpletypes;
 pub mod slide;
 pub mod table;
+pub mod tags;
+pub mod template;
 pub mod text;
 pub mod theme;
 pub mod xmlchemy;
@@ -21,12 +26,24 @@ pub mod xmlchemy;
 // Core XML parsing
 pub use xmlchemy::{XmlElement, XmlParser, BaseOxmlElement};
 
+// Action parsing
+pub use action::{ParsedAction, ShapeAction};
+
 // Slide parsing
 pub use slide::{SlideParser, ParsedSlide, ParsedShape, ParsedTable, ParsedTableCell, Paragraph, TextRun};
 
+// Formatting inheritance
+pub use inheritance::SlideStyles;
+
 // Presentation reading
 pub use presentation::{PresentationReader, PresentationInfo};
 
+// Template inspection
+pub use template::{PlaceholderOwner, Template, TemplatePlaceholder};
+
+// Comments
+pub use comments::{CommentReply, CommentStatus, SlideComment};
+
 // Presentation editing
 pub use editor::PresentationEditor;
 
@@ -53,4 +70,5 @@ pub use dml::{
 pub use chart::{ChartKind, ChartSeries as OxmlChartSeries, ChartAxis, ChartLegend, ChartTitle, NumericData, StringData, DataPoint, CategoryPoint};
 
 // Repair functionality
+#[cfg(feature = "repair")]
 pub use repair::{PptxRepair, RepairIssue, RepairResult};
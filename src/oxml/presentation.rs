@@ -2,10 +2,15 @@
 //!
 //! Parses presentation.xml and provides high-level access to presentation content.
 
+use super::action::ShapeAction;
+use super::inheritance::SlideStyles;
 use super::slide::{ParsedSlide, SlideParser};
 use super::xmlchemy::XmlParser;
 use crate::exc::PptxError;
-use crate::opc::Package;
+use crate::opc::{Package, PartRef};
+use crate::opc::packuri::resolve_part_path;
+use crate::generator::Provenance;
+use crate::instrument::{event, span};
 
 /// Parsed presentation metadata
 #[derive(Debug, Clone)]
@@ -40,6 +45,11 @@ impl Default for PresentationInfo {
 }
 
 /// Presentation reader for parsing PPTX files
+///
+/// Reading only needs `&self` and the reader is `Send + Sync`, so one
+/// opened deck in an `Arc` can answer requests from many threads; clones
+/// share the part data (see [`Package`]).
+#[derive(Clone)]
 pub struct PresentationReader {
     package: Package,
     info: PresentationInfo,
@@ -49,13 +59,28 @@ pub struct PresentationReader {
 impl PresentationReader {
     /// Open a PPTX file for reading
     pub fn open(path: &str) -> Result<Self, PptxError> {
-        let package = Package::open(path)?;
+        let _span = span!(INFO, "presentation_open", path = %path);
+        Self::from_package(Package::open(path)?)
+    }
+
+    /// Open a password-protected PPTX file for reading
+    ///
+    /// Returns `PptxError::WrongPassword` if `password` doesn't open it.
+    #[cfg(feature = "encryption")]
+    pub fn open_with_password(path: &str, password: &str) -> Result<Self, PptxError> {
+        let _span = span!(INFO, "presentation_open", path = %path);
+        Self::from_package(Package::open_encrypted(path, password)?)
+    }
+
+    /// Read a package that is already open
+    pub fn from_package(package: Package) -> Result<Self, PptxError> {
         let mut reader = PresentationReader {
             package,
             info: PresentationInfo::new(),
             slide_paths: Vec::new(),
         };
         reader.parse_structure()?;
+        event!(INFO, slides = reader.slide_paths.len(), "presentation opened");
         Ok(reader)
     }
 
@@ -77,10 +102,27 @@ impl PresentationReader {
         let xml = self.package.get_part(path)
             .ok_or_else(|| PptxError::NotFound(format!("Slide file not found: {path}")))?;
         
+        let _span = span!(DEBUG, "parse_part", part = %path);
         let xml_str = String::from_utf8_lossy(xml);
         SlideParser::parse(&xml_str)
     }
 
+    /// Get slide by index (0-based) with the text formatting it inherits
+    ///
+    /// Font sizes, fonts, colors and bold/italic/underline that a run
+    /// doesn't set come from its layout, master and theme (see
+    /// [`SlideStyles`]), so runs report what PowerPoint shows.
+    pub fn get_resolved_slide(&self, index: usize) -> Result<ParsedSlide, PptxError> {
+        let path = self.slide_paths.get(index)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide {index} not found")))?;
+        let xml = self.package.get_part(path)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide file not found: {path}")))?;
+
+        let _span = span!(DEBUG, "parse_part", part = %path);
+        let styles = SlideStyles::for_slide(&self.package, path)?;
+        SlideParser::parse_with_styles(&String::from_utf8_lossy(xml), &styles)
+    }
+
     /// Get all slides
     pub fn get_all_slides(&self) -> Result<Vec<ParsedSlide>, PptxError> {
         let mut slides = Vec::new();
@@ -90,6 +132,86 @@ impl PresentationReader {
         Ok(slides)
     }
 
+    /// Get speaker notes for a slide (0-based), if the slide has a notes part
+    ///
+    /// Paragraphs of the notes body are joined with newlines.
+    pub fn get_slide_notes(&self, index: usize) -> Result<Option<String>, PptxError> {
+        let path = self.slide_paths.get(index)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide {index} not found")))?;
+
+        let (dir, _) = path.rsplit_once('/').unwrap_or(("", path.as_str()));
+        let rels = self.slide_relationships(index)?;
+        let notes_target = rels.iter()
+            .find(|rel| rel.rel_type.ends_with("/notesSlide"))
+            .map(|rel| rel.target.as_str());
+        let Some(target) = notes_target else {
+            return Ok(None);
+        };
+
+        let notes_path = resolve_part_path(dir, target);
+        let Some(notes_xml) = self.package.get_part(&notes_path) else {
+            return Ok(None);
+        };
+
+        let root = XmlParser::parse_str(&String::from_utf8_lossy(notes_xml))?;
+        for shape in root.find_all_descendants("sp") {
+            let is_body = shape.find_descendant("ph")
+                .is_some_and(|ph| ph.attr("type") == Some("body"));
+            if !is_body {
+                continue;
+            }
+            let paragraphs: Vec<String> = shape.find_all_descendants("p").into_iter()
+                .map(|p| p.find_all_descendants("t").iter().map(|t| t.text_content()).collect())
+                .collect();
+            let text = paragraphs.join("\n").trim().to_string();
+            return Ok(if text.is_empty() { None } else { Some(text) });
+        }
+        Ok(None)
+    }
+
+    /// Get the click and hover actions of a slide's shapes
+    ///
+    /// Only shapes that carry at least one action are returned. Slide jumps
+    /// resolve to `HyperlinkAction::Slide` with the target's slide number.
+    pub fn get_shape_actions(&self, index: usize) -> Result<Vec<ShapeAction>, PptxError> {
+        let slide = self.get_slide(index)?;
+        let rels = self.slide_relationships(index)?;
+        let target_of = |r_id: Option<&String>| {
+            r_id.and_then(|id| rels.iter().find(|rel| &rel.id == id))
+                .map(|rel| rel.target.as_str())
+        };
+
+        Ok(slide.shapes.iter()
+            .filter(|shape| shape.click_action.is_some() || shape.hover_action.is_some())
+            .map(|shape| ShapeAction {
+                shape_name: shape.name.clone(),
+                click: shape.click_action.as_ref().and_then(|a| a.resolve(target_of(a.r_id.as_ref()))),
+                hover: shape.hover_action.as_ref().and_then(|a| a.resolve(target_of(a.r_id.as_ref()))),
+            })
+            .collect())
+    }
+
+    /// Read the relationships of a slide part
+    fn slide_relationships(&self, index: usize) -> Result<Vec<SlideRelationship>, PptxError> {
+        let path = self.slide_paths.get(index)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide {index} not found")))?;
+
+        let (dir, file) = path.rsplit_once('/').unwrap_or(("", path.as_str()));
+        let rels_path = format!("{dir}/_rels/{file}.rels");
+        let Some(rels_xml) = self.package.get_part(&rels_path) else {
+            return Ok(Vec::new());
+        };
+
+        let rels = XmlParser::parse_str(&String::from_utf8_lossy(rels_xml))?;
+        Ok(rels.find_all("Relationship").into_iter()
+            .map(|rel| SlideRelationship {
+                id: rel.attr("Id").unwrap_or_default().to_string(),
+                rel_type: rel.attr("Type").unwrap_or_default().to_string(),
+                target: rel.attr("Target").unwrap_or_default().to_string(),
+            })
+            .collect())
+    }
+
     /// Get all text from presentation
     pub fn extract_all_text(&self) -> Result<Vec<String>, PptxError> {
         let mut all_text = Vec::new();
@@ -99,6 +221,35 @@ impl PresentationReader {
         Ok(all_text)
     }
 
+    /// The package being read
+    pub(crate) fn package(&self) -> &Package {
+        &self.package
+    }
+
+    /// Slide part paths in presentation order
+    pub(crate) fn slide_paths(&self) -> &[String] {
+        &self.slide_paths
+    }
+
+    /// Every part of the package, typed as slide, image, chart or other
+    pub fn parts(&self) -> impl Iterator<Item = PartRef<'_>> {
+        self.package.parts()
+    }
+
+    /// Provenance record written by `Presentation::provenance`, if any
+    ///
+    /// Looks through every `customXml/item*.xml` part, so decks that
+    /// picked up other custom XML (e.g. from SharePoint) still work.
+    pub fn provenance(&self) -> Option<Provenance> {
+        let mut items: Vec<&str> = self.package.part_paths().into_iter()
+            .filter(|p| p.starts_with("customXml/item") && !p.starts_with("customXml/itemProps") && p.ends_with(".xml"))
+            .collect();
+        items.sort();
+        items.into_iter()
+            .filter_map(|path| self.package.get_part_string(path))
+            .find_map(|xml| Provenance::from_xml(&xml))
+    }
+
     /// Parse presentation structure
     fn parse_structure(&mut self) -> Result<(), PptxError> {
         // Parse core properties
@@ -175,13 +326,7 @@ impl PresentationReader {
         
         // Fallback: scan for slide files
         if self.slide_paths.is_empty() {
-            let paths = self.package.part_paths();
-            let mut slides: Vec<String> = paths.into_iter()
-                .filter(|p| p.starts_with("ppt/slides/slide") && p.ends_with(".xml") && !p.contains("_rels"))
-                .map(|s| s.to_string())
-                .collect();
-            slides.sort();
-            self.slide_paths = slides;
+            self.slide_paths = self.package.slide_parts().map(|slide| slide.path().to_string()).collect();
         }
         
         self.info.slide_count = self.slide_paths.len();
@@ -189,6 +334,13 @@ impl PresentationReader {
     }
 }
 
+/// Relationship entry from a slide's `.rels` part
+struct SlideRelationship {
+    id: String,
+    rel_type: String,
+    target: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,6 +375,29 @@ mod tests {
         fs::remove_file("test_read.pptx").ok();
     }
 
+    #[test]
+    fn test_read_provenance() {
+        use crate::api::Presentation;
+
+        let provenance = Provenance::new().source(b"# Deck").built_at("2026-10-16T09:30:00Z");
+        let pptx_data = Presentation::with_title("Provenance")
+            .provenance(provenance.clone())
+            .add_slide(SlideContent::new("Slide"))
+            .build()
+            .unwrap();
+        fs::write("test_provenance.pptx", &pptx_data).unwrap();
+
+        let reader = PresentationReader::open("test_provenance.pptx").unwrap();
+        assert_eq!(reader.provenance(), Some(provenance));
+        assert_eq!(reader.slide_count(), 1);
+        fs::remove_file("test_provenance.pptx").ok();
+
+        let plain = create_pptx_with_content("Plain", vec![SlideContent::new("Slide")]).unwrap();
+        fs::write("test_no_provenance.pptx", &plain).unwrap();
+        assert_eq!(PresentationReader::open("test_no_provenance.pptx").unwrap().provenance(), None);
+        fs::remove_file("test_no_provenance.pptx").ok();
+    }
+
     #[test]
     fn test_extract_all_text() {
         let slides = vec![
@@ -244,4 +419,89 @@ mod tests {
         
         fs::remove_file("test_extract.pptx").ok();
     }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let slides = (1..=4).map(|i| SlideContent::new(&format!("Slide {i}"))).collect();
+        let pptx_data = create_pptx_with_content("Shared", slides).unwrap();
+        fs::write("test_shared.pptx", &pptx_data).unwrap();
+        let reader = std::sync::Arc::new(PresentationReader::open("test_shared.pptx").unwrap());
+        fs::remove_file("test_shared.pptx").ok();
+
+        let titles: Vec<Option<String>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..reader.slide_count())
+                .map(|i| {
+                    let reader = std::sync::Arc::clone(&reader);
+                    scope.spawn(move || reader.get_slide(i).unwrap().title)
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(titles[3].as_deref(), Some("Slide 4"));
+    }
+
+    #[test]
+    fn test_get_resolved_slide() {
+        let slides = vec![SlideContent::new("Resolved").add_bullet("Point")];
+        let pptx_data = create_pptx_with_content("Resolve Test", slides).unwrap();
+        fs::write("test_resolved.pptx", &pptx_data).unwrap();
+
+        let reader = PresentationReader::open("test_resolved.pptx").unwrap();
+        let resolved = reader.get_resolved_slide(0).unwrap();
+        assert_eq!(resolved.title, reader.get_slide(0).unwrap().title);
+        assert_eq!(resolved.body_text, vec!["Point".to_string()]);
+        assert!(reader.get_resolved_slide(1).is_err());
+
+        fs::remove_file("test_resolved.pptx").ok();
+    }
+
+    #[test]
+    fn test_read_slide_notes() {
+        let slides = vec![
+            SlideContent::new("With Notes").notes("Say hello\nThen pause"),
+            SlideContent::new("Without Notes"),
+        ];
+
+        let pptx_data = create_pptx_with_content("Notes Test", slides).unwrap();
+        fs::write("test_notes_read.pptx", &pptx_data).unwrap();
+
+        let reader = PresentationReader::open("test_notes_read.pptx").unwrap();
+        let notes = reader.get_slide_notes(0).unwrap();
+        assert!(notes.is_some_and(|n| n.contains("Say hello") && n.contains("Then pause")));
+        assert!(reader.get_slide_notes(1).unwrap().is_none());
+
+        fs::remove_file("test_notes_read.pptx").ok();
+    }
+
+    #[test]
+    fn test_read_shape_actions() {
+        use crate::generator::{Shape, ShapeType, Hyperlink, HyperlinkAction};
+
+        let menu = SlideContent::new("Menu")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 457200)
+                .with_hyperlink(Hyperlink::slide(2)))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 914400, 914400, 457200)
+                .with_hyperlink(Hyperlink::end_show())
+                .with_hover_action(Hyperlink::url("https://example.com")));
+        let slides = vec![menu, SlideContent::new("Target")];
+
+        let pptx_data = create_pptx_with_content("Actions Test", slides).unwrap();
+        fs::write("test_actions_read.pptx", &pptx_data).unwrap();
+
+        let reader = PresentationReader::open("test_actions_read.pptx").unwrap();
+        let actions = reader.get_shape_actions(0).unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].click, Some(HyperlinkAction::Slide(2)));
+        assert_eq!(actions[1].click, Some(HyperlinkAction::EndShow));
+        assert_eq!(actions[1].hover, Some(HyperlinkAction::url("https://example.com")));
+        assert!(reader.get_shape_actions(1).unwrap().is_empty());
+
+        fs::remove_file("test_actions_read.pptx").ok();
+    }
+
+    #[test]
+    fn test_resolve_part_path() {
+        assert_eq!(resolve_part_path("ppt/slides", "../notesSlides/notesSlide1.xml"), "ppt/notesSlides/notesSlide1.xml");
+        assert_eq!(resolve_part_path("ppt/slides", "/ppt/media/a.png"), "ppt/media/a.png");
+    }
 }
@@ -9,9 +9,21 @@
 
 use crate::exc::{PptxError, Result};
 use crate::opc::Package;
+use crate::opc::packuri::{rels_source_dir, resolve_part_path};
+use crate::core::escape_xml_attr;
+use crate::oxml::xmlchemy::{XmlElement, XmlParser};
 use std::collections::HashSet;
 use std::path::Path;
 
+/// Relationships part of the presentation
+const PRESENTATION_RELS: &str = "ppt/_rels/presentation.xml.rels";
+
+/// Whether `rel` points at a slide, rather than the master, theme or other parts
+fn is_slide_relationship(rel: &XmlElement) -> bool {
+    rel.attr("Type") == Some("http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide")
+        && rel.attr("TargetMode") != Some("External")
+}
+
 /// Types of issues that can be detected in a PPTX file
 #[derive(Debug, Clone, PartialEq)]
 pub enum RepairIssue {
@@ -316,8 +328,7 @@ impl PptxRepair {
             }
         }
 
-        // Try to use anyrepair for JSON-like content repair
-        // For XML, we do basic validation
+        // Every opened tag must be closed
         if depth < 0 {
             return Err("Unbalanced tags".to_string());
         }
@@ -354,8 +365,11 @@ impl PptxRepair {
                     if let Some(target) = self.extract_attribute(line, "Target") {
                         let rel_id = self.extract_attribute(line, "Id").unwrap_or_default();
                         
-                        // Skip external relationships
-                        if target.starts_with("http://") || target.starts_with("https://") {
+                        // Skip external relationships (web, mailto: and file: links)
+                        if line.contains("TargetMode=\"External\"")
+                            || target.starts_with("http://")
+                            || target.starts_with("https://")
+                        {
                             continue;
                         }
                         
@@ -387,52 +401,23 @@ impl PptxRepair {
     }
 
     fn resolve_path(&self, rels_path: &str, target: &str) -> String {
-        if target.starts_with('/') {
-            return target[1..].to_string();
-        }
-
-        // Get directory of rels file
-        let rels_dir = rels_path.rsplit_once('/').map(|(d, _)| d).unwrap_or("");
-        let base_dir = rels_dir.replace("_rels", "").trim_end_matches('/').to_string();
-        
-        if target.starts_with("../") {
-            // Go up one directory
-            let parent = base_dir.rsplit_once('/').map(|(d, _)| d).unwrap_or("");
-            format!("{}/{}", parent, target.trim_start_matches("../"))
-        } else {
-            if base_dir.is_empty() {
-                target.to_string()
-            } else {
-                format!("{}/{}", base_dir, target)
-            }
-        }
+        resolve_part_path(rels_source_dir(rels_path), target)
     }
 
     fn check_slide_references(&mut self) {
-        // Get slides from presentation.xml.rels
-        let mut referenced_slides: HashSet<String> = HashSet::new();
-        
-        if let Some(rels_content) = self.package.get_part("ppt/_rels/presentation.xml.rels") {
-            let xml_str = String::from_utf8_lossy(rels_content);
-            for line in xml_str.lines() {
-                if line.contains("slide") && line.contains("Target=") {
-                    if let Some(target) = self.extract_attribute(line, "Target") {
-                        let full_path = if target.starts_with('/') {
-                            target[1..].to_string()
-                        } else {
-                            format!("ppt/{}", target)
-                        };
-                        referenced_slides.insert(full_path);
-                    }
-                }
-            }
-        }
+        // Slides referenced from presentation.xml.rels, by relationship type
+        let Some(relationships) = self.presentation_relationships() else {
+            return;
+        };
+        let referenced_slides: HashSet<String> = relationships.iter()
+            .filter(|rel| is_slide_relationship(rel))
+            .filter_map(|rel| rel.attr("Target"))
+            .map(|target| self.resolve_path(PRESENTATION_RELS, target))
+            .collect();
 
         // Get actual slide files
-        let actual_slides: HashSet<String> = self.package.part_paths()
-            .iter()
-            .filter(|p| p.starts_with("ppt/slides/slide") && p.ends_with(".xml") && !p.contains("_rels"))
-            .map(|s| s.to_string())
+        let actual_slides: HashSet<String> = self.package.slide_parts()
+            .map(|slide| slide.path().to_string())
             .collect();
 
         // Check for orphan references (referenced but don't exist)
@@ -526,8 +511,7 @@ impl PptxRepair {
         if let Some(content) = self.package.get_part(path) {
             let xml_str = String::from_utf8_lossy(content).to_string();
             
-            // Try to repair using anyrepair for JSON-like structures
-            // For XML, we try basic fixes
+            // Basic fixes such as a missing declaration or bare ampersands
             let repaired = self.attempt_xml_repair(&xml_str);
             
             self.package.add_part(path.to_string(), repaired.into_bytes());
@@ -676,22 +660,51 @@ impl PptxRepair {
     }
 
     fn repair_orphan_slide(&mut self, slide_path: &str) -> Result<()> {
-        // Remove the orphan reference from presentation.xml.rels
-        if let Some(content) = self.package.get_part("ppt/_rels/presentation.xml.rels") {
-            let xml_str = String::from_utf8_lossy(content).to_string();
-            let slide_target = slide_path.replace("ppt/", "");
-            
-            // Find and remove the relationship
-            let lines: Vec<&str> = xml_str.lines()
-                .filter(|line| !line.contains(&format!("Target=\"{}\"", slide_target)))
-                .collect();
-            
-            let repaired = lines.join("\n");
-            self.package.add_part("ppt/_rels/presentation.xml.rels".to_string(), repaired.into_bytes());
+        // Remove the orphan reference from presentation.xml.rels and its sldId
+        let Some(relationships) = self.presentation_relationships() else {
+            return Ok(());
+        };
+        let (orphans, kept): (Vec<_>, Vec<_>) = relationships.into_iter().partition(|rel| {
+            is_slide_relationship(rel)
+                && rel.attr("Target").is_some_and(|target| self.resolve_path(PRESENTATION_RELS, target) == slide_path)
+        });
+        if orphans.is_empty() {
+            return Ok(());
+        }
+
+        let mut rels = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+        );
+        for rel in &kept {
+            rels.push_str("\n<Relationship");
+            for name in ["Id", "Type", "Target", "TargetMode"] {
+                if let Some(value) = rel.attr(name) {
+                    rels.push_str(&format!(" {name}=\"{}\"", escape_xml_attr(value)));
+                }
+            }
+            rels.push_str("/>");
+        }
+        rels.push_str("\n</Relationships>");
+        self.package.add_part(PRESENTATION_RELS.to_string(), rels.into_bytes());
+
+        if let Some(content) = self.package.get_part("ppt/presentation.xml") {
+            let mut xml_str = String::from_utf8_lossy(content).to_string();
+            for rel_id in orphans.iter().filter_map(|rel| rel.attr("Id")) {
+                let re = regex::Regex::new(&format!(r#"\s*<p:sldId [^>]*r:id="{}"\s*/>"#, regex::escape(rel_id))).unwrap();
+                xml_str = re.replace_all(&xml_str, "").into_owned();
+            }
+            self.package.add_part("ppt/presentation.xml".to_string(), xml_str.into_bytes());
         }
         Ok(())
     }
 
+    /// `Relationship` elements of `ppt/_rels/presentation.xml.rels`, if it parses
+    fn presentation_relationships(&self) -> Option<Vec<XmlElement>> {
+        let content = self.package.get_part(PRESENTATION_RELS)?;
+        let root = XmlParser::parse(content).ok()?;
+        Some(root.find_all("Relationship").into_iter().cloned().collect())
+    }
+
     fn repair_invalid_content_type(&mut self, path: &str) -> Result<()> {
         if let Some(content) = self.package.get_part("[Content_Types].xml") {
             let xml_str = String::from_utf8_lossy(content).to_string();
@@ -917,6 +930,45 @@ mod tests {
         );
     }
 
+    /// A generated two-slide deck with its presentation rels saved on one line, as PowerPoint does
+    fn single_line_rels_deck() -> PptxRepair {
+        let data = crate::Presentation::with_title("Deck")
+            .add_slide(crate::SlideContent::new("One"))
+            .add_slide(crate::SlideContent::new("Two"))
+            .build()
+            .unwrap();
+        let mut repair = PptxRepair::from_bytes(&data).unwrap();
+        let rels = String::from_utf8_lossy(repair.package().get_part(PRESENTATION_RELS).unwrap()).replace('\n', "");
+        repair.package_mut().add_part(PRESENTATION_RELS.to_string(), rels.into_bytes());
+        repair
+    }
+
+    #[test]
+    fn test_single_line_rels_have_no_slide_issues() {
+        let issues = single_line_rels_deck().validate();
+        assert!(
+            !issues.iter().any(|i| matches!(i, RepairIssue::OrphanSlide { .. } | RepairIssue::MissingSlideReference { .. })),
+            "{issues:?}"
+        );
+    }
+
+    #[test]
+    fn test_orphan_slide_repair_keeps_other_relationships() {
+        let mut repair = single_line_rels_deck();
+        repair.package_mut().remove_part("ppt/slides/slide2.xml");
+        let issue = RepairIssue::OrphanSlide { slide_path: "ppt/slides/slide2.xml".to_string() };
+        assert!(repair.validate().contains(&issue));
+
+        repair.repair_issue(&issue).unwrap();
+        let rels = repair.presentation_relationships().unwrap();
+        let targets: Vec<_> = rels.iter().filter_map(|rel| rel.attr("Target")).collect();
+        assert!(targets.contains(&"slides/slide1.xml") && targets.contains(&"slideMasters/slideMaster1.xml"));
+        assert!(!targets.contains(&"slides/slide2.xml"));
+        let presentation = String::from_utf8_lossy(repair.package().get_part("ppt/presentation.xml").unwrap()).to_string();
+        assert_eq!(presentation.matches("<p:sldId ").count(), 1);
+        assert!(!repair.validate().iter().any(|i| matches!(i, RepairIssue::OrphanSlide { .. })));
+    }
+
     #[test]
     fn test_resolve_path() {
         let repair = PptxRepair {
@@ -932,6 +984,47 @@ mod tests {
             repair.resolve_path("_rels/.rels", "ppt/presentation.xml"),
             "ppt/presentation.xml"
         );
+        assert_eq!(
+            repair.resolve_path("ppt/slides/_rels/slide1.xml.rels", "../../docProps/app.xml"),
+            "docProps/app.xml"
+        );
+    }
+
+    /// Directory segments of part names (no `.`/`..`, no `_rels`)
+    fn segment() -> impl proptest::strategy::Strategy<Value = String> {
+        "[A-Za-z][A-Za-z0-9]{0,7}"
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_resolve_path_climbs_one_directory_per_parent_segment(
+            dirs in proptest::collection::vec(segment(), 0..5),
+            ups in 0usize..7,
+            rest in proptest::collection::vec(segment(), 1..4),
+        ) {
+            let repair = PptxRepair { package: Package::new(), issues: Vec::new() };
+            let rels_path = dirs.iter().map(|d| format!("{d}/")).collect::<String>() + "_rels/part.xml.rels";
+            let target = "../".repeat(ups) + &rest.join("/");
+
+            let kept = &dirs[..dirs.len().saturating_sub(ups)];
+            let expected = kept.iter().chain(&rest).cloned().collect::<Vec<_>>().join("/");
+            proptest::prop_assert_eq!(repair.resolve_path(&rels_path, &target), expected);
+        }
+
+        #[test]
+        fn prop_resolve_path_yields_normalized_part_names(
+            rels_path in "[A-Za-z_./]{0,30}",
+            target in "(\\.\\./|\\./|/|[A-Za-z0-9_]{1,6}/?){0,8}",
+        ) {
+            let repair = PptxRepair { package: Package::new(), issues: Vec::new() };
+            let resolved = repair.resolve_path(&rels_path, &target);
+            proptest::prop_assert!(!resolved.starts_with('/'), "{resolved}");
+            proptest::prop_assert!(resolved.split('/').all(|s| s != ".." && s != "."), "{resolved}");
+            proptest::prop_assert!(!resolved.contains("//"), "{resolved}");
+            // Absolute targets do not depend on the source part
+            let absolute = format!("/{target}");
+            proptest::prop_assert_eq!(repair.resolve_path(&rels_path, &absolute), repair.resolve_path("_rels/.rels", &absolute));
+        }
     }
 
     #[test]
@@ -1018,6 +1111,24 @@ mod tests {
         assert!(content.contains("p:sldIdLst"));
     }
 
+    #[test]
+    fn test_external_relationships_are_not_broken() {
+        let mut package = Package::new();
+        package.add_part(
+            "ppt/slides/_rels/slide1.xml.rels".to_string(),
+            br#"<Relationships>
+<Relationship Id="rId2" Type="hyperlink" Target="mailto:team@example.com" TargetMode="External"/>
+<Relationship Id="rId3" Type="hyperlink" Target="file:///C:/report.pdf" TargetMode="External"/>
+<Relationship Id="rId4" Type="slide" Target="slide9.xml"/>
+</Relationships>"#.to_vec(),
+        );
+        let mut repair = PptxRepair { package, issues: Vec::new() };
+
+        repair.check_rels_file("ppt/slides/_rels/slide1.xml.rels");
+        assert_eq!(repair.issues.len(), 1);
+        assert!(matches!(&repair.issues[0], RepairIssue::BrokenRelationship { rel_id, .. } if rel_id == "rId4"));
+    }
+
     #[test]
     fn test_attempt_xml_repair() {
         let repair = PptxRepair {
@@ -3,6 +3,7 @@
 //! Provides types for parsing and generating DrawingML shape elements.
 
 use super::xmlchemy::XmlElement;
+use crate::core::escape_xml_attr;
 
 /// Transform properties (a:xfrm)
 #[derive(Debug, Clone, Default)]
@@ -252,21 +253,13 @@ impl NonVisualProperties {
 
     pub fn to_xml(&self) -> String {
         let id = self.id;
-        let name = escape_xml(&self.name);
+        let name = escape_xml_attr(&self.name);
         format!(
             r#"<p:nvSpPr><p:cNvPr id="{id}" name="{name}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr>"#
         )
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
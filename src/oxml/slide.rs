@@ -2,6 +2,8 @@
 //!
 //! Parses slide XML to extract text, shapes, tables, and other content.
 
+use super::action::ParsedAction;
+use super::inheritance::{RunFormat, SlideStyles};
 use super::xmlchemy::{XmlElement, XmlParser};
 use crate::exc::PptxError;
 
@@ -14,6 +16,8 @@ pub struct TextRun {
     pub underline: bool,
     pub font_size: Option<u32>,
     pub color: Option<String>,
+    /// Latin typeface, e.g. `Calibri` (theme fonts such as `+mn-lt` included)
+    pub font_family: Option<String>,
 }
 
 impl TextRun {
@@ -25,6 +29,7 @@ impl TextRun {
             underline: false,
             font_size: None,
             color: None,
+            font_family: None,
         }
     }
 }
@@ -68,6 +73,10 @@ pub struct ParsedShape {
     pub height: i64,
     pub is_title: bool,
     pub is_body: bool,
+    /// Action run when the shape is clicked
+    pub click_action: Option<ParsedAction>,
+    /// Action run when the mouse hovers over the shape
+    pub hover_action: Option<ParsedAction>,
 }
 
 impl ParsedShape {
@@ -82,6 +91,8 @@ impl ParsedShape {
             height: 0,
             is_title: false,
             is_body: false,
+            click_action: None,
+            hover_action: None,
         }
     }
 
@@ -175,7 +186,19 @@ pub struct SlideParser;
 
 impl SlideParser {
     /// Parse slide XML content
+    ///
+    /// Runs carry only the formatting set on the slide itself.
     pub fn parse(xml: &str) -> Result<ParsedSlide, PptxError> {
+        Self::parse_inner(xml, None)
+    }
+
+    /// Parse slide XML content, filling in the text formatting the slide
+    /// inherits from its layout, master and theme
+    pub fn parse_with_styles(xml: &str, styles: &SlideStyles) -> Result<ParsedSlide, PptxError> {
+        Self::parse_inner(xml, Some(styles))
+    }
+
+    fn parse_inner(xml: &str, styles: Option<&SlideStyles>) -> Result<ParsedSlide, PptxError> {
         let root = XmlParser::parse_str(xml)?;
         let mut slide = ParsedSlide::new();
 
@@ -183,7 +206,7 @@ impl SlideParser {
         if let Some(sp_tree) = root.find_descendant("spTree") {
             // Parse shapes
             for sp in sp_tree.find_all("sp") {
-                if let Some(mut shape) = Self::parse_shape(sp) {
+                if let Some(mut shape) = Self::parse_shape(sp, styles) {
                     // Check if this is title or body
                     if Self::is_title_shape(sp) {
                         shape.is_title = true;
@@ -212,14 +235,21 @@ impl SlideParser {
         Ok(slide)
     }
 
-    fn parse_shape(sp: &XmlElement) -> Option<ParsedShape> {
+    fn parse_shape(sp: &XmlElement, styles: Option<&SlideStyles>) -> Option<ParsedShape> {
         // Get shape name from nvSpPr/cNvPr
-        let name = sp.find_descendant("cNvPr")
+        let cnv_pr = sp.find_descendant("cNvPr");
+        let name = cnv_pr
             .and_then(|e| e.attr("name"))
             .unwrap_or("Shape");
 
         let mut shape = ParsedShape::new(name);
 
+        // Get click/hover actions from cNvPr
+        if let Some(cnv_pr) = cnv_pr {
+            shape.click_action = cnv_pr.find("hlinkClick").map(ParsedAction::from_element);
+            shape.hover_action = cnv_pr.find("hlinkHover").map(ParsedAction::from_element);
+        }
+
         // Get position and size from spPr/xfrm
         if let Some(xfrm) = sp.find_descendant("xfrm") {
             if let Some(off) = xfrm.find("off") {
@@ -239,13 +269,13 @@ impl SlideParser {
 
         // Parse text body
         if let Some(tx_body) = sp.find_descendant("txBody") {
-            shape.paragraphs = Self::parse_text_body(tx_body);
+            shape.paragraphs = Self::parse_text_body(tx_body, sp, styles);
         }
 
         Some(shape)
     }
 
-    fn parse_text_body(tx_body: &XmlElement) -> Vec<Paragraph> {
+    fn parse_text_body(tx_body: &XmlElement, sp: &XmlElement, styles: Option<&SlideStyles>) -> Vec<Paragraph> {
         let mut paragraphs = Vec::new();
 
         for p in tx_body.find_all("p") {
@@ -263,22 +293,11 @@ impl SlideParser {
                     continue;
                 }
 
-                let mut run = TextRun::new(&text);
-
-                // Parse run properties
-                if let Some(rpr) = r.find("rPr") {
-                    run.bold = rpr.attr("b").map(|v| v == "1" || v == "true").unwrap_or(false);
-                    run.italic = rpr.attr("i").map(|v| v == "1" || v == "true").unwrap_or(false);
-                    run.underline = rpr.attr("u").is_some();
-                    run.font_size = rpr.attr("sz").and_then(|v| v.parse().ok());
-
-                    // Get color from solidFill/srgbClr
-                    if let Some(solid_fill) = rpr.find_descendant("solidFill") {
-                        if let Some(srgb) = solid_fill.find("srgbClr") {
-                            run.color = srgb.attr("val").map(|s| s.to_string());
-                        }
-                    }
+                let mut format = r.find("rPr").map(RunFormat::from_rpr).unwrap_or_default();
+                if let Some(styles) = styles {
+                    format = styles.resolve(format, sp, para.level);
                 }
+                let run = format.into_run(&text);
 
                 para.runs.push(run);
             }
@@ -431,7 +450,7 @@ mod tests {
                         <p:txBody>
                             <a:p>
                                 <a:r>
-                                    <a:rPr b="1" i="1" sz="4400"/>
+                                    <a:rPr b="1" i="1" sz="4400"><a:latin typeface="Georgia"/></a:rPr>
                                     <a:t>Bold Italic</a:t>
                                 </a:r>
                             </a:p>
@@ -449,5 +468,6 @@ mod tests {
         assert!(run.bold);
         assert!(run.italic);
         assert_eq!(run.font_size, Some(4400));
+        assert_eq!(run.font_family.as_deref(), Some("Georgia"));
     }
 }
@@ -3,6 +3,7 @@
 //! Provides types for parsing and generating DrawingML table elements.
 
 use super::xmlchemy::XmlElement;
+use crate::core::escape_xml;
 
 /// Table cell properties
 #[derive(Debug, Clone, Default)]
@@ -257,14 +258,6 @@ impl Default for Table {
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
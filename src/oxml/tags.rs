@@ -0,0 +1,333 @@
+//! Reading and editing slide and shape tags
+//!
+//! A slide's tags are referenced from `p:cSld/p:custDataLst`, a shape's from
+//! `p:nvPr/p:custDataLst`; both point at a tag list part (`ppt/tags/tagN.xml`)
+//! through the slide's relationships. See [`Tags`] for building them.
+
+use super::editor::PresentationEditor;
+use super::presentation::PresentationReader;
+use super::xmlchemy::{XmlElement, XmlParser};
+use crate::exc::PptxError;
+use crate::generator::slide_content::SlideContent;
+use crate::generator::slide_xml::SlideRelIds;
+use crate::generator::tags::{Tags, TAGS_RELATIONSHIP_TYPE, cust_data_xml, tags_content_type_xml};
+use crate::opc::Package;
+use crate::opc::packuri::resolve_part_path;
+
+/// Relationship ID of the tag list in a `p:custDataLst` under `parent`
+fn tags_r_id(parent: &XmlElement) -> Option<&str> {
+    parent.find("custDataLst")?.find("tags")?.attr("r:id")
+}
+
+/// Path of the part the slide relationship `r_id` points at
+fn relationship_target(package: &Package, slide_path: &str, r_id: &str) -> Result<Option<String>, PptxError> {
+    let (dir, file) = slide_path.rsplit_once('/').unwrap_or(("", slide_path));
+    let Some(rels_xml) = package.get_part_string(&format!("{dir}/_rels/{file}.rels")) else {
+        return Ok(None);
+    };
+    let rels = XmlParser::parse_str(&rels_xml)?;
+    Ok(rels.find_all("Relationship").into_iter()
+        .find(|rel| rel.attr("Id") == Some(r_id))
+        .and_then(|rel| rel.attr("Target"))
+        .map(|target| resolve_part_path(dir, target)))
+}
+
+/// Tag list part and tags referenced by `r_id` (empty tags if the part is missing)
+fn read_tags(package: &Package, slide_path: &str, r_id: &str) -> Result<Option<(String, Tags)>, PptxError> {
+    let Some(path) = relationship_target(package, slide_path, r_id)? else {
+        return Ok(None);
+    };
+    let mut tags = match package.get_part_string(&path) {
+        Some(xml) => Tags::from_xml(&xml)?,
+        None => Tags::new(),
+    };
+    tags.r_id = Some(r_id.to_string());
+    Ok(Some((path, tags)))
+}
+
+fn parse_slide(package: &Package, slide_path: &str) -> Result<XmlElement, PptxError> {
+    let xml = package.get_part_string(slide_path)
+        .ok_or_else(|| PptxError::NotFound(format!("Slide file not found: {slide_path}")))?;
+    XmlParser::parse_str(&xml)
+}
+
+/// Tag list part and tags of a slide
+fn slide_tags(package: &Package, slide_path: &str) -> Result<Option<(String, Tags)>, PptxError> {
+    let slide = parse_slide(package, slide_path)?;
+    match slide.find("cSld").and_then(tags_r_id) {
+        Some(r_id) => read_tags(package, slide_path, r_id),
+        None => Ok(None),
+    }
+}
+
+/// Tag list part and tags of every tagged shape, by shape name
+fn shape_tags(package: &Package, slide_path: &str) -> Result<Vec<(String, String, Tags)>, PptxError> {
+    let slide = parse_slide(package, slide_path)?;
+    let mut shapes = Vec::new();
+    for nv_props in ["nvSpPr", "nvPicPr", "nvGraphicFramePr", "nvCxnSpPr", "nvGrpSpPr"] {
+        for nv in slide.find_all_descendants(nv_props) {
+            let name = nv.find("cNvPr").and_then(|c| c.attr("name")).unwrap_or_default();
+            if let Some(r_id) = nv.find("nvPr").and_then(tags_r_id)
+                && let Some((path, tags)) = read_tags(package, slide_path, r_id)?
+            {
+                shapes.push((name.to_string(), path, tags));
+            }
+        }
+    }
+    Ok(shapes)
+}
+
+impl PresentationReader {
+    /// Custom tags of a slide (0-based)
+    pub fn slide_tags(&self, index: usize) -> Result<Tags, PptxError> {
+        let path = self.slide_paths().get(index)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide {index} not found")))?;
+        Ok(slide_tags(self.package(), path)?.map(|(_, tags)| tags).unwrap_or_default())
+    }
+
+    /// Custom tags of a slide's shapes, by shape name; untagged shapes are left out
+    pub fn shape_tags(&self, index: usize) -> Result<Vec<(String, Tags)>, PptxError> {
+        let path = self.slide_paths().get(index)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide {index} not found")))?;
+        Ok(shape_tags(self.package(), path)?.into_iter().map(|(name, _, tags)| (name, tags)).collect())
+    }
+}
+
+impl PresentationEditor {
+    /// Custom tags of a slide (0-based)
+    pub fn slide_tags(&self, index: usize) -> Result<Tags, PptxError> {
+        let path = self.slide_path(index)?;
+        Ok(slide_tags(self.package(), &path)?.map(|(_, tags)| tags).unwrap_or_default())
+    }
+
+    /// Set a tag on a slide (0-based), adding a tag list if it has none
+    pub fn set_slide_tag(&mut self, index: usize, name: &str, value: &str) -> Result<(), PptxError> {
+        let path = self.slide_path(index)?;
+        match slide_tags(self.package(), &path)? {
+            Some((part, mut tags)) => {
+                tags.set(name, value);
+                self.package_mut().add_part(part, tags.to_xml().into_bytes());
+            }
+            None => {
+                let r_id = self.add_tags_part(&path, &Tags::new().with(name, value))?;
+                let mut xml = self.slide_xml(&path)?;
+                let pos = xml.find("</p:spTree>")
+                    .ok_or_else(|| PptxError::InvalidValue(format!("{path} has no shape tree")))?;
+                xml.insert_str(pos + "</p:spTree>".len(), &cust_data_xml(&r_id));
+                self.package_mut().add_part(path, xml.into_bytes());
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove a tag from a slide (0-based), returning its value
+    pub fn remove_slide_tag(&mut self, index: usize, name: &str) -> Result<Option<String>, PptxError> {
+        let path = self.slide_path(index)?;
+        let Some((part, mut tags)) = slide_tags(self.package(), &path)? else {
+            return Ok(None);
+        };
+        let removed = tags.remove(name);
+        self.package_mut().add_part(part, tags.to_xml().into_bytes());
+        Ok(removed)
+    }
+
+    /// Custom tags of the shape called `shape` on a slide (0-based)
+    pub fn shape_tags(&self, index: usize, shape: &str) -> Result<Tags, PptxError> {
+        let path = self.slide_path(index)?;
+        Ok(shape_tags(self.package(), &path)?.into_iter()
+            .find(|(name, _, _)| name == shape)
+            .map(|(_, _, tags)| tags)
+            .unwrap_or_default())
+    }
+
+    /// Set a tag on the shape called `shape` on a slide (0-based)
+    ///
+    /// Errors if the slide has no shape with that name.
+    pub fn set_shape_tag(&mut self, index: usize, shape: &str, name: &str, value: &str) -> Result<(), PptxError> {
+        let path = self.slide_path(index)?;
+        if let Some((_, part, mut tags)) = shape_tags(self.package(), &path)?.into_iter().find(|(n, _, _)| n == shape) {
+            tags.set(name, value);
+            self.package_mut().add_part(part, tags.to_xml().into_bytes());
+            return Ok(());
+        }
+
+        let mut xml = self.slide_xml(&path)?;
+        let nv_pr = shape_nv_pr(&xml, shape)
+            .ok_or_else(|| PptxError::NotFound(format!("Shape \"{shape}\" not found on slide {}", index + 1)))?;
+        let r_id = self.add_tags_part(&path, &Tags::new().with(name, value))?;
+        let cust_data = cust_data_xml(&r_id);
+        if xml[nv_pr..].starts_with("<p:nvPr/>") {
+            xml.replace_range(nv_pr..nv_pr + "<p:nvPr/>".len(), &format!("<p:nvPr>{cust_data}</p:nvPr>"));
+        } else {
+            // custDataLst goes after the placeholder and media, before extLst
+            let end = nv_pr + xml[nv_pr..].find("</p:nvPr>").unwrap_or(0);
+            let pos = xml[nv_pr..end].find("<p:extLst").map_or(end, |ext| nv_pr + ext);
+            xml.insert_str(pos, &cust_data);
+        }
+        self.package_mut().add_part(path, xml.into_bytes());
+        Ok(())
+    }
+
+    /// Remove a tag from the shape called `shape`, returning its value
+    pub fn remove_shape_tag(&mut self, index: usize, shape: &str, name: &str) -> Result<Option<String>, PptxError> {
+        let path = self.slide_path(index)?;
+        let Some((_, part, mut tags)) = shape_tags(self.package(), &path)?.into_iter().find(|(n, _, _)| n == shape) else {
+            return Ok(None);
+        };
+        let removed = tags.remove(name);
+        self.package_mut().add_part(part, tags.to_xml().into_bytes());
+        Ok(removed)
+    }
+
+    /// Relationship IDs for the tags of `content`, about to be written to `slide_path`
+    ///
+    /// New tag list parts are added for the slide and each tagged shape. A
+    /// slide that is being replaced keeps its tag list, with the tags of
+    /// `content` merged in.
+    pub(crate) fn tag_rel_ids(&mut self, slide_path: &str, content: &mut SlideContent) -> Result<SlideRelIds, PptxError> {
+        let kept = if self.package().has_part(slide_path) {
+            slide_tags(self.package(), slide_path)?
+        } else {
+            None
+        };
+        let tags = match kept {
+            Some((part, mut tags)) => {
+                for (name, value) in content.tags.iter() {
+                    tags.set(name, value);
+                }
+                self.package_mut().add_part(part, tags.to_xml().into_bytes());
+                content.tags = tags;
+                content.tags.r_id.clone()
+            }
+            None if !content.tags.is_empty() => Some(self.add_tags_part(slide_path, &content.tags)?),
+            None => None,
+        };
+
+        let mut shape_tags = Vec::new();
+        if content.shapes.iter().any(|s| !s.tags.is_empty()) {
+            for shape in &content.shapes {
+                let r_id = if shape.tags.is_empty() { None } else { Some(self.add_tags_part(slide_path, &shape.tags)?) };
+                shape_tags.push(r_id);
+            }
+        }
+        Ok(SlideRelIds { tags, shape_tags, ..SlideRelIds::default() })
+    }
+
+    /// Write `tags` to a new tag list part related from `slide_path`, returning the relationship ID
+    pub(crate) fn add_tags_part(&mut self, slide_path: &str, tags: &Tags) -> Result<String, PptxError> {
+        let number = (1..).find(|n| !self.package().has_part(&format!("ppt/tags/tag{n}.xml"))).unwrap_or(1);
+        self.package_mut().add_part(format!("ppt/tags/tag{number}.xml"), tags.to_xml().into_bytes());
+
+        if let Some(mut types) = self.package().get_part_string("[Content_Types].xml")
+            && let Some(pos) = types.rfind("</Types>")
+        {
+            types.insert_str(pos, &format!("{}\n", tags_content_type_xml(number)));
+            self.package_mut().add_part("[Content_Types].xml".to_string(), types.into_bytes());
+        }
+
+        let (dir, file) = slide_path.rsplit_once('/').unwrap_or(("", slide_path));
+        let rels_path = format!("{dir}/_rels/{file}.rels");
+        let mut rels = self.package().get_part_string(&rels_path).unwrap_or_else(|| {
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+</Relationships>"#.to_string()
+        });
+        let used: Vec<u32> = XmlParser::parse_str(&rels)?.find_all("Relationship").into_iter()
+            .filter_map(|rel| rel.attr("Id")?.strip_prefix("rId")?.parse().ok())
+            .collect();
+        let r_id = format!("rId{}", used.into_iter().max().unwrap_or(0) + 1);
+        let relationship = format!(r#"<Relationship Id="{r_id}" Type="{TAGS_RELATIONSHIP_TYPE}" Target="../tags/tag{number}.xml"/>"#);
+        if let Some(pos) = rels.rfind("</Relationships>") {
+            rels.insert_str(pos, &format!("{relationship}\n"));
+        }
+        self.package_mut().add_part(rels_path, rels.into_bytes());
+        Ok(r_id)
+    }
+
+    fn slide_xml(&self, path: &str) -> Result<String, PptxError> {
+        self.package().get_part_string(path)
+            .ok_or_else(|| PptxError::NotFound(format!("Slide file not found: {path}")))
+    }
+}
+
+/// Offset of the `<p:nvPr` of the shape called `name` in slide XML
+///
+/// Each `p:cNvPr` start tag is parsed on its own, so the name matches however
+/// the slide escaped it.
+fn shape_nv_pr(xml: &str, name: &str) -> Option<usize> {
+    let mut from = 0;
+    while let Some(start) = xml[from..].find("<p:cNvPr ").map(|i| from + i) {
+        let end = start + xml[start..].find('>')?;
+        let attrs = xml[start + "<p:cNvPr".len()..end].trim_end_matches('/');
+        let c_nv_pr = XmlParser::parse_str(&format!("<cNvPr{attrs}/>")).ok();
+        if c_nv_pr.as_ref().and_then(|e| e.attr("name")) == Some(name) {
+            return xml[end..].find("<p:nvPr").map(|i| end + i);
+        }
+        from = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{Shape, ShapeType, SlideContent, create_pptx_with_content};
+
+    fn package(slides: Vec<SlideContent>) -> Package {
+        let data = create_pptx_with_content("Tags", slides).unwrap();
+        Package::open_reader(std::io::Cursor::new(data)).unwrap()
+    }
+
+    #[test]
+    fn test_generated_tags_are_read_back() {
+        let slide = SlideContent::new("Budget")
+            .tag("owner", "finance")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_name("Total").tag("managed-by", "sync"))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_name("Plain"));
+        let reader = PresentationReader::from_package(package(vec![SlideContent::new("Intro"), slide])).unwrap();
+
+        assert!(reader.slide_tags(0).unwrap().is_empty());
+        assert_eq!(reader.slide_tags(1).unwrap().get("OWNER"), Some("finance"));
+        let shapes = reader.shape_tags(1).unwrap();
+        assert_eq!(shapes.len(), 1);
+        assert_eq!((shapes[0].0.as_str(), shapes[0].1.get("managed-by")), ("Total", Some("sync")));
+    }
+
+    #[test]
+    fn test_editor_sets_and_removes_tags() {
+        let slide = |title: &str| SlideContent::new(title)
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_name("Total"));
+        let mut editor = PresentationEditor::from_package(package(vec![slide("Budget")]));
+
+        editor.set_slide_tag(0, "owner", "finance").unwrap();
+        editor.set_slide_tag(0, "status", "draft").unwrap();
+        assert_eq!(editor.remove_slide_tag(0, "status").unwrap().as_deref(), Some("draft"));
+        // Rewriting the slide keeps its tags
+        editor.update_slide(0, slide("Budget v2").tag("reviewed", "yes")).unwrap();
+        editor.set_shape_tag(0, "Total", "managed-by", "sync").unwrap();
+        assert!(editor.set_shape_tag(0, "Missing", "a", "b").is_err());
+        assert_eq!(editor.shape_tags(0, "Total").unwrap().get("managed-by"), Some("sync"));
+
+        let mut data = std::io::Cursor::new(Vec::new());
+        editor.package().save_writer(&mut data).unwrap();
+        let reader = PresentationReader::from_package(Package::open_reader(std::io::Cursor::new(data.into_inner())).unwrap()).unwrap();
+        let tags = reader.slide_tags(0).unwrap();
+        assert_eq!((tags.get("owner"), tags.get("reviewed"), tags.len()), (Some("finance"), Some("yes"), 2));
+        assert_eq!(reader.shape_tags(0).unwrap()[0].1.get("managed-by"), Some("sync"));
+        let types = reader.package().get_part_string("[Content_Types].xml").unwrap();
+        assert_eq!(types.matches("presentationml.tags+xml").count(), 2);
+    }
+
+    #[test]
+    fn test_shape_tag_matches_parsed_name() {
+        let slide = SlideContent::new("Budget")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_name("Total\nQ3"))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_name("Grand Total"));
+        let mut editor = PresentationEditor::from_package(package(vec![slide]));
+
+        editor.set_shape_tag(0, "Total\nQ3", "managed-by", "sync").unwrap();
+        assert!(editor.set_shape_tag(0, "Total", "a", "b").is_err());
+        assert_eq!(editor.shape_tags(0, "Total\nQ3").unwrap().get("managed-by"), Some("sync"));
+        assert!(editor.shape_tags(0, "Grand Total").unwrap().is_empty());
+    }
+}
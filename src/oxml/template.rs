@@ -0,0 +1,271 @@
+//! Placeholder inventory of template decks
+//!
+//! [`Template::placeholders`] lists the placeholders of every slide layout
+//! and slide in a template with their type, index, name and position, so
+//! tooling can show the fillable slots of a template and check that a data
+//! source supplies every field before generating from it:
+//!
+//! ```no_run
+//! use ppt_rs::Template;
+//!
+//! let template = Template::open("quarterly-template.pptx")?;
+//! for slot in template.placeholders() {
+//!     println!("{}: {} ({}) at {},{}", slot.owner, slot.name, slot.kind, slot.x, slot.y);
+//! }
+//! template.require_fields(&["Title 1", "Content Placeholder 2"])?;
+//! # Ok::<(), ppt_rs::PptxError>(())
+//! ```
+//!
+//! Placeholders that don't set their position take it from the matching
+//! placeholder on the layout and then the master, as PowerPoint does.
+
+use std::fmt;
+
+use super::inheritance::{find_placeholder, master_type, placeholder, related_part};
+use super::presentation::PresentationReader;
+use super::xmlchemy::{XmlElement, XmlParser};
+use crate::exc::PptxError;
+use crate::opc::Package;
+
+/// Placeholder types PowerPoint fills in itself
+const AUTOMATIC_KINDS: &[&str] = &["dt", "ftr", "sldNum", "hdr"];
+
+/// Where a placeholder is defined
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PlaceholderOwner {
+    /// A slide layout, by its `p:cSld` name
+    Layout { name: String, path: String },
+    /// A slide, numbered from 1 in presentation order
+    Slide { number: usize, path: String },
+}
+
+impl fmt::Display for PlaceholderOwner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaceholderOwner::Layout { name, .. } => write!(f, "layout \"{name}\""),
+            PlaceholderOwner::Slide { number, .. } => write!(f, "slide {number}"),
+        }
+    }
+}
+
+/// One placeholder of a template
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TemplatePlaceholder {
+    pub owner: PlaceholderOwner,
+    /// `p:ph/@type`, `obj` when unset
+    pub kind: String,
+    /// `p:ph/@idx`
+    pub idx: Option<u32>,
+    /// Shape name (`p:cNvPr/@name`)
+    pub name: String,
+    /// Position and size in EMU; 0 when neither the placeholder nor its
+    /// layout or master sets them
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl TemplatePlaceholder {
+    /// Whether the placeholder holds content rather than a date, footer,
+    /// header or slide number
+    pub fn is_fillable(&self) -> bool {
+        !AUTOMATIC_KINDS.contains(&self.kind.as_str())
+    }
+}
+
+/// A template deck opened for inspection
+pub struct Template {
+    reader: PresentationReader,
+}
+
+impl Template {
+    /// Open a template PPTX file
+    pub fn open(path: &str) -> Result<Self, PptxError> {
+        Ok(Template { reader: PresentationReader::open(path)? })
+    }
+
+    /// Inspect a package that is already open
+    pub fn from_package(package: Package) -> Result<Self, PptxError> {
+        Ok(Template { reader: PresentationReader::from_package(package)? })
+    }
+
+    /// Placeholders of every layout (in `slideLayoutN.xml` order), then of
+    /// every slide (in presentation order)
+    pub fn placeholders(&self) -> Vec<TemplatePlaceholder> {
+        let package = self.reader.package();
+        let mut layouts: Vec<(u32, &str)> = package.part_paths().into_iter()
+            .filter_map(|path| {
+                let number = path.strip_prefix("ppt/slideLayouts/slideLayout")?.strip_suffix(".xml")?.parse().ok()?;
+                Some((number, path))
+            })
+            .collect();
+        layouts.sort_unstable();
+
+        let mut placeholders = Vec::new();
+        for (_, path) in layouts {
+            let Some(root) = parse_part(package, path) else { continue };
+            let name = root.find("cSld").and_then(|c| c.attr("name")).unwrap_or_default().to_string();
+            let owner = PlaceholderOwner::Layout { name, path: path.to_string() };
+            let master = related_xml(package, path, "slideMaster");
+            placeholders.extend(collect(&root, &owner, &[master.as_ref()]));
+        }
+        for (index, path) in self.reader.slide_paths().iter().enumerate() {
+            let Some(root) = parse_part(package, path) else { continue };
+            let owner = PlaceholderOwner::Slide { number: index + 1, path: path.clone() };
+            let layout_path = related_part(package, path, "slideLayout").ok().flatten();
+            let layout = layout_path.as_deref().and_then(|layout| parse_part(package, layout));
+            let master = layout_path.as_deref().and_then(|layout| related_xml(package, layout, "slideMaster"));
+            placeholders.extend(collect(&root, &owner, &[layout.as_ref(), master.as_ref()]));
+        }
+        placeholders
+    }
+
+    /// Names of the fillable slide placeholders that `supplied` has no
+    /// value for
+    pub fn missing_fields(&self, supplied: &[&str]) -> Vec<TemplatePlaceholder> {
+        self.placeholders().into_iter()
+            .filter(|p| matches!(p.owner, PlaceholderOwner::Slide { .. }) && p.is_fillable())
+            .filter(|p| !supplied.contains(&p.name.as_str()))
+            .collect()
+    }
+
+    /// Fail with `PptxError::Validation` naming every fillable slide
+    /// placeholder that `supplied` has no value for
+    pub fn require_fields(&self, supplied: &[&str]) -> Result<(), PptxError> {
+        let missing = self.missing_fields(supplied);
+        let Some(first) = missing.first() else { return Ok(()) };
+        let names: Vec<String> = missing.iter().map(|p| format!("\"{}\" ({})", p.name, p.owner)).collect();
+        Err(PptxError::Validation {
+            location: first.owner.to_string(),
+            message: format!("no value for placeholder {}", names.join(", ")),
+        })
+    }
+}
+
+fn parse_part(package: &Package, path: &str) -> Option<XmlElement> {
+    XmlParser::parse_str(&package.get_part_string(path)?).ok()
+}
+
+/// Parsed target of `part`'s relationship of type `kind`
+fn related_xml(package: &Package, part: &str, kind: &str) -> Option<XmlElement> {
+    parse_part(package, &related_part(package, part, kind).ok().flatten()?)
+}
+
+/// Placeholders of a layout or slide; `inherited` are the parts positions
+/// come from when a placeholder doesn't set them, nearest first
+fn collect(root: &XmlElement, owner: &PlaceholderOwner, inherited: &[Option<&XmlElement>]) -> Vec<TemplatePlaceholder> {
+    let Some(tree) = root.find_descendant("spTree") else { return Vec::new() };
+    tree.find_all("sp").into_iter()
+        .filter_map(|sp| {
+            let (kind, idx) = placeholder(sp)?;
+            let geometry = xfrm(sp).or_else(|| {
+                inherited.iter().enumerate().find_map(|(level, part)| {
+                    // Masters match by type only
+                    let last = level + 1 == inherited.len();
+                    let (kind, idx) = if last { (master_type(kind), None) } else { (kind, idx) };
+                    xfrm(find_placeholder((*part)?, kind, idx)?)
+                })
+            });
+            let [x, y, width, height] = geometry.unwrap_or_default();
+            Some(TemplatePlaceholder {
+                owner: owner.clone(),
+                kind: kind.to_string(),
+                idx: idx.and_then(|idx| idx.parse().ok()),
+                name: sp.find_descendant("cNvPr").and_then(|c| c.attr("name")).unwrap_or_default().to_string(),
+                x,
+                y,
+                width,
+                height,
+            })
+        })
+        .collect()
+}
+
+/// `[x, y, cx, cy]` of a shape's own `a:xfrm`
+fn xfrm(sp: &XmlElement) -> Option<[i64; 4]> {
+    let xfrm = sp.find("spPr")?.find("xfrm")?;
+    let (off, ext) = (xfrm.find("off")?, xfrm.find("ext")?);
+    let value = |element: &XmlElement, name| element.attr(name).and_then(|v| v.parse().ok()).unwrap_or(0);
+    Some([value(off, "x"), value(off, "y"), value(ext, "cx"), value(ext, "cy")])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NS: &str = r#"xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main""#;
+
+    fn sp(id: u32, name: &str, ph: &str, xfrm: &str) -> String {
+        format!(r#"<p:sp><p:nvSpPr><p:cNvPr id="{id}" name="{name}"/><p:cNvSpPr/><p:nvPr>{ph}</p:nvPr></p:nvSpPr><p:spPr>{xfrm}</p:spPr></p:sp>"#)
+    }
+
+    fn at(x: i64, y: i64) -> String {
+        format!(r#"<a:xfrm><a:off x="{x}" y="{y}"/><a:ext cx="100" cy="50"/></a:xfrm>"#)
+    }
+
+    fn rels(kind: &str, target: &str) -> String {
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{kind}" Target="{target}"/></Relationships>"#
+        )
+    }
+
+    fn template() -> Template {
+        let mut package = Package::new();
+        let mut add = |path: &str, xml: String| package.add_part(path.to_string(), xml.into_bytes());
+        add("ppt/slideMasters/slideMaster1.xml", format!(
+            r#"<p:sldMaster {NS}><p:cSld><p:spTree>{}{}</p:spTree></p:cSld></p:sldMaster>"#,
+            sp(2, "Title Placeholder 1", r#"<p:ph type="title"/>"#, &at(10, 20)),
+            sp(3, "Text Placeholder 2", r#"<p:ph type="body" idx="1"/>"#, &at(10, 200)),
+        ));
+        add("ppt/slideLayouts/slideLayout1.xml", format!(
+            r#"<p:sldLayout {NS}><p:cSld name="Title and Content"><p:spTree>{}{}{}</p:spTree></p:cSld></p:sldLayout>"#,
+            sp(2, "Title 1", r#"<p:ph type="title"/>"#, ""),
+            sp(3, "Content Placeholder 2", r#"<p:ph idx="1"/>"#, &at(30, 300)),
+            sp(4, "Slide Number Placeholder 3", r#"<p:ph type="sldNum" idx="12"/>"#, &at(900, 700)),
+        ));
+        add("ppt/slideLayouts/_rels/slideLayout1.xml.rels", rels("slideMaster", "../slideMasters/slideMaster1.xml"));
+        add("ppt/slides/slide1.xml", format!(
+            r#"<p:sld {NS}><p:cSld><p:spTree>{}{}{}{}</p:spTree></p:cSld></p:sld>"#,
+            sp(2, "Title 1", r#"<p:ph type="title"/>"#, ""),
+            sp(3, "Content Placeholder 2", r#"<p:ph idx="1"/>"#, ""),
+            sp(4, "Slide Number Placeholder 3", r#"<p:ph type="sldNum" idx="12"/>"#, ""),
+            sp(5, "Logo", "", &at(1, 1)),
+        ));
+        add("ppt/slides/_rels/slide1.xml.rels", rels("slideLayout", "../slideLayouts/slideLayout1.xml"));
+        Template::from_package(package).unwrap()
+    }
+
+    #[test]
+    fn test_placeholders_per_layout_and_slide() {
+        let placeholders = template().placeholders();
+        assert_eq!(placeholders.len(), 6);
+        let layout = &placeholders[0];
+        assert_eq!(layout.owner.to_string(), "layout \"Title and Content\"");
+        assert_eq!((layout.kind.as_str(), layout.x, layout.y), ("title", 10, 20));
+
+        let slide: Vec<&TemplatePlaceholder> = placeholders.iter()
+            .filter(|p| matches!(p.owner, PlaceholderOwner::Slide { number: 1, .. }))
+            .collect();
+        let names: Vec<&str> = slide.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Title 1", "Content Placeholder 2", "Slide Number Placeholder 3"]);
+        assert_eq!((slide[0].x, slide[0].y), (10, 20), "from the master");
+        assert_eq!((slide[1].kind.as_str(), slide[1].idx, slide[1].y), ("obj", Some(1), 300), "from the layout");
+        assert_eq!((slide[2].x, slide[2].width), (900, 100));
+    }
+
+    #[test]
+    fn test_required_fields() {
+        let template = template();
+        let missing: Vec<String> = template.missing_fields(&["Title 1"]).into_iter().map(|p| p.name).collect();
+        assert_eq!(missing, vec!["Content Placeholder 2"]);
+        assert!(template.require_fields(&["Title 1", "Content Placeholder 2"]).is_ok());
+        match template.require_fields(&[]) {
+            Err(PptxError::Validation { location, message }) => {
+                assert_eq!(location, "slide 1");
+                assert!(message.contains("\"Title 1\"") && message.contains("\"Content Placeholder 2\""));
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+}
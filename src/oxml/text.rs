@@ -3,6 +3,7 @@
 //! Provides types for parsing and generating DrawingML text elements.
 
 use super::xmlchemy::XmlElement;
+use crate::core::{escape_xml, escape_xml_attr};
 
 /// Text body properties (a:bodyPr)
 #[derive(Debug, Clone, Default)]
@@ -177,7 +178,7 @@ impl RunProperties {
             inner.push_str(&format!(r#"<a:solidFill><a:srgbClr val="{color}"/></a:solidFill>"#));
         }
         if let Some(ref font) = self.font_family {
-            inner.push_str(&format!(r#"<a:latin typeface="{font}"/>"#));
+            inner.push_str(&format!(r#"<a:latin typeface="{}"/>"#, escape_xml_attr(font)));
         }
 
         if inner.is_empty() {
@@ -326,14 +327,6 @@ impl Default for TextBody {
     }
 }
 
-fn escape_xml(s: &str) -> String {
-    s.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,8 +8,13 @@
 //! - Emphasis animations (pulse, spin, grow/shrink, etc.)
 //! - Motion path animations
 //! - Slide transitions
+//!
+//! Animations triggered by clicking another shape are written as
+//! interactive sequences (`p:seq` with `nodeType="interactiveSeq"`) next
+//! to the slide's main click sequence.
 
 use crate::exc::PptxError;
+use crate::generator::placement::SlideSize;
 
 /// Animation effect type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -118,6 +123,8 @@ pub enum AnimationTrigger {
     OnClick,
     WithPrevious,
     AfterPrevious,
+    /// Clicking the shape with this ID starts the animation
+    OnShapeClick(u32),
 }
 
 impl AnimationTrigger {
@@ -126,6 +133,15 @@ impl AnimationTrigger {
             AnimationTrigger::OnClick => "onClick",
             AnimationTrigger::WithPrevious => "withPrev",
             AnimationTrigger::AfterPrevious => "afterPrev",
+            AnimationTrigger::OnShapeClick(_) => "clickEffect",
+        }
+    }
+
+    /// ID of the shape whose click starts the animation, for trigger animations
+    pub fn trigger_shape(&self) -> Option<u32> {
+        match self {
+            AnimationTrigger::OnShapeClick(shape_id) => Some(*shape_id),
+            _ => None,
         }
     }
 }
@@ -163,6 +179,95 @@ impl AnimationDirection {
     }
 }
 
+/// Custom motion path for a shape
+///
+/// Points are EMU offsets from the shape's starting position. PowerPoint
+/// stores the path as a VML-like string in fractions of the slide size, so
+/// paths on decks that aren't 4:3 need [`MotionPath::slide_size`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotionPath {
+    pub points: Vec<(i64, i64)>,
+    /// Curve through the points instead of straight segments
+    pub smooth: bool,
+    /// Size of the slide the points are scaled against
+    pub slide_size: SlideSize,
+}
+
+impl MotionPath {
+    /// Create a path through the given points (EMU offsets, usually starting at (0, 0))
+    pub fn points(points: &[(i64, i64)]) -> Self {
+        MotionPath {
+            points: points.to_vec(),
+            smooth: false,
+            slide_size: SlideSize::STANDARD,
+        }
+    }
+
+    /// Curve smoothly through the points
+    pub fn smooth(mut self) -> Self {
+        self.smooth = true;
+        self
+    }
+
+    /// Scale the points against a slide of `size` instead of the standard 4:3 slide
+    pub fn slide_size(mut self, size: SlideSize) -> Self {
+        self.slide_size = size;
+        self
+    }
+
+    /// Generate the `animMotion` path string, e.g. `M 0 0 L 0.25 0.125 E`
+    pub fn to_path_string(&self) -> String {
+        let (width, height) = (self.slide_size.width.get() as f64, self.slide_size.height.get() as f64);
+        let scaled: Vec<(f64, f64)> = self.points.iter()
+            .map(|&(x, y)| (x as f64 / width, y as f64 / height))
+            .collect();
+        let Some(&first) = scaled.first() else {
+            return "M 0 0 E".to_string();
+        };
+
+        let mut path = format!("M {} {}", format_coord(first.0), format_coord(first.1));
+        for i in 1..scaled.len() {
+            let (x, y) = scaled[i];
+            if self.smooth {
+                // Catmull-Rom spline through the points, as cubic Bézier segments
+                let p0 = scaled[i.saturating_sub(2)];
+                let p1 = scaled[i - 1];
+                let p3 = scaled[(i + 1).min(scaled.len() - 1)];
+                let c1 = (p1.0 + (x - p0.0) / 6.0, p1.1 + (y - p0.1) / 6.0);
+                let c2 = (x - (p3.0 - p1.0) / 6.0, y - (p3.1 - p1.1) / 6.0);
+                path.push_str(&format!(
+                    " C {} {} {} {} {} {}",
+                    format_coord(c1.0), format_coord(c1.1),
+                    format_coord(c2.0), format_coord(c2.1),
+                    format_coord(x), format_coord(y)
+                ));
+            } else {
+                path.push_str(&format!(" L {} {}", format_coord(x), format_coord(y)));
+            }
+        }
+        path.push_str(" E");
+        path
+    }
+
+    /// Point types for `ptsTypes`: automatic ends, smooth or corner interior points
+    fn point_types(&self) -> String {
+        let interior = if self.smooth { 'S' } else { 'F' };
+        (0..self.points.len())
+            .map(|i| if i == 0 || i + 1 == self.points.len() { 'A' } else { interior })
+            .collect()
+    }
+}
+
+/// Format a path coordinate with up to 5 decimals and no trailing zeros
+fn format_coord(value: f64) -> String {
+    let text = format!("{:.5}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "" | "-0" => "0".to_string(),
+        _ => text.to_string(),
+    }
+}
+
 /// Single animation on a shape
 #[derive(Debug, Clone)]
 pub struct Animation {
@@ -174,6 +279,8 @@ pub struct Animation {
     pub delay_ms: u32,
     pub repeat_count: Option<u32>,
     pub auto_reverse: bool,
+    /// Custom trajectory, written as `animMotion`
+    pub motion_path: Option<MotionPath>,
 }
 
 impl Animation {
@@ -188,6 +295,7 @@ impl Animation {
             delay_ms: 0,
             repeat_count: None,
             auto_reverse: false,
+            motion_path: None,
         }
     }
 
@@ -197,6 +305,12 @@ impl Animation {
         self
     }
 
+    /// Start the animation when the shape `trigger_shape_id` is clicked
+    pub fn triggered_by(mut self, trigger_shape_id: u32) -> Self {
+        self.trigger = AnimationTrigger::OnShapeClick(trigger_shape_id);
+        self
+    }
+
     /// Set direction
     pub fn direction(mut self, direction: AnimationDirection) -> Self {
         self.direction = direction;
@@ -227,6 +341,15 @@ impl Animation {
         self
     }
 
+    /// Move the shape along a custom path
+    ///
+    /// Turns the animation into a custom motion path effect.
+    pub fn motion_path(mut self, path: MotionPath) -> Self {
+        self.effect = AnimationEffect::Custom;
+        self.motion_path = Some(path);
+        self
+    }
+
     /// Generate animation XML
     pub fn to_xml(&self, seq_id: u32) -> String {
         let repeat_attr = self.repeat_count
@@ -234,6 +357,38 @@ impl Animation {
             .unwrap_or_default();
         let reverse_attr = if self.auto_reverse { r#" autoRev="1""# } else { "" };
 
+        let behavior = format!(
+            r#"<p:cBhvr>
+          <p:cTn id="{}" dur="{}" fill="hold"{}{}>
+            <p:stCondLst><p:cond delay="0"/></p:stCondLst>
+          </p:cTn>
+          <p:tgtEl>
+            <p:spTgt spid="{}"/>
+          </p:tgtEl>{}
+        </p:cBhvr>"#,
+            seq_id + 1,
+            self.duration_ms,
+            repeat_attr,
+            reverse_attr,
+            self.shape_id,
+            if self.motion_path.is_some() {
+                "\n          <p:attrNameLst>\n            <p:attrName>ppt_x</p:attrName>\n            <p:attrName>ppt_y</p:attrName>\n          </p:attrNameLst>"
+            } else {
+                ""
+            }
+        );
+        let effect_xml = match &self.motion_path {
+            Some(path) => format!(
+                r#"<p:animMotion origin="layout" path="{}" pathEditMode="relative" ptsTypes="{}">
+        {}
+      </p:animMotion>"#,
+                path.to_path_string(),
+                path.point_types(),
+                behavior
+            ),
+            None => format!("<p:set>\n        {}\n      </p:set>", behavior),
+        };
+
         format!(
             r#"<p:par>
   <p:cTn id="{}" presetID="{}" presetClass="{}" presetSubtype="0" fill="hold" nodeType="{}">
@@ -241,16 +396,7 @@ impl Animation {
       <p:cond delay="{}"/>
     </p:stCondLst>
     <p:childTnLst>
-      <p:set>
-        <p:cBhvr>
-          <p:cTn id="{}" dur="{}" fill="hold"{}{}>
-            <p:stCondLst><p:cond delay="0"/></p:stCondLst>
-          </p:cTn>
-          <p:tgtEl>
-            <p:spTgt spid="{}"/>
-          </p:tgtEl>
-        </p:cBhvr>
-      </p:set>
+      {}
     </p:childTnLst>
   </p:cTn>
 </p:par>"#,
@@ -259,11 +405,7 @@ impl Animation {
             self.effect.preset_class(),
             self.trigger.as_str(),
             self.delay_ms,
-            seq_id + 1,
-            self.duration_ms,
-            repeat_attr,
-            reverse_attr,
-            self.shape_id
+            effect_xml
         )
     }
 }
@@ -432,16 +574,70 @@ impl SlideAnimations {
     }
 
     /// Generate timing XML for slide
+    ///
+    /// Animations with an `OnShapeClick` trigger go into one interactive
+    /// sequence per trigger shape; all others form the main sequence.
     pub fn to_timing_xml(&self) -> Result<String, PptxError> {
         if self.animations.is_empty() {
             return Ok(String::new());
         }
 
-        let animations_xml: String = self.animations.iter()
-            .enumerate()
-            .map(|(i, a)| a.to_xml((i * 2 + 1) as u32))
-            .collect::<Vec<_>>()
-            .join("\n");
+        // Time node IDs 1 and 2 are the root and main sequence
+        let mut next_id = 3;
+        fn sequence_xml(animations: Vec<&Animation>, next_id: &mut u32) -> String {
+            animations.into_iter()
+                .map(|a| {
+                    let xml = a.to_xml(*next_id);
+                    *next_id += 2;
+                    xml
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+
+        let animations_xml = sequence_xml(
+            self.animations.iter().filter(|a| a.trigger.trigger_shape().is_none()).collect(),
+            &mut next_id,
+        );
+
+        let mut trigger_shapes: Vec<u32> = Vec::new();
+        for shape_id in self.animations.iter().filter_map(|a| a.trigger.trigger_shape()) {
+            if !trigger_shapes.contains(&shape_id) {
+                trigger_shapes.push(shape_id);
+            }
+        }
+        let mut interactive_xml = String::new();
+        for shape_id in trigger_shapes {
+            let seq_id = next_id;
+            next_id += 1;
+            let effects = sequence_xml(
+                self.animations.iter().filter(|a| a.trigger.trigger_shape() == Some(shape_id)).collect(),
+                &mut next_id,
+            );
+            interactive_xml.push_str(&format!(
+                r#"
+          <p:seq concurrent="1" nextAc="seek">
+            <p:cTn id="{seq_id}" restart="whenNotActive" fill="hold" evtFilter="cancelBubble" nodeType="interactiveSeq">
+              <p:stCondLst>
+                <p:cond evt="onClick" delay="0">
+                  <p:tgtEl><p:spTgt spid="{shape_id}"/></p:tgtEl>
+                </p:cond>
+              </p:stCondLst>
+              <p:endSync evt="end" delay="0">
+                <p:rtn val="all"/>
+              </p:endSync>
+              <p:childTnLst>
+                {effects}
+              </p:childTnLst>
+            </p:cTn>
+            <p:nextCondLst>
+              <p:cond evt="onClick" delay="0">
+                <p:tgtEl><p:spTgt spid="{shape_id}"/></p:tgtEl>
+              </p:cond>
+            </p:nextCondLst>
+          </p:seq>"#
+            ));
+        }
 
         Ok(format!(
             r#"<p:timing>
@@ -455,13 +651,14 @@ impl SlideAnimations {
                 {}
               </p:childTnLst>
             </p:cTn>
-          </p:seq>
+          </p:seq>{}
         </p:childTnLst>
       </p:cTn>
     </p:par>
   </p:tnLst>
 </p:timing>"#,
-            animations_xml
+            animations_xml,
+            interactive_xml
         ))
     }
 }
@@ -496,6 +693,37 @@ mod tests {
         assert!(xml.contains("spid=\"2\""));
     }
 
+    #[test]
+    fn test_motion_path_string() {
+        let path = MotionPath::points(&[(0, 0), (1828800, 685800)]);
+        assert_eq!(path.to_path_string(), "M 0 0 L 0.2 0.1 E");
+        assert_eq!(path.point_types(), "AA");
+
+        let curve = MotionPath::points(&[(0, 0), (914400, 685800), (1828800, 0)]).smooth();
+        let text = curve.to_path_string();
+        assert!(text.starts_with("M 0 0 C "));
+        assert_eq!(text.matches(" C ").count(), 2);
+        assert!(text.ends_with("0.2 0 E"));
+        assert_eq!(curve.point_types(), "ASA");
+
+        let wide = MotionPath::points(&[(0, 0), (2438400, 685800)]).slide_size(SlideSize::WIDESCREEN);
+        assert_eq!(wide.to_path_string(), "M 0 0 L 0.2 0.1 E");
+    }
+
+    #[test]
+    fn test_motion_path_animation_xml() {
+        let anim = Animation::new(4, AnimationEffect::Fade)
+            .motion_path(MotionPath::points(&[(0, 0), (1828800, 685800)]))
+            .duration(2000);
+        assert_eq!(anim.effect.preset_class(), "path");
+
+        let xml = anim.to_xml(3);
+        assert!(xml.contains(r#"<p:animMotion origin="layout" path="M 0 0 L 0.2 0.1 E""#));
+        assert!(xml.contains(r#"<p:cTn id="4" dur="2000" fill="hold">"#));
+        assert!(xml.contains("<p:attrName>ppt_x</p:attrName>"));
+        assert!(!xml.contains("<p:set>"));
+    }
+
     #[test]
     fn test_transition_new() {
         let trans = SlideTransition::new(TransitionEffect::Fade);
@@ -532,6 +760,35 @@ mod tests {
         assert!(xml.contains("p:tnLst"));
     }
 
+    #[test]
+    fn test_trigger_animations_use_interactive_sequence() {
+        let anims = SlideAnimations::new()
+            .add(Animation::new(2, AnimationEffect::Fade))
+            .add(Animation::new(4, AnimationEffect::Appear).triggered_by(3))
+            .add(Animation::new(5, AnimationEffect::Appear).triggered_by(3))
+            .add(Animation::new(7, AnimationEffect::Pulse).triggered_by(6));
+        let xml = anims.to_timing_xml().unwrap();
+
+        assert_eq!(xml.matches(r#"nodeType="interactiveSeq""#).count(), 2);
+        assert_eq!(xml.matches(r#"<p:spTgt spid="3"/>"#).count(), 2);
+        assert!(xml.contains(r#"nodeType="clickEffect""#));
+
+        // Triggered effects sit inside their sequence, after the main one
+        let main = xml.find(r#"nodeType="mainSeq""#).unwrap();
+        let interactive = xml.find(r#"nodeType="interactiveSeq""#).unwrap();
+        assert!(main < interactive && interactive < xml.find(r#"spid="4""#).unwrap());
+        assert!(xml.find(r#"spid="2""#).unwrap() < interactive);
+
+        // Time node IDs are unique
+        let mut ids: Vec<&str> = xml.match_indices(r#"<p:cTn id=""#)
+            .map(|(i, m)| xml[i + m.len()..].split('"').next().unwrap())
+            .collect();
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+    }
+
     #[test]
     fn test_effect_preset_class() {
         assert_eq!(AnimationEffect::Fade.preset_class(), "entr");
@@ -3,6 +3,7 @@
 //! Represents docProps/core.xml with document metadata.
 
 use super::base::{Part, PartType, ContentType};
+use crate::core::escape_xml;
 use crate::exc::PptxError;
 use crate::oxml::XmlParser;
 use chrono::Utc;
@@ -78,14 +79,6 @@ impl CorePropertiesPart {
             *rev += 1;
         }
     }
-
-    fn escape_xml(s: &str) -> String {
-        s.replace('&', "&amp;")
-            .replace('<', "&lt;")
-            .replace('>', "&gt;")
-            .replace('"', "&quot;")
-            .replace('\'', "&apos;")
-    }
 }
 
 impl Default for CorePropertiesPart {
@@ -111,22 +104,22 @@ impl Part for CorePropertiesPart {
         let mut elements = Vec::new();
 
         if let Some(ref title) = self.title {
-            elements.push(format!("<dc:title>{}</dc:title>", Self::escape_xml(title)));
+            elements.push(format!("<dc:title>{}</dc:title>", escape_xml(title)));
         }
         if let Some(ref subject) = self.subject {
-            elements.push(format!("<dc:subject>{}</dc:subject>", Self::escape_xml(subject)));
+            elements.push(format!("<dc:subject>{}</dc:subject>", escape_xml(subject)));
         }
         if let Some(ref creator) = self.creator {
-            elements.push(format!("<dc:creator>{}</dc:creator>", Self::escape_xml(creator)));
+            elements.push(format!("<dc:creator>{}</dc:creator>", escape_xml(creator)));
         }
         if let Some(ref keywords) = self.keywords {
-            elements.push(format!("<cp:keywords>{}</cp:keywords>", Self::escape_xml(keywords)));
+            elements.push(format!("<cp:keywords>{}</cp:keywords>", escape_xml(keywords)));
         }
         if let Some(ref description) = self.description {
-            elements.push(format!("<dc:description>{}</dc:description>", Self::escape_xml(description)));
+            elements.push(format!("<dc:description>{}</dc:description>", escape_xml(description)));
         }
         if let Some(ref last_modified_by) = self.last_modified_by {
-            elements.push(format!("<cp:lastModifiedBy>{}</cp:lastModifiedBy>", Self::escape_xml(last_modified_by)));
+            elements.push(format!("<cp:lastModifiedBy>{}</cp:lastModifiedBy>", escape_xml(last_modified_by)));
         }
         if let Some(revision) = self.revision {
             elements.push(format!("<cp:revision>{}</cp:revision>", revision));
@@ -3,6 +3,7 @@
 //! Represents fonts embedded in the presentation for consistent rendering.
 
 use super::base::{Part, PartType, ContentType};
+use crate::core::escape_xml_attr;
 use crate::exc::PptxError;
 
 /// Font embedding type
@@ -106,7 +107,7 @@ impl EmbeddedFontPart {
   </p:font>
   <p:{}><a:extLst><a:ext uri="{{28A0092B-C50C-407E-A947-70E740481C1C}}"><a14:useLocalDpi xmlns:a14="http://schemas.microsoft.com/office/drawing/2010/main" val="0"/></a:ext></a:extLst></p:{}>
 </p:embeddedFont>"#,
-            self.font_name,
+            escape_xml_attr(&self.font_name),
             charset_attr,
             pitch_attr,
             self.embed_type.as_str(),
@@ -73,7 +73,7 @@ pub use relationships::{Relationship, RelationshipType, Relationships};
 
 // Animation
 pub use animation::{
-    Animation, AnimationEffect, AnimationTrigger, AnimationDirection,
+    Animation, AnimationEffect, AnimationTrigger, AnimationDirection, MotionPath,
     SlideTransition, TransitionEffect, SlideAnimations,
 };
 
@@ -2,6 +2,7 @@
 //!
 //! Manages relationships between parts in a PPTX package.
 
+use crate::core::escape_xml_attr;
 use crate::exc::PptxError;
 use crate::oxml::XmlParser;
 
@@ -85,7 +86,7 @@ impl Relationship {
     pub fn to_xml(&self) -> String {
         format!(
             r#"<Relationship Id="{}" Type="{}" Target="{}"/>"#,
-            self.id, self.rel_type.uri(), self.target
+            escape_xml_attr(&self.id), self.rel_type.uri(), escape_xml_attr(&self.target)
         )
     }
 }
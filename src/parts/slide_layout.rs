@@ -3,6 +3,7 @@
 //! Represents a slide layout template (ppt/slideLayouts/slideLayoutN.xml).
 
 use super::base::{Part, PartType, ContentType};
+use crate::core::escape_xml_attr;
 use crate::exc::PptxError;
 
 /// Slide layout types
@@ -140,7 +141,7 @@ impl SlideLayoutPart {
   </p:clrMapOvr>
 </p:sldLayout>"#,
             self.layout_type.type_value(),
-            self.name
+            escape_xml_attr(&self.name)
         )
     }
 }
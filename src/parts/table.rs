@@ -13,7 +13,7 @@
 
 use super::base::{Part, PartType, ContentType};
 use crate::exc::PptxError;
-use crate::core::escape_xml;
+use crate::core::{escape_xml, escape_xml_attr};
 
 /// Horizontal alignment
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -368,7 +368,7 @@ impl TableCellPart {
 
         // Font family
         let font_xml = self.font_family.as_ref()
-            .map(|f| format!(r#"<a:latin typeface="{}"/>"#, f))
+            .map(|f| format!(r#"<a:latin typeface="{}"/>"#, escape_xml_attr(f)))
             .unwrap_or_default();
 
         // Paragraph alignment
@@ -3,6 +3,7 @@
 //! Represents a theme (ppt/theme/themeN.xml).
 
 use super::base::{Part, PartType, ContentType};
+use crate::core::escape_xml_attr;
 use crate::exc::PptxError;
 
 /// Theme color
@@ -171,10 +172,10 @@ impl ThemePart {
   <a:objectDefaults/>
   <a:extraClrSchemeLst/>
 </a:theme>"#,
-            self.name,
+            escape_xml_attr(&self.name),
             colors_xml,
-            self.major_font.typeface,
-            self.minor_font.typeface
+            escape_xml_attr(&self.major_font.typeface),
+            escape_xml_attr(&self.minor_font.typeface)
         )
     }
 }
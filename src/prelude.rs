@@ -27,6 +27,7 @@ pub use crate::generator::{
     create_pptx, create_pptx_with_content,
     BulletStyle, BulletPoint,
     TextFormat, FormattedText,
+    SlideSize, Anchor, Placement, Snippet,
 };
 
 pub use crate::generator::shapes::{
@@ -34,6 +35,7 @@ pub use crate::generator::shapes::{
 };
 
 pub use crate::elements::{Color, RgbColor, Position, Size};
+pub use crate::core::{Emu, Pt, Inches, Cm, Positioned, ElementSized, Styled};
 pub use crate::exc::Result;
 
 /// Font size module with common presets (in points)
@@ -178,6 +180,7 @@ impl QuickPptx {
     }
     
     /// Build and save to a file
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn save(self, path: &str) -> std::result::Result<(), Box<dyn std::error::Error>> {
         let data = self.build()?;
         std::fs::write(path, data)?;
@@ -377,6 +380,9 @@ pub mod colors {
 
 /// Theme presets for presentations
 pub mod themes {
+    use crate::generator::gradients::{GradientFill, GradientDirection};
+    use crate::generator::{ShapeFill, SlideContent};
+
     /// Theme definition with color palette
     #[derive(Debug, Clone)]
     pub struct Theme {
@@ -390,6 +396,44 @@ pub mod themes {
         pub dark: &'static str,
     }
 
+    impl Theme {
+        /// Left-to-right gradient from `primary` to `dark`, used for section bands
+        pub fn section_gradient(&self) -> GradientFill {
+            GradientFill::two_color(self.primary, self.dark)
+                .with_direction(GradientDirection::Horizontal)
+        }
+
+        /// Colors chart series cycle through
+        pub fn chart_palette(&self) -> [&'static str; 4] {
+            [self.primary, self.accent, self.secondary, self.dark]
+        }
+
+        /// Fill in the colors `slide` leaves unset
+        ///
+        /// Titles use `primary`; shapes without a fill get a `primary` fill;
+        /// the table header row gets a `primary` background with
+        /// `background`-colored text; chart series cycle through
+        /// [`chart_palette`](Self::chart_palette). Explicit colors are kept.
+        pub fn apply_defaults(&self, slide: &mut SlideContent) {
+            slide.title_color.get_or_insert_with(|| self.primary.to_string());
+            for shape in slide.shapes.iter_mut().filter(|s| s.fill.is_none() && s.gradient.is_none()) {
+                shape.fill = Some(ShapeFill::new(self.primary));
+            }
+            if let Some(header) = slide.table.as_mut().and_then(|t| t.rows.first_mut()) {
+                for cell in &mut header.cells {
+                    cell.background_color.get_or_insert_with(|| self.primary.to_string());
+                    cell.text_color.get_or_insert_with(|| self.background.to_string());
+                }
+            }
+            let palette = self.chart_palette();
+            for chart in &mut slide.charts {
+                for (series, color) in chart.series.iter_mut().zip(palette.iter().cycle()) {
+                    series.color.get_or_insert_with(|| color.to_string());
+                }
+            }
+        }
+    }
+
     /// Corporate blue theme - Professional and trustworthy
     pub const CORPORATE: Theme = Theme {
         name: "Corporate",
@@ -584,7 +628,7 @@ mod tests {
     #[test]
     fn test_shape_builders() {
         let rect = shapes::rect(1.0, 1.0, 2.0, 1.0);
-        assert_eq!(rect.width, inches(2.0));
+        assert_eq!(rect.width, Emu::from(inches(2.0)));
         
         let circle = shapes::circle(1.0, 1.0, 1.0);
         assert_eq!(circle.width, circle.height);
@@ -593,10 +637,10 @@ mod tests {
     #[test]
     fn test_arrow_shapes() {
         let arrow = shapes::arrow_right(1.0, 1.0, 2.0, 1.0);
-        assert_eq!(arrow.width, inches(2.0));
+        assert_eq!(arrow.width, Emu::from(inches(2.0)));
         
         let up = shapes::arrow_up(1.0, 1.0, 1.0, 2.0);
-        assert_eq!(up.height, inches(2.0));
+        assert_eq!(up.height, Emu::from(inches(2.0)));
     }
     
     #[test]
@@ -0,0 +1,537 @@
+//! Org-wide style guide enforcement
+//!
+//! A [`StyleProfile`] lists the fonts, smallest font size, color palette,
+//! footer text and bullet limit a deck must follow. Attach one with
+//! [`Presentation::style_profile`] to fail the build on the first violation
+//! ([`Enforcement::Error`]) or fix what it can ([`Enforcement::AutoCorrect`]),
+//! or lint an existing file with [`StyleProfile::lint_file`].
+//!
+//! Profiles are usually kept as JSON next to the brand assets:
+//!
+//! ```json
+//! {
+//!   "allowed_fonts": ["Calibri", "Arial"],
+//!   "min_font_size": 14,
+//!   "palette": ["1565C0", "FF6F00", "212121", "FFFFFF"],
+//!   "footer": "ACME Corp — Confidential",
+//!   "max_bullets": 6
+//! }
+//! ```
+//!
+//! Every rule is optional; an empty list or missing value allows anything.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::Presentation;
+use crate::elements::{ColorInput, RgbColor};
+use crate::exc::{PptxError, Result};
+use crate::generator::{Shape, ShapeType, SlideContent};
+use crate::oxml::presentation::PresentationReader;
+
+/// Style rules a deck must follow
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleProfile {
+    /// Font families text may use; empty allows any font
+    pub allowed_fonts: Vec<String>,
+    /// Smallest font size in points
+    pub min_font_size: Option<u32>,
+    /// Colors (hex or CSS names) text, fills and chart series may use; empty allows any color
+    pub palette: Vec<String>,
+    /// Text every slide must show, e.g. `Confidential`
+    pub footer: Option<String>,
+    /// Most bullets a slide may have
+    pub max_bullets: Option<usize>,
+}
+
+/// What the builder does when a slide breaks the profile
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Enforcement {
+    /// Fail the build with the first violation
+    #[default]
+    Error,
+    /// Fix violations: swap in the first allowed font, raise sizes to the
+    /// minimum, snap colors to the nearest palette color, add the footer and
+    /// move extra bullets onto continuation slides
+    AutoCorrect,
+}
+
+/// Rule a [`StyleViolation`] breaks
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleRule {
+    Font,
+    FontSize,
+    Color,
+    Footer,
+    BulletCount,
+}
+
+/// Place where a slide breaks its style profile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleViolation {
+    /// 1-based slide number
+    pub slide: usize,
+    /// Offending element, e.g. `bullet 3` (empty for the slide itself)
+    pub element: String,
+    /// Rule that is broken
+    pub rule: StyleRule,
+    /// What is wrong
+    pub message: String,
+}
+
+impl StyleViolation {
+    /// Location as used by [`PptxError::Validation`], e.g. `slide 2, bullet 3`
+    pub fn location(&self) -> String {
+        if self.element.is_empty() {
+            format!("slide {}", self.slide)
+        } else {
+            format!("slide {}, {}", self.slide, self.element)
+        }
+    }
+}
+
+impl fmt::Display for StyleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location(), self.message)
+    }
+}
+
+impl From<StyleViolation> for PptxError {
+    fn from(v: StyleViolation) -> Self {
+        PptxError::Validation { location: v.location(), message: v.message }
+    }
+}
+
+impl StyleProfile {
+    /// Profile without rules
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a profile from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| PptxError::InvalidValue(format!("invalid style profile: {e}")))
+    }
+
+    /// Read a JSON profile from a file
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Set the allowed font families
+    pub fn allowed_fonts<S: Into<String>>(mut self, fonts: impl IntoIterator<Item = S>) -> Self {
+        self.allowed_fonts = fonts.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the smallest font size in points
+    pub fn min_font_size(mut self, points: u32) -> Self {
+        self.min_font_size = Some(points);
+        self
+    }
+
+    /// Set the allowed colors
+    pub fn palette<S: Into<String>>(mut self, colors: impl IntoIterator<Item = S>) -> Self {
+        self.palette = colors.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Require `text` on every slide
+    pub fn footer(mut self, text: &str) -> Self {
+        self.footer = Some(text.to_string());
+        self
+    }
+
+    /// Set the most bullets a slide may have
+    pub fn max_bullets(mut self, count: usize) -> Self {
+        self.max_bullets = Some(count);
+        self
+    }
+
+    fn font_allowed(&self, font: &str) -> bool {
+        // Theme font references (`+mn-lt`) follow the theme, not the slide
+        self.allowed_fonts.is_empty()
+            || font.starts_with('+')
+            || self.allowed_fonts.iter().any(|f| f.eq_ignore_ascii_case(font))
+    }
+
+    fn palette_rgb(&self) -> Vec<RgbColor> {
+        self.palette.iter().filter_map(|c| RgbColor::from_hex(&ColorInput::from(c.as_str()).to_hex())).collect()
+    }
+
+    fn color_allowed(&self, color: &str) -> bool {
+        self.palette.is_empty()
+            || self.palette.iter().any(|c| ColorInput::from(c.as_str()).to_hex().eq_ignore_ascii_case(color))
+    }
+
+    /// Palette color closest to `color` in RGB space
+    fn nearest_color(&self, color: &str) -> Option<String> {
+        let rgb = RgbColor::from_hex(color)?;
+        let distance = |c: &RgbColor| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(c.r, rgb.r) + d(c.g, rgb.g) + d(c.b, rgb.b)
+        };
+        self.palette_rgb().into_iter().min_by_key(distance).map(|c| c.to_hex())
+    }
+
+    /// Every violation in `slides`
+    pub fn check(&self, slides: &[SlideContent]) -> Vec<StyleViolation> {
+        let mut violations = Vec::new();
+        for (i, slide) in slides.iter().enumerate() {
+            self.check_slide(i + 1, slide, &mut violations);
+        }
+        violations
+    }
+
+    /// Every violation in the slides of `presentation`
+    pub fn lint(&self, presentation: &Presentation) -> Vec<StyleViolation> {
+        self.check(presentation.slides())
+    }
+
+    fn check_slide(&self, number: usize, slide: &SlideContent, out: &mut Vec<StyleViolation>) {
+        let mut report = |element: String, rule: StyleRule, message: String| {
+            out.push(StyleViolation { slide: number, element, rule, message });
+        };
+        let mut text = |element: &str, font: Option<&str>, size: Option<u32>, color: Option<&str>| {
+            if let Some(font) = font.filter(|f| !self.font_allowed(f)) {
+                report(element.to_string(), StyleRule::Font, format!("font \"{font}\" is not allowed"));
+            }
+            if let (Some(size), Some(min)) = (size, self.min_font_size)
+                && size < min
+            {
+                report(element.to_string(), StyleRule::FontSize, format!("font size {size}pt is below {min}pt"));
+            }
+            if let Some(color) = color.filter(|c| !self.color_allowed(c)) {
+                report(element.to_string(), StyleRule::Color, format!("color {color} is not in the palette"));
+            }
+        };
+
+        text("title", None, slide.title_size, slide.title_color.as_deref());
+        text("content", None, slide.content_size, slide.content_color.as_deref());
+        for (i, bullet) in slide.bullets.iter().enumerate() {
+            if let Some(format) = &bullet.format {
+                let element = format!("bullet {}", i + 1);
+                text(&element, format.font_family.as_deref(), format.font_size, format.color.as_deref());
+            }
+        }
+        if let Some(table) = &slide.table {
+            for (r, row) in table.rows.iter().enumerate() {
+                for (c, cell) in row.cells.iter().enumerate() {
+                    let element = format!("table cell ({}, {})", r + 1, c + 1);
+                    text(&element, cell.font_family.as_deref(), cell.font_size, cell.text_color.as_deref());
+                    text(&element, None, None, cell.background_color.as_deref());
+                }
+            }
+        }
+        for (i, shape) in slide.shapes.iter().enumerate() {
+            let fill = shape.fill.as_ref().map(|f| f.color.as_str());
+            text(&format!("shape {}", i + 1), None, None, fill);
+        }
+        for (i, chart) in slide.charts.iter().enumerate() {
+            let element = format!("chart {}", i + 1);
+            if let Some(style) = &chart.style {
+                text(&element, Some(&style.font), Some(style.label_size.min(style.title_size)), None);
+            }
+            for series in &chart.series {
+                text(&element, None, None, series.color.as_deref());
+            }
+        }
+
+        if let Some(footer) = &self.footer
+            && !has_footer(slide, footer)
+        {
+            report(String::new(), StyleRule::Footer, format!("footer \"{footer}\" is missing"));
+        }
+        if let Some(max) = self.max_bullets
+            && bullet_count(slide) > max
+        {
+            report(
+                String::new(),
+                StyleRule::BulletCount,
+                format!("{} bullets exceed the limit of {max}", bullet_count(slide)),
+            );
+        }
+    }
+
+    /// Fix every violation in `slides`
+    ///
+    /// Slides with too many bullets are split; the overflow goes onto
+    /// `"<title> (cont.)"` slides with the same layout and text styling.
+    pub fn correct(&self, slides: Vec<SlideContent>) -> Vec<SlideContent> {
+        let mut corrected = Vec::with_capacity(slides.len());
+        for slide in slides {
+            for mut slide in self.split_bullets(slide) {
+                self.correct_slide(&mut slide);
+                corrected.push(slide);
+            }
+        }
+        corrected
+    }
+
+    fn correct_slide(&self, slide: &mut SlideContent) {
+        let font = |font: &mut Option<String>| {
+            if let Some(allowed) = self.allowed_fonts.first()
+                && font.as_deref().is_some_and(|f| !self.font_allowed(f))
+            {
+                *font = Some(allowed.clone());
+            }
+        };
+        let size = |size: &mut Option<u32>| {
+            if let (Some(s), Some(min)) = (size.as_mut(), self.min_font_size) {
+                *s = (*s).max(min);
+            }
+        };
+        let color = |color: &mut Option<String>| {
+            if let Some(c) = color.as_mut()
+                && !self.color_allowed(c)
+                && let Some(nearest) = self.nearest_color(c)
+            {
+                *c = nearest;
+            }
+        };
+
+        size(&mut slide.title_size);
+        size(&mut slide.content_size);
+        color(&mut slide.title_color);
+        color(&mut slide.content_color);
+        for format in slide.bullets.iter_mut().filter_map(|b| b.format.as_mut()) {
+            font(&mut format.font_family);
+            size(&mut format.font_size);
+            color(&mut format.color);
+        }
+        if let Some(table) = &mut slide.table {
+            for cell in table.rows.iter_mut().flat_map(|r| r.cells.iter_mut()) {
+                font(&mut cell.font_family);
+                size(&mut cell.font_size);
+                color(&mut cell.text_color);
+                color(&mut cell.background_color);
+            }
+        }
+        for fill in slide.shapes.iter_mut().filter_map(|s| s.fill.as_mut()) {
+            let mut fill_color = Some(std::mem::take(&mut fill.color));
+            color(&mut fill_color);
+            fill.color = fill_color.unwrap_or_default();
+        }
+        for chart in &mut slide.charts {
+            if let Some(style) = &mut chart.style {
+                let mut chart_font = Some(std::mem::take(&mut style.font));
+                font(&mut chart_font);
+                style.font = chart_font.unwrap_or_default();
+                if let Some(min) = self.min_font_size {
+                    style.title_size = style.title_size.max(min);
+                    style.label_size = style.label_size.max(min);
+                }
+            }
+            for series in &mut chart.series {
+                color(&mut series.color);
+            }
+        }
+
+        if let Some(footer) = &self.footer
+            && !has_footer(slide, footer)
+        {
+            // Bottom strip of the 4:3 slide, below the content placeholder
+            let shape = Shape::new(ShapeType::Rectangle, 457200, 6356350, 8229600, 365125)
+                .with_name("Footer")
+                .with_text(footer);
+            slide.shapes.push(shape);
+        }
+    }
+
+    fn split_bullets(&self, mut slide: SlideContent) -> Vec<SlideContent> {
+        let Some(max) = self.max_bullets.filter(|max| *max > 0 && bullet_count(&slide) > *max) else {
+            return vec![slide];
+        };
+        let mut bullets = slide.bullets.split_off(slide.bullets.len().min(max));
+        let mut content = slide.content.split_off(slide.content.len().min(max));
+        let title = format!("{} (cont.)", slide.title);
+        let mut slides = Vec::new();
+        while !bullets.is_empty() || !content.is_empty() {
+            let rest_bullets = bullets.split_off(bullets.len().min(max));
+            let rest_content = content.split_off(content.len().min(max));
+            slides.push(SlideContent {
+                bullets,
+                content,
+                bullet_style: slide.bullet_style,
+                title_size: slide.title_size,
+                content_size: slide.content_size,
+                title_bold: slide.title_bold,
+                content_bold: slide.content_bold,
+                title_italic: slide.title_italic,
+                content_italic: slide.content_italic,
+                title_underline: slide.title_underline,
+                content_underline: slide.content_underline,
+                title_color: slide.title_color.clone(),
+                content_color: slide.content_color.clone(),
+                layout: slide.layout,
+                transition: slide.transition,
+                density: slide.density,
+                ..SlideContent::new(&title)
+            });
+            bullets = rest_bullets;
+            content = rest_content;
+        }
+        slides.insert(0, slide);
+        slides
+    }
+
+    /// Lint an existing PPTX file
+    ///
+    /// Checks run fonts, sizes and colors of every shape, the footer text
+    /// and the number of body paragraphs per slide.
+    pub fn lint_file(&self, path: &str) -> Result<Vec<StyleViolation>> {
+        let reader = PresentationReader::open(path)?;
+        let mut violations = Vec::new();
+        for (i, slide) in reader.get_all_slides()?.into_iter().enumerate() {
+            let mut report = |element: String, rule: StyleRule, message: String| {
+                violations.push(StyleViolation { slide: i + 1, element, rule, message });
+            };
+            for shape in &slide.shapes {
+                let element = format!("shape \"{}\"", shape.name);
+                for run in shape.paragraphs.iter().flat_map(|p| &p.runs) {
+                    if let Some(font) = run.font_family.as_deref().filter(|f| !self.font_allowed(f)) {
+                        report(element.clone(), StyleRule::Font, format!("font \"{font}\" is not allowed"));
+                    }
+                    // `sz` is in hundredths of a point
+                    if let (Some(size), Some(min)) = (run.font_size, self.min_font_size)
+                        && size < min * 100
+                    {
+                        let size = size as f64 / 100.0;
+                        report(element.clone(), StyleRule::FontSize, format!("font size {size}pt is below {min}pt"));
+                    }
+                    if let Some(color) = run.color.as_deref().filter(|c| !self.color_allowed(c)) {
+                        report(element.clone(), StyleRule::Color, format!("color {color} is not in the palette"));
+                    }
+                }
+            }
+            if let Some(footer) = &self.footer
+                && !slide.shapes.iter().any(|s| s.text().contains(footer.as_str()))
+            {
+                report(String::new(), StyleRule::Footer, format!("footer \"{footer}\" is missing"));
+            }
+            if let Some(max) = self.max_bullets
+                && slide.body_text.len() > max
+            {
+                let count = slide.body_text.len();
+                report(String::new(), StyleRule::BulletCount, format!("{count} bullets exceed the limit of {max}"));
+            }
+        }
+        Ok(violations)
+    }
+}
+
+fn bullet_count(slide: &SlideContent) -> usize {
+    if slide.bullets.is_empty() { slide.content.len() } else { slide.bullets.len() }
+}
+
+fn has_footer(slide: &SlideContent, footer: &str) -> bool {
+    slide.shapes.iter().any(|s| s.text.as_deref().is_some_and(|t| t.contains(footer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{BulletTextFormat, ChartBuilder, ChartSeries, ChartType, ShapeFill};
+
+    fn profile() -> StyleProfile {
+        StyleProfile::new()
+            .allowed_fonts(["Calibri", "Arial"])
+            .min_font_size(14)
+            .palette(["#1565C0", "white", "212121"])
+            .footer("Confidential")
+            .max_bullets(3)
+    }
+
+    fn off_brand() -> SlideContent {
+        let mut slide = SlideContent::new("Results").title_color("FF0000").content_size(10);
+        for text in ["a", "b", "c", "d", "e"] {
+            slide = slide.add_bullet(text);
+        }
+        slide.bullets[0].format = Some(BulletTextFormat::new().font_family("Comic Sans MS"));
+        slide.shapes.push(Shape::new(ShapeType::Rectangle, 0, 0, 100, 100).with_fill(ShapeFill::new("1565C0")));
+        slide.charts.push(
+            ChartBuilder::new("Sales", ChartType::Bar)
+                .categories(vec!["Q1"])
+                .add_series(ChartSeries::new("2025", vec![1.0]).with_color("0000FF"))
+                .build(),
+        );
+        slide
+    }
+
+    #[test]
+    fn test_profile_from_json() {
+        let json = r#"{"allowed_fonts": ["Calibri"], "min_font_size": 12, "footer": "ACME", "max_bullets": 5}"#;
+        let profile = StyleProfile::from_json(json).unwrap();
+        assert_eq!(profile.allowed_fonts, ["Calibri"]);
+        assert_eq!(profile.min_font_size, Some(12));
+        assert!(profile.palette.is_empty());
+        assert!(StyleProfile::from_json(r#"{"max_slides": 3}"#).is_err());
+    }
+
+    #[test]
+    fn test_check_reports_violations() {
+        let violations = profile().check(&[off_brand()]);
+        let rules: Vec<_> = violations.iter().map(|v| (v.element.as_str(), v.rule)).collect();
+        assert_eq!(
+            rules,
+            [
+                ("title", StyleRule::Color),
+                ("content", StyleRule::FontSize),
+                ("bullet 1", StyleRule::Font),
+                ("chart 1", StyleRule::Color),
+                ("", StyleRule::Footer),
+                ("", StyleRule::BulletCount),
+            ]
+        );
+        assert_eq!(violations[0].to_string(), "slide 1, title: color FF0000 is not in the palette");
+        assert!(StyleProfile::new().check(&[off_brand()]).is_empty());
+    }
+
+    #[test]
+    fn test_correct_fixes_slides() {
+        let profile = profile();
+        let slides = profile.correct(vec![off_brand()]);
+        assert_eq!(slides.len(), 2);
+        assert!(profile.check(&slides).is_empty());
+
+        let first = &slides[0];
+        assert_eq!(first.title_color.as_deref(), Some("212121"));
+        assert_eq!(first.content_size, Some(14));
+        assert_eq!(first.bullets.len(), 3);
+        assert_eq!(first.bullets[0].format.as_ref().unwrap().font_family.as_deref(), Some("Calibri"));
+        assert_eq!(first.charts[0].series[0].color.as_deref(), Some("1565C0"));
+
+        let cont = &slides[1];
+        assert_eq!(cont.title, "Results (cont.)");
+        assert_eq!(cont.content, ["d", "e"]);
+        assert!(cont.shapes.iter().any(|s| s.text.as_deref() == Some("Confidential")));
+        assert!(cont.charts.is_empty());
+    }
+
+    #[test]
+    fn test_presentation_enforcement() {
+        let pres = Presentation::new().add_slide(off_brand());
+        let Err(PptxError::Validation { location, .. }) = pres.clone().style_profile(profile(), Enforcement::Error).build()
+        else {
+            panic!("off-brand slide built");
+        };
+        assert_eq!(location, "slide 1, title");
+        assert!(pres.style_profile(profile(), Enforcement::AutoCorrect).build().is_ok());
+    }
+
+    #[test]
+    fn test_lint_file() {
+        let path = std::env::temp_dir().join(format!("ppt_rs_style_{}.pptx", uuid::Uuid::new_v4()));
+        Presentation::new().add_slide(off_brand()).save(&path).unwrap();
+        let violations = profile().lint_file(path.to_str().unwrap()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let rules: Vec<_> = violations.iter().map(|v| v.rule).collect();
+        assert!(rules.contains(&StyleRule::Font), "{violations:?}");
+        assert!(rules.contains(&StyleRule::Footer));
+        assert!(rules.contains(&StyleRule::BulletCount));
+        assert!(violations.iter().all(|v| v.slide == 1));
+    }
+}
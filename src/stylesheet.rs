@@ -0,0 +1,313 @@
+//! Named text styles, like CSS classes
+//!
+//! A [`StyleSheet`] defines formatting once under a name, and bullets,
+//! shapes and table cells refer to it with `.style("name")` instead of
+//! repeating the same size, font and color calls:
+//!
+//! ```
+//! use ppt_rs::{BulletPoint, Presentation, SchemeColor, Shape, ShapeType, SlideContent};
+//! use ppt_rs::prelude::themes::CORPORATE;
+//! use ppt_rs::stylesheet::{Style, StyleSheet};
+//!
+//! let styles = StyleSheet::new()
+//!     .define("kpi-label", Style::new().size(14).bold().color(SchemeColor::Accent1))
+//!     .define("note", Style::new().size(12).italic().spacing(6, 0));
+//!
+//! let slide = SlideContent::new("Q3")
+//!     .add_bullet_point(BulletPoint::new("Revenue up 12%").style("kpi-label"))
+//!     .add_bullet_point(BulletPoint::new("Excludes one-offs").style("note"))
+//!     .add_shape(Shape::new(ShapeType::Rectangle, 457200, 5_000_000, 2_000_000, 500_000)
+//!         .with_text("Target met")
+//!         .style("kpi-label"));
+//!
+//! let pres = Presentation::with_title("Review").theme(&CORPORATE).styles(styles).add_slide(slide);
+//! assert!(pres.build().is_ok());
+//! ```
+//!
+//! Styles are resolved when the presentation is built. Formatting set on the
+//! element itself wins over its style, and scheme colors take the active
+//! theme's palette (`accent1` is the theme's primary color). References to
+//! styles the sheet doesn't define are reported by
+//! [`Presentation::build_with_warnings`](crate::api::Presentation::build_with_warnings).
+
+use std::collections::BTreeMap;
+
+use crate::elements::{Color, ColorInput, SchemeColor};
+use crate::generator::{BulletPoint, FormattedText, Shape, SlideContent, TableCell, Warning, WarningKind, Warnings};
+use crate::prelude::themes::Theme;
+
+/// Text formatting defined once and referred to by name
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    pub font_family: Option<String>,
+    /// Font size in points
+    pub size: Option<u32>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub color: Option<ColorInput>,
+    /// Space before the paragraph in points (bullets only)
+    pub space_before: Option<u32>,
+    /// Space after the paragraph in points (bullets only)
+    pub space_after: Option<u32>,
+}
+
+impl Style {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn font(mut self, family: &str) -> Self {
+        self.font_family = Some(family.to_string());
+        self
+    }
+
+    /// Set the font size in points
+    pub fn size(mut self, points: u32) -> Self {
+        self.size = Some(points);
+        self
+    }
+
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Set the text color; scheme colors follow the presentation's theme
+    pub fn color(mut self, color: impl Into<ColorInput>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the space before and after bullet paragraphs in points
+    pub fn spacing(mut self, before: u32, after: u32) -> Self {
+        self.space_before = Some(before);
+        self.space_after = Some(after);
+        self
+    }
+
+    /// Hex color of the text under `theme`
+    fn resolved_color(&self, theme: Option<&Theme>) -> Option<String> {
+        self.color.as_ref().map(|color| match (color.color(), theme) {
+            (Ok(Color::Scheme(scheme)), Some(theme)) => theme_color(scheme, theme).unwrap_or_else(|| color.to_hex()),
+            _ => color.to_hex(),
+        })
+    }
+}
+
+/// Theme color a scheme color slot maps to
+fn theme_color(scheme: &SchemeColor, theme: &Theme) -> Option<String> {
+    let hex = match scheme {
+        SchemeColor::Accent1 => theme.primary,
+        SchemeColor::Accent2 => theme.secondary,
+        SchemeColor::Accent3 => theme.accent,
+        SchemeColor::Dark1 | SchemeColor::Text1 => theme.text,
+        SchemeColor::Dark2 => theme.dark,
+        SchemeColor::Light1 | SchemeColor::Background1 => theme.background,
+        SchemeColor::Light2 | SchemeColor::Background2 => theme.light,
+        _ => return None,
+    };
+    Some(hex.to_string())
+}
+
+/// Named styles for a presentation
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyleSheet {
+    styles: BTreeMap<String, Style>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define (or replace) the style called `name`
+    pub fn define(mut self, name: &str, style: Style) -> Self {
+        self.styles.insert(name.to_string(), style);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Style> {
+        self.styles.get(name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// Format the styled bullets, shapes and table cells of `slide`
+    ///
+    /// Formatting the element already has is kept; names the sheet doesn't
+    /// define are skipped (see [`Self::check`]).
+    pub fn apply(&self, slide: &mut SlideContent, theme: Option<&Theme>) {
+        for bullet in &mut slide.bullets {
+            if let Some(style) = bullet.style_name.as_deref().and_then(|name| self.get(name)) {
+                style_bullet(bullet, style, theme);
+            }
+        }
+        for shape in &mut slide.shapes {
+            if let Some(style) = shape.style_name.as_deref().and_then(|name| self.get(name)) {
+                style_shape(shape, style, theme);
+            }
+        }
+        let cells = slide.table.iter_mut().flat_map(|t| &mut t.rows).flat_map(|r| &mut r.cells);
+        for cell in cells {
+            if let Some(style) = cell.style_name.as_deref().and_then(|name| self.get(name)) {
+                style_cell(cell, style, theme);
+            }
+        }
+    }
+
+    /// Warnings for style names `slides` use that the sheet doesn't define
+    pub fn check(&self, slides: &[SlideContent]) -> Warnings {
+        let mut warnings = Warnings::new();
+        for (i, slide) in slides.iter().enumerate() {
+            let names = slide.bullets.iter().filter_map(|b| b.style_name.as_deref())
+                .chain(slide.shapes.iter().filter_map(|s| s.style_name.as_deref()))
+                .chain(slide.table.iter().flat_map(|t| &t.rows).flat_map(|r| &r.cells).filter_map(|c| c.style_name.as_deref()));
+            let mut reported: Vec<&str> = Vec::new();
+            for name in names {
+                if !self.styles.contains_key(name) && !reported.contains(&name) {
+                    reported.push(name);
+                    warnings.push(Warning::new(Some(i + 1), WarningKind::UnknownStyle { name: name.to_string() }));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+fn style_bullet(bullet: &mut BulletPoint, style: &Style, theme: Option<&Theme>) {
+    let format = bullet.format.get_or_insert_with(Default::default);
+    format.bold |= style.bold;
+    format.italic |= style.italic;
+    format.underline |= style.underline;
+    if format.font_size.is_none() {
+        format.font_size = style.size;
+    }
+    if format.font_family.is_none() {
+        format.font_family = style.font_family.clone();
+    }
+    if format.color.is_none() {
+        format.color = style.resolved_color(theme);
+    }
+    if bullet.spacing_before.is_none() {
+        bullet.spacing_before = style.space_before.map(|points| points * 100);
+    }
+    if bullet.spacing_after.is_none() {
+        bullet.spacing_after = style.space_after.map(|points| points * 100);
+    }
+}
+
+/// Style every run; plain text becomes a single run so it can carry the format
+fn style_shape(shape: &mut Shape, style: &Style, theme: Option<&Theme>) {
+    if shape.rich_text.is_empty() {
+        match &shape.text {
+            Some(text) => shape.rich_text.push(FormattedText::new(text)),
+            None => return,
+        }
+    }
+    for run in &mut shape.rich_text {
+        let format = &mut run.format;
+        format.bold |= style.bold;
+        format.italic |= style.italic;
+        format.underline |= style.underline;
+        if format.font_size.is_none() {
+            format.font_size = style.size;
+        }
+        if format.font_family.is_none() {
+            format.font_family = style.font_family.clone();
+        }
+        if format.color.is_none() {
+            format.color = style.resolved_color(theme);
+        }
+    }
+}
+
+fn style_cell(cell: &mut TableCell, style: &Style, theme: Option<&Theme>) {
+    cell.bold |= style.bold;
+    cell.italic |= style.italic;
+    cell.underline |= style.underline;
+    if cell.font_size.is_none() {
+        cell.font_size = style.size;
+    }
+    if cell.font_family.is_none() {
+        cell.font_family = style.font_family.clone();
+    }
+    if cell.text_color.is_none() {
+        cell.text_color = style.resolved_color(theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{BulletTextFormat, ShapeType, TableBuilder, TableRow};
+    use crate::prelude::themes::CORPORATE;
+
+    fn sheet() -> StyleSheet {
+        StyleSheet::new()
+            .define("kpi", Style::new().size(14).bold().color(SchemeColor::Accent1).spacing(6, 3))
+            .define("code", Style::new().font("Consolas").color("#00FF00"))
+    }
+
+    #[test]
+    fn test_bullet_style_keeps_explicit_format() {
+        let mut slide = SlideContent::new("KPIs")
+            .add_bullet_point(BulletPoint::new("Revenue").style("kpi"))
+            .add_bullet_point(BulletPoint::new("Margin").font_size(20).style("kpi"));
+        sheet().apply(&mut slide, Some(&CORPORATE));
+
+        let format = slide.bullets[0].format.as_ref().unwrap();
+        assert_eq!(format.font_size, Some(14));
+        assert!(format.bold);
+        assert_eq!(format.color.as_deref(), Some(CORPORATE.primary));
+        assert_eq!((slide.bullets[0].spacing_before, slide.bullets[0].spacing_after), (Some(600), Some(300)));
+        assert_eq!(slide.bullets[1].format.as_ref().unwrap().font_size, Some(20));
+    }
+
+    #[test]
+    fn test_scheme_color_without_theme_uses_default_palette() {
+        let mut bullet = BulletPoint::new("x").with_format(BulletTextFormat::new());
+        style_bullet(&mut bullet, sheet().get("kpi").unwrap(), None);
+        assert_eq!(bullet.format.unwrap().color, Some(Color::Scheme(SchemeColor::Accent1).to_hex()));
+    }
+
+    #[test]
+    fn test_shape_and_cell_styles() {
+        let table = TableBuilder::new(vec![914400])
+            .add_row(TableRow::new(vec![TableCell::new("fn main()").style("code")]))
+            .build();
+        let mut slide = SlideContent::new("Code")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 914400).with_text("42").style("kpi"))
+            .table(table);
+        sheet().apply(&mut slide, Some(&CORPORATE));
+
+        let run = &slide.shapes[0].rich_text[0];
+        assert_eq!(run.text, "42");
+        assert_eq!(run.format.font_size, Some(14));
+        let cell = &slide.table.as_ref().unwrap().rows[0].cells[0];
+        assert_eq!(cell.font_family.as_deref(), Some("Consolas"));
+        assert_eq!(cell.text_color.as_deref(), Some("00FF00"));
+    }
+
+    #[test]
+    fn test_check_reports_unknown_names_once_per_slide() {
+        let slide = SlideContent::new("Typos")
+            .add_bullet_point(BulletPoint::new("a").style("kpi-lable"))
+            .add_bullet_point(BulletPoint::new("b").style("kpi-lable"))
+            .add_bullet_point(BulletPoint::new("c").style("kpi"));
+        let messages: Vec<String> = sheet().check(&[slide]).iter().map(ToString::to_string).collect();
+        assert_eq!(messages, ["slide 1: unknown style 'kpi-lable'"]);
+    }
+}
@@ -0,0 +1,225 @@
+//! Seeded random decks for examples, benchmarks and property tests
+//!
+//! [`DeckGenerator`] builds slides with varied layouts, bullets, tables,
+//! charts, shapes and notes, mixing plain ASCII with accented, CJK,
+//! right-to-left and emoji text and XML metacharacters. The same seed
+//! always gives the same deck, so a failing case can be replayed from
+//! its seed alone:
+//!
+//! ```
+//! use ppt_rs::testdata::DeckGenerator;
+//!
+//! for seed in 0..5 {
+//!     let deck = DeckGenerator::new(seed).presentation(4);
+//!     deck.validate_strict().unwrap_or_else(|e| panic!("seed {seed}: {e}"));
+//! }
+//! ```
+
+use crate::api::Presentation;
+use crate::generator::{
+    BulletStyle, Chart, ChartBuilder, ChartSeries, ChartType, Region, Shape, ShapeFill, ShapeType, SlideContent,
+    SlideLayout, Table, TableBuilder, TransitionType,
+};
+
+/// Words that text is drawn from; some need escaping or complex shaping
+const WORDS: &[&str] = &[
+    "quarterly", "revenue", "growth", "roadmap", "customer", "launch", "team", "review",
+    "naïve", "façade", "Zürich", "señor", "Ελληνικά", "Привет", "日本語", "中文", "한국어",
+    "العربية", "עברית", "हिन्दी", "🚀", "📈", "✓", "<tag>", "R&D", "\"quoted\"", "it's", "50%",
+];
+
+const LAYOUTS: [SlideLayout; 7] = [
+    SlideLayout::TitleOnly,
+    SlideLayout::TitleAndContent,
+    SlideLayout::TitleAndBigContent,
+    SlideLayout::Blank,
+    SlideLayout::CenteredTitle,
+    SlideLayout::TwoColumn,
+    SlideLayout::SectionHeader,
+];
+
+const SHAPES: [ShapeType; 8] = [
+    ShapeType::Rectangle,
+    ShapeType::RoundedRectangle,
+    ShapeType::Ellipse,
+    ShapeType::Triangle,
+    ShapeType::Diamond,
+    ShapeType::RightArrow,
+    ShapeType::Star5,
+    ShapeType::Hexagon,
+];
+
+const TRANSITIONS: [TransitionType; 5] = [
+    TransitionType::None,
+    TransitionType::Fade,
+    TransitionType::Push,
+    TransitionType::Wipe,
+    TransitionType::Zoom,
+];
+
+/// Small deterministic PRNG (SplitMix64)
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform integer in `low..=high`
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_u64() % (high - low + 1) as u64) as usize
+    }
+
+    /// Uniform float in `0.0..1.0`
+    pub fn float(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// True with probability `p`
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.float() < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() - 1)]
+    }
+}
+
+/// Generator of random but reproducible slides and decks
+#[derive(Clone, Debug)]
+pub struct DeckGenerator {
+    rng: Rng,
+}
+
+impl DeckGenerator {
+    pub fn new(seed: u64) -> Self {
+        DeckGenerator { rng: Rng::new(seed) }
+    }
+
+    /// The underlying random number generator
+    pub fn rng(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
+    /// `1..=max_words` random words
+    pub fn text(&mut self, max_words: usize) -> String {
+        let count = self.rng.range(1, max_words.max(1));
+        (0..count).map(|_| *self.rng.pick(WORDS)).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Table of 2-5 rows and columns with a header row
+    pub fn table(&mut self) -> Table {
+        let columns = self.rng.range(2, 5);
+        let rows = self.rng.range(2, 5);
+        let mut builder = TableBuilder::new(vec![1_500_000; columns]).position(500_000, 1_600_000);
+        for _ in 0..rows {
+            let cells: Vec<String> = (0..columns).map(|_| self.text(3)).collect();
+            builder = builder.add_simple_row(cells.iter().map(String::as_str).collect());
+        }
+        builder.build()
+    }
+
+    /// Chart of any type, with the series count its type needs
+    pub fn chart(&mut self) -> Chart {
+        let chart_type = *self.rng.pick(&ChartType::ALL);
+        let categories: Vec<String> = (0..self.rng.range(2, 6)).map(|_| self.text(2)).collect();
+        let series_count = match chart_type {
+            ChartType::StockHLC => 3,
+            ChartType::StockOHLC => 4,
+            _ => self.rng.range(1, 3),
+        };
+        let title = self.text(4);
+        let mut builder = ChartBuilder::new(&title, chart_type)
+            .categories(categories.iter().map(String::as_str).collect())
+            .region(Region::RightHalf);
+        for _ in 0..series_count {
+            let values = categories.iter().map(|_| (self.rng.float() * 1000.0).round() / 10.0).collect();
+            builder = builder.add_series(ChartSeries::new(&self.text(2), values));
+        }
+        builder.build()
+    }
+
+    /// Filled shape, sometimes with text, somewhere on the slide
+    pub fn shape(&mut self) -> Shape {
+        let shape_type = *self.rng.pick(&SHAPES);
+        let x = self.rng.range(0, 7_000_000) as u32;
+        let y = self.rng.range(0, 5_500_000) as u32;
+        let width = self.rng.range(300_000, 2_000_000) as u32;
+        let height = self.rng.range(300_000, 1_500_000) as u32;
+        let color = format!("{:06X}", self.rng.next_u64() & 0xFF_FFFF);
+        let mut shape = Shape::new(shape_type, x, y, width, height).with_fill(ShapeFill::new(color.as_str()));
+        if self.rng.chance(0.5) {
+            shape = shape.with_text(&self.text(5));
+        }
+        shape
+    }
+
+    /// Slide with a random layout and a random mix of elements
+    pub fn slide(&mut self) -> SlideContent {
+        let mut slide = SlideContent::new(&self.text(6))
+            .layout(*self.rng.pick(&LAYOUTS))
+            .with_transition(*self.rng.pick(&TRANSITIONS));
+        if self.rng.chance(0.3) {
+            slide = slide.with_bullet_style(BulletStyle::Number);
+        }
+        for _ in 0..self.rng.range(0, 6) {
+            slide = if self.rng.chance(0.3) {
+                slide.add_sub_bullet(&self.text(8))
+            } else {
+                slide.add_bullet(&self.text(8))
+            };
+        }
+        if self.rng.chance(0.3) {
+            slide = slide.table(self.table());
+        }
+        if self.rng.chance(0.3) {
+            slide = slide.add_chart(self.chart());
+        }
+        for _ in 0..self.rng.range(0, 3) {
+            slide = slide.add_shape(self.shape());
+        }
+        if self.rng.chance(0.4) {
+            slide = slide.notes(&self.text(12));
+        }
+        slide
+    }
+
+    /// Deck of `slides` random slides
+    pub fn presentation(&mut self, slides: usize) -> Presentation {
+        let title = self.text(4);
+        (0..slides).fold(Presentation::with_title(&title), |pres, _| pres.add_slide(self.slide()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_deck() {
+        let a = DeckGenerator::new(7).slide();
+        let b = DeckGenerator::new(7).slide();
+        assert_eq!(a.title, b.title);
+        assert_eq!(a.bullets.len(), b.bullets.len());
+        assert_ne!(DeckGenerator::new(7).text(20), DeckGenerator::new(8).text(20));
+    }
+
+    #[test]
+    fn test_generated_decks_pass_strict_validation() {
+        for seed in 0..64 {
+            let deck = DeckGenerator::new(seed).presentation(6);
+            deck.validate_strict().unwrap_or_else(|e| panic!("seed {seed}: {e}"));
+        }
+    }
+}
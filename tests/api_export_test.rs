@@ -1,5 +1,6 @@
 use ppt_rs::api::Presentation;
 use ppt_rs::generator::SlideContent;
+use ppt_rs::export::notes_script;
 use std::path::Path;
 use std::fs;
 
@@ -40,6 +41,24 @@ fn test_from_path_api() {
     fs::remove_file(pptx_path).unwrap();
 }
 
+#[test]
+fn test_notes_script_from_saved_deck() {
+    let pres = Presentation::new()
+        .title("Notes Test")
+        .add_slide(SlideContent::new("Opening").notes("Welcome the audience."))
+        .add_slide(SlideContent::new("Closing"));
+
+    let pptx_path = "test_notes_script.pptx";
+    pres.save(pptx_path).unwrap();
+
+    let imported = Presentation::from_path(pptx_path).unwrap();
+    let script = notes_script(&imported);
+    assert!(script.contains("## Slide 1: Opening\n\nWelcome the audience."));
+    assert!(script.contains("## Slide 2: Closing"));
+
+    fs::remove_file(pptx_path).unwrap();
+}
+
 #[test]
 #[ignore] // Requires LibreOffice
 fn test_pdf_export_api() {
@@ -100,3 +119,27 @@ fn test_from_pdf_api() {
         }
     }
 }
+
+#[test]
+fn test_root_facade_covers_reading_and_repair() {
+    // Readers, editors and the package type are reachable without the low-level modules
+    use ppt_rs::{Package, PptxRepair, PresentationEditor, PresentationReader, SlideParser};
+
+    let data = Presentation::with_title("Facade")
+        .add_slide(SlideContent::new("One").add_bullet("Point"))
+        .build()
+        .unwrap();
+    let path = "test_root_facade.pptx";
+    fs::write(path, &data).unwrap();
+
+    let reader = PresentationReader::open(path).unwrap();
+    assert_eq!(reader.slide_count(), 1);
+    assert!(PresentationEditor::open(path).is_ok());
+    let package = Package::open(path).unwrap();
+    let slide = SlideParser::parse(&package.get_part_string("ppt/slides/slide1.xml").unwrap()).unwrap();
+    assert_eq!(slide.title.as_deref(), Some("One"));
+    assert!(PptxRepair::from_bytes(&data).is_ok());
+    fs::remove_file(path).unwrap();
+
+    assert_eq!(ppt_rs::VERSION, env!("CARGO_PKG_VERSION"));
+}
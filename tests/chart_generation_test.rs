@@ -65,3 +65,71 @@ fn test_chart_generation() {
     // Cleanup
     std::fs::remove_file(output).unwrap_or(());
 }
+
+#[test]
+fn test_multiple_charts_with_table_and_shapes() {
+    use ppt_rs::generator::{create_pptx_with_content, Region, Shape, ShapeType, TableBuilder};
+    use std::io::Cursor;
+
+    let bar = ChartBuilder::new("Revenue", ChartType::Bar)
+        .categories(vec!["Q1", "Q2"])
+        .add_series(ChartSeries::new("2024", vec![10.0, 20.0]))
+        .region(Region::LeftHalf)
+        .build();
+    let pie = ChartBuilder::new("Mix", ChartType::Pie)
+        .categories(vec!["A", "B"])
+        .add_series(ChartSeries::new("Share", vec![60.0, 40.0]))
+        .region(Region::RightHalf)
+        .build();
+    let table = TableBuilder::new(vec![1828800, 1828800])
+        .add_simple_row(vec!["Metric", "Value"])
+        .position(457200, 5500000)
+        .build();
+
+    let slides = vec![
+        SlideContent::new("First").add_chart(bar.clone()),
+        SlideContent::new("Dashboard")
+            .notes("Walk through both charts")
+            .table(table)
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 914400))
+            .add_shape(Shape::new(ShapeType::Ellipse, 914400, 0, 914400, 914400))
+            .add_chart(bar)
+            .add_chart(pie),
+    ];
+
+    let data = create_pptx_with_content("Mixed", slides).unwrap();
+    let mut archive = zip::ZipArchive::new(Cursor::new(data)).unwrap();
+
+    for chart in ["ppt/charts/chart1.xml", "ppt/charts/chart2.xml", "ppt/charts/chart3.xml"] {
+        assert!(archive.by_name(chart).is_ok(), "{chart} should exist");
+    }
+
+    let mut content_types = String::new();
+    archive.by_name("[Content_Types].xml").unwrap().read_to_string(&mut content_types).unwrap();
+    assert!(content_types.contains("/ppt/charts/chart3.xml"));
+
+    // Slide 2 has notes (rId2), so its charts are rId3 and rId4 -> chart2, chart3
+    let mut rels = String::new();
+    archive.by_name("ppt/slides/_rels/slide2.xml.rels").unwrap().read_to_string(&mut rels).unwrap();
+    assert!(rels.contains(r#"Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="../charts/chart2.xml""#));
+    assert!(rels.contains(r#"Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="../charts/chart3.xml""#));
+
+    let mut slide_xml = String::new();
+    archive.by_name("ppt/slides/slide2.xml").unwrap().read_to_string(&mut slide_xml).unwrap();
+    assert!(slide_xml.contains(r#"r:id="rId3""#));
+    assert!(slide_xml.contains(r#"r:id="rId4""#));
+    assert!(slide_xml.contains("<a:tbl>"));
+
+    // Every element on the slide has a unique id
+    let mut ids: Vec<&str> = slide_xml
+        .match_indices("<p:cNvPr id=\"")
+        .map(|(i, m)| {
+            let rest = &slide_xml[i + m.len()..];
+            &rest[..rest.find('"').unwrap()]
+        })
+        .collect();
+    let total = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), total, "cNvPr ids must be unique on a slide");
+}
@@ -0,0 +1,332 @@
+//! Compatibility corpus: decks that must open without a repair prompt
+//!
+//! PowerPoint offers to "repair" files with dangling relationships,
+//! undeclared parts, duplicate shape IDs or malformed XML even when they
+//! otherwise render. Every case in [`corpus`] exercises one feature area
+//! and must pass:
+//!
+//! - `validate_package` (XML, content types, relationships, shape IDs)
+//! - `PptxRepair::validate` reporting no issues
+//! - re-reading with `PresentationReader`
+//!
+//! None of these is a schema validator: they check that parts are
+//! well-formed and wired together, not element order or which children an
+//! element allows. A cache misplaced under `c:catAx`, for instance, passes
+//! all three. Those rules are covered only by the XML generators' unit
+//! tests, or by running the Open XML SDK validator by hand over the decks
+//! in `PPTX_CORPUS_DIR` (CI uploads them but does not validate them).
+//!
+//! Set `PPTX_CORPUS_DIR` to also write the decks out, e.g. for the Open XML
+//! SDK validator. `test_corpus_opens_in_libreoffice` is ignored by default;
+//! CI runs it with `cargo test --test compat_test -- --include-ignored` where
+//! LibreOffice is installed.
+
+use std::io::Cursor;
+use std::path::Path;
+use std::process::Command;
+
+use ppt_rs::generator::validate::validate_package;
+use ppt_rs::generator::{
+    ActionSound, Audio, AudioFormat, BulletStyle, ChartBuilder, ChartSeries, ChartType, Connector, ConnectorType,
+    CustomLayout, FormattedText, Hyperlink, Image, ImageGrid, ImageSource, MorphOption, PrintSettings, Region, Shape,
+    ShapeFill, ShapeGradientDirection, ShapeGradientFill, ShapeType, SlideContent, SlideLayout, SlideNumberFormat,
+    SlideNumbering, SlideZoom, Table, TableCell, TableRow, TransitionType, Video, VideoFormat,
+};
+use ppt_rs::prelude::themes;
+use ppt_rs::testdata::DeckGenerator;
+use ppt_rs::{CompatProfile, Presentation, PptxRepair, PresentationReader};
+
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut data = Vec::new();
+    image::RgbImage::from_pixel(width, height, image::Rgb([30, 90, 160]))
+        .write_to(&mut Cursor::new(&mut data), image::ImageOutputFormat::Png)
+        .unwrap();
+    data
+}
+
+fn wav() -> Vec<u8> {
+    let mut wav = b"RIFF".to_vec();
+    wav.extend_from_slice(&36u32.to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&[1, 0, 1, 0]);
+    wav.extend_from_slice(&8000u32.to_le_bytes());
+    wav.extend_from_slice(&16000u32.to_le_bytes());
+    wav.extend_from_slice(&[2, 0, 16, 0]);
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&0u32.to_le_bytes());
+    wav
+}
+
+fn deck(slides: impl IntoIterator<Item = SlideContent>) -> Presentation {
+    slides.into_iter().fold(Presentation::with_title("Compatibility"), Presentation::add_slide)
+}
+
+fn text() -> Presentation {
+    deck([
+        SlideContent::new("Bullets")
+            .add_bullet("First")
+            .add_sub_bullet("Nested")
+            .add_numbered("Numbered")
+            .add_lettered("Lettered"),
+        SlideContent::new("Scripts").with_bullet_style(BulletStyle::Number)
+            .add_bullet("naïve façade Zürich")
+            .add_bullet("日本語 中文 한국어")
+            .add_bullet("العربية עברית")
+            .add_bullet("🚀 📈 ✓")
+            .add_bullet("<tag> R&D \"quoted\" it's 50%"),
+        SlideContent::new("Formatting").title_color("1F4E79").content_bold(true).content_size(20)
+            .add_shape(Shape::new(ShapeType::Rectangle, 914400, 4572000, 4572000, 914400).with_rich_text(vec![
+                FormattedText::new("Bold ").bold(),
+                FormattedText::new("and colored").color("C00000"),
+            ])),
+    ])
+}
+
+fn layouts() -> Presentation {
+    deck(SlideLayout::ALL.map(|layout| SlideContent::new(layout.as_str()).layout(layout).add_bullet("Body")))
+}
+
+fn tables() -> Presentation {
+    let merged = Table::new(
+        vec![
+            TableRow::new(vec![TableCell::new("Merged across").with_col_span(2).with_h_merge(), TableCell::new("")]),
+            TableRow::new(vec![TableCell::new("Merged down").with_row_span(2), TableCell::new("B")]),
+            TableRow::new(vec![TableCell::new("").with_v_merge(), TableCell::new("C")]),
+        ],
+        vec![2_000_000, 2_000_000],
+        914400,
+        1_600_200,
+    );
+    let mut styled = Table::from_data(
+        vec![vec!["Region", "Sales"], vec!["EMEA", "12"], vec!["APAC", "9"]],
+        vec![2_000_000; 2],
+        914400,
+        1_600_200,
+    );
+    styled.rows[0].cells = styled.rows[0].cells.iter().map(|c| c.clone().bold().background_color("1F4E79").text_color("FFFFFF")).collect();
+    deck([SlideContent::new("Merged").table(merged), SlideContent::new("Styled").table(styled)])
+}
+
+fn charts() -> Presentation {
+    deck(ChartType::ALL.map(|chart_type| {
+        let series = match chart_type {
+            ChartType::StockHLC => 3,
+            ChartType::StockOHLC => 4,
+            _ => 2,
+        };
+        let chart = (0..series).fold(
+            ChartBuilder::new(chart_type.as_str(), chart_type).categories(vec!["Q1", "Q2", "Q3"]),
+            |builder, i| builder.add_series(ChartSeries::new(&format!("Series {i}"), vec![10.0 + i as f64, 12.5, 9.0])),
+        );
+        SlideContent::new(chart_type.as_str()).layout(SlideLayout::TitleOnly).add_chart(chart.build())
+    }))
+}
+
+fn images() -> Presentation {
+    let grid = (0..5).fold(ImageGrid::new(), |grid, i| {
+        grid.captioned(Image::from_source(&format!("grid{i}.png"), ImageSource::Bytes(png(40, 30))), &format!("Photo {i}"))
+    });
+    let layout = CustomLayout::new("Product", SlideLayout::TitleOnly)
+        .picture("hero", Region::LeftHalf)
+        .picture("logo", Region::RightHalf);
+    deck([
+        SlideContent::new("Picture").add_image(
+            Image::from_source("photo.png", ImageSource::Bytes(png(64, 48)))
+                .position(914400, 1_600_200)
+                .with_crop(0.1, 0.0, 0.1, 0.0),
+        ),
+        SlideContent::new("Grid").image_grid(grid),
+        layout.slide("Placeholders").set_picture("hero", ImageSource::Bytes(png(32, 32))).unwrap(),
+    ])
+}
+
+fn media(dir: &Path) -> Presentation {
+    let video = dir.join("clip.mp4");
+    let audio = dir.join("sound.mp3");
+    std::fs::write(&video, b"\0\0\0\x18ftypmp42").unwrap();
+    std::fs::write(&audio, b"ID3\x03\0\0\0\0\0\0").unwrap();
+    deck([
+        SlideContent::new("Video").add_video(Video::new(video.to_str().unwrap(), VideoFormat::Mp4, 914400, 1_600_200, 4_572_000, 2_571_750)),
+        SlideContent::new("Audio").add_audio(Audio::new(audio.to_str().unwrap(), AudioFormat::Mp3, 914400, 1_600_200, 457_200, 457_200)),
+        SlideContent::new("Narration").notes("Read along").narration(wav(), AudioFormat::Wav),
+    ])
+}
+
+fn notes() -> Presentation {
+    deck([
+        SlideContent::new("One").add_bullet("Point").notes("Plain notes"),
+        SlideContent::new("Two"),
+        SlideContent::new("Three").notes("Notes with <markup> & \"quotes\"\nand a second line"),
+    ])
+}
+
+fn transitions() -> Presentation {
+    let types = [
+        TransitionType::None,
+        TransitionType::Fade,
+        TransitionType::Cut,
+        TransitionType::Push,
+        TransitionType::Wipe,
+        TransitionType::Split,
+        TransitionType::Reveal,
+        TransitionType::Cover,
+        TransitionType::Zoom,
+        TransitionType::Morph(MorphOption::ByObject),
+        TransitionType::Morph(MorphOption::ByWord),
+    ];
+    deck(types.into_iter().enumerate().map(|(i, transition)| {
+        SlideContent::new(&format!("Transition {i}"))
+            .with_transition(transition)
+            .add_shape(Shape::new(ShapeType::Ellipse, 914400 * (i as u32 % 8), 2_000_000, 914400, 914400).with_name("Dot"))
+    }))
+}
+
+fn shapes() -> Presentation {
+    let linear = ShapeGradientFill::linear("1F4E79", "9DC3E6", ShapeGradientDirection::Vertical);
+    let three = ShapeGradientFill::three_color("C00000", "FFC000", "70AD47", ShapeGradientDirection::Angle(30));
+    deck([
+        SlideContent::new("Shapes")
+            .add_shape(Shape::new(ShapeType::RoundedRectangle, 457200, 1_600_200, 2_000_000, 1_000_000).with_fill(ShapeFill::new("70AD47")).with_text("Filled"))
+            .add_shape(Shape::new(ShapeType::Star5, 3_000_000, 1_600_200, 1_000_000, 1_000_000).with_gradient(linear))
+            .add_shape(Shape::new(ShapeType::Hexagon, 5_000_000, 1_600_200, 1_000_000, 1_000_000).with_gradient(three))
+            .add_connector(Connector::new(ConnectorType::Elbow, 457200, 3_000_000, 3_000_000, 4_000_000))
+            .add_connector(Connector::new(ConnectorType::Curved, 3_000_000, 3_000_000, 6_000_000, 4_500_000)),
+        SlideContent::new("Links")
+            .add_shape(Shape::new(ShapeType::Rectangle, 457200, 1_600_200, 2_000_000, 500_000).with_text("Web").with_hyperlink(Hyperlink::url("https://example.com/?a=1&b=2")))
+            .add_shape(Shape::new(ShapeType::Rectangle, 457200, 2_300_000, 2_000_000, 500_000).with_text("Next").with_hyperlink(Hyperlink::slide(1)))
+            .add_shape(Shape::new(ShapeType::Rectangle, 457200, 3_000_000, 2_000_000, 500_000).with_text("Mail").with_hyperlink(Hyperlink::email("team@example.com")))
+            .add_shape(
+                Shape::new(ShapeType::Rectangle, 457200, 3_700_000, 2_000_000, 500_000)
+                    .with_text("Chime")
+                    .with_hover_action(Hyperlink::play_sound(ActionSound::new("chime.wav", wav()))),
+            )
+            .add_shape(
+                Shape::new(ShapeType::ActionButtonForwardNext, 457200, 4_400_000, 914400, 500_000)
+                    .with_hyperlink(Hyperlink::slide(1)),
+            ),
+    ])
+}
+
+fn zoom() -> Presentation {
+    deck([
+        SlideContent::new("Agenda").summary_zoom(&[2, 3]).add_slide_zoom(SlideZoom::new(4, 0, 0, 1_219_200, 914_400)),
+        SlideContent::new("Part one").add_bullet("A"),
+        SlideContent::new("Part two").layout(SlideLayout::SectionHeader),
+        SlideContent::new("Part three").add_bullet("C"),
+    ])
+}
+
+fn deck_settings() -> Presentation {
+    deck([
+        SlideContent::new("Title").layout(SlideLayout::CenteredTitle),
+        SlideContent::new("Section").layout(SlideLayout::SectionHeader),
+        SlideContent::new("Body").add_bullet("Numbered and themed"),
+    ])
+    .theme(&themes::DARK)
+    .section_bands(&themes::DARK)
+    .slide_numbers(SlideNumbering::new(SlideNumberFormat::OfTotal).skip_title(true))
+    .print_settings(PrintSettings::new())
+}
+
+/// Every corpus deck, by name
+fn corpus(dir: &Path) -> Vec<(String, Presentation)> {
+    let mut cases = vec![
+        ("text".to_string(), text()),
+        ("layouts".to_string(), layouts()),
+        ("tables".to_string(), tables()),
+        ("charts".to_string(), charts()),
+        ("images".to_string(), images()),
+        ("media".to_string(), media(dir)),
+        ("notes".to_string(), notes()),
+        ("transitions".to_string(), transitions()),
+        ("shapes".to_string(), shapes()),
+        ("zoom".to_string(), zoom()),
+        ("deck_settings".to_string(), deck_settings()),
+    ];
+    cases.extend((0..8).map(|seed| (format!("random_{seed}"), DeckGenerator::new(seed).presentation(6))));
+    cases
+}
+
+fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("ppt_rs_compat_{name}_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_corpus_has_no_repair_triggers() {
+    let dir = scratch_dir("check");
+    let out_dir = std::env::var_os("PPTX_CORPUS_DIR");
+    let mut failures = Vec::new();
+    for (name, pres) in corpus(&dir) {
+        let data = match pres.build() {
+            Ok(data) => data,
+            Err(e) => {
+                failures.push(format!("{name}: build failed: {e}"));
+                continue;
+            }
+        };
+        if let Some(out_dir) = &out_dir {
+            std::fs::create_dir_all(out_dir).unwrap();
+            std::fs::write(Path::new(out_dir).join(format!("{name}.pptx")), &data).unwrap();
+        }
+        if let Err(e) = validate_package(&data) {
+            failures.push(format!("{name}: {e}"));
+        }
+        let issues = PptxRepair::from_bytes(&data).unwrap().validate();
+        if !issues.is_empty() {
+            failures.push(format!("{name}: repair issues {issues:?}"));
+        }
+        let path = dir.join(format!("{name}.pptx"));
+        std::fs::write(&path, &data).unwrap();
+        if let Err(e) = PresentationReader::open(path.to_str().unwrap()) {
+            failures.push(format!("{name}: reader failed: {e}"));
+        }
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_profiles_keep_corpus_valid() {
+    let dir = scratch_dir("profiles");
+    for profile in [CompatProfile::GoogleSlides, CompatProfile::Keynote] {
+        for (name, pres) in corpus(&dir) {
+            let data = pres.compat(profile).build().unwrap();
+            validate_package(&data).unwrap_or_else(|e| panic!("{name} for {}: {e}", profile.as_str()));
+            let package = ppt_rs::api::open_reader(Cursor::new(data)).unwrap();
+            for part in package.part_paths().into_iter().filter(|p| p.starts_with("ppt/slides/slide") || p.starts_with("ppt/charts/")) {
+                let xml = package.get_part_string(part).unwrap();
+                for unsupported in ["p159:morph", "actionButton", "c:stockChart"] {
+                    assert!(!xml.contains(unsupported), "{name} for {}: {part} has {unsupported}", profile.as_str());
+                }
+            }
+        }
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+#[ignore] // Requires LibreOffice
+fn test_corpus_opens_in_libreoffice() {
+    let dir = scratch_dir("soffice");
+    let mut failures = Vec::new();
+    for (name, pres) in corpus(&dir) {
+        let path = dir.join(format!("{name}.pptx"));
+        pres.save(&path).unwrap();
+        let output = Command::new("soffice")
+            .args(["--headless", "--convert-to", "pdf", "--outdir"])
+            .arg(&dir)
+            .arg(&path)
+            .output()
+            .expect("running soffice");
+        let pdf = dir.join(format!("{name}.pdf"));
+        let size = std::fs::metadata(&pdf).map(|m| m.len()).unwrap_or(0);
+        if !output.status.success() || size == 0 {
+            failures.push(format!("{name}: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
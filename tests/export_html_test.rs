@@ -1,5 +1,5 @@
 use ppt_rs::api::Presentation;
-use ppt_rs::generator::SlideContent;
+use ppt_rs::generator::{SlideContent, SlideSize};
 use ppt_rs::export::html::export_to_html;
 
 #[test]
@@ -18,3 +18,14 @@ fn test_html_export() {
     assert!(html.contains("<h2>Second Slide</h2>"));
     assert!(html.contains(".slide {")); // CSS check
 }
+
+#[test]
+fn test_html_export_uses_slide_size() {
+    let pres = Presentation::with_title("Wide")
+        .slide_size(SlideSize::WIDESCREEN)
+        .add_slide(SlideContent::new("First Slide"));
+
+    let html = export_to_html(&pres).expect("Failed to export HTML");
+
+    assert!(html.contains("id=\"slide-1\" style=\"aspect-ratio: 12192000 / 6858000\""));
+}
@@ -1,6 +1,7 @@
 #[cfg(feature = "web2ppt")]
 #[test]
 fn test_image_from_url() {
+    use ppt_rs::core::Emu;
     use ppt_rs::generator::Image;
     use ppt_rs::elements::Position;
 
@@ -9,8 +10,8 @@ fn test_image_from_url() {
     let img = Image::from_url(url, 1500000, 1500000, "PNG");
     
     // Check metadata
-    assert_eq!(img.width, 1500000);
-    assert_eq!(img.height, 1500000);
+    assert_eq!(img.width, Emu(1500000));
+    assert_eq!(img.height, Emu(1500000));
     assert_eq!(img.format, "PNG");
     
     // Check fetching (this requires network)
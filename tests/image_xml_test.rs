@@ -2,6 +2,7 @@
 //!
 //! Tests the image XML generation module
 
+use ppt_rs::core::Emu;
 use ppt_rs::generator::{Image, ImageBuilder, generate_image_xml, generate_image_relationship, generate_image_content_type};
 
 // ============================================================================
@@ -12,8 +13,8 @@ use ppt_rs::generator::{Image, ImageBuilder, generate_image_xml, generate_image_
 fn test_image_creation() {
     let img = Image::new("photo.png", 1920, 1080, "PNG");
     assert_eq!(img.filename, "photo.png");
-    assert_eq!(img.width, 1920);
-    assert_eq!(img.height, 1080);
+    assert_eq!(img.width, Emu(1920));
+    assert_eq!(img.height, Emu(1080));
     assert_eq!(img.format, "PNG");
 }
 
@@ -21,8 +22,8 @@ fn test_image_creation() {
 fn test_image_position() {
     let img = Image::new("photo.png", 1920, 1080, "PNG")
         .position(500000, 1000000);
-    assert_eq!(img.x, 500000);
-    assert_eq!(img.y, 1000000);
+    assert_eq!(img.x, Emu(500000));
+    assert_eq!(img.y, Emu(1000000));
 }
 
 #[test]
@@ -50,16 +51,16 @@ fn test_image_aspect_ratio_1_1() {
 fn test_image_scale_to_width() {
     let img = Image::new("photo.png", 1920, 1080, "PNG")
         .scale_to_width(960);
-    assert_eq!(img.width, 960);
-    assert_eq!(img.height, 540);
+    assert_eq!(img.width, Emu(960));
+    assert_eq!(img.height, Emu(540));
 }
 
 #[test]
 fn test_image_scale_to_height() {
     let img = Image::new("photo.png", 1920, 1080, "PNG")
         .scale_to_height(540);
-    assert_eq!(img.width, 960);
-    assert_eq!(img.height, 540);
+    assert_eq!(img.width, Emu(960));
+    assert_eq!(img.height, Emu(540));
 }
 
 #[test]
@@ -106,8 +107,8 @@ fn test_image_mime_type_bmp() {
 fn test_image_builder_basic() {
     let img = ImageBuilder::new("photo.png", 1920, 1080).build();
     assert_eq!(img.filename, "photo.png");
-    assert_eq!(img.width, 1920);
-    assert_eq!(img.height, 1080);
+    assert_eq!(img.width, Emu(1920));
+    assert_eq!(img.height, Emu(1080));
 }
 
 #[test]
@@ -115,8 +116,8 @@ fn test_image_builder_with_position() {
     let img = ImageBuilder::new("photo.png", 1920, 1080)
         .position(500000, 1000000)
         .build();
-    assert_eq!(img.x, 500000);
-    assert_eq!(img.y, 1000000);
+    assert_eq!(img.x, Emu(500000));
+    assert_eq!(img.y, Emu(1000000));
 }
 
 #[test]
@@ -132,8 +133,8 @@ fn test_image_builder_scale_to_width() {
     let img = ImageBuilder::new("photo.png", 1920, 1080)
         .scale_to_width(960)
         .build();
-    assert_eq!(img.width, 960);
-    assert_eq!(img.height, 540);
+    assert_eq!(img.width, Emu(960));
+    assert_eq!(img.height, Emu(540));
 }
 
 #[test]
@@ -141,8 +142,8 @@ fn test_image_builder_scale_to_height() {
     let img = ImageBuilder::new("photo.png", 1920, 1080)
         .scale_to_height(540)
         .build();
-    assert_eq!(img.width, 960);
-    assert_eq!(img.height, 540);
+    assert_eq!(img.width, Emu(960));
+    assert_eq!(img.height, Emu(540));
 }
 
 #[test]
@@ -153,10 +154,10 @@ fn test_image_builder_fluent() {
         .build();
     
     assert_eq!(img.filename, "photo.png");
-    assert_eq!(img.width, 960);
-    assert_eq!(img.height, 540);
-    assert_eq!(img.x, 500000);
-    assert_eq!(img.y, 1000000);
+    assert_eq!(img.width, Emu(960));
+    assert_eq!(img.height, Emu(540));
+    assert_eq!(img.x, Emu(500000));
+    assert_eq!(img.y, Emu(1000000));
 }
 
 #[test]
@@ -334,8 +335,8 @@ fn test_image_various_sizes() {
     
     for (width, height) in sizes {
         let img = Image::new("photo.png", width, height, "PNG");
-        assert_eq!(img.width, width);
-        assert_eq!(img.height, height);
+        assert_eq!(img.width, Emu(width));
+        assert_eq!(img.height, Emu(height));
     }
 }
 
@@ -9,8 +9,14 @@
 //! - File integrity
 
 use ppt_rs::generator::{
-    SlideContent, Table, TableRow, TableCell, SlideLayout, create_pptx_with_content,
+    SlideContent, Table, TableRow, TableCell, SlideLayout, AudioFormat, SlideZoom,
+    Shape, ShapeType, Hyperlink, ActionSound, PrintSettings, ChartBuilder, ChartType, ChartSeries,
+    create_pptx_with_content, create_pptx_with_print_settings, SlideSize, Anchor,
+    Image, ImagePlaceholder,
 };
+use ppt_rs::core::Inches;
+use ppt_rs::Position;
+use ppt_rs::prelude::themes;
 use std::fs;
 use std::io::{Cursor, Read};
 use zip::ZipArchive;
@@ -464,6 +470,7 @@ fn test_all_layouts_generate_valid_pptx() {
         SlideLayout::TitleAndBigContent,
         SlideLayout::TwoColumn,
         SlideLayout::Blank,
+        SlideLayout::SectionHeader,
     ];
 
     for layout in layouts {
@@ -487,6 +494,32 @@ fn test_all_layouts_generate_valid_pptx() {
     }
 }
 
+#[test]
+fn test_section_band_renders_behind_title() {
+    let band = themes::CORPORATE.section_gradient();
+    let slides = vec![
+        SlideContent::new("Part 2").layout(SlideLayout::SectionHeader).section_band(band.clone()),
+        SlideContent::new("Body").add_bullet("Point").section_band(band),
+    ];
+
+    let pptx_data = create_pptx_with_content("Test", slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let mut divider = String::new();
+    archive.by_name("ppt/slides/slide1.xml").unwrap().read_to_string(&mut divider).unwrap();
+    let band_pos = divider.find(r#"name="Section Band""#).expect("band should be rendered");
+    let title_pos = divider.find(r#"name="Title""#).unwrap();
+    assert!(band_pos < title_pos, "Band should be drawn behind the title");
+    assert!(divider.contains(r#"<a:srgbClr val="1565C0""#));
+    assert!(divider.contains(r#"<a:srgbClr val="0D47A1""#));
+    assert!(divider.contains(r#"<a:pPr algn="l"/>"#));
+
+    // Bands only apply to section layouts
+    let mut body = String::new();
+    archive.by_name("ppt/slides/slide2.xml").unwrap().read_to_string(&mut body).unwrap();
+    assert!(!body.contains("Section Band"));
+}
+
 // ============================================================================
 // MULTI-SLIDE TESTS
 // ============================================================================
@@ -635,3 +668,289 @@ fn test_large_presentation() {
     let zip_result = ZipArchive::new(cursor);
     assert!(zip_result.is_ok(), "Should produce valid ZIP for large presentation");
 }
+
+// ============================================================================
+// MEDIA TESTS
+// ============================================================================
+
+fn read_part(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> String {
+    let mut content = String::new();
+    archive.by_name(name).unwrap().read_to_string(&mut content).unwrap();
+    content
+}
+
+#[test]
+fn test_narration_embeds_audio_and_auto_advances() {
+    // 1 second of 8 kHz, 16-bit mono PCM
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36u32 + 16000).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&[1, 0, 1, 0]);
+    wav.extend_from_slice(&8000u32.to_le_bytes());
+    wav.extend_from_slice(&16000u32.to_le_bytes());
+    wav.extend_from_slice(&[2, 0, 16, 0]);
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&16000u32.to_le_bytes());
+    wav.resize(44 + 16000, 0);
+
+    let slides = vec![
+        SlideContent::new("Plain"),
+        SlideContent::new("Narrated")
+            .notes("Read along")
+            .narration(wav.clone(), AudioFormat::Wav),
+    ];
+
+    let pptx_data = create_pptx_with_content("Test", slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let mut audio = Vec::new();
+    archive.by_name("ppt/media/narration2.wav").unwrap().read_to_end(&mut audio).unwrap();
+    assert_eq!(audio, wav);
+    assert!(archive.by_name("ppt/media/narrationIcon.png").is_ok());
+
+    let content_types = read_part(&mut archive, "[Content_Types].xml");
+    assert!(content_types.contains(r#"<Default Extension="wav" ContentType="audio/wav"/>"#));
+    assert!(content_types.contains(r#"<Default Extension="png""#));
+
+    // rId1 layout, rId2 notes, then audio/media/icon
+    let rels = read_part(&mut archive, "ppt/slides/_rels/slide2.xml.rels");
+    assert!(rels.contains(r#"Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio" Target="../media/narration2.wav""#));
+    assert!(rels.contains(r#"Id="rId4" Type="http://schemas.microsoft.com/office/2007/relationships/media""#));
+    assert!(rels.contains(r#"Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/narrationIcon.png""#));
+
+    let slide = read_part(&mut archive, "ppt/slides/slide2.xml");
+    assert!(slide.contains(r#"<a:audioFile r:link="rId3"/>"#));
+    assert!(slide.contains(r#"<p:transition advTm="1000">"#));
+    assert!(slide.contains(r#"showWhenStopped="0""#));
+    let transition = slide.find("<p:transition").unwrap();
+    let timing = slide.find("<p:timing>").unwrap();
+    assert!(slide.find("</p:cSld>").unwrap() < transition && transition < timing);
+
+    let plain = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(!plain.contains("<p:timing>"));
+}
+
+#[test]
+fn test_slide_zoom_links_to_target_with_thumbnail() {
+    let slides = vec![
+        SlideContent::new("Agenda").summary_zoom(&[2, 3]),
+        SlideContent::new("Part 1").add_bullet("Detail"),
+        SlideContent::new("Part 2").add_bullet("More"),
+    ];
+
+    let pptx_data = create_pptx_with_content("Test", slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+    assert!(rels.contains(r#"Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slide3.xml""#));
+    assert!(rels.contains(r#"Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/zoom1_2.png""#));
+
+    let mut thumbnail = Vec::new();
+    archive.by_name("ppt/media/zoom1_2.png").unwrap().read_to_end(&mut thumbnail).unwrap();
+    assert_eq!(&thumbnail[1..4], b"PNG");
+
+    let content_types = read_part(&mut archive, "[Content_Types].xml");
+    assert!(content_types.contains(r#"<Default Extension="png""#));
+
+    // The zoom names its target by slide ID and creation ID, as PowerPoint does
+    let target = read_part(&mut archive, "ppt/slides/slide3.xml");
+    let creation_id = target.split(r#"<p14:creationId "#).nth(1).unwrap()
+        .split(r#"val=""#).nth(1).unwrap()
+        .split('"').next().unwrap()
+        .to_string();
+    assert!(target.ends_with("</p:extLst></p:sld>"));
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(&format!(r#"<pslz:sldZmObj sldId="259" cId="{creation_id}">"#)));
+    assert!(slide.contains(r#"<a:hlinkClick r:id="rId4" action="ppaction://hlinksldjump"/>"#));
+    assert!(slide.contains(r#"returnToParent="1""#));
+}
+
+#[test]
+fn test_readable_images_are_embedded_and_missing_ones_get_placeholders() {
+    let png = b"\x89PNG\r\n\x1a\nfake".to_vec();
+    let slides = vec![
+        SlideContent::new("Pictures")
+            .add_image(Image::from_bytes(png.clone(), 914400, 914400, "png"))
+            .add_image(Image::new("missing/logo.jpg", 914400, 914400, "jpg")
+                .with_placeholder(ImagePlaceholder::new().icon("⚠").fill("FFF3E0"))),
+    ];
+
+    let pptx_data = create_pptx_with_content("Test", slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+    assert!(rels.contains(r#"Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image1_1.png""#));
+    assert!(!rels.contains("image1_2"));
+
+    let mut embedded = Vec::new();
+    archive.by_name("ppt/media/image1_1.png").unwrap().read_to_end(&mut embedded).unwrap();
+    assert_eq!(embedded, png);
+    let content_types = read_part(&mut archive, "[Content_Types].xml");
+    assert!(content_types.contains(r#"<Default Extension="png" ContentType="image/png"/>"#));
+    assert!(!content_types.contains(r#"Extension="jpg""#));
+
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(r#"<a:blip r:embed="rId2"/>"#));
+    assert!(slide.contains(r#"name="Image Placeholder: missing/logo.jpg""#));
+    assert!(slide.contains("<a:t>⚠ missing/logo.jpg</a:t>"));
+    assert!(slide.contains(r#"<a:srgbClr val="FFF3E0"/>"#));
+}
+
+#[test]
+fn test_slide_zoom_rejects_missing_target() {
+    let slides = vec![
+        SlideContent::new("Agenda").add_slide_zoom(SlideZoom::new(5, 0, 0, 1219200, 914400)),
+        SlideContent::new("Only"),
+    ];
+    let err = create_pptx_with_content("Test", slides).unwrap_err();
+    assert!(err.to_string().contains("zoom to slide 5"));
+}
+
+#[test]
+fn test_shape_actions_write_relationships_and_sounds() {
+    let wav = b"RIFF\0\0\0\0WAVE".to_vec();
+    let slides = vec![
+        SlideContent::new("Menu")
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 457200)
+                .with_hyperlink(Hyperlink::run_program(r"C:\Tools\demo.exe")))
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 914400, 914400, 457200)
+                .with_hyperlink(Hyperlink::end_show())
+                .with_hover_action(Hyperlink::play_sound(ActionSound::new("chime.wav", wav.clone())))),
+    ];
+
+    let pptx_data = create_pptx_with_content("Test", slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+    assert!(rels.contains(r#"Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="file:///C:/Tools/demo.exe" TargetMode="External""#));
+    assert!(rels.contains(r#"Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/audio" Target="../media/sound1_1.wav""#));
+
+    let mut sound = Vec::new();
+    archive.by_name("ppt/media/sound1_1.wav").unwrap().read_to_end(&mut sound).unwrap();
+    assert_eq!(sound, wav);
+    assert!(read_part(&mut archive, "[Content_Types].xml").contains(r#"<Default Extension="wav""#));
+
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(r#"<a:hlinkClick r:id="rId2" highlightClick="1" action="ppaction://program"/>"#));
+    assert!(slide.contains(r#"action="ppaction://hlinkshowjump?jump=endshow"/><a:hlinkHover"#));
+    assert!(slide.contains(r#"<a:snd r:embed="rId3" name="chime.wav"/></a:hlinkHover>"#));
+}
+
+#[test]
+fn test_slide_jump_action_rejects_missing_slide() {
+    let slides = vec![SlideContent::new("Menu")
+        .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 914400, 457200).with_hyperlink(Hyperlink::slide(9)))];
+    let err = create_pptx_with_content("Test", slides).unwrap_err();
+    assert!(err.to_string().contains("an action to slide 9"));
+}
+
+#[test]
+fn test_print_settings_write_pres_props() {
+    let slides = vec![SlideContent::new("Agenda").notes("Welcome")];
+    let print = PrintSettings::handouts_6_up().grayscale().frame_slides(true);
+
+    let pptx_data = create_pptx_with_print_settings("Test", slides, &print).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let props = read_part(&mut archive, "ppt/presProps.xml");
+    assert!(props.contains(r#"<p:prnPr prnWhat="handouts6" clrMode="gray" hiddenSlides="0" scaleToFitPaper="0" frameSlides="1"/>"#));
+    let rels = read_part(&mut archive, "ppt/_rels/presentation.xml.rels");
+    assert!(rels.contains(r#"Id="rId4" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesMaster""#));
+    assert!(rels.contains(r#"Id="rId5" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/presProps" Target="presProps.xml""#));
+    assert!(read_part(&mut archive, "[Content_Types].xml").contains(r#"<Override PartName="/ppt/presProps.xml""#));
+}
+
+#[test]
+fn test_quotes_and_newlines_are_escaped_in_every_part() {
+    let tricky = "Say \"hi\" & <wave>\n'bye'";
+    let table = Table::new(
+        vec![TableRow::new(vec![TableCell::new(tricky).font_family(tricky)])],
+        vec![2_000_000],
+        0,
+        0,
+    );
+    let chart = ChartBuilder::new(tricky, ChartType::Bar)
+        .categories(vec![tricky])
+        .add_series(ChartSeries::new(tricky, vec![1.0]))
+        .build();
+    let slides = vec![
+        SlideContent::new(tricky)
+            .add_bullet(tricky)
+            .notes(tricky)
+            .table(table)
+            .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 1_000_000, 500_000).with_name(tricky).with_text(tricky))
+            .add_chart(chart),
+    ];
+
+    let pptx_data = create_pptx_with_content(tricky, slides).unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+    let names: Vec<String> = archive.file_names().map(String::from).collect();
+    for name in names.iter().filter(|n| n.ends_with(".xml") || n.ends_with(".rels")) {
+        let xml = read_part(&mut archive, name);
+        for event in xml::reader::EventReader::from_str(&xml) {
+            if let Err(e) = event {
+                panic!("{name} is not well-formed: {e}");
+            }
+        }
+    }
+
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(r#"name="Say &quot;hi&quot; &amp; &lt;wave&gt;&#10;&apos;bye&apos;""#));
+    assert!(slide.contains(r#"typeface="Say &quot;hi&quot; &amp; &lt;wave&gt;&#10;&apos;bye&apos;""#));
+    let chart = read_part(&mut archive, "ppt/charts/chart1.xml");
+    assert!(chart.contains("<a:t>Say &quot;hi&quot; &amp; &lt;wave&gt;\n&apos;bye&apos;</a:t>"));
+}
+
+#[test]
+fn test_placements_follow_widescreen_slide_size() {
+    let slide = SlideContent::new("Placed")
+        .add_shape(Shape::new(ShapeType::Rectangle, 0, 0, 1_000_000, 500_000).at(Anchor::TopRight.offset(Inches(0.2), Inches(0.2))))
+        .add_shape(Shape::new(ShapeType::Ellipse, 0, 0, 100, 100).at(Position::percent(0.5, 0.5)));
+
+    let pptx_data = ppt_rs::Presentation::new().slide_size(SlideSize::WIDESCREEN).add_slide(slide).build().unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    assert!(read_part(&mut archive, "ppt/presentation.xml").contains(r#"<p:sldSz cx="12192000" cy="6858000"/>"#));
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(r#"<a:off x="11009120" y="182880"/>"#));
+    assert!(slide.contains(r#"<a:off x="6096000" y="3429000"/>"#));
+}
+
+#[test]
+fn test_layouts_and_regions_follow_widescreen_slide_size() {
+    let chart = ChartBuilder::new("Sales", ChartType::Bar)
+        .categories(vec!["Q1", "Q2"])
+        .add_series(ChartSeries::new("2024", vec![1.0, 2.0]))
+        .build();
+    let pptx_data = ppt_rs::Presentation::new()
+        .slide_size(SlideSize::WIDESCREEN)
+        .add_slide(SlideContent::new("Wide").add_bullet("Point"))
+        .add_slide(SlideContent::new("Chart").layout(SlideLayout::TitleOnly).add_chart(chart))
+        .build()
+        .unwrap();
+    let mut archive = ZipArchive::new(Cursor::new(pptx_data)).unwrap();
+
+    let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+    assert!(slide.contains(r#"<a:ext cx="12192000" cy="6858000"/>"#));
+    // Title frame spans the widescreen slide less its scaled margins
+    assert!(slide.contains("<a:off x=\"609600\" y=\"274638\"/>\n<a:ext cx=\"10973600\" cy=\"1143000\"/>"));
+    let slide = read_part(&mut archive, "ppt/slides/slide2.xml");
+    assert!(slide.contains("<p:xfrm>\n<a:off x=\"609600\" y=\"1600200\"/>\n<a:ext cx=\"10973600\""));
+}
+
+#[test]
+fn test_invalid_content_fails_build_unless_validation_is_skipped() {
+    let chart = ChartBuilder::new("Sales", ChartType::Bar)
+        .categories(vec!["Q1", "Q2"])
+        .add_series(ChartSeries::new("2024", vec![1.0, 2.0, 3.0]))
+        .build();
+    let slides = vec![SlideContent::new("Intro"), SlideContent::new("Sales").add_chart(chart)];
+
+    let err = create_pptx_with_content("Test", slides.clone()).unwrap_err();
+    assert_eq!(err.to_string(), "slide 2, chart 1: series \"2024\" has 3 values but there are 2 categories");
+
+    let progress = ppt_rs::generator::BuildProgress::new().skip_validation();
+    assert!(ppt_rs::generator::create_pptx_with_progress("Test", slides, progress).is_ok());
+}
@@ -3,6 +3,7 @@
 //! Tests for complex PPTX components including data tables, charts, and images
 //! Generates example files demonstrating each advanced element type
 
+use ppt_rs::core::Emu;
 use ppt_rs::generator::{
     SlideContent, create_pptx_with_content,
     Table, TableRow, TableCell, TableBuilder,
@@ -116,8 +117,8 @@ fn test_table_with_position() {
         .add_simple_row(vec!["A", "B"])
         .build();
     
-    assert_eq!(table.x, 500000);
-    assert_eq!(table.y, 1000000);
+    assert_eq!(table.x, Emu(500000));
+    assert_eq!(table.y, Emu(1000000));
 }
 
 #[test]
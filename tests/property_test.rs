@@ -0,0 +1,105 @@
+//! Property tests for the string and number utilities under every part
+//!
+//! - `escape_xml` / `escape_xml_attr` output parses back to the input
+//! - EMU conversions round to the nearest EMU and saturate instead of overflowing
+
+use ppt_rs::core::units::{EMU_PER_CM, EMU_PER_INCH, EMU_PER_PT};
+use ppt_rs::core::{cm_to_emu, escape_xml, escape_xml_attr, inches_to_emu, pt_to_emu, Cm, Emu, Inches, Pt};
+use proptest::prelude::*;
+use xml::reader::{EventReader, XmlEvent};
+
+/// Strings of characters XML 1.0 can carry
+fn xml_text() -> impl Strategy<Value = String> {
+    any::<String>().prop_map(|s| {
+        s.chars()
+            .filter(|&c| matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{D7FF}' | '\u{E000}'..='\u{FFFD}' | '\u{10000}'..))
+            .collect()
+    })
+}
+
+/// Text content and attribute `a` of the document's root element
+fn parse(xml: &str) -> (String, String) {
+    let mut text = String::new();
+    let mut attr = String::new();
+    for event in EventReader::new(xml.as_bytes()) {
+        match event.unwrap_or_else(|e| panic!("{xml:?}: {e}")) {
+            XmlEvent::StartElement { attributes, .. } => {
+                attr = attributes.into_iter().find(|a| a.name.local_name == "a").map(|a| a.value).unwrap_or_default();
+            }
+            XmlEvent::Characters(s) | XmlEvent::Whitespace(s) | XmlEvent::CData(s) => text.push_str(&s),
+            _ => {}
+        }
+    }
+    (text, attr)
+}
+
+proptest! {
+    #[test]
+    fn escape_xml_round_trips_as_text(s in xml_text()) {
+        // Parsers normalize line endings in text, so compare without CR
+        let s = s.replace('\r', "");
+        let (text, _) = parse(&format!("<t>{}</t>", escape_xml(&s)));
+        prop_assert_eq!(text, s);
+    }
+
+    #[test]
+    fn escape_xml_attr_round_trips_with_whitespace(s in xml_text()) {
+        let (_, attr) = parse(&format!("<t a=\"{}\"/>", escape_xml_attr(&s)));
+        prop_assert_eq!(attr, s);
+    }
+
+    #[test]
+    fn escaped_text_has_no_markup(s in ".*") {
+        let escaped = escape_xml(&s);
+        prop_assert!(!escaped.contains(['<', '>', '"', '\'']));
+        prop_assert_eq!(escaped.matches('&').count(), escaped.matches(';').count() - s.matches(';').count());
+    }
+
+    #[test]
+    fn unit_conversions_round_to_nearest_emu(value in -1.0e6f64..1.0e6) {
+        for (emu, per_unit) in [
+            (Emu::from(Inches(value)), EMU_PER_INCH),
+            (Emu::from(Cm(value)), EMU_PER_CM),
+            (Emu::from(Pt(value)), EMU_PER_PT),
+        ] {
+            let exact = value * per_unit as f64;
+            prop_assert!((emu.get() as f64 - exact).abs() <= 0.5 + exact.abs() * f64::EPSILON, "{value} -> {emu}");
+        }
+        let inches = Emu::from(Inches(value)).to_inches();
+        prop_assert!((inches - value).abs() <= 0.5 / EMU_PER_INCH as f64 + value.abs() * f64::EPSILON);
+    }
+
+    #[test]
+    fn unit_conversions_are_monotonic(a in -1.0e9f64..1.0e9, b in -1.0e9f64..1.0e9) {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        prop_assert!(Emu::from(Inches(low)) <= Emu::from(Inches(high)));
+        prop_assert!(Emu::from(Pt(low)) <= Emu::from(Pt(high)));
+    }
+
+    #[test]
+    fn unit_conversions_saturate(value in any::<f64>()) {
+        let emu = Emu::from(Inches(value));
+        if value.is_nan() {
+            prop_assert_eq!(emu, Emu::ZERO);
+        } else if value * EMU_PER_INCH as f64 >= i64::MAX as f64 {
+            prop_assert_eq!(emu, Emu(i64::MAX));
+        } else if value * EMU_PER_INCH as f64 <= i64::MIN as f64 {
+            prop_assert_eq!(emu, Emu(i64::MIN));
+        }
+    }
+
+    #[test]
+    fn u32_helpers_match_emu(value in 0.0f64..4000.0) {
+        prop_assert_eq!(Some(inches_to_emu(value)), Emu::from(Inches(value)).to_u32());
+        prop_assert_eq!(Some(cm_to_emu(value)), Emu::from(Cm(value)).to_u32());
+        prop_assert_eq!(Some(pt_to_emu(value)), Emu::from(Pt(value)).to_u32());
+    }
+
+    #[test]
+    fn checked_arithmetic_matches_wide_integers(a in any::<i64>(), b in any::<i64>()) {
+        let wide = a as i128 + b as i128;
+        let expected = i64::try_from(wide).ok().map(Emu);
+        prop_assert_eq!(Emu(a).checked_add(Emu(b)), expected);
+        prop_assert_eq!(Emu(a).saturating_add(Emu(b)), Emu(wide.clamp(i64::MIN as i128, i64::MAX as i128) as i64));
+    }
+}